@@ -17,9 +17,29 @@ use crate::{
     join_leave_room_modal::{
         JoinLeaveModalKind, JoinLeaveRoomModalAction, JoinLeaveRoomModalWidgetRefExt,
     },
-    kanban::{KanbanActions, KanbanAppState},
+    kanban::{KanbanActions, KanbanAppState, KanbanCard},
+    kanban::outbox::{self, KanbanMutation},
+    kanban::saved_filters::{self, SavedFilter},
+    kanban::reminders,
+    kanban::analytics::{self, BoardReport},
+    kanban::state::kanban_state::ActivityType,
+    kanban::state::template::{CardTemplate, RecurrenceTrigger},
+    kanban::state::swimlane::{NO_VALUE_LANE, SwimlaneGrouping},
+    kanban::state::calendar,
+    kanban::state::workflow::{STATUS_COMPLETED, STATUS_PENDING, StatusCategory},
+    kanban::state::dependency::CardLink,
+    kanban::state::bulk::{BulkFailure, BulkOperation, BulkProgress},
+    kanban::state::permissions::KanbanPermission,
+    kanban::state::history::{HistoryPanel, UndoState, restore_card, undo_replay_actions},
+    kanban::state::time_tracking::{self, WorkSession},
     kanban::components::edit_list_name_modal::EditListNameModalWidgetRefExt,
+    kanban::components::workflow_editor_modal::WorkflowEditorModalWidgetRefExt,
+    kanban::components::board_settings_modal::BoardSettingsModalWidgetRefExt,
+    kanban::components::due_soon_modal::DueSoonModalWidgetRefExt,
+    kanban::components::card_templates_modal::CardTemplatesModalWidgetRefExt,
     kanban::components::card_modal::CardDetailModalWidgetRefExt,
+    kanban::components::create_card_modal::CreateCardModalWidgetRefExt,
+    kanban::chat_commands::CardCommand,
     login::login_screen::LoginAction,
     logout::logout_confirm_modal::{
         LogoutAction, LogoutConfirmModalAction, LogoutConfirmModalWidgetRefExt,
@@ -39,7 +59,7 @@ use crate::{
 };
 use makepad_widgets::*;
 use matrix_sdk::{
-    ruma::{OwnedRoomId, RoomId},
+    ruma::{OwnedRoomId, OwnedUserId, RoomId},
     RoomState,
 };
 use serde::{Deserialize, Serialize};
@@ -65,6 +85,14 @@ live_design! {
     use crate::kanban::components::card_modal::CardDetailModal;
     use crate::kanban::components::tag_management_modal::TagManagementModal;
     use crate::kanban::components::edit_list_name_modal::EditListNameModal;
+    use crate::kanban::components::workflow_editor_modal::WorkflowEditorModal;
+    use crate::kanban::components::board_settings_modal::BoardSettingsModal;
+    use crate::kanban::components::archive_modal::ArchiveModal;
+    use crate::kanban::components::card_templates_modal::CardTemplatesModal;
+    use crate::kanban::components::due_soon_modal::DueSoonModal;
+    use crate::kanban::components::analytics_modal::AnalyticsModal;
+    use crate::kanban::components::create_card_modal::CreateCardModal;
+    use crate::kanban::components::history_modal::HistoryModal;
 
 
     App = {{App}} {
@@ -170,6 +198,62 @@ live_design! {
                             }
                         }
 
+                        // Show the workflow editor modal for kanban lists
+                        workflow_editor_modal = <Modal> {
+                            content: {
+                                workflow_editor_modal_inner = <WorkflowEditorModal> {}
+                            }
+                        }
+
+                        // Show the archived kanban cards and lists
+                        archive_modal = <Modal> {
+                            content: {
+                                archive_modal_inner = <ArchiveModal> {}
+                            }
+                        }
+
+                        // Show the board settings (permission roles) for kanban lists
+                        board_settings_modal = <Modal> {
+                            content: {
+                                board_settings_modal_inner = <BoardSettingsModal> {}
+                            }
+                        }
+
+                        // Show the due-soon cards and reminder settings for kanban cards
+                        due_soon_modal = <Modal> {
+                            content: {
+                                due_soon_modal_inner = <DueSoonModal> {}
+                            }
+                        }
+
+                        // Show the kanban analytics (cumulative flow, lead/cycle time, throughput)
+                        analytics_modal = <Modal> {
+                            content: {
+                                analytics_modal_inner = <AnalyticsModal> {}
+                            }
+                        }
+
+                        // Show the card templates of a kanban list
+                        card_templates_modal = <Modal> {
+                            content: {
+                                card_templates_modal_inner = <CardTemplatesModal> {}
+                            }
+                        }
+
+                        // Create a kanban card from a chat message
+                        create_card_modal = <Modal> {
+                            content: {
+                                create_card_modal_inner = <CreateCardModal> {}
+                            }
+                        }
+
+                        // Show the prior versions of a kanban card or tag library
+                        history_modal = <Modal> {
+                            content: {
+                                history_modal_inner = <HistoryModal> {}
+                            }
+                        }
+
                         // Show incoming verification requests in front of the aforementioned UI elements.
                         verification_modal = <Modal> {
                             content: {
@@ -562,6 +646,20 @@ impl MatchEvent for App {
                 continue;
             }
 
+            // Handle card detail modal history button
+            if self
+                .ui
+                .button(ids!(
+                    card_detail_modal.modal.content.modal_header.history_button
+                ))
+                .clicked(actions)
+            {
+                if let Some(card_id) = self.app_state.kanban_state.selected_card_id.clone() {
+                    self.handle_kanban_action(cx, KanbanActions::ShowCardHistory { card_id });
+                }
+                continue;
+            }
+
             // Handle edit list name modal buttons
             if self
                 .ui
@@ -600,6 +698,180 @@ fn clear_all_app_state(cx: &mut Cx) {
     clear_avatar_cache(cx);
 }
 
+/// 子卡片状态变化后同步父卡片中对应待办的完成状态，并保存发生变化的 Todo 列表
+fn sync_parent_todos(state: &mut KanbanAppState, child_id: &OwnedRoomId) {
+    for parent_id in state.sync_child_todos(child_id) {
+        let parent = &state.cards[&parent_id];
+        log!("🧩 Synced todo of parent card {} with child card {}", parent_id, child_id);
+        outbox::submit(KanbanMutation::SaveCardTodos {
+            card_id: parent_id.clone(),
+            space_id: parent.space_id.clone(),
+            todos: parent.todos.clone(),
+        });
+    }
+}
+
+/// 卡片状态变化后记录状态变更活动（统计报表依赖其中的 from / to），并同步父卡片的待办
+///
+/// `previous_space_id` 为卡片原来所在的列表（跨列表移动时状态名称按原列表的工作流显示）。
+fn record_status_change(
+    state: &mut KanbanAppState,
+    card_id: &OwnedRoomId,
+    previous_space_id: &OwnedRoomId,
+    previous_status: &str,
+) {
+    let Some(card) = state.cards.get(card_id) else {
+        return;
+    };
+    if card.status == previous_status {
+        return;
+    }
+    let previous_workflow = state.workflow(previous_space_id);
+    let workflow = state.workflow(&card.space_id);
+    submit_async_request(MatrixRequest::RecordCardActivity {
+        card_id: card_id.clone(),
+        activity_type: ActivityType::StatusChange,
+        text: format!(
+            "状态变更: {} → {}",
+            previous_workflow.display_name(previous_status),
+            workflow.display_name(&card.status)
+        ),
+        metadata: Some(serde_json::json!({
+            "from": previous_status,
+            "to": card.status,
+        })),
+    });
+    sync_parent_todos(state, card_id);
+}
+
+/// 设置截止时间后记录活动（看板订阅会转发该活动）
+fn record_end_time_set(card_id: &OwnedRoomId, end_time: u64) {
+    submit_async_request(MatrixRequest::RecordCardActivity {
+        card_id: card_id.clone(),
+        activity_type: ActivityType::EndTimeSet,
+        text: format!("截止时间设为 {}", reminders::format_due_time(end_time)),
+        metadata: Some(serde_json::json!({ "end_time": end_time })),
+    });
+}
+
+/// 从服务器加载卡片后同步与它关联的待办：作为子卡片时同步父卡片，作为父卡片时按子卡片同步
+///
+/// 子卡片的状态可能在其他客户端被修改。状态不在所在列表的工作流中时说明工作流尚未加载，
+/// 此时无法判断是否完成，跳过该子卡片。
+fn sync_loaded_card_todos(state: &mut KanbanAppState, card_id: &OwnedRoomId) {
+    let Some(card) = state.cards.get(card_id) else {
+        return;
+    };
+    let mut child_ids: Vec<OwnedRoomId> = card
+        .todos
+        .iter()
+        .filter_map(|todo| todo.child_card_id.clone())
+        .collect();
+    child_ids.push(card_id.clone());
+    for child_id in child_ids {
+        let status_known = state.cards.get(&child_id).is_some_and(|child| {
+            let workflow = state.workflow(&child.space_id);
+            workflow.status(&child.status).is_some()
+        });
+        if status_known {
+            sync_parent_todos(state, &child_id);
+        }
+    }
+}
+
+/// 修改卡片并保存：标题变化时同时重命名卡片 Room，待办变化时保存待办，
+/// 状态变化时记录活动并同步父卡片的待办；找不到卡片时返回 false
+fn save_card_changes(
+    state: &mut KanbanAppState,
+    card_id: &OwnedRoomId,
+    change: impl FnOnce(&mut KanbanCard),
+) -> bool {
+    let Some(card) = state.cards.get_mut(card_id) else {
+        return false;
+    };
+    let (title, todos, status) = (card.title.clone(), card.todos.clone(), card.status.clone());
+    let space_id = card.space_id.clone();
+    change(card);
+    card.touch();
+
+    if card.title != title {
+        outbox::submit(KanbanMutation::UpdateCardTitle { card: card.clone() });
+    } else {
+        outbox::submit(KanbanMutation::SaveCardMetadata { card: card.clone() });
+    }
+    if card.todos != todos {
+        outbox::submit(KanbanMutation::SaveCardTodos {
+            card_id: card.id.clone(),
+            space_id: card.space_id.clone(),
+            todos: card.todos.clone(),
+        });
+    }
+    record_status_change(state, card_id, &space_id, &status);
+    true
+}
+
+/// 在卡片 Room 中记录一段工作时间（`work_logged` 活动，工时表由这些活动生成）
+fn record_work_session(session: &WorkSession) {
+    submit_async_request(MatrixRequest::RecordCardActivity {
+        card_id: session.card_id.clone(),
+        activity_type: ActivityType::WorkLogged,
+        text: format!(
+            "记录了 {} 工时",
+            time_tracking::format_duration(session.duration_secs())
+        ),
+        metadata: Some(session.activity_metadata()),
+    });
+}
+
+/// 撤销 / 重做 / 恢复历史版本时把保存的内容写回并同步到 Matrix
+///
+/// 返回恢复负责人和状态需要重新执行的 Action（见 [`undo_replay_actions`]）
+fn apply_undo_state(state: &mut KanbanAppState, saved: UndoState) -> Vec<KanbanActions> {
+    match saved {
+        UndoState::Card(saved) => {
+            let replay = state
+                .cards
+                .get(&saved.id)
+                .map(|current| undo_replay_actions(current, &saved))
+                .unwrap_or_default();
+            save_card_changes(state, &saved.id, |card| restore_card(card, &saved));
+            replay
+        }
+        UndoState::TagLibrary { space_id, tags } => {
+            state.space_tags.insert(space_id.clone(), tags.clone());
+            if get_client().is_some() {
+                submit_async_request(MatrixRequest::SaveSpaceTags { space_id, tags });
+            }
+            Vec::new()
+        }
+    }
+}
+
+/// 保存重新分配了排序键的卡片（见 [`KanbanAppState::rebalance_list`]）
+fn save_rebalanced_cards(state: &mut KanbanAppState) {
+    for card_id in state.take_rebalanced_cards() {
+        if let Some(card) = state.cards.get(&card_id) {
+            outbox::submit(KanbanMutation::SaveCardMetadata { card: card.clone() });
+        }
+    }
+}
+
+/// 把仍被阻塞的卡片移到列表后处于"已完成"状态时给出提示（不阻止移动）
+fn warn_if_blocked(state: &KanbanAppState, card_id: &OwnedRoomId, target_space_id: &OwnedRoomId) {
+    use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+
+    if !state.is_done_after_move(card_id, target_space_id) {
+        return;
+    }
+    if let Some(warning) = state.blocked_warning(card_id) {
+        enqueue_popup_notification(PopupItem {
+            message: format!("{warning}，请确认阻塞项已处理"),
+            kind: PopupKind::Warning,
+            auto_dismissal_duration: Some(4000.0),
+        });
+    }
+}
+
 impl AppMain for App {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event) {
         if let Event::Shutdown = event {
@@ -774,6 +1046,37 @@ impl App {
 
     fn handle_kanban_action(&mut self, cx: &mut Cx, action: KanbanActions) {
         let state = &mut self.app_state.kanban_state;
+
+        // 检查看板权限，没有权限的操作直接拒绝
+        if let Some((space_id, permission)) = state.required_permission(&action) {
+            if !state.can(&space_id, permission) {
+                log!(
+                    "🔒 {:?} 被拒绝: 在 {} 中没有「{}」权限",
+                    permission,
+                    space_id,
+                    permission.display_name()
+                );
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: format!("没有「{}」权限", permission.display_name()),
+                    kind: PopupKind::Warning,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+                return;
+            }
+        }
+
+        // 记录修改前的快照，修改后内容有变化时可以撤销
+        let pending_undo = state.begin_undo(&action);
+        self.dispatch_kanban_action(cx, action);
+        if let Some(pending) = pending_undo {
+            self.app_state.kanban_state.finish_undo(pending);
+        }
+    }
+
+    fn dispatch_kanban_action(&mut self, cx: &mut Cx, action: KanbanActions) {
+        let state = &mut self.app_state.kanban_state;
+
         match action {
             KanbanActions::LoadLists => {
                 // 加载所有 kanban Space（列表）
@@ -781,11 +1084,41 @@ impl App {
                     submit_async_request(MatrixRequest::LoadKanbanLists);
                     state.loading = true;
                 }
+
+                // 加载当前看板保存的过滤器
+                state.reload_saved_filters();
+                state.reminder_settings = reminders::settings();
+
+                // 继续发送上次未同步完成的离线操作
+                state.pending_sync = outbox::pending_count();
+                if state.pending_sync > 0 {
+                    submit_async_request(MatrixRequest::FlushKanbanOutbox);
+                }
             }
 
             KanbanActions::ListLoaded(list) => {
                 // 列表已加载
                 log!("ListLoaded: space_id='{}', name='{}'", list.id, list.name);
+                // 叠加尚未同步的本地移动 / 重命名
+                let list = outbox::reconcile_loaded_list(list);
+
+                // 加载列表的工作流和卡片模板
+                if !state.workflows.contains_key(&list.id) {
+                    submit_async_request(MatrixRequest::LoadSpaceWorkflow {
+                        space_id: list.id.clone(),
+                    });
+                }
+                if !state.space_templates.contains_key(&list.id) {
+                    submit_async_request(MatrixRequest::LoadSpaceTemplates {
+                        space_id: list.id.clone(),
+                    });
+                }
+                if !state.board_permissions.contains_key(&list.id) {
+                    submit_async_request(MatrixRequest::LoadBoardPermissions {
+                        space_id: list.id.clone(),
+                    });
+                }
+
                 state.upsert_list(list);
                 self.ui.redraw(cx);
             }
@@ -794,6 +1127,22 @@ impl App {
                 // 卡片已加载
                 log!("CardLoaded: card_id='{}', title='{}'", card.id, card.title);
 
+                // 记录服务器端状态，并叠加离线队列中尚未同步的本地修改
+                let mut card = outbox::reconcile_loaded_card(card);
+
+                // 状态不在已加载的工作流中（例如工作流在其他客户端被修改）时只在本地迁移，
+                // 服务器端的状态由修改工作流或移动卡片的请求迁移
+                let original_status = card.status.clone();
+                let migrated = state.migrate_loaded_card_status(&mut card);
+                if migrated {
+                    log!(
+                        "🔀 CardLoaded: showing status '{}' as '{}' for card {}",
+                        original_status,
+                        card.status,
+                        card.id
+                    );
+                }
+
                 // 添加卡片到 state
                 let space_id = card.space_id.clone();
                 let card_id = card.id.clone();
@@ -806,6 +1155,11 @@ impl App {
                     }
                 }
 
+                // 子卡片的状态可能在其他客户端被修改；本地迁移的状态不同步到父卡片，避免写回服务器
+                if !migrated {
+                    sync_loaded_card_todos(state, &card_id);
+                }
+
                 // 如果当前打开的模态框是这张卡片，强制重绘模态框
                 if state.selected_card_id.as_ref() == Some(&card_id) {
                     log!("🔄 Forcing modal redraw for updated card {}", card_id);
@@ -819,9 +1173,12 @@ impl App {
             }
 
             KanbanActions::CreateList { name } => {
-                // 创建新列表（Space）
+                // 创建新列表（Space），加入当前看板
                 if get_client().is_some() {
-                    submit_async_request(MatrixRequest::CreateKanbanList { name });
+                    submit_async_request(MatrixRequest::CreateKanbanList {
+                        name,
+                        board_id: state.current_board_id.clone(),
+                    });
                     state.loading = true;
                 }
             }
@@ -835,9 +1192,7 @@ impl App {
                 log!("✅ UpdateListName: 本地状态已更新，列表名称改为 '{}'", name);
 
                 // 同步到 Matrix 服务器
-                if get_client().is_some() {
-                    submit_async_request(MatrixRequest::UpdateKanbanListName { list_id, name });
-                }
+                outbox::submit(KanbanMutation::UpdateListName { list_id, name });
 
                 // 强制重绘整个 UI 以更新列表名称显示
                 self.ui.redraw(cx);
@@ -879,7 +1234,11 @@ impl App {
                 );
                 if get_client().is_some() {
                     log!("🎯 Submitting CreateKanbanCard request to worker thread...");
-                    submit_async_request(MatrixRequest::CreateKanbanCard { space_id, title });
+                    submit_async_request(MatrixRequest::CreateKanbanCard {
+                        space_id,
+                        title,
+                        description: None,
+                    });
                     state.loading = true;
                     log!("🎯 Request submitted, loading=true");
                 } else {
@@ -922,6 +1281,16 @@ impl App {
                         card_id: card_id.clone(),
                         limit: Some(50),
                     });
+
+                    // 加载附件（图片缩略图和封面候选）
+                    submit_async_request(MatrixRequest::LoadCardAttachments {
+                        card_id: card_id.clone(),
+                    });
+
+                    // 加载卡片成员（用于选择负责人）
+                    submit_async_request(MatrixRequest::LoadCardMembers {
+                        card_id: card_id.clone(),
+                    });
                 } else {
                     log!("ShowCardDetail: No Matrix client available!");
                 }
@@ -942,6 +1311,7 @@ impl App {
                 position,
             } => {
                 // 移动卡片到不同列表
+                warn_if_blocked(state, &card_id, &target_space_id);
                 if let Some(card) = state.cards.get_mut(&card_id) {
                     let old_space_id = card.space_id.clone();
                     card.space_id = target_space_id.clone();
@@ -970,6 +1340,56 @@ impl App {
                     status
                 );
 
+                let Some(space_id) = state.cards.get(&card_id).map(|c| c.space_id.clone()) else {
+                    log!("❌ UpdateCardStatus: Card not found in state");
+                    return;
+                };
+                let workflow = state.workflow(&space_id);
+                let current_status = state.cards[&card_id].status.clone();
+                if current_status == status {
+                    return;
+                }
+
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+
+                // 检查工作流是否允许该流转
+                if !workflow.can_transition(&current_status, &status) {
+                    enqueue_popup_notification(PopupItem {
+                        message: format!(
+                            "工作流不允许从「{}」流转到「{}」",
+                            workflow.display_name(&current_status),
+                            workflow.display_name(&status)
+                        ),
+                        kind: PopupKind::Warning,
+                        auto_dismissal_duration: Some(3000.0),
+                    });
+                    return;
+                }
+
+                // 检查 WIP 上限
+                if workflow.exceeds_wip_limit(&status, state.status_count(&space_id, &status)) {
+                    enqueue_popup_notification(PopupItem {
+                        message: format!(
+                            "「{}」已达到 WIP 上限，请先完成其中的卡片",
+                            workflow.display_name(&status)
+                        ),
+                        kind: PopupKind::Warning,
+                        auto_dismissal_duration: Some(3000.0),
+                    });
+                    return;
+                }
+
+                // 被阻塞的卡片标记为完成时提示（不阻止）
+                if workflow.category(&status) == StatusCategory::Done {
+                    if let Some(warning) = state.blocked_warning(&card_id) {
+                        enqueue_popup_notification(PopupItem {
+                            message: format!("{warning}，请确认阻塞项已处理"),
+                            kind: PopupKind::Warning,
+                            auto_dismissal_duration: Some(4000.0),
+                        });
+                    }
+                }
+
                 if let Some(card) = state.cards.get_mut(&card_id) {
                     card.status = status;
                     card.touch();
@@ -978,16 +1398,26 @@ impl App {
                         card.status
                     );
 
-                    // 保存到 Matrix
-                    if let Some(_client) = get_client() {
-                        log!("💾 UpdateCardStatus: Submitting SaveCardMetadata request");
-                        submit_async_request(MatrixRequest::SaveCardMetadata {
-                            card: card.clone(),
+                    // 保存到 Matrix（周期规则由生成任务在服务器端清除，这里保持不变）
+                    outbox::submit(KanbanMutation::SaveCardMetadata { card: card.clone() });
+
+                    // 完成时创建下一张周期卡片（周期规则移到新卡片上）
+                    let spawn_next = matches!(workflow.category(&card.status), StatusCategory::Done)
+                        && card
+                            .recurrence
+                            .as_ref()
+                            .is_some_and(|rule| rule.trigger == RecurrenceTrigger::OnComplete);
+                    if spawn_next {
+                        let recurring_card = card.clone();
+                        card.recurrence = None;
+                        submit_async_request(MatrixRequest::SpawnRecurringCard {
+                            card: recurring_card,
                         });
-                    } else {
-                        log!("⚠️ UpdateCardStatus: No Matrix client available");
                     }
 
+                    // 记录状态变更活动；由待办转换的子卡片同步父卡片的待办进度
+                    record_status_change(state, &card_id, &space_id, &current_status);
+
                     // 触发 UI 重绘
                     self.ui.redraw(cx);
                 } else {
@@ -1007,14 +1437,7 @@ impl App {
                     );
 
                     // 同步到 Matrix 服务器（传递完整的卡片数据以便保存元数据）
-                    if get_client().is_some() {
-                        let card_clone = card.clone();
-                        submit_async_request(MatrixRequest::UpdateKanbanCardTitle {
-                            card_id,
-                            title,
-                            card: card_clone,
-                        });
-                    }
+                    outbox::submit(KanbanMutation::UpdateCardTitle { card: card.clone() });
 
                     self.ui.redraw(cx);
                 }
@@ -1023,6 +1446,7 @@ impl App {
             KanbanActions::UpdateCardDescription {
                 card_id,
                 description,
+                description_html,
             } => {
                 // 更新卡片描述
                 log!(
@@ -1032,34 +1456,39 @@ impl App {
                 );
                 if let Some(card) = state.cards.get_mut(&card_id) {
                     card.description = description.clone();
+                    card.description_html = description_html;
                     card.touch(); // 更新 updated_at 时间戳
                     log!("✅ UpdateCardDescription: 本地状态已更新");
 
                     // 同步到 Matrix 服务器（传递完整的卡片数据以便保存元数据）
-                    if get_client().is_some() {
-                        let card_clone = card.clone();
-                        submit_async_request(MatrixRequest::UpdateKanbanCardDescription {
-                            card_id,
-                            description,
-                            card: card_clone,
-                        });
-                    }
+                    outbox::submit(KanbanMutation::SaveCardMetadata { card: card.clone() });
 
                     self.ui.redraw(cx);
                 }
             }
 
             KanbanActions::DeleteCard { card_id } => {
-                // 删除卡片
+                // 永久删除卡片
                 log!("DeleteCard: card_id='{}'", card_id);
                 if let Some(card) = state.cards.remove(&card_id) {
                     // 从列表中移除卡片 ID
                     if let Some(list) = state.lists.get_mut(&card.space_id) {
                         list.card_ids.retain(|id| id != &card_id);
                     }
+                    state.activities.remove(&card_id);
+                    state.card_members.remove(&card_id);
+                    if state.selected_card_id.as_ref() == Some(&card_id) {
+                        state.selected_card_id = None;
+                        self.ui.card_detail_modal(ids!(card_detail_modal)).close(cx);
+                    }
+
+                    // 同步到 Matrix 服务器（解除父子关系并退出卡片 Room）
+                    submit_async_request(MatrixRequest::DeleteKanbanCard {
+                        space_id: card.space_id,
+                        card_id,
+                    });
                     self.ui.redraw(cx);
                 }
-                // TODO: 同步到 Matrix 服务器（删除 Room）
             }
 
             // ========== Phase 2: TodoList Action Handlers ==========
@@ -1083,13 +1512,11 @@ impl App {
                     self.ui.redraw(cx);
 
                     // 异步保存到 Matrix（传递完整的todos列表）
-                    if get_client().is_some() {
-                        let todos_clone = card.todos.clone();
-                        submit_async_request(MatrixRequest::SaveCardTodos {
-                            card_id: card_id.clone(),
-                            todos: todos_clone,
-                        });
-                    }
+                    outbox::submit(KanbanMutation::SaveCardTodos {
+                        card_id: card_id.clone(),
+                        space_id: card.space_id.clone(),
+                        todos: card.todos.clone(),
+                    });
                 }
             }
 
@@ -1100,10 +1527,46 @@ impl App {
                     todo_id
                 );
 
+                // 已转换为子卡片的待办：改为切换子卡片的状态，完成状态随后同步回来
+                let child = state.cards.get(&card_id).and_then(|card| {
+                    let todo = card.todos.iter().find(|t| t.id == todo_id)?;
+                    let child = state.cards.get(todo.child_card_id.as_ref()?)?;
+                    Some((child.id.clone(), child.space_id.clone(), todo.completed))
+                });
+                if let Some((child_id, child_space_id, completed)) = child {
+                    let target = if completed {
+                        STATUS_PENDING
+                    } else {
+                        STATUS_COMPLETED
+                    };
+                    cx.action(KanbanActions::UpdateCardStatus {
+                        card_id: child_id,
+                        status: state.workflow(&child_space_id).migrate_status(target),
+                    });
+                    return;
+                }
+
                 // 立即更新内存中的 state
                 if let Some(card) = state.cards.get_mut(&card_id) {
                     if let Some(todo) = card.todos.iter_mut().find(|t| t.id == todo_id) {
                         todo.completed = !todo.completed;
+                        todo.completed_at = todo.completed.then(|| {
+                            std::time::SystemTime::now()
+                                .duration_since(std::time::UNIX_EPOCH)
+                                .unwrap()
+                                .as_secs()
+                        });
+                        let (activity_type, verb) = if todo.completed {
+                            (ActivityType::TodoCompleted, "完成了待办")
+                        } else {
+                            (ActivityType::TodoUncompleted, "重新打开了待办")
+                        };
+                        submit_async_request(MatrixRequest::RecordCardActivity {
+                            card_id: card_id.clone(),
+                            activity_type,
+                            text: format!("{}: {}", verb, todo.text),
+                            metadata: Some(serde_json::json!({ "todo_id": todo.id })),
+                        });
                         card.touch();
                         log!("✅ Toggled todo in memory immediately");
 
@@ -1117,13 +1580,11 @@ impl App {
                         self.ui.redraw(cx);
 
                         // 异步保存到 Matrix
-                        if get_client().is_some() {
-                            let todos_clone = card.todos.clone();
-                            submit_async_request(MatrixRequest::SaveCardTodos {
-                                card_id: card_id.clone(),
-                                todos: todos_clone,
-                            });
-                        }
+                        outbox::submit(KanbanMutation::SaveCardTodos {
+                            card_id: card_id.clone(),
+                            space_id: card.space_id.clone(),
+                            todos: card.todos.clone(),
+                        });
                     }
                 }
             }
@@ -1157,13 +1618,11 @@ impl App {
                         self.ui.redraw(cx);
 
                         // 异步保存到 Matrix
-                        if get_client().is_some() {
-                            let todos_clone = card.todos.clone();
-                            submit_async_request(MatrixRequest::SaveCardTodos {
-                                card_id: card_id.clone(),
-                                todos: todos_clone,
-                            });
-                        }
+                        outbox::submit(KanbanMutation::SaveCardTodos {
+                            card_id: card_id.clone(),
+                            space_id: card.space_id.clone(),
+                            todos: card.todos.clone(),
+                        });
                     }
                 }
             }
@@ -1191,14 +1650,142 @@ impl App {
                     self.ui.redraw(cx);
 
                     // 异步保存到 Matrix
-                    if get_client().is_some() {
-                        let todos_clone = card.todos.clone();
-                        submit_async_request(MatrixRequest::SaveCardTodos {
+                    outbox::submit(KanbanMutation::SaveCardTodos {
+                        card_id: card_id.clone(),
+                        space_id: card.space_id.clone(),
+                        todos: card.todos.clone(),
+                    });
+                }
+            }
+
+            KanbanActions::SetTodoAssignee {
+                card_id,
+                todo_id,
+                assignee,
+            } => {
+                log!(
+                    "👤 SetTodoAssignee: card_id='{}', todo_id='{}', assignee={:?}",
+                    card_id,
+                    todo_id,
+                    assignee
+                );
+                if let Some(card) = state.cards.get_mut(&card_id) {
+                    if let Some(todo) = card.todos.iter_mut().find(|t| t.id == todo_id) {
+                        todo.assignee = assignee;
+                        card.touch();
+                        outbox::submit(KanbanMutation::SaveCardTodos {
+                            card_id: card_id.clone(),
+                            space_id: card.space_id.clone(),
+                            todos: card.todos.clone(),
+                        });
+                        self.ui.redraw(cx);
+                    }
+                }
+            }
+
+            KanbanActions::SetTodoDueTime {
+                card_id,
+                todo_id,
+                due_time,
+            } => {
+                log!(
+                    "📅 SetTodoDueTime: card_id='{}', todo_id='{}', due_time={:?}",
+                    card_id,
+                    todo_id,
+                    due_time
+                );
+                if let Some(card) = state.cards.get_mut(&card_id) {
+                    if let Some(todo) = card.todos.iter_mut().find(|t| t.id == todo_id) {
+                        todo.due_time = due_time;
+                        card.touch();
+                        outbox::submit(KanbanMutation::SaveCardTodos {
                             card_id: card_id.clone(),
-                            todos: todos_clone,
+                            space_id: card.space_id.clone(),
+                            todos: card.todos.clone(),
+                        });
+                        self.ui.redraw(cx);
+                    }
+                }
+            }
+
+            KanbanActions::ConvertTodoToCard { card_id, todo_id } => {
+                log!(
+                    "🧩 ConvertTodoToCard: card_id='{}', todo_id='{}'",
+                    card_id,
+                    todo_id
+                );
+                let Some(parent) = state.cards.get(&card_id) else {
+                    return;
+                };
+                let Some(todo) = parent.todos.iter().find(|t| t.id == todo_id) else {
+                    return;
+                };
+                // 已经转换过的待办直接打开子卡片
+                if let Some(child_id) = &todo.child_card_id {
+                    cx.action(KanbanActions::ShowCardDetail {
+                        card_id: child_id.clone(),
+                    });
+                    return;
+                }
+                submit_async_request(MatrixRequest::CreateChildCard {
+                    parent: parent.clone(),
+                    todo: todo.clone(),
+                });
+            }
+
+            KanbanActions::TodoConvertedToCard {
+                parent_id,
+                todo_id,
+                card,
+            } => {
+                log!(
+                    "🧩 TodoConvertedToCard: parent='{}', todo='{}', child='{}'",
+                    parent_id,
+                    todo_id,
+                    card.id
+                );
+                let child_id = card.id.clone();
+                let child_space_id = card.space_id.clone();
+                let mut completed = false;
+                if let Some(parent) = state.cards.get_mut(&parent_id) {
+                    if let Some(todo) = parent.todos.iter_mut().find(|t| t.id == todo_id) {
+                        todo.child_card_id = Some(child_id.clone());
+                        completed = todo.completed;
+                        parent.touch();
+                        outbox::submit(KanbanMutation::SaveCardTodos {
+                            card_id: parent_id.clone(),
+                            space_id: parent.space_id.clone(),
+                            todos: parent.todos.clone(),
                         });
                     }
                 }
+                cx.action(KanbanActions::CardLoaded(card));
+
+                // 已完成的待办：子卡片直接进入完成状态
+                if completed {
+                    cx.action(KanbanActions::UpdateCardStatus {
+                        card_id: child_id,
+                        status: state.workflow(&child_space_id).migrate_status(STATUS_COMPLETED),
+                    });
+                }
+
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: "已把待办转换为子卡片".to_string(),
+                    kind: PopupKind::Success,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::TodoConversionFailed { card_id, error } => {
+                error!("❌ TodoConversionFailed: card_id='{}', error={}", card_id, error);
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: format!("转换子卡片失败: {}", error),
+                    kind: PopupKind::Error,
+                    auto_dismissal_duration: None,
+                });
             }
 
             // ========== Phase 3: Tags Action Handlers ==========
@@ -1222,12 +1809,7 @@ impl App {
                         self.ui.redraw(cx);
 
                         // 异步保存到 Matrix（传递完整的卡片数据）
-                        if get_client().is_some() {
-                            let card_clone = card.clone();
-                            submit_async_request(MatrixRequest::SaveCardMetadata {
-                                card: card_clone,
-                            });
-                        }
+                        outbox::submit(KanbanMutation::SaveCardMetadata { card: card.clone() });
                     }
                 }
             }
@@ -1251,10 +1833,7 @@ impl App {
                     self.ui.redraw(cx);
 
                     // 异步保存到 Matrix（传递完整的卡片数据）
-                    if get_client().is_some() {
-                        let card_clone = card.clone();
-                        submit_async_request(MatrixRequest::SaveCardMetadata { card: card_clone });
-                    }
+                    outbox::submit(KanbanMutation::SaveCardMetadata { card: card.clone() });
                 }
             }
 
@@ -1271,6 +1850,7 @@ impl App {
                     card.end_time = Some(end_time);
                     card.touch();
                     log!("✅ Updated end_time in memory immediately");
+                    record_end_time_set(&card_id, end_time);
 
                     // 如果模态框打开的是这张卡片，立即重绘
                     if state.selected_card_id.as_ref() == Some(&card_id) {
@@ -1282,10 +1862,7 @@ impl App {
                     self.ui.redraw(cx);
 
                     // 异步保存到 Matrix（传递完整的卡片数据）
-                    if get_client().is_some() {
-                        let card_clone = card.clone();
-                        submit_async_request(MatrixRequest::SaveCardMetadata { card: card_clone });
-                    }
+                    outbox::submit(KanbanMutation::SaveCardMetadata { card: card.clone() });
                 }
             }
 
@@ -1308,11 +1885,101 @@ impl App {
                     self.ui.redraw(cx);
 
                     // 异步保存到 Matrix（传递完整的卡片数据）
-                    if get_client().is_some() {
-                        let card_clone = card.clone();
-                        submit_async_request(MatrixRequest::SaveCardMetadata { card: card_clone });
-                    }
+                    outbox::submit(KanbanMutation::SaveCardMetadata { card: card.clone() });
+                }
+            }
+
+            // ========== 估时与工时 Action Handlers ==========
+            KanbanActions::SetCardEstimate { card_id, estimate } => {
+                log!(
+                    "⏱️ SetCardEstimate: card_id='{}', estimate={:?}",
+                    card_id,
+                    estimate
+                );
+                if save_card_changes(state, &card_id, |card| card.estimate = estimate) {
+                    self.ui.redraw(cx);
+                }
+            }
+
+            KanbanActions::StartCardTimer { card_id } => {
+                let Some(user_id) = current_user_id() else {
+                    log!("⚠️ StartCardTimer: not logged in");
+                    return;
+                };
+                let already_running = state
+                    .cards
+                    .get(&card_id)
+                    .is_none_or(|card| card.timer_of(&user_id).is_some());
+                if already_running {
+                    return;
+                }
+                log!("⏱️ StartCardTimer: card_id='{}', user={}", card_id, user_id);
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+                save_card_changes(state, &card_id, |card| {
+                    card.start_timer(user_id, now);
+                });
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::StopCardTimer { card_id } => {
+                let Some(user_id) = current_user_id() else {
+                    log!("⚠️ StopCardTimer: not logged in");
+                    return;
+                };
+                let running = state
+                    .cards
+                    .get(&card_id)
+                    .is_some_and(|card| card.timer_of(&user_id).is_some());
+                if !running {
+                    return;
+                }
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+                let mut session = None;
+                save_card_changes(state, &card_id, |card| {
+                    session = card.stop_timer(&user_id, now);
+                });
+                if let Some(session) = session {
+                    log!(
+                        "⏱️ StopCardTimer: card_id='{}', duration={}s",
+                        card_id,
+                        session.duration_secs()
+                    );
+                    record_work_session(&session);
+                }
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::LogCardWork {
+                card_id,
+                duration_secs,
+            } => {
+                let Some(user_id) = current_user_id() else {
+                    log!("⚠️ LogCardWork: not logged in");
+                    return;
+                };
+                log!(
+                    "⏱️ LogCardWork: card_id='{}', duration={}s",
+                    card_id,
+                    duration_secs
+                );
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+                let mut session = None;
+                save_card_changes(state, &card_id, |card| {
+                    session = Some(card.log_work(user_id, now.saturating_sub(duration_secs), now));
+                });
+                if let Some(session) = session {
+                    record_work_session(&session);
                 }
+                self.ui.redraw(cx);
             }
 
             // ========== Phase 5: Activities Action Handlers ==========
@@ -1438,6 +2105,100 @@ impl App {
                 cx.redraw_all();
             }
 
+            // ========== 附件与封面 Action Handlers ==========
+            KanbanActions::AttachmentsLoaded {
+                card_id,
+                attachments,
+            } => {
+                log!(
+                    "📎 AttachmentsLoaded: card_id='{}', count={}",
+                    card_id,
+                    attachments.len()
+                );
+                state.set_attachments(card_id, attachments);
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::UploadAttachment { card_id, path } => {
+                // 从文件管理器拖入或复制的路径可能带引号
+                let path = path.trim().trim_matches(|c| c == '"' || c == '\'');
+                log!(
+                    "📎 UploadAttachment: card_id='{}', path='{}'",
+                    card_id,
+                    path
+                );
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                if path.is_empty() || !std::path::Path::new(path).is_file() {
+                    enqueue_popup_notification(PopupItem {
+                        message: format!("找不到文件: {path}"),
+                        kind: PopupKind::Warning,
+                        auto_dismissal_duration: Some(3000.0),
+                    });
+                    return;
+                }
+                if get_client().is_some() {
+                    submit_async_request(MatrixRequest::UploadCardAttachment {
+                        card_id,
+                        path: path.into(),
+                    });
+                    enqueue_popup_notification(PopupItem {
+                        message: "正在上传附件…".to_string(),
+                        kind: PopupKind::Info,
+                        auto_dismissal_duration: Some(2000.0),
+                    });
+                }
+            }
+
+            KanbanActions::AttachmentUploaded {
+                card_id,
+                attachment,
+            } => {
+                log!(
+                    "📎 AttachmentUploaded: card_id='{}', name='{}'",
+                    card_id,
+                    attachment.name
+                );
+                // 卡片还没有封面时，第一张图片自动成为封面
+                let set_cover = attachment.is_image()
+                    && state
+                        .cards
+                        .get(&card_id)
+                        .is_some_and(|card| card.cover.is_none());
+                if set_cover {
+                    cx.action(KanbanActions::SetCardCover {
+                        card_id: card_id.clone(),
+                        cover: Some(attachment.url.clone()),
+                    });
+                }
+                state.add_attachment(card_id, attachment);
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::AttachmentUploadFailed { card_id, error } => {
+                error!(
+                    "❌ AttachmentUploadFailed: card_id='{}': {}",
+                    card_id, error
+                );
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: format!("附件上传失败: {error}"),
+                    kind: PopupKind::Error,
+                    auto_dismissal_duration: Some(4000.0),
+                });
+            }
+
+            KanbanActions::SetCardCover { card_id, cover } => {
+                log!("🖼️ SetCardCover: card_id='{}', cover={:?}", card_id, cover);
+                if let Some(card) = state.cards.get_mut(&card_id) {
+                    if card.cover != cover {
+                        card.cover = cover;
+                        card.touch();
+                        outbox::submit(KanbanMutation::SaveCardMetadata { card: card.clone() });
+                        self.ui.redraw(cx);
+                    }
+                }
+            }
+
             // ========== Space 标签库管理 Action Handlers ==========
             KanbanActions::LoadSpaceTags { space_id } => {
                 log!("📚 LoadSpaceTags: space_id='{}'", space_id);
@@ -1460,6 +2221,7 @@ impl App {
                         tag.color
                     );
                 }
+                state.tags_loaded_for_undo(&space_id, &tags);
                 state.space_tags.insert(space_id, tags);
 
                 // 触发 UI 重绘以显示加载的标签
@@ -1520,12 +2282,7 @@ impl App {
                         log!("AddTagToCard: Added tag, new tags: {:?}", card.tags);
 
                         // 保存到 Matrix
-                        if get_client().is_some() {
-                            log!("AddTagToCard: Submitting SaveCardMetadata request");
-                            submit_async_request(MatrixRequest::SaveCardMetadata {
-                                card: card.clone(),
-                            });
-                        }
+                        outbox::submit(KanbanMutation::SaveCardMetadata { card: card.clone() });
 
                         // 触发 UI 重绘以显示新标签
                         self.ui.redraw(cx);
@@ -1550,11 +2307,7 @@ impl App {
                     card.touch();
 
                     // 保存到 Matrix
-                    if get_client().is_some() {
-                        submit_async_request(MatrixRequest::SaveCardMetadata {
-                            card: card.clone(),
-                        });
-                    }
+                    outbox::submit(KanbanMutation::SaveCardMetadata { card: card.clone() });
 
                     // 触发 UI 重绘
                     self.ui.redraw(cx);
@@ -1583,11 +2336,9 @@ impl App {
                                 card.touch();
 
                                 // 保存到 Matrix
-                                if get_client().is_some() {
-                                    submit_async_request(MatrixRequest::SaveCardMetadata {
-                                        card: card.clone(),
-                                    });
-                                }
+                                outbox::submit(KanbanMutation::SaveCardMetadata {
+                                    card: card.clone(),
+                                });
 
                                 // 触发 UI 重绘
                                 self.ui.redraw(cx);
@@ -1636,6 +2387,7 @@ impl App {
                 card_id,
                 space_id,
                 position,
+                lane,
             } => {
                 log!(
                     "🎯 StartDragCard: card_id='{}', space_id='{}', position={}",
@@ -1654,6 +2406,8 @@ impl App {
                     card_id: card_id.clone(),
                     source_space_id: space_id.clone(),
                     source_position: position,
+                    source_lane: lane,
+                    drop_index: None,
                     start_time: now,
                 });
 
@@ -1661,16 +2415,20 @@ impl App {
                 self.ui.redraw(cx);
             }
 
+            KanbanActions::UpdateDropIndex { drop_index } => {
+                if let Some(drag_state) = state.drag_state.as_mut() {
+                    drag_state.drop_index = Some(drop_index);
+                }
+            }
+
             KanbanActions::DropCard {
                 card_id,
                 target_space_id,
-                target_position,
             } => {
                 log!(
-                    "🎯 DropCard: card_id='{}', target_space_id='{}', target_position={}",
+                    "🎯 DropCard: card_id='{}', target_space_id='{}'",
                     card_id,
-                    target_space_id,
-                    target_position
+                    target_space_id
                 );
 
                 // 获取拖拽状态
@@ -1678,60 +2436,65 @@ impl App {
 
                 if let Some(drag_state) = drag_state {
                     let source_space_id = drag_state.source_space_id.clone();
-                    let source_position = drag_state.source_position;
 
-                    // 检查是否真的移动了
-                    let is_same_space = source_space_id == target_space_id;
-                    let position_diff = (source_position - target_position).abs();
+                    // 放到拖拽经过的卡片之前；没有经过目标列表的卡片时放到末尾
+                    let current_user = current_user_id();
+                    let next_id = drag_state
+                        .drop_index
+                        .filter(|(space_id, _)| *space_id == target_space_id)
+                        .and_then(|(space_id, index)| {
+                            state.card_at_drop_index(
+                                &space_id,
+                                &card_id,
+                                index,
+                                current_user.as_deref(),
+                            )
+                        });
 
-                    if is_same_space && position_diff < 0.1 {
+                    // 检查是否真的移动了（放回同一列表中原来的位置）
+                    let is_same_space = source_space_id == target_space_id;
+                    let current_next_id = state
+                        .visible_list_cards(&target_space_id, current_user.as_deref())
+                        .into_iter()
+                        .skip_while(|card| card.id != card_id)
+                        .nth(1)
+                        .map(|card| card.id.clone());
+
+                    if is_same_space && next_id == current_next_id {
                         log!("DropCard: Card dropped at same position, ignoring");
                         return;
                     }
 
-                    // 乐观更新：立即更新本地状态
-                    if let Some(card) = state.cards.get_mut(&card_id) {
-                        let old_space_id = card.space_id.clone();
-                        card.space_id = target_space_id.clone();
-                        card.position = target_position;
-                        card.touch();
+                    if !is_same_space {
+                        warn_if_blocked(state, &card_id, &target_space_id);
+                    }
 
+                    // 乐观更新：立即更新本地状态
+                    let previous_status = state.cards.get(&card_id).map(|c| c.status.clone());
+                    if let Some(old_space_id) =
+                        state.move_card_before(&card_id, &target_space_id, next_id.as_ref())
+                    {
                         log!(
-                            "✅ Updated card in local state: space_id={}, position={}",
+                            "✅ Updated card in local state: space_id={}, rank={}",
                             target_space_id,
-                            target_position
+                            state.cards[&card_id].rank
                         );
-
-                        // 更新列表的 card_ids
-                        if old_space_id != target_space_id {
-                            // 从旧列表移除
-                            if let Some(old_list) = state.lists.get_mut(&old_space_id) {
-                                old_list.card_ids.retain(|id| id != &card_id);
-                                log!("✅ Removed card from old list {}", old_space_id);
-                            }
-
-                            // 添加到新列表
-                            if let Some(new_list) = state.lists.get_mut(&target_space_id) {
-                                if !new_list.card_ids.contains(&card_id) {
-                                    new_list.card_ids.push(card_id.clone());
-                                    log!("✅ Added card to new list {}", target_space_id);
-                                }
-                            }
+                        save_rebalanced_cards(state);
+                        // 移到其他列表时状态可能按目标工作流迁移
+                        if let Some(previous_status) = &previous_status {
+                            record_status_change(state, &card_id, &old_space_id, previous_status);
                         }
 
                         // 触发 UI 重绘
                         self.ui.redraw(cx);
 
                         // 异步同步到 Matrix
-                        if get_client().is_some() {
-                            let card_clone = card.clone();
-                            submit_async_request(MatrixRequest::MoveCard {
-                                card_id: card_id.clone(),
-                                source_space_id: old_space_id,
-                                target_space_id: target_space_id.clone(),
-                                card: card_clone,
-                            });
-                        }
+                        outbox::submit(KanbanMutation::MoveCard {
+                            card_id: card_id.clone(),
+                            source_space_id: old_space_id,
+                            target_space_id: target_space_id.clone(),
+                            card: state.cards[&card_id].clone(),
+                        });
                     } else {
                         log!("❌ DropCard: Card not found in state");
                     }
@@ -1748,10 +2511,138 @@ impl App {
                 self.ui.redraw(cx);
             }
 
+            KanbanActions::DropCardInLane {
+                card_id,
+                target_space_id,
+                lane_key,
+            } => {
+                log!(
+                    "🎯 DropCardInLane: card_id='{}', target_space_id='{}', lane='{}'",
+                    card_id,
+                    target_space_id,
+                    lane_key
+                );
+
+                let Some(drag_state) = state.drag_state.take() else {
+                    log!("⚠️ DropCardInLane: No drag state found");
+                    return;
+                };
+                let Some(card) = state.cards.get(&card_id) else {
+                    log!("❌ DropCardInLane: Card not found in state");
+                    return;
+                };
+
+                // 放到其他列表的单元格时先移动卡片（放到列表末尾）
+                if card.space_id != target_space_id {
+                    let previous_status = card.status.clone();
+                    warn_if_blocked(state, &card_id, &target_space_id);
+                    if let Some(old_space_id) = state.move_card_to(&card_id, &target_space_id) {
+                        save_rebalanced_cards(state);
+                        record_status_change(state, &card_id, &old_space_id, &previous_status);
+                        outbox::submit(KanbanMutation::MoveCard {
+                            card_id: card_id.clone(),
+                            source_space_id: old_space_id,
+                            target_space_id: target_space_id.clone(),
+                            card: state.cards[&card_id].clone(),
+                        });
+                    }
+                }
+
+                // 更新分组属性：用目标泳道的值替换原泳道的值
+                let source_lane = drag_state.source_lane.unwrap_or_default();
+                if source_lane == lane_key {
+                    return;
+                }
+                match state.swimlane_grouping {
+                    SwimlaneGrouping::None => {}
+                    SwimlaneGrouping::Status => {
+                        if lane_key != NO_VALUE_LANE {
+                            cx.action(KanbanActions::UpdateCardStatus {
+                                card_id,
+                                status: lane_key,
+                            });
+                        }
+                    }
+                    SwimlaneGrouping::Tag => {
+                        if source_lane != NO_VALUE_LANE {
+                            cx.action(KanbanActions::RemoveTagFromCard {
+                                card_id: card_id.clone(),
+                                tag_id: source_lane,
+                            });
+                        }
+                        if lane_key != NO_VALUE_LANE {
+                            cx.action(KanbanActions::AddTagToCard {
+                                card_id,
+                                tag_id: lane_key,
+                            });
+                        }
+                    }
+                    SwimlaneGrouping::Assignee => {
+                        if let Ok(user_id) = OwnedUserId::try_from(source_lane) {
+                            cx.action(KanbanActions::UnassignCard {
+                                card_id: card_id.clone(),
+                                user_id,
+                            });
+                        }
+                        if let Ok(user_id) = OwnedUserId::try_from(lane_key) {
+                            cx.action(KanbanActions::AssignCard { card_id, user_id });
+                        }
+                    }
+                }
+                self.ui.redraw(cx);
+            }
+
+            // ========== 泳道 Action Handlers ==========
+            KanbanActions::SetSwimlaneGrouping { grouping } => {
+                log!("SetSwimlaneGrouping: {:?}", grouping);
+                state.swimlane_grouping = grouping;
+                state.collapsed_lanes.clear();
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::ToggleSwimlane { lane_key } => {
+                if !state.collapsed_lanes.remove(&lane_key) {
+                    state.collapsed_lanes.insert(lane_key);
+                }
+                self.ui.redraw(cx);
+            }
+
+            // ========== 日历 / 时间线 Action Handlers ==========
+            KanbanActions::SetBoardViewMode { mode } => {
+                log!("📅 SetBoardViewMode: {}", mode.display_name());
+                state.board_view_mode = mode;
+                state.drag_state = None;
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::SetCalendarView { mode, anchor } => {
+                state.calendar_mode = mode;
+                state.calendar_anchor = anchor;
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::DropCardOnDate { card_id, date } => {
+                log!("📅 DropCardOnDate: card_id='{}', date={}", card_id, date);
+                state.drag_state = None;
+
+                let Some(card) = state.cards.get(&card_id) else {
+                    log!("❌ DropCardOnDate: Card not found in state");
+                    self.ui.redraw(cx);
+                    return;
+                };
+                let end_time = calendar::end_time_on_day(card.end_time, date);
+                if card.end_time == Some(end_time) {
+                    log!("ℹ️ DropCardOnDate: Dropped on the same day, nothing to do");
+                    self.ui.redraw(cx);
+                    return;
+                }
+                cx.action(KanbanActions::SetEndTime { card_id, end_time });
+            }
+
             KanbanActions::MoveCardFailed {
                 card_id,
                 original_space_id,
-                original_position,
+                original_rank,
                 error,
             } => {
                 log!(
@@ -1764,7 +2655,7 @@ impl App {
                 if let Some(card) = state.cards.get_mut(&card_id) {
                     let current_space_id = card.space_id.clone();
                     card.space_id = original_space_id.clone();
-                    card.position = original_position;
+                    card.rank = original_rank;
 
                     // 更新列表的 card_ids
                     if current_space_id != original_space_id {
@@ -1794,6 +2685,1496 @@ impl App {
                 });
             }
 
+            // ========== 过滤 Action Handlers ==========
+            KanbanActions::SetCardFilter { query } => {
+                match crate::kanban::state::card_filter::CardFilter::parse(&query, state) {
+                    Ok(filter) => {
+                        state.card_filter = filter;
+                        state.filter_error = None;
+                    }
+                    Err(e) => {
+                        // 保留上一次有效的过滤条件
+                        state.filter_error = Some(format!("{e}"));
+                    }
+                }
+                state.filter_query = query;
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::SaveCardFilter { name } => {
+                let query = state.filter_query.trim().to_string();
+                if query.is_empty() || state.filter_error.is_some() {
+                    log!("SaveCardFilter: skipped, query is empty or invalid");
+                    return;
+                }
+                log!("SaveCardFilter: name='{}', query='{}'", name, query);
+                state.saved_filters = saved_filters::save_filter(
+                    &state.saved_filters_key(),
+                    SavedFilter { name, query },
+                );
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::DeleteSavedFilter { name } => {
+                log!("DeleteSavedFilter: name='{}'", name);
+                state.saved_filters =
+                    saved_filters::delete_filter(&state.saved_filters_key(), &name);
+                self.ui.redraw(cx);
+            }
+
+            // ========== 截止时间提醒 Action Handlers ==========
+            KanbanActions::CheckDueReminders => {
+                let due = reminders::collect_due(state);
+                if due.is_empty() {
+                    return;
+                }
+
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                for reminder in due {
+                    let message = reminder.message(now);
+                    log!("⏰ Due reminder: {}", message);
+                    if state.reminder_settings.os_notifications {
+                        reminders::send_os_notification("看板提醒", &message);
+                    }
+                    enqueue_popup_notification(PopupItem {
+                        message,
+                        kind: if reminder.is_overdue() {
+                            PopupKind::Error
+                        } else {
+                            PopupKind::Warning
+                        },
+                        auto_dismissal_duration: None,
+                    });
+                }
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::ShowDueSoon => {
+                self.ui
+                    .due_soon_modal(ids!(due_soon_modal_inner))
+                    .set_data(cx, &state.reminder_settings);
+                self.ui.modal(ids!(due_soon_modal)).open(cx);
+            }
+
+            KanbanActions::CloseDueSoon => {
+                self.ui.modal(ids!(due_soon_modal)).close(cx);
+            }
+
+            KanbanActions::UpdateReminderSettings { settings } => {
+                log!("UpdateReminderSettings: {:?}", settings);
+                reminders::set_settings(settings.clone());
+                state.reminder_settings = settings;
+
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: "提醒设置已保存".to_string(),
+                    kind: PopupKind::Success,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+                self.ui.redraw(cx);
+            }
+
+            // ========== 卡片模板与周期卡片 Action Handlers ==========
+            KanbanActions::SpaceTemplatesLoaded { space_id, templates } => {
+                log!(
+                    "SpaceTemplatesLoaded: space_id='{}', {} templates",
+                    space_id,
+                    templates.len()
+                );
+                state.space_templates.insert(space_id, templates);
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::ShowCardTemplates { space_id } => {
+                log!("ShowCardTemplates: space_id='{}'", space_id);
+                self.ui
+                    .card_templates_modal(ids!(card_templates_modal_inner))
+                    .set_space(cx, space_id);
+                self.ui.modal(ids!(card_templates_modal)).open(cx);
+            }
+
+            KanbanActions::CloseCardTemplates => {
+                self.ui.modal(ids!(card_templates_modal)).close(cx);
+            }
+
+            KanbanActions::SaveCardAsTemplate { card_id, name } => {
+                let Some(card) = state.cards.get(&card_id) else {
+                    log!("❌ SaveCardAsTemplate: Card not found in state");
+                    return;
+                };
+                let space_id = card.space_id.clone();
+                let template = CardTemplate::from_card(name, card);
+                log!(
+                    "SaveCardAsTemplate: card_id='{}', template='{}'",
+                    card_id,
+                    template.name
+                );
+
+                let templates = state.space_templates.entry(space_id.clone()).or_default();
+                templates.push(template);
+                submit_async_request(MatrixRequest::SaveSpaceTemplates {
+                    space_id,
+                    templates: templates.clone(),
+                });
+
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: "已保存为模板".to_string(),
+                    kind: PopupKind::Success,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::DeleteCardTemplate {
+                space_id,
+                template_id,
+            } => {
+                log!(
+                    "DeleteCardTemplate: space_id='{}', template_id='{}'",
+                    space_id,
+                    template_id
+                );
+                if let Some(templates) = state.space_templates.get_mut(&space_id) {
+                    templates.retain(|template| template.id != template_id);
+                    submit_async_request(MatrixRequest::SaveSpaceTemplates {
+                        space_id,
+                        templates: templates.clone(),
+                    });
+                }
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::CreateCardFromTemplate {
+                space_id,
+                template_id,
+            } => {
+                let Some(template) = state
+                    .space_templates
+                    .get(&space_id)
+                    .and_then(|templates| templates.iter().find(|t| t.id == template_id))
+                    .cloned()
+                else {
+                    log!("❌ CreateCardFromTemplate: Template not found");
+                    return;
+                };
+                log!(
+                    "CreateCardFromTemplate: space_id='{}', template='{}'",
+                    space_id,
+                    template.name
+                );
+                if get_client().is_some() {
+                    submit_async_request(MatrixRequest::CreateCardFromTemplate {
+                        space_id,
+                        template,
+                    });
+                    self.ui.modal(ids!(card_templates_modal)).close(cx);
+                }
+            }
+
+            KanbanActions::SetCardRecurrence {
+                card_id,
+                recurrence,
+            } => {
+                log!(
+                    "SetCardRecurrence: card_id='{}', recurrence={:?}",
+                    card_id,
+                    recurrence
+                );
+                if let Some(card) = state.cards.get_mut(&card_id) {
+                    card.recurrence = recurrence;
+                    card.touch();
+                    outbox::submit(KanbanMutation::SaveCardMetadata { card: card.clone() });
+                    self.ui.redraw(cx);
+                }
+            }
+
+            KanbanActions::AddCardLink {
+                card_id,
+                link_type,
+                target_id,
+            } => {
+                log!(
+                    "🔗 AddCardLink: card_id='{}', type={:?}, target='{}'",
+                    card_id,
+                    link_type,
+                    target_id
+                );
+                if let Err(e) = state.check_link(&card_id, link_type, &target_id) {
+                    use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                    enqueue_popup_notification(PopupItem {
+                        message: format!("无法添加关联: {e}"),
+                        kind: PopupKind::Warning,
+                        auto_dismissal_duration: Some(3000.0),
+                    });
+                    return;
+                }
+                if let Some(card) = state.cards.get_mut(&card_id) {
+                    card.links.push(CardLink {
+                        link_type,
+                        card_id: target_id,
+                    });
+                    card.touch();
+                    outbox::submit(KanbanMutation::SaveCardMetadata { card: card.clone() });
+                    self.ui.redraw(cx);
+                }
+            }
+
+            KanbanActions::RemoveCardLink { source_id, link } => {
+                log!(
+                    "🔗 RemoveCardLink: source_id='{}', link={:?}",
+                    source_id,
+                    link
+                );
+                if let Some(card) = state.cards.get_mut(&source_id) {
+                    let before = card.links.len();
+                    card.links.retain(|existing| existing != &link);
+                    if card.links.len() != before {
+                        card.touch();
+                        outbox::submit(KanbanMutation::SaveCardMetadata { card: card.clone() });
+                        self.ui.redraw(cx);
+                    }
+                }
+            }
+
+            KanbanActions::CheckRecurringCards => {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+
+                // 按时触发的周期卡片：到达截止时间时创建下一张
+                let due_ids: Vec<_> = state
+                    .cards
+                    .values()
+                    .filter(|card| {
+                        card.recurrence
+                            .as_ref()
+                            .is_some_and(|rule| rule.trigger == RecurrenceTrigger::OnSchedule)
+                            && card.end_time.is_some_and(|end_time| end_time <= now)
+                    })
+                    .map(|card| card.id.clone())
+                    .collect();
+
+                for card_id in due_ids {
+                    let Some(card) = state.cards.get_mut(&card_id) else {
+                        continue;
+                    };
+                    log!("🔁 CheckRecurringCards: card {} reached its end time", card_id);
+                    // 只在本地清除周期规则避免重复触发，服务器端由生成任务清除
+                    let recurring_card = card.clone();
+                    card.recurrence = None;
+                    submit_async_request(MatrixRequest::SpawnRecurringCard {
+                        card: recurring_card,
+                    });
+                }
+            }
+
+            KanbanActions::RecurringCardSpawned { previous, card } => {
+                log!(
+                    "🔁 RecurringCardSpawned: previous='{}', next='{}'",
+                    previous.id,
+                    card.id
+                );
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: format!("已创建下一张周期卡片 '{}'", card.title),
+                    kind: PopupKind::Success,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+                cx.action(KanbanActions::CardLoaded(previous));
+                cx.action(KanbanActions::CardLoaded(card));
+            }
+
+            KanbanActions::RecurringCardFailed { card_id, error } => {
+                error!("❌ RecurringCardFailed: card_id='{}', error='{}'", card_id, error);
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: format!("创建下一张周期卡片失败: {}", error),
+                    kind: PopupKind::Error,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+            }
+
+            KanbanActions::TemplateUpdateFailed { space_id, error } => {
+                error!("❌ TemplateUpdateFailed: space_id='{}', error='{}'", space_id, error);
+
+                // 重新加载服务器端的模板，丢弃本地的乐观更新
+                submit_async_request(MatrixRequest::LoadSpaceTemplates { space_id });
+
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: format!("卡片模板操作失败: {}", error),
+                    kind: PopupKind::Error,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+            }
+
+            // ========== Space 工作流 Action Handlers ==========
+            KanbanActions::SpaceWorkflowLoaded { space_id, workflow } => {
+                log!(
+                    "SpaceWorkflowLoaded: space_id='{}', custom={}",
+                    space_id,
+                    workflow.is_some()
+                );
+                match workflow {
+                    Some(workflow) => {
+                        state.workflows.insert(space_id.clone(), workflow);
+                        state.migrate_list_statuses(&space_id);
+                    }
+                    None => {
+                        state.workflows.remove(&space_id);
+                    }
+                }
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::ShowWorkflowEditor { space_id } => {
+                log!("ShowWorkflowEditor: space_id='{}'", space_id);
+                let workflow = state.workflow(&space_id);
+                self.ui
+                    .workflow_editor_modal(ids!(workflow_editor_modal_inner))
+                    .set_data(cx, space_id, workflow);
+                self.ui.modal(ids!(workflow_editor_modal)).open(cx);
+            }
+
+            KanbanActions::CloseWorkflowEditor => {
+                self.ui.modal(ids!(workflow_editor_modal)).close(cx);
+            }
+
+            KanbanActions::SaveSpaceWorkflow { space_id, workflow } => {
+                log!(
+                    "SaveSpaceWorkflow: space_id='{}', {} statuses",
+                    space_id,
+                    workflow.statuses.len()
+                );
+                state.workflows.insert(space_id.clone(), workflow.clone());
+                // 服务器端的卡片状态由保存请求迁移，这里只同步更新本地显示
+                state.migrate_list_statuses(&space_id);
+                submit_async_request(MatrixRequest::SaveSpaceWorkflow { space_id, workflow });
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::WorkflowSaved { space_id, migrated } => {
+                log!("✅ WorkflowSaved: space_id='{}', migrated={}", space_id, migrated);
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: if migrated > 0 {
+                        format!("工作流已保存，{} 张卡片的状态已迁移", migrated)
+                    } else {
+                        "工作流已保存".to_string()
+                    },
+                    kind: PopupKind::Success,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+            }
+
+            KanbanActions::WorkflowUpdateFailed { space_id, error } => {
+                error!("❌ WorkflowUpdateFailed: space_id='{}', error='{}'", space_id, error);
+
+                // 重新加载服务器端的工作流，丢弃本地的乐观更新
+                submit_async_request(MatrixRequest::LoadSpaceWorkflow { space_id });
+
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: format!("保存工作流失败: {}", error),
+                    kind: PopupKind::Error,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+            }
+
+            // ========== 归档 Action Handlers ==========
+            KanbanActions::ArchiveCard { card_id } => {
+                log!("🗄️ ArchiveCard: card_id='{}'", card_id);
+                if let Some(card) = state.cards.get_mut(&card_id) {
+                    card.archived_at = Some(
+                        std::time::SystemTime::now()
+                            .duration_since(std::time::UNIX_EPOCH)
+                            .unwrap()
+                            .as_secs(),
+                    );
+                    card.touch();
+                    outbox::submit(KanbanMutation::SaveCardMetadata { card: card.clone() });
+
+                    if state.selected_card_id.as_ref() == Some(&card_id) {
+                        state.selected_card_id = None;
+                        self.ui.card_detail_modal(ids!(card_detail_modal)).close(cx);
+                    }
+                    self.ui.redraw(cx);
+                }
+            }
+
+            KanbanActions::RestoreCard { card_id } => {
+                log!("🗄️ RestoreCard: card_id='{}'", card_id);
+                let Some(card) = state.cards.get_mut(&card_id) else {
+                    return;
+                };
+                // 卡片仍保留所属列表和位置，清除归档时间即可回到原处
+                card.archived_at = None;
+                card.touch();
+                outbox::submit(KanbanMutation::SaveCardMetadata { card: card.clone() });
+
+                // 原列表也被归档时一并恢复
+                let space_id = card.space_id.clone();
+                if state
+                    .lists
+                    .get(&space_id)
+                    .is_some_and(|list| list.archived_at.is_some())
+                {
+                    cx.action(KanbanActions::RestoreList { list_id: space_id });
+                }
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::ArchiveList { list_id } => {
+                log!("🗄️ ArchiveList: list_id='{}'", list_id);
+                if let Some(list) = state.lists.get_mut(&list_id) {
+                    let archived_at = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs();
+                    list.archived_at = Some(archived_at);
+                    submit_async_request(MatrixRequest::SetListArchived {
+                        space_id: list_id,
+                        archived_at: Some(archived_at),
+                    });
+                    self.ui.redraw(cx);
+                }
+            }
+
+            KanbanActions::RestoreList { list_id } => {
+                log!("🗄️ RestoreList: list_id='{}'", list_id);
+                if let Some(list) = state.lists.get_mut(&list_id) {
+                    list.archived_at = None;
+                    submit_async_request(MatrixRequest::SetListArchived {
+                        space_id: list_id,
+                        archived_at: None,
+                    });
+                    self.ui.redraw(cx);
+                }
+            }
+
+            KanbanActions::DeleteList { list_id } => {
+                log!("🗑️ DeleteList: list_id='{}'", list_id);
+                if let Some(list) = state.lists.remove(&list_id) {
+                    for card_id in &list.card_ids {
+                        state.cards.remove(card_id);
+                        state.activities.remove(card_id);
+                        state.card_members.remove(card_id);
+                    }
+                    state.workflows.remove(&list_id);
+                    state.space_templates.remove(&list_id);
+                    state.space_tags.remove(&list_id);
+                    state.board_permissions.remove(&list_id);
+
+                    submit_async_request(MatrixRequest::DeleteKanbanList {
+                        space_id: list_id,
+                        card_ids: list.card_ids,
+                    });
+                    self.ui.redraw(cx);
+                }
+            }
+
+            KanbanActions::ShowArchive => {
+                log!("🗄️ ShowArchive: {} archived items", state.archived_items().len());
+                self.ui.modal(ids!(archive_modal)).open(cx);
+            }
+
+            KanbanActions::CloseArchive => {
+                self.ui.modal(ids!(archive_modal)).close(cx);
+            }
+
+            KanbanActions::ListArchiveFailed { list_id, error } => {
+                error!("❌ ListArchiveFailed: list_id='{}', error='{}'", list_id, error);
+
+                // 重新加载列表，丢弃本地的乐观更新
+                submit_async_request(MatrixRequest::LoadKanbanLists);
+
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: format!("归档列表失败: {}", error),
+                    kind: PopupKind::Error,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+            }
+
+            KanbanActions::DeleteFailed { id, error } => {
+                error!("❌ DeleteFailed: id='{}', error='{}'", id, error);
+
+                // 重新加载列表，恢复未能删除的卡片或列表
+                submit_async_request(MatrixRequest::LoadKanbanLists);
+
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: format!("永久删除失败: {}", error),
+                    kind: PopupKind::Error,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+            }
+
+            // ========== 多选与批量操作 Action Handlers ==========
+            KanbanActions::ToggleCardSelection { card_id } => {
+                state.toggle_card_selection(&card_id);
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::ExtendCardSelection { card_id } => {
+                let current_user = current_user_id();
+                state.extend_card_selection(&card_id, current_user.as_deref());
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::SelectAllInList { space_id } => {
+                let current_user = current_user_id();
+                state.select_all_in_list(&space_id, current_user.as_deref());
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::ClearCardSelection => {
+                state.clear_card_selection();
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::BulkApply { operation } => {
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+
+                let label = operation.describe(state);
+                let (updates, failures) = state.apply_bulk_operation(&operation);
+                save_rebalanced_cards(state);
+                log!(
+                    "📦 BulkApply: {} — {} to sync, {} rejected",
+                    label,
+                    updates.len(),
+                    failures.len()
+                );
+
+                let status_changes = updates
+                    .iter()
+                    .filter(|update| update.previous_status != update.card.status)
+                    .map(|update| {
+                        (
+                            update.card.id.clone(),
+                            (
+                                update.source_space_id.clone(),
+                                update.previous_status.clone(),
+                            ),
+                        )
+                    })
+                    .collect();
+                let progress = BulkProgress::new(label, updates.len(), failures, status_changes);
+
+                // 逐张加入离线队列，由队列报告每张卡片的同步结果
+                for update in &updates {
+                    if update.source_space_id != update.card.space_id {
+                        outbox::submit_bulk(
+                            &progress.id,
+                            KanbanMutation::MoveCard {
+                                card_id: update.card.id.clone(),
+                                source_space_id: update.source_space_id.clone(),
+                                target_space_id: update.card.space_id.clone(),
+                                card: update.card.clone(),
+                            },
+                        );
+                    } else {
+                        outbox::submit_bulk(
+                            &progress.id,
+                            KanbanMutation::SaveCardMetadata {
+                                card: update.card.clone(),
+                            },
+                        );
+                    }
+                    // 与单张卡片设置截止时间一样记录活动
+                    if let BulkOperation::SetDueDate {
+                        end_time: Some(end_time),
+                    } = operation
+                    {
+                        record_end_time_set(&update.card.id, end_time);
+                    }
+                }
+
+                if updates.is_empty() {
+                    enqueue_popup_notification(PopupItem {
+                        message: progress.summary(),
+                        kind: if progress.failures.is_empty() {
+                            PopupKind::Info
+                        } else {
+                            PopupKind::Warning
+                        },
+                        auto_dismissal_duration: Some(4000.0),
+                    });
+                }
+                state.start_bulk_operation(progress);
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::BulkCardProcessed {
+                bulk_id,
+                card_id,
+                error,
+            } => {
+                let Some(progress) = state.bulk_operation_mut(&bulk_id) else {
+                    return;
+                };
+                progress.processed += 1;
+                let previous_status = progress.status_changes.remove(&card_id);
+                if let Some(error) = error {
+                    let title = state
+                        .cards
+                        .get(&card_id)
+                        .map_or_else(|| card_id.to_string(), |card| card.title.clone());
+                    progress.failures.push(BulkFailure {
+                        card_id,
+                        title,
+                        error,
+                    });
+                } else if let Some((previous_space_id, previous_status)) = previous_status {
+                    // 状态变更已同步：记录活动，并同步父卡片的待办进度
+                    record_status_change(state, &card_id, &previous_space_id, &previous_status);
+                }
+
+                let Some(progress) = state.bulk_operation_mut(&bulk_id).cloned() else {
+                    return;
+                };
+                if !progress.is_finished() {
+                    self.ui.redraw(cx);
+                    return;
+                }
+                log!("📦 Bulk operation finished: {}", progress.summary());
+
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                if progress.failures.is_empty() {
+                    state.clear_card_selection();
+                    enqueue_popup_notification(PopupItem {
+                        message: progress.summary(),
+                        kind: PopupKind::Success,
+                        auto_dismissal_duration: Some(3000.0),
+                    });
+                } else {
+                    // 只保留失败的卡片为选中状态，方便重试；重新加载以丢弃失败卡片的本地修改
+                    state.selected_cards = progress
+                        .failures
+                        .iter()
+                        .map(|failure| failure.card_id.clone())
+                        .collect();
+                    enqueue_popup_notification(PopupItem {
+                        message: progress.summary(),
+                        kind: PopupKind::Warning,
+                        auto_dismissal_duration: None,
+                    });
+                    submit_async_request(MatrixRequest::LoadKanbanLists);
+                }
+                self.ui.redraw(cx);
+            }
+
+            // ========== 看板权限 Action Handlers ==========
+            KanbanActions::BoardPermissionsLoaded {
+                space_id,
+                permissions,
+            } => {
+                log!(
+                    "BoardPermissionsLoaded: space_id='{}', user_level={}",
+                    space_id,
+                    permissions.user_level
+                );
+                let board_settings = self.ui.board_settings_modal(ids!(board_settings_modal_inner));
+                if board_settings.space_id().as_ref() == Some(&space_id) {
+                    board_settings.set_data(cx, space_id.clone(), permissions.clone());
+                }
+                state.board_permissions.insert(space_id, permissions);
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::ShowBoardSettings { space_id } => {
+                log!("ShowBoardSettings: space_id='{}'", space_id);
+                let Some(permissions) = state.board_permissions.get(&space_id).cloned() else {
+                    submit_async_request(MatrixRequest::LoadBoardPermissions { space_id });
+                    use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                    enqueue_popup_notification(PopupItem {
+                        message: "正在加载看板权限，请稍后再试".to_string(),
+                        kind: PopupKind::Info,
+                        auto_dismissal_duration: Some(3000.0),
+                    });
+                    return;
+                };
+                self.ui
+                    .board_settings_modal(ids!(board_settings_modal_inner))
+                    .set_data(cx, space_id, permissions);
+                self.ui.modal(ids!(board_settings_modal)).open(cx);
+            }
+
+            KanbanActions::CloseBoardSettings => {
+                self.ui.modal(ids!(board_settings_modal)).close(cx);
+            }
+
+            KanbanActions::SaveBoardPermissions {
+                space_id,
+                permissions,
+            } => {
+                log!(
+                    "SaveBoardPermissions: space_id='{}', {} users",
+                    space_id,
+                    permissions.users.len()
+                );
+                submit_async_request(MatrixRequest::SaveBoardPermissions {
+                    space_id,
+                    permissions,
+                });
+                self.ui.modal(ids!(board_settings_modal)).close(cx);
+            }
+
+            KanbanActions::BoardPermissionsFailed { space_id, error } => {
+                error!(
+                    "❌ BoardPermissionsFailed: space_id='{}', error='{}'",
+                    space_id, error
+                );
+
+                // 重新加载服务器端的权限设置
+                submit_async_request(MatrixRequest::LoadBoardPermissions { space_id });
+
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: format!("保存看板权限失败: {}", error),
+                    kind: PopupKind::Error,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+            }
+
+            // ========== 负责人 Action Handlers ==========
+            KanbanActions::AssignCard { card_id, user_id } => {
+                log!("AssignCard: card_id='{}', user_id='{}'", card_id, user_id);
+
+                if let Some(card) = state.cards.get_mut(&card_id) {
+                    if !card.is_assigned_to(&user_id) {
+                        card.assignees.push(user_id.clone());
+                        card.touch();
+
+                        outbox::submit(KanbanMutation::SaveCardMetadata { card: card.clone() });
+                        outbox::submit(KanbanMutation::AssignCardMember { card_id, user_id });
+
+                        self.ui.redraw(cx);
+                    }
+                }
+            }
+
+            KanbanActions::UnassignCard { card_id, user_id } => {
+                log!("UnassignCard: card_id='{}', user_id='{}'", card_id, user_id);
+
+                if let Some(card) = state.cards.get_mut(&card_id) {
+                    if card.is_assigned_to(&user_id) {
+                        card.assignees.retain(|a| a != &user_id);
+                        card.touch();
+
+                        outbox::submit(KanbanMutation::SaveCardMetadata { card: card.clone() });
+                        outbox::submit(KanbanMutation::UnassignCardMember { card_id, user_id });
+
+                        self.ui.redraw(cx);
+                    }
+                }
+            }
+
+            KanbanActions::CardMembersLoaded { card_id, members } => {
+                log!("CardMembersLoaded: card_id='{}', {} members", card_id, members.len());
+                state.card_members.insert(card_id, members);
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::AssignCardFailed {
+                card_id,
+                user_id,
+                error,
+            } => {
+                error!(
+                    "❌ AssignCardFailed: card_id='{}', user_id='{}', error='{}'",
+                    card_id, user_id, error
+                );
+
+                // 邀请失败的用户不是卡片成员，撤销分配并同步到服务器
+                if let Some(card) = state.cards.get_mut(&card_id) {
+                    if card.is_assigned_to(&user_id) {
+                        card.assignees.retain(|a| a != &user_id);
+                        card.touch();
+
+                        outbox::submit(KanbanMutation::SaveCardMetadata { card: card.clone() });
+                        self.ui.redraw(cx);
+                    }
+                }
+
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: format!("分配负责人失败: {}", error),
+                    kind: PopupKind::Error,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+            }
+
+            KanbanActions::AssigneeUpdateFailed { card_id, error } => {
+                error!("❌ AssigneeUpdateFailed: card_id='{}', error='{}'", card_id, error);
+
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: format!("更新负责人失败: {}", error),
+                    kind: PopupKind::Error,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+            }
+
+            KanbanActions::ToggleMyCardsFilter => {
+                state.show_only_my_cards = !state.show_only_my_cards;
+                log!("ToggleMyCardsFilter: show_only_my_cards={}", state.show_only_my_cards);
+                self.ui.redraw(cx);
+            }
+
+            // ========== 离线队列 Action Handlers ==========
+            KanbanActions::SyncStatus { pending, error } => {
+                log!("📮 SyncStatus: pending={}, error={:?}", pending, error);
+                state.pending_sync = pending;
+                state.sync_error = error;
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::SyncEntryDropped { description, error } => {
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: format!(
+                        "同步失败，已放弃操作「{}」并恢复为服务器端的内容: {}",
+                        description, error
+                    ),
+                    kind: PopupKind::Error,
+                    auto_dismissal_duration: Some(5000.0),
+                });
+            }
+
+            // ========== 统计报表 Action Handlers ==========
+            KanbanActions::ShowAnalytics => {
+                let card_ids: Vec<OwnedRoomId> = state.cards.keys().cloned().collect();
+                log!("📊 ShowAnalytics: loading activities of {} cards", card_ids.len());
+                state.board_report = None;
+                submit_async_request(MatrixRequest::LoadBoardActivities { card_ids });
+                self.ui.modal(ids!(analytics_modal)).open(cx);
+            }
+
+            KanbanActions::CloseAnalytics => {
+                self.ui.modal(ids!(analytics_modal)).close(cx);
+            }
+
+            KanbanActions::BoardActivitiesLoaded { activities } => {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+                let report = BoardReport::build(state, &activities, now);
+                log!(
+                    "📊 BoardActivitiesLoaded: {} lists, {} cards",
+                    report.flows.len(),
+                    report.card_times.len()
+                );
+                state.board_report = Some(report);
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::AnalyticsFailed(message) => {
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: format!("加载统计数据失败: {}", message),
+                    kind: PopupKind::Error,
+                    auto_dismissal_duration: Some(5000.0),
+                });
+                self.ui.modal(ids!(analytics_modal)).close(cx);
+            }
+
+            KanbanActions::ExportAnalyticsCsv => {
+                let Some(report) = &state.board_report else {
+                    log!("⚠️ ExportAnalyticsCsv: report not loaded yet");
+                    return;
+                };
+                let dir = analytics::default_report_dir();
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                match report.write_csv(&dir) {
+                    Ok(files) => enqueue_popup_notification(PopupItem {
+                        message: format!("已导出 {} 个 CSV 文件到 {}", files.len(), dir.display()),
+                        kind: PopupKind::Success,
+                        auto_dismissal_duration: Some(5000.0),
+                    }),
+                    Err(e) => {
+                        error!("❌ Failed to export analytics: {e:?}");
+                        enqueue_popup_notification(PopupItem {
+                            message: format!("导出统计报表失败: {e}"),
+                            kind: PopupKind::Error,
+                            auto_dismissal_duration: Some(5000.0),
+                        });
+                    }
+                }
+            }
+
+            KanbanActions::ExportTimesheet { from, to } => {
+                // 包括已归档的卡片，按日期范围重新读取全部活动
+                let card_ids: Vec<OwnedRoomId> = state
+                    .cards
+                    .values()
+                    .filter(|card| state.is_in_current_board(&card.space_id))
+                    .map(|card| card.id.clone())
+                    .collect();
+                log!(
+                    "⏱️ ExportTimesheet: loading work sessions of {} cards, {}..={}",
+                    card_ids.len(),
+                    from,
+                    to
+                );
+                submit_async_request(MatrixRequest::LoadWorkSessions { card_ids, from, to });
+            }
+
+            KanbanActions::WorkSessionsLoaded { from, to, sessions } => {
+                let entries = analytics::timesheet_entries(state, sessions);
+                let path = analytics::default_timesheet_path(from, to);
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                match analytics::write_timesheet(&entries, from, to, &path) {
+                    Ok(count) => enqueue_popup_notification(PopupItem {
+                        message: format!("已导出 {} 条工时记录到 {}", count, path.display()),
+                        kind: PopupKind::Success,
+                        auto_dismissal_duration: Some(5000.0),
+                    }),
+                    Err(e) => {
+                        error!("❌ Failed to export timesheet: {e:?}");
+                        enqueue_popup_notification(PopupItem {
+                            message: format!("导出工时表失败: {e}"),
+                            kind: PopupKind::Error,
+                            auto_dismissal_duration: Some(5000.0),
+                        });
+                    }
+                }
+            }
+
+            // ========== 看板 Action Handlers ==========
+            KanbanActions::BoardsLoaded(boards) => {
+                log!("📋 BoardsLoaded: {} boards", boards.len());
+                state.set_boards(boards);
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::SelectBoard { board_id } => {
+                log!("📋 SelectBoard: {:?}", board_id);
+                state.select_board(board_id);
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::CreateBoard {
+                name,
+                background_color,
+            } => {
+                log!("📋 CreateBoard: name='{}'", name);
+                if get_client().is_some() {
+                    submit_async_request(MatrixRequest::CreateKanbanBoard {
+                        name,
+                        background_color,
+                    });
+                    state.loading = true;
+                }
+            }
+
+            KanbanActions::BoardCreated(board) => {
+                log!("📋 BoardCreated: {} ({})", board.name, board.id);
+                let board_id = board.id.clone();
+                state.upsert_board(board);
+                state.select_board(Some(board_id));
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::ListAddedToBoard { board_id, list_id } => {
+                state.add_list_to_board(&board_id, list_id);
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::UpdateBoardBackground {
+                board_id,
+                background_color,
+                background_image,
+            } => {
+                let Some(board) = state.boards.get_mut(&board_id) else {
+                    return;
+                };
+                board.background_color = background_color;
+                board.background_image = background_image;
+                submit_async_request(MatrixRequest::SaveKanbanBoard {
+                    board: board.clone(),
+                });
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::InviteToBoard { board_id, user_id } => {
+                log!("📨 InviteToBoard: board={}, user={}", board_id, user_id);
+                if get_client().is_some() {
+                    submit_async_request(MatrixRequest::InviteToKanbanBoard { board_id, user_id });
+                }
+            }
+
+            KanbanActions::BoardInviteSent {
+                board_id,
+                user_id,
+                rooms,
+            } => {
+                let board_name = state
+                    .boards
+                    .get(&board_id)
+                    .map_or_else(|| board_id.to_string(), |board| board.name.clone());
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: format!(
+                        "已邀请 {} 加入看板「{}」（{} 个看板、列表和卡片）",
+                        user_id, board_name, rooms
+                    ),
+                    kind: PopupKind::Success,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+            }
+
+            KanbanActions::JoinBoard { board_id } => {
+                log!("🚪 JoinBoard: {}", board_id);
+                if get_client().is_some() {
+                    submit_async_request(MatrixRequest::JoinKanbanBoard { board_id });
+                    state.loading = true;
+                }
+            }
+
+            KanbanActions::BoardJoined { board_id } => {
+                // 重新加载看板和列表，然后切换到刚加入的看板
+                if let Some(board) = state.boards.get_mut(&board_id) {
+                    board.invited = false;
+                }
+                state.select_board(Some(board_id));
+                submit_async_request(MatrixRequest::LoadKanbanLists);
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::SetBoardSubscription {
+                board_id,
+                room,
+                activity_types,
+            } => {
+                log!(
+                    "🔔 SetBoardSubscription: board={}, room='{}'",
+                    board_id,
+                    room
+                );
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                let Some(client) = get_client() else {
+                    return;
+                };
+                // 支持 Room ID（`!...`）和 Room 别名（`#...`），只能订阅到已加入的 Room
+                let room = room.trim();
+                let target = client.rooms().into_iter().find(|joined| {
+                    joined.room_id().as_str() == room
+                        || joined
+                            .canonical_alias()
+                            .is_some_and(|alias| alias.as_str() == room)
+                });
+                let Some(target) = target.filter(|r| r.state() == RoomState::Joined) else {
+                    enqueue_popup_notification(PopupItem {
+                        message: format!("找不到已加入的聊天室「{}」", room),
+                        kind: PopupKind::Warning,
+                        auto_dismissal_duration: Some(4000.0),
+                    });
+                    return;
+                };
+                let Some(board) = state.boards.get_mut(&board_id) else {
+                    return;
+                };
+                let unsubscribe = activity_types.is_empty();
+                board.set_subscription(target.room_id().to_owned(), activity_types);
+                submit_async_request(MatrixRequest::SaveKanbanBoard {
+                    board: board.clone(),
+                });
+                let room_name = target.name().unwrap_or_else(|| room.to_string());
+                enqueue_popup_notification(PopupItem {
+                    message: if unsubscribe {
+                        format!("已取消「{}」的看板动态订阅", room_name)
+                    } else {
+                        format!("看板「{}」的动态将转发到「{}」", board.name, room_name)
+                    },
+                    kind: PopupKind::Success,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+                self.ui.redraw(cx);
+            }
+
+            // ========== 导入 / 导出 Action Handlers ==========
+            KanbanActions::ExportBoard => {
+                let path = crate::kanban::import_export::default_export_path();
+                log!("📦 ExportBoard: path='{}'", path.display());
+                if get_client().is_some() {
+                    // 只导出当前看板中的列表
+                    let list_ids: Vec<OwnedRoomId> = state
+                        .lists
+                        .keys()
+                        .filter(|list_id| state.is_in_current_board(list_id))
+                        .cloned()
+                        .collect();
+                    submit_async_request(MatrixRequest::ExportKanbanBoard {
+                        path,
+                        name: state.current_board().map(|board| board.name.clone()),
+                        list_ids,
+                    });
+                    state.loading = true;
+                }
+            }
+
+            KanbanActions::BoardExported { path, lists, cards } => {
+                state.loading = false;
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: format!(
+                        "已导出 {} 个列表、{} 张卡片到 {}",
+                        lists,
+                        cards,
+                        path.display()
+                    ),
+                    kind: PopupKind::Success,
+                    auto_dismissal_duration: Some(5000.0),
+                });
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::ExportCalendar => {
+                let path = crate::kanban::import_export::default_ics_path();
+                log!("📅 ExportCalendar: path='{}'", path.display());
+                let (content, count) = crate::kanban::import_export::export_deadlines(state);
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                match crate::kanban::import_export::write_ics_file(&content, &path) {
+                    Ok(()) => enqueue_popup_notification(PopupItem {
+                        message: format!("已导出 {} 张卡片的截止时间到 {}", count, path.display()),
+                        kind: PopupKind::Success,
+                        auto_dismissal_duration: Some(5000.0),
+                    }),
+                    Err(e) => {
+                        error!("❌ Failed to export calendar: {e:?}");
+                        enqueue_popup_notification(PopupItem {
+                            message: format!("导出日历失败: {e}"),
+                            kind: PopupKind::Error,
+                            auto_dismissal_duration: Some(5000.0),
+                        });
+                    }
+                }
+            }
+
+            KanbanActions::ImportBoard { path } => {
+                log!("📥 ImportBoard: path='{}'", path.display());
+                if get_client().is_some() {
+                    submit_async_request(MatrixRequest::ImportKanbanBoard { path });
+                    state.loading = true;
+                }
+            }
+
+            KanbanActions::BoardImported {
+                lists,
+                cards,
+                failures,
+            } => {
+                log!(
+                    "📥 BoardImported: lists={}, cards={}, failures={}",
+                    lists,
+                    cards,
+                    failures.len()
+                );
+                state.loading = false;
+
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                if failures.is_empty() {
+                    enqueue_popup_notification(PopupItem {
+                        message: format!("已导入 {} 个列表、{} 张卡片", lists, cards),
+                        kind: PopupKind::Success,
+                        auto_dismissal_duration: Some(3000.0),
+                    });
+                } else {
+                    enqueue_popup_notification(PopupItem {
+                        message: format!(
+                            "已导入 {} 个列表、{} 张卡片，{} 项失败:\n{}",
+                            lists,
+                            cards,
+                            failures.len(),
+                            failures.join("\n")
+                        ),
+                        kind: PopupKind::Warning,
+                        auto_dismissal_duration: None,
+                    });
+                }
+
+                // 重新加载所有列表以显示导入的内容
+                if get_client().is_some() {
+                    submit_async_request(MatrixRequest::LoadKanbanLists);
+                }
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::ImportExportFailed(message) => {
+                state.loading = false;
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message,
+                    kind: PopupKind::Error,
+                    auto_dismissal_duration: Some(5000.0),
+                });
+                self.ui.redraw(cx);
+            }
+
+            // ========== 从聊天创建卡片 Action Handlers ==========
+            KanbanActions::ShowCreateCardFromMessage { title, description } => {
+                log!("📝 ShowCreateCardFromMessage: title='{}'", title);
+                if state.lists.is_empty() && get_client().is_some() {
+                    submit_async_request(MatrixRequest::LoadKanbanLists);
+                }
+                self.ui
+                    .create_card_modal(ids!(create_card_modal_inner))
+                    .set_draft(cx, &title, &description);
+                self.ui.modal(ids!(create_card_modal)).open(cx);
+            }
+
+            KanbanActions::CloseCreateCardModal => {
+                self.ui.modal(ids!(create_card_modal)).close(cx);
+            }
+
+            KanbanActions::CreateCardFromChat {
+                space_id,
+                title,
+                description,
+            } => {
+                log!(
+                    "📝 CreateCardFromChat: space_id='{}', title='{}'",
+                    space_id,
+                    title
+                );
+                if get_client().is_some() {
+                    submit_async_request(MatrixRequest::CreateKanbanCard {
+                        space_id,
+                        title,
+                        description,
+                    });
+                    state.loading = true;
+                }
+            }
+
+            KanbanActions::RunCardCommand { command, room_id } => {
+                log!("⌨️ RunCardCommand: {:?} in {}", command, room_id);
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                if state.lists.is_empty() {
+                    // 还没有打开过看板，先加载列表
+                    submit_async_request(MatrixRequest::LoadKanbanLists);
+                    enqueue_popup_notification(PopupItem {
+                        message: "看板列表正在加载，请稍后再试".to_string(),
+                        kind: PopupKind::Warning,
+                        auto_dismissal_duration: Some(3000.0),
+                    });
+                    return;
+                }
+                let Some(list) = state.command_target_list(&command) else {
+                    let message = match &command {
+                        CardCommand::Card { list, .. } => format!("找不到列表「{}」", list),
+                        CardCommand::Todo { .. } => "当前看板还没有列表".to_string(),
+                    };
+                    enqueue_popup_notification(PopupItem {
+                        message,
+                        kind: PopupKind::Warning,
+                        auto_dismissal_duration: Some(3000.0),
+                    });
+                    return;
+                };
+                // 创建卡片需要向列表添加 `m.space.child`，与移动卡片相同
+                if !state.can(&list.id, KanbanPermission::MoveCard) {
+                    log!(
+                        "🔒 RunCardCommand 被拒绝: 在 {} 中没有创建卡片的权限",
+                        list.id
+                    );
+                    enqueue_popup_notification(PopupItem {
+                        message: format!("没有在「{}」中创建卡片的权限", list.name),
+                        kind: PopupKind::Warning,
+                        auto_dismissal_duration: Some(3000.0),
+                    });
+                    return;
+                }
+                enqueue_popup_notification(PopupItem {
+                    message: format!("正在「{}」中创建卡片「{}」", list.name, command.title()),
+                    kind: PopupKind::Info,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+                submit_async_request(MatrixRequest::CreateKanbanCard {
+                    space_id: list.id.clone(),
+                    title: command.title().to_string(),
+                    description: Some(format!("来源聊天室: {}", room_id.matrix_to_uri())),
+                });
+            }
+
+            // ========== 历史版本与撤销 Action Handlers ==========
+            KanbanActions::Undo => {
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                let (message, replay) = match state.undo_history.take_undo() {
+                    Some((label, saved)) => {
+                        log!("↩️ Undo: {}", label);
+                        let replay = apply_undo_state(state, saved);
+                        (format!("已撤销「{}」", label), replay)
+                    }
+                    None => ("没有可撤销的操作".to_string(), Vec::new()),
+                };
+                enqueue_popup_notification(PopupItem {
+                    message,
+                    kind: PopupKind::Info,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+                // 负责人和状态通过原来的 Action 恢复（不再记录为新的撤销步骤）
+                for action in replay {
+                    self.dispatch_kanban_action(cx, action);
+                }
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::Redo => {
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                let (message, replay) = match state.undo_history.take_redo() {
+                    Some((label, saved)) => {
+                        log!("↪️ Redo: {}", label);
+                        let replay = apply_undo_state(state, saved);
+                        (format!("已重做「{}」", label), replay)
+                    }
+                    None => ("没有可重做的操作".to_string(), Vec::new()),
+                };
+                enqueue_popup_notification(PopupItem {
+                    message,
+                    kind: PopupKind::Info,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+                // 负责人和状态通过原来的 Action 恢复（不再记录为新的撤销步骤）
+                for action in replay {
+                    self.dispatch_kanban_action(cx, action);
+                }
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::ShowCardHistory { card_id } => {
+                log!("🕘 ShowCardHistory: card_id='{}'", card_id);
+                state.history_panel = Some(HistoryPanel::Card {
+                    card_id: card_id.clone(),
+                    revisions: None,
+                });
+                if get_client().is_some() {
+                    submit_async_request(MatrixRequest::LoadCardHistory { card_id });
+                }
+                self.ui.modal(ids!(history_modal)).open(cx);
+            }
+
+            KanbanActions::ShowTagLibraryHistory { space_id } => {
+                log!("🕘 ShowTagLibraryHistory: space_id='{}'", space_id);
+                state.history_panel = Some(HistoryPanel::TagLibrary {
+                    space_id: space_id.clone(),
+                    revisions: None,
+                });
+                if get_client().is_some() {
+                    submit_async_request(MatrixRequest::LoadTagLibraryHistory { space_id });
+                }
+                self.ui.modal(ids!(history_modal)).open(cx);
+            }
+
+            KanbanActions::CardHistoryLoaded { card_id, revisions } => {
+                log!(
+                    "🕘 CardHistoryLoaded: card_id='{}', count={}",
+                    card_id,
+                    revisions.len()
+                );
+                // 只更新仍然打开着的面板
+                if let Some(HistoryPanel::Card {
+                    card_id: open_id,
+                    revisions: open_revisions,
+                }) = &mut state.history_panel
+                {
+                    if *open_id == card_id {
+                        *open_revisions = Some(revisions);
+                    }
+                }
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::TagLibraryHistoryLoaded {
+                space_id,
+                revisions,
+            } => {
+                log!(
+                    "🕘 TagLibraryHistoryLoaded: space_id='{}', count={}",
+                    space_id,
+                    revisions.len()
+                );
+                if let Some(HistoryPanel::TagLibrary {
+                    space_id: open_id,
+                    revisions: open_revisions,
+                }) = &mut state.history_panel
+                {
+                    if *open_id == space_id {
+                        *open_revisions = Some(revisions);
+                    }
+                }
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::RestoreCardRevision { card_id, event_id } => {
+                log!(
+                    "🕘 RestoreCardRevision: card_id='{}', event_id='{}'",
+                    card_id,
+                    event_id
+                );
+                let Some(snapshot) = state
+                    .card_revision(&event_id)
+                    .map(|revision| revision.snapshot.clone())
+                else {
+                    log!("❌ RestoreCardRevision: revision not found");
+                    return;
+                };
+                if !save_card_changes(state, &card_id, |card| snapshot.apply_to(card)) {
+                    log!("❌ RestoreCardRevision: Card not found in state");
+                    return;
+                }
+
+                // 恢复本身会成为新版本，关闭面板避免显示过期的列表
+                state.history_panel = None;
+                self.ui.modal(ids!(history_modal)).close(cx);
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: "已恢复卡片的历史版本".to_string(),
+                    kind: PopupKind::Success,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::RestoreTagLibraryRevision { space_id, event_id } => {
+                log!(
+                    "🕘 RestoreTagLibraryRevision: space_id='{}', event_id='{}'",
+                    space_id,
+                    event_id
+                );
+                let Some(tags) = state
+                    .tag_library_revision(&event_id)
+                    .map(|revision| revision.tags.clone())
+                else {
+                    log!("❌ RestoreTagLibraryRevision: revision not found");
+                    return;
+                };
+                apply_undo_state(state, UndoState::TagLibrary { space_id, tags });
+
+                state.history_panel = None;
+                self.ui.modal(ids!(history_modal)).close(cx);
+                use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+                enqueue_popup_notification(PopupItem {
+                    message: "已恢复标签库的历史版本".to_string(),
+                    kind: PopupKind::Success,
+                    auto_dismissal_duration: Some(3000.0),
+                });
+                self.ui.redraw(cx);
+            }
+
+            KanbanActions::CloseHistory => {
+                state.history_panel = None;
+                self.ui.modal(ids!(history_modal)).close(cx);
+            }
+
             KanbanActions::Loading(loading) => {
                 state.loading = loading;
             }
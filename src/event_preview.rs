@@ -316,6 +316,11 @@ pub fn text_preview_of_message(message: &timeline::Message, sender_username: &st
                 htmlize::escape_text(&video.body)
             }
         ),
+        custom @ MessageType::_Custom(_)
+            if custom.msgtype() == crate::kanban::matrix_adapter::CARD_ACTIVITY_MSGTYPE =>
+        {
+            format!("<i>{}</i>", htmlize::escape_text(custom.body()))
+        }
         MessageType::_Custom(custom) => format!("[Custom message]: {:?}", custom,),
         other => format!(
             "[Unknown message type]: {}",
@@ -3,7 +3,7 @@ use makepad_widgets::*;
 use crate::{
     app::AppState,
     home::navigation_tab_bar::{NavigationBarAction, SelectedTab},
-    kanban::KanbanActions,
+    kanban::{KanbanActions, components::boards_list::BoardsListAction},
     settings::settings_screen::SettingsScreenWidgetRefExt,
 };
 
@@ -23,7 +23,9 @@ live_design! {
     use crate::home::main_desktop_ui::MainDesktopUI;
     use crate::settings::settings_screen::SettingsScreen;
     use crate::kanban::components::boards_list::BoardsList;
-    use crate::kanban::components::space::SpaceList;
+    use crate::kanban::components::swimlane_board::KanbanBoardView;
+    use crate::kanban::components::filter_bar::KanbanFilterBar;
+    use crate::kanban::components::bulk_action_bar::KanbanBulkActionBar;
     use crate::kanban::components::card_detail_view::CardDetailView;
 
 
@@ -137,10 +139,96 @@ live_design! {
                                         }
                                     }
 
+                                    sync_status_label = <Label> {
+                                        margin: {left: 15}
+                                        text: ""
+                                        draw_text: {
+                                            text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                                            color: #5E6C84
+                                        }
+                                    }
+
                                     <View> { width: Fill, height: Fit }
 
-                                    create_board_button = <Button> {
-                                        text: "创建看板"
+                                    undo_button = <Button> {
+                                        text: "撤销"
+                                        width: 70,
+                                        height: 40,
+                                        margin: {right: 10}
+                                    }
+
+                                    redo_button = <Button> {
+                                        text: "重做"
+                                        width: 70,
+                                        height: 40,
+                                        margin: {right: 10}
+                                    }
+
+                                    my_cards_button = <Button> {
+                                        text: "只看我的"
+                                        width: 100,
+                                        height: 40,
+                                        margin: {right: 10}
+                                    }
+
+                                    due_soon_button = <Button> {
+                                        text: "即将到期"
+                                        width: 100,
+                                        height: 40,
+                                        margin: {right: 10}
+                                    }
+
+                                    analytics_button = <Button> {
+                                        text: "统计"
+                                        width: 80,
+                                        height: 40,
+                                        margin: {right: 10}
+                                    }
+
+                                    archive_button = <Button> {
+                                        text: "归档"
+                                        width: 80,
+                                        height: 40,
+                                        margin: {right: 10}
+                                    }
+
+                                    import_path_input = <TextInput> {
+                                        width: 260,
+                                        height: 40,
+                                        margin: {right: 10}
+                                        empty_text: "归档或 Trello JSON 文件路径"
+                                        draw_text: {
+                                            color: #333333FF,
+                                            text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                                        }
+                                        draw_bg: {
+                                            color: #FFFFFFFF
+                                        }
+                                    }
+
+                                    import_board_button = <Button> {
+                                        text: "导入"
+                                        width: 80,
+                                        height: 40,
+                                        margin: {right: 10}
+                                    }
+
+                                    export_board_button = <Button> {
+                                        text: "导出"
+                                        width: 80,
+                                        height: 40,
+                                        margin: {right: 10}
+                                    }
+
+                                    export_calendar_button = <Button> {
+                                        text: "导出日历"
+                                        width: 100,
+                                        height: 40,
+                                        margin: {right: 10}
+                                    }
+
+                                    create_list_button = <Button> {
+                                        text: "新建列表"
                                         width: 120,
                                         height: 40,
                                         draw_bg: {
@@ -153,16 +241,15 @@ live_design! {
                                     }
                                 }
 
-                                // 看板区域 - 直接显示所有列表和卡片
-                                <ScrollXYView> {
-                                    width: Fill, height: Fill
-                                    scroll_bars: <ScrollBars> {
-                                        show_scroll_x: true,
-                                        show_scroll_y: true,
-                                    }
+                                // 看板切换栏
+                                <BoardsList> {}
 
-                                    <SpaceList> {}
-                                }
+                                // 卡片过滤栏
+                                <KanbanFilterBar> {}
+                                <KanbanBulkActionBar> {}
+
+                                // 看板区域 - 显示当前看板的列表和卡片（或按泳道分组）
+                                <KanbanBoardView> {}
                             }
 
                             // 看板详情页面 - 显示列表和卡片
@@ -196,16 +283,12 @@ live_design! {
                                     <View> { width: Fill, height: Fit }
                                 }
 
-                                // 列表区域 - 显示所有列表和卡片
-                                <ScrollXYView> {
-                                    width: Fill, height: Fill
-                                    scroll_bars: <ScrollBars> {
-                                        show_scroll_x: true,
-                                        show_scroll_y: false,
-                                    }
+                                // 卡片过滤栏
+                                <KanbanFilterBar> {}
+                                <KanbanBulkActionBar> {}
 
-                                    <SpaceList> {}
-                                }
+                                // 列表区域 - 显示所有列表和卡片（或按泳道分组）
+                                <KanbanBoardView> {}
                             }
                         }
                     }
@@ -295,10 +378,96 @@ live_design! {
                                                     }
                                                 }
 
+                                                sync_status_label = <Label> {
+                                                    margin: {left: 15}
+                                                    text: ""
+                                                    draw_text: {
+                                                        text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                                                        color: #5E6C84
+                                                    }
+                                                }
+
                                                 <View> { width: Fill, height: Fit }
 
-                                                create_board_button = <Button> {
-                                                    text: "创建看板"
+                                                undo_button = <Button> {
+                                                    text: "撤销"
+                                                    width: 70,
+                                                    height: 40,
+                                                    margin: {right: 10}
+                                                }
+
+                                                redo_button = <Button> {
+                                                    text: "重做"
+                                                    width: 70,
+                                                    height: 40,
+                                                    margin: {right: 10}
+                                                }
+
+                                                my_cards_button = <Button> {
+                                                    text: "只看我的"
+                                                    width: 100,
+                                                    height: 40,
+                                                    margin: {right: 10}
+                                                }
+
+                                                due_soon_button = <Button> {
+                                                    text: "即将到期"
+                                                    width: 100,
+                                                    height: 40,
+                                                    margin: {right: 10}
+                                                }
+
+                                                analytics_button = <Button> {
+                                                    text: "统计"
+                                                    width: 80,
+                                                    height: 40,
+                                                    margin: {right: 10}
+                                                }
+
+                                                archive_button = <Button> {
+                                                    text: "归档"
+                                                    width: 80,
+                                                    height: 40,
+                                                    margin: {right: 10}
+                                                }
+
+                                                import_path_input = <TextInput> {
+                                                    width: 260,
+                                                    height: 40,
+                                                    margin: {right: 10}
+                                                    empty_text: "归档或 Trello JSON 文件路径"
+                                                    draw_text: {
+                                                        color: #333333FF,
+                                                        text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                                                    }
+                                                    draw_bg: {
+                                                        color: #FFFFFFFF
+                                                    }
+                                                }
+
+                                                import_board_button = <Button> {
+                                                    text: "导入"
+                                                    width: 80,
+                                                    height: 40,
+                                                    margin: {right: 10}
+                                                }
+
+                                                export_board_button = <Button> {
+                                                    text: "导出"
+                                                    width: 80,
+                                                    height: 40,
+                                                    margin: {right: 10}
+                                                }
+
+                                                export_calendar_button = <Button> {
+                                                    text: "导出日历"
+                                                    width: 100,
+                                                    height: 40,
+                                                    margin: {right: 10}
+                                                }
+
+                                                create_list_button = <Button> {
+                                                    text: "新建列表"
                                                     width: 120,
                                                     height: 40,
                                                     draw_bg: {
@@ -319,7 +488,11 @@ live_design! {
                                                     show_scroll_y: true,
                                                 }
 
-                                                <BoardsList> {}
+                                                <BoardsList> {
+                                                    height: Fill
+                                                    open_on_select: true
+                                                    boards = { height: Fill, flow: Down }
+                                                }
                                             }
                                         }
 
@@ -354,16 +527,12 @@ live_design! {
                                                 <View> { width: Fill, height: Fit }
                                             }
 
-                                            // 列表区域 - 显示所有列表和卡片
-                                            <ScrollXYView> {
-                                                width: Fill, height: Fill
-                                                scroll_bars: <ScrollBars> {
-                                                    show_scroll_x: true,
-                                                    show_scroll_y: false,
-                                                }
+                                            // 卡片过滤栏
+                                            <KanbanFilterBar> {}
+                                            <KanbanBulkActionBar> {}
 
-                                                <SpaceList> {}
-                                            }
+                                            // 列表区域 - 显示所有列表和卡片（或按泳道分组）
+                                            <KanbanBoardView> {}
                                         }
                                     }
                                 }
@@ -477,7 +646,7 @@ impl Widget for HomeScreen {
         // 先让子widget处理事件
         self.view.handle_event(cx, event, scope);
 
-        // 然后处理创建看板按钮点击
+        // 然后处理看板页面的按钮点击
         if let Event::Actions(actions) = event {
             // 处理 CardDetailView 的关闭 Action
             if let Some(crate::kanban::components::card_detail_view::CardDetailViewAction::Close) =
@@ -493,7 +662,7 @@ impl Widget for HomeScreen {
             let kanban_page_flip = page_flip
                 .page_flip(ids!(kanban_page))
                 .page_flip(ids!(kanban_page_flip));
-            let button_ref = kanban_page_flip.button(ids!(create_board_button));
+            let button_ref = kanban_page_flip.button(ids!(create_list_button));
 
             if button_ref.clicked(actions) {
                 log!("Creating new kanban list...");
@@ -503,6 +672,59 @@ impl Widget for HomeScreen {
                 });
             }
 
+            // 撤销 / 重做本次会话中的卡片修改
+            if kanban_page_flip.button(ids!(undo_button)).clicked(actions) {
+                cx.action(KanbanActions::Undo);
+            }
+
+            if kanban_page_flip.button(ids!(redo_button)).clicked(actions) {
+                cx.action(KanbanActions::Redo);
+            }
+
+            // 切换"只看我的卡片"
+            if kanban_page_flip.button(ids!(my_cards_button)).clicked(actions) {
+                cx.action(KanbanActions::ToggleMyCardsFilter);
+            }
+
+            // 打开"即将到期"视图
+            if kanban_page_flip.button(ids!(due_soon_button)).clicked(actions) {
+                cx.action(KanbanActions::ShowDueSoon);
+            }
+
+            if kanban_page_flip.button(ids!(analytics_button)).clicked(actions) {
+                cx.action(KanbanActions::ShowAnalytics);
+            }
+
+            // 打开归档面板
+            if kanban_page_flip.button(ids!(archive_button)).clicked(actions) {
+                cx.action(KanbanActions::ShowArchive);
+            }
+
+            // 处理导出 / 导入按钮点击
+            if kanban_page_flip.button(ids!(export_board_button)).clicked(actions) {
+                log!("Exporting kanban board...");
+                cx.action(KanbanActions::ExportBoard);
+            }
+
+            if kanban_page_flip.button(ids!(export_calendar_button)).clicked(actions) {
+                log!("Exporting card deadlines as iCalendar...");
+                cx.action(KanbanActions::ExportCalendar);
+            }
+
+            if kanban_page_flip.button(ids!(import_board_button)).clicked(actions) {
+                let import_path_input = kanban_page_flip.text_input(ids!(import_path_input));
+                let path = import_path_input.text().trim().to_string();
+                if path.is_empty() {
+                    log!("Import skipped: no archive path given");
+                } else {
+                    log!("Importing kanban board from {}", path);
+                    cx.action(KanbanActions::ImportBoard {
+                        path: std::path::PathBuf::from(path),
+                    });
+                    import_path_input.set_text(cx, "");
+                }
+            }
+
             // 处理返回按钮点击
             let kanban_page_flip = page_flip
                 .page_flip(ids!(kanban_page))
@@ -515,8 +737,19 @@ impl Widget for HomeScreen {
                 self.view.redraw(cx);
             }
 
-            // 简化架构：不再需要 SelectBoard，直接显示所有列表
-            // 处理 KanbanActions 会在 app.rs 中统一处理
+            // 移动端布局：在看板切换栏中选择看板后打开看板详情页
+            for action in actions {
+                if let Some(BoardsListAction::Opened(board_name)) =
+                    action.downcast_ref::<BoardsListAction>()
+                {
+                    kanban_page_flip
+                        .label(ids!(board_title_label))
+                        .set_text(cx, board_name);
+                    kanban_page_flip.set_active_page(cx, id!(board_detail_page));
+                    self.view.redraw(cx);
+                }
+            }
+            // 其他 KanbanActions 在 app.rs 中统一处理
         }
 
         if let Event::Actions(actions) = event {
@@ -623,6 +856,32 @@ impl Widget for HomeScreen {
         // so we must re-set it to the correct page based on `app_state.selected_tab`.
         self.update_active_page_from_selection(cx, app_state);
 
+        // 显示离线队列的同步状态
+        let kanban_state = &app_state.kanban_state;
+        let sync_status = match (kanban_state.pending_sync, &kanban_state.sync_error) {
+            (0, _) => String::new(),
+            (pending, Some(error)) => format!("⚠️ {} 项待同步（{}）", pending, error),
+            (pending, None) => format!("⏳ {} 项待同步", pending),
+        };
+        self.view
+            .page_flip(ids!(home_screen_page_flip))
+            .page_flip(ids!(kanban_page))
+            .page_flip(ids!(kanban_page_flip))
+            .label(ids!(sync_status_label))
+            .set_text(cx, &sync_status);
+
+        let my_cards_text = if kanban_state.show_only_my_cards {
+            "显示全部"
+        } else {
+            "只看我的"
+        };
+        self.view
+            .page_flip(ids!(home_screen_page_flip))
+            .page_flip(ids!(kanban_page))
+            .page_flip(ids!(kanban_page_flip))
+            .button(ids!(my_cards_button))
+            .set_text(cx, my_cards_text);
+
         self.view.draw_walk(cx, scope, walk)
     }
 }
@@ -165,6 +165,11 @@ live_design! {
                 text: "Copy Link to Message"
             }
 
+            create_card_button = <ContextMenuButton> {
+                draw_icon: { svg_file: (ICON_ADD) }
+                text: "Create Card from Message"
+            }
+
             view_source_button = <ContextMenuButton> {
                 draw_icon: { svg_file: (ICON_VIEW_SOURCE) }
                 icon_walk: { margin: {top: 6, right: 3} }
@@ -447,6 +452,13 @@ impl WidgetMatchEvent for NewMessageContextMenu {
                 MessageAction::CopyLink(details.clone()),
             );
             close_menu = true;
+        } else if self.button(ids!(create_card_button)).clicked(actions) {
+            cx.widget_action(
+                details.room_screen_widget_uid,
+                &scope.path,
+                MessageAction::CreateCard(details.clone()),
+            );
+            close_menu = true;
         } else if self.button(ids!(view_source_button)).clicked(actions) {
             cx.widget_action(
                 details.room_screen_widget_uid,
@@ -529,6 +541,7 @@ impl NewMessageContextMenu {
         let copy_text_button = self.view.button(ids!(copy_text_button));
         let copy_html_button = self.view.button(ids!(copy_html_button));
         let copy_link_button = self.view.button(ids!(copy_link_to_message_button));
+        let create_card_button = self.view.button(ids!(create_card_button));
         let view_source_button = self.view.button(ids!(view_source_button));
         let jump_to_related_button = self.view.button(ids!(jump_to_related_button));
         // let report_button = self.view.button(ids!(report_button));
@@ -536,7 +549,8 @@ impl NewMessageContextMenu {
 
         // Determine which buttons should be shown.
         // Note that some buttons are always enabled:
-        // `copy_text_button`, `copy_link_to_message_button`, and `view_source_button`
+        // `copy_text_button`, `copy_link_to_message_button`, `create_card_button`,
+        // and `view_source_button`
         let show_react = details.abilities.contains(MessageAbilities::CanReact);
         let show_reply_to = details.abilities.contains(MessageAbilities::CanReplyTo);
         let show_divider_after_react_reply = show_react || show_reply_to;
@@ -545,6 +559,7 @@ impl NewMessageContextMenu {
         let show_copy_text = true;
         let show_copy_html = details.abilities.contains(MessageAbilities::HasHtml);
         let show_copy_link = true;
+        let show_create_card = true;
         let show_view_source = true;
         let show_jump_to_related = details.related_event_id.is_some();
         // let show_report = true;
@@ -585,6 +600,7 @@ impl NewMessageContextMenu {
         copy_text_button.reset_hover(cx);
         copy_html_button.reset_hover(cx);
         copy_link_button.reset_hover(cx);
+        create_card_button.reset_hover(cx);
         view_source_button.reset_hover(cx);
         jump_to_related_button.reset_hover(cx);
         // report_button.reset_hover(cx);
@@ -606,6 +622,7 @@ impl NewMessageContextMenu {
             + show_copy_text as u8
             + show_copy_html as u8
             + show_copy_link as u8
+            + show_create_card as u8
             + show_view_source as u8
             + show_jump_to_related as u8
             // + show_report as u8
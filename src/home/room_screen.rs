@@ -2189,6 +2189,38 @@ impl RoomScreen {
                         );
                     }
                 }
+                MessageAction::CreateCard(details) => {
+                    let Some(tl) = self.tl_state.as_ref() else {
+                        return;
+                    };
+                    let Some(body) = tl.items.get(details.item_id).and_then(|tl_item| {
+                        tl_item.as_event().map(plaintext_body_of_timeline_item)
+                    }) else {
+                        enqueue_popup_notification(PopupItem {
+                            message: "Could not find message in timeline to create a card from. Please try again!".to_string(),
+                            kind: PopupKind::Error,
+                            auto_dismissal_duration: None,
+                        });
+                        error!(
+                            "MessageAction::CreateCard: couldn't find event [{}] {:?} in room {}",
+                            details.item_id,
+                            details.event_id.as_deref(),
+                            tl.room_id,
+                        );
+                        continue;
+                    };
+                    let permalink = details
+                        .event_id
+                        .map(|event_id| tl.room_id.matrix_to_event_uri(event_id).to_string());
+                    let (title, description) = crate::kanban::chat_commands::draft_from_message(
+                        &body,
+                        permalink.as_deref(),
+                    );
+                    cx.action(crate::kanban::KanbanActions::ShowCreateCardFromMessage {
+                        title,
+                        description,
+                    });
+                }
                 MessageAction::ViewSource(_details) => {
                     enqueue_popup_notification(PopupItem {
                         message: "Viewing an event's source is not yet implemented.".to_string(),
@@ -2673,6 +2705,15 @@ impl RoomScreen {
         self.show_timeline(cx);
     }
 
+    /// Stops displaying the current room (if any).
+    ///
+    /// The room's timeline state is saved such that another `RoomScreen` can show it,
+    /// e.g., when a kanban card's embedded timeline is closed.
+    pub fn clear_displayed_room(&mut self) {
+        self.hide_timeline();
+        self.room_name_id = None;
+    }
+
     /// Sends read receipts based on the current scroll position of the timeline.
     fn send_user_read_receipts_based_on_scroll_pos(
         &mut self,
@@ -2792,6 +2833,19 @@ impl RoomScreenRef {
         };
         inner.set_displayed_room(cx, room_name_id);
     }
+
+    /// See [`RoomScreen::clear_displayed_room()`].
+    pub fn clear_displayed_room(&self) {
+        let Some(mut inner) = self.borrow_mut() else {
+            return;
+        };
+        inner.clear_displayed_room();
+    }
+
+    /// Returns the ID of the currently-displayed room, if any.
+    pub fn displayed_room_id(&self) -> Option<OwnedRoomId> {
+        self.borrow()?.room_name_id.as_ref().map(|rn| rn.room_id().clone())
+    }
 }
 
 /// Immutable RoomScreen states passed via Scope props
@@ -3533,6 +3587,51 @@ fn populate_message_view(
                         (item, false)
                     }
                 }
+                // A kanban card activity (e.g., a status change) is shown inline with the card's comments,
+                // styled like a notice and prefixed with the activity's icon.
+                activity @ MessageType::_Custom(_)
+                    if activity.msgtype() == crate::kanban::matrix_adapter::CARD_ACTIVITY_MSGTYPE =>
+                {
+                    is_notice = true;
+                    has_html_body = false;
+                    let template = if use_compact_view {
+                        id!(CondensedMessage)
+                    } else {
+                        id!(Message)
+                    };
+                    let (item, existed) = list.item_with_existed(cx, item_id, template);
+                    if existed && item_drawn_status.content_drawn {
+                        (item, true)
+                    } else {
+                        let icon = activity
+                            .data()
+                            .get("activity_type")
+                            .and_then(|t| t.as_str())
+                            .and_then(crate::kanban::state::kanban_state::ActivityType::parse)
+                            .map_or("📋", |t| t.icon_symbol());
+                        let html_or_plaintext_ref = item.html_or_plaintext(ids!(content.message));
+                        html_or_plaintext_ref.apply_over(
+                            cx,
+                            live!(
+                                html_view = {
+                                    html = {
+                                        font_color: (COLOR_MESSAGE_NOTICE_TEXT),
+                                        draw_normal:      { color: (COLOR_MESSAGE_NOTICE_TEXT), }
+                                        draw_italic:      { color: (COLOR_MESSAGE_NOTICE_TEXT), }
+                                        draw_bold:        { color: (COLOR_MESSAGE_NOTICE_TEXT), }
+                                        draw_bold_italic: { color: (COLOR_MESSAGE_NOTICE_TEXT), }
+                                    }
+                                }
+                            ),
+                        );
+                        html_or_plaintext_ref.show_html(
+                            cx,
+                            format!("<i>{} {}</i>", icon, htmlize::escape_text(activity.body())),
+                        );
+                        new_drawn_status.content_drawn = true;
+                        (item, false)
+                    }
+                }
                 _ => {
                     has_html_body = false;
                     let (item, existed) = list.item_with_existed(cx, item_id, id!(Message));
@@ -4605,6 +4704,9 @@ pub enum MessageAction {
     CopyHtml(MessageDetails),
     /// The user clicked the "copy link" button on a message.
     CopyLink(MessageDetails),
+    /// The user clicked the "create card" button on a message,
+    /// indicating that they want to turn the message into a kanban card.
+    CreateCard(MessageDetails),
     /// The user clicked the "view source" button on a message.
     ViewSource(MessageDetails),
     /// The user clicked the "jump to related" button on a message,
@@ -0,0 +1,655 @@
+//! 看板统计报表
+//!
+//! 基于卡片 Room 中的 `m.kanban.card.activity` 活动（状态变更的 `metadata` 里有 `from` / `to`）
+//! 和卡片的 `created_at`，重建每张卡片的状态历史，计算：
+//!
+//! - 累积流图（每个列表每天处于各状态的卡片数）
+//! - 前置时间（创建 → 完成）与周期时间（开始处理 → 完成）的分布
+//! - 每周吞吐量（完成的卡片数）
+//!
+//! 报表可以导出为 CSV（每种报表一个文件）。工时表由活动中的 `work_logged` 记录生成
+//! （见 [`crate::kanban::state::time_tracking`]）：报表中只汇总最近的活动，按日期范围导出
+//! 工时表时会重新读取每张卡片（包括已归档的卡片）在该范围内的全部活动。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{Duration, NaiveDate};
+use makepad_widgets::log;
+use matrix_sdk::ruma::OwnedRoomId;
+
+use crate::kanban::state::calendar::{day_start, local_date, today, week_start};
+use crate::kanban::state::kanban_state::{ActivityType, CardActivity, KanbanAppState, KanbanCard};
+use crate::kanban::state::time_tracking::{WorkSession, work_sessions};
+use crate::kanban::state::workflow::{StatusCategory, Workflow};
+
+/// 统计时每张卡片最多加载的活动数
+pub const ACTIVITY_LIMIT: usize = 200;
+
+/// 累积流图显示的天数
+pub const FLOW_DAYS: i64 = 28;
+
+/// 吞吐量显示的周数
+pub const THROUGHPUT_WEEKS: i64 = 8;
+
+/// 时间分布的分组（上限天数，显示名称）
+const DURATION_BUCKETS: &[(f64, &str)] = &[
+    (1.0, "< 1 天"),
+    (3.0, "1-3 天"),
+    (7.0, "3-7 天"),
+    (14.0, "1-2 周"),
+    (30.0, "2-4 周"),
+    (f64::INFINITY, "> 4 周"),
+];
+
+/// 一次状态变更
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusTransition {
+    /// 变更时间（Unix timestamp 秒）
+    pub at: u64,
+    pub from: Option<String>,
+    pub to: String,
+}
+
+/// 一张卡片的时间统计
+#[derive(Debug, Clone)]
+pub struct CardTimes {
+    pub card_id: OwnedRoomId,
+    pub title: String,
+    pub list_name: String,
+    pub created_at: u64,
+
+    /// 第一次进入"进行中"类状态的时间
+    pub started_at: Option<u64>,
+
+    /// 第一次进入"已完成"类状态的时间
+    pub completed_at: Option<u64>,
+}
+
+impl CardTimes {
+    /// 前置时间（秒）：创建 → 完成
+    pub fn lead_time(&self) -> Option<u64> {
+        self.completed_at
+            .map(|done| done.saturating_sub(self.created_at))
+    }
+
+    /// 周期时间（秒）：开始处理 → 完成
+    pub fn cycle_time(&self) -> Option<u64> {
+        self.completed_at
+            .zip(self.started_at)
+            .map(|(done, started)| done.saturating_sub(started))
+    }
+}
+
+/// 一个列表的累积流数据
+#[derive(Debug, Clone)]
+pub struct ListFlow {
+    pub space_id: OwnedRoomId,
+    pub name: String,
+
+    /// 工作流中的状态（ID，名称，颜色），按工作流顺序
+    pub statuses: Vec<(String, String, String)>,
+
+    /// 每天结束时处于各状态的卡片数（与 `statuses` 一一对应）
+    pub days: Vec<(NaiveDate, Vec<usize>)>,
+}
+
+/// 一组时长的统计值（单位：天）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DurationStats {
+    pub count: usize,
+    pub mean: f64,
+    pub p50: f64,
+    pub p85: f64,
+    pub max: f64,
+
+    /// 按 [`DURATION_BUCKETS`] 分组的数量
+    pub histogram: Vec<(&'static str, usize)>,
+}
+
+impl DurationStats {
+    /// 从时长（秒）计算统计值
+    pub fn from_secs(values: impl IntoIterator<Item = u64>) -> Self {
+        let mut days: Vec<f64> = values
+            .into_iter()
+            .map(|secs| secs as f64 / 86400.0)
+            .collect();
+        days.sort_by(f64::total_cmp);
+
+        let histogram = DURATION_BUCKETS
+            .iter()
+            .enumerate()
+            .map(|(i, (upper, label))| {
+                let lower = if i == 0 {
+                    0.0
+                } else {
+                    DURATION_BUCKETS[i - 1].0
+                };
+                let count = days.iter().filter(|d| **d >= lower && *d < *upper).count();
+                (*label, count)
+            })
+            .collect();
+
+        if days.is_empty() {
+            return Self {
+                histogram,
+                ..Self::default()
+            };
+        }
+        Self {
+            count: days.len(),
+            mean: days.iter().sum::<f64>() / days.len() as f64,
+            p50: percentile(&days, 0.50),
+            p85: percentile(&days, 0.85),
+            max: days[days.len() - 1],
+            histogram,
+        }
+    }
+
+    /// 摘要文本，例如"12 张 · 平均 3.2 天 · 中位数 2.0 天 · 85% 6.5 天"
+    pub fn summary(&self) -> String {
+        if self.count == 0 {
+            return "暂无数据".to_string();
+        }
+        format!(
+            "{} 张 · 平均 {:.1} 天 · 中位数 {:.1} 天 · 85% {:.1} 天 · 最长 {:.1} 天",
+            self.count, self.mean, self.p50, self.p85, self.max
+        )
+    }
+}
+
+/// 最近邻插值的百分位数（`sorted` 已排序且非空）
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// 工时表中的一段工作时间
+#[derive(Debug, Clone)]
+pub struct TimesheetEntry {
+    pub title: String,
+    pub list_name: String,
+    pub session: WorkSession,
+}
+
+/// 为工作时间补上卡片标题和所在列表名称，按开始时间排序
+///
+/// 已归档的卡片和列表同样会被查找，工时不会因为卡片归档而从工时表中消失。
+pub fn timesheet_entries(
+    state: &KanbanAppState,
+    sessions: impl IntoIterator<Item = WorkSession>,
+) -> Vec<TimesheetEntry> {
+    let mut entries: Vec<TimesheetEntry> = sessions
+        .into_iter()
+        .map(|session| {
+            let card = state.cards.get(&session.card_id);
+            TimesheetEntry {
+                title: card.map(|card| card.title.clone()).unwrap_or_default(),
+                list_name: card
+                    .and_then(|card| state.lists.get(&card.space_id))
+                    .map(|list| list.name.clone())
+                    .unwrap_or_default(),
+                session,
+            }
+        })
+        .collect();
+    entries.sort_by_key(|entry| entry.session.started_at);
+    entries
+}
+
+/// 看板统计报表
+#[derive(Debug, Clone)]
+pub struct BoardReport {
+    /// 生成时间（Unix timestamp 秒）
+    pub generated_at: u64,
+
+    pub flows: Vec<ListFlow>,
+    pub card_times: Vec<CardTimes>,
+
+    /// 每周（周一）完成的卡片数，按时间顺序
+    pub throughput: Vec<(NaiveDate, usize)>,
+
+    /// 当前看板所有卡片（包括已归档的卡片）最近记录的工作时间，按开始时间排序
+    pub timesheet: Vec<TimesheetEntry>,
+}
+
+impl BoardReport {
+    /// 由看板状态和各卡片的活动记录生成报表
+    pub fn build(
+        state: &KanbanAppState,
+        activities: &HashMap<OwnedRoomId, Vec<CardActivity>>,
+        now: u64,
+    ) -> Self {
+        let no_activities = Vec::new();
+        let mut flows = Vec::new();
+        let mut card_times = Vec::new();
+
+        let last_day = local_date(now).unwrap_or_else(today);
+        let first_day = last_day - Duration::days(FLOW_DAYS - 1);
+
+        for list in state.all_lists() {
+            let workflow = state.workflow(&list.id);
+            let statuses: Vec<(String, String, String)> = workflow
+                .statuses
+                .iter()
+                .map(|s| (s.id.clone(), s.name.clone(), s.color.clone()))
+                .collect();
+
+            let histories: Vec<(&KanbanCard, Vec<StatusTransition>)> = state
+                .list_cards(&list.id)
+                .into_iter()
+                .map(|card| {
+                    let card_activities = activities.get(&card.id).unwrap_or(&no_activities);
+                    (card, status_transitions(card_activities))
+                })
+                .collect();
+
+            let days = (0..FLOW_DAYS)
+                .map(|offset| {
+                    let date = first_day + Duration::days(offset);
+                    let end_of_day = day_start(date + Duration::days(1)).saturating_sub(1);
+                    let mut counts = vec![0; statuses.len()];
+                    for (card, transitions) in &histories {
+                        let Some(status) = status_at(card, transitions, end_of_day) else {
+                            continue;
+                        };
+                        if let Some(i) = statuses.iter().position(|(id, _, _)| *id == status) {
+                            counts[i] += 1;
+                        }
+                    }
+                    (date, counts)
+                })
+                .collect();
+
+            for (card, transitions) in &histories {
+                card_times.push(card_times_for(card, transitions, &workflow, &list.name));
+            }
+
+            flows.push(ListFlow {
+                space_id: list.id.clone(),
+                name: list.name.clone(),
+                statuses,
+                days,
+            });
+        }
+
+        let this_week = week_start(last_day);
+        let throughput = (0..THROUGHPUT_WEEKS)
+            .rev()
+            .map(|weeks_ago| {
+                let start = this_week - Duration::weeks(weeks_ago);
+                let from = day_start(start);
+                let to = day_start(start + Duration::weeks(1));
+                let completed = card_times
+                    .iter()
+                    .filter(|times| times.completed_at.is_some_and(|t| t >= from && t < to))
+                    .count();
+                (start, completed)
+            })
+            .collect();
+
+        // 工时包括已归档的卡片和列表
+        let timesheet = timesheet_entries(
+            state,
+            state
+                .cards
+                .values()
+                .filter(|card| state.is_in_current_board(&card.space_id))
+                .flat_map(|card| {
+                    work_sessions(&card.id, activities.get(&card.id).unwrap_or(&no_activities))
+                }),
+        );
+
+        Self {
+            generated_at: now,
+            flows,
+            card_times,
+            throughput,
+            timesheet,
+        }
+    }
+
+    /// 前置时间统计
+    pub fn lead_time_stats(&self) -> DurationStats {
+        DurationStats::from_secs(self.card_times.iter().filter_map(CardTimes::lead_time))
+    }
+
+    /// 周期时间统计
+    pub fn cycle_time_stats(&self) -> DurationStats {
+        DurationStats::from_secs(self.card_times.iter().filter_map(CardTimes::cycle_time))
+    }
+
+    /// 导出为 CSV：累积流、卡片时间、吞吐量各一个文件，返回写入的文件路径
+    pub fn write_csv(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        std::fs::create_dir_all(dir).context("Failed to create report directory")?;
+
+        let mut flow_csv = String::from("list,date,status,cards\n");
+        for flow in &self.flows {
+            for (date, counts) in &flow.days {
+                for ((_, status_name, _), count) in flow.statuses.iter().zip(counts) {
+                    flow_csv.push_str(&csv_row(&[
+                        &flow.name,
+                        &date.format("%Y-%m-%d").to_string(),
+                        status_name,
+                        &count.to_string(),
+                    ]));
+                }
+            }
+        }
+
+        let mut times_csv = String::from(
+            "card_id,title,list,created_at,started_at,completed_at,lead_time_days,cycle_time_days\n",
+        );
+        for times in &self.card_times {
+            let format_days = |secs: Option<u64>| {
+                secs.map(|s| format!("{:.2}", s as f64 / 86400.0))
+                    .unwrap_or_default()
+            };
+            times_csv.push_str(&csv_row(&[
+                times.card_id.as_str(),
+                &times.title,
+                &times.list_name,
+                &format_timestamp(Some(times.created_at)),
+                &format_timestamp(times.started_at),
+                &format_timestamp(times.completed_at),
+                &format_days(times.lead_time()),
+                &format_days(times.cycle_time()),
+            ]));
+        }
+
+        let mut throughput_csv = String::from("week_start,completed\n");
+        for (week, completed) in &self.throughput {
+            throughput_csv.push_str(&csv_row(&[
+                &week.format("%Y-%m-%d").to_string(),
+                &completed.to_string(),
+            ]));
+        }
+
+        let mut written = Vec::new();
+        for (name, content) in [
+            ("cumulative_flow.csv", flow_csv),
+            ("card_times.csv", times_csv),
+            ("throughput.csv", throughput_csv),
+        ] {
+            let path = dir.join(name);
+            std::fs::write(&path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            written.push(path);
+        }
+        log!("📊 Wrote kanban report to {}", dir.display());
+        Ok(written)
+    }
+}
+
+/// 开始日期在 `from` 到 `to`（含）之间的工作时间
+pub fn entries_between(
+    entries: &[TimesheetEntry],
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Vec<&TimesheetEntry> {
+    entries
+        .iter()
+        .filter(|entry| {
+            local_date(entry.session.started_at).is_some_and(|date| date >= from && date <= to)
+        })
+        .collect()
+}
+
+/// 导出 `from` 到 `to`（含）之间的工时表，返回导出的记录数
+///
+/// 每段工作时间一行，最后按成员汇总工时。
+pub fn write_timesheet(
+    entries: &[TimesheetEntry],
+    from: NaiveDate,
+    to: NaiveDate,
+    path: &Path,
+) -> Result<usize> {
+    let entries = entries_between(entries, from, to);
+
+    let mut csv = String::from("date,user,card_id,card,list,started_at,ended_at,hours\n");
+    let mut totals: Vec<(String, u64)> = Vec::new();
+    for entry in &entries {
+        let session = &entry.session;
+        let date = local_date(session.started_at)
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        csv.push_str(&csv_row(&[
+            &date,
+            session.user_id.as_str(),
+            session.card_id.as_str(),
+            &entry.title,
+            &entry.list_name,
+            &format_timestamp(Some(session.started_at)),
+            &format_timestamp(Some(session.ended_at)),
+            &format_hours(session.duration_secs()),
+        ]));
+
+        match totals
+            .iter_mut()
+            .find(|(user, _)| user == session.user_id.as_str())
+        {
+            Some((_, secs)) => *secs += session.duration_secs(),
+            None => totals.push((session.user_id.to_string(), session.duration_secs())),
+        }
+    }
+
+    // 按成员汇总
+    for (user, secs) in &totals {
+        csv.push_str(&csv_row(&[
+            "total",
+            user,
+            "",
+            "",
+            "",
+            "",
+            "",
+            &format_hours(*secs),
+        ]));
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).context("Failed to create export directory")?;
+    }
+    std::fs::write(path, csv).with_context(|| format!("Failed to write {}", path.display()))?;
+    log!(
+        "⏱️ Wrote timesheet ({} entries) to {}",
+        entries.len(),
+        path.display()
+    );
+    Ok(entries.len())
+}
+
+/// 默认的工时表导出路径：`<app_data_dir>/kanban_exports/timesheet_<开始>_<结束>.csv`
+pub fn default_timesheet_path(from: NaiveDate, to: NaiveDate) -> PathBuf {
+    crate::app_data_dir().join("kanban_exports").join(format!(
+        "timesheet_{}_{}.csv",
+        from.format("%Y%m%d"),
+        to.format("%Y%m%d")
+    ))
+}
+
+/// 默认的报表导出目录：`<app_data_dir>/kanban_exports/report_<时间>`
+pub fn default_report_dir() -> PathBuf {
+    crate::app_data_dir().join("kanban_exports").join(format!(
+        "report_{}",
+        chrono::Local::now().format("%Y%m%d_%H%M%S")
+    ))
+}
+
+/// 从活动记录中提取状态变更，按时间排序
+pub fn status_transitions(activities: &[CardActivity]) -> Vec<StatusTransition> {
+    let mut transitions: Vec<StatusTransition> = activities
+        .iter()
+        .filter(|activity| activity.activity_type == ActivityType::StatusChange)
+        .filter_map(|activity| {
+            let metadata = activity.metadata.as_ref()?;
+            Some(StatusTransition {
+                at: activity.created_at,
+                from: metadata
+                    .get("from")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string),
+                to: metadata.get("to")?.as_str()?.to_string(),
+            })
+        })
+        .collect();
+    transitions.sort_by_key(|t| t.at);
+    transitions
+}
+
+/// 卡片在某一时刻的状态；卡片尚未创建时返回 `None`
+fn status_at(card: &KanbanCard, transitions: &[StatusTransition], at: u64) -> Option<String> {
+    if card.created_at > at {
+        return None;
+    }
+    match transitions.iter().rev().find(|t| t.at <= at) {
+        Some(transition) => Some(transition.to.clone()),
+        // 第一次变更之前的状态；没有变更记录时就是当前状态
+        None => Some(
+            transitions
+                .first()
+                .and_then(|t| t.from.clone())
+                .unwrap_or_else(|| card.status.clone()),
+        ),
+    }
+}
+
+fn card_times_for(
+    card: &KanbanCard,
+    transitions: &[StatusTransition],
+    workflow: &Workflow,
+    list_name: &str,
+) -> CardTimes {
+    let first_entering = |category: StatusCategory| {
+        transitions
+            .iter()
+            .find(|t| workflow.category(&t.to) == category)
+            .map(|t| t.at)
+    };
+
+    // 没有状态变更记录但已经完成的卡片，用最后更新时间近似完成时间
+    let is_done = workflow.category(&card.status) == StatusCategory::Done;
+    let completed_at = if is_done {
+        first_entering(StatusCategory::Done).or(Some(card.updated_at))
+    } else {
+        None
+    };
+
+    CardTimes {
+        card_id: card.id.clone(),
+        title: card.title.clone(),
+        list_name: list_name.to_string(),
+        created_at: card.created_at,
+        started_at: first_entering(StatusCategory::InProgress),
+        completed_at,
+    }
+}
+
+fn format_timestamp(timestamp: Option<u64>) -> String {
+    timestamp
+        .and_then(|t| chrono::DateTime::from_timestamp(t as i64, 0))
+        .map(|dt| {
+            dt.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+/// 秒数转换为小时（两位小数）
+fn format_hours(secs: u64) -> String {
+    format!("{:.2}", secs as f64 / 3600.0)
+}
+
+/// 一行 CSV（按需加引号并转义）
+fn csv_row(fields: &[&str]) -> String {
+    let escaped: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect();
+    escaped.join(",") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(created_at: u64, status: &str) -> KanbanCard {
+        let mut card = KanbanCard::new(
+            OwnedRoomId::try_from("!card:example.org").expect("valid room id"),
+            "卡片".to_string(),
+            OwnedRoomId::try_from("!list:example.org").expect("valid room id"),
+        );
+        card.created_at = created_at;
+        card.status = status.to_string();
+        card
+    }
+
+    fn transition(at: u64, from: Option<&str>, to: &str) -> StatusTransition {
+        StatusTransition {
+            at,
+            from: from.map(str::to_string),
+            to: to.to_string(),
+        }
+    }
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        let sorted = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+        assert_eq!(percentile(&sorted, 0.50), 5.0);
+        assert_eq!(percentile(&sorted, 0.85), 9.0);
+        assert_eq!(percentile(&sorted, 1.0), 10.0);
+        assert_eq!(percentile(&sorted, 0.0), 1.0);
+    }
+
+    #[test]
+    fn percentile_of_single_value() {
+        assert_eq!(percentile(&[3.5], 0.50), 3.5);
+        assert_eq!(percentile(&[3.5], 0.85), 3.5);
+    }
+
+    #[test]
+    fn status_before_creation_is_none() {
+        let card = card(100, "done");
+        assert_eq!(status_at(&card, &[], 99), None);
+        assert_eq!(status_at(&card, &[], 100), Some("done".to_string()));
+    }
+
+    #[test]
+    fn status_without_transitions_is_current_status() {
+        let card = card(100, "doing");
+        assert_eq!(status_at(&card, &[], 500), Some("doing".to_string()));
+    }
+
+    #[test]
+    fn status_follows_transitions() {
+        let card = card(100, "done");
+        let transitions = [
+            transition(200, Some("todo"), "doing"),
+            transition(300, Some("doing"), "done"),
+        ];
+
+        let at = |time| status_at(&card, &transitions, time);
+
+        // 第一次变更之前使用其 from 状态
+        assert_eq!(at(150).as_deref(), Some("todo"));
+        assert_eq!(at(200).as_deref(), Some("doing"));
+        assert_eq!(at(299).as_deref(), Some("doing"));
+        assert_eq!(at(300).as_deref(), Some("done"));
+        assert_eq!(at(1000).as_deref(), Some("done"));
+    }
+
+    #[test]
+    fn status_before_first_transition_without_from_is_current_status() {
+        let card = card(100, "done");
+        let transitions = [transition(200, None, "done")];
+        assert_eq!(status_at(&card, &transitions, 150).as_deref(), Some("done"));
+    }
+}
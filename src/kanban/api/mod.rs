@@ -1,5 +1,4 @@
-pub mod kanban_requests;
 pub mod repositories;
 
 // Re-export main types
-pub use kanban_requests::*;
+pub use repositories::KanbanApi;
@@ -1,158 +1,484 @@
 //! 看板 API 层
 //!
-//! 提供高级别的看板操作 API，封装数据层与 Matrix SDK 的交互
+//! 在数据仓储之上提供界面使用的看板操作，`sliding_sync` 中的看板请求通过它访问 Matrix
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Result;
+use makepad_widgets::{error, log};
+use matrix_sdk::{
+    Client,
+    ruma::{OwnedRoomId, RoomId, UserId},
+};
 
 use crate::kanban::data::repositories::{
-    BoardRepositoryTrait, ListRepositoryTrait, CardRepositoryTrait, MatrixBoardRepository,
-    MatrixListRepository, MatrixCardRepository, BoardUpdateRequest, ListUpdateRequest,
-    CardUpdateRequest,
+    ActivityRepository, AttachmentRepository, BoardRepository, CardRepository, HistoryRepository,
+    ListRepository, MemberRepository, PermissionRepository, TagRepository, TemplateRepository,
+    WorkflowRepository,
 };
-use crate::kanban::data::models::{KanbanBoard, KanbanList, KanbanCard};
-use matrix_sdk::{ruma::RoomId, Client};
-use anyhow::Result;
-use std::sync::Arc;
+use crate::kanban::import_export::{self, BoardArchive, ImportSummary};
+use crate::kanban::matrix_adapter::MatrixKanbanAdapter;
+use crate::kanban::state::attachments::CardAttachment;
+use crate::kanban::state::history::{CardRevision, TagLibraryRevision};
+use crate::kanban::state::kanban_state::{
+    ActivityType, CardActivity, CardMember, KanbanBoard, KanbanCard, KanbanList, SpaceTag, TodoItem,
+};
+use crate::kanban::state::permissions::BoardPermissions;
+use crate::kanban::state::template::CardTemplate;
+use crate::kanban::state::time_tracking::{WorkSession, work_sessions};
+use crate::kanban::state::workflow::Workflow;
+
+/// 新建卡片后重新加载的次数
+const CREATE_CARD_LOAD_RETRIES: u32 = 3;
 
 /// 看板 API
-pub struct KanbanApi {
-    board_repository: Arc<MatrixBoardRepository>,
-    list_repository: Arc<MatrixListRepository>,
-    card_repository: Arc<MatrixCardRepository>,
+pub struct KanbanApi<R = MatrixKanbanAdapter> {
+    repository: R,
 }
 
 impl KanbanApi {
-    pub fn new() -> Self {
-        Self {
-            board_repository: Arc::new(MatrixBoardRepository::new()),
-            list_repository: Arc::new(MatrixListRepository::new()),
-            card_repository: Arc::new(MatrixCardRepository::new()),
-        }
+    /// 使用 Matrix 仓储
+    pub fn new(client: Client) -> Self {
+        Self::with_repository(MatrixKanbanAdapter::new(client))
+    }
+}
+
+impl<R> KanbanApi<R>
+where
+    R: BoardRepository
+        + ListRepository
+        + CardRepository
+        + TagRepository
+        + ActivityRepository
+        + AttachmentRepository
+        + HistoryRepository
+        + MemberRepository
+        + WorkflowRepository
+        + PermissionRepository
+        + TemplateRepository
+        + Sync,
+{
+    pub fn with_repository(repository: R) -> Self {
+        Self { repository }
+    }
+
+    /// 底层仓储
+    pub fn repository(&self) -> &R {
+        &self.repository
+    }
+
+    /// 加载所有看板
+    pub async fn load_boards(&self) -> Result<Vec<KanbanBoard>> {
+        self.repository.load_boards().await
     }
 
     /// 创建看板
-    pub async fn create_board(
-        &self,
-        client: &Client,
-        name: &str,
-        description: Option<&str>,
-    ) -> Result<KanbanBoard> {
-        self.board_repository
-            .create_board(client, name, description)
-            .await
+    pub async fn create_board(&self, name: &str, background_color: &str) -> Result<KanbanBoard> {
+        let board_id = self.repository.create_board(name, background_color).await?;
+        Ok(KanbanBoard {
+            id: board_id,
+            name: name.to_string(),
+            description: None,
+            background_color: background_color.to_string(),
+            background_image: None,
+            list_ids: Vec::new(),
+            archived_at: None,
+            invited: false,
+            subscriptions: Vec::new(),
+        })
+    }
+
+    /// 保存看板的背景、归档状态和订阅
+    pub async fn save_board(&self, board: &KanbanBoard) -> Result<()> {
+        self.repository.save_board(board).await
+    }
+
+    /// 邀请用户加入看板（包括其中的列表和卡片）
+    pub async fn invite_to_board(&self, board_id: &RoomId, user_id: &UserId) -> Result<usize> {
+        self.repository.invite_member(board_id, user_id).await
     }
 
-    /// 获取看板
-    pub async fn get_board(&self, client: &Client, room_id: &RoomId) -> Result<KanbanBoard> {
-        self.board_repository.get_board(client, room_id).await
+    /// 接受看板邀请
+    pub async fn join_board(&self, board_id: &RoomId) -> Result<usize> {
+        self.repository.join_board(board_id).await
     }
 
-    /// 更新看板
-    pub async fn update_board(
+    /// 加载所有列表
+    pub async fn load_lists(&self) -> Result<Vec<KanbanList>> {
+        self.repository.load_lists().await
+    }
+
+    /// 加载列表的标签库
+    pub async fn load_tags(&self, list_id: &RoomId) -> Result<Vec<SpaceTag>> {
+        self.repository.load_tags(list_id).await
+    }
+
+    /// 加载列表中的所有卡片（加载失败的卡片会被跳过）
+    pub async fn load_list_cards(&self, list: &KanbanList) -> Vec<KanbanCard> {
+        log!(
+            "Loading {} cards for space {}",
+            list.card_ids.len(),
+            list.id
+        );
+        let mut cards = Vec::with_capacity(list.card_ids.len());
+        for card_id in &list.card_ids {
+            match self.repository.load_card(card_id, list.id.clone()).await {
+                Ok(card) => {
+                    log!("Loaded card: {} ({})", card.title, card.id);
+                    cards.push(card);
+                }
+                Err(e) => {
+                    error!("Failed to load card {}: {e:?}", card_id);
+                }
+            }
+        }
+        cards
+    }
+
+    /// 创建列表；指定看板时加入该看板
+    pub async fn create_list(&self, name: &str, board_id: Option<&RoomId>) -> Result<KanbanList> {
+        let list_id = self.repository.create_list(name).await?;
+
+        // 等待 Matrix SDK 同步新创建的 Space
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+
+        if let Some(board_id) = board_id {
+            self.repository.add_list(board_id, &list_id).await?;
+        }
+
+        Ok(KanbanList {
+            id: list_id,
+            name: name.to_string(),
+            card_ids: Vec::new(),
+            position: 1000.0,
+            archived_at: None,
+        })
+    }
+
+    /// 重命名列表
+    pub async fn rename_list(&self, list_id: &RoomId, name: &str) -> Result<()> {
+        self.repository.rename_list(list_id, name).await
+    }
+
+    /// 归档（`Some(时间)`）或恢复（`None`）列表
+    pub async fn set_list_archived(
         &self,
-        client: &Client,
-        room_id: &RoomId,
-        updates: BoardUpdateRequest,
+        list_id: &RoomId,
+        archived_at: Option<u64>,
     ) -> Result<()> {
-        self.board_repository
-            .update_board(client, room_id, updates)
+        self.repository
+            .set_list_archived(list_id, archived_at)
+            .await
+    }
+
+    /// 永久删除列表及其中的卡片
+    pub async fn delete_list(&self, list_id: &RoomId, card_ids: &[OwnedRoomId]) -> Result<()> {
+        self.repository.delete_list(list_id, card_ids).await
+    }
+
+    /// 创建卡片并加载；多次加载失败时返回只有标题的卡片
+    pub async fn create_card(&self, list_id: &RoomId, title: &str) -> Result<KanbanCard> {
+        let card_id = self.repository.create_card(list_id, title).await?;
+        log!("✅ Successfully created kanban card: {}", card_id);
+
+        // 等待 Matrix SDK 同步新创建的卡片
+        log!("⏳ Waiting for Matrix SDK to sync the new card...");
+        tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+
+        // 记录创建活动（同时转发到看板订阅的聊天 Room）
+        if let Err(e) = self
+            .repository
+            .record_activity(
+                &card_id,
+                ActivityType::Created,
+                format!("创建了卡片「{}」", title),
+                None,
+            )
             .await
+        {
+            error!("❌ Failed to record creation of card {}: {e:?}", card_id);
+        }
+
+        for retries_left in (0..CREATE_CARD_LOAD_RETRIES).rev() {
+            match self
+                .repository
+                .load_card(&card_id, list_id.to_owned())
+                .await
+            {
+                Ok(card) => {
+                    log!(
+                        "✅ Loaded newly created card: {} (title: {})",
+                        card.id,
+                        card.title
+                    );
+                    return Ok(self.migrate_new_card_status(list_id, card).await);
+                }
+                Err(e) if retries_left > 0 => {
+                    error!(
+                        "❌ Failed to load newly created card (retries left: {}): {e:?}",
+                        retries_left
+                    );
+                    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+                }
+                Err(e) => {
+                    error!("❌ Failed to load newly created card after all retries: {e:?}");
+                }
+            }
+        }
+
+        log!("🔧 Creating temporary card object for {}", card_id);
+        let card = KanbanCard::new(card_id, title.to_string(), list_id.to_owned());
+        Ok(self.migrate_new_card_status(list_id, card).await)
+    }
+
+    /// 新卡片使用默认状态：列表定义了工作流时迁移到该工作流的状态并保存
+    async fn migrate_new_card_status(&self, list_id: &RoomId, mut card: KanbanCard) -> KanbanCard {
+        let workflow = match self.repository.load_workflow(list_id).await {
+            Ok(Some(workflow)) => workflow,
+            Ok(None) => return card,
+            Err(e) => {
+                error!("❌ Failed to load workflow of list {}: {e:?}", list_id);
+                return card;
+            }
+        };
+        if workflow.status(&card.status).is_none() {
+            card.status = workflow.migrate_status(&card.status);
+            card.touch();
+            if let Err(e) = self.repository.save_card(&card).await {
+                error!("❌ Failed to save status of new card {}: {e:?}", card.id);
+            }
+        }
+        card
+    }
+
+    /// 加载卡片
+    pub async fn load_card(&self, card_id: &RoomId, list_id: OwnedRoomId) -> Result<KanbanCard> {
+        self.repository.load_card(card_id, list_id).await
+    }
+
+    /// 保存卡片元数据
+    pub async fn save_card(&self, card: &KanbanCard) -> Result<()> {
+        self.repository.save_card(card).await
     }
 
-    /// 删除看板
-    pub async fn delete_board(&self, client: &Client, room_id: &RoomId) -> Result<()> {
-        self.board_repository.delete_board(client, room_id).await
+    /// 保存卡片的待办
+    pub async fn save_todos(&self, card_id: &RoomId, todos: &[TodoItem]) -> Result<()> {
+        self.repository.save_todos(card_id, todos).await
     }
 
-    /// 获取所有看板
-    pub async fn get_boards(&self, client: &Client) -> Result<Vec<KanbanBoard>> {
-        self.board_repository.get_boards(client).await
+    /// 把卡片从 `source_list_id` 移动到 `card.space_id`（同一列表时只保存元数据）
+    pub async fn move_card(&self, card: &KanbanCard, source_list_id: &RoomId) -> Result<()> {
+        self.repository.move_card(card, source_list_id).await
     }
 
-    /// 创建列表
-    pub async fn create_list(&self, board_id: &RoomId, name: &str) -> Result<KanbanList> {
-        self.list_repository.create_list(board_id, name).await
+    /// 永久删除卡片
+    pub async fn delete_card(&self, list_id: &RoomId, card_id: &RoomId) -> Result<()> {
+        self.repository.delete_card(list_id, card_id).await
     }
 
-    /// 更新列表
-    pub async fn update_list(
+    /// 为周期卡片创建下一张；已经生成过时新卡片为 `None`
+    pub async fn spawn_next_occurrence(
         &self,
-        board_id: &RoomId,
-        list_id: &str,
-        updates: ListUpdateRequest,
-    ) -> Result<()> {
-        self.list_repository
-            .update_list(board_id, list_id, updates)
-            .await
+        card: &KanbanCard,
+    ) -> Result<(KanbanCard, Option<KanbanCard>)> {
+        self.repository.spawn_next_occurrence(card).await
+    }
+
+    /// 把父卡片的待办转换为子卡片
+    pub async fn create_child_card(
+        &self,
+        parent: &KanbanCard,
+        todo: &TodoItem,
+    ) -> Result<KanbanCard> {
+        self.repository.create_child_card(parent, todo).await
     }
 
-    /// 删除列表
-    pub async fn delete_list(&self, board_id: &RoomId, list_id: &str) -> Result<()> {
-        self.list_repository.delete_list(board_id, list_id).await
+    /// 加载卡片 Room 的成员
+    pub async fn load_members(&self, card_id: &RoomId) -> Result<Vec<CardMember>> {
+        self.repository.load_members(card_id).await
+    }
+
+    /// 把用户设为卡片负责人
+    pub async fn assign_member(&self, card_id: &RoomId, user_id: &UserId) -> Result<()> {
+        self.repository.assign_member(card_id, user_id).await
+    }
+
+    /// 取消用户的卡片负责人身份
+    pub async fn unassign_member(&self, card_id: &RoomId, user_id: &UserId) -> Result<()> {
+        self.repository.unassign_member(card_id, user_id).await
+    }
+
+    /// 加载卡片最近的活动（最新的在前）
+    pub async fn load_activities(
+        &self,
+        card_id: &RoomId,
+        limit: Option<usize>,
+    ) -> Result<Vec<CardActivity>> {
+        self.repository.load_activities(card_id, limit).await
     }
 
-    /// 移动列表
-    pub async fn move_list(
+    /// 加载多张卡片最近的活动（加载失败的卡片会被跳过）
+    pub async fn load_board_activities(
         &self,
-        board_id: &RoomId,
-        list_id: &str,
-        new_position: f64,
+        card_ids: &[OwnedRoomId],
+        limit: usize,
+    ) -> HashMap<OwnedRoomId, Vec<CardActivity>> {
+        let mut activities = HashMap::new();
+        for card_id in card_ids {
+            match self.repository.load_activities(card_id, Some(limit)).await {
+                Ok(card_activities) => {
+                    activities.insert(card_id.clone(), card_activities);
+                }
+                Err(e) => {
+                    error!("❌ Skipping activities of card {}: {e:?}", card_id);
+                }
+            }
+        }
+        activities
+    }
+
+    /// 记录一条活动
+    pub async fn record_activity(
+        &self,
+        card_id: &RoomId,
+        activity_type: ActivityType,
+        text: String,
+        metadata: Option<serde_json::Value>,
     ) -> Result<()> {
-        self.list_repository
-            .move_list(board_id, list_id, new_position)
+        self.repository
+            .record_activity(card_id, activity_type, text, metadata)
             .await
     }
 
-    /// 创建卡片
-    pub async fn create_card(
+    /// 加载卡片在 `since`（Unix timestamp 秒）之后记录的工作时间（加载失败的卡片会被跳过）
+    pub async fn load_work_sessions(
         &self,
-        client: &Client,
-        board_id: &RoomId,
-        list_id: &str,
-        title: &str,
-    ) -> Result<KanbanCard> {
-        self.card_repository
-            .create_card(client, board_id, list_id, title)
-            .await
+        card_ids: &[OwnedRoomId],
+        since: u64,
+    ) -> Vec<WorkSession> {
+        let mut sessions = Vec::new();
+        for card_id in card_ids {
+            match self.repository.load_activities_since(card_id, since).await {
+                Ok(activities) => sessions.extend(work_sessions(card_id, &activities)),
+                Err(e) => {
+                    error!("❌ Skipping work sessions of card {}: {e:?}", card_id);
+                }
+            }
+        }
+        sessions
+    }
+
+    /// 加载卡片的附件
+    pub async fn load_attachments(&self, card_id: &RoomId) -> Result<Vec<CardAttachment>> {
+        self.repository.load_attachments(card_id).await
+    }
+
+    /// 上传本地文件作为卡片附件
+    pub async fn upload_attachment(&self, card_id: &RoomId, path: &Path) -> Result<CardAttachment> {
+        self.repository.upload_attachment(card_id, path).await
+    }
+
+    /// 保存列表的标签库
+    pub async fn save_tags(&self, list_id: &RoomId, tags: Vec<SpaceTag>) -> Result<()> {
+        self.repository.save_tags(list_id, tags).await
     }
 
-    /// 更新卡片
-    pub async fn update_card(
+    /// 向列表的标签库添加标签
+    pub async fn add_tag(&self, list_id: &RoomId, tag: SpaceTag) -> Result<()> {
+        self.repository.add_tag(list_id, tag).await
+    }
+
+    /// 更新列表标签库中的标签
+    pub async fn update_tag(&self, list_id: &RoomId, tag: SpaceTag) -> Result<()> {
+        self.repository.update_tag(list_id, tag).await
+    }
+
+    /// 从列表的标签库删除标签
+    pub async fn delete_tag(&self, list_id: &RoomId, tag_id: &str) -> Result<()> {
+        self.repository.delete_tag(list_id, tag_id).await
+    }
+
+    /// 加载列表的工作流
+    pub async fn load_workflow(&self, list_id: &RoomId) -> Result<Option<Workflow>> {
+        self.repository.load_workflow(list_id).await
+    }
+
+    /// 保存列表的工作流
+    pub async fn save_workflow(&self, list_id: &RoomId, workflow: &Workflow) -> Result<()> {
+        self.repository.save_workflow(list_id, workflow).await
+    }
+
+    /// 把列表中卡片的状态迁移到新工作流，返回被修改的卡片
+    pub async fn migrate_card_statuses(
         &self,
-        client: &Client,
-        board_id: &RoomId,
-        card_id: &str,
-        updates: CardUpdateRequest,
-    ) -> Result<()> {
-        self.card_repository
-            .update_card(client, board_id, card_id, updates)
+        list_id: &RoomId,
+        workflow: &Workflow,
+    ) -> Result<Vec<KanbanCard>> {
+        self.repository
+            .migrate_card_statuses(list_id, workflow)
             .await
     }
 
-    /// 删除卡片
-    pub async fn delete_card(&self, board_id: &RoomId, card_id: &str) -> Result<()> {
-        self.card_repository.delete_card(board_id, card_id).await
+    /// 加载列表的权限设置
+    pub async fn load_permissions(&self, list_id: &RoomId) -> Result<BoardPermissions> {
+        self.repository.load_permissions(list_id).await
     }
 
-    /// 移动卡片
-    pub async fn move_card(
+    /// 保存列表的权限设置，返回保存后服务器上的权限
+    pub async fn save_permissions(
         &self,
-        board_id: &RoomId,
-        card_id: &str,
-        to_list_id: &str,
-        new_position: f64,
-    ) -> Result<()> {
-        self.card_repository
-            .move_card(board_id, card_id, to_list_id, new_position)
+        list_id: &RoomId,
+        permissions: &BoardPermissions,
+    ) -> Result<BoardPermissions> {
+        self.repository
+            .save_permissions(list_id, permissions)
+            .await?;
+        self.repository.load_permissions(list_id).await
+    }
+
+    /// 加载列表的卡片模板
+    pub async fn load_templates(&self, list_id: &RoomId) -> Result<Vec<CardTemplate>> {
+        self.repository.load_templates(list_id).await
+    }
+
+    /// 保存列表的卡片模板
+    pub async fn save_templates(&self, list_id: &RoomId, templates: &[CardTemplate]) -> Result<()> {
+        self.repository.save_templates(list_id, templates).await
+    }
+
+    /// 按模板在列表中创建卡片
+    pub async fn create_card_from_template(
+        &self,
+        list_id: &RoomId,
+        template: &CardTemplate,
+    ) -> Result<KanbanCard> {
+        self.repository
+            .create_card_from_template(list_id, template, None)
             .await
     }
 
-    /// 获取卡片列表
-    pub async fn get_cards(&self, board_id: &RoomId, list_id: &str) -> Result<Vec<KanbanCard>> {
-        self.card_repository.get_cards(board_id, list_id).await
+    /// 加载卡片的历史版本（最早的在前）
+    pub async fn load_card_history(&self, card_id: &RoomId) -> Result<Vec<CardRevision>> {
+        self.repository.load_card_history(card_id).await
+    }
+
+    /// 加载列表标签库的历史版本（最早的在前）
+    pub async fn load_tag_history(&self, list_id: &RoomId) -> Result<Vec<TagLibraryRevision>> {
+        self.repository.load_tag_history(list_id).await
+    }
+
+    /// 导出看板中的列表和卡片
+    pub async fn export_board(
+        &self,
+        name: Option<String>,
+        list_ids: &[OwnedRoomId],
+    ) -> Result<BoardArchive> {
+        import_export::export_board(&self.repository, name, list_ids).await
     }
-}
 
-impl Default for KanbanApi {
-    fn default() -> Self {
-        Self::new()
+    /// 把归档导入为新的看板
+    pub async fn import_board(&self, archive: &BoardArchive) -> Result<ImportSummary> {
+        import_export::import_board(&self.repository, archive).await
     }
 }
@@ -0,0 +1,167 @@
+//! 从聊天创建卡片
+//!
+//! - 任意 Room 的输入框中可以使用 `/card <列表> <标题>` 和 `/todo <标题>` 命令直接创建卡片，
+//!   列表名称包含空格时用引号括起来，例如 `/card "进行中" 修复登录`
+//! - `/todo` 创建到名为「待办」/ `To Do` 的列表，没有时创建到当前看板的第一个列表
+//! - 以 `//card` / `//todo` 开头的消息按普通消息发送，去掉开头的一个斜杠
+//! - 消息右键菜单中的「Create Card from Message」用消息内容和消息链接预填新卡片
+//!
+//! 命令在 UI 线程中解析，列表在 [`KanbanAppState`] 中查找，卡片通过
+//! [`crate::sliding_sync::MatrixRequest::CreateKanbanCard`] 创建。
+
+use crate::kanban::state::kanban_state::{KanbanAppState, KanbanList};
+
+/// `/todo` 优先使用的列表名称（不区分大小写）
+const TODO_LIST_NAMES: &[&str] = &["待办", "todo", "to do", "to-do"];
+
+/// 从消息创建卡片时标题的最大长度（字符）
+const MAX_TITLE_CHARS: usize = 80;
+
+/// 创建卡片的聊天命令
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardCommand {
+    /// `/card <列表> <标题>`
+    Card { list: String, title: String },
+    /// `/todo <标题>`
+    Todo { title: String },
+}
+
+impl CardCommand {
+    pub fn title(&self) -> &str {
+        match self {
+            CardCommand::Card { title, .. } | CardCommand::Todo { title } => title,
+        }
+    }
+}
+
+/// 解析输入框中的文本
+///
+/// 不是卡片命令时返回 `None`（按普通消息发送）；是卡片命令但格式不对时返回用法说明。
+pub fn parse_command(text: &str) -> Option<Result<CardCommand, &'static str>> {
+    let text = text.trim();
+    let (command, args) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+    let args = args.trim();
+    match command {
+        "/card" => Some(parse_card_args(args).ok_or("用法: /card <列表> <标题>")),
+        "/todo" if args.is_empty() => Some(Err("用法: /todo <标题>")),
+        "/todo" => Some(Ok(CardCommand::Todo {
+            title: args.to_string(),
+        })),
+        _ => None,
+    }
+}
+
+/// 转义的命令（`//card` / `//todo`）去掉开头的一个斜杠，其他文本原样返回
+pub fn unescape_command(text: &str) -> &str {
+    let escaped = text.strip_prefix('/').filter(|rest| {
+        let command = rest.split(char::is_whitespace).next().unwrap_or(rest);
+        command == "/card" || command == "/todo"
+    });
+    escaped.unwrap_or(text)
+}
+
+/// `<列表> <标题>`，列表名称可以用引号括起来
+fn parse_card_args(args: &str) -> Option<CardCommand> {
+    let (list, title) = match args.strip_prefix(['"', '“']) {
+        Some(quoted) => quoted.split_once(['"', '”'])?,
+        None => args.split_once(char::is_whitespace)?,
+    };
+    let (list, title) = (list.trim(), title.trim());
+    if list.is_empty() || title.is_empty() {
+        return None;
+    }
+    Some(CardCommand::Card {
+        list: list.to_string(),
+        title: title.to_string(),
+    })
+}
+
+/// 从消息创建卡片时的标题和描述：标题取消息第一行，描述是完整消息和消息链接
+pub fn draft_from_message(body: &str, permalink: Option<&str>) -> (String, String) {
+    let first_line = body
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or("");
+    let mut title: String = first_line.trim().chars().take(MAX_TITLE_CHARS).collect();
+    if first_line.trim().chars().count() > MAX_TITLE_CHARS {
+        title.push('…');
+    }
+
+    let mut description = body.trim().to_string();
+    if let Some(permalink) = permalink {
+        description.push_str(&format!("\n\n来源消息: {}", permalink));
+    }
+    (title, description)
+}
+
+impl KanbanAppState {
+    /// 可以创建卡片的列表：当前看板的列表在前，然后是其他看板中未归档的列表
+    pub fn card_target_lists(&self) -> Vec<&KanbanList> {
+        let mut lists = self.all_lists();
+        let mut others: Vec<&KanbanList> = self
+            .lists
+            .values()
+            .filter(|list| list.archived_at.is_none() && !self.is_in_current_board(&list.id))
+            .collect();
+        others.sort_by(|a, b| a.name.cmp(&b.name));
+        lists.extend(others);
+        lists
+    }
+
+    /// 命令要创建卡片的列表
+    pub fn command_target_list(&self, command: &CardCommand) -> Option<&KanbanList> {
+        match command {
+            CardCommand::Card { list, .. } => self.find_list_by_name(list),
+            CardCommand::Todo { .. } => TODO_LIST_NAMES
+                .iter()
+                .find_map(|name| self.find_list_by_name(name))
+                .or_else(|| self.all_lists().into_iter().next()),
+        }
+    }
+
+    /// 按名称（不区分大小写）查找未归档的列表，同名时优先当前看板中的列表
+    pub fn find_list_by_name(&self, name: &str) -> Option<&KanbanList> {
+        let name = name.to_lowercase();
+        let mut matches: Vec<&KanbanList> = self
+            .lists
+            .values()
+            .filter(|list| list.archived_at.is_none() && list.name.to_lowercase() == name)
+            .collect();
+        matches.sort_by_key(|list| !self.is_in_current_board(&list.id));
+        matches.into_iter().next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_card_commands() {
+        assert_eq!(
+            parse_command("/card \"进行中\" 修复登录"),
+            Some(Ok(CardCommand::Card {
+                list: "进行中".to_string(),
+                title: "修复登录".to_string(),
+            }))
+        );
+        assert_eq!(
+            parse_command("/todo 写周报"),
+            Some(Ok(CardCommand::Todo {
+                title: "写周报".to_string(),
+            }))
+        );
+        assert!(matches!(parse_command("/todo"), Some(Err(_))));
+        assert_eq!(parse_command("普通消息"), None);
+    }
+
+    #[test]
+    fn escaped_commands_are_sent_as_messages() {
+        assert_eq!(parse_command("//todo 写周报"), None);
+        assert_eq!(unescape_command("//todo 写周报"), "/todo 写周报");
+        assert_eq!(unescape_command("//card"), "/card");
+        assert_eq!(unescape_command("//cards 不是命令"), "//cards 不是命令");
+        assert_eq!(unescape_command("// 注释"), "// 注释");
+        assert_eq!(unescape_command("普通消息"), "普通消息");
+    }
+}
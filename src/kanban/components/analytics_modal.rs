@@ -0,0 +1,594 @@
+use makepad_widgets::*;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::kanban::analytics::{BoardReport, DurationStats};
+use crate::kanban::components::card_info_section::parse_hex_color;
+use crate::kanban::state::calendar::today;
+use crate::kanban::state::time_tracking::format_duration;
+
+/// 横条的最大宽度（像素）
+const MAX_BAR_WIDTH: f64 = 420.0;
+
+const BAR_SEGMENTS: &[&[LiveId]] =
+    ids_array!(seg_0, seg_1, seg_2, seg_3, seg_4, seg_5, seg_6, seg_7);
+const LEGEND_ITEMS: &[&[LiveId]] = ids_array!(
+    legend_0, legend_1, legend_2, legend_3, legend_4, legend_5, legend_6, legend_7
+);
+
+/// 单色横条（吞吐量、时间分布）的颜色
+fn bar_color() -> Vec4 {
+    vec4(0.0, 0.475, 0.749, 1.0)
+}
+
+live_design! {
+    use link::theme::*;
+    use link::widgets::*;
+
+    BarSegment = <View> {
+        width: 0,
+        height: Fill,
+        visible: false,
+        show_bg: true,
+        draw_bg: {
+            color: #0079BF
+        }
+    }
+
+    LegendItem = <View> {
+        width: Fit,
+        height: Fit,
+        flow: Right,
+        spacing: 4,
+        align: {y: 0.5},
+        visible: false,
+
+        swatch = <View> {
+            width: 12,
+            height: 12,
+            show_bg: true,
+            draw_bg: {
+                color: #0079BF
+            }
+        }
+
+        legend_label = <Label> {
+            width: Fit,
+            height: Fit,
+            text: "",
+            draw_text: {
+                color: #5E6C84,
+                text_style: <THEME_FONT_REGULAR>{font_size: 11}
+            }
+        }
+    }
+
+    AnalyticsHeading = <Label> {
+        width: Fill,
+        height: Fit,
+        margin: {top: 10},
+        text: "",
+        draw_text: {
+            color: #172B4D,
+            text_style: <THEME_FONT_BOLD>{font_size: 14}
+        }
+    }
+
+    AnalyticsText = <Label> {
+        width: Fill,
+        height: Fit,
+        text: "",
+        draw_text: {
+            wrap: Word,
+            color: #5E6C84,
+            text_style: <THEME_FONT_REGULAR>{font_size: 12}
+        }
+    }
+
+    // 一根（可堆叠的）横条：左侧标签，中间各段，右侧数值
+    AnalyticsBarRow = <View> {
+        width: Fill,
+        height: 18,
+        flow: Right,
+        spacing: 8,
+        align: {y: 0.5},
+
+        bar_label = <Label> {
+            width: 80,
+            height: Fit,
+            text: "",
+            draw_text: {
+                color: #5E6C84,
+                text_style: <THEME_FONT_REGULAR>{font_size: 11}
+            }
+        }
+
+        <View> {
+            width: 420,
+            height: 14,
+            flow: Right,
+
+            seg_0 = <BarSegment> {}
+            seg_1 = <BarSegment> {}
+            seg_2 = <BarSegment> {}
+            seg_3 = <BarSegment> {}
+            seg_4 = <BarSegment> {}
+            seg_5 = <BarSegment> {}
+            seg_6 = <BarSegment> {}
+            seg_7 = <BarSegment> {}
+        }
+
+        value_label = <Label> {
+            width: Fit,
+            height: Fit,
+            text: "",
+            draw_text: {
+                color: #172B4D,
+                text_style: <THEME_FONT_REGULAR>{font_size: 11}
+            }
+        }
+    }
+
+    TimesheetDateInput = <TextInput> {
+        width: 120,
+        height: 32,
+        empty_text: "YYYY-MM-DD",
+        draw_text: {
+            color: #172B4D,
+            text_style: <THEME_FONT_REGULAR>{font_size: 13}
+        }
+        draw_bg: {
+            color: #FFFFFF,
+            border_color: #DFE1E6,
+            border_width: 2.0,
+            radius: 3.0,
+        }
+        draw_cursor: {
+            color: #172B4D
+        }
+    }
+
+    AnalyticsLegend = <View> {
+        width: Fill,
+        height: Fit,
+        flow: Right,
+        spacing: 12,
+
+        legend_0 = <LegendItem> {}
+        legend_1 = <LegendItem> {}
+        legend_2 = <LegendItem> {}
+        legend_3 = <LegendItem> {}
+        legend_4 = <LegendItem> {}
+        legend_5 = <LegendItem> {}
+        legend_6 = <LegendItem> {}
+        legend_7 = <LegendItem> {}
+    }
+
+    pub AnalyticsModal = {{AnalyticsModal}} {
+        width: Fit,
+        height: Fit,
+
+        <RoundedView> {
+            width: 640,
+            height: Fit,
+            padding: 20,
+            flow: Down,
+            spacing: 12,
+            draw_bg: {
+                color: #FFFFFF
+            }
+
+            <View> {
+                width: Fill,
+                height: Fit,
+                flow: Right,
+                spacing: 10,
+                align: {y: 0.5},
+
+                <Label> {
+                    width: Fill,
+                    height: Fit,
+                    text: "看板统计",
+                    draw_text: {
+                        text_style: <THEME_FONT_BOLD>{font_size: 18}
+                        color: #172B4D
+                    }
+                }
+
+                // 切换累积流图显示的列表
+                list_button = <Button> {
+                    width: 160,
+                    height: 32,
+                    text: "列表",
+                }
+            }
+
+            loading_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "正在加载卡片活动记录……",
+                draw_text: {
+                    color: #5E6C84,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 13}
+                }
+            }
+
+            chart_lines = <PortalList> {
+                width: Fill,
+                height: 480,
+                flow: Down,
+                spacing: 4,
+
+                Heading = <AnalyticsHeading> {}
+                Text = <AnalyticsText> {}
+                Bar = <AnalyticsBarRow> {}
+                Legend = <AnalyticsLegend> {}
+            }
+
+            // 工时表：导出日期范围内（含首尾）的工作记录
+            <View> {
+                width: Fill,
+                height: Fit,
+                flow: Right,
+                spacing: 8,
+                align: {y: 0.5}
+
+                <Label> {
+                    width: Fit,
+                    height: Fit,
+                    text: "工时表",
+                    draw_text: {
+                        color: #172B4D,
+                        text_style: <THEME_FONT_BOLD>{font_size: 13}
+                    }
+                }
+
+                timesheet_from_input = <TimesheetDateInput> {}
+
+                <Label> {
+                    width: Fit,
+                    height: Fit,
+                    text: "至",
+                    draw_text: {
+                        color: #5E6C84,
+                        text_style: <THEME_FONT_REGULAR>{font_size: 13}
+                    }
+                }
+
+                timesheet_to_input = <TimesheetDateInput> {}
+
+                export_timesheet_button = <Button> {
+                    width: 110,
+                    height: 32,
+                    text: "导出工时表",
+                }
+            }
+
+            timesheet_error_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "",
+                draw_text: {
+                    color: #EB5A46,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
+            }
+
+            // 按钮区域
+            <View> {
+                width: Fill,
+                height: Fit,
+                flow: Right,
+                spacing: 10,
+                align: {x: 1.0, y: 0.5}
+
+                refresh_button = <Button> {
+                    width: 80,
+                    height: 36,
+                    text: "刷新",
+                }
+
+                export_csv_button = <Button> {
+                    width: 100,
+                    height: 36,
+                    text: "导出 CSV",
+                }
+
+                close_button = <Button> {
+                    width: 80,
+                    height: 36,
+                    text: "关闭",
+                    draw_bg: {
+                        color: #95A5A6,
+                        radius: 3.0,
+                    }
+                    draw_text: {
+                        color: #FFFFFF,
+                        text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 报表中的一行
+enum ChartLine {
+    Heading(String),
+    Text(String),
+    /// (标签, 各段的 (数量, 颜色), 数值文本)
+    Bar(String, Vec<(usize, Vec4)>, String),
+    /// (名称, 颜色)
+    Legend(Vec<(String, Vec4)>),
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct AnalyticsModal {
+    #[deref]
+    view: View,
+    /// 累积流图显示的列表序号
+    #[rust]
+    list_index: usize,
+    #[rust]
+    list_count: usize,
+    /// 工时表的日期范围是否已填入默认值（本月 1 日到今天）
+    #[rust]
+    timesheet_range_set: bool,
+}
+
+impl Widget for AnalyticsModal {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if let Event::Actions(actions) = event {
+            if self.view.button(ids!(list_button)).clicked(actions) && self.list_count > 0 {
+                self.list_index = (self.list_index + 1) % self.list_count;
+                self.view.redraw(cx);
+            }
+
+            if self.view.button(ids!(refresh_button)).clicked(actions) {
+                cx.action(crate::kanban::KanbanActions::ShowAnalytics);
+            }
+
+            if self.view.button(ids!(export_csv_button)).clicked(actions) {
+                cx.action(crate::kanban::KanbanActions::ExportAnalyticsCsv);
+            }
+
+            if self
+                .view
+                .button(ids!(export_timesheet_button))
+                .clicked(actions)
+            {
+                let parse = |text: String| NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d").ok();
+                let from = parse(self.view.text_input(ids!(timesheet_from_input)).text());
+                let to = parse(self.view.text_input(ids!(timesheet_to_input)).text());
+                let error = match (from, to) {
+                    (Some(from), Some(to)) if from <= to => {
+                        cx.action(crate::kanban::KanbanActions::ExportTimesheet { from, to });
+                        ""
+                    }
+                    (Some(_), Some(_)) => "开始日期不能晚于结束日期",
+                    _ => "请输入日期，格式: YYYY-MM-DD",
+                };
+                self.view
+                    .label(ids!(timesheet_error_label))
+                    .set_text(cx, error);
+                self.view.redraw(cx);
+            }
+
+            if self.view.button(ids!(close_button)).clicked(actions) {
+                cx.action(crate::kanban::KanbanActions::CloseAnalytics);
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        let report = scope
+            .data
+            .get::<crate::app::AppState>()
+            .and_then(|app_state| app_state.kanban_state.board_report.clone());
+
+        self.view
+            .label(ids!(loading_label))
+            .set_visible(cx, report.is_none());
+
+        if !self.timesheet_range_set {
+            self.timesheet_range_set = true;
+            let to = today();
+            let from = to.with_day(1).unwrap_or(to);
+            self.view
+                .text_input(ids!(timesheet_from_input))
+                .set_text(cx, &from.format("%Y-%m-%d").to_string());
+            self.view
+                .text_input(ids!(timesheet_to_input))
+                .set_text(cx, &to.format("%Y-%m-%d").to_string());
+        }
+
+        let lines = match &report {
+            Some(report) => {
+                self.list_count = report.flows.len();
+                if self.list_index >= self.list_count {
+                    self.list_index = 0;
+                }
+                let list_name = report
+                    .flows
+                    .get(self.list_index)
+                    .map_or("无列表", |flow| flow.name.as_str());
+                self.view
+                    .button(ids!(list_button))
+                    .set_text(cx, &format!("列表：{}", list_name));
+                chart_lines(report, self.list_index)
+            }
+            None => Vec::new(),
+        };
+
+        let scale = bar_scale(&lines);
+
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                list.set_item_range(cx, 0, lines.len());
+
+                while let Some(idx) = list.next_visible_item(cx) {
+                    let Some(line) = lines.get(idx) else {
+                        continue;
+                    };
+
+                    let item_widget = match line {
+                        ChartLine::Heading(text) => {
+                            let item = list.item(cx, idx, live_id!(Heading));
+                            item.as_label().set_text(cx, text);
+                            item
+                        }
+                        ChartLine::Text(text) => {
+                            let item = list.item(cx, idx, live_id!(Text));
+                            item.as_label().set_text(cx, text);
+                            item
+                        }
+                        ChartLine::Bar(label, segments, value) => {
+                            let item = list.item(cx, idx, live_id!(Bar));
+                            item.label(ids!(bar_label)).set_text(cx, label);
+                            item.label(ids!(value_label)).set_text(cx, value);
+                            for (i, seg_id) in BAR_SEGMENTS.iter().enumerate() {
+                                let seg = item.view(*seg_id);
+                                match segments.get(i).filter(|(count, _)| *count > 0) {
+                                    Some((count, color)) => {
+                                        let width = *count as f64 * scale;
+                                        let color = *color;
+                                        seg.apply_over(
+                                            cx,
+                                            live! { width: (width), draw_bg: { color: (color) } },
+                                        );
+                                        seg.set_visible(cx, true);
+                                    }
+                                    None => seg.set_visible(cx, false),
+                                }
+                            }
+                            item
+                        }
+                        ChartLine::Legend(entries) => {
+                            let item = list.item(cx, idx, live_id!(Legend));
+                            for (i, legend_id) in LEGEND_ITEMS.iter().enumerate() {
+                                let legend = item.view(*legend_id);
+                                match entries.get(i) {
+                                    Some((name, color)) => {
+                                        let color = *color;
+                                        legend.label(ids!(legend_label)).set_text(cx, name);
+                                        legend
+                                            .view(ids!(swatch))
+                                            .apply_over(cx, live! { draw_bg: { color: (color) } });
+                                        legend.set_visible(cx, true);
+                                    }
+                                    None => legend.set_visible(cx, false),
+                                }
+                            }
+                            item
+                        }
+                    };
+                    item_widget.draw_all(cx, &mut Scope::empty());
+                }
+            }
+        }
+        DrawStep::done()
+    }
+}
+
+/// 把报表转换为图表中的各行
+fn chart_lines(report: &BoardReport, list_index: usize) -> Vec<ChartLine> {
+    let mut lines = Vec::new();
+
+    // 累积流图
+    if let Some(flow) = report.flows.get(list_index) {
+        lines.push(ChartLine::Heading(format!("累积流 · {}", flow.name)));
+        let colors: Vec<Vec4> = flow
+            .statuses
+            .iter()
+            .map(|(_, _, color)| parse_hex_color(color).unwrap_or_else(bar_color))
+            .collect();
+        lines.push(ChartLine::Legend(
+            flow.statuses
+                .iter()
+                .zip(&colors)
+                .map(|((_, name, _), color)| (name.clone(), *color))
+                .collect(),
+        ));
+        for (date, counts) in &flow.days {
+            let total: usize = counts.iter().sum();
+            lines.push(ChartLine::Bar(
+                date.format("%m-%d").to_string(),
+                counts.iter().copied().zip(colors.iter().copied()).collect(),
+                total.to_string(),
+            ));
+        }
+    }
+
+    // 前置时间 / 周期时间
+    for (title, stats) in [
+        ("前置时间（创建 → 完成）", report.lead_time_stats()),
+        ("周期时间（开始 → 完成）", report.cycle_time_stats()),
+    ] {
+        lines.push(ChartLine::Heading(title.to_string()));
+        lines.push(ChartLine::Text(stats.summary()));
+        push_histogram(&mut lines, &stats);
+    }
+
+    // 每周吞吐量
+    lines.push(ChartLine::Heading("每周吞吐量（完成的卡片）".to_string()));
+    for (week, completed) in &report.throughput {
+        lines.push(ChartLine::Bar(
+            week.format("%m-%d").to_string(),
+            vec![(*completed, bar_color())],
+            completed.to_string(),
+        ));
+    }
+
+    // 工时记录
+    lines.push(ChartLine::Heading("工时记录".to_string()));
+    let logged_secs: u64 = report
+        .timesheet
+        .iter()
+        .map(|entry| entry.session.duration_secs())
+        .sum();
+    lines.push(ChartLine::Text(if report.timesheet.is_empty() {
+        "暂无数据".to_string()
+    } else {
+        format!(
+            "{} 段 · 合计 {}",
+            report.timesheet.len(),
+            format_duration(logged_secs)
+        )
+    }));
+
+    lines
+}
+
+fn push_histogram(lines: &mut Vec<ChartLine>, stats: &DurationStats) {
+    if stats.count == 0 {
+        return;
+    }
+    for (label, count) in &stats.histogram {
+        lines.push(ChartLine::Bar(
+            label.to_string(),
+            vec![(*count, bar_color())],
+            count.to_string(),
+        ));
+    }
+}
+
+/// 每个单位对应的像素宽度：所有横条中最长的一根占满 [`MAX_BAR_WIDTH`]
+fn bar_scale(lines: &[ChartLine]) -> f64 {
+    let max_total = lines
+        .iter()
+        .filter_map(|line| match line {
+            ChartLine::Bar(_, segments, _) => {
+                Some(segments.iter().map(|(count, _)| count).sum::<usize>())
+            }
+            _ => None,
+        })
+        .max()
+        .unwrap_or(0);
+    if max_total == 0 {
+        0.0
+    } else {
+        MAX_BAR_WIDTH / max_total as f64
+    }
+}
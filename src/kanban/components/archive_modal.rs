@@ -0,0 +1,296 @@
+use makepad_widgets::*;
+
+use crate::kanban::state::archived::ArchivedItem;
+
+live_design! {
+    use link::theme::*;
+    use link::widgets::*;
+
+    // 单个已归档的卡片或列表
+    ArchivedItemRow = {{ArchivedItemRow}} {
+        width: Fill,
+        height: Fit,
+        flow: Right,
+        spacing: 10,
+        align: {y: 0.5},
+        padding: {top: 6, bottom: 6, left: 8, right: 8},
+        show_bg: true,
+        draw_bg: {
+            color: #F4F5F7
+        }
+
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Down,
+            spacing: 3,
+
+            item_name_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "",
+                draw_text: {
+                    wrap: Ellipsis,
+                    color: #172B4D,
+                    text_style: <THEME_FONT_BOLD>{font_size: 14}
+                }
+            }
+
+            item_summary_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "",
+                draw_text: {
+                    wrap: Word,
+                    color: #5E6C84,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
+            }
+        }
+
+        restore_button = <Button> {
+            width: 60,
+            height: 28,
+            text: "恢复",
+        }
+
+        delete_button = <Button> {
+            width: 80,
+            height: 28,
+            text: "永久删除",
+        }
+    }
+
+    pub ArchiveModal = {{ArchiveModal}} {
+        width: Fit,
+        height: Fit,
+
+        <RoundedView> {
+            width: 520,
+            height: Fit,
+            padding: 20,
+            flow: Down,
+            spacing: 15,
+            draw_bg: {
+                color: #FFFFFF
+            }
+
+            // 标题
+            <Label> {
+                width: Fill,
+                height: Fit,
+                text: "已归档",
+                draw_text: {
+                    text_style: <THEME_FONT_BOLD>{font_size: 18}
+                    color: #172B4D
+                }
+            }
+
+            empty_label = <Label> {
+                width: Fill,
+                height: Fit,
+                visible: false,
+                text: "没有已归档的卡片或列表",
+                draw_text: {
+                    wrap: Word,
+                    color: #5E6C84,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 13}
+                }
+            }
+
+            archived_list = <PortalList> {
+                width: Fill,
+                height: 360,
+                flow: Down,
+                spacing: 5,
+
+                ArchivedItemRow = <ArchivedItemRow> {}
+            }
+
+            // 按钮区域
+            <View> {
+                width: Fill,
+                height: Fit,
+                flow: Right,
+                align: {x: 1.0, y: 0.5}
+
+                close_button = <Button> {
+                    width: 80,
+                    height: 36,
+                    text: "关闭",
+                    draw_bg: {
+                        color: #95A5A6,
+                        radius: 3.0,
+                    }
+                    draw_text: {
+                        color: #FFFFFF,
+                        text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct ArchivedItemRow {
+    #[deref]
+    view: View,
+    #[rust]
+    item: Option<ArchivedItem>,
+    /// 已点击过一次"永久删除"，再次点击才真正删除
+    #[rust]
+    confirm_delete: bool,
+}
+
+impl Widget for ArchivedItemRow {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if let Event::Actions(actions) = event {
+            let Some(item) = self.item.clone() else {
+                return;
+            };
+
+            if self.view.button(ids!(restore_button)).clicked(actions) {
+                log!("ArchivedItemRow: 恢复 {}", item.id());
+                match item {
+                    ArchivedItem::List { id, .. } => {
+                        cx.action(crate::kanban::KanbanActions::RestoreList { list_id: id });
+                    }
+                    ArchivedItem::Card { id, .. } => {
+                        cx.action(crate::kanban::KanbanActions::RestoreCard { card_id: id });
+                    }
+                }
+                return;
+            }
+
+            if self.view.button(ids!(delete_button)).clicked(actions) {
+                if !self.confirm_delete {
+                    self.confirm_delete = true;
+                    self.view
+                        .button(ids!(delete_button))
+                        .set_text(cx, "确认删除");
+                    self.view.redraw(cx);
+                    return;
+                }
+
+                log!("ArchivedItemRow: 永久删除 {}", item.id());
+                self.confirm_delete = false;
+                match item {
+                    ArchivedItem::List { id, .. } => {
+                        cx.action(crate::kanban::KanbanActions::DeleteList { list_id: id });
+                    }
+                    ArchivedItem::Card { id, .. } => {
+                        cx.action(crate::kanban::KanbanActions::DeleteCard { card_id: id });
+                    }
+                }
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+impl ArchivedItemRow {
+    /// 设置显示的条目（换成其他条目时取消待确认的删除）
+    fn set_item(&mut self, cx: &mut Cx, item: ArchivedItem) {
+        if self.item.as_ref().map(ArchivedItem::id) != Some(item.id()) {
+            self.confirm_delete = false;
+        }
+
+        let (name, summary) = match &item {
+            ArchivedItem::List {
+                name,
+                card_count,
+                archived_at,
+                ..
+            } => (
+                format!("📋 {}", name),
+                format!(
+                    "列表 · {} 张卡片 · 归档于 {}",
+                    card_count,
+                    crate::kanban::reminders::format_due_time(*archived_at)
+                ),
+            ),
+            ArchivedItem::Card {
+                title,
+                list_name,
+                archived_at,
+                ..
+            } => (
+                title.clone(),
+                format!(
+                    "卡片 · 原列表「{}」 · 归档于 {}",
+                    list_name,
+                    crate::kanban::reminders::format_due_time(*archived_at)
+                ),
+            ),
+        };
+        self.view.label(ids!(item_name_label)).set_text(cx, &name);
+        self.view
+            .label(ids!(item_summary_label))
+            .set_text(cx, &summary);
+        self.view.button(ids!(delete_button)).set_text(
+            cx,
+            if self.confirm_delete {
+                "确认删除"
+            } else {
+                "永久删除"
+            },
+        );
+        self.item = Some(item);
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct ArchiveModal {
+    #[deref]
+    view: View,
+}
+
+impl Widget for ArchiveModal {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if let Event::Actions(actions) = event {
+            if self.view.button(ids!(close_button)).clicked(actions) {
+                cx.action(crate::kanban::KanbanActions::CloseArchive);
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        let items = scope
+            .data
+            .get::<crate::app::AppState>()
+            .map(|app_state| app_state.kanban_state.archived_items())
+            .unwrap_or_default();
+
+        self.view
+            .label(ids!(empty_label))
+            .set_visible(cx, items.is_empty());
+
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                list.set_item_range(cx, 0, items.len());
+
+                while let Some(idx) = list.next_visible_item(cx) {
+                    let Some(archived) = items.get(idx) else {
+                        continue;
+                    };
+
+                    let item_widget = list.item(cx, idx, live_id!(ArchivedItemRow));
+                    if let Some(mut row) = item_widget.as_archived_item_row().borrow_mut() {
+                        row.set_item(cx, archived.clone());
+                    }
+
+                    item_widget.draw_all(cx, &mut Scope::empty());
+                }
+            }
+        }
+        DrawStep::done()
+    }
+}
@@ -0,0 +1,237 @@
+use makepad_widgets::*;
+
+use crate::shared::avatar::AvatarWidgetRefExt;
+
+live_design! {
+    use link::theme::*;
+    use link::widgets::*;
+    use crate::shared::avatar::Avatar;
+
+    // 单个卡片成员
+    MemberItem = {{MemberItem}} {
+        width: Fill,
+        height: Fit,
+        flow: Right,
+        spacing: 10,
+        align: {y: 0.5},
+        padding: {top: 5, bottom: 5, left: 5, right: 5},
+
+        member_avatar = <Avatar> {
+            width: 24.,
+            height: 24.,
+            text_view = { text = { draw_text: {
+                text_style: { font_size: 8.0 }
+            }}}
+        }
+
+        // 成员名称
+        member_name = <Label> {
+            width: Fill,
+            height: Fit,
+            text: "成员",
+            draw_text: {
+                color: #172B4D,
+                text_style: <THEME_FONT_REGULAR>{font_size: 14}
+            }
+        }
+
+        // 分配 / 取消分配按钮
+        assign_btn = <Button> {
+            width: 70,
+            height: 25,
+            text: "分配",
+            draw_bg: {
+                color: #0079BF,
+            }
+            draw_text: {
+                color: #FFFFFF,
+                text_style: <THEME_FONT_REGULAR>{font_size: 12}
+            }
+        }
+    }
+
+    // 负责人管理区域
+    pub AssigneeSection = {{AssigneeSection}} {
+        width: Fill,
+        height: Fit,
+        flow: Down,
+        spacing: 10,
+
+        // 标题栏
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 10,
+            align: {y: 0.5},
+
+            <Label> {
+                text: "负责人",
+                draw_text: {
+                    text_style: <THEME_FONT_BOLD>{font_size: 14}
+                    color: #5E6C84
+                }
+            }
+
+            <View> { width: Fill, height: Fit }
+
+            assignee_count_label = <Label> {
+                text: "未分配",
+                draw_text: {
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                    color: #5E6C84
+                }
+            }
+        }
+
+        // 成员列表（使用PortalList）
+        member_list = <PortalList> {
+            width: Fill,
+            height: 120,
+            flow: Down,
+            spacing: 5,
+
+            MemberItem = <MemberItem> {}
+        }
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct MemberItem {
+    #[deref]
+    view: View,
+    #[rust]
+    user_id: Option<matrix_sdk::ruma::OwnedUserId>,
+    #[rust]
+    card_id: Option<matrix_sdk::ruma::OwnedRoomId>,
+    #[rust]
+    is_assigned: bool,
+}
+
+impl Widget for MemberItem {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if let Event::Actions(actions) = event {
+            if self.view.button(ids!(assign_btn)).clicked(actions) {
+                if let (Some(card_id), Some(user_id)) = (&self.card_id, &self.user_id) {
+                    if self.is_assigned {
+                        log!("MemberItem: 取消分配 {}", user_id);
+                        cx.action(crate::kanban::KanbanActions::UnassignCard {
+                            card_id: card_id.clone(),
+                            user_id: user_id.clone(),
+                        });
+                    } else {
+                        log!("MemberItem: 分配给 {}", user_id);
+                        cx.action(crate::kanban::KanbanActions::AssignCard {
+                            card_id: card_id.clone(),
+                            user_id: user_id.clone(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct AssigneeSection {
+    #[deref]
+    view: View,
+    #[rust]
+    card_id: Option<matrix_sdk::ruma::OwnedRoomId>,
+}
+
+impl Widget for AssigneeSection {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        // 从 AppState 获取成员和当前负责人
+        let (members, assignees) = if let Some(app_state) = scope.data.get::<crate::app::AppState>()
+        {
+            if let Some(selected_card_id) = &app_state.kanban_state.selected_card_id {
+                self.card_id = Some(selected_card_id.clone());
+
+                let assignees = app_state
+                    .kanban_state
+                    .cards
+                    .get(selected_card_id)
+                    .map(|card| card.assignees.clone())
+                    .unwrap_or_default();
+                let members = app_state
+                    .kanban_state
+                    .card_members
+                    .get(selected_card_id)
+                    .cloned()
+                    .unwrap_or_default();
+                (members, assignees)
+            } else {
+                (Vec::new(), Vec::new())
+            }
+        } else {
+            (Vec::new(), Vec::new())
+        };
+
+        let count_text = if assignees.is_empty() {
+            "未分配".to_string()
+        } else {
+            format!("{} 人", assignees.len())
+        };
+        self.view
+            .label(ids!(assignee_count_label))
+            .set_text(cx, &count_text);
+
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                list.set_item_range(cx, 0, members.len());
+
+                while let Some(member_idx) = list.next_visible_item(cx) {
+                    if member_idx >= members.len() {
+                        continue;
+                    }
+
+                    let member_item_widget = list.item(cx, member_idx, live_id!(MemberItem));
+                    let member = &members[member_idx];
+                    let is_assigned = assignees.contains(&member.user_id);
+
+                    member_item_widget
+                        .label(ids!(member_name))
+                        .set_text(cx, &member.name());
+                    member_item_widget
+                        .button(ids!(assign_btn))
+                        .set_text(cx, if is_assigned { "取消分配" } else { "分配" });
+
+                    if let Some(card_id) = &self.card_id {
+                        member_item_widget
+                            .avatar(ids!(member_avatar))
+                            .set_avatar_and_get_username(
+                                cx,
+                                card_id,
+                                &member.user_id,
+                                None,
+                                None,
+                                false,
+                            );
+                    }
+
+                    // 传递成员信息给 MemberItem
+                    let member_item_ref = member_item_widget.as_member_item();
+                    if let Some(mut member_item) = member_item_ref.borrow_mut() {
+                        member_item.user_id = Some(member.user_id.clone());
+                        member_item.card_id = self.card_id.clone();
+                        member_item.is_assigned = is_assigned;
+                    }
+
+                    member_item_widget.draw_all(cx, &mut Scope::empty());
+                }
+            }
+        }
+        DrawStep::done()
+    }
+}
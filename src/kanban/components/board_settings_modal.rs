@@ -0,0 +1,441 @@
+use makepad_widgets::*;
+use matrix_sdk::ruma::{OwnedRoomId, OwnedUserId};
+
+use crate::kanban::state::permissions::{BoardPermissions, BoardRole, KanbanPermission};
+
+/// 每种操作一行（与 `KanbanPermission::ALL` 顺序一致）
+const PERMISSION_ROWS: &[&[LiveId]] = ids_array!(perm_0, perm_1, perm_2, perm_3, perm_4);
+
+live_design! {
+    use link::theme::*;
+    use link::widgets::*;
+
+    // 一种操作及其要求的角色
+    PermissionRow = <View> {
+        width: Fill,
+        height: Fit,
+        flow: Right,
+        spacing: 10,
+        align: {y: 0.5},
+
+        permission_label = <Label> {
+            width: Fill,
+            height: Fit,
+            text: "",
+            draw_text: {
+                color: #172B4D,
+                text_style: <THEME_FONT_REGULAR>{font_size: 14}
+            }
+        }
+
+        role_button = <Button> {
+            width: 90,
+            height: 28,
+            text: "成员",
+        }
+    }
+
+    // 单独设置了角色的用户
+    BoardUserItem = {{BoardUserItem}} {
+        width: Fill,
+        height: Fit,
+        flow: Right,
+        spacing: 10,
+        align: {y: 0.5},
+        padding: {top: 4, bottom: 4, left: 8, right: 8},
+        show_bg: true,
+        draw_bg: {
+            color: #F4F5F7
+        }
+
+        user_label = <Label> {
+            width: Fill,
+            height: Fit,
+            text: "",
+            draw_text: {
+                wrap: Ellipsis,
+                color: #172B4D,
+                text_style: <THEME_FONT_REGULAR>{font_size: 13}
+            }
+        }
+
+        user_role_button = <Button> {
+            width: 90,
+            height: 28,
+            text: "成员",
+        }
+    }
+
+    pub BoardSettingsModal = {{BoardSettingsModal}} {
+        width: Fit,
+        height: Fit,
+
+        <RoundedView> {
+            width: 520,
+            height: Fit,
+            padding: 20,
+            flow: Down,
+            spacing: 15,
+            draw_bg: {
+                color: #FFFFFF
+            }
+
+            // 标题
+            <Label> {
+                width: Fill,
+                height: Fit,
+                text: "看板设置",
+                draw_text: {
+                    text_style: <THEME_FONT_BOLD>{font_size: 18}
+                    color: #172B4D
+                }
+            }
+
+            // 当前用户的角色
+            level_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "",
+                draw_text: {
+                    wrap: Word,
+                    color: #5E6C84,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
+            }
+
+            // 每种操作要求的角色
+            <Label> {
+                width: Fill,
+                height: Fit,
+                text: "操作权限（点击切换所需角色）",
+                draw_text: {
+                    text_style: <THEME_FONT_BOLD>{font_size: 14}
+                    color: #172B4D
+                }
+            }
+
+            perm_0 = <PermissionRow> {}
+            perm_1 = <PermissionRow> {}
+            perm_2 = <PermissionRow> {}
+            perm_3 = <PermissionRow> {}
+            perm_4 = <PermissionRow> {}
+
+            // 用户角色
+            <Label> {
+                width: Fill,
+                height: Fit,
+                text: "成员角色",
+                draw_text: {
+                    text_style: <THEME_FONT_BOLD>{font_size: 14}
+                    color: #172B4D
+                }
+            }
+
+            users_list = <PortalList> {
+                width: Fill,
+                height: 160,
+                flow: Down,
+                spacing: 5,
+
+                BoardUserItem = <BoardUserItem> {}
+            }
+
+            add_user_row = <View> {
+                width: Fill,
+                height: Fit,
+                flow: Right,
+                spacing: 10,
+                align: {y: 0.5},
+
+                add_user_input = <TextInput> {
+                    width: Fill,
+                    height: 32,
+                    empty_text: "@user:server",
+                    draw_text: {
+                        color: #172B4D,
+                        text_style: <THEME_FONT_REGULAR>{font_size: 13}
+                    }
+                }
+
+                add_user_button = <Button> {
+                    width: 60,
+                    height: 32,
+                    text: "添加",
+                }
+            }
+
+            // 错误提示
+            error_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "",
+                draw_text: {
+                    wrap: Word,
+                    color: #EB5A46,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
+            }
+
+            // 按钮区域
+            <View> {
+                width: Fill,
+                height: Fit,
+                flow: Right,
+                spacing: 10,
+                align: {x: 1.0, y: 0.5}
+
+                cancel_button = <Button> {
+                    width: 80,
+                    height: 36,
+                    text: "取消",
+                    draw_bg: {
+                        color: #95A5A6,
+                        radius: 3.0,
+                    }
+                    draw_text: {
+                        color: #FFFFFF,
+                        text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                    }
+                }
+
+                save_button = <Button> {
+                    width: 80,
+                    height: 36,
+                    text: "保存",
+                    draw_bg: {
+                        color: #0079BF,
+                        radius: 3.0,
+                    }
+                    draw_text: {
+                        color: #FFFFFF,
+                        text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 用户条目发给设置面板的 Action
+#[derive(Clone, Debug, DefaultNone)]
+pub enum BoardSettingsAction {
+    /// 切换用户的角色
+    CycleUserRole(OwnedUserId),
+    None,
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct BoardUserItem {
+    #[deref]
+    view: View,
+    #[rust]
+    user_id: Option<OwnedUserId>,
+}
+
+impl Widget for BoardUserItem {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if let Event::Actions(actions) = event {
+            if self.view.button(ids!(user_role_button)).clicked(actions) {
+                if let Some(user_id) = self.user_id.clone() {
+                    cx.action(BoardSettingsAction::CycleUserRole(user_id));
+                }
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct BoardSettingsModal {
+    #[deref]
+    view: View,
+    #[rust]
+    space_id: Option<OwnedRoomId>,
+    /// 正在编辑的权限设置（保存前不生效）
+    #[rust]
+    draft: Option<BoardPermissions>,
+}
+
+impl Widget for BoardSettingsModal {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        let Event::Actions(actions) = event else {
+            return;
+        };
+
+        if self.view.button(ids!(cancel_button)).clicked(actions) {
+            log!("BoardSettingsModal: 取消编辑");
+            cx.action(crate::kanban::KanbanActions::CloseBoardSettings);
+            return;
+        }
+
+        let Some(draft) = self.draft.as_mut() else {
+            return;
+        };
+        if !draft.can_edit {
+            return;
+        }
+        let mut changed = false;
+
+        // 切换操作所需的角色
+        for (row, permission) in PERMISSION_ROWS.iter().zip(KanbanPermission::ALL) {
+            if self
+                .view
+                .view(*row)
+                .button(ids!(role_button))
+                .clicked(actions)
+            {
+                let role = draft.required_role(permission).next();
+                log!(
+                    "BoardSettingsModal: 「{}」需要 {}",
+                    permission.display_name(),
+                    role.display_name()
+                );
+                draft.required.insert(permission, role.level());
+                changed = true;
+            }
+        }
+
+        // 切换用户的角色
+        for action in actions {
+            if let Some(BoardSettingsAction::CycleUserRole(user_id)) = action.downcast_ref() {
+                if let Some((_, level)) = draft.users.iter_mut().find(|(id, _)| id == user_id) {
+                    *level = BoardRole::from_level(*level).next().level();
+                    changed = true;
+                }
+            }
+        }
+
+        // 添加用户（默认协管员）
+        let add_user = self.view.button(ids!(add_user_button)).clicked(actions)
+            || self
+                .view
+                .text_input(ids!(add_user_input))
+                .returned(actions)
+                .is_some();
+        if add_user {
+            let text = self.view.text_input(ids!(add_user_input)).text();
+            match OwnedUserId::try_from(text.trim()) {
+                Ok(user_id) => {
+                    if !draft.users.iter().any(|(id, _)| *id == user_id) {
+                        draft.users.push((user_id, BoardRole::Moderator.level()));
+                    }
+                    self.view.text_input(ids!(add_user_input)).set_text(cx, "");
+                    self.view.label(ids!(error_label)).set_text(cx, "");
+                    changed = true;
+                }
+                Err(e) => {
+                    log!("⚠️ BoardSettingsModal: 用户 ID 无效: {}", e);
+                    self.view
+                        .label(ids!(error_label))
+                        .set_text(cx, &format!("用户 ID 无效: {}", text.trim()));
+                    changed = true;
+                }
+            }
+        }
+
+        if self.view.button(ids!(save_button)).clicked(actions) {
+            if let Some(space_id) = self.space_id.clone() {
+                log!("BoardSettingsModal: 保存看板权限 (Space: {})", space_id);
+                cx.action(crate::kanban::KanbanActions::SaveBoardPermissions {
+                    space_id,
+                    permissions: draft.clone(),
+                });
+            }
+        }
+
+        if changed {
+            self.view.redraw(cx);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if let Some(draft) = self.draft.as_ref() {
+            let role = BoardRole::from_level(draft.user_level);
+            let level_text = if draft.can_edit {
+                format!(
+                    "你的角色: {}（等级 {}）",
+                    role.display_name(),
+                    draft.user_level
+                )
+            } else {
+                format!(
+                    "你的角色: {}（等级 {}），没有修改权限设置的权限",
+                    role.display_name(),
+                    draft.user_level
+                )
+            };
+            self.view.label(ids!(level_label)).set_text(cx, &level_text);
+
+            for (row, permission) in PERMISSION_ROWS.iter().zip(KanbanPermission::ALL) {
+                let row = self.view.view(*row);
+                row.label(ids!(permission_label))
+                    .set_text(cx, permission.display_name());
+                row.button(ids!(role_button))
+                    .set_text(cx, draft.required_role(permission).display_name());
+            }
+
+            self.view
+                .view(ids!(add_user_row))
+                .set_visible(cx, draft.can_edit);
+            self.view
+                .button(ids!(save_button))
+                .set_visible(cx, draft.can_edit);
+        }
+
+        let users = self
+            .draft
+            .as_ref()
+            .map(|draft| draft.users.clone())
+            .unwrap_or_default();
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                list.set_item_range(cx, 0, users.len());
+
+                while let Some(idx) = list.next_visible_item(cx) {
+                    let Some((user_id, level)) = users.get(idx) else {
+                        continue;
+                    };
+
+                    let item_widget = list.item(cx, idx, live_id!(BoardUserItem));
+                    item_widget
+                        .label(ids!(user_label))
+                        .set_text(cx, user_id.as_str());
+                    item_widget
+                        .button(ids!(user_role_button))
+                        .set_text(cx, BoardRole::from_level(*level).display_name());
+
+                    if let Some(mut user_item) = item_widget.as_board_user_item().borrow_mut() {
+                        user_item.user_id = Some(user_id.clone());
+                    }
+
+                    item_widget.draw_all(cx, &mut Scope::empty());
+                }
+            }
+        }
+        DrawStep::done()
+    }
+}
+
+impl BoardSettingsModalRef {
+    /// 正在编辑的列表
+    pub fn space_id(&self) -> Option<OwnedRoomId> {
+        self.borrow().and_then(|inner| inner.space_id.clone())
+    }
+
+    pub fn set_data(&self, cx: &mut Cx, space_id: OwnedRoomId, permissions: BoardPermissions) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.space_id = Some(space_id);
+            inner.draft = Some(permissions);
+            inner.view.text_input(ids!(add_user_input)).set_text(cx, "");
+            inner.view.label(ids!(error_label)).set_text(cx, "");
+            inner.view.redraw(cx);
+        }
+    }
+}
@@ -1,147 +1,599 @@
 use makepad_widgets::*;
+use matrix_sdk::ruma::{OwnedMxcUri, OwnedRoomId, OwnedUserId};
+
+use crate::avatar_cache::{self, AvatarCacheEntry};
+use crate::kanban::KanbanActions;
+use crate::kanban::components::card_info_section::parse_hex_color;
+use crate::kanban::state::boards::BOARD_BACKGROUND_COLORS;
+use crate::kanban::state::kanban_state::ActivityType;
+use crate::kanban::state::subscriptions::SUBSCRIBABLE_ACTIVITY_TYPES;
+use crate::utils;
+
+/// 订阅活动类型开关（与 `SUBSCRIBABLE_ACTIVITY_TYPES` 一一对应）
+const SUBSCRIPTION_TYPE_BUTTONS: &[&[LiveId]] = ids_array!(
+    subscription_type_0,
+    subscription_type_1,
+    subscription_type_2,
+    subscription_type_3,
+);
 
 live_design! {
     use link::theme::*;
     use link::widgets::*;
 
+    BoardToolInput = <TextInput> {
+        width: 180,
+        height: 36,
+        draw_text: {
+            color: #172B4D,
+            text_style: <THEME_FONT_REGULAR>{font_size: 12}
+        }
+        draw_bg: {
+            color: #FFFFFF,
+            border_color: #DFE1E6,
+            border_width: 2.0,
+            radius: 3.0,
+        }
+    }
+
+    // 订阅的活动类型开关，文字在绘制时设置
+    SubscriptionTypeButton = <Button> {
+        width: Fit,
+        height: 36,
+        padding: {left: 10, right: 10},
+        text: "",
+    }
+
     pub BoardCard = {{BoardCard}} {
-        width: 250,
-        height: 150,
-        margin: 10,
-        padding: 15,
+        width: 220,
+        height: 100,
+        margin: {right: 10}
         show_bg: true,
         draw_bg: {
             color: #0079BF
         }
 
-        flow: Down
+        flow: Overlay
 
-        board_name_label = <Label> {
+        // 看板背景图片（mxc://）
+        background_image = <Image> {
             width: Fill,
-            height: Fit,
-            text: "看板名称"
-            draw_text: {
-                text_style: <THEME_FONT_BOLD>{font_size: 18}
-                color: #FFFFFF
-            }
+            height: Fill,
+            fit: Biggest,
+            visible: false,
         }
 
         <View> {
             width: Fill,
-            height: Fit,
-            margin: {top: 10}
+            height: Fill,
+            flow: Down,
+            padding: 12,
+
+            board_name_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "看板名称"
+                draw_text: {
+                    text_style: <THEME_FONT_BOLD>{font_size: 16}
+                    color: #FFFFFF
+                }
+            }
 
-            <Label> {
-                text: "点击查看详情"
+            <View> { width: Fill, height: Fill }
+
+            board_info_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: ""
                 draw_text: {
-                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                    text_style: <THEME_FONT_REGULAR>{font_size: 11}
                     color: #FFFFFFCC
                 }
             }
         }
     }
 
+    // 看板切换栏：所有看板、新建看板以及当前看板的邀请 / 背景设置
     pub BoardsList = {{BoardsList}} {
-        <PortalList> {
-            width: Fill, height: Fill
+        width: Fill,
+        height: Fit,
+        flow: Down,
+        spacing: 10,
+        margin: {bottom: 10},
+
+        boards = <PortalList> {
+            width: Fill, height: 100
             flow: Right
-            spacing: 15
-            padding: 10
 
             Board = <BoardCard> {}
         }
+
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 10,
+            align: {y: 0.5},
+
+            new_board_name_input = <BoardToolInput> {
+                empty_text: "新看板名称",
+            }
+
+            // 新看板的背景颜色，点击切换
+            board_color_button = <Button> {
+                width: 36,
+                height: 36,
+                text: "",
+                draw_bg: {
+                    color: #0079BF,
+                    radius: 3.0,
+                }
+            }
+
+            create_board_button = <Button> {
+                width: 100,
+                height: 36,
+                text: "创建看板",
+                draw_bg: {
+                    color: #0079BF
+                }
+                draw_text: {
+                    color: #FFFFFF
+                    text_style: <THEME_FONT_REGULAR>{font_size: 13}
+                }
+            }
+
+            <View> { width: Fill, height: Fit }
+
+            // 只在选中看板时显示
+            current_board_tools = <View> {
+                width: Fit,
+                height: Fit,
+                flow: Right,
+                spacing: 10,
+                align: {y: 0.5},
+
+                invite_user_input = <BoardToolInput> {
+                    empty_text: "@用户:服务器",
+                }
+
+                invite_board_button = <Button> {
+                    width: 70,
+                    height: 36,
+                    text: "邀请",
+                }
+
+                background_input = <BoardToolInput> {
+                    width: 220,
+                    empty_text: "背景：#RRGGBB 或 mxc:// 图片",
+                }
+
+                set_background_button = <Button> {
+                    width: 90,
+                    height: 36,
+                    text: "设置背景",
+                }
+            }
+        }
+
+        // 看板动态订阅：把卡片活动转发到聊天 Room，只在选中看板时显示
+        subscription_tools = <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 10,
+            align: {y: 0.5},
+
+            subscription_room_input = <BoardToolInput> {
+                width: 220,
+                empty_text: "!房间ID 或 #别名",
+            }
+
+            subscription_type_0 = <SubscriptionTypeButton> {}
+            subscription_type_1 = <SubscriptionTypeButton> {}
+            subscription_type_2 = <SubscriptionTypeButton> {}
+            subscription_type_3 = <SubscriptionTypeButton> {}
+
+            subscribe_button = <Button> {
+                width: 90,
+                height: 36,
+                text: "订阅动态",
+            }
+
+            subscriptions_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: ""
+                draw_text: {
+                    wrap: Word,
+                    color: #5E6C84,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 11}
+                }
+            }
+        }
     }
 }
 
+/// 看板切换栏中的一项
+#[derive(Debug, Clone)]
+struct BoardEntry {
+    /// `None` 表示不属于任何看板的列表
+    board_id: Option<OwnedRoomId>,
+    name: String,
+    info: String,
+    background_color: String,
+    background_image: Option<String>,
+    invited: bool,
+    selected: bool,
+}
+
+/// 看板切换栏发出的 Action
+#[derive(Clone, Debug, DefaultNone)]
+pub enum BoardsListAction {
+    /// 打开了看板（`open_on_select` 为 true 时发出，附带看板名称）
+    Opened(String),
+    None,
+}
+
 #[derive(Live, LiveHook, Widget)]
 pub struct BoardCard {
     #[deref]
     view: View,
     #[rust]
-    board_id: Option<String>,
+    entry: Option<BoardEntry>,
+    #[rust]
+    open_on_select: bool,
 }
 
 impl Widget for BoardCard {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         self.view.handle_event(cx, event, scope);
 
-        // 处理点击事件 - 使用 Hit 来检测点击
-        match event.hits(cx, self.view.area()) {
-            Hit::FingerUp(f) => {
-                if f.was_tap() {
-                    // 简化架构：不再需要 SelectBoard，直接显示所有列表
-                    log!(
-                        "BoardCard: Clicked (simplified architecture - no board selection needed)"
-                    );
+        if let Hit::FingerUp(f) = event.hits(cx, self.view.area()) {
+            if !f.was_tap() {
+                return;
+            }
+            let Some(entry) = &self.entry else {
+                return;
+            };
+            match (&entry.board_id, entry.invited) {
+                // 收到邀请的看板：接受邀请
+                (Some(board_id), true) => {
+                    log!("BoardCard: 接受看板邀请 {}", board_id);
+                    cx.action(KanbanActions::JoinBoard {
+                        board_id: board_id.clone(),
+                    });
+                }
+                (board_id, _) => {
+                    log!("BoardCard: 切换到看板 {:?}", board_id);
+                    cx.action(KanbanActions::SelectBoard {
+                        board_id: board_id.clone(),
+                    });
+                    if self.open_on_select {
+                        cx.action(BoardsListAction::Opened(entry.name.clone()));
+                    }
                 }
             }
-            _ => {}
         }
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
-        // 从 scope.props 获取 board_id（简化架构后不再使用）
-        if let Some(board_id) = scope.props.get::<String>() {
-            self.board_id = Some(board_id.clone());
-        }
-
         self.view.draw_walk(cx, scope, walk)
     }
 }
 
+impl BoardCard {
+    fn set_entry(&mut self, cx: &mut Cx, entry: BoardEntry, open_on_select: bool) {
+        self.view.label(ids!(board_name_label)).set_text(
+            cx,
+            &if entry.selected {
+                format!("✓ {}", entry.name)
+            } else {
+                entry.name.clone()
+            },
+        );
+        self.view
+            .label(ids!(board_info_label))
+            .set_text(cx, &entry.info);
+
+        let color = parse_hex_color(&entry.background_color)
+            .unwrap_or_else(|| vec4(0.0, 0.475, 0.749, 1.0));
+        self.view
+            .apply_over(cx, live! { draw_bg: { color: (color) } });
+
+        // 背景图片通过头像缓存下载，下载完成前只显示背景颜色
+        let image = self.view.image(ids!(background_image));
+        let loaded = entry
+            .background_image
+            .as_deref()
+            .is_some_and(|url| load_background_image(cx, &image, url));
+        image.set_visible(cx, loaded);
+
+        self.entry = Some(entry);
+        self.open_on_select = open_on_select;
+    }
+}
+
+impl BoardCardRef {
+    fn set_entry(&self, cx: &mut Cx, entry: BoardEntry, open_on_select: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_entry(cx, entry, open_on_select);
+        }
+    }
+}
+
 #[derive(Live, LiveHook, Widget)]
 pub struct BoardsList {
     #[deref]
     view: View,
+    /// 点击看板后是否打开看板详情页（移动端布局）
+    #[live]
+    open_on_select: bool,
+    /// 新看板使用的背景颜色（`BOARD_BACKGROUND_COLORS` 的下标）
+    #[rust]
+    color_index: usize,
+    /// 当前看板 ID 和背景颜色
+    #[rust]
+    current_board: Option<(OwnedRoomId, String)>,
+    /// 订阅时不转发的活动类型（默认转发所有可订阅的类型）
+    #[rust]
+    excluded_types: Vec<ActivityType>,
 }
 
 impl Widget for BoardsList {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         self.view.handle_event(cx, event, scope);
-    }
 
-    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
-        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
-            if let Some(mut list) = item.as_portal_list().borrow_mut() {
-                // 简化架构：显示所有列表（Space）而不是看板
-                let lists: Vec<_> = {
-                    if let Some(app_state) = scope.data.get::<crate::app::AppState>() {
-                        app_state
-                            .kanban_state
-                            .all_lists()
-                            .into_iter()
-                            .map(|l| l.clone())
-                            .collect()
-                    } else {
-                        Vec::new()
-                    }
-                };
+        let Event::Actions(actions) = event else {
+            return;
+        };
 
-                list.set_item_range(cx, 0, lists.len());
+        if self.view.button(ids!(board_color_button)).clicked(actions) {
+            self.color_index = (self.color_index + 1) % BOARD_BACKGROUND_COLORS.len();
+            self.view.redraw(cx);
+        }
 
-                while let Some(list_idx) = list.next_visible_item(cx) {
-                    if list_idx >= lists.len() {
-                        continue;
-                    }
+        if self.view.button(ids!(create_board_button)).clicked(actions) {
+            let name_input = self.view.text_input(ids!(new_board_name_input));
+            let name = match name_input.text().trim() {
+                "" => "新看板".to_string(),
+                name => name.to_string(),
+            };
+            log!("BoardsList: 创建看板 '{}'", name);
+            cx.action(KanbanActions::CreateBoard {
+                name,
+                background_color: BOARD_BACKGROUND_COLORS[self.color_index].to_string(),
+            });
+            name_input.set_text(cx, "");
+        }
 
-                    let kanban_list = &lists[list_idx];
+        let Some((board_id, background_color)) = self.current_board.clone() else {
+            return;
+        };
 
-                    let list_item = list.item(cx, list_idx, live_id!(Board));
+        if self.view.button(ids!(invite_board_button)).clicked(actions) {
+            let invite_input = self.view.text_input(ids!(invite_user_input));
+            match OwnedUserId::try_from(invite_input.text().trim()) {
+                Ok(user_id) => {
+                    cx.action(KanbanActions::InviteToBoard { board_id, user_id });
+                    invite_input.set_text(cx, "");
+                }
+                Err(_) => show_warning(format!("无效的用户 ID: {}", invite_input.text())),
+            }
+            return;
+        }
 
-                    // 设置列表名称
-                    list_item
-                        .label(ids!(board_name_label))
-                        .set_text(cx, &kanban_list.name);
+        if self
+            .view
+            .button(ids!(set_background_button))
+            .clicked(actions)
+        {
+            let background_input = self.view.text_input(ids!(background_input));
+            let value = background_input.text().trim().to_string();
+            // mxc:// 设置背景图片（保留颜色）；颜色替换颜色并清除图片；留空只清除图片
+            let (background_color, background_image) = if value.starts_with("mxc://") {
+                (background_color, Some(value))
+            } else if value.is_empty() {
+                (background_color, None)
+            } else if parse_hex_color(&value).is_some() {
+                (value, None)
+            } else {
+                show_warning(format!("无效的背景: {}", value));
+                return;
+            };
+            cx.action(KanbanActions::UpdateBoardBackground {
+                board_id,
+                background_color,
+                background_image,
+            });
+            background_input.set_text(cx, "");
+            return;
+        }
 
-                    // 传递 space_id 给 BoardCard 并立即绘制
-                    let space_id_str = kanban_list.id.to_string();
-                    if let Some(app_state) = scope.data.get_mut::<crate::app::AppState>() {
-                        let mut list_scope = Scope::with_data_props(app_state, &space_id_str);
-                        list_item.draw_all(cx, &mut list_scope);
-                    }
+        for (button, activity_type) in SUBSCRIPTION_TYPE_BUTTONS
+            .iter()
+            .zip(SUBSCRIBABLE_ACTIVITY_TYPES)
+        {
+            if self.view.button(*button).clicked(actions) {
+                if self.excluded_types.contains(activity_type) {
+                    self.excluded_types.retain(|t| t != activity_type);
+                } else {
+                    self.excluded_types.push(activity_type.clone());
+                }
+                self.view.redraw(cx);
+                return;
+            }
+        }
+
+        if self.view.button(ids!(subscribe_button)).clicked(actions) {
+            let room_input = self.view.text_input(ids!(subscription_room_input));
+            let room = room_input.text().trim().to_string();
+            if !room.starts_with('!') && !room.starts_with('#') {
+                show_warning(format!("无效的聊天室: {}", room));
+                return;
+            }
+            // 不选任何活动类型时取消该聊天室的订阅
+            let activity_types = SUBSCRIBABLE_ACTIVITY_TYPES
+                .iter()
+                .filter(|t| !self.excluded_types.contains(t))
+                .cloned()
+                .collect();
+            cx.action(KanbanActions::SetBoardSubscription {
+                board_id,
+                room,
+                activity_types,
+            });
+            room_input.set_text(cx, "");
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        let entries: Vec<BoardEntry> = scope
+            .data
+            .get::<crate::app::AppState>()
+            .map(|app_state| board_entries(&app_state.kanban_state))
+            .unwrap_or_default();
+        self.current_board = scope
+            .data
+            .get::<crate::app::AppState>()
+            .and_then(|app_state| {
+                app_state
+                    .kanban_state
+                    .current_board()
+                    .map(|board| (board.id.clone(), board.background_color.clone()))
+            });
+
+        let subscriptions = scope
+            .data
+            .get::<crate::app::AppState>()
+            .and_then(|app_state| app_state.kanban_state.current_board())
+            .map(subscriptions_text);
+
+        self.view
+            .view(ids!(current_board_tools))
+            .set_visible(cx, self.current_board.is_some());
+        self.view
+            .view(ids!(subscription_tools))
+            .set_visible(cx, subscriptions.is_some());
+        self.view
+            .label(ids!(subscriptions_label))
+            .set_text(cx, subscriptions.as_deref().unwrap_or_default());
+        for (button, activity_type) in SUBSCRIPTION_TYPE_BUTTONS
+            .iter()
+            .zip(SUBSCRIBABLE_ACTIVITY_TYPES)
+        {
+            let mark = if self.excluded_types.contains(activity_type) {
+                "☐"
+            } else {
+                "☑"
+            };
+            self.view
+                .button(*button)
+                .set_text(cx, &format!("{} {}", mark, activity_type.display_text()));
+        }
+        if let Some(color) = parse_hex_color(BOARD_BACKGROUND_COLORS[self.color_index]) {
+            self.view
+                .button(ids!(board_color_button))
+                .apply_over(cx, live! { draw_bg: { color: (color) } });
+        }
+
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                list.set_item_range(cx, 0, entries.len());
+
+                while let Some(item_idx) = list.next_visible_item(cx) {
+                    let Some(entry) = entries.get(item_idx) else {
+                        continue;
+                    };
+                    let board_item = list.item(cx, item_idx, live_id!(Board));
+                    board_item
+                        .as_board_card()
+                        .set_entry(cx, entry.clone(), self.open_on_select);
+                    board_item.draw_all(cx, scope);
                 }
             }
         }
         DrawStep::done()
     }
 }
+
+fn show_warning(message: String) {
+    use crate::shared::popup_list::{PopupItem, PopupKind, enqueue_popup_notification};
+    enqueue_popup_notification(PopupItem {
+        message,
+        kind: PopupKind::Warning,
+        auto_dismissal_duration: Some(3000.0),
+    });
+}
+
+/// 加载 mxc:// 背景图片，返回是否已显示
+fn load_background_image(cx: &mut Cx, image: &ImageRef, url: &str) -> bool {
+    let mxc_uri = OwnedMxcUri::from(url);
+    if !mxc_uri.is_valid() {
+        return false;
+    }
+    match avatar_cache::get_or_fetch_avatar(cx, mxc_uri) {
+        AvatarCacheEntry::Loaded(data) => utils::load_png_or_jpg(image, cx, &data).is_ok(),
+        AvatarCacheEntry::Requested | AvatarCacheEntry::Failed => false,
+    }
+}
+
+/// 当前看板已有的订阅，例如 `已订阅：团队群（创建了卡片、移动了卡片）`
+fn subscriptions_text(board: &crate::kanban::state::kanban_state::KanbanBoard) -> String {
+    if board.subscriptions.is_empty() {
+        return "未订阅任何聊天室".to_string();
+    }
+    let client = crate::sliding_sync::get_client();
+    let rooms: Vec<String> = board
+        .subscriptions
+        .iter()
+        .map(|subscription| {
+            let name = client
+                .as_ref()
+                .and_then(|client| client.get_room(&subscription.room_id))
+                .and_then(|room| room.name())
+                .unwrap_or_else(|| subscription.room_id.to_string());
+            format!("{}（{}）", name, subscription.describe())
+        })
+        .collect();
+    format!("已订阅：{}", rooms.join("；"))
+}
+
+/// 切换栏中显示的看板：有未分组的列表（或还没有看板）时最后显示“未分组列表”
+fn board_entries(state: &crate::kanban::state::KanbanAppState) -> Vec<BoardEntry> {
+    let mut entries: Vec<BoardEntry> = state
+        .all_boards()
+        .into_iter()
+        .map(|board| BoardEntry {
+            board_id: Some(board.id.clone()),
+            name: board.name.clone(),
+            info: if board.invited {
+                "收到邀请 · 点击加入".to_string()
+            } else {
+                format!("{} 个列表", board.list_ids.len())
+            },
+            background_color: board.background_color.clone(),
+            background_image: board.background_image.clone(),
+            invited: board.invited,
+            selected: state.current_board_id.as_ref() == Some(&board.id),
+        })
+        .collect();
+
+    if entries.is_empty() || state.has_ungrouped_lists() {
+        let count = state
+            .lists
+            .keys()
+            .filter(|list_id| state.board_of_list(list_id).is_none())
+            .count();
+        entries.push(BoardEntry {
+            board_id: None,
+            name: "未分组列表".to_string(),
+            info: format!("{} 个列表", count),
+            background_color: "#5E6C84".to_string(),
+            background_image: None,
+            invited: false,
+            selected: state.current_board_id.is_none(),
+        });
+    }
+    entries
+}
@@ -0,0 +1,282 @@
+use makepad_widgets::*;
+use matrix_sdk::ruma::OwnedRoomId;
+
+use crate::kanban::state::bulk::BulkOperation;
+
+live_design! {
+    use link::theme::*;
+    use link::widgets::*;
+
+    BulkInput = <TextInput> {
+        width: 110,
+        height: 32,
+        draw_text: {
+            color: #172B4D,
+            text_style: <THEME_FONT_REGULAR>{font_size: 12}
+        }
+        draw_bg: {
+            color: #FFFFFF,
+            border_color: #DFE1E6,
+            border_width: 2.0,
+            radius: 3.0,
+        }
+    }
+
+    BulkButton = <Button> {
+        width: Fit,
+        height: 32,
+        padding: {left: 10, right: 10},
+        draw_text: {
+            text_style: <THEME_FONT_REGULAR>{font_size: 12}
+        }
+    }
+
+    // 多选后的批量操作栏（没有选中卡片且没有批量操作时隐藏）
+    pub KanbanBulkActionBar = {{KanbanBulkActionBar}} {
+        width: Fill,
+        height: Fit,
+        flow: Down,
+        spacing: 5,
+        margin: {bottom: 10},
+        padding: 8,
+        visible: false,
+        show_bg: true,
+        draw_bg: {
+            color: #E4F0F6
+        }
+
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 8,
+            align: {y: 0.5},
+
+            selection_label = <Label> {
+                width: Fit,
+                height: Fit,
+                text: "已选中 0 张卡片",
+                draw_text: {
+                    color: #172B4D,
+                    text_style: <THEME_FONT_BOLD>{font_size: 13}
+                }
+            }
+
+            // 点击切换移动目标列表
+            move_target_button = <BulkButton> {
+                text: "目标：-",
+            }
+            move_button = <BulkButton> {
+                text: "移动",
+            }
+
+            status_input = <BulkInput> {
+                empty_text: "状态名称",
+            }
+            status_button = <BulkButton> {
+                text: "设置状态",
+            }
+
+            tag_input = <BulkInput> {
+                empty_text: "标签名称",
+            }
+            add_tag_button = <BulkButton> {
+                text: "加标签",
+            }
+            remove_tag_button = <BulkButton> {
+                text: "去标签",
+            }
+
+            due_input = <BulkInput> {
+                empty_text: "YYYY-MM-DD",
+            }
+            due_button = <BulkButton> {
+                text: "截止时间",
+            }
+
+            archive_button = <BulkButton> {
+                text: "归档",
+            }
+            clear_selection_button = <BulkButton> {
+                text: "取消选择",
+            }
+        }
+
+        // 批量操作进度和失败报告；输入错误也显示在这里
+        progress_label = <Label> {
+            width: Fill,
+            height: Fit,
+            visible: false,
+            text: "",
+            draw_text: {
+                wrap: Word,
+                color: #5E6C84,
+                text_style: <THEME_FONT_REGULAR>{font_size: 12}
+            }
+        }
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct KanbanBulkActionBar {
+    #[deref]
+    view: View,
+    /// 可以移动到的列表（ID，名称）
+    #[rust]
+    move_targets: Vec<(OwnedRoomId, String)>,
+    /// 当前选择的移动目标在 `move_targets` 中的位置
+    #[rust]
+    move_target_index: usize,
+    /// 输入错误（例如无效的日期），显示在进度标签中
+    #[rust]
+    input_error: Option<String>,
+}
+
+impl Widget for KanbanBulkActionBar {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        let Event::Actions(actions) = event else {
+            return;
+        };
+
+        if self.view.button(ids!(move_target_button)).clicked(actions)
+            && !self.move_targets.is_empty()
+        {
+            self.move_target_index = (self.move_target_index + 1) % self.move_targets.len();
+            self.view.redraw(cx);
+        }
+
+        let mut operation = None;
+
+        if self.view.button(ids!(move_button)).clicked(actions) {
+            if let Some((target_space_id, _)) = self.move_targets.get(self.move_target_index) {
+                operation = Some(BulkOperation::MoveToList {
+                    target_space_id: target_space_id.clone(),
+                });
+            }
+        }
+
+        if self.view.button(ids!(status_button)).clicked(actions) {
+            let status = self.view.text_input(ids!(status_input)).text();
+            if !status.trim().is_empty() {
+                operation = Some(BulkOperation::SetStatus {
+                    status: status.trim().to_string(),
+                });
+            }
+        }
+
+        let tag_name = self.view.text_input(ids!(tag_input)).text();
+        if self.view.button(ids!(add_tag_button)).clicked(actions) && !tag_name.trim().is_empty() {
+            operation = Some(BulkOperation::AddTag {
+                tag_name: tag_name.trim().to_string(),
+            });
+        }
+        if self.view.button(ids!(remove_tag_button)).clicked(actions) && !tag_name.trim().is_empty()
+        {
+            operation = Some(BulkOperation::RemoveTag {
+                tag_name: tag_name.trim().to_string(),
+            });
+        }
+
+        // 日期为空时清除截止时间
+        if self.view.button(ids!(due_button)).clicked(actions) {
+            let text = self.view.text_input(ids!(due_input)).text();
+            if text.trim().is_empty() {
+                operation = Some(BulkOperation::SetDueDate { end_time: None });
+            } else {
+                match chrono::NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d") {
+                    Ok(date) => {
+                        operation = Some(BulkOperation::SetDueDate {
+                            end_time: Some(crate::kanban::state::calendar::end_time_on_day(
+                                None, date,
+                            )),
+                        });
+                    }
+                    Err(_) => {
+                        self.input_error =
+                            Some(format!("无效的日期 '{}'，格式为 YYYY-MM-DD", text.trim()));
+                        self.view.redraw(cx);
+                    }
+                }
+            }
+        }
+
+        if self.view.button(ids!(archive_button)).clicked(actions) {
+            operation = Some(BulkOperation::Archive);
+        }
+
+        if self
+            .view
+            .button(ids!(clear_selection_button))
+            .clicked(actions)
+        {
+            cx.action(crate::kanban::KanbanActions::ClearCardSelection);
+        }
+
+        if let Some(operation) = operation {
+            log!("KanbanBulkActionBar: 批量操作 {:?}", operation);
+            self.input_error = None;
+            cx.action(crate::kanban::KanbanActions::BulkApply { operation });
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        let Some(app_state) = scope.data.get::<crate::app::AppState>() else {
+            self.view.set_visible(cx, false);
+            return self.view.draw_walk(cx, scope, walk);
+        };
+        let kanban_state = &app_state.kanban_state;
+        let selected = kanban_state.selected_cards.len();
+        let operations = &kanban_state.bulk_operations;
+
+        self.view
+            .set_visible(cx, selected > 0 || !operations.is_empty());
+        self.view
+            .label(ids!(selection_label))
+            .set_text(cx, &format!("已选中 {} 张卡片", selected));
+
+        self.move_targets = kanban_state
+            .all_lists()
+            .into_iter()
+            .map(|list| (list.id.clone(), list.name.clone()))
+            .collect();
+        if self.move_target_index >= self.move_targets.len() {
+            self.move_target_index = 0;
+        }
+        let target_name = self
+            .move_targets
+            .get(self.move_target_index)
+            .map_or("-", |(_, name)| name.as_str());
+        self.view
+            .button(ids!(move_target_button))
+            .set_text(cx, &format!("目标：{}", target_name));
+
+        // 没有选中卡片时禁用操作按钮（进行中的批量操作各自报告进度，不影响新的操作）
+        for button in [
+            ids!(move_button),
+            ids!(status_button),
+            ids!(add_tag_button),
+            ids!(remove_tag_button),
+            ids!(due_button),
+            ids!(archive_button),
+        ] {
+            self.view.button(button).set_enabled(cx, selected > 0);
+        }
+
+        let message = self.input_error.clone().or_else(|| {
+            (!operations.is_empty()).then(|| {
+                operations
+                    .iter()
+                    .map(|operation| operation.summary())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+        });
+        let progress_label = self.view.label(ids!(progress_label));
+        progress_label.set_visible(cx, message.is_some());
+        progress_label.set_text(cx, message.as_deref().unwrap_or_default());
+
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
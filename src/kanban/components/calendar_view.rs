@@ -0,0 +1,792 @@
+use chrono::{Datelike, Duration, NaiveDate};
+use makepad_widgets::*;
+use matrix_sdk::ruma::OwnedRoomId;
+
+use crate::kanban::state::calendar::{self, CalendarMode, TIMELINE_DAYS, TimelineEntry, week_start};
+use crate::kanban::state::workflow::StatusCategory;
+
+/// 时间线中每一天的宽度（像素）
+const TIMELINE_DAY_WIDTH: f64 = 32.0;
+
+/// 每个日期单元格中最多显示的卡片数
+const MAX_CHIPS_PER_DAY: usize = 4;
+
+const WEEK_ROWS: &[&[LiveId]] = ids_array!(week_0, week_1, week_2, week_3, week_4, week_5);
+const DAY_CELLS: &[&[LiveId]] = ids_array!(day_0, day_1, day_2, day_3, day_4, day_5, day_6);
+const DAY_CHIPS: &[&[LiveId]] = ids_array!(chip_0, chip_1, chip_2, chip_3);
+const SCALE_LABELS: &[&[LiveId]] = ids_array!(scale_0, scale_1, scale_2, scale_3);
+
+live_design! {
+    use link::theme::*;
+    use link::widgets::*;
+
+    // 日历中的一张卡片（可拖到其他日期，点击打开详情）
+    CalendarCardChip = {{CalendarCardChip}} {
+        width: Fill,
+        height: Fit,
+        visible: false,
+
+        chip_bg = <RoundedView> {
+            width: Fill,
+            height: Fit,
+            padding: {left: 6, right: 6, top: 3, bottom: 3},
+            draw_bg: {
+                color: #DCEBFAFF
+            }
+
+            chip_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "",
+                draw_text: {
+                    wrap: Ellipsis,
+                    color: #172B4D,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 10}
+                }
+            }
+        }
+    }
+
+    // 日历中的一天
+    CalendarDayCell = {{CalendarDayCell}} {
+        width: Fill,
+        height: Fill,
+        padding: 6,
+        flow: Down,
+        spacing: 3,
+        show_bg: true,
+        draw_bg: {
+            color: #FFFFFFFF
+        }
+
+        day_label = <Label> {
+            width: Fill,
+            height: Fit,
+            text: "1",
+            draw_text: {
+                color: #172B4D,
+                text_style: <THEME_FONT_BOLD>{font_size: 11}
+            }
+        }
+
+        chip_0 = <CalendarCardChip> {}
+        chip_1 = <CalendarCardChip> {}
+        chip_2 = <CalendarCardChip> {}
+        chip_3 = <CalendarCardChip> {}
+
+        more_label = <Label> {
+            width: Fill,
+            height: Fit,
+            visible: false,
+            text: "",
+            draw_text: {
+                color: #5E6C84,
+                text_style: <THEME_FONT_REGULAR>{font_size: 10}
+            }
+        }
+    }
+
+    CalendarWeekRow = <View> {
+        width: Fill,
+        height: Fill,
+        flow: Right,
+        spacing: 2,
+
+        day_0 = <CalendarDayCell> {}
+        day_1 = <CalendarDayCell> {}
+        day_2 = <CalendarDayCell> {}
+        day_3 = <CalendarDayCell> {}
+        day_4 = <CalendarDayCell> {}
+        day_5 = <CalendarDayCell> {}
+        day_6 = <CalendarDayCell> {}
+    }
+
+    WeekdayLabel = <Label> {
+        width: Fill,
+        height: Fit,
+        draw_text: {
+            color: #5E6C84,
+            text_style: <THEME_FONT_BOLD>{font_size: 11}
+        }
+    }
+
+    // 日历视图：按月或按周显示卡片的截止时间
+    pub CalendarView = {{CalendarView}} {
+        width: Fill,
+        height: Fill,
+        flow: Down,
+        spacing: 2,
+        padding: 10,
+
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 8,
+            align: {y: 0.5},
+            margin: {bottom: 8},
+
+            prev_button = <Button> { width: 40, height: 32, text: "<" }
+            today_button = <Button> { width: 60, height: 32, text: "今天" }
+            next_button = <Button> { width: 40, height: 32, text: ">" }
+
+            title_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "",
+                draw_text: {
+                    color: #172B4D,
+                    text_style: <THEME_FONT_BOLD>{font_size: 16}
+                }
+            }
+
+            mode_button = <Button> { width: 80, height: 32, text: "按周" }
+        }
+
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 2,
+
+            <WeekdayLabel> { text: "周一" }
+            <WeekdayLabel> { text: "周二" }
+            <WeekdayLabel> { text: "周三" }
+            <WeekdayLabel> { text: "周四" }
+            <WeekdayLabel> { text: "周五" }
+            <WeekdayLabel> { text: "周六" }
+            <WeekdayLabel> { text: "周日" }
+        }
+
+        week_0 = <CalendarWeekRow> {}
+        week_1 = <CalendarWeekRow> {}
+        week_2 = <CalendarWeekRow> {}
+        week_3 = <CalendarWeekRow> {}
+        week_4 = <CalendarWeekRow> {}
+        week_5 = <CalendarWeekRow> {}
+    }
+
+    // 时间线中的一张卡片：左侧标题，右侧从创建时间到截止时间的横条
+    TimelineRow = {{TimelineRow}} {
+        width: Fit,
+        height: 32,
+        flow: Right,
+        align: {y: 0.5},
+
+        row_title_label = <Label> {
+            width: 200,
+            height: Fit,
+            text: "",
+            draw_text: {
+                wrap: Ellipsis,
+                color: #172B4D,
+                text_style: <THEME_FONT_REGULAR>{font_size: 11}
+            }
+        }
+
+        track = <View> {
+            width: 896,
+            height: Fill,
+            flow: Right,
+            align: {y: 0.5},
+            show_bg: true,
+            draw_bg: {
+                color: #F4F5F7FF
+            }
+
+            bar = <RoundedView> {
+                width: 32,
+                height: 22,
+                padding: {left: 6, right: 6},
+                align: {y: 0.5},
+                draw_bg: {
+                    color: #0079BFFF
+                }
+
+                bar_label = <Label> {
+                    width: Fill,
+                    height: Fit,
+                    text: "",
+                    draw_text: {
+                        wrap: Ellipsis,
+                        color: #FFFFFF,
+                        text_style: <THEME_FONT_REGULAR>{font_size: 10}
+                    }
+                }
+            }
+        }
+    }
+
+    ScaleLabel = <Label> {
+        width: 224,
+        height: Fit,
+        draw_text: {
+            color: #5E6C84,
+            text_style: <THEME_FONT_REGULAR>{font_size: 10}
+        }
+    }
+
+    // 时间线（甘特图）视图：拖动横条的右端修改截止时间
+    pub TimelineView = {{TimelineView}} {
+        width: Fill,
+        height: Fill,
+        flow: Down,
+        spacing: 4,
+        padding: 10,
+
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 8,
+            align: {y: 0.5},
+            margin: {bottom: 8},
+
+            prev_button = <Button> { width: 40, height: 32, text: "<" }
+            today_button = <Button> { width: 60, height: 32, text: "今天" }
+            next_button = <Button> { width: 40, height: 32, text: ">" }
+
+            range_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "",
+                draw_text: {
+                    color: #172B4D,
+                    text_style: <THEME_FONT_BOLD>{font_size: 16}
+                }
+            }
+        }
+
+        <View> {
+            width: Fit,
+            height: Fit,
+            flow: Right,
+
+            <View> { width: 200, height: 1 }
+            scale_0 = <ScaleLabel> {}
+            scale_1 = <ScaleLabel> {}
+            scale_2 = <ScaleLabel> {}
+            scale_3 = <ScaleLabel> {}
+        }
+
+        empty_label = <Label> {
+            width: Fill,
+            height: Fit,
+            visible: false,
+            text: "这段时间内没有设置截止时间的卡片",
+            draw_text: {
+                color: #5E6C84,
+                text_style: <THEME_FONT_REGULAR>{font_size: 12}
+            }
+        }
+
+        timeline_rows = <PortalList> {
+            width: Fill,
+            height: Fill,
+            flow: Down,
+            spacing: 4,
+
+            Row = <TimelineRow> {}
+        }
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct CalendarCardChip {
+    #[deref]
+    view: View,
+    #[rust]
+    card_id: Option<OwnedRoomId>,
+    #[rust]
+    drag_start_pos: Option<DVec2>,
+    #[rust]
+    is_dragging: bool,
+}
+
+impl Widget for CalendarCardChip {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        let Some(card_id) = self.card_id.clone() else {
+            return;
+        };
+        match event {
+            Event::MouseDown(e) => {
+                if self.view.area().rect(cx).contains(e.abs) {
+                    self.drag_start_pos = Some(e.abs);
+                }
+            }
+            Event::MouseMove(e) => {
+                let Some(start_pos) = self.drag_start_pos else {
+                    return;
+                };
+                // 移动距离超过 5px 时开始拖拽
+                if (e.abs - start_pos).length() > 5.0 && !self.is_dragging {
+                    if let Some(card) = scope
+                        .data
+                        .get::<crate::app::AppState>()
+                        .and_then(|app_state| app_state.kanban_state.cards.get(&card_id))
+                    {
+                        log!("CalendarCardChip: 开始拖拽卡片 {}", card_id);
+                        cx.action(crate::kanban::KanbanActions::StartDragCard {
+                            card_id: card_id.clone(),
+                            space_id: card.space_id.clone(),
+                            position: card.position,
+                            lane: None,
+                        });
+                        self.is_dragging = true;
+                    }
+                }
+            }
+            Event::MouseUp(e) => {
+                // 没有拖动时视为点击
+                if self.drag_start_pos.is_some()
+                    && !self.is_dragging
+                    && self.view.area().rect(cx).contains(e.abs)
+                {
+                    cx.action(crate::kanban::KanbanActions::ShowCardDetail { card_id });
+                }
+                self.drag_start_pos = None;
+                self.is_dragging = false;
+            }
+            _ => {}
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct CalendarDayCell {
+    #[deref]
+    view: View,
+    #[rust]
+    date: Option<NaiveDate>,
+    /// 是否属于当前显示的月份（月视图中前后月份的日期显示为灰色）
+    #[rust]
+    in_period: bool,
+    /// (卡片 ID, 显示文本, 是否已逾期)
+    #[rust]
+    cards: Vec<(OwnedRoomId, String, bool)>,
+}
+
+impl Widget for CalendarDayCell {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        // 拖拽的卡片放到这一天：修改截止时间
+        if let Event::MouseUp(e) = event {
+            let Some(date) = self.date else {
+                return;
+            };
+            let Some(drag_state) = scope
+                .data
+                .get::<crate::app::AppState>()
+                .and_then(|app_state| app_state.kanban_state.drag_state.as_ref())
+            else {
+                return;
+            };
+            if self.view.area().rect(cx).contains(e.abs) {
+                log!("CalendarDayCell: 放置卡片到 {}", date);
+                cx.action(crate::kanban::KanbanActions::DropCardOnDate {
+                    card_id: drag_state.card_id.clone(),
+                    date,
+                });
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        let Some(date) = self.date else {
+            return self.view.draw_walk(cx, scope, walk);
+        };
+
+        let is_today = date == calendar::today();
+        let bg_color = if is_today {
+            vec4(0.91, 0.96, 0.99, 1.0)
+        } else if self.in_period {
+            vec4(1.0, 1.0, 1.0, 1.0)
+        } else {
+            vec4(0.96, 0.96, 0.97, 1.0)
+        };
+        self.view
+            .apply_over(cx, live! { draw_bg: { color: (bg_color) } });
+
+        let day_label = self.view.label(ids!(day_label));
+        if date.day() == 1 {
+            day_label.set_text(cx, &format!("{}月{}日", date.month(), date.day()));
+        } else {
+            day_label.set_text(cx, &date.day().to_string());
+        }
+        let day_color = if self.in_period {
+            vec4(0.09, 0.17, 0.30, 1.0)
+        } else {
+            vec4(0.65, 0.68, 0.73, 1.0)
+        };
+        day_label.apply_over(cx, live! { draw_text: { color: (day_color) } });
+
+        for (i, chip_id) in DAY_CHIPS.iter().enumerate() {
+            let chip_widget = self.view.widget(*chip_id);
+            match self.cards.get(i) {
+                Some((card_id, text, overdue)) => {
+                    chip_widget.label(ids!(chip_label)).set_text(cx, text);
+                    let chip_color = if *overdue {
+                        vec4(0.98, 0.87, 0.86, 1.0)
+                    } else {
+                        vec4(0.86, 0.92, 0.98, 1.0)
+                    };
+                    chip_widget
+                        .view(ids!(chip_bg))
+                        .apply_over(cx, live! { draw_bg: { color: (chip_color) } });
+                    if let Some(mut chip) = chip_widget.as_calendar_card_chip().borrow_mut() {
+                        chip.card_id = Some(card_id.clone());
+                    }
+                    chip_widget.set_visible(cx, true);
+                }
+                None => {
+                    if let Some(mut chip) = chip_widget.as_calendar_card_chip().borrow_mut() {
+                        chip.card_id = None;
+                    }
+                    chip_widget.set_visible(cx, false);
+                }
+            }
+        }
+
+        let more_label = self.view.label(ids!(more_label));
+        let hidden = self.cards.len().saturating_sub(MAX_CHIPS_PER_DAY);
+        more_label.set_text(cx, &format!("还有 {} 张", hidden));
+        more_label.set_visible(cx, hidden > 0);
+
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct CalendarView {
+    #[deref]
+    view: View,
+    #[rust]
+    mode: CalendarMode,
+    #[rust]
+    anchor: NaiveDate,
+}
+
+impl Widget for CalendarView {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        match event {
+            Event::Actions(actions) => {
+                let new_view = if self.view.button(ids!(prev_button)).clicked(actions) {
+                    Some((self.mode, Some(self.mode.shift(self.anchor, false))))
+                } else if self.view.button(ids!(next_button)).clicked(actions) {
+                    Some((self.mode, Some(self.mode.shift(self.anchor, true))))
+                } else if self.view.button(ids!(today_button)).clicked(actions) {
+                    Some((self.mode, None))
+                } else if self.view.button(ids!(mode_button)).clicked(actions) {
+                    let mode = match self.mode {
+                        CalendarMode::Month => CalendarMode::Week,
+                        CalendarMode::Week => CalendarMode::Month,
+                    };
+                    Some((mode, Some(self.anchor)))
+                } else {
+                    None
+                };
+                if let Some((mode, anchor)) = new_view {
+                    cx.action(crate::kanban::KanbanActions::SetCalendarView { mode, anchor });
+                }
+            }
+            // 没有放到任何日期时取消拖拽（放到日期的 DropCardOnDate 会先被处理）
+            Event::MouseUp(_) => {
+                let dragging = scope
+                    .data
+                    .get::<crate::app::AppState>()
+                    .is_some_and(|app_state| app_state.kanban_state.drag_state.is_some());
+                if dragging {
+                    cx.action(crate::kanban::KanbanActions::CancelDragCard);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        let current_user = crate::sliding_sync::current_user_id();
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        let Some(app_state) = scope.data.get::<crate::app::AppState>() else {
+            return self.view.draw_walk(cx, scope, walk);
+        };
+        let state = &app_state.kanban_state;
+        self.mode = state.calendar_mode;
+        self.anchor = state.calendar_anchor();
+
+        self.view
+            .label(ids!(title_label))
+            .set_text(cx, &self.mode.title(self.anchor));
+        self.view.button(ids!(mode_button)).set_text(
+            cx,
+            match self.mode {
+                CalendarMode::Month => "按周",
+                CalendarMode::Week => "按月",
+            },
+        );
+
+        let days = self.mode.days(self.anchor);
+        for (row, week_id) in WEEK_ROWS.iter().enumerate() {
+            let week = self.view.widget(*week_id);
+            week.set_visible(cx, row * 7 < days.len());
+
+            for (col, day_id) in DAY_CELLS.iter().enumerate() {
+                let Some(date) = days.get(row * 7 + col).copied() else {
+                    continue;
+                };
+                let cards: Vec<(OwnedRoomId, String, bool)> = state
+                    .cards_due_on(date, current_user.as_deref())
+                    .into_iter()
+                    .map(|card| {
+                        let done = state.workflow(&card.space_id).category(&card.status)
+                            == StatusCategory::Done;
+                        let overdue = !done && card.end_time.is_some_and(|t| t < now);
+                        let title = if done {
+                            format!("✓ {}", card.title)
+                        } else {
+                            card.title.clone()
+                        };
+                        (card.id.clone(), title, overdue)
+                    })
+                    .collect();
+
+                let path = [week_id[0], day_id[0]];
+                if let Some(mut cell) = self.view.widget(&path).as_calendar_day_cell().borrow_mut()
+                {
+                    cell.date = Some(date);
+                    cell.in_period = match self.mode {
+                        CalendarMode::Month => date.month() == self.anchor.month(),
+                        CalendarMode::Week => true,
+                    };
+                    cell.cards = cards;
+                }
+            }
+        }
+
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct TimelineRow {
+    #[deref]
+    view: View,
+    #[rust]
+    entry: Option<TimelineEntry>,
+    /// 开始拖动横条时鼠标的横坐标
+    #[rust]
+    drag_start_x: Option<f64>,
+    /// 拖动的水平距离（像素）
+    #[rust]
+    drag_offset: f64,
+}
+
+impl TimelineRow {
+    /// 按当前拖动距离计算移动的天数
+    fn drag_days(&self) -> i64 {
+        (self.drag_offset / TIMELINE_DAY_WIDTH).round() as i64
+    }
+}
+
+impl Widget for TimelineRow {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        let Some(entry) = &self.entry else {
+            return;
+        };
+        match event {
+            Event::MouseDown(e) => {
+                if self.view.view(ids!(bar)).area().rect(cx).contains(e.abs) {
+                    self.drag_start_x = Some(e.abs.x);
+                    self.drag_offset = 0.0;
+                }
+            }
+            Event::MouseMove(e) => {
+                if let Some(start_x) = self.drag_start_x {
+                    self.drag_offset = e.abs.x - start_x;
+                    self.view.redraw(cx);
+                }
+            }
+            Event::MouseUp(_) => {
+                if self.drag_start_x.take().is_none() {
+                    return;
+                }
+                let card_id = entry.card.id.clone();
+                let days = self.drag_days();
+                if days != 0 {
+                    if let Some(end_time) = entry.card.end_time {
+                        let end_time = (end_time as i64 + days * 86400).max(0) as u64;
+                        log!("TimelineRow: 截止时间移动 {} 天", days);
+                        cx.action(crate::kanban::KanbanActions::SetEndTime { card_id, end_time });
+                    }
+                } else if self.drag_offset.abs() < 5.0 {
+                    cx.action(crate::kanban::KanbanActions::ShowCardDetail { card_id });
+                }
+                self.drag_offset = 0.0;
+                self.view.redraw(cx);
+            }
+            _ => {}
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        let Some(entry) = &self.entry else {
+            return self.view.draw_walk(cx, scope, walk);
+        };
+
+        self.view
+            .label(ids!(row_title_label))
+            .set_text(cx, &entry.card.title);
+
+        let window_days = TIMELINE_DAYS as f64;
+        let left = entry.start_day.clamp(0.0, window_days) * TIMELINE_DAY_WIDTH;
+        let right = (entry.end_day.clamp(0.0, window_days) * TIMELINE_DAY_WIDTH + self.drag_offset)
+            .clamp(0.0, window_days * TIMELINE_DAY_WIDTH);
+        let width = (right - left).max(8.0);
+
+        let done = scope
+            .data
+            .get::<crate::app::AppState>()
+            .is_some_and(|app_state| {
+                app_state
+                    .kanban_state
+                    .workflow(&entry.card.space_id)
+                    .category(&entry.card.status)
+                    == StatusCategory::Done
+            });
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let bar_color = if done {
+            vec4(0.38, 0.74, 0.31, 1.0)
+        } else if entry.card.end_time.is_some_and(|t| t < now) {
+            vec4(0.92, 0.35, 0.27, 1.0)
+        } else {
+            vec4(0.0, 0.47, 0.75, 1.0)
+        };
+
+        let bar = self.view.view(ids!(bar));
+        bar.apply_over(
+            cx,
+            live! {
+                margin: {left: (left)},
+                width: (width),
+                draw_bg: { color: (bar_color) }
+            },
+        );
+
+        let end_time = entry
+            .card
+            .end_time
+            .map(|t| (t as i64 + self.drag_days() * 86400).max(0) as u64);
+        let due_text = end_time
+            .and_then(calendar::local_date)
+            .map(|date| date.format("%m-%d").to_string())
+            .unwrap_or_default();
+        self.view.label(ids!(bar_label)).set_text(cx, &due_text);
+
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct TimelineView {
+    #[deref]
+    view: View,
+    #[rust]
+    mode: CalendarMode,
+    #[rust]
+    anchor: NaiveDate,
+}
+
+impl Widget for TimelineView {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if let Event::Actions(actions) = event {
+            let anchor = if self.view.button(ids!(prev_button)).clicked(actions) {
+                Some(Some(self.anchor - Duration::days(7)))
+            } else if self.view.button(ids!(next_button)).clicked(actions) {
+                Some(Some(self.anchor + Duration::days(7)))
+            } else if self.view.button(ids!(today_button)).clicked(actions) {
+                Some(None)
+            } else {
+                None
+            };
+            if let Some(anchor) = anchor {
+                cx.action(crate::kanban::KanbanActions::SetCalendarView {
+                    mode: self.mode,
+                    anchor,
+                });
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        let current_user = crate::sliding_sync::current_user_id();
+        let entries = scope
+            .data
+            .get::<crate::app::AppState>()
+            .map(|app_state| {
+                let state = &app_state.kanban_state;
+                self.mode = state.calendar_mode;
+                self.anchor = state.calendar_anchor();
+                state.timeline_entries(current_user.as_deref())
+            })
+            .unwrap_or_default();
+
+        let first_day = week_start(self.anchor);
+        let last_day = first_day + Duration::days(TIMELINE_DAYS - 1);
+        self.view.label(ids!(range_label)).set_text(
+            cx,
+            &format!(
+                "{} ~ {}",
+                first_day.format("%Y-%m-%d"),
+                last_day.format("%Y-%m-%d")
+            ),
+        );
+        for (week, scale_id) in SCALE_LABELS.iter().enumerate() {
+            let date = first_day + Duration::days(week as i64 * 7);
+            self.view
+                .label(*scale_id)
+                .set_text(cx, &date.format("%m-%d").to_string());
+        }
+        self.view
+            .label(ids!(empty_label))
+            .set_visible(cx, entries.is_empty());
+
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                list.set_item_range(cx, 0, entries.len());
+
+                while let Some(idx) = list.next_visible_item(cx) {
+                    let Some(entry) = entries.get(idx) else {
+                        continue;
+                    };
+
+                    let row_widget = list.item(cx, idx, live_id!(Row));
+                    if let Some(mut row) = row_widget.as_timeline_row().borrow_mut() {
+                        // 拖动中的横条保持自己的状态，避免被重绘覆盖
+                        if row.drag_start_x.is_none() {
+                            row.entry = Some(entry.clone());
+                        }
+                    }
+                    row_widget.draw_all(cx, scope);
+                }
+            }
+        }
+        DrawStep::done()
+    }
+}
@@ -1,14 +1,357 @@
 use makepad_widgets::*;
+use matrix_sdk::RoomDisplayName;
+use matrix_sdk::ruma::{OwnedMxcUri, OwnedRoomId};
+
+use crate::home::room_screen::RoomScreenWidgetRefExt;
+use crate::kanban::media;
+use crate::kanban::state::attachments::CardAttachment;
+use crate::kanban::state::dependency::{CardLink, CardLinkType};
+use crate::utils::RoomNameId;
 
 live_design! {
     use link::theme::*;
     use link::widgets::*;
+    use crate::home::room_screen::RoomScreen;
+
+    // 卡片评论与活动时间线：直接嵌入卡片 Room 的完整时间线，
+    // 支持回复、编辑、表情回应、@提及以及 Markdown/HTML 渲染，
+    // 系统活动（m.kanban.card.activity）按时间穿插显示在评论之间
+    pub CardTimelineView = {{CardTimelineView}} {
+        width: Fill,
+        height: Fill,
+        flow: Down,
+        spacing: 10,
+
+        <Label> {
+            width: Fill,
+            height: Fit,
+            text: "评论与活动"
+            draw_text: {
+                text_style: <THEME_FONT_BOLD>{font_size: 14}
+                color: #5E6C84
+            }
+        }
+
+        card_room_screen = <RoomScreen> {}
+    }
+
+    // 单条卡片关联
+    CardLinkItem = {{CardLinkItem}} {
+        width: Fill,
+        height: Fit,
+        flow: Right,
+        spacing: 8,
+        align: {y: 0.5},
+        padding: {top: 3, bottom: 3, left: 5, right: 5},
+
+        link_type_label = <Label> {
+            width: 70,
+            height: Fit,
+            text: "关联",
+            draw_text: {
+                color: #5E6C84,
+                text_style: <THEME_FONT_REGULAR>{font_size: 12}
+            }
+        }
+
+        // 点击打开关联的卡片
+        link_title_button = <Button> {
+            width: Fill,
+            height: 25,
+            text: "卡片",
+            draw_bg: {
+                color: #F4F5F7,
+            }
+            draw_text: {
+                color: #172B4D,
+                text_style: <THEME_FONT_REGULAR>{font_size: 13}
+            }
+        }
+
+        remove_link_button = <Button> {
+            width: 30,
+            height: 25,
+            text: "✕",
+            draw_bg: {
+                color: #FF6B6B,
+            }
+            draw_text: {
+                color: #FFFFFF,
+                text_style: <THEME_FONT_REGULAR>{font_size: 12}
+            }
+        }
+    }
+
+    // 卡片关联：阻塞 / 被阻塞 / 关联 / 重复，包括其他卡片指向当前卡片的关联
+    pub CardLinksView = {{CardLinksView}} {
+        width: Fill,
+        height: Fit,
+        flow: Down,
+        spacing: 10,
+
+        // 标题栏
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 10,
+            align: {y: 0.5},
+
+            <Label> {
+                text: "关联卡片",
+                draw_text: {
+                    text_style: <THEME_FONT_BOLD>{font_size: 14}
+                    color: #5E6C84
+                }
+            }
+
+            <View> { width: Fill, height: Fit }
+
+            blocked_label = <Label> {
+                text: "",
+                draw_text: {
+                    text_style: <THEME_FONT_BOLD>{font_size: 12}
+                    color: #EB5A46
+                }
+            }
+        }
+
+        link_list = <PortalList> {
+            width: Fill,
+            height: 100,
+            flow: Down,
+            spacing: 3,
+
+            CardLinkItem = <CardLinkItem> {}
+        }
+
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 10,
+            align: {y: 0.5},
+
+            // 点击切换关联类型
+            link_type_button = <Button> {
+                width: 80,
+                height: 30,
+                text: "阻塞",
+                draw_bg: {
+                    color: #DFE1E6,
+                    radius: 3.0,
+                }
+                draw_text: {
+                    color: #172B4D,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
+            }
+
+            link_target_input = <TextInput> {
+                width: Fill,
+                height: 35,
+                empty_text: "卡片标题或 Room ID",
+                draw_text: {
+                    color: #172B4D,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                }
+                draw_bg: {
+                    color: #FFFFFF,
+                    border_color: #DFE1E6,
+                    border_width: 2.0,
+                    radius: 3.0,
+                }
+                draw_cursor: {
+                    color: #172B4D
+                }
+            }
+
+            add_link_button = <Button> {
+                width: 60,
+                height: 30,
+                text: "添加",
+                draw_bg: {
+                    color: #0079BF,
+                    radius: 3.0,
+                }
+                draw_text: {
+                    color: #FFFFFF,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
+            }
+        }
+
+        link_error_label = <Label> {
+            width: Fill,
+            height: Fit,
+            text: "",
+            draw_text: {
+                wrap: Word,
+                color: #EB5A46,
+                text_style: <THEME_FONT_REGULAR>{font_size: 12}
+            }
+        }
+    }
+
+    // 单个附件：图片显示缩略图，文件显示图标
+    CardAttachmentItem = {{CardAttachmentItem}} {
+        width: Fill,
+        height: Fit,
+        flow: Right,
+        spacing: 8,
+        align: {y: 0.5},
+        padding: {top: 3, bottom: 3, left: 5, right: 5},
+
+        thumbnail = <Image> {
+            width: 48,
+            height: 48,
+            fit: Biggest,
+            visible: false,
+        }
+
+        file_icon_label = <Label> {
+            width: 48,
+            height: Fit,
+            align: {x: 0.5},
+            text: "📄",
+            draw_text: {
+                text_style: <THEME_FONT_REGULAR>{font_size: 20}
+            }
+        }
+
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Down,
+            spacing: 2,
+
+            attachment_name_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "",
+                draw_text: {
+                    wrap: Ellipsis,
+                    color: #172B4D,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 13}
+                }
+            }
+
+            attachment_info_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "",
+                draw_text: {
+                    color: #5E6C84,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 11}
+                }
+            }
+        }
+
+        // 只有图片可以设为封面
+        cover_button = <Button> {
+            width: 80,
+            height: 25,
+            text: "设为封面",
+            draw_bg: {
+                color: #DFE1E6,
+                radius: 3.0,
+            }
+            draw_text: {
+                color: #172B4D,
+                text_style: <THEME_FONT_REGULAR>{font_size: 12}
+            }
+        }
+    }
+
+    // 卡片附件：上传文件 / 图片（卡片 Room 中的 m.file / m.image 消息），选择封面图片
+    pub CardAttachmentsView = {{CardAttachmentsView}} {
+        width: Fill,
+        height: Fit,
+        flow: Down,
+        spacing: 10,
+
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 10,
+            align: {y: 0.5},
+
+            <Label> {
+                text: "附件",
+                draw_text: {
+                    text_style: <THEME_FONT_BOLD>{font_size: 14}
+                    color: #5E6C84
+                }
+            }
+
+            <View> { width: Fill, height: Fit }
+
+            attachment_count_label = <Label> {
+                text: "",
+                draw_text: {
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                    color: #5E6C84
+                }
+            }
+        }
+
+        attachment_list = <PortalList> {
+            width: Fill,
+            height: 160,
+            flow: Down,
+            spacing: 3,
+
+            CardAttachmentItem = <CardAttachmentItem> {}
+        }
+
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 10,
+            align: {y: 0.5},
+
+            attachment_path_input = <TextInput> {
+                width: Fill,
+                height: 35,
+                empty_text: "本地文件路径",
+                draw_text: {
+                    color: #172B4D,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                }
+                draw_bg: {
+                    color: #FFFFFF,
+                    border_color: #DFE1E6,
+                    border_width: 2.0,
+                    radius: 3.0,
+                }
+                draw_cursor: {
+                    color: #172B4D
+                }
+            }
+
+            upload_attachment_button = <Button> {
+                width: 60,
+                height: 30,
+                text: "上传",
+                draw_bg: {
+                    color: #0079BF,
+                    radius: 3.0,
+                }
+                draw_text: {
+                    color: #FFFFFF,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
+            }
+        }
+    }
 
     // 简化的卡片详情查看器
     pub CardDetailView = {{CardDetailView}} {
         <RoundedView> {
             width: 600,
-            height: 400,
+            height: 700,
             padding: 20,
             flow: Down,
             spacing: 15,
@@ -101,6 +444,14 @@ live_design! {
                 }
             }
 
+            // 关联卡片
+            <CardLinksView> {}
+
+            // 评论与活动
+            card_timeline = <CardTimelineView> {
+                height: 300,
+            }
+
             // 操作按钮
             <View> {
                 width: Fill,
@@ -120,8 +471,8 @@ live_design! {
                     }
                 }
 
-                delete_button = <Button> {
-                    text: "删除",
+                archive_button = <Button> {
+                    text: "归档",
                     width: 80,
                     height: 35,
                     draw_bg: {
@@ -164,11 +515,11 @@ impl Widget for CardDetailView {
                 }
             }
 
-            // 处理删除按钮
-            if self.view.button(ids!(delete_button)).clicked(actions) {
+            // 处理归档按钮
+            if self.view.button(ids!(archive_button)).clicked(actions) {
                 if let Some(card_id) = &self.card_id {
-                    log!("CardDetailView: 删除按钮被点击, card_id={}", card_id);
-                    cx.action(crate::kanban::KanbanActions::DeleteCard {
+                    log!("CardDetailView: 归档按钮被点击, card_id={}", card_id);
+                    cx.action(crate::kanban::KanbanActions::ArchiveCard {
                         card_id: card_id.clone(),
                     });
                     cx.action(CardDetailViewAction::Close);
@@ -203,6 +554,428 @@ impl Widget for CardDetailView {
     }
 }
 
+#[derive(Live, LiveHook, Widget)]
+pub struct CardTimelineView {
+    #[deref]
+    view: View,
+}
+
+impl Widget for CardTimelineView {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        // 显示当前选中卡片的 Room 时间线
+        let card = scope.data.get::<crate::app::AppState>().and_then(|app_state| {
+            let card_id = app_state.kanban_state.selected_card_id.as_ref()?;
+            let title = app_state
+                .kanban_state
+                .cards
+                .get(card_id)
+                .map(|card| card.title.clone());
+            Some((card_id.clone(), title))
+        });
+
+        let room_screen = self.view.room_screen(ids!(card_room_screen));
+        match card {
+            Some((card_id, title)) if room_screen.displayed_room_id().as_ref() != Some(&card_id) => {
+                log!("CardTimelineView: 显示卡片 {} 的时间线", card_id);
+                let display_name = title.map_or(RoomDisplayName::Empty, RoomDisplayName::Named);
+                room_screen.set_displayed_room(cx, &RoomNameId::new(display_name, card_id));
+            }
+            Some(_) => {}
+            None => room_screen.clear_displayed_room(),
+        }
+
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+impl CardTimelineViewRef {
+    /// 停止显示卡片时间线，使卡片 Room 可以在聊天界面中正常打开
+    pub fn clear(&self) {
+        if let Some(inner) = self.borrow() {
+            inner.view.room_screen(ids!(card_room_screen)).clear_displayed_room();
+        }
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct CardLinkItem {
+    #[deref]
+    view: View,
+    /// 保存该关联的卡片
+    #[rust]
+    source_id: Option<matrix_sdk::ruma::OwnedRoomId>,
+    #[rust]
+    link: Option<CardLink>,
+    /// 关联的另一张卡片
+    #[rust]
+    other_id: Option<matrix_sdk::ruma::OwnedRoomId>,
+}
+
+impl Widget for CardLinkItem {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        let Event::Actions(actions) = event else {
+            return;
+        };
+
+        if self.view.button(ids!(link_title_button)).clicked(actions) {
+            if let Some(other_id) = &self.other_id {
+                log!("CardLinkItem: 打开关联卡片 {}", other_id);
+                cx.action(crate::kanban::KanbanActions::ShowCardDetail {
+                    card_id: other_id.clone(),
+                });
+            }
+        }
+
+        if self.view.button(ids!(remove_link_button)).clicked(actions) {
+            if let (Some(source_id), Some(link)) = (&self.source_id, &self.link) {
+                log!("CardLinkItem: 删除关联 {:?}（保存在 {}）", link, source_id);
+                cx.action(crate::kanban::KanbanActions::RemoveCardLink {
+                    source_id: source_id.clone(),
+                    link: link.clone(),
+                });
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct CardLinksView {
+    #[deref]
+    view: View,
+    #[rust]
+    card_id: Option<matrix_sdk::ruma::OwnedRoomId>,
+    /// 新关联的类型（默认为"阻塞"，与按钮初始文字一致）
+    #[rust]
+    link_type: CardLinkType,
+}
+
+impl Widget for CardLinksView {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        let Event::Actions(actions) = event else {
+            return;
+        };
+
+        // 切换关联类型
+        if self.view.button(ids!(link_type_button)).clicked(actions) {
+            self.link_type = self.link_type.next();
+            self.view
+                .button(ids!(link_type_button))
+                .set_text(cx, self.link_type.display_name());
+            self.view.redraw(cx);
+        }
+
+        let Some(card_id) = self.card_id.clone() else {
+            return;
+        };
+
+        // 添加关联：按卡片 ID 或唯一的卡片标题查找目标卡片
+        let target_input = self.view.text_input(ids!(link_target_input));
+        let submitted = target_input.returned(actions).is_some();
+        if self.view.button(ids!(add_link_button)).clicked(actions) || submitted {
+            let reference = target_input.text();
+            if reference.trim().is_empty() {
+                return;
+            }
+            let target_id = scope.data.get::<crate::app::AppState>().and_then(|app_state| {
+                app_state
+                    .kanban_state
+                    .find_card_by_reference(&reference)
+                    .map(|card| card.id.clone())
+            });
+            match target_id {
+                Some(target_id) => {
+                    log!(
+                        "CardLinksView: 添加关联 {} {:?} {}",
+                        card_id,
+                        self.link_type,
+                        target_id
+                    );
+                    target_input.set_text(cx, "");
+                    self.view.label(ids!(link_error_label)).set_text(cx, "");
+                    cx.action(crate::kanban::KanbanActions::AddCardLink {
+                        card_id,
+                        link_type: self.link_type,
+                        target_id,
+                    });
+                }
+                None => {
+                    log!("⚠️ CardLinksView: 找不到卡片 '{}'", reference);
+                    self.view
+                        .label(ids!(link_error_label))
+                        .set_text(cx, "找不到卡片（标题重复时请使用 Room ID）");
+                }
+            }
+            self.view.redraw(cx);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        // (关联, 对方卡片标题)
+        let mut links = Vec::new();
+        let mut blocked_text = String::new();
+        if let Some(app_state) = scope.data.get::<crate::app::AppState>() {
+            let state = &app_state.kanban_state;
+            if let Some(card_id) = &state.selected_card_id {
+                if self.card_id.as_ref() != Some(card_id) {
+                    self.card_id = Some(card_id.clone());
+                    self.view.text_input(ids!(link_target_input)).set_text(cx, "");
+                    self.view.label(ids!(link_error_label)).set_text(cx, "");
+                }
+                links = state
+                    .card_links(card_id)
+                    .into_iter()
+                    .map(|linked| {
+                        let title = state
+                            .cards
+                            .get(&linked.other_id)
+                            .map_or_else(|| linked.other_id.to_string(), |card| card.title.clone());
+                        (linked, title)
+                    })
+                    .collect();
+                let blockers = state.blockers(card_id).len();
+                if blockers > 0 {
+                    blocked_text = format!("⛔ 被 {} 张卡片阻塞", blockers);
+                }
+            }
+        }
+        self.view.label(ids!(blocked_label)).set_text(cx, &blocked_text);
+
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                list.set_item_range(cx, 0, links.len());
+
+                while let Some(link_idx) = list.next_visible_item(cx) {
+                    let Some((linked, title)) = links.get(link_idx) else {
+                        continue;
+                    };
+
+                    let link_item_widget = list.item(cx, link_idx, live_id!(CardLinkItem));
+                    link_item_widget
+                        .label(ids!(link_type_label))
+                        .set_text(cx, linked.label);
+                    link_item_widget
+                        .button(ids!(link_title_button))
+                        .set_text(cx, title);
+
+                    if let Some(mut link_item) = link_item_widget.as_card_link_item().borrow_mut() {
+                        link_item.source_id = Some(linked.source_id.clone());
+                        link_item.link = Some(linked.link.clone());
+                        link_item.other_id = Some(linked.other_id.clone());
+                    }
+
+                    link_item_widget.draw_all(cx, &mut Scope::empty());
+                }
+            }
+        }
+        DrawStep::done()
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct CardAttachmentItem {
+    #[deref]
+    view: View,
+    #[rust]
+    card_id: Option<OwnedRoomId>,
+    /// 图片附件的地址（文件附件为 `None`）
+    #[rust]
+    image_url: Option<OwnedMxcUri>,
+    /// 是否为当前封面
+    #[rust]
+    is_cover: bool,
+    /// 已显示的缩略图
+    #[rust]
+    loaded_thumbnail: Option<OwnedMxcUri>,
+}
+
+impl Widget for CardAttachmentItem {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        let Event::Actions(actions) = event else {
+            return;
+        };
+
+        if self.view.button(ids!(cover_button)).clicked(actions) {
+            if let (Some(card_id), Some(url)) = (&self.card_id, &self.image_url) {
+                let cover = (!self.is_cover).then(|| url.clone());
+                log!(
+                    "CardAttachmentItem: 设置卡片 {} 的封面: {:?}",
+                    card_id,
+                    cover
+                );
+                cx.action(crate::kanban::KanbanActions::SetCardCover {
+                    card_id: card_id.clone(),
+                    cover,
+                });
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+impl CardAttachmentItem {
+    /// 显示附件；返回缩略图是否还在下载
+    fn set_attachment(
+        &mut self,
+        cx: &mut Cx,
+        card_id: &OwnedRoomId,
+        attachment: &CardAttachment,
+        is_cover: bool,
+    ) -> bool {
+        self.card_id = Some(card_id.clone());
+        self.image_url = attachment.is_image().then(|| attachment.url.clone());
+        self.is_cover = is_cover;
+
+        self.view
+            .label(ids!(attachment_name_label))
+            .set_text(cx, &attachment.name);
+        let mut info = attachment.size_text();
+        if is_cover {
+            info.push_str(" · 封面");
+        }
+        self.view
+            .label(ids!(attachment_info_label))
+            .set_text(cx, &info);
+
+        let cover_button = self.view.button(ids!(cover_button));
+        cover_button.set_visible(cx, attachment.is_image());
+        let cover_text = if is_cover {
+            "取消封面"
+        } else {
+            "设为封面"
+        };
+        cover_button.set_text(cx, cover_text);
+
+        let thumbnail = self.view.image(ids!(thumbnail));
+        if self.loaded_thumbnail != self.image_url {
+            let loaded = self
+                .image_url
+                .as_ref()
+                .is_some_and(|url| media::load_thumbnail(cx, &thumbnail, url));
+            self.loaded_thumbnail = if loaded { self.image_url.clone() } else { None };
+        }
+        let has_thumbnail = self.loaded_thumbnail.is_some();
+        thumbnail.set_visible(cx, has_thumbnail);
+        self.view
+            .label(ids!(file_icon_label))
+            .set_visible(cx, !has_thumbnail);
+        self.image_url.is_some() && !has_thumbnail
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct CardAttachmentsView {
+    #[deref]
+    view: View,
+    #[rust]
+    card_id: Option<OwnedRoomId>,
+    /// 有缩略图还在下载，下载完成后需要重绘
+    #[rust]
+    thumbnails_pending: bool,
+}
+
+impl Widget for CardAttachmentsView {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if let Event::Signal = event {
+            if self.thumbnails_pending {
+                self.view.redraw(cx);
+            }
+        }
+
+        let Event::Actions(actions) = event else {
+            return;
+        };
+        let Some(card_id) = self.card_id.clone() else {
+            return;
+        };
+
+        let path_input = self.view.text_input(ids!(attachment_path_input));
+        let upload_button = self.view.button(ids!(upload_attachment_button));
+        if upload_button.clicked(actions) || path_input.returned(actions).is_some() {
+            let path = path_input.text();
+            if path.trim().is_empty() {
+                return;
+            }
+            log!("CardAttachmentsView: 上传附件 {} 到卡片 {}", path, card_id);
+            path_input.set_text(cx, "");
+            cx.action(crate::kanban::KanbanActions::UploadAttachment { card_id, path });
+            self.view.redraw(cx);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        let mut attachments = Vec::new();
+        let mut cover = None;
+        if let Some(app_state) = scope.data.get::<crate::app::AppState>() {
+            let state = &app_state.kanban_state;
+            if let Some(card_id) = &state.selected_card_id {
+                if self.card_id.as_ref() != Some(card_id) {
+                    self.card_id = Some(card_id.clone());
+                    self.view
+                        .text_input(ids!(attachment_path_input))
+                        .set_text(cx, "");
+                }
+                attachments = state.card_attachments(card_id).to_vec();
+                cover = state.cards.get(card_id).and_then(|card| card.cover.clone());
+            }
+        }
+        let count_text = if attachments.is_empty() {
+            String::new()
+        } else {
+            format!("{} 个", attachments.len())
+        };
+        self.view
+            .label(ids!(attachment_count_label))
+            .set_text(cx, &count_text);
+
+        let mut thumbnails_pending = false;
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                list.set_item_range(cx, 0, attachments.len());
+
+                while let Some(attachment_idx) = list.next_visible_item(cx) {
+                    let (Some(card_id), Some(attachment)) =
+                        (&self.card_id, attachments.get(attachment_idx))
+                    else {
+                        continue;
+                    };
+
+                    let item_widget = list.item(cx, attachment_idx, live_id!(CardAttachmentItem));
+                    if let Some(mut attachment_item) =
+                        item_widget.as_card_attachment_item().borrow_mut()
+                    {
+                        let is_cover = cover.as_ref() == Some(&attachment.url);
+                        thumbnails_pending |=
+                            attachment_item.set_attachment(cx, card_id, attachment, is_cover);
+                    }
+
+                    item_widget.draw_all(cx, &mut Scope::empty());
+                }
+            }
+        }
+        self.thumbnails_pending = thumbnails_pending;
+        DrawStep::done()
+    }
+}
+
 /// 卡片详情视图的 Action
 #[derive(Clone, Debug, DefaultNone)]
 pub enum CardDetailViewAction {
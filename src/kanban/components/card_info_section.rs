@@ -1,9 +1,33 @@
 use makepad_widgets::*;
 
+use crate::kanban::markdown::{checklist_progress, render_description};
+use crate::shared::html_or_plaintext::HtmlOrPlaintextWidgetExt;
+
 live_design! {
     use link::theme::*;
     use link::widgets::*;
     use crate::shared::styles::*;
+    use crate::shared::html_or_plaintext::HtmlOrPlaintext;
+
+    // 状态流转按钮（文字和颜色由工作流决定）
+    StatusButton = <Button> {
+        width: Fit,
+        height: 28,
+        padding: {left: 10, right: 10},
+        margin: {bottom: 5},
+        visible: false,
+        text: "状态"
+        draw_bg: {
+            color: #95A5A6
+            radius: 3.0
+        }
+        draw_text: {
+            color: #FFFFFF
+            text_style: {
+                font_size: 12.0
+            }
+        }
+    }
 
     // 卡片基本信息区域（标题、描述、状态）
     pub CardInfoSection = {{CardInfoSection}} {
@@ -150,6 +174,19 @@ live_design! {
                     }
                 }
 
+                // 描述中清单的完成情况
+                checklist_label = <Label> {
+                    width: Fit,
+                    height: Fit,
+                    text: ""
+                    draw_text: {
+                        color: #5E6C84
+                        text_style: {
+                            font_size: 12.0
+                        }
+                    }
+                }
+
                 edit_description_button = <Button> {
                     width: 60,
                     height: 25,
@@ -166,18 +203,8 @@ live_design! {
                 }
             }
 
-            // 描述显示区域
-            card_description_label = <Label> {
-                width: Fill,
-                height: Fit,
-                text: "暂无描述"
-                draw_text: {
-                    color: #333333
-                    text_style: {
-                        font_size: 14.0
-                    }
-                }
-            }
+            // 描述显示区域（Markdown 渲染后的 HTML）
+            card_description_view = <HtmlOrPlaintext> {}
 
             // 描述编辑区域
             description_edit_container = <View> {
@@ -189,10 +216,36 @@ live_design! {
 
                 card_description_input = <SimpleTextInput> {
                     width: Fill,
-                    height: 80,
+                    height: 120,
                     text: "",
                 }
 
+                <Label> {
+                    width: Fill,
+                    height: Fit,
+                    text: "支持 Markdown、清单 \"- [ ] 事项\"、提及 @用户:服务器、卡片链接 [[卡片标题]]"
+                    draw_text: {
+                        wrap: Word,
+                        color: #999999
+                        text_style: {
+                            font_size: 11.0
+                        }
+                    }
+                }
+
+                // 实时预览
+                <RoundedView> {
+                    width: Fill,
+                    height: Fit,
+                    padding: 8,
+                    draw_bg: {
+                        color: #F4F5F7
+                        radius: 3.0
+                    }
+
+                    description_preview = <HtmlOrPlaintext> {}
+                }
+
                 <View> {
                     width: Fill,
                     height: Fit,
@@ -269,59 +322,40 @@ live_design! {
                         }
                     }
                 }
+            }
 
-                status_pending_btn = <Button> {
-                    width: 70,
-                    height: 28,
-                    text: "未完成"
-                    draw_bg: {
-                        color: #FFA500
-                        radius: 3.0
-                    }
-                    draw_text: {
-                        color: #FFFFFF
-                        text_style: {
-                            font_size: 12.0
-                        }
-                    }
-                }
-
-                status_completed_btn = <Button> {
-                    width: 70,
-                    height: 28,
-                    text: "已完成"
-                    draw_bg: {
-                        color: #61BD4F
-                        radius: 3.0
-                    }
-                    draw_text: {
-                        color: #FFFFFF
-                        text_style: {
-                            font_size: 12.0
-                        }
-                    }
-                }
-
-                status_archived_btn = <Button> {
-                    width: 70,
-                    height: 28,
-                    text: "已归档"
-                    draw_bg: {
-                        color: #95A5A6
-                        radius: 3.0
-                    }
-                    draw_text: {
-                        color: #FFFFFF
-                        text_style: {
-                            font_size: 12.0
-                        }
-                    }
-                }
+            // 可流转到的状态（按所属 Space 的工作流动态显示）
+            status_buttons = <View> {
+                width: Fill,
+                height: Fit,
+                flow: RightWrap,
+                spacing: 8,
+
+                status_btn_0 = <StatusButton> {}
+                status_btn_1 = <StatusButton> {}
+                status_btn_2 = <StatusButton> {}
+                status_btn_3 = <StatusButton> {}
+                status_btn_4 = <StatusButton> {}
+                status_btn_5 = <StatusButton> {}
+                status_btn_6 = <StatusButton> {}
+                status_btn_7 = <StatusButton> {}
             }
         }
     }
 }
 
+/// 状态按钮（数量与 `MAX_WORKFLOW_STATUSES` 一致）
+const STATUS_BUTTONS: &[&[LiveId]] = ids_array!(
+    status_btn_0,
+    status_btn_1,
+    status_btn_2,
+    status_btn_3,
+    status_btn_4,
+    status_btn_5,
+    status_btn_6,
+    status_btn_7,
+);
+
 #[derive(Live, LiveHook, Widget)]
 pub struct CardInfoSection {
     #[deref]
@@ -332,6 +366,12 @@ pub struct CardInfoSection {
     is_editing_title: bool,
     #[rust]
     is_editing_description: bool,
+    /// 当前显示的描述（原文, HTML），避免每次绘制都重新设置 HTML
+    #[rust]
+    shown_description: Option<(Option<String>, Option<String>)>,
+    /// 各状态按钮对应的状态 ID
+    #[rust]
+    status_button_ids: Vec<String>,
 }
 
 impl Widget for CardInfoSection {
@@ -428,18 +468,25 @@ impl Widget for CardInfoSection {
             {
                 self.is_editing_description = true;
 
-                // 获取当前描述并设置到输入框
-                let current_desc = self.view.label(ids!(card_description_label)).text();
+                // 获取当前描述（Markdown 原文）并设置到输入框
+                let current_desc = scope
+                    .data
+                    .get::<crate::app::AppState>()
+                    .zip(self.card_id.as_ref())
+                    .and_then(|(app_state, card_id)| app_state.kanban_state.cards.get(card_id))
+                    .and_then(|card| card.description.clone())
+                    .unwrap_or_default();
                 self.view
                     .text_input(ids!(card_description_input))
                     .set_text(cx, &current_desc);
+                self.update_preview(cx, scope, &current_desc);
 
                 // 显示编辑区域，隐藏显示区域
                 self.view
                     .view(ids!(description_edit_container))
                     .set_visible(cx, true);
                 self.view
-                    .label(ids!(card_description_label))
+                    .widget(ids!(card_description_view))
                     .set_visible(cx, false);
                 self.view
                     .button(ids!(edit_description_button))
@@ -461,23 +508,25 @@ impl Widget for CardInfoSection {
             {
                 let new_desc = self.view.text_input(ids!(card_description_input)).text();
 
-                if let Some(card_id) = &self.card_id {
+                if let Some(card_id) = self.card_id.clone() {
                     let desc_option = if new_desc.trim().is_empty() {
                         None
                     } else {
                         Some(new_desc.trim().to_string())
                     };
+                    let html = desc_option.as_deref().and_then(|desc| {
+                        let app_state = scope.data.get::<crate::app::AppState>()?;
+                        render_description(cx, desc, &app_state.kanban_state, Some(&card_id))
+                    });
+
+                    // 更新显示区域
+                    self.show_description(cx, desc_option.as_deref(), html.as_deref());
 
                     cx.action(crate::kanban::KanbanActions::UpdateCardDescription {
-                        card_id: card_id.clone(),
-                        description: desc_option.clone(),
+                        card_id,
+                        description: desc_option,
+                        description_html: html,
                     });
-
-                    // 更新显示标签
-                    let display_text = desc_option.unwrap_or_else(|| "暂无描述".to_string());
-                    self.view
-                        .label(ids!(card_description_label))
-                        .set_text(cx, &display_text);
                 }
 
                 // 隐藏编辑区域，显示显示区域
@@ -486,7 +535,7 @@ impl Widget for CardInfoSection {
                     .view(ids!(description_edit_container))
                     .set_visible(cx, false);
                 self.view
-                    .label(ids!(card_description_label))
+                    .widget(ids!(card_description_view))
                     .set_visible(cx, true);
                 self.view
                     .button(ids!(edit_description_button))
@@ -508,7 +557,7 @@ impl Widget for CardInfoSection {
                     .view(ids!(description_edit_container))
                     .set_visible(cx, false);
                 self.view
-                    .label(ids!(card_description_label))
+                    .widget(ids!(card_description_view))
                     .set_visible(cx, true);
                 self.view
                     .button(ids!(edit_description_button))
@@ -517,39 +566,27 @@ impl Widget for CardInfoSection {
                 self.view.redraw(cx);
             }
 
-            // 处理状态按钮
-            if self.view.button(ids!(status_pending_btn)).clicked(actions) {
-                if let Some(card_id) = &self.card_id {
-                    log!("CardInfoSection: Status -> Pending");
-                    cx.action(crate::kanban::KanbanActions::UpdateCardStatus {
-                        card_id: card_id.clone(),
-                        status: crate::kanban::state::kanban_state::CardStatus::Pending,
-                    });
-                }
-            }
-
-            if self
+            // 编辑描述时实时更新预览
+            if let Some(text) = self
                 .view
-                .button(ids!(status_completed_btn))
-                .clicked(actions)
+                .text_input(ids!(card_description_input))
+                .changed(actions)
             {
-                if let Some(card_id) = &self.card_id {
-                    log!("CardInfoSection: Status -> Completed");
-                    cx.action(crate::kanban::KanbanActions::UpdateCardStatus {
-                        card_id: card_id.clone(),
-                        status: crate::kanban::state::kanban_state::CardStatus::Completed,
-                    });
-                }
+                self.update_preview(cx, scope, &text);
             }
 
-            if self.view.button(ids!(status_archived_btn)).clicked(actions) {
-                if let Some(card_id) = &self.card_id {
-                    log!("CardInfoSection: Status -> Archived");
-                    cx.action(crate::kanban::KanbanActions::UpdateCardStatus {
-                        card_id: card_id.clone(),
-                        status: crate::kanban::state::kanban_state::CardStatus::Archived,
-                    });
-                }
+            // 处理状态按钮
+            let clicked_status = STATUS_BUTTONS
+                .iter()
+                .zip(&self.status_button_ids)
+                .find(|(button_id, _)| self.view.button(*button_id).clicked(actions))
+                .map(|(_, status)| status.clone());
+            if let (Some(status), Some(card_id)) = (clicked_status, &self.card_id) {
+                log!("CardInfoSection: Status -> {}", status);
+                cx.action(crate::kanban::KanbanActions::UpdateCardStatus {
+                    card_id: card_id.clone(),
+                    status,
+                });
             }
         }
     }
@@ -571,16 +608,43 @@ impl Widget for CardInfoSection {
                         .label(ids!(card_title_label))
                         .set_text(cx, &card.title);
 
-                    let desc_text = card.description.as_deref().unwrap_or("暂无描述");
+                    self.show_description(
+                        cx,
+                        card.description.as_deref(),
+                        card.description_html.as_deref(),
+                    );
+                    let checklist_text = card
+                        .description
+                        .as_deref()
+                        .and_then(checklist_progress)
+                        .map(|(done, total)| format!("清单 {}/{}", done, total))
+                        .unwrap_or_default();
                     self.view
-                        .label(ids!(card_description_label))
-                        .set_text(cx, desc_text);
+                        .label(ids!(checklist_label))
+                        .set_text(cx, &checklist_text);
 
                     // 更新状态显示
-                    let status_display = card.status.display_name();
+                    let workflow = app_state.kanban_state.workflow(&card.space_id);
                     self.view
                         .label(ids!(card_status_label))
-                        .set_text(cx, status_display);
+                        .set_text(cx, workflow.display_name(&card.status));
+
+                    // 只显示工作流允许流转到的状态
+                    let next_statuses = workflow.next_statuses(&card.status);
+                    self.status_button_ids = next_statuses.iter().map(|s| s.id.clone()).collect();
+                    for (i, button_id) in STATUS_BUTTONS.iter().enumerate() {
+                        let button = self.view.button(*button_id);
+                        match next_statuses.get(i) {
+                            Some(status) => {
+                                button.set_text(cx, &status.name);
+                                button.set_visible(cx, true);
+                                if let Some(color) = parse_hex_color(&status.color) {
+                                    button.apply_over(cx, live! { draw_bg: { color: (color) } });
+                                }
+                            }
+                            None => button.set_visible(cx, false),
+                        }
+                    }
 
                     if should_update_card_id {
                         log!("CardInfoSection: Got card_id from AppState: {}", card_id);
@@ -607,12 +671,57 @@ impl CardInfoSectionRef {
             // 设置标题
             inner.view.label(ids!(card_title_label)).set_text(cx, title);
 
-            // 设置描述
-            let desc_text = description.unwrap_or("暂无描述");
-            inner
-                .view
-                .label(ids!(card_description_label))
-                .set_text(cx, desc_text);
+            // 设置描述（没有 HTML 时按纯文本显示）
+            inner.show_description(cx, description, None);
+        }
+    }
+}
+
+impl CardInfoSection {
+    /// 显示描述：有 HTML 时显示 HTML，否则显示原文
+    fn show_description(&mut self, cx: &mut Cx, description: Option<&str>, html: Option<&str>) {
+        let shown = (description.map(str::to_string), html.map(str::to_string));
+        if self.shown_description.as_ref() == Some(&shown) {
+            return;
+        }
+        let view = self.view.html_or_plaintext(ids!(card_description_view));
+        match (description, html) {
+            (_, Some(html)) => view.show_html(cx, html),
+            (Some(description), None) => view.show_plaintext(cx, description),
+            (None, None) => view.show_plaintext(cx, "暂无描述"),
         }
+        self.shown_description = Some(shown);
+    }
+
+    /// 用输入框中的 Markdown 更新预览
+    fn update_preview(&mut self, cx: &mut Cx, scope: &mut Scope, markdown: &str) {
+        let preview = self.view.html_or_plaintext(ids!(description_preview));
+        let html = scope
+            .data
+            .get::<crate::app::AppState>()
+            .and_then(|app_state| {
+                render_description(cx, markdown, &app_state.kanban_state, self.card_id.as_ref())
+            });
+        match html {
+            Some(html) => preview.show_html(cx, html),
+            None if markdown.trim().is_empty() => preview.show_plaintext(cx, "（预览）"),
+            None => preview.show_plaintext(cx, markdown),
+        }
+        self.view.redraw(cx);
+    }
+}
+
+/// 解析十六进制颜色
+pub(crate) fn parse_hex_color(hex: &str) -> Option<Vec4> {
+    let hex = hex.trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
     }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(vec4(
+        channel(0)? as f32 / 255.0,
+        channel(2)? as f32 / 255.0,
+        channel(4)? as f32 / 255.0,
+        1.0,
+    ))
 }
@@ -1,9 +1,25 @@
 use makepad_widgets::*;
 
+use matrix_sdk::ruma::OwnedMxcUri;
+
+use crate::shared::avatar::AvatarWidgetExt;
+use crate::kanban::media;
+use crate::kanban::state::permissions::KanbanPermission;
+
 live_design! {
     use link::theme::*;
     use link::widgets::*;
     use crate::shared::styles::*;
+    use crate::shared::avatar::Avatar;
+
+    AssigneeAvatar = <Avatar> {
+        width: 22.,
+        height: 22.,
+        visible: false,
+        text_view = { text = { draw_text: {
+            text_style: { font_size: 7.0 }
+        }}}
+    }
 
     pub CardItem = {{CardItem}} {
         <RoundedView> {
@@ -17,6 +33,14 @@ live_design! {
                 color: #FFFFFFFF
             }
 
+            // 封面图片（卡片附件中的一张图片）
+            cover_image = <Image> {
+                width: Fill,
+                height: 120,
+                fit: Biggest,
+                visible: false,
+            }
+
             // 标题显示区域
             title_display_container = <View> {
                 width: Fill,
@@ -68,10 +92,10 @@ live_design! {
                         }
                     }
 
-                    delete_card_btn = <Button> {
+                    archive_card_btn = <Button> {
                         width: 50,
                         height: 30,
-                        text: "删除",
+                        text: "归档",
                         draw_bg: {
                             color: #FF6B6BFF
                         }
@@ -139,10 +163,87 @@ live_design! {
                     }
                 }
             }
+
+            // 被未完成的卡片阻塞时显示
+            blocked_badge = <RoundedView> {
+                width: Fit,
+                height: Fit,
+                padding: {left: 6, right: 6, top: 2, bottom: 2},
+                visible: false,
+                draw_bg: {
+                    color: #FDECEA,
+                    radius: 3.0,
+                }
+
+                blocked_badge_label = <Label> {
+                    width: Fit,
+                    height: Fit,
+                    text: "⛔ 被阻塞",
+                    draw_text: {
+                        color: #EB5A46,
+                        text_style: {
+                            font_size: 11.0,
+                        }
+                    }
+                }
+            }
+
+            // 多选选中时显示
+            selected_badge = <RoundedView> {
+                width: Fit,
+                height: Fit,
+                padding: {left: 6, right: 6, top: 2, bottom: 2},
+                visible: false,
+                draw_bg: {
+                    color: #E4F0F6,
+                    radius: 3.0,
+                }
+
+                <Label> {
+                    width: Fit,
+                    height: Fit,
+                    text: "✔ 已选中",
+                    draw_text: {
+                        color: #0079BF,
+                        text_style: {
+                            font_size: 11.0,
+                        }
+                    }
+                }
+            }
+
+            // 负责人头像（最多显示 3 个，其余显示为 +N）
+            assignees_row = <View> {
+                width: Fill,
+                height: Fit,
+                flow: Right,
+                spacing: 4,
+                align: {y: 0.5},
+                visible: false,
+
+                assignee_avatar_0 = <AssigneeAvatar> {}
+                assignee_avatar_1 = <AssigneeAvatar> {}
+                assignee_avatar_2 = <AssigneeAvatar> {}
+
+                assignees_more_label = <Label> {
+                    width: Fit,
+                    height: Fit,
+                    text: "",
+                    draw_text: {
+                        color: #666666FF,
+                        text_style: {
+                            font_size: 11.0,
+                        }
+                    }
+                }
+            }
         }
     }
 }
 
+/// 卡片上最多显示的负责人头像数量
+const MAX_ASSIGNEE_AVATARS: usize = 3;
+
 #[derive(Live, LiveHook, Widget)]
 pub struct CardItem {
     #[deref]
@@ -157,6 +258,18 @@ pub struct CardItem {
     drag_start_pos: Option<DVec2>,
     #[rust]
     is_dragging: bool,
+    /// 在泳道视图中所在泳道的 key
+    #[rust]
+    lane: Option<String>,
+    /// 当前用户能否移动这张卡片（没有权限时不启动拖拽）
+    #[rust]
+    can_move: bool,
+    /// 已显示的封面图片
+    #[rust]
+    loaded_cover: Option<OwnedMxcUri>,
+    /// 封面缩略图还在下载，下载完成后需要重绘
+    #[rust]
+    cover_pending: bool,
 }
 
 impl Widget for CardItem {
@@ -164,6 +277,12 @@ impl Widget for CardItem {
         // 先让子组件处理事件（按钮、输入框等）
         self.view.handle_event(cx, event, scope);
 
+        if let Event::Signal = event {
+            if self.cover_pending {
+                self.redraw(cx);
+            }
+        }
+
         // 只有在不处于编辑模式时才处理拖拽事件
         if !self.is_editing {
             match event {
@@ -181,16 +300,34 @@ impl Widget for CardItem {
                         .area()
                         .rect(cx)
                         .contains(e.abs);
-                    let delete_btn_hit = self
+                    let archive_btn_hit = self
                         .view
-                        .button(ids!(delete_card_btn))
+                        .button(ids!(archive_card_btn))
                         .area()
                         .rect(cx)
                         .contains(e.abs);
 
-                    // 如果点击在按钮上，不启动拖拽
-                    if !edit_btn_hit && !detail_btn_hit && !delete_btn_hit {
-                        self.drag_start_pos = Some(e.abs);
+                    // 只在点击本卡片（且不在按钮上）时启动拖拽；
+                    // 按住 Shift / Ctrl / ⌘ 点击时改为多选
+                    let card_hit = self.view.area().rect(cx).contains(e.abs);
+                    if card_hit && !edit_btn_hit && !detail_btn_hit && !archive_btn_hit {
+                        let card_id = self
+                            .card_id
+                            .as_deref()
+                            .and_then(|id| matrix_sdk::ruma::RoomId::parse(id).ok());
+                        match card_id {
+                            Some(card_id) if e.modifiers.shift => {
+                                cx.action(crate::kanban::KanbanActions::ExtendCardSelection {
+                                    card_id,
+                                });
+                            }
+                            Some(card_id) if e.modifiers.control || e.modifiers.logo => {
+                                cx.action(crate::kanban::KanbanActions::ToggleCardSelection {
+                                    card_id,
+                                });
+                            }
+                            _ => self.drag_start_pos = Some(e.abs),
+                        }
                     }
                 }
                 Event::MouseMove(e) => {
@@ -198,7 +335,7 @@ impl Widget for CardItem {
                         let distance = (e.abs - start_pos).length();
 
                         // 移动距离超过 5px 时开始拖拽
-                        if distance > 5.0 && !self.is_dragging {
+                        if distance > 5.0 && !self.is_dragging && self.can_move {
                             if let Some(card_id_str) = &self.card_id {
                                 if let Ok(card_id) =
                                     matrix_sdk::ruma::RoomId::parse(card_id_str.as_str())
@@ -217,6 +354,7 @@ impl Widget for CardItem {
                                                     card_id: card_id.clone(),
                                                     space_id: card.space_id.clone(),
                                                     position: card.position,
+                                                    lane: self.lane.clone(),
                                                 },
                                             );
                                             self.is_dragging = true;
@@ -226,6 +364,7 @@ impl Widget for CardItem {
                             }
                         }
                     }
+                    self.update_drop_index(cx, e.abs, scope);
                 }
                 Event::MouseUp(_) => {
                     if self.is_dragging {
@@ -325,14 +464,14 @@ impl Widget for CardItem {
                 }
             }
 
-            // 处理删除按钮点击
-            if self.view.button(ids!(delete_card_btn)).clicked(actions) {
+            // 处理归档按钮点击
+            if self.view.button(ids!(archive_card_btn)).clicked(actions) {
                 if let Some(card_id) = &self.card_id {
-                    log!("CardItem: 删除卡片 {}", card_id);
+                    log!("CardItem: 归档卡片 {}", card_id);
 
                     // 解析 card_id 为 OwnedRoomId
                     if let Ok(room_id) = matrix_sdk::ruma::RoomId::parse(card_id.as_str()) {
-                        cx.action(crate::kanban::KanbanActions::DeleteCard { card_id: room_id });
+                        cx.action(crate::kanban::KanbanActions::ArchiveCard { card_id: room_id });
                     }
                 }
             }
@@ -343,8 +482,143 @@ impl Widget for CardItem {
         // 从 scope.props 获取 card_id (OwnedRoomId)
         if let Some(card_id) = scope.props.get::<matrix_sdk::ruma::OwnedRoomId>() {
             self.card_id = Some(card_id.to_string());
+
+            let state = scope
+                .data
+                .get::<crate::app::AppState>()
+                .map(|app_state| &app_state.kanban_state);
+            let card = state.and_then(|state| state.cards.get(card_id));
+            let assignees = card.map(|card| card.assignees.clone()).unwrap_or_default();
+            let cover = card.and_then(|card| card.cover.clone());
+            let blockers = state.map_or(0, |state| state.blockers(card_id).len());
+            self.show_assignees(cx, card_id, &assignees);
+            self.show_cover(cx, cover.as_ref());
+
+            // 隐藏没有权限的操作
+            let can = |permission| state.is_none_or(|state| state.can_on_card(card_id, permission));
+            self.can_move = can(KanbanPermission::MoveCard);
+            self.view
+                .button(ids!(edit_title_btn))
+                .set_visible(cx, can(KanbanPermission::EditCard));
+            self.view
+                .button(ids!(archive_card_btn))
+                .set_visible(cx, can(KanbanPermission::Archive));
+
+            let selected = state.is_some_and(|state| state.is_card_selected(card_id));
+            self.view
+                .view(ids!(selected_badge))
+                .set_visible(cx, selected);
+            self.view
+                .view(ids!(blocked_badge))
+                .set_visible(cx, blockers > 0);
+            if blockers > 0 {
+                self.view
+                    .label(ids!(blocked_badge_label))
+                    .set_text(cx, &format!("⛔ 被阻塞（{}）", blockers));
+            }
         }
 
         self.view.draw_walk(cx, scope, walk)
     }
 }
+
+impl CardItem {
+    /// 显示封面图片；缩略图下载完成前先隐藏
+    fn show_cover(&mut self, cx: &mut Cx, cover: Option<&OwnedMxcUri>) {
+        let image = self.view.image(ids!(cover_image));
+        if self.loaded_cover.as_ref() != cover {
+            let loaded = cover.is_some_and(|mxc_uri| media::load_thumbnail(cx, &image, mxc_uri));
+            self.loaded_cover = if loaded { cover.cloned() } else { None };
+        }
+        self.cover_pending = cover.is_some() && self.loaded_cover.is_none();
+        image.set_visible(cx, self.loaded_cover.is_some());
+    }
+
+    /// 显示负责人头像
+    fn show_assignees(
+        &mut self,
+        cx: &mut Cx,
+        card_id: &matrix_sdk::ruma::RoomId,
+        assignees: &[matrix_sdk::ruma::OwnedUserId],
+    ) {
+        self.view
+            .view(ids!(assignees_row))
+            .set_visible(cx, !assignees.is_empty());
+
+        let avatar_ids = [
+            ids!(assignee_avatar_0),
+            ids!(assignee_avatar_1),
+            ids!(assignee_avatar_2),
+        ];
+        for (i, avatar_id) in avatar_ids.into_iter().enumerate() {
+            let avatar = self.view.avatar(avatar_id);
+            match assignees.get(i) {
+                Some(user_id) => {
+                    avatar.set_visible(cx, true);
+                    avatar.set_avatar_and_get_username(cx, card_id, user_id, None, None, false);
+                }
+                None => avatar.set_visible(cx, false),
+            }
+        }
+
+        let more = assignees.len().saturating_sub(MAX_ASSIGNEE_AVATARS);
+        let more_text = if more > 0 {
+            format!("+{}", more)
+        } else {
+            String::new()
+        };
+        self.view
+            .label(ids!(assignees_more_label))
+            .set_text(cx, &more_text);
+    }
+
+    /// 拖拽其他卡片经过本卡片时，按鼠标在上半部分还是下半部分更新放置位置（泳道视图不排序）
+    fn update_drop_index(&self, cx: &mut Cx, abs: DVec2, scope: &mut Scope) {
+        if self.lane.is_some() {
+            return;
+        }
+        let Some(card_id) = self
+            .card_id
+            .as_deref()
+            .and_then(|id| matrix_sdk::ruma::RoomId::parse(id).ok())
+        else {
+            return;
+        };
+        let Some(app_state) = scope.data.get::<crate::app::AppState>() else {
+            return;
+        };
+        let state = &app_state.kanban_state;
+        let Some(drag_state) = &state.drag_state else {
+            return;
+        };
+        if drag_state.card_id == card_id {
+            return;
+        }
+        let rect = self.view.area().rect(cx);
+        if !rect.contains(abs) {
+            return;
+        }
+        let after = abs.y > rect.pos.y + rect.size.y / 2.0;
+        let current_user = crate::sliding_sync::current_user_id();
+        let drop_index = state.drop_index_at(
+            &card_id,
+            after,
+            &drag_state.card_id,
+            current_user.as_deref(),
+        );
+        if let Some(drop_index) = drop_index
+            && drag_state.drop_index.as_ref() != Some(&drop_index)
+        {
+            cx.action(crate::kanban::KanbanActions::UpdateDropIndex { drop_index });
+        }
+    }
+}
+
+impl CardItemRef {
+    /// 设置卡片所在的泳道（只在泳道视图中使用）
+    pub fn set_lane(&self, lane: Option<String>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.lane = lane;
+        }
+    }
+}
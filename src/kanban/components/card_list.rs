@@ -115,10 +115,11 @@ impl Widget for CardList {
         };
 
         // 获取卡片数据并克隆，避免借用冲突
+        let current_user = crate::sliding_sync::current_user_id();
         let cards: Vec<_> = if let Some(app_state) = scope.data.get::<crate::app::AppState>() {
             app_state
                 .kanban_state
-                .list_cards(&space_id)
+                .visible_list_cards(&space_id, current_user.as_deref())
                 .into_iter()
                 .map(|c| c.clone())
                 .collect()
@@ -1,21 +1,28 @@
 use makepad_widgets::*;
 
+use crate::kanban::components::card_detail_view::CardTimelineViewWidgetRefExt;
+
 live_design! {
     use link::theme::*;
     use link::widgets::*;
     use crate::kanban::components::modal_header::ModalHeader;
     use crate::kanban::components::card_info_section::CardInfoSection;
     use crate::kanban::components::tag_section::TagSection;
+    use crate::kanban::components::assignee_section::AssigneeSection;
     use crate::kanban::components::tag_management_modal::TagManagementModal;
     use crate::kanban::components::todo_section::TodoSection;
     use crate::kanban::components::endtime_section::EndTimeSection;
-    use crate::kanban::components::active_section::ActiveSection;
+    use crate::kanban::components::recurrence_section::RecurrenceSection;
+    use crate::kanban::components::time_tracking_section::TimeTrackingSection;
+    use crate::kanban::components::card_detail_view::CardTimelineView;
+    use crate::kanban::components::card_detail_view::CardLinksView;
+    use crate::kanban::components::card_detail_view::CardAttachmentsView;
 
     pub CardDetailModal = {{CardDetailModal}} {
         modal = <Modal> {
             content: <RoundedView> {
-                width: 800,
-                height: 600,
+                width: 960,
+                height: 680,
                 padding: 20,
                 flow: Down,
                 spacing: 15,
@@ -46,13 +53,18 @@ live_design! {
 
                             <CardInfoSection> {}
                             <TagSection> {}
+                            <AssigneeSection> {}
                             <EndTimeSection> {}
+                            <RecurrenceSection> {}
+                            <TimeTrackingSection> {}
+                            <CardLinksView> {}
+                            <CardAttachmentsView> {}
                             <TodoSection> {}
                         }
                     }
 
-                    <ActiveSection> {
-                        width: 350,
+                    card_timeline = <CardTimelineView> {
+                        width: Fill,
                         height: Fill,
                     }
                 }
@@ -70,6 +82,18 @@ pub struct CardDetailModal {
 impl Widget for CardDetailModal {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
         self.view.handle_event(cx, event, scope);
+
+        // 点击外部关闭模态框时，同样释放卡片时间线
+        if let Event::Actions(actions) = event {
+            if actions
+                .iter()
+                .any(|a| matches!(a.downcast_ref(), Some(ModalAction::Dismissed)))
+            {
+                self.view
+                    .card_timeline_view(ids!(modal.content.card_timeline))
+                    .clear();
+            }
+        }
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
@@ -86,6 +110,10 @@ impl CardDetailModalRef {
 
     pub fn close(&self, cx: &mut Cx) {
         if let Some(inner) = self.borrow() {
+            inner
+                .view
+                .card_timeline_view(ids!(modal.content.card_timeline))
+                .clear();
             inner.view.modal(ids!(modal)).close(cx);
         }
     }
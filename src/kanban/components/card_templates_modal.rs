@@ -0,0 +1,260 @@
+use makepad_widgets::*;
+use matrix_sdk::ruma::OwnedRoomId;
+
+live_design! {
+    use link::theme::*;
+    use link::widgets::*;
+
+    // 单个卡片模板
+    CardTemplateItem = {{CardTemplateItem}} {
+        width: Fill,
+        height: Fit,
+        flow: Right,
+        spacing: 10,
+        align: {y: 0.5},
+        padding: {top: 6, bottom: 6, left: 8, right: 8},
+        show_bg: true,
+        draw_bg: {
+            color: #F4F5F7
+        }
+
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Down,
+            spacing: 3,
+
+            template_name_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "模板",
+                draw_text: {
+                    wrap: Ellipsis,
+                    color: #172B4D,
+                    text_style: <THEME_FONT_BOLD>{font_size: 14}
+                }
+            }
+
+            template_summary_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "",
+                draw_text: {
+                    wrap: Word,
+                    color: #5E6C84,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
+            }
+        }
+
+        create_button = <Button> {
+            width: 60,
+            height: 28,
+            text: "创建",
+        }
+
+        delete_button = <Button> {
+            width: 60,
+            height: 28,
+            text: "删除",
+        }
+    }
+
+    pub CardTemplatesModal = {{CardTemplatesModal}} {
+        width: Fit,
+        height: Fit,
+
+        <RoundedView> {
+            width: 520,
+            height: Fit,
+            padding: 20,
+            flow: Down,
+            spacing: 15,
+            draw_bg: {
+                color: #FFFFFF
+            }
+
+            // 标题
+            <Label> {
+                width: Fill,
+                height: Fit,
+                text: "卡片模板",
+                draw_text: {
+                    text_style: <THEME_FONT_BOLD>{font_size: 18}
+                    color: #172B4D
+                }
+            }
+
+            empty_label = <Label> {
+                width: Fill,
+                height: Fit,
+                visible: false,
+                text: "还没有模板，可以在卡片详情中把卡片保存为模板",
+                draw_text: {
+                    wrap: Word,
+                    color: #5E6C84,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 13}
+                }
+            }
+
+            templates_list = <PortalList> {
+                width: Fill,
+                height: 300,
+                flow: Down,
+                spacing: 5,
+
+                CardTemplateItem = <CardTemplateItem> {}
+            }
+
+            // 按钮区域
+            <View> {
+                width: Fill,
+                height: Fit,
+                flow: Right,
+                align: {x: 1.0, y: 0.5}
+
+                close_button = <Button> {
+                    width: 80,
+                    height: 36,
+                    text: "关闭",
+                    draw_bg: {
+                        color: #95A5A6,
+                        radius: 3.0,
+                    }
+                    draw_text: {
+                        color: #FFFFFF,
+                        text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct CardTemplateItem {
+    #[deref]
+    view: View,
+    #[rust]
+    space_id: Option<OwnedRoomId>,
+    #[rust]
+    template_id: String,
+}
+
+impl Widget for CardTemplateItem {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if let Event::Actions(actions) = event {
+            let Some(space_id) = self.space_id.clone() else {
+                return;
+            };
+
+            if self.view.button(ids!(create_button)).clicked(actions) {
+                log!("CardTemplateItem: 用模板 {} 创建卡片", self.template_id);
+                cx.action(crate::kanban::KanbanActions::CreateCardFromTemplate {
+                    space_id: space_id.clone(),
+                    template_id: self.template_id.clone(),
+                });
+            }
+
+            if self.view.button(ids!(delete_button)).clicked(actions) {
+                log!("CardTemplateItem: 删除模板 {}", self.template_id);
+                cx.action(crate::kanban::KanbanActions::DeleteCardTemplate {
+                    space_id,
+                    template_id: self.template_id.clone(),
+                });
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct CardTemplatesModal {
+    #[deref]
+    view: View,
+    #[rust]
+    space_id: Option<OwnedRoomId>,
+}
+
+impl Widget for CardTemplatesModal {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if let Event::Actions(actions) = event {
+            if self.view.button(ids!(close_button)).clicked(actions) {
+                cx.action(crate::kanban::KanbanActions::CloseCardTemplates);
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        let templates = scope
+            .data
+            .get::<crate::app::AppState>()
+            .zip(self.space_id.as_ref())
+            .and_then(|(app_state, space_id)| {
+                app_state.kanban_state.space_templates.get(space_id).cloned()
+            })
+            .unwrap_or_default();
+
+        self.view
+            .label(ids!(empty_label))
+            .set_visible(cx, templates.is_empty());
+
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                list.set_item_range(cx, 0, templates.len());
+
+                while let Some(idx) = list.next_visible_item(cx) {
+                    let Some(template) = templates.get(idx) else {
+                        continue;
+                    };
+
+                    let item_widget = list.item(cx, idx, live_id!(CardTemplateItem));
+                    item_widget
+                        .label(ids!(template_name_label))
+                        .set_text(cx, &template.name);
+
+                    // 摘要：标题 · 待办数 · 截止时间 · 周期
+                    let mut summary = vec![template.title.clone()];
+                    if !template.todos.is_empty() {
+                        summary.push(format!("{} 个待办", template.todos.len()));
+                    }
+                    if let Some(days) = template.due_in_days {
+                        summary.push(format!("{} 天后到期", days));
+                    }
+                    if let Some(rule) = &template.recurrence {
+                        summary.push(rule.describe());
+                    }
+                    item_widget
+                        .label(ids!(template_summary_label))
+                        .set_text(cx, &summary.join(" · "));
+
+                    if let Some(mut template_item) =
+                        item_widget.as_card_template_item().borrow_mut()
+                    {
+                        template_item.space_id = self.space_id.clone();
+                        template_item.template_id = template.id.clone();
+                    }
+
+                    item_widget.draw_all(cx, &mut Scope::empty());
+                }
+            }
+        }
+        DrawStep::done()
+    }
+}
+
+impl CardTemplatesModalRef {
+    pub fn set_space(&self, cx: &mut Cx, space_id: OwnedRoomId) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.space_id = Some(space_id);
+            inner.view.redraw(cx);
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use makepad_widgets::*;
+use matrix_sdk::ruma::OwnedRoomId;
+
+live_design! {
+    use link::theme::*;
+    use link::widgets::*;
+
+    CreateCardInput = <TextInput> {
+        width: Fill,
+        height: 40,
+        text: "",
+        draw_text: {
+            color: #172B4D,
+            text_style: <THEME_FONT_REGULAR>{font_size: 14}
+        }
+        draw_bg: {
+            color: #FFFFFF,
+            border_color: #DFE1E6,
+            border_width: 2.0,
+            radius: 3.0,
+        }
+        draw_cursor: {
+            color: #172B4D
+        }
+    }
+
+    // 从聊天消息创建卡片：选择列表，编辑预填的标题和描述
+    pub CreateCardModal = {{CreateCardModal}} {
+        width: Fit,
+        height: Fit,
+
+        <RoundedView> {
+            width: 480,
+            height: Fit,
+            padding: 20,
+            flow: Down,
+            spacing: 15,
+            draw_bg: {
+                color: #FFFFFF
+            }
+
+            // 标题
+            <Label> {
+                width: Fill,
+                height: Fit,
+                text: "从消息创建卡片",
+                draw_text: {
+                    text_style: <THEME_FONT_BOLD>{font_size: 18}
+                    color: #172B4D
+                }
+            }
+
+            // 目标列表，点击切换
+            list_button = <Button> {
+                width: Fill,
+                height: 36,
+                text: "列表",
+            }
+
+            title_input = <CreateCardInput> {
+                empty_text: "卡片标题",
+            }
+
+            description_input = <CreateCardInput> {
+                height: 140,
+                empty_text: "描述",
+                draw_text: {
+                    wrap: Word,
+                }
+            }
+
+            // 按钮区域
+            <View> {
+                width: Fill,
+                height: Fit,
+                flow: Right,
+                spacing: 10,
+                align: {x: 1.0, y: 0.5}
+
+                cancel_button = <Button> {
+                    width: 80,
+                    height: 36,
+                    text: "取消",
+                    draw_bg: {
+                        color: #95A5A6,
+                        radius: 3.0,
+                    }
+                    draw_text: {
+                        color: #FFFFFF,
+                        text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                    }
+                }
+
+                create_button = <Button> {
+                    width: 80,
+                    height: 36,
+                    text: "创建",
+                    draw_bg: {
+                        color: #0079BF,
+                        radius: 3.0,
+                    }
+                    draw_text: {
+                        color: #FFFFFF,
+                        text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct CreateCardModal {
+    #[deref]
+    view: View,
+    /// 可选的列表（绘制时从 `KanbanAppState` 更新）
+    #[rust]
+    lists: Vec<(OwnedRoomId, String)>,
+    /// 当前选中的列表在 `lists` 中的下标
+    #[rust]
+    list_index: usize,
+}
+
+impl Widget for CreateCardModal {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        let Event::Actions(actions) = event else {
+            return;
+        };
+
+        if self.view.button(ids!(list_button)).clicked(actions) && !self.lists.is_empty() {
+            self.list_index = (self.list_index + 1) % self.lists.len();
+            self.view.redraw(cx);
+        }
+
+        if self.view.button(ids!(cancel_button)).clicked(actions) {
+            cx.action(crate::kanban::KanbanActions::CloseCreateCardModal);
+        }
+
+        if self.view.button(ids!(create_button)).clicked(actions) {
+            let Some((space_id, _)) = self.lists.get(self.list_index) else {
+                log!("⚠️ CreateCardModal: 没有可用的列表");
+                return;
+            };
+            let title = self.view.text_input(ids!(title_input)).text();
+            let title = match title.trim() {
+                "" => "新卡片".to_string(),
+                title => title.to_string(),
+            };
+            let description = self.view.text_input(ids!(description_input)).text();
+            let description = Some(description.trim().to_string()).filter(|d| !d.is_empty());
+            log!("CreateCardModal: 在列表 {} 中创建卡片 '{}'", space_id, title);
+            cx.action(crate::kanban::KanbanActions::CreateCardFromChat {
+                space_id: space_id.clone(),
+                title,
+                description,
+            });
+            cx.action(crate::kanban::KanbanActions::CloseCreateCardModal);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if let Some(app_state) = scope.data.get::<crate::app::AppState>() {
+            self.lists = app_state
+                .kanban_state
+                .card_target_lists()
+                .into_iter()
+                .map(|list| (list.id.clone(), list.name.clone()))
+                .collect();
+        }
+        if self.list_index >= self.lists.len() {
+            self.list_index = 0;
+        }
+
+        let list_text = match self.lists.get(self.list_index) {
+            Some((_, name)) if self.lists.len() > 1 => format!("列表：{}（点击切换）", name),
+            Some((_, name)) => format!("列表：{}", name),
+            None => "还没有列表，请先在看板中创建列表".to_string(),
+        };
+        self.view.button(ids!(list_button)).set_text(cx, &list_text);
+
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+impl CreateCardModalRef {
+    /// 用消息内容预填标题和描述
+    pub fn set_draft(&self, cx: &mut Cx, title: &str, description: &str) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner
+                .view
+                .text_input(ids!(title_input))
+                .set_text(cx, title);
+            inner
+                .view
+                .text_input(ids!(description_input))
+                .set_text(cx, description);
+            inner.view.redraw(cx);
+        }
+    }
+}
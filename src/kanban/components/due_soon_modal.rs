@@ -0,0 +1,363 @@
+use makepad_widgets::*;
+
+use crate::kanban::reminders::{self, ReminderSettings};
+
+live_design! {
+    use link::theme::*;
+    use link::widgets::*;
+
+    // 单张即将到期的卡片
+    DueSoonItem = {{DueSoonItem}} {
+        width: Fill,
+        height: Fit,
+        flow: Right,
+        spacing: 10,
+        align: {y: 0.5},
+        padding: {top: 6, bottom: 6, left: 8, right: 8},
+        show_bg: true,
+        draw_bg: {
+            color: #F4F5F7
+        }
+
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Down,
+            spacing: 3,
+
+            card_title_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "卡片",
+                draw_text: {
+                    wrap: Ellipsis,
+                    color: #172B4D,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                }
+            }
+
+            due_time_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "",
+                draw_text: {
+                    color: #5E6C84,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
+            }
+        }
+
+        open_card_button = <Button> {
+            width: 60,
+            height: 28,
+            text: "查看",
+        }
+    }
+
+    pub DueSoonModal = {{DueSoonModal}} {
+        width: Fit,
+        height: Fit,
+
+        <RoundedView> {
+            width: 520,
+            height: Fit,
+            padding: 20,
+            flow: Down,
+            spacing: 15,
+            draw_bg: {
+                color: #FFFFFF
+            }
+
+            // 标题
+            <Label> {
+                width: Fill,
+                height: Fit,
+                text: "即将到期",
+                draw_text: {
+                    text_style: <THEME_FONT_BOLD>{font_size: 18}
+                    color: #172B4D
+                }
+            }
+
+            empty_label = <Label> {
+                width: Fill,
+                height: Fit,
+                visible: false,
+                text: "没有即将到期的卡片",
+                draw_text: {
+                    color: #5E6C84,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 13}
+                }
+            }
+
+            due_soon_list = <PortalList> {
+                width: Fill,
+                height: 260,
+                flow: Down,
+                spacing: 5,
+
+                DueSoonItem = <DueSoonItem> {}
+            }
+
+            // 提醒设置
+            <Label> {
+                width: Fill,
+                height: Fit,
+                text: "提前提醒（分钟，逗号分隔，0 表示到期时提醒）",
+                draw_text: {
+                    color: #5E6C84,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
+            }
+
+            <View> {
+                width: Fill,
+                height: Fit,
+                flow: Right,
+                spacing: 10,
+                align: {y: 0.5},
+
+                lead_times_input = <TextInput> {
+                    width: Fill,
+                    height: 36,
+                    empty_text: "1440, 60, 0",
+                    draw_text: {
+                        color: #172B4D,
+                        text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                    }
+                    draw_bg: {
+                        color: #FFFFFF,
+                        border_color: #DFE1E6,
+                        border_width: 2.0,
+                        radius: 3.0,
+                    }
+                }
+
+                os_notifications_button = <Button> {
+                    width: 120,
+                    height: 36,
+                    text: "系统通知：开",
+                }
+            }
+
+            error_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "",
+                draw_text: {
+                    color: #EB5A46,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
+            }
+
+            // 按钮区域
+            <View> {
+                width: Fill,
+                height: Fit,
+                flow: Right,
+                spacing: 10,
+                align: {x: 1.0, y: 0.5}
+
+                close_button = <Button> {
+                    width: 80,
+                    height: 36,
+                    text: "关闭",
+                    draw_bg: {
+                        color: #95A5A6,
+                        radius: 3.0,
+                    }
+                    draw_text: {
+                        color: #FFFFFF,
+                        text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                    }
+                }
+
+                save_button = <Button> {
+                    width: 80,
+                    height: 36,
+                    text: "保存",
+                    draw_bg: {
+                        color: #0079BF,
+                        radius: 3.0,
+                    }
+                    draw_text: {
+                        color: #FFFFFF,
+                        text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct DueSoonItem {
+    #[deref]
+    view: View,
+    #[rust]
+    card_id: Option<matrix_sdk::ruma::OwnedRoomId>,
+}
+
+impl Widget for DueSoonItem {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if let Event::Actions(actions) = event {
+            if self.view.button(ids!(open_card_button)).clicked(actions) {
+                if let Some(card_id) = self.card_id.clone() {
+                    log!("DueSoonItem: 打开卡片 {}", card_id);
+                    cx.action(crate::kanban::KanbanActions::CloseDueSoon);
+                    cx.action(crate::kanban::KanbanActions::ShowCardDetail { card_id });
+                }
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct DueSoonModal {
+    #[deref]
+    view: View,
+    /// 编辑中的系统通知开关
+    #[rust]
+    os_notifications: bool,
+}
+
+impl Widget for DueSoonModal {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if let Event::Actions(actions) = event {
+            if self.view.button(ids!(os_notifications_button)).clicked(actions) {
+                self.os_notifications = !self.os_notifications;
+                self.update_os_notifications_button(cx);
+            }
+
+            if self.view.button(ids!(save_button)).clicked(actions) {
+                let text = self.view.text_input(ids!(lead_times_input)).text();
+                match ReminderSettings::parse_lead_times(&text) {
+                    Ok(lead_times_mins) => {
+                        log!("DueSoonModal: 保存提醒设置 {:?}", lead_times_mins);
+                        self.view.label(ids!(error_label)).set_text(cx, "");
+                        cx.action(crate::kanban::KanbanActions::UpdateReminderSettings {
+                            settings: ReminderSettings {
+                                lead_times_mins,
+                                os_notifications: self.os_notifications,
+                            },
+                        });
+                    }
+                    Err(e) => {
+                        log!("⚠️ DueSoonModal: 提醒时间无效: {}", e);
+                        self.view
+                            .label(ids!(error_label))
+                            .set_text(cx, &format!("{}", e));
+                        self.view.redraw(cx);
+                    }
+                }
+            }
+
+            if self.view.button(ids!(close_button)).clicked(actions) {
+                cx.action(crate::kanban::KanbanActions::CloseDueSoon);
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        // (卡片 ID, 标题, 截止时间)
+        let cards: Vec<_> = scope
+            .data
+            .get::<crate::app::AppState>()
+            .map(|app_state| {
+                let state = &app_state.kanban_state;
+                state
+                    .due_soon_cards(state.reminder_settings.due_soon_window_secs())
+                    .into_iter()
+                    .filter_map(|card| {
+                        card.end_time
+                            .map(|end_time| (card.id.clone(), card.title.clone(), end_time))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        self.view
+            .label(ids!(empty_label))
+            .set_visible(cx, cards.is_empty());
+
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                list.set_item_range(cx, 0, cards.len());
+
+                while let Some(idx) = list.next_visible_item(cx) {
+                    let Some((card_id, title, end_time)) = cards.get(idx) else {
+                        continue;
+                    };
+
+                    let item_widget = list.item(cx, idx, live_id!(DueSoonItem));
+                    item_widget.label(ids!(card_title_label)).set_text(cx, title);
+
+                    let overdue = *end_time < now;
+                    let due_label = item_widget.label(ids!(due_time_label));
+                    due_label.set_text(
+                        cx,
+                        &format!(
+                            "{} · {}",
+                            reminders::format_due_time(*end_time),
+                            reminders::format_remaining(*end_time, now)
+                        ),
+                    );
+                    // 已过期显示为红色
+                    let color = if overdue {
+                        vec4(0.922, 0.353, 0.275, 1.0)
+                    } else {
+                        vec4(0.369, 0.424, 0.518, 1.0)
+                    };
+                    due_label.apply_over(cx, live! { draw_text: { color: (color) } });
+
+                    if let Some(mut due_item) = item_widget.as_due_soon_item().borrow_mut() {
+                        due_item.card_id = Some(card_id.clone());
+                    }
+
+                    item_widget.draw_all(cx, &mut Scope::empty());
+                }
+            }
+        }
+        DrawStep::done()
+    }
+}
+
+impl DueSoonModal {
+    fn update_os_notifications_button(&mut self, cx: &mut Cx) {
+        self.view.button(ids!(os_notifications_button)).set_text(
+            cx,
+            if self.os_notifications {
+                "系统通知：开"
+            } else {
+                "系统通知：关"
+            },
+        );
+    }
+}
+
+impl DueSoonModalRef {
+    pub fn set_data(&self, cx: &mut Cx, settings: &ReminderSettings) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner
+                .view
+                .text_input(ids!(lead_times_input))
+                .set_text(cx, &settings.lead_times_text());
+            inner.view.label(ids!(error_label)).set_text(cx, "");
+            inner.os_notifications = settings.os_notifications;
+            inner.update_os_notifications_button(cx);
+        }
+    }
+}
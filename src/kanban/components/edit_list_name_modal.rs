@@ -57,6 +57,22 @@ live_design! {
                 spacing: 10,
                 align: {x: 1.0, y: 0.5}
 
+                archive_list_button = <Button> {
+                    width: 100,
+                    height: 36,
+                    text: "归档列表",
+                    draw_bg: {
+                        color: #FF9F1A,
+                        radius: 3.0,
+                    }
+                    draw_text: {
+                        color: #FFFFFF,
+                        text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                    }
+                }
+
+                <View> { width: Fill, height: 1 }
+
                 cancel_button = <Button> {
                     width: 80,
                     height: 36,
@@ -137,6 +153,17 @@ impl Widget for EditListNameModal {
                 cx.action(crate::kanban::KanbanActions::CloseEditListNameModal);
             }
 
+            // 处理归档列表按钮
+            if self.view.button(ids!(archive_list_button)).clicked(actions) {
+                if let Some(list_id) = &self.list_id {
+                    log!("EditListNameModal: 归档列表 {}", list_id);
+                    cx.action(crate::kanban::KanbanActions::ArchiveList {
+                        list_id: list_id.clone(),
+                    });
+                    cx.action(crate::kanban::KanbanActions::CloseEditListNameModal);
+                }
+            }
+
             // 处理回车键
             if let Some((text, _)) = self
                 .view
@@ -0,0 +1,277 @@
+use makepad_widgets::*;
+
+use crate::kanban::saved_filters::SavedFilter;
+use crate::kanban::state::calendar::BoardViewMode;
+use crate::kanban::state::swimlane::SwimlaneGrouping;
+
+live_design! {
+    use link::theme::*;
+    use link::widgets::*;
+
+    // 保存的过滤器按钮
+    SavedFilterChip = <Button> {
+        width: Fit,
+        height: 30,
+        padding: {left: 10, right: 10},
+        visible: false,
+        text: "过滤器",
+        draw_bg: {
+            color: #DFE1E6,
+            radius: 3.0,
+        }
+        draw_text: {
+            color: #172B4D,
+            text_style: <THEME_FONT_REGULAR>{font_size: 12}
+        }
+    }
+
+    // 看板过滤栏
+    pub KanbanFilterBar = {{KanbanFilterBar}} {
+        width: Fill,
+        height: Fit,
+        flow: Down,
+        spacing: 5,
+        margin: {bottom: 10},
+
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 10,
+            align: {y: 0.5},
+
+            filter_input = <TextInput> {
+                width: Fill,
+                height: 36,
+                empty_text: "过滤卡片：文本 tag:标签 status:状态 @me overdue todo:open due:<2025-12-31",
+                draw_text: {
+                    color: #172B4D,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 13}
+                }
+                draw_bg: {
+                    color: #FFFFFF,
+                    border_color: #DFE1E6,
+                    border_width: 2.0,
+                    radius: 3.0,
+                }
+            }
+
+            clear_filter_button = <Button> {
+                width: 60,
+                height: 36,
+                text: "清除",
+            }
+
+            filter_name_input = <TextInput> {
+                width: 140,
+                height: 36,
+                empty_text: "过滤器名称",
+                draw_text: {
+                    color: #172B4D,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 13}
+                }
+                draw_bg: {
+                    color: #FFFFFF,
+                    border_color: #DFE1E6,
+                    border_width: 2.0,
+                    radius: 3.0,
+                }
+            }
+
+            save_filter_button = <Button> {
+                width: 60,
+                height: 36,
+                text: "保存",
+            }
+
+            delete_filter_button = <Button> {
+                width: 60,
+                height: 36,
+                text: "删除",
+            }
+
+            // 切换泳道分组方式
+            swimlane_button = <Button> {
+                width: 110,
+                height: 36,
+                text: "泳道：无",
+            }
+
+            // 切换看板 / 日历 / 时间线视图
+            view_mode_button = <Button> {
+                width: 110,
+                height: 36,
+                text: "视图：看板",
+            }
+        }
+
+        // 查询解析错误
+        filter_error_label = <Label> {
+            width: Fill,
+            height: Fit,
+            visible: false,
+            text: "",
+            draw_text: {
+                color: #EB5A46,
+                text_style: <THEME_FONT_REGULAR>{font_size: 12}
+            }
+        }
+
+        // 保存的过滤器
+        saved_filters_row = <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 8,
+            visible: false,
+
+            saved_filter_0 = <SavedFilterChip> {}
+            saved_filter_1 = <SavedFilterChip> {}
+            saved_filter_2 = <SavedFilterChip> {}
+            saved_filter_3 = <SavedFilterChip> {}
+            saved_filter_4 = <SavedFilterChip> {}
+            saved_filter_5 = <SavedFilterChip> {}
+        }
+    }
+}
+
+/// 保存的过滤器按钮（最多显示 6 个）
+const SAVED_FILTER_CHIPS: &[&[LiveId]] = ids_array!(
+    saved_filter_0,
+    saved_filter_1,
+    saved_filter_2,
+    saved_filter_3,
+    saved_filter_4,
+    saved_filter_5,
+);
+
+#[derive(Live, LiveHook, Widget)]
+pub struct KanbanFilterBar {
+    #[deref]
+    view: View,
+    /// 当前显示的保存过滤器（与按钮一一对应）
+    #[rust]
+    saved_filters: Vec<SavedFilter>,
+    /// 当前的泳道分组方式
+    #[rust]
+    swimlane_grouping: SwimlaneGrouping,
+    /// 当前的看板显示方式
+    #[rust]
+    board_view_mode: BoardViewMode,
+}
+
+impl Widget for KanbanFilterBar {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        let Event::Actions(actions) = event else {
+            return;
+        };
+
+        let filter_input = self.view.text_input(ids!(filter_input));
+
+        // 输入时实时过滤
+        if let Some(query) = filter_input.changed(actions) {
+            cx.action(crate::kanban::KanbanActions::SetCardFilter { query });
+        }
+
+        if self.view.button(ids!(clear_filter_button)).clicked(actions) {
+            filter_input.set_text(cx, "");
+            cx.action(crate::kanban::KanbanActions::SetCardFilter {
+                query: String::new(),
+            });
+        }
+
+        if self.view.button(ids!(save_filter_button)).clicked(actions) {
+            let name_input = self.view.text_input(ids!(filter_name_input));
+            let query = filter_input.text();
+            let name = match name_input.text().trim() {
+                "" => query.trim().to_string(),
+                name => name.to_string(),
+            };
+            if !name.is_empty() {
+                log!("KanbanFilterBar: 保存过滤器 '{}'", name);
+                cx.action(crate::kanban::KanbanActions::SaveCardFilter { name });
+                name_input.set_text(cx, "");
+            }
+        }
+
+        // 删除与当前查询相同的保存过滤器
+        if self.view.button(ids!(delete_filter_button)).clicked(actions) {
+            let query = filter_input.text();
+            if let Some(saved) = self.saved_filters.iter().find(|f| f.query == query.trim()) {
+                log!("KanbanFilterBar: 删除过滤器 '{}'", saved.name);
+                cx.action(crate::kanban::KanbanActions::DeleteSavedFilter {
+                    name: saved.name.clone(),
+                });
+            }
+        }
+
+        if self.view.button(ids!(swimlane_button)).clicked(actions) {
+            let grouping = self.swimlane_grouping.next();
+            log!("KanbanFilterBar: 泳道分组切换为 {:?}", grouping);
+            cx.action(crate::kanban::KanbanActions::SetSwimlaneGrouping { grouping });
+        }
+
+        if self.view.button(ids!(view_mode_button)).clicked(actions) {
+            let mode = self.board_view_mode.next();
+            log!("KanbanFilterBar: 视图切换为 {:?}", mode);
+            cx.action(crate::kanban::KanbanActions::SetBoardViewMode { mode });
+        }
+
+        // 应用保存的过滤器
+        for (chip, saved) in SAVED_FILTER_CHIPS.iter().zip(&self.saved_filters) {
+            if self.view.button(*chip).clicked(actions) {
+                log!("KanbanFilterBar: 应用过滤器 '{}'", saved.name);
+                filter_input.set_text(cx, &saved.query);
+                cx.action(crate::kanban::KanbanActions::SetCardFilter {
+                    query: saved.query.clone(),
+                });
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if let Some(app_state) = scope.data.get::<crate::app::AppState>() {
+            let kanban_state = &app_state.kanban_state;
+
+            let error_label = self.view.label(ids!(filter_error_label));
+            match &kanban_state.filter_error {
+                Some(error) => {
+                    error_label.set_text(cx, error);
+                    error_label.set_visible(cx, true);
+                }
+                None => error_label.set_visible(cx, false),
+            }
+
+            self.swimlane_grouping = kanban_state.swimlane_grouping;
+            self.view.button(ids!(swimlane_button)).set_text(
+                cx,
+                &format!("泳道：{}", self.swimlane_grouping.display_name()),
+            );
+
+            self.board_view_mode = kanban_state.board_view_mode;
+            self.view.button(ids!(view_mode_button)).set_text(
+                cx,
+                &format!("视图：{}", self.board_view_mode.display_name()),
+            );
+
+            self.saved_filters = kanban_state.saved_filters.clone();
+            self.view
+                .view(ids!(saved_filters_row))
+                .set_visible(cx, !self.saved_filters.is_empty());
+            for (i, chip) in SAVED_FILTER_CHIPS.iter().enumerate() {
+                let button = self.view.button(*chip);
+                match self.saved_filters.get(i) {
+                    Some(saved) => {
+                        button.set_text(cx, &saved.name);
+                        button.set_visible(cx, true);
+                    }
+                    None => button.set_visible(cx, false),
+                }
+            }
+        }
+
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
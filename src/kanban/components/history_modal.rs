@@ -0,0 +1,296 @@
+use makepad_widgets::*;
+
+use crate::kanban::state::history::{HistoryItem, HistoryPanel};
+
+live_design! {
+    use link::theme::*;
+    use link::widgets::*;
+
+    // 一个历史版本：保存时间、保存者和与上一个版本相比的变化
+    HistoryEntry = {{HistoryEntry}} {
+        width: Fill,
+        height: Fit,
+        flow: Right,
+        spacing: 10,
+        align: {y: 0.0},
+        padding: {top: 6, bottom: 6, left: 8, right: 8},
+        show_bg: true,
+        draw_bg: {
+            color: #F4F5F7
+        }
+
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Down,
+            spacing: 3,
+
+            header_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "",
+                draw_text: {
+                    color: #5E6C84,
+                    text_style: <THEME_FONT_BOLD>{font_size: 12}
+                }
+            }
+
+            changes_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "",
+                draw_text: {
+                    wrap: Word,
+                    color: #172B4D,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 13}
+                }
+            }
+        }
+
+        restore_button = <Button> {
+            width: 60,
+            height: 28,
+            text: "恢复",
+        }
+    }
+
+    pub HistoryModal = {{HistoryModal}} {
+        width: Fit,
+        height: Fit,
+
+        <RoundedView> {
+            width: 560,
+            height: Fit,
+            padding: 20,
+            flow: Down,
+            spacing: 15,
+            draw_bg: {
+                color: #FFFFFF
+            }
+
+            title_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "历史版本",
+                draw_text: {
+                    text_style: <THEME_FONT_BOLD>{font_size: 18}
+                    color: #172B4D
+                }
+            }
+
+            status_label = <Label> {
+                width: Fill,
+                height: Fit,
+                visible: false,
+                text: "",
+                draw_text: {
+                    color: #5E6C84,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 13}
+                }
+            }
+
+            history_list = <PortalList> {
+                width: Fill,
+                height: 360,
+                flow: Down,
+                spacing: 5,
+
+                HistoryEntry = <HistoryEntry> {}
+            }
+
+            // 按钮区域
+            <View> {
+                width: Fill,
+                height: Fit,
+                flow: Right,
+                align: {x: 1.0, y: 0.5}
+
+                close_button = <Button> {
+                    width: 80,
+                    height: 36,
+                    text: "关闭",
+                    draw_bg: {
+                        color: #95A5A6,
+                        radius: 3.0,
+                    }
+                    draw_text: {
+                        color: #FFFFFF,
+                        text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct HistoryEntry {
+    #[deref]
+    view: View,
+    /// 点击「恢复」时发送的 Action
+    #[rust]
+    restore_action: Option<crate::kanban::KanbanActions>,
+}
+
+impl Widget for HistoryEntry {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if let Event::Actions(actions) = event {
+            if self.view.button(ids!(restore_button)).clicked(actions) {
+                if let Some(action) = self.restore_action.clone() {
+                    log!("HistoryEntry: {:?}", action);
+                    cx.action(action);
+                }
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct HistoryModal {
+    #[deref]
+    view: View,
+}
+
+impl Widget for HistoryModal {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if let Event::Actions(actions) = event {
+            if self.view.button(ids!(close_button)).clicked(actions) {
+                cx.action(crate::kanban::KanbanActions::CloseHistory);
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        // (标题, 是否已加载, [(版本, 恢复该版本的 Action)])
+        let (title, loaded, items) = scope
+            .data
+            .get::<crate::app::AppState>()
+            .map(|app_state| history_data(&app_state.kanban_state))
+            .unwrap_or_else(|| ("历史版本".to_string(), false, Vec::new()));
+
+        self.view.label(ids!(title_label)).set_text(cx, &title);
+        let status = if !loaded {
+            "正在加载历史版本…"
+        } else if items.is_empty() {
+            "没有找到历史版本"
+        } else {
+            ""
+        };
+        let status_label = self.view.label(ids!(status_label));
+        status_label.set_text(cx, status);
+        status_label.set_visible(cx, !status.is_empty());
+
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                list.set_item_range(cx, 0, items.len());
+
+                while let Some(idx) = list.next_visible_item(cx) {
+                    let Some((history_item, restore_action)) = items.get(idx) else {
+                        continue;
+                    };
+
+                    let item_widget = list.item(cx, idx, live_id!(HistoryEntry));
+                    // 最新的版本就是当前内容，不需要恢复
+                    let header = if idx == 0 {
+                        format!("{}（当前）", history_item.header)
+                    } else {
+                        history_item.header.clone()
+                    };
+                    item_widget.label(ids!(header_label)).set_text(cx, &header);
+
+                    let mut changes: Vec<String> = history_item
+                        .changes
+                        .iter()
+                        .map(|change| change.describe())
+                        .collect();
+                    if idx + 1 == items.len() {
+                        changes.insert(0, "最早的版本".to_string());
+                    } else if changes.is_empty() {
+                        changes.push("没有可比较的变化".to_string());
+                    }
+                    item_widget
+                        .label(ids!(changes_label))
+                        .set_text(cx, &changes.join("\n"));
+                    item_widget
+                        .button(ids!(restore_button))
+                        .set_visible(cx, idx > 0);
+
+                    if let Some(mut entry) = item_widget.as_history_entry().borrow_mut() {
+                        entry.restore_action = restore_action.clone();
+                    }
+
+                    item_widget.draw_all(cx, &mut Scope::empty());
+                }
+            }
+        }
+        DrawStep::done()
+    }
+}
+
+/// 历史面板的标题、是否已加载，以及每个版本和恢复该版本的 Action（最新的在前）
+fn history_data(
+    state: &crate::kanban::KanbanAppState,
+) -> (
+    String,
+    bool,
+    Vec<(HistoryItem, Option<crate::kanban::KanbanActions>)>,
+) {
+    let (title, loaded) = match &state.history_panel {
+        Some(HistoryPanel::Card { card_id, revisions }) => (
+            format!(
+                "卡片历史：{}",
+                state
+                    .cards
+                    .get(card_id)
+                    .map_or("", |card| card.title.as_str())
+            ),
+            revisions.is_some(),
+        ),
+        Some(HistoryPanel::TagLibrary {
+            space_id,
+            revisions,
+        }) => (
+            format!(
+                "标签库历史：{}",
+                state
+                    .lists
+                    .get(space_id)
+                    .map_or("", |list| list.name.as_str())
+            ),
+            revisions.is_some(),
+        ),
+        None => ("历史版本".to_string(), false),
+    };
+    let items = state
+        .history_items()
+        .into_iter()
+        .map(|item| {
+            let event_id = item.event_id.clone();
+            let action = match &state.history_panel {
+                Some(HistoryPanel::Card { card_id, .. }) => {
+                    Some(crate::kanban::KanbanActions::RestoreCardRevision {
+                        card_id: card_id.clone(),
+                        event_id,
+                    })
+                }
+                Some(HistoryPanel::TagLibrary { space_id, .. }) => {
+                    Some(crate::kanban::KanbanActions::RestoreTagLibraryRevision {
+                        space_id: space_id.clone(),
+                        event_id,
+                    })
+                }
+                None => None,
+            };
+            (item, action)
+        })
+        .collect();
+    (title, loaded, items)
+}
@@ -7,15 +7,29 @@ pub mod card_modal;
 pub mod boards_list;
 pub mod card_detail_view;
 pub mod edit_list_name_modal;
+pub mod workflow_editor_modal;
+pub mod board_settings_modal;
+pub mod filter_bar;
+pub mod due_soon_modal;
+pub mod card_templates_modal;
+pub mod swimlane_board;
+pub mod calendar_view;
+pub mod analytics_modal;
+pub mod archive_modal;
+pub mod bulk_action_bar;
+pub mod create_card_modal;
+pub mod history_modal;
 
 // 模态框子组件
 pub mod modal_header;
 pub mod card_info_section;
 pub mod tag_section;
+pub mod assignee_section;
 pub mod tag_management_modal;
 pub mod todo_section;
 pub mod endtime_section;
-pub mod active_section;
+pub mod recurrence_section;
+pub mod time_tracking_section;
 
 pub use space::*;
 pub use card_list::*;
@@ -31,15 +45,29 @@ pub fn live_design(cx: &mut Cx) {
     boards_list::live_design(cx);
     card_detail_view::live_design(cx);
     edit_list_name_modal::live_design(cx);
+    workflow_editor_modal::live_design(cx);
+    board_settings_modal::live_design(cx);
+    filter_bar::live_design(cx);
+    due_soon_modal::live_design(cx);
+    card_templates_modal::live_design(cx);
+    calendar_view::live_design(cx);
+    analytics_modal::live_design(cx);
+    archive_modal::live_design(cx);
+    bulk_action_bar::live_design(cx);
+    create_card_modal::live_design(cx);
+    history_modal::live_design(cx);
+    swimlane_board::live_design(cx);
 
     // 模态框子组件
     modal_header::live_design(cx);
     card_info_section::live_design(cx);
     tag_section::live_design(cx);
+    assignee_section::live_design(cx);
     tag_management_modal::live_design(cx);
     todo_section::live_design(cx);
     endtime_section::live_design(cx);
-    active_section::live_design(cx);
+    recurrence_section::live_design(cx);
+    time_tracking_section::live_design(cx);
 
     // 主模态框组件（依赖子组件，所以放在最后）
     card_modal::live_design(cx);
@@ -23,6 +23,20 @@ live_design! {
             }
         }
 
+        // 查看卡片的历史版本
+        history_button = <Button> {
+            width: Fit,
+            height: 30,
+            margin: {right: 8}
+            text: "历史"
+            draw_text: {
+                color: #333333
+                text_style: {
+                    font_size: 12.0
+                }
+            }
+        }
+
         close_button = <Button> {
             width: 30,
             height: 30,
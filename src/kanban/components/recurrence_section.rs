@@ -0,0 +1,271 @@
+use makepad_widgets::*;
+
+use crate::kanban::state::template::RecurrenceRule;
+
+live_design! {
+    use link::theme::*;
+    use link::widgets::*;
+
+    // 周期与模板区域
+    pub RecurrenceSection = {{RecurrenceSection}} {
+        width: Fill,
+        height: Fit,
+        flow: Down,
+        spacing: 10,
+
+        // 标题栏
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 10,
+            align: {y: 0.5},
+
+            <Label> {
+                text: "重复",
+                draw_text: {
+                    text_style: <THEME_FONT_BOLD>{font_size: 14}
+                    color: #5E6C84
+                }
+            }
+
+            recurrence_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "不重复",
+                draw_text: {
+                    color: #95A5A6,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 13}
+                }
+            }
+        }
+
+        <Label> {
+            width: Fill,
+            height: Fit,
+            text: "格式: daily / weekly / monthly [间隔] [complete | schedule]，例如 weekly 2 schedule",
+            draw_text: {
+                wrap: Word,
+                color: #5E6C84,
+                text_style: <THEME_FONT_REGULAR>{font_size: 12}
+            }
+        }
+
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 10,
+            align: {y: 0.5},
+
+            recurrence_input = <TextInput> {
+                width: Fill,
+                height: 35,
+                empty_text: "weekly complete",
+                draw_text: {
+                    color: #172B4D,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                }
+                draw_bg: {
+                    color: #FFFFFF,
+                    border_color: #DFE1E6,
+                    border_width: 2.0,
+                    radius: 3.0,
+                }
+                draw_cursor: {
+                    color: #172B4D
+                }
+            }
+
+            set_recurrence_button = <Button> {
+                width: 60,
+                height: 30,
+                text: "设置",
+                draw_bg: {
+                    color: #0079BF,
+                    radius: 3.0,
+                }
+                draw_text: {
+                    color: #FFFFFF,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
+            }
+
+            clear_recurrence_button = <Button> {
+                width: 60,
+                height: 30,
+                text: "清除",
+                draw_bg: {
+                    color: #FF6B6B,
+                    radius: 3.0,
+                }
+                draw_text: {
+                    color: #FFFFFF,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
+            }
+        }
+
+        error_label = <Label> {
+            width: Fill,
+            height: Fit,
+            text: "",
+            draw_text: {
+                wrap: Word,
+                color: #EB5A46,
+                text_style: <THEME_FONT_REGULAR>{font_size: 12}
+            }
+        }
+
+        // 保存为模板
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 10,
+            align: {y: 0.5},
+
+            template_name_input = <TextInput> {
+                width: Fill,
+                height: 35,
+                empty_text: "模板名称（默认为卡片标题）",
+                draw_text: {
+                    color: #172B4D,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                }
+                draw_bg: {
+                    color: #FFFFFF,
+                    border_color: #DFE1E6,
+                    border_width: 2.0,
+                    radius: 3.0,
+                }
+                draw_cursor: {
+                    color: #172B4D
+                }
+            }
+
+            save_template_button = <Button> {
+                width: Fit,
+                height: 30,
+                text: "📋 保存为模板",
+                draw_bg: {
+                    color: #61BD4F,
+                    radius: 3.0,
+                }
+                draw_text: {
+                    color: #FFFFFF,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
+            }
+        }
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct RecurrenceSection {
+    #[deref]
+    view: View,
+    #[rust]
+    card_id: Option<matrix_sdk::ruma::OwnedRoomId>,
+    #[rust]
+    card_title: String,
+}
+
+impl Widget for RecurrenceSection {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        let Event::Actions(actions) = event else {
+            return;
+        };
+        let Some(card_id) = self.card_id.clone() else {
+            return;
+        };
+
+        // 处理设置周期按钮
+        if self.view.button(ids!(set_recurrence_button)).clicked(actions) {
+            let text = self.view.text_input(ids!(recurrence_input)).text();
+            match RecurrenceRule::parse(&text) {
+                Ok(rule) => {
+                    log!("RecurrenceSection: 设置周期 {:?} 到卡片 {}", rule, card_id);
+                    self.view.label(ids!(error_label)).set_text(cx, "");
+                    cx.action(crate::kanban::KanbanActions::SetCardRecurrence {
+                        card_id: card_id.clone(),
+                        recurrence: Some(rule),
+                    });
+                }
+                Err(e) => {
+                    log!("⚠️ RecurrenceSection: 周期设置无效: {}", e);
+                    self.view
+                        .label(ids!(error_label))
+                        .set_text(cx, &format!("{}", e));
+                }
+            }
+            self.view.redraw(cx);
+        }
+
+        // 处理清除周期按钮
+        if self.view.button(ids!(clear_recurrence_button)).clicked(actions) {
+            log!("RecurrenceSection: 清除卡片 {} 的周期", card_id);
+            self.view.text_input(ids!(recurrence_input)).set_text(cx, "");
+            self.view.label(ids!(error_label)).set_text(cx, "");
+            cx.action(crate::kanban::KanbanActions::SetCardRecurrence {
+                card_id: card_id.clone(),
+                recurrence: None,
+            });
+        }
+
+        // 处理保存为模板按钮
+        if self.view.button(ids!(save_template_button)).clicked(actions) {
+            let name_input = self.view.text_input(ids!(template_name_input));
+            let name = name_input.text().trim().to_string();
+            let name = if name.is_empty() {
+                self.card_title.clone()
+            } else {
+                name
+            };
+            log!("RecurrenceSection: 把卡片 {} 保存为模板 '{}'", card_id, name);
+            name_input.set_text(cx, "");
+            cx.action(crate::kanban::KanbanActions::SaveCardAsTemplate { card_id, name });
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if let Some(app_state) = scope.data.get::<crate::app::AppState>() {
+            let state = &app_state.kanban_state;
+            if let Some(card) = state
+                .selected_card_id
+                .as_ref()
+                .and_then(|card_id| state.cards.get(card_id))
+            {
+                // 切换到另一张卡片时用卡片当前的周期规则填充输入框
+                if self.card_id.as_ref() != Some(&card.id) {
+                    self.card_id = Some(card.id.clone());
+                    self.view.text_input(ids!(recurrence_input)).set_text(
+                        cx,
+                        &card
+                            .recurrence
+                            .as_ref()
+                            .map(RecurrenceRule::to_text)
+                            .unwrap_or_default(),
+                    );
+                    self.view.label(ids!(error_label)).set_text(cx, "");
+                }
+                self.card_title = card.title.clone();
+
+                let (text, color) = match &card.recurrence {
+                    Some(rule) => (
+                        format!("🔁 {}", rule.describe()),
+                        vec4(0.090, 0.169, 0.302, 1.0),
+                    ),
+                    None => ("不重复".to_string(), vec4(0.584, 0.647, 0.651, 1.0)),
+                };
+                let recurrence_label = self.view.label(ids!(recurrence_label));
+                recurrence_label.set_text(cx, &text);
+                recurrence_label.apply_over(cx, live! { draw_text: { color: (color) } });
+            }
+        }
+
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
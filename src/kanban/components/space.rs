@@ -1,5 +1,9 @@
 use makepad_widgets::*;
 
+use crate::kanban::components::time_tracking_section::member_name;
+use crate::kanban::state::permissions::KanbanPermission;
+use crate::kanban::state::time_tracking::format_duration;
+
 live_design! {
     use link::theme::*;
     use link::widgets::*;
@@ -36,6 +40,69 @@ live_design! {
                         }
                     }
                 }
+
+                workflow_button = <Button> {
+                    width: 50,
+                    height: 35,
+                    margin: {left: 5},
+                    text: "流程",
+                    draw_bg: {
+                        color: #F8F9FAFF
+                    }
+                    draw_text: {
+                        color: #333333FF,
+                        text_style: {
+                            font_size: 12.0,
+                        }
+                    }
+                }
+
+                templates_button = <Button> {
+                    width: 50,
+                    height: 35,
+                    margin: {left: 5},
+                    text: "模板",
+                    draw_bg: {
+                        color: #F8F9FAFF
+                    }
+                    draw_text: {
+                        color: #333333FF,
+                        text_style: {
+                            font_size: 12.0,
+                        }
+                    }
+                }
+
+                settings_button = <Button> {
+                    width: 50,
+                    height: 35,
+                    margin: {left: 5},
+                    text: "权限",
+                    draw_bg: {
+                        color: #F8F9FAFF
+                    }
+                    draw_text: {
+                        color: #333333FF,
+                        text_style: {
+                            font_size: 12.0,
+                        }
+                    }
+                }
+            }
+
+            // 列表的估时和工时合计（按成员）
+            time_summary_label = <Label> {
+                width: Fill,
+                height: Fit,
+                visible: false,
+                text: "",
+                draw_text: {
+                    wrap: Word,
+                    color: #5E6C84FF,
+                    text_style: {
+                        font_size: 11.0,
+                    }
+                }
             }
 
             <ScrollXYView> {
@@ -50,11 +117,25 @@ live_design! {
                 <CardList> {}
             }
 
-            create_button = <Button> {
-                text: "创建卡片",
-                width: 120,
-                height: 40,
+            <View> {
+                width: Fill,
+                height: Fit,
+                flow: Right,
+                spacing: 5,
                 margin: {top: 10}
+
+                create_button = <Button> {
+                    text: "创建卡片",
+                    width: 120,
+                    height: 40,
+                }
+
+                // 选中（或取消选中）列表中所有可见的卡片
+                select_all_button = <Button> {
+                    text: "全选",
+                    width: 60,
+                    height: 40,
+                }
             }
         }
     }
@@ -121,29 +202,10 @@ impl Widget for SpaceColumn {
                                 {
                                     log!("SpaceColumn: 放置卡片到 Space {}", target_space_id);
 
-                                    // 计算目标位置（放到列表末尾）
-                                    let target_position = if let Some(list) =
-                                        app_state.kanban_state.lists.get(&target_space_id)
-                                    {
-                                        // 获取列表中最后一张卡片的位置
-                                        let last_position = list
-                                            .card_ids
-                                            .iter()
-                                            .filter_map(|card_id| {
-                                                app_state.kanban_state.cards.get(card_id)
-                                            })
-                                            .map(|card| card.position)
-                                            .max_by(|a, b| a.partial_cmp(b).unwrap())
-                                            .unwrap_or(0.0);
-                                        last_position + 1000.0
-                                    } else {
-                                        1000.0
-                                    };
-
+                                    // 放到拖拽经过的位置（排序键在 app.rs 中计算）
                                     cx.action(crate::kanban::KanbanActions::DropCard {
                                         card_id: drag_state.card_id.clone(),
                                         target_space_id,
-                                        target_position,
                                     });
                                 }
                             }
@@ -182,6 +244,46 @@ impl Widget for SpaceColumn {
                 }
             }
 
+            // 处理工作流按钮点击 - 打开工作流编辑模态框
+            if self.view.button(ids!(workflow_button)).clicked(actions) {
+                if let Some(space_id) = scope.props.get::<matrix_sdk::ruma::OwnedRoomId>() {
+                    log!("SpaceColumn: 打开工作流编辑模态框 {}", space_id);
+                    cx.action(crate::kanban::KanbanActions::ShowWorkflowEditor {
+                        space_id: space_id.clone(),
+                    });
+                }
+            }
+
+            // 处理模板按钮点击 - 打开卡片模板模态框
+            if self.view.button(ids!(templates_button)).clicked(actions) {
+                if let Some(space_id) = scope.props.get::<matrix_sdk::ruma::OwnedRoomId>() {
+                    log!("SpaceColumn: 打开卡片模板模态框 {}", space_id);
+                    cx.action(crate::kanban::KanbanActions::ShowCardTemplates {
+                        space_id: space_id.clone(),
+                    });
+                }
+            }
+
+            // 处理权限按钮点击 - 打开看板设置模态框
+            if self.view.button(ids!(settings_button)).clicked(actions) {
+                if let Some(space_id) = scope.props.get::<matrix_sdk::ruma::OwnedRoomId>() {
+                    log!("SpaceColumn: 打开看板设置模态框 {}", space_id);
+                    cx.action(crate::kanban::KanbanActions::ShowBoardSettings {
+                        space_id: space_id.clone(),
+                    });
+                }
+            }
+
+            // 处理全选按钮点击
+            if self.view.button(ids!(select_all_button)).clicked(actions) {
+                if let Some(space_id) = scope.props.get::<matrix_sdk::ruma::OwnedRoomId>() {
+                    log!("SpaceColumn: 全选列表 {} 中的卡片", space_id);
+                    cx.action(crate::kanban::KanbanActions::SelectAllInList {
+                        space_id: space_id.clone(),
+                    });
+                }
+            }
+
             // 只在按钮被点击时才输出日志
             if self.view.button(ids!(create_button)).clicked(actions) {
                 log!("🎯🎯🎯 SpaceColumn: 创建卡片按钮被点击!!!");
@@ -208,6 +310,43 @@ impl Widget for SpaceColumn {
         // 从 scope.props 获取 space_id (OwnedRoomId) 并保存为字符串
         if let Some(space_id) = scope.props.get::<matrix_sdk::ruma::OwnedRoomId>() {
             self.list_id = Some(space_id.to_string());
+
+            // 没有管理列表权限时隐藏工作流按钮
+            let can_manage_lists = scope
+                .data
+                .get::<crate::app::AppState>()
+                .is_none_or(|app_state| {
+                    app_state
+                        .kanban_state
+                        .can(space_id, KanbanPermission::ManageLists)
+                });
+            self.view
+                .button(ids!(workflow_button))
+                .set_visible(cx, can_manage_lists);
+
+            // 估时和工时合计
+            let totals = scope
+                .data
+                .get::<crate::app::AppState>()
+                .map(|app_state| app_state.kanban_state.list_time_totals(space_id))
+                .unwrap_or_default();
+            let summary_label = self.view.label(ids!(time_summary_label));
+            summary_label.set_visible(cx, !totals.is_empty());
+            if !totals.is_empty() {
+                let mut text = totals.summary();
+                let members: Vec<String> = totals
+                    .by_user
+                    .iter()
+                    .map(|(user_id, secs)| {
+                        format!("{} {}", member_name(cx, user_id), format_duration(*secs))
+                    })
+                    .collect();
+                if !members.is_empty() {
+                    text.push('\n');
+                    text.push_str(&members.join(" · "));
+                }
+                summary_label.set_text(cx, &text);
+            }
         }
 
         // 如果是拖拽目标，高亮边框（暂时移除 live! 宏的使用）
@@ -0,0 +1,379 @@
+use makepad_widgets::*;
+use matrix_sdk::ruma::OwnedRoomId;
+
+use crate::kanban::components::card_item::CardItemWidgetRefExt;
+use crate::kanban::state::calendar::BoardViewMode;
+use crate::kanban::state::swimlane::{Swimlane, SwimlaneGrouping};
+
+live_design! {
+    use link::theme::*;
+    use link::widgets::*;
+    use crate::kanban::components::card_item::CardItem;
+    use crate::kanban::components::space::SpaceList;
+    use crate::kanban::components::calendar_view::CalendarView;
+    use crate::kanban::components::calendar_view::TimelineView;
+
+    // 泳道中的一个单元格（某个列表中属于该泳道的卡片）
+    SwimlaneCell = {{SwimlaneCell}} {
+        <RoundedView> {
+            width: 300,
+            height: 320,
+            padding: 10,
+            flow: Down,
+            spacing: 8,
+            draw_bg: {
+                color: #E8F4FDFF
+            }
+
+            list_name_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "列表",
+                draw_text: {
+                    wrap: Ellipsis,
+                    color: #5E6C84,
+                    text_style: <THEME_FONT_BOLD>{font_size: 12}
+                }
+            }
+
+            cards = <PortalList> {
+                width: Fill,
+                height: Fill,
+                flow: Down,
+                spacing: 5,
+
+                Card = <CardItem> {}
+            }
+        }
+    }
+
+    // 一条泳道：可折叠的标题 + 每个列表一个单元格
+    SwimlaneRow = {{SwimlaneRow}} {
+        width: Fill,
+        height: Fit,
+        flow: Down,
+        spacing: 8,
+        margin: {bottom: 15},
+
+        lane_toggle_button = <Button> {
+            width: Fit,
+            height: 32,
+            text: "▼ 泳道",
+            draw_bg: {
+                color: #F8F9FAFF
+            }
+            draw_text: {
+                color: #172B4D,
+                text_style: <THEME_FONT_BOLD>{font_size: 14}
+            }
+        }
+
+        cells = <PortalList> {
+            width: Fill,
+            height: 320,
+            flow: Right,
+            spacing: 15,
+
+            Cell = <SwimlaneCell> {}
+        }
+    }
+
+    SwimlaneBoard = {{SwimlaneBoard}} {
+        width: Fill,
+        height: Fill,
+
+        lanes = <PortalList> {
+            width: Fill,
+            height: Fill,
+            flow: Down,
+
+            Lane = <SwimlaneRow> {}
+        }
+    }
+
+    // 看板主体：按列表显示（选择了泳道分组时按泳道显示），或者显示日历 / 时间线
+    pub KanbanBoardView = {{KanbanBoardView}} {
+        width: Fill,
+        height: Fill,
+
+        lists_view = <ScrollXYView> {
+            width: Fill, height: Fill
+            scroll_bars: <ScrollBars> {
+                show_scroll_x: true,
+                show_scroll_y: true,
+            }
+
+            <SpaceList> {}
+        }
+
+        swimlane_board = <SwimlaneBoard> {
+            visible: false
+        }
+
+        calendar_view = <CalendarView> {
+            visible: false
+        }
+
+        timeline_view = <TimelineView> {
+            visible: false
+        }
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct SwimlaneCell {
+    #[deref]
+    view: View,
+    #[rust]
+    space_id: Option<OwnedRoomId>,
+    #[rust]
+    lane_key: String,
+    #[rust]
+    card_ids: Vec<OwnedRoomId>,
+}
+
+impl Widget for SwimlaneCell {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        // 拖拽的卡片放到本单元格：更新分组属性，必要时移动到本列表
+        if let Event::MouseUp(e) = event {
+            let Some(space_id) = self.space_id.clone() else {
+                return;
+            };
+            let Some(drag_state) = scope
+                .data
+                .get::<crate::app::AppState>()
+                .and_then(|app_state| app_state.kanban_state.drag_state.as_ref())
+            else {
+                return;
+            };
+            if self.view.area().rect(cx).contains(e.abs) {
+                log!(
+                    "SwimlaneCell: 放置卡片到列表 {} 的泳道 '{}'",
+                    space_id,
+                    self.lane_key
+                );
+                cx.action(crate::kanban::KanbanActions::DropCardInLane {
+                    card_id: drag_state.card_id.clone(),
+                    target_space_id: space_id,
+                    lane_key: self.lane_key.clone(),
+                });
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                list.set_item_range(cx, 0, self.card_ids.len());
+
+                while let Some(idx) = list.next_visible_item(cx) {
+                    let Some(card_id) = self.card_ids.get(idx) else {
+                        continue;
+                    };
+                    let Some(app_state) = scope.data.get_mut::<crate::app::AppState>() else {
+                        continue;
+                    };
+                    let Some(card) = app_state.kanban_state.cards.get(card_id).cloned() else {
+                        continue;
+                    };
+
+                    let card_item = list.item(cx, idx, live_id!(Card));
+                    card_item.as_card_item().set_lane(Some(self.lane_key.clone()));
+
+                    let mut card_scope = Scope::with_data_props(app_state, card_id);
+                    card_item.draw_all(cx, &mut card_scope);
+
+                    card_item
+                        .label(ids!(card_title_label))
+                        .set_text(cx, &card.title);
+                    let description = card
+                        .description
+                        .as_deref()
+                        .filter(|desc| !desc.is_empty())
+                        .unwrap_or("无");
+                    card_item
+                        .label(ids!(card_tags))
+                        .set_text(cx, &format!("描述: {}", description));
+                }
+            }
+        }
+        DrawStep::done()
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct SwimlaneRow {
+    #[deref]
+    view: View,
+    #[rust]
+    lane: Option<Swimlane>,
+    /// (列表 ID, 列表名称)
+    #[rust]
+    lists: Vec<(OwnedRoomId, String)>,
+}
+
+impl Widget for SwimlaneRow {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if let Event::Actions(actions) = event {
+            if self.view.button(ids!(lane_toggle_button)).clicked(actions) {
+                if let Some(lane) = &self.lane {
+                    cx.action(crate::kanban::KanbanActions::ToggleSwimlane {
+                        lane_key: lane.key.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        let Some(lane) = self.lane.clone() else {
+            return DrawStep::done();
+        };
+        let collapsed = scope
+            .data
+            .get::<crate::app::AppState>()
+            .is_some_and(|app_state| app_state.kanban_state.is_lane_collapsed(&lane.key));
+
+        self.view.button(ids!(lane_toggle_button)).set_text(
+            cx,
+            &format!(
+                "{} {}（{}）",
+                if collapsed { "▶" } else { "▼" },
+                lane.title,
+                lane.card_count()
+            ),
+        );
+        self.view.widget(ids!(cells)).set_visible(cx, !collapsed);
+
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                list.set_item_range(cx, 0, self.lists.len());
+
+                while let Some(idx) = list.next_visible_item(cx) {
+                    let Some((space_id, list_name)) = self.lists.get(idx) else {
+                        continue;
+                    };
+
+                    let cell_widget = list.item(cx, idx, live_id!(Cell));
+                    let card_ids = lane.cards_in(space_id).to_vec();
+                    cell_widget
+                        .label(ids!(list_name_label))
+                        .set_text(cx, &format!("{}（{}）", list_name, card_ids.len()));
+                    if let Some(mut cell) = cell_widget.as_swimlane_cell().borrow_mut() {
+                        cell.space_id = Some(space_id.clone());
+                        cell.lane_key = lane.key.clone();
+                        cell.card_ids = card_ids;
+                    }
+
+                    cell_widget.draw_all(cx, scope);
+                }
+            }
+        }
+        DrawStep::done()
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct SwimlaneBoard {
+    #[deref]
+    view: View,
+}
+
+impl Widget for SwimlaneBoard {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        // 没有放到任何单元格时取消拖拽（放到单元格的 DropCardInLane 会先被处理）
+        if let Event::MouseUp(_) = event {
+            let dragging = scope
+                .data
+                .get::<crate::app::AppState>()
+                .is_some_and(|app_state| app_state.kanban_state.drag_state.is_some());
+            if dragging {
+                cx.action(crate::kanban::KanbanActions::CancelDragCard);
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        let current_user = crate::sliding_sync::current_user_id();
+        let (lanes, lists) = scope
+            .data
+            .get::<crate::app::AppState>()
+            .map(|app_state| {
+                let state = &app_state.kanban_state;
+                let lists: Vec<_> = state
+                    .all_lists()
+                    .into_iter()
+                    .map(|list| (list.id.clone(), list.name.clone()))
+                    .collect();
+                (state.swimlanes(current_user.as_deref()), lists)
+            })
+            .unwrap_or_default();
+
+        while let Some(item) = self.view.draw_walk(cx, scope, walk).step() {
+            if let Some(mut list) = item.as_portal_list().borrow_mut() {
+                list.set_item_range(cx, 0, lanes.len());
+
+                while let Some(idx) = list.next_visible_item(cx) {
+                    let Some(lane) = lanes.get(idx) else {
+                        continue;
+                    };
+
+                    let row_widget = list.item(cx, idx, live_id!(Lane));
+                    if let Some(mut row) = row_widget.as_swimlane_row().borrow_mut() {
+                        row.lane = Some(lane.clone());
+                        row.lists = lists.clone();
+                    }
+
+                    row_widget.draw_all(cx, scope);
+                }
+            }
+        }
+        DrawStep::done()
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct KanbanBoardView {
+    #[deref]
+    view: View,
+}
+
+impl Widget for KanbanBoardView {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        let (view_mode, grouping) = scope
+            .data
+            .get::<crate::app::AppState>()
+            .map(|app_state| {
+                let state = &app_state.kanban_state;
+                (state.board_view_mode, state.swimlane_grouping)
+            })
+            .unwrap_or_default();
+        let show_board = view_mode == BoardViewMode::Board;
+        let show_swimlanes = show_board && grouping != SwimlaneGrouping::None;
+
+        self.view
+            .view(ids!(lists_view))
+            .set_visible(cx, show_board && !show_swimlanes);
+        self.view
+            .widget(ids!(swimlane_board))
+            .set_visible(cx, show_swimlanes);
+        self.view
+            .widget(ids!(calendar_view))
+            .set_visible(cx, view_mode == BoardViewMode::Calendar);
+        self.view
+            .widget(ids!(timeline_view))
+            .set_visible(cx, view_mode == BoardViewMode::Timeline);
+
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
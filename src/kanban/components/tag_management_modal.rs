@@ -36,6 +36,20 @@ live_design! {
                     }
                 }
 
+                // 查看标签库的历史版本
+                history_button = <Button> {
+                    width: Fit,
+                    height: 30,
+                    text: "历史",
+                    draw_bg: {
+                        color: #00000000
+                    }
+                    draw_text: {
+                        color: #5E6C84,
+                        text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                    }
+                }
+
                 close_button = <Button> {
                     width: 30,
                     height: 30,
@@ -459,6 +473,14 @@ impl Widget for TagManagementModal {
                 cx.action(crate::kanban::KanbanActions::CloseTagManagementModal);
             }
 
+            if self.view.button(ids!(history_button)).clicked(actions) {
+                if let Some(space_id) = &self.space_id {
+                    cx.action(crate::kanban::KanbanActions::ShowTagLibraryHistory {
+                        space_id: space_id.clone(),
+                    });
+                }
+            }
+
             // 处理标签按钮点击（添加标签到卡片）
             for i in 0..5 {
                 let button_id = match i {
@@ -585,6 +607,14 @@ impl Widget for TagManagementModal {
         // 更新标签按钮显示
         if let Some(app_state) = scope.data.get::<crate::app::AppState>() {
             if let Some(space_id) = &self.space_id {
+                // 没有管理标签权限时隐藏创建按钮
+                let can_manage_tags = app_state
+                    .kanban_state
+                    .can(space_id, crate::kanban::state::permissions::KanbanPermission::ManageTags);
+                self.view
+                    .button(ids!(create_button))
+                    .set_visible(cx, can_manage_tags);
+
                 if let Some(tags) = app_state.kanban_state.space_tags.get(space_id) {
                     // 检查标签库是否有变化
                     let tags_changed =
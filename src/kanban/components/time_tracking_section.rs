@@ -0,0 +1,428 @@
+use makepad_widgets::*;
+
+use crate::kanban::state::time_tracking::{Estimate, format_clock, format_duration, parse_duration};
+use crate::profile::user_profile_cache::with_user_profile;
+use crate::sliding_sync::current_user_id;
+
+live_design! {
+    use link::theme::*;
+    use link::widgets::*;
+
+    TimeTrackingInput = <TextInput> {
+        width: Fill,
+        height: 35,
+        draw_text: {
+            color: #172B4D,
+            text_style: <THEME_FONT_REGULAR>{font_size: 14}
+        }
+        draw_bg: {
+            color: #FFFFFF,
+            border_color: #DFE1E6,
+            border_width: 2.0,
+            radius: 3.0,
+        }
+        draw_cursor: {
+            color: #172B4D
+        }
+    }
+
+    // 估时与工时区域
+    pub TimeTrackingSection = {{TimeTrackingSection}} {
+        width: Fill,
+        height: Fit,
+        flow: Down,
+        spacing: 10,
+
+        // 标题栏
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 10,
+            align: {y: 0.5},
+
+            <Label> {
+                text: "工时",
+                draw_text: {
+                    text_style: <THEME_FONT_BOLD>{font_size: 14}
+                    color: #5E6C84
+                }
+            }
+
+            summary_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "未记录工时",
+                draw_text: {
+                    color: #95A5A6,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 13}
+                }
+            }
+        }
+
+        // 计时
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 10,
+            align: {y: 0.5},
+
+            timer_button = <Button> {
+                width: Fit,
+                height: 30,
+                text: "▶ 开始计时",
+                draw_bg: {
+                    color: #61BD4F,
+                    radius: 3.0,
+                }
+                draw_text: {
+                    color: #FFFFFF,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 13}
+                }
+            }
+
+            timer_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "",
+                draw_text: {
+                    wrap: Word,
+                    color: #172B4D,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 13}
+                }
+            }
+        }
+
+        // 每个成员记录的工时
+        members_label = <Label> {
+            width: Fill,
+            height: Fit,
+            text: "",
+            draw_text: {
+                wrap: Word,
+                color: #5E6C84,
+                text_style: <THEME_FONT_REGULAR>{font_size: 12}
+            }
+        }
+
+        <Label> {
+            width: Fill,
+            height: Fit,
+            text: "估时格式: 3h（小时）或 5p（故事点）；补记格式: 1.5h / 90m / 1h30m",
+            draw_text: {
+                wrap: Word,
+                color: #5E6C84,
+                text_style: <THEME_FONT_REGULAR>{font_size: 12}
+            }
+        }
+
+        // 估时
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 10,
+            align: {y: 0.5},
+
+            estimate_input = <TimeTrackingInput> {
+                empty_text: "估时，例如 3h 或 5p",
+            }
+
+            set_estimate_button = <Button> {
+                width: 60,
+                height: 30,
+                text: "设置",
+                draw_bg: {
+                    color: #0079BF,
+                    radius: 3.0,
+                }
+                draw_text: {
+                    color: #FFFFFF,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
+            }
+
+            clear_estimate_button = <Button> {
+                width: 60,
+                height: 30,
+                text: "清除",
+                draw_bg: {
+                    color: #FF6B6B,
+                    radius: 3.0,
+                }
+                draw_text: {
+                    color: #FFFFFF,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
+            }
+        }
+
+        // 手动补记
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 10,
+            align: {y: 0.5},
+
+            log_input = <TimeTrackingInput> {
+                empty_text: "补记工时，例如 1.5h",
+            }
+
+            log_button = <Button> {
+                width: 60,
+                height: 30,
+                text: "补记",
+                draw_bg: {
+                    color: #00C2E0,
+                    radius: 3.0,
+                }
+                draw_text: {
+                    color: #FFFFFF,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
+            }
+        }
+
+        error_label = <Label> {
+            width: Fill,
+            height: Fit,
+            text: "",
+            draw_text: {
+                wrap: Word,
+                color: #EB5A46,
+                text_style: <THEME_FONT_REGULAR>{font_size: 12}
+            }
+        }
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct TimeTrackingSection {
+    #[deref]
+    view: View,
+    #[rust]
+    card_id: Option<matrix_sdk::ruma::OwnedRoomId>,
+    /// 当前用户是否正在计时
+    #[rust]
+    is_running: bool,
+    /// 计时时每秒刷新一次
+    #[rust]
+    tick_timer: Timer,
+}
+
+impl Widget for TimeTrackingSection {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if self.tick_timer.is_event(event).is_some() {
+            self.view.redraw(cx);
+        }
+
+        let Event::Actions(actions) = event else {
+            return;
+        };
+        let Some(card_id) = self.card_id.clone() else {
+            return;
+        };
+
+        // 处理计时按钮
+        if self.view.button(ids!(timer_button)).clicked(actions) {
+            log!(
+                "TimeTrackingSection: {} 卡片 {} 的计时",
+                if self.is_running { "停止" } else { "开始" },
+                card_id
+            );
+            if self.is_running {
+                cx.action(crate::kanban::KanbanActions::StopCardTimer { card_id });
+            } else {
+                cx.action(crate::kanban::KanbanActions::StartCardTimer { card_id });
+            }
+            return;
+        }
+
+        // 处理设置估时按钮
+        if self.view.button(ids!(set_estimate_button)).clicked(actions) {
+            let text = self.view.text_input(ids!(estimate_input)).text();
+            match Estimate::parse(&text) {
+                Ok(estimate) => {
+                    log!(
+                        "TimeTrackingSection: 设置估时 {:?} 到卡片 {}",
+                        estimate,
+                        card_id
+                    );
+                    self.view.label(ids!(error_label)).set_text(cx, "");
+                    cx.action(crate::kanban::KanbanActions::SetCardEstimate {
+                        card_id: card_id.clone(),
+                        estimate: Some(estimate),
+                    });
+                }
+                Err(e) => {
+                    log!("⚠️ TimeTrackingSection: 估时无效: {}", e);
+                    self.view
+                        .label(ids!(error_label))
+                        .set_text(cx, &format!("{}", e));
+                }
+            }
+            self.view.redraw(cx);
+        }
+
+        // 处理清除估时按钮
+        if self
+            .view
+            .button(ids!(clear_estimate_button))
+            .clicked(actions)
+        {
+            log!("TimeTrackingSection: 清除卡片 {} 的估时", card_id);
+            self.view.text_input(ids!(estimate_input)).set_text(cx, "");
+            self.view.label(ids!(error_label)).set_text(cx, "");
+            cx.action(crate::kanban::KanbanActions::SetCardEstimate {
+                card_id: card_id.clone(),
+                estimate: None,
+            });
+        }
+
+        // 处理补记按钮
+        if self.view.button(ids!(log_button)).clicked(actions) {
+            let log_input = self.view.text_input(ids!(log_input));
+            match parse_duration(&log_input.text()) {
+                Ok(duration_secs) => {
+                    log!(
+                        "TimeTrackingSection: 补记 {} 秒到卡片 {}",
+                        duration_secs,
+                        card_id
+                    );
+                    log_input.set_text(cx, "");
+                    self.view.label(ids!(error_label)).set_text(cx, "");
+                    cx.action(crate::kanban::KanbanActions::LogCardWork {
+                        card_id,
+                        duration_secs,
+                    });
+                }
+                Err(e) => {
+                    log!("⚠️ TimeTrackingSection: 补记时长无效: {}", e);
+                    self.view
+                        .label(ids!(error_label))
+                        .set_text(cx, &format!("{}", e));
+                }
+            }
+            self.view.redraw(cx);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if let Some(app_state) = scope.data.get::<crate::app::AppState>() {
+            let state = &app_state.kanban_state;
+            if let Some(card) = state
+                .selected_card_id
+                .as_ref()
+                .and_then(|card_id| state.cards.get(card_id))
+            {
+                // 切换到另一张卡片时用卡片当前的估时填充输入框
+                if self.card_id.as_ref() != Some(&card.id) {
+                    self.card_id = Some(card.id.clone());
+                    self.view.text_input(ids!(estimate_input)).set_text(
+                        cx,
+                        &card
+                            .estimate
+                            .as_ref()
+                            .map(Estimate::to_text)
+                            .unwrap_or_default(),
+                    );
+                    self.view.text_input(ids!(log_input)).set_text(cx, "");
+                    self.view.label(ids!(error_label)).set_text(cx, "");
+                }
+
+                // 已记录 / 估时
+                let logged = card.logged_secs();
+                let (summary, color) = match (&card.estimate, logged) {
+                    (None, 0) => ("未记录工时".to_string(), vec4(0.584, 0.647, 0.651, 1.0)),
+                    (None, _) => (
+                        format!("已记录 {}", format_duration(logged)),
+                        vec4(0.090, 0.169, 0.302, 1.0),
+                    ),
+                    (Some(estimate), _) => (
+                        format!(
+                            "已记录 {} / 估时 {}",
+                            format_duration(logged),
+                            estimate.describe()
+                        ),
+                        vec4(0.090, 0.169, 0.302, 1.0),
+                    ),
+                };
+                let summary_label = self.view.label(ids!(summary_label));
+                summary_label.set_text(cx, &summary);
+                summary_label.apply_over(cx, live! { draw_text: { color: (color) } });
+
+                let members: Vec<String> = card
+                    .logged_time
+                    .iter()
+                    .filter(|(_, secs)| **secs > 0)
+                    .map(|(user_id, secs)| {
+                        format!("{} {}", member_name(cx, user_id), format_duration(*secs))
+                    })
+                    .collect();
+                self.view
+                    .label(ids!(members_label))
+                    .set_text(cx, &members.join(" · "));
+
+                // 计时状态（自己的计时显示时长，其他成员的计时只显示名字）
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+                let current_user = current_user_id();
+                let own_timer = current_user
+                    .as_deref()
+                    .and_then(|user_id| card.timer_of(user_id));
+                let others: Vec<String> = card
+                    .timers
+                    .iter()
+                    .filter(|timer| Some(&timer.user_id) != current_user.as_ref())
+                    .map(|timer| member_name(cx, &timer.user_id))
+                    .collect();
+                let mut timer_text = own_timer
+                    .map(|timer| {
+                        format!("⏱ {}", format_clock(now.saturating_sub(timer.started_at)))
+                    })
+                    .unwrap_or_default();
+                if !others.is_empty() {
+                    if !timer_text.is_empty() {
+                        timer_text.push_str(" · ");
+                    }
+                    timer_text.push_str(&format!("{} 正在计时", others.join("、")));
+                }
+                self.view.label(ids!(timer_label)).set_text(cx, &timer_text);
+
+                let is_running = own_timer.is_some();
+                if is_running != self.is_running {
+                    self.is_running = is_running;
+                    let (text, color) = if is_running {
+                        ("■ 停止计时", vec4(0.922, 0.353, 0.275, 1.0))
+                    } else {
+                        ("▶ 开始计时", vec4(0.380, 0.741, 0.310, 1.0))
+                    };
+                    let timer_button = self.view.button(ids!(timer_button));
+                    timer_button.set_text(cx, text);
+                    timer_button.apply_over(cx, live! { draw_bg: { color: (color) } });
+                    if is_running {
+                        self.tick_timer = cx.start_interval(1.0);
+                    } else {
+                        cx.stop_timer(self.tick_timer);
+                    }
+                }
+            }
+        }
+
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+/// 成员的显示名称，没有加载时回退到用户 ID
+pub(crate) fn member_name(cx: &mut Cx, user_id: &matrix_sdk::ruma::UserId) -> String {
+    with_user_profile(cx, user_id.to_owned(), true, |profile, _| {
+        profile.displayable_name().to_string()
+    })
+    .unwrap_or_else(|| user_id.to_string())
+}
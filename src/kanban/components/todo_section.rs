@@ -1,5 +1,7 @@
 use makepad_widgets::*;
 
+use crate::kanban::state::calendar;
+
 live_design! {
     use link::theme::*;
     use link::widgets::*;
@@ -8,44 +10,115 @@ live_design! {
     TodoItem = {{TodoItem}} {
         width: Fill,
         height: Fit,
-        flow: Right,
-        spacing: 10,
-        align: {y: 0.5},
+        flow: Down,
+        spacing: 4,
         padding: {top: 5, bottom: 5, left: 5, right: 5},
 
-        // 复选框（使用Button模拟）
-        checkbox = <Button> {
-            width: 20,
-            height: 20,
-            text: "",
-            draw_bg: {
-                color: #FFFFFF,
-                border_color: #DFE1E6,
+        <View> {
+            width: Fill,
+            height: Fit,
+            flow: Right,
+            spacing: 10,
+            align: {y: 0.5},
+
+            // 复选框（使用Button模拟）
+            checkbox = <Button> {
+                width: 20,
+                height: 20,
+                text: "",
+                draw_bg: {
+                    color: #FFFFFF,
+                    border_color: #DFE1E6,
+                }
+            }
+
+            // Todo文本
+            todo_text = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "待办事项",
+                draw_text: {
+                    color: #172B4D,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                }
+            }
+
+            // 删除按钮
+            delete_btn = <Button> {
+                width: 50,
+                height: 25,
+                text: "删除",
+                draw_bg: {
+                    color: #FF6B6B,
+                }
+                draw_text: {
+                    color: #FFFFFF,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
             }
         }
 
-        // Todo文本
-        todo_text = <Label> {
+        // 负责人、截止时间与子卡片
+        <View> {
             width: Fill,
             height: Fit,
-            text: "待办事项",
-            draw_text: {
-                color: #172B4D,
-                text_style: <THEME_FONT_REGULAR>{font_size: 14}
+            flow: Right,
+            spacing: 8,
+            align: {y: 0.5},
+            padding: {left: 30},
+
+            todo_meta = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "",
+                draw_text: {
+                    color: #5E6C84,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 11}
+                }
             }
-        }
 
-        // 删除按钮
-        delete_btn = <Button> {
-            width: 50,
-            height: 25,
-            text: "删除",
-            draw_bg: {
-                color: #FF6B6B,
+            // 依次切换卡片成员作为负责人
+            assignee_btn = <Button> {
+                width: Fit,
+                height: 22,
+                text: "👤",
+                draw_bg: {
+                    color: #DFE1E6,
+                }
+                draw_text: {
+                    color: #172B4D,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 11}
+                }
             }
-            draw_text: {
-                color: #FFFFFF,
-                text_style: <THEME_FONT_REGULAR>{font_size: 12}
+
+            // 回车保存，清空后回车清除截止时间
+            due_input = <TextInput> {
+                width: 95,
+                height: 22,
+                empty_text: "YYYY-MM-DD",
+                draw_text: {
+                    color: #172B4D,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 11}
+                }
+                draw_bg: {
+                    color: #FFFFFF,
+                    border_color: #DFE1E6,
+                    border_width: 1.0,
+                    radius: 3.0,
+                }
+            }
+
+            convert_btn = <Button> {
+                width: Fit,
+                height: 22,
+                text: "转为卡片",
+                draw_bg: {
+                    color: #0079BF,
+                }
+                draw_text: {
+                    color: #FFFFFF,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 11}
+                }
             }
         }
     }
@@ -191,6 +264,11 @@ pub struct TodoItem {
     todo_id: String,
     #[rust]
     card_id: Option<matrix_sdk::ruma::OwnedRoomId>,
+    #[rust]
+    assignee: Option<matrix_sdk::ruma::OwnedUserId>,
+    /// 卡片 Room 的成员（负责人候选）
+    #[rust]
+    members: Vec<matrix_sdk::ruma::OwnedUserId>,
 }
 
 impl Widget for TodoItem {
@@ -219,6 +297,60 @@ impl Widget for TodoItem {
                     });
                 }
             }
+
+            // 处理负责人按钮：未分配 → 成员 1 → 成员 2 → ... → 未分配
+            if self.view.button(ids!(assignee_btn)).clicked(actions) {
+                if let Some(card_id) = &self.card_id {
+                    let next_index = self
+                        .assignee
+                        .as_ref()
+                        .and_then(|assignee| self.members.iter().position(|m| m == assignee))
+                        .map_or(0, |index| index + 1);
+                    let assignee = self.members.get(next_index).cloned();
+                    log!("TodoItem: 设置待办 '{}' 的负责人为 {:?}", self.todo_id, assignee);
+                    cx.action(crate::kanban::KanbanActions::SetTodoAssignee {
+                        card_id: card_id.clone(),
+                        todo_id: self.todo_id.clone(),
+                        assignee,
+                    });
+                }
+            }
+
+            // 处理截止时间输入
+            if let Some((text, _)) = self.view.text_input(ids!(due_input)).returned(actions) {
+                if let Some(card_id) = &self.card_id {
+                    let text = text.trim();
+                    let due_time = if text.is_empty() {
+                        Some(None)
+                    } else {
+                        chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                            .ok()
+                            .map(|date| Some(calendar::end_time_on_day(None, date)))
+                    };
+                    match due_time {
+                        Some(due_time) => {
+                            log!("TodoItem: 设置待办 '{}' 的截止时间为 {:?}", self.todo_id, due_time);
+                            cx.action(crate::kanban::KanbanActions::SetTodoDueTime {
+                                card_id: card_id.clone(),
+                                todo_id: self.todo_id.clone(),
+                                due_time,
+                            });
+                        }
+                        None => log!("⚠️ TodoItem: 无效的日期 '{}'", text),
+                    }
+                }
+            }
+
+            // 处理转为卡片 / 打开子卡片按钮
+            if self.view.button(ids!(convert_btn)).clicked(actions) {
+                if let Some(card_id) = &self.card_id {
+                    log!("TodoItem: 转换待办 '{}' 为子卡片", self.todo_id);
+                    cx.action(crate::kanban::KanbanActions::ConvertTodoToCard {
+                        card_id: card_id.clone(),
+                        todo_id: self.todo_id.clone(),
+                    });
+                }
+            }
         }
     }
 
@@ -299,6 +431,17 @@ impl Widget for TodoSection {
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        // 卡片成员（负责人候选）
+        let members: Vec<matrix_sdk::ruma::OwnedUserId> = scope
+            .data
+            .get::<crate::app::AppState>()
+            .and_then(|app_state| {
+                let state = &app_state.kanban_state;
+                state.card_members.get(state.selected_card_id.as_ref()?)
+            })
+            .map(|members| members.iter().map(|m| m.user_id.clone()).collect())
+            .unwrap_or_default();
+
         // 从 AppState 获取 selected_card_id
         let todos: Vec<_> = if let Some(app_state) = scope.data.get::<crate::app::AppState>() {
             if let Some(selected_card_id) = &app_state.kanban_state.selected_card_id {
@@ -314,8 +457,14 @@ impl Widget for TodoSection {
 
                     // log!("🎨 TodoSection draw_walk: card_id={}, todos_count={}", selected_card_id, card.todos.len());
 
-                    // 克隆todos列表
-                    card.todos.clone()
+                    // 克隆todos列表，同时生成负责人 / 截止时间 / 子卡片说明
+                    card.todos
+                        .iter()
+                        .map(|todo| {
+                            let meta = todo_meta_text(&app_state.kanban_state, todo);
+                            (todo.clone(), meta)
+                        })
+                        .collect()
                 } else {
                     log!("⚠️ TodoSection: Card not found in state");
                     Vec::new()
@@ -342,7 +491,7 @@ impl Widget for TodoSection {
                     // log!("🎨 TodoSection: Rendering todo #{}: '{}'", todo_idx, todos[todo_idx].text);
 
                     let todo_item_widget = list.item(cx, todo_idx, live_id!(TodoItem));
-                    let todo = &todos[todo_idx];
+                    let (todo, meta) = &todos[todo_idx];
 
                     // 设置复选框状态（通过改变背景色和文本）
                     let checkbox_btn = todo_item_widget.button(ids!(checkbox));
@@ -356,11 +505,32 @@ impl Widget for TodoSection {
                     let todo_label = todo_item_widget.label(ids!(todo_text));
                     todo_label.set_text(cx, &todo.text);
 
+                    todo_item_widget.label(ids!(todo_meta)).set_text(cx, meta);
+                    todo_item_widget.button(ids!(convert_btn)).set_text(
+                        cx,
+                        if todo.child_card_id.is_some() {
+                            "打开卡片"
+                        } else {
+                            "转为卡片"
+                        },
+                    );
+
                     // 传递 todo_id 和 card_id 给 TodoItem
                     let todo_item_ref = todo_item_widget.as_todo_item();
                     if let Some(mut todo_item) = todo_item_ref.borrow_mut() {
+                        // 切换到另一条待办时用它的截止时间填充输入框
+                        if todo_item.todo_id != todo.id {
+                            let due_text = todo
+                                .due_time
+                                .and_then(calendar::local_date)
+                                .map(|date| date.format("%Y-%m-%d").to_string())
+                                .unwrap_or_default();
+                            todo_item.view.text_input(ids!(due_input)).set_text(cx, &due_text);
+                        }
                         todo_item.todo_id = todo.id.clone();
                         todo_item.card_id = self.card_id.clone();
+                        todo_item.assignee = todo.assignee.clone();
+                        todo_item.members = members.clone();
                     }
 
                     todo_item_widget.draw_all(cx, &mut Scope::empty());
@@ -370,3 +540,36 @@ impl Widget for TodoSection {
         DrawStep::done()
     }
 }
+
+/// 待办的负责人、截止时间和子卡片状态，例如"👤 Alice · 📅 2025-03-10 · 🧩 进行中"
+fn todo_meta_text(
+    state: &crate::kanban::KanbanAppState,
+    todo: &crate::kanban::state::kanban_state::TodoItem,
+) -> String {
+    let mut parts = Vec::new();
+    if let Some(assignee) = &todo.assignee {
+        let name = state
+            .selected_card_id
+            .as_ref()
+            .and_then(|card_id| state.card_members.get(card_id))
+            .and_then(|members| members.iter().find(|m| &m.user_id == assignee))
+            .map_or_else(|| assignee.to_string(), |member| member.name());
+        parts.push(format!("👤 {}", name));
+    }
+    if let Some(date) = todo.due_time.and_then(calendar::local_date) {
+        parts.push(format!("📅 {}", date.format("%Y-%m-%d")));
+    }
+    if let Some(child_id) = &todo.child_card_id {
+        let status = state.cards.get(child_id).map_or_else(
+            || "未加载".to_string(),
+            |child| {
+                state
+                    .workflow(&child.space_id)
+                    .display_name(&child.status)
+                    .to_string()
+            },
+        );
+        parts.push(format!("🧩 {}", status));
+    }
+    parts.join(" · ")
+}
@@ -0,0 +1,195 @@
+use makepad_widgets::*;
+
+use crate::kanban::state::workflow::Workflow;
+
+live_design! {
+    use link::theme::*;
+    use link::widgets::*;
+
+    pub WorkflowEditorModal = {{WorkflowEditorModal}} {
+        width: Fit,
+        height: Fit,
+
+        <RoundedView> {
+            width: 520,
+            height: Fit,
+            padding: 20,
+            flow: Down,
+            spacing: 15,
+            draw_bg: {
+                color: #FFFFFF
+            }
+
+            // 标题
+            <Label> {
+                width: Fill,
+                height: Fit,
+                text: "编辑工作流",
+                draw_text: {
+                    text_style: <THEME_FONT_BOLD>{font_size: 18}
+                    color: #172B4D
+                }
+            }
+
+            // 格式说明
+            <Label> {
+                width: Fill,
+                height: Fit,
+                text: "每行一个状态（Shift+Enter 换行）：名称 [WIP上限] [doing|done|archived] -> 可流转到的状态, ...\n第一个状态为新卡片的初始状态；不写 -> 表示可以流转到任意状态。",
+                draw_text: {
+                    wrap: Word,
+                    color: #5E6C84,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
+            }
+
+            // 工作流定义输入框
+            workflow_input = <TextInput> {
+                width: Fill,
+                height: 200,
+                text: "",
+                draw_text: {
+                    color: #172B4D,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                }
+                draw_bg: {
+                    color: #FFFFFF,
+                    border_color: #DFE1E6,
+                    border_width: 2.0,
+                    radius: 3.0,
+                }
+                draw_cursor: {
+                    color: #172B4D
+                }
+            }
+
+            // 解析错误提示
+            error_label = <Label> {
+                width: Fill,
+                height: Fit,
+                text: "",
+                draw_text: {
+                    wrap: Word,
+                    color: #EB5A46,
+                    text_style: <THEME_FONT_REGULAR>{font_size: 12}
+                }
+            }
+
+            // 按钮区域
+            <View> {
+                width: Fill,
+                height: Fit,
+                flow: Right,
+                spacing: 10,
+                align: {x: 1.0, y: 0.5}
+
+                cancel_button = <Button> {
+                    width: 80,
+                    height: 36,
+                    text: "取消",
+                    draw_bg: {
+                        color: #95A5A6,
+                        radius: 3.0,
+                    }
+                    draw_text: {
+                        color: #FFFFFF,
+                        text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                    }
+                }
+
+                save_button = <Button> {
+                    width: 80,
+                    height: 36,
+                    text: "保存",
+                    draw_bg: {
+                        color: #0079BF,
+                        radius: 3.0,
+                    }
+                    draw_text: {
+                        color: #FFFFFF,
+                        text_style: <THEME_FONT_REGULAR>{font_size: 14}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct WorkflowEditorModal {
+    #[deref]
+    view: View,
+    #[rust]
+    space_id: Option<matrix_sdk::ruma::OwnedRoomId>,
+    #[rust]
+    current: Workflow,
+}
+
+impl Widget for WorkflowEditorModal {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+
+        if let Event::Actions(actions) = event {
+            // 处理保存按钮
+            if self.view.button(ids!(save_button)).clicked(actions) {
+                let Some(space_id) = self.space_id.clone() else {
+                    log!("⚠️ WorkflowEditorModal: space_id 为 None");
+                    return;
+                };
+
+                let text = self.view.text_input(ids!(workflow_input)).text();
+                match Workflow::parse_definition(&text, &self.current) {
+                    Ok(workflow) => {
+                        log!(
+                            "WorkflowEditorModal: 保存工作流，共 {} 个状态 (Space: {})",
+                            workflow.statuses.len(),
+                            space_id
+                        );
+                        self.view.label(ids!(error_label)).set_text(cx, "");
+                        cx.action(crate::kanban::KanbanActions::SaveSpaceWorkflow {
+                            space_id,
+                            workflow,
+                        });
+                        cx.action(crate::kanban::KanbanActions::CloseWorkflowEditor);
+                    }
+                    Err(e) => {
+                        log!("⚠️ WorkflowEditorModal: 工作流定义无效: {}", e);
+                        self.view
+                            .label(ids!(error_label))
+                            .set_text(cx, &format!("{}", e));
+                        self.view.redraw(cx);
+                    }
+                }
+            }
+
+            // 处理取消按钮
+            if self.view.button(ids!(cancel_button)).clicked(actions) {
+                log!("WorkflowEditorModal: 取消编辑");
+                cx.action(crate::kanban::KanbanActions::CloseWorkflowEditor);
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+impl WorkflowEditorModalRef {
+    pub fn set_data(
+        &self,
+        cx: &mut Cx,
+        space_id: matrix_sdk::ruma::OwnedRoomId,
+        workflow: Workflow,
+    ) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.space_id = Some(space_id);
+            inner
+                .view
+                .text_input(ids!(workflow_input))
+                .set_text(cx, &workflow.to_definition());
+            inner.view.label(ids!(error_label)).set_text(cx, "");
+            inner.current = workflow;
+        }
+    }
+}
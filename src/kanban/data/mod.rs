@@ -1,5 +1,4 @@
-pub mod models;
 pub mod repositories;
 
 // Re-export main types
-pub use models::*;
+pub use repositories::*;
@@ -1,467 +1,471 @@
 //! 看板数据仓储层
 //!
-//! 实现与 Matrix SDK 的交互，提供看板、列表、卡片的数据操作
+//! 按数据类型划分的仓储 trait，读写的都是 [`crate::kanban::state::kanban_state`] 中的统一模型，
+//! 在 Matrix 事件中的存储格式见 [`crate::kanban::state::schema`]。
+//! [`MatrixKanbanAdapter`] 实现了所有仓储，[`crate::kanban::api::KanbanApi`] 在它们之上
+//! 组合出界面使用的操作。
+
+use std::path::Path;
+
+use anyhow::Result;
+use matrix_sdk::ruma::{OwnedRoomId, RoomId, UserId};
+
+use crate::kanban::matrix_adapter::MatrixKanbanAdapter;
+use crate::kanban::state::attachments::CardAttachment;
+use crate::kanban::state::history::{CardRevision, TagLibraryRevision};
+use crate::kanban::state::kanban_state::{
+    ActivityType, CardActivity, CardMember, KanbanBoard, KanbanCard, KanbanList, SpaceTag, TodoItem,
+};
+use crate::kanban::state::permissions::BoardPermissions;
+use crate::kanban::state::schema::BoardMetadata;
+use crate::kanban::state::template::CardTemplate;
+use crate::kanban::state::workflow::Workflow;
+
+/// 看板仓储（看板对应包含列表 Space 的父 Space）
+#[async_trait::async_trait]
+pub trait BoardRepository {
+    /// 加载当前用户已加入或收到邀请的所有看板
+    async fn load_boards(&self) -> Result<Vec<KanbanBoard>>;
+
+    /// 创建看板，返回看板 ID
+    async fn create_board(&self, name: &str, background_color: &str) -> Result<OwnedRoomId>;
+
+    /// 保存看板的背景、归档状态和订阅
+    async fn save_board(&self, board: &KanbanBoard) -> Result<()>;
 
-use makepad_widgets::log;
-use matrix_sdk::{ruma::RoomId, Client};
-use anyhow::{Result, anyhow};
-use std::sync::Arc;
-use tokio::sync::Mutex;
-use crate::kanban::data::models::{KanbanBoard, KanbanList, KanbanCard, CardDueDate};
+    /// 把列表加入看板
+    async fn add_list(&self, board_id: &RoomId, list_id: &RoomId) -> Result<()>;
 
-/// 看板仓储 trait
+    /// 邀请用户加入看板及其中的列表和卡片，返回新邀请的 Room 数量
+    async fn invite_member(&self, board_id: &RoomId, user_id: &UserId) -> Result<usize>;
+
+    /// 接受看板邀请，返回加入的 Room 数量
+    async fn join_board(&self, board_id: &RoomId) -> Result<usize>;
+}
+
+/// 列表仓储（列表对应 Matrix Space）
 #[async_trait::async_trait]
-pub trait BoardRepositoryTrait {
-    async fn create_board(
+pub trait ListRepository {
+    /// 加载当前用户可见的所有列表
+    async fn load_lists(&self) -> Result<Vec<KanbanList>>;
+
+    /// 创建列表，返回列表 ID
+    async fn create_list(&self, name: &str) -> Result<OwnedRoomId>;
+
+    /// 重命名列表
+    async fn rename_list(&self, list_id: &RoomId, name: &str) -> Result<()>;
+
+    /// 归档（`Some(时间)`）或恢复（`None`）列表
+    async fn set_list_archived(&self, list_id: &RoomId, archived_at: Option<u64>) -> Result<()>;
+
+    /// 永久删除列表及其中的卡片
+    async fn delete_list(&self, list_id: &RoomId, card_ids: &[OwnedRoomId]) -> Result<()>;
+}
+
+/// 卡片仓储（卡片对应 Matrix Room）
+#[async_trait::async_trait]
+pub trait CardRepository {
+    /// 在列表中创建卡片，返回卡片 ID
+    async fn create_card(&self, list_id: &RoomId, title: &str) -> Result<OwnedRoomId>;
+
+    /// 加载卡片（包括待办）
+    async fn load_card(&self, card_id: &RoomId, list_id: OwnedRoomId) -> Result<KanbanCard>;
+
+    /// 加载服务器端的卡片原始数据，用于和离线修改合并（任何一部分加载失败都返回错误）
+    async fn load_remote_card(&self, card_id: &RoomId, list_id: OwnedRoomId) -> Result<KanbanCard>;
+
+    /// 保存卡片元数据（不包括待办）
+    async fn save_card(&self, card: &KanbanCard) -> Result<()>;
+
+    /// 更新卡片 Room 的名称
+    async fn rename_card(&self, card_id: &RoomId, title: &str) -> Result<()>;
+
+    /// 保存卡片的待办
+    async fn save_todos(&self, card_id: &RoomId, todos: &[TodoItem]) -> Result<()>;
+
+    /// 把卡片从 `source_list_id` 移动到 `card.space_id`
+    async fn move_card(&self, card: &KanbanCard, source_list_id: &RoomId) -> Result<()>;
+
+    /// 永久删除卡片
+    async fn delete_card(&self, list_id: &RoomId, card_id: &RoomId) -> Result<()>;
+
+    /// 为周期卡片创建下一张，返回（服务器端的原卡片，新卡片）
+    ///
+    /// 服务器端的原卡片已没有周期规则时不创建新卡片
+    async fn spawn_next_occurrence(
         &self,
-        client: &Client,
-        name: &str,
-        description: Option<&str>,
-    ) -> Result<KanbanBoard>;
-    async fn get_board(&self, client: &Client, room_id: &RoomId) -> Result<KanbanBoard>;
-    async fn update_board(
+        card: &KanbanCard,
+    ) -> Result<(KanbanCard, Option<KanbanCard>)>;
+
+    /// 把父卡片的待办转换为子卡片
+    async fn create_child_card(&self, parent: &KanbanCard, todo: &TodoItem) -> Result<KanbanCard>;
+}
+
+/// 成员仓储（成员和负责人对应卡片 Room 的成员）
+#[async_trait::async_trait]
+pub trait MemberRepository {
+    /// 加载卡片 Room 的成员
+    async fn load_members(&self, card_id: &RoomId) -> Result<Vec<CardMember>>;
+
+    /// 把用户设为卡片负责人（必要时邀请进卡片 Room）
+    async fn assign_member(&self, card_id: &RoomId, user_id: &UserId) -> Result<()>;
+
+    /// 取消用户的卡片负责人身份
+    async fn unassign_member(&self, card_id: &RoomId, user_id: &UserId) -> Result<()>;
+}
+
+/// 标签仓储（标签库保存在列表 Space 上）
+#[async_trait::async_trait]
+pub trait TagRepository {
+    /// 加载列表的标签库
+    async fn load_tags(&self, list_id: &RoomId) -> Result<Vec<SpaceTag>>;
+
+    /// 保存列表的标签库
+    async fn save_tags(&self, list_id: &RoomId, tags: Vec<SpaceTag>) -> Result<()>;
+
+    /// 向标签库添加标签
+    async fn add_tag(&self, list_id: &RoomId, tag: SpaceTag) -> Result<()>;
+
+    /// 更新标签库中的标签
+    async fn update_tag(&self, list_id: &RoomId, tag: SpaceTag) -> Result<()>;
+
+    /// 从标签库删除标签
+    async fn delete_tag(&self, list_id: &RoomId, tag_id: &str) -> Result<()>;
+}
+
+/// 工作流仓储（工作流保存在列表 Space 的状态事件中）
+#[async_trait::async_trait]
+pub trait WorkflowRepository {
+    /// 加载列表的工作流，未设置时返回 `None`
+    async fn load_workflow(&self, list_id: &RoomId) -> Result<Option<Workflow>>;
+
+    /// 保存列表的工作流
+    async fn save_workflow(&self, list_id: &RoomId, workflow: &Workflow) -> Result<()>;
+
+    /// 把列表中卡片的状态迁移到新工作流，返回被修改的卡片
+    async fn migrate_card_statuses(
         &self,
-        client: &Client,
-        room_id: &RoomId,
-        updates: BoardUpdateRequest,
-    ) -> Result<()>;
-    async fn delete_board(&self, client: &Client, room_id: &RoomId) -> Result<()>;
-    async fn get_boards(&self, client: &Client) -> Result<Vec<KanbanBoard>>;
+        list_id: &RoomId,
+        workflow: &Workflow,
+    ) -> Result<Vec<KanbanCard>>;
 }
 
-/// 列表仓储 trait
+/// 权限仓储（权限对应列表 Space 的 power levels）
 #[async_trait::async_trait]
-pub trait ListRepositoryTrait {
-    async fn create_list(&self, board_id: &RoomId, name: &str) -> Result<KanbanList>;
-    async fn update_list(
+pub trait PermissionRepository {
+    /// 加载列表的权限设置
+    async fn load_permissions(&self, list_id: &RoomId) -> Result<BoardPermissions>;
+
+    /// 保存列表的权限设置
+    async fn save_permissions(
         &self,
-        board_id: &RoomId,
-        list_id: &str,
-        updates: ListUpdateRequest,
+        list_id: &RoomId,
+        permissions: &BoardPermissions,
     ) -> Result<()>;
-    async fn delete_list(&self, board_id: &RoomId, list_id: &str) -> Result<()>;
-    async fn move_list(&self, board_id: &RoomId, list_id: &str, new_position: f64) -> Result<()>;
 }
 
-/// 卡片仓储 trait
+/// 卡片模板仓储（模板保存在列表 Space 的状态事件中）
 #[async_trait::async_trait]
-pub trait CardRepositoryTrait {
-    async fn create_card(
+pub trait TemplateRepository {
+    /// 加载列表的卡片模板
+    async fn load_templates(&self, list_id: &RoomId) -> Result<Vec<CardTemplate>>;
+
+    /// 保存列表的卡片模板
+    async fn save_templates(&self, list_id: &RoomId, templates: &[CardTemplate]) -> Result<()>;
+
+    /// 按模板在列表中创建卡片
+    async fn create_card_from_template(
         &self,
-        client: &Client,
-        board_id: &RoomId,
-        list_id: &str,
-        title: &str,
+        list_id: &RoomId,
+        template: &CardTemplate,
+        end_time: Option<u64>,
     ) -> Result<KanbanCard>;
-    async fn update_card(
+}
+
+/// 活动仓储（活动是卡片 Room 时间线中的消息）
+#[async_trait::async_trait]
+pub trait ActivityRepository {
+    /// 加载卡片最近的活动（最新的在前）
+    async fn load_activities(
         &self,
-        client: &Client,
-        board_id: &RoomId,
-        card_id: &str,
-        updates: CardUpdateRequest,
-    ) -> Result<()>;
-    async fn delete_card(&self, board_id: &RoomId, card_id: &str) -> Result<()>;
-    async fn move_card(
+        card_id: &RoomId,
+        limit: Option<usize>,
+    ) -> Result<Vec<CardActivity>>;
+
+    /// 加载卡片在 `since`（Unix timestamp 秒）之后的所有活动（最新的在前）
+    async fn load_activities_since(
+        &self,
+        card_id: &RoomId,
+        since: u64,
+    ) -> Result<Vec<CardActivity>>;
+
+    /// 记录一条活动
+    async fn record_activity(
         &self,
-        board_id: &RoomId,
-        card_id: &str,
-        to_list_id: &str,
-        new_position: f64,
+        card_id: &RoomId,
+        activity_type: ActivityType,
+        text: String,
+        metadata: Option<serde_json::Value>,
     ) -> Result<()>;
-    async fn get_cards(&self, board_id: &RoomId, list_id: &str) -> Result<Vec<KanbanCard>>;
 }
 
-/// 看板更新请求
-#[derive(Debug, Clone, Default)]
-pub struct BoardUpdateRequest {
-    pub name: Option<String>,
-    pub description: Option<Option<String>>,
-    pub background_color: Option<String>,
-    pub background_image: Option<Option<String>>,
-}
+/// 附件仓储（附件是卡片 Room 时间线中的 `m.image` / `m.file` 消息）
+#[async_trait::async_trait]
+pub trait AttachmentRepository {
+    /// 加载卡片的附件
+    async fn load_attachments(&self, card_id: &RoomId) -> Result<Vec<CardAttachment>>;
 
-/// 列表更新请求
-#[derive(Debug, Clone, Default)]
-pub struct ListUpdateRequest {
-    pub name: Option<String>,
-    pub archived: Option<bool>,
+    /// 上传本地文件作为卡片附件；超过服务器上传上限的文件返回错误
+    async fn upload_attachment(&self, card_id: &RoomId, path: &Path) -> Result<CardAttachment>;
 }
 
-/// 卡片更新请求
-#[derive(Debug, Clone, Default)]
-pub struct CardUpdateRequest {
-    pub title: Option<String>,
-    pub description: Option<Option<String>>,
-    pub label_ids: Option<Vec<String>>,
-    pub member_ids: Option<Vec<matrix_sdk::ruma::OwnedUserId>>,
-    pub due_date: Option<Option<CardDueDate>>,
-    pub is_starred: Option<bool>,
-    pub archived: Option<bool>,
-}
+/// 历史版本仓储（旧版本是卡片 Room / 列表 Space 时间线中的元数据消息和状态事件）
+#[async_trait::async_trait]
+pub trait HistoryRepository {
+    /// 加载卡片的历史版本（最早的在前）
+    async fn load_card_history(&self, card_id: &RoomId) -> Result<Vec<CardRevision>>;
 
-/// Matrix SDK 看板仓储实现
-pub struct MatrixBoardRepository {
-    /// 本地存储的看板列表
-    local_boards: Arc<Mutex<Vec<KanbanBoard>>>,
+    /// 加载列表标签库的历史版本（最早的在前）
+    async fn load_tag_history(&self, list_id: &RoomId) -> Result<Vec<TagLibraryRevision>>;
 }
 
-impl MatrixBoardRepository {
-    pub fn new() -> Self {
-        Self {
-            local_boards: Arc::new(Mutex::new(Vec::new())),
-        }
+#[async_trait::async_trait]
+impl BoardRepository for MatrixKanbanAdapter {
+    async fn load_boards(&self) -> Result<Vec<KanbanBoard>> {
+        self.get_all_boards().await
+    }
+
+    async fn create_board(&self, name: &str, background_color: &str) -> Result<OwnedRoomId> {
+        MatrixKanbanAdapter::create_board(self, name, background_color).await
+    }
+
+    async fn save_board(&self, board: &KanbanBoard) -> Result<()> {
+        self.save_board_metadata(&board.id, &BoardMetadata::from_board(board))
+            .await
     }
-}
 
-impl Default for MatrixBoardRepository {
-    fn default() -> Self {
-        Self::new()
+    async fn add_list(&self, board_id: &RoomId, list_id: &RoomId) -> Result<()> {
+        self.add_list_to_board(board_id, list_id).await
+    }
+
+    async fn invite_member(&self, board_id: &RoomId, user_id: &UserId) -> Result<usize> {
+        self.invite_to_board(board_id, user_id).await
+    }
+
+    async fn join_board(&self, board_id: &RoomId) -> Result<usize> {
+        MatrixKanbanAdapter::join_board(self, board_id).await
     }
 }
 
 #[async_trait::async_trait]
-impl BoardRepositoryTrait for MatrixBoardRepository {
-    async fn create_board(
-        &self,
-        _client: &Client,
-        name: &str,
-        description: Option<&str>,
-    ) -> Result<KanbanBoard> {
-        // 生成占位 Room ID (实际项目中需要从 Matrix SDK 创建房间后获取)
-        let room_id = format!("!kanban-{}:local", uuid::Uuid::new_v4());
-        let room_id = matrix_sdk::ruma::OwnedRoomId::try_from(room_id).unwrap_or_else(|_| {
-            matrix_sdk::ruma::OwnedRoomId::try_from("!kanban:local").expect("fallback board id")
-        });
-
-        let board = KanbanBoard {
-            id: room_id.clone(),
-            name: name.to_string(),
-            description: description.map(|s| s.to_string()),
-            ..Default::default()
-        };
-
-        // 保存到本地存储
-        let mut boards = self.local_boards.lock().await;
-        boards.push(board.clone());
-
-        // TODO: 实际创建 Matrix 房间
-        // 需要使用 MatrixRequest 提交创建房间请求
-        log!(
-            "Creating board '{}' with description: {:?}",
-            name,
-            description
-        );
-
-        Ok(board)
-    }
-
-    async fn get_board(&self, client: &Client, room_id: &RoomId) -> Result<KanbanBoard> {
-        let room = client
-            .get_room(room_id)
-            .ok_or_else(|| anyhow!("Room not found: {}", room_id))?;
-
-        let name = room.name().unwrap_or_default().to_string();
-        let topic = room.topic();
-
-        let board = KanbanBoard {
-            id: room_id.to_owned(),
-            name,
-            description: topic.map(|t| t.to_string()),
-            background_color: "#0079BF".to_string(),
-            ..Default::default()
-        };
-
-        Ok(board)
-    }
-
-    async fn update_board(
-        &self,
-        _client: &Client,
-        _room_id: &RoomId,
-        updates: BoardUpdateRequest,
-    ) -> Result<()> {
-        let _room = _client
-            .get_room(_room_id)
-            .ok_or_else(|| anyhow!("Room not found: {}", _room_id))?;
-
-        // 更新房间名称
-        if let Some(name) = updates.name {
-            // 使用 Matrix SDK 更新房间名称
-            // 注意：Matrix SDK 的 Room 类型可能需要使用不同的方法
-            // 这里使用占位实现，实际项目中需要根据 SDK 版本调整
-            log!("Updating room name to: {}", name);
-            // TODO: 使用 room.set_name() 或发送状态事件更新房间名称
-        }
-
-        // 更新房间描述
-        if let Some(description) = updates.description {
-            log!("Updating room topic to: {:?}", description);
-            // TODO: 使用 room.set_topic() 或发送状态事件更新房间描述
-        }
-
-        // 更新背景颜色需要发送状态事件 (m.room.encryption 等)
-        // TODO: 背景颜色更新需要发送自定义状态事件
-
-        Ok(())
-    }
-
-    async fn delete_board(&self, client: &Client, room_id: &RoomId) -> Result<()> {
-        // 使用 LeaveRoom API
-        if let Some(room) = client.get_room(room_id) {
-            room.leave().await?;
-        }
-        Ok(())
-    }
-
-    async fn get_boards(&self, client: &Client) -> Result<Vec<KanbanBoard>> {
-        // 从 Client 获取所有房间
-        let rooms = client.rooms();
-
-        let mut boards = Vec::new();
-
-        // 遍历所有房间，筛选可能的看板
-        // 注意：这需要实际实现与 Room 的交互
-        // 目前返回空列表作为占位
-        for room in rooms {
-            let name = room.name().unwrap_or_default().to_string();
-            let board = KanbanBoard {
-                id: room.room_id().to_owned(),
-                name,
-                ..Default::default()
-            };
-            boards.push(board);
-        }
-
-        Ok(boards)
+impl ListRepository for MatrixKanbanAdapter {
+    async fn load_lists(&self) -> Result<Vec<KanbanList>> {
+        self.get_all_kanban_spaces().await
     }
-}
 
-/// Matrix SDK 列表仓储实现
-pub struct MatrixListRepository {
-    /// 本地存储的列表数据
-    local_lists: Arc<Mutex<Vec<KanbanList>>>,
-}
+    async fn create_list(&self, name: &str) -> Result<OwnedRoomId> {
+        self.create_space(name).await
+    }
 
-impl MatrixListRepository {
-    pub fn new() -> Self {
-        Self {
-            local_lists: Arc::new(Mutex::new(Vec::new())),
-        }
+    async fn rename_list(&self, list_id: &RoomId, name: &str) -> Result<()> {
+        self.rename_space(list_id, name).await
+    }
+
+    async fn set_list_archived(&self, list_id: &RoomId, archived_at: Option<u64>) -> Result<()> {
+        MatrixKanbanAdapter::set_list_archived(self, list_id, archived_at).await
     }
-}
 
-impl Default for MatrixListRepository {
-    fn default() -> Self {
-        Self::new()
+    async fn delete_list(&self, list_id: &RoomId, card_ids: &[OwnedRoomId]) -> Result<()> {
+        MatrixKanbanAdapter::delete_list(self, list_id, card_ids).await
     }
 }
 
 #[async_trait::async_trait]
-impl ListRepositoryTrait for MatrixListRepository {
-    async fn create_list(&self, board_id: &RoomId, name: &str) -> Result<KanbanList> {
-        let list = KanbanList::new(name, board_id.to_owned());
-        let mut lists = self.local_lists.lock().await;
-        lists.push(list.clone());
-        Ok(list)
+impl CardRepository for MatrixKanbanAdapter {
+    async fn create_card(&self, list_id: &RoomId, title: &str) -> Result<OwnedRoomId> {
+        MatrixKanbanAdapter::create_card(self, list_id, title).await
+    }
+
+    async fn load_card(&self, card_id: &RoomId, list_id: OwnedRoomId) -> Result<KanbanCard> {
+        MatrixKanbanAdapter::load_card(self, card_id, list_id).await
+    }
+
+    async fn load_remote_card(&self, card_id: &RoomId, list_id: OwnedRoomId) -> Result<KanbanCard> {
+        MatrixKanbanAdapter::load_remote_card(self, card_id, list_id).await
+    }
+
+    async fn save_card(&self, card: &KanbanCard) -> Result<()> {
+        self.save_card_metadata(card).await
+    }
+
+    async fn rename_card(&self, card_id: &RoomId, title: &str) -> Result<()> {
+        self.rename_card_room(card_id, title).await
+    }
+
+    async fn save_todos(&self, card_id: &RoomId, todos: &[TodoItem]) -> Result<()> {
+        self.save_card_todos(card_id, todos).await
+    }
+
+    async fn move_card(&self, card: &KanbanCard, source_list_id: &RoomId) -> Result<()> {
+        MatrixKanbanAdapter::move_card(self, &card.id, source_list_id, &card.space_id, card).await
     }
 
-    async fn update_list(
+    async fn delete_card(&self, list_id: &RoomId, card_id: &RoomId) -> Result<()> {
+        MatrixKanbanAdapter::delete_card(self, list_id, card_id).await
+    }
+
+    async fn spawn_next_occurrence(
         &self,
-        board_id: &RoomId,
-        list_id: &str,
-        updates: ListUpdateRequest,
-    ) -> Result<()> {
-        let mut lists = self.local_lists.lock().await;
-        if let Some(list) = lists
-            .iter_mut()
-            .find(|l| l.id == list_id && &l.board_id == board_id)
-        {
-            if let Some(name) = updates.name {
-                list.name = name;
-                list.updated_at = chrono::Utc::now().to_rfc3339();
-            }
-            if let Some(archived) = updates.archived {
-                list.is_archived = archived;
-            }
-        }
-        Ok(())
-    }
-
-    async fn delete_list(&self, board_id: &RoomId, list_id: &str) -> Result<()> {
-        let mut lists = self.local_lists.lock().await;
-        lists.retain(|l| !(l.id == list_id && &l.board_id == board_id));
-        Ok(())
-    }
-
-    async fn move_list(&self, board_id: &RoomId, list_id: &str, new_position: f64) -> Result<()> {
-        let mut lists = self.local_lists.lock().await;
-        if let Some(list) = lists
-            .iter_mut()
-            .find(|l| l.id == list_id && &l.board_id == board_id)
-        {
-            list.position = new_position;
-            list.updated_at = chrono::Utc::now().to_rfc3339();
-        }
-        Ok(())
+        card: &KanbanCard,
+    ) -> Result<(KanbanCard, Option<KanbanCard>)> {
+        MatrixKanbanAdapter::spawn_next_occurrence(self, card).await
     }
-}
 
-/// Matrix SDK 卡片仓储实现
-pub struct MatrixCardRepository {
-    /// 本地存储的卡片数据
-    local_cards: Arc<Mutex<Vec<KanbanCard>>>,
+    async fn create_child_card(&self, parent: &KanbanCard, todo: &TodoItem) -> Result<KanbanCard> {
+        MatrixKanbanAdapter::create_child_card(self, parent, todo).await
+    }
 }
 
-impl MatrixCardRepository {
-    pub fn new() -> Self {
-        Self {
-            local_cards: Arc::new(Mutex::new(Vec::new())),
-        }
+#[async_trait::async_trait]
+impl MemberRepository for MatrixKanbanAdapter {
+    async fn load_members(&self, card_id: &RoomId) -> Result<Vec<CardMember>> {
+        self.load_card_members(card_id).await
+    }
+
+    async fn assign_member(&self, card_id: &RoomId, user_id: &UserId) -> Result<()> {
+        self.assign_card_member(card_id, user_id).await
+    }
+
+    async fn unassign_member(&self, card_id: &RoomId, user_id: &UserId) -> Result<()> {
+        self.unassign_card_member(card_id, user_id).await
     }
 }
 
-impl Default for MatrixCardRepository {
-    fn default() -> Self {
-        Self::new()
+#[async_trait::async_trait]
+impl TagRepository for MatrixKanbanAdapter {
+    async fn load_tags(&self, list_id: &RoomId) -> Result<Vec<SpaceTag>> {
+        self.load_space_tags(list_id).await
+    }
+
+    async fn save_tags(&self, list_id: &RoomId, tags: Vec<SpaceTag>) -> Result<()> {
+        self.save_space_tags(list_id, tags).await
+    }
+
+    async fn add_tag(&self, list_id: &RoomId, tag: SpaceTag) -> Result<()> {
+        self.add_space_tag(list_id, tag).await
+    }
+
+    async fn update_tag(&self, list_id: &RoomId, tag: SpaceTag) -> Result<()> {
+        self.update_space_tag(list_id, tag).await
+    }
+
+    async fn delete_tag(&self, list_id: &RoomId, tag_id: &str) -> Result<()> {
+        self.delete_space_tag(list_id, tag_id).await
     }
 }
 
 #[async_trait::async_trait]
-impl CardRepositoryTrait for MatrixCardRepository {
-    async fn create_card(
+impl WorkflowRepository for MatrixKanbanAdapter {
+    async fn load_workflow(&self, list_id: &RoomId) -> Result<Option<Workflow>> {
+        self.load_space_workflow(list_id).await
+    }
+
+    async fn save_workflow(&self, list_id: &RoomId, workflow: &Workflow) -> Result<()> {
+        self.save_space_workflow(list_id, workflow).await
+    }
+
+    async fn migrate_card_statuses(
         &self,
-        client: &Client,
-        board_id: &RoomId,
-        list_id: &str,
-        title: &str,
-    ) -> Result<KanbanCard> {
-        let _room = client
-            .get_room(board_id)
-            .ok_or_else(|| anyhow!("Room not found: {}", board_id))?;
+        list_id: &RoomId,
+        workflow: &Workflow,
+    ) -> Result<Vec<KanbanCard>> {
+        MatrixKanbanAdapter::migrate_card_statuses(self, list_id, workflow).await
+    }
+}
 
-        // 卡片事件 ID (使用 UUID 作为占位，实际项目中需要从 Matrix 事件获取)
-        let card_id = uuid::Uuid::new_v4().to_string();
+#[async_trait::async_trait]
+impl PermissionRepository for MatrixKanbanAdapter {
+    async fn load_permissions(&self, list_id: &RoomId) -> Result<BoardPermissions> {
+        self.load_board_permissions(list_id).await
+    }
+
+    async fn save_permissions(
+        &self,
+        list_id: &RoomId,
+        permissions: &BoardPermissions,
+    ) -> Result<()> {
+        self.save_board_permissions(list_id, permissions).await
+    }
+}
 
-        let card = KanbanCard {
-            id: card_id.clone(),
-            title: title.to_string(),
-            list_id: list_id.to_string(),
-            board_id: board_id.to_owned(),
-            ..Default::default()
-        };
+#[async_trait::async_trait]
+impl TemplateRepository for MatrixKanbanAdapter {
+    async fn load_templates(&self, list_id: &RoomId) -> Result<Vec<CardTemplate>> {
+        self.load_space_templates(list_id).await
+    }
 
-        let mut cards = self.local_cards.lock().await;
-        cards.push(card.clone());
+    async fn save_templates(&self, list_id: &RoomId, templates: &[CardTemplate]) -> Result<()> {
+        self.save_space_templates(list_id, templates).await
+    }
 
-        // TODO: 实际发送消息到 Matrix 房间
-        // 需要使用 MatrixRequest 提交消息发送请求
-        log!("Created card '{}' in board {}", title, board_id);
+    async fn create_card_from_template(
+        &self,
+        list_id: &RoomId,
+        template: &CardTemplate,
+        end_time: Option<u64>,
+    ) -> Result<KanbanCard> {
+        MatrixKanbanAdapter::create_card_from_template(self, list_id, template, end_time).await
+    }
+}
 
-        Ok(card)
+#[async_trait::async_trait]
+impl ActivityRepository for MatrixKanbanAdapter {
+    async fn load_activities(
+        &self,
+        card_id: &RoomId,
+        limit: Option<usize>,
+    ) -> Result<Vec<CardActivity>> {
+        MatrixKanbanAdapter::load_activities(self, card_id, limit).await
     }
 
-    async fn update_card(
+    async fn load_activities_since(
         &self,
-        client: &Client,
-        board_id: &RoomId,
-        card_id: &str,
-        updates: CardUpdateRequest,
-    ) -> Result<()> {
-        let _room = client
-            .get_room(board_id)
-            .ok_or_else(|| anyhow!("Room not found: {}", board_id))?;
-
-        // 更新本地卡片数据
-        let mut cards = self.local_cards.lock().await;
-        if let Some(card) = cards
-            .iter_mut()
-            .find(|c| c.id == card_id && &c.board_id == board_id)
-        {
-            if let Some(title) = updates.title {
-                card.title = title;
-            }
-            if let Some(desc) = updates.description {
-                card.description = desc;
-            }
-            if let Some(labels) = updates.label_ids {
-                card.label_ids = labels;
-            }
-            if let Some(members) = updates.member_ids {
-                card.member_ids = members;
-            }
-            if let Some(due_date) = updates.due_date {
-                card.due_date = due_date;
-            }
-            if let Some(starred) = updates.is_starred {
-                card.is_starred = starred;
-            }
-            if let Some(archived) = updates.archived {
-                card.is_archived = archived;
-            }
-            card.updated_at = chrono::Utc::now().to_rfc3339();
-
-            // TODO: 实际更新 Matrix 房间中的消息
-            // 需要使用 MatrixRequest 提交消息编辑请求
-            log!("Updated card '{}' in board {}", card_id, board_id);
-        }
-        Ok(())
-    }
-
-    async fn delete_card(&self, board_id: &RoomId, card_id: &str) -> Result<()> {
-        let mut cards = self.local_cards.lock().await;
-        cards.retain(|c| !(c.id == card_id && &c.board_id == board_id));
-        Ok(())
-    }
-
-    async fn move_card(
+        card_id: &RoomId,
+        since: u64,
+    ) -> Result<Vec<CardActivity>> {
+        MatrixKanbanAdapter::load_activities_since(self, card_id, since).await
+    }
+
+    async fn record_activity(
         &self,
-        board_id: &RoomId,
-        card_id: &str,
-        to_list_id: &str,
-        new_position: f64,
+        card_id: &RoomId,
+        activity_type: ActivityType,
+        text: String,
+        metadata: Option<serde_json::Value>,
     ) -> Result<()> {
-        let mut cards = self.local_cards.lock().await;
-        if let Some(card) = cards
-            .iter_mut()
-            .find(|c| c.id == card_id && &c.board_id == board_id)
-        {
-            card.list_id = to_list_id.to_string();
-            card.position = new_position;
-            card.updated_at = chrono::Utc::now().to_rfc3339();
-        }
-        Ok(())
-    }
-
-    async fn get_cards(&self, board_id: &RoomId, list_id: &str) -> Result<Vec<KanbanCard>> {
-        let cards = self.local_cards.lock().await;
-        let filtered: Vec<KanbanCard> = cards
-            .iter()
-            .filter(|c| &c.board_id == board_id && c.list_id == list_id)
-            .cloned()
-            .collect();
-        Ok(filtered)
+        self.send_activity(card_id, activity_type, text, metadata)
+            .await
     }
 }
 
-/// 仓储工厂，用于创建和管理仓储实例
-pub struct RepositoryFactory {
-    pub board_repository: MatrixBoardRepository,
-    pub list_repository: MatrixListRepository,
-    pub card_repository: MatrixCardRepository,
-}
+#[async_trait::async_trait]
+impl AttachmentRepository for MatrixKanbanAdapter {
+    async fn load_attachments(&self, card_id: &RoomId) -> Result<Vec<CardAttachment>> {
+        MatrixKanbanAdapter::load_attachments(self, card_id).await
+    }
 
-impl RepositoryFactory {
-    pub fn new() -> Self {
-        Self {
-            board_repository: MatrixBoardRepository::new(),
-            list_repository: MatrixListRepository::new(),
-            card_repository: MatrixCardRepository::new(),
-        }
+    async fn upload_attachment(&self, card_id: &RoomId, path: &Path) -> Result<CardAttachment> {
+        MatrixKanbanAdapter::upload_attachment(self, card_id, path).await
     }
 }
 
-impl Default for RepositoryFactory {
-    fn default() -> Self {
-        Self::new()
+#[async_trait::async_trait]
+impl HistoryRepository for MatrixKanbanAdapter {
+    async fn load_card_history(&self, card_id: &RoomId) -> Result<Vec<CardRevision>> {
+        MatrixKanbanAdapter::load_card_history(self, card_id).await
+    }
+
+    async fn load_tag_history(&self, list_id: &RoomId) -> Result<Vec<TagLibraryRevision>> {
+        self.load_tag_library_history(list_id).await
     }
 }
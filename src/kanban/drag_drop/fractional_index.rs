@@ -0,0 +1,219 @@
+//! 分数索引排序键
+//!
+//! 卡片在列表中的顺序由字符串排序键（rank）决定，按字典序比较：
+//! - 排序键看作 62 进制的小数 `0.xxxx`，数字字符按 ASCII 递增排列，
+//!   字典序与数值大小一致；末尾不允许出现 `0`，保证两个排序键之间总能插入新键
+//! - 插入时只需要计算前后两个排序键之间的新键，不需要修改其他卡片，精度不受限制
+//! - 排序键相同（两个用户同时把卡片放到同一位置）时按卡片 ID 排序，
+//!   所有客户端看到的顺序一致
+//! - 无法计算新键（排序键过长或是旧卡片）时，重新均匀分配整个列表的排序键
+//!   并保存到服务器，见 [`evenly_spaced_ranks`] 和 `KanbanAppState::rank_at_end`
+
+use std::cmp::Ordering;
+
+/// 排序键使用的数字（按 ASCII 递增）
+const DIGITS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+const BASE: usize = DIGITS.len();
+
+/// 排序键超过该长度时需要重新分配整个列表
+pub const MAX_RANK_LEN: usize = 8;
+
+fn digit_value(digit: u8) -> Option<usize> {
+    match digit {
+        b'0'..=b'9' => Some((digit - b'0') as usize),
+        b'A'..=b'Z' => Some((digit - b'A') as usize + 10),
+        b'a'..=b'z' => Some((digit - b'a') as usize + 36),
+        _ => None,
+    }
+}
+
+/// 是否是有效的排序键（非空、只包含 62 进制数字、末尾不是 `0`）
+pub fn is_valid_rank(rank: &str) -> bool {
+    !rank.is_empty()
+        && !rank.ends_with('0')
+        && rank.bytes().all(|digit| digit_value(digit).is_some())
+}
+
+/// 计算位于 `before` 和 `after` 之间的排序键；`None` 表示列表开头 / 末尾
+///
+/// 两个排序键无效、相同或顺序颠倒时返回 `None`，此时需要重新分配整个列表。
+pub fn rank_between(before: Option<&str>, after: Option<&str>) -> Option<String> {
+    if before.is_some_and(|rank| !is_valid_rank(rank))
+        || after.is_some_and(|rank| !is_valid_rank(rank))
+    {
+        return None;
+    }
+
+    match (before, after) {
+        (Some(before), Some(after)) if before >= after => None,
+        (Some(before), None) => Some(increment(before)),
+        (None, Some(after)) => Some(decrement(after).unwrap_or_else(|| midpoint("", Some(after)))),
+        (before, after) => Some(midpoint(before.unwrap_or(""), after)),
+    }
+}
+
+/// 追加到末尾：把第一个不是最大数字的位置加一，其后截断（新键不会比原来的更长）
+fn increment(rank: &str) -> String {
+    let bytes = rank.as_bytes();
+    match bytes.iter().position(|&digit| digit != DIGITS[BASE - 1]) {
+        Some(i) => {
+            let mut next = rank[..i].to_string();
+            next.push(DIGITS[digit_value(bytes[i]).unwrap() + 1] as char);
+            next
+        }
+        None => midpoint(rank, None),
+    }
+}
+
+/// 插入到开头：把第一个大于 `1` 的位置减一，其后截断
+fn decrement(rank: &str) -> Option<String> {
+    let bytes = rank.as_bytes();
+    let i = bytes
+        .iter()
+        .position(|&digit| digit_value(digit).unwrap() > 1)?;
+    // 前面全是 0 / 1 时截断会得到更大的键，这里只处理前缀全为 0 的情况
+    if bytes[..i].iter().any(|&digit| digit != DIGITS[0]) {
+        return None;
+    }
+    let mut previous = rank[..i].to_string();
+    previous.push(DIGITS[digit_value(bytes[i]).unwrap() - 1] as char);
+    Some(previous)
+}
+
+/// 计算 `a` 和 `b` 之间的中点（`a` 为空表示 0，`b` 为 `None` 表示 1），要求 `a < b`
+fn midpoint(a: &str, b: Option<&str>) -> String {
+    if let Some(b) = b {
+        // 跳过公共前缀（`a` 较短时按末尾补 0 比较）
+        let a_bytes = a.as_bytes();
+        let b_bytes = b.as_bytes();
+        let mut n = 0;
+        while n < b_bytes.len() && a_bytes.get(n).copied().unwrap_or(DIGITS[0]) == b_bytes[n] {
+            n += 1;
+        }
+        if n > 0 {
+            return format!(
+                "{}{}",
+                &b[..n],
+                midpoint(&a[n.min(a.len())..], Some(&b[n..]))
+            );
+        }
+    }
+
+    let digit_a = a
+        .bytes()
+        .next()
+        .map_or(0, |digit| digit_value(digit).unwrap());
+    let digit_b = b
+        .and_then(|b| b.bytes().next())
+        .map_or(BASE, |digit| digit_value(digit).unwrap());
+
+    if digit_b - digit_a > 1 {
+        let middle = (digit_a + digit_b).div_ceil(2);
+        (DIGITS[middle] as char).to_string()
+    } else if let Some(b) = b.filter(|b| b.len() > 1) {
+        // 第一位相邻：取 `b` 的第一位即可（`b` 末尾不是 0，所以它比 `b` 小）
+        b[..1].to_string()
+    } else {
+        let rest = if a.is_empty() { "" } else { &a[1..] };
+        format!("{}{}", DIGITS[digit_a] as char, midpoint(rest, None))
+    }
+}
+
+/// 为 `count` 张卡片生成均匀分布、长度相同的排序键（用于重新分配整个列表）
+///
+/// 每两个排序键之间至少留出一个空位，之后的插入在很长一段时间内都不会变长。
+pub fn evenly_spaced_ranks(count: usize) -> Vec<String> {
+    let mut len = 1;
+    let mut capacity = BASE as u128;
+    while capacity < 2 * (count as u128 + 1) {
+        len += 1;
+        capacity *= BASE as u128;
+    }
+
+    let step = capacity / (count as u128 + 1);
+    (1..=count as u128)
+        .map(|i| {
+            let mut value = i * step;
+            let mut digits = vec![DIGITS[0]; len];
+            for digit in digits.iter_mut().rev() {
+                *digit = DIGITS[(value % BASE as u128) as usize];
+                value /= BASE as u128;
+            }
+            let rank = String::from_utf8(digits).unwrap();
+            rank.trim_end_matches('0').to_string()
+        })
+        .collect()
+}
+
+/// 按排序键比较，排序键相同时按卡片 ID 比较（保证并发移动后所有客户端顺序一致）
+pub fn compare_ranks(a_rank: &str, a_id: &str, b_rank: &str, b_id: &str) -> Ordering {
+    a_rank.cmp(b_rank).then_with(|| a_id.cmp(b_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rank_between_keeps_order() {
+        let first = rank_between(None, None).unwrap();
+        let mut ranks = vec![first];
+        // 反复在末尾、开头和中间插入
+        for i in 0..300 {
+            let rank = match i % 3 {
+                0 => rank_between(ranks.last().map(String::as_str), None),
+                1 => rank_between(None, ranks.first().map(String::as_str)),
+                _ => {
+                    let mid = ranks.len() / 2;
+                    rank_between(Some(&ranks[mid - 1]), Some(&ranks[mid]))
+                }
+            }
+            .unwrap();
+            assert!(is_valid_rank(&rank), "invalid rank {rank}");
+            ranks.push(rank);
+            ranks.sort();
+        }
+        let mut deduped = ranks.clone();
+        deduped.dedup();
+        assert_eq!(deduped.len(), ranks.len());
+    }
+
+    #[test]
+    fn rank_between_adjacent_ranks() {
+        let rank = rank_between(Some("V"), Some("W")).unwrap();
+        assert!("V" < rank.as_str() && rank.as_str() < "W");
+        let rank = rank_between(None, Some("1")).unwrap();
+        assert!(is_valid_rank(&rank) && rank.as_str() < "1");
+        let rank = rank_between(Some("z"), None).unwrap();
+        assert!(rank.as_str() > "z");
+    }
+
+    #[test]
+    fn rank_between_rejects_ties_and_invalid_ranks() {
+        assert_eq!(rank_between(Some("V"), Some("V")), None);
+        assert_eq!(rank_between(Some("W"), Some("V")), None);
+        assert_eq!(rank_between(Some("V0"), None), None);
+        assert_eq!(rank_between(None, Some("")), None);
+    }
+
+    #[test]
+    fn evenly_spaced_ranks_are_sorted_and_short() {
+        for count in [0, 1, 5, 61, 200, 5000] {
+            let ranks = evenly_spaced_ranks(count);
+            assert_eq!(ranks.len(), count);
+            assert!(ranks.windows(2).all(|w| w[0] < w[1]));
+            assert!(
+                ranks
+                    .iter()
+                    .all(|rank| is_valid_rank(rank) && rank.len() <= 3)
+            );
+        }
+    }
+
+    #[test]
+    fn ties_are_broken_by_card_id() {
+        assert_eq!(compare_ranks("V", "!a:x", "V", "!b:x"), Ordering::Less);
+        assert_eq!(compare_ranks("W", "!a:x", "V", "!b:x"), Ordering::Greater);
+    }
+}
@@ -1,4 +1,4 @@
-pub mod order_manager;
+pub mod fractional_index;
 
 // Re-export main types
-pub use order_manager::*;
+pub use fractional_index::*;
@@ -0,0 +1,494 @@
+//! 看板归档格式（版本化 JSON）
+//!
+//! 归档保存一块看板：看板中的列表（Space）、卡片（Room）元数据、Todo、
+//! Space 标签库、截止时间以及活动记录。
+//! 导出和导入都通过 [`crate::kanban::data::repositories`] 中的仓储 trait 读写，
+//! 导入时创建以归档名称命名的新看板，并在其中重新创建所有 Space 和 Room。
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use makepad_widgets::{log, error};
+use matrix_sdk::ruma::OwnedRoomId;
+use serde::{Deserialize, Serialize};
+
+use crate::kanban::data::repositories::{
+    ActivityRepository, BoardRepository, CardRepository, ListRepository, TagRepository,
+    TemplateRepository, WorkflowRepository,
+};
+use crate::kanban::state::boards::BOARD_BACKGROUND_COLORS;
+use crate::kanban::state::kanban_state::{
+    ActivityType, CardActivity, KanbanCard, SpaceTag, TodoItem,
+};
+use crate::kanban::state::template::{CardTemplate, RecurrenceRule};
+use crate::kanban::state::time_tracking::Estimate;
+use crate::kanban::state::workflow::{STATUS_PENDING, Workflow};
+
+/// 归档格式标识
+pub const ARCHIVE_FORMAT: &str = "toona.kanban.board";
+
+/// 当前归档版本
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// 每张卡片导出的活动记录上限
+const ACTIVITY_EXPORT_LIMIT: usize = 200;
+
+/// 归档没有名称时导入的看板名称
+const DEFAULT_IMPORTED_BOARD_NAME: &str = "导入的看板";
+
+/// 整块看板的归档
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardArchive {
+    /// 格式标识，固定为 [`ARCHIVE_FORMAT`]
+    pub format: String,
+
+    /// 归档版本
+    pub version: u32,
+
+    /// 导出时间（Unix timestamp 秒）
+    pub exported_at: u64,
+
+    /// 看板名称（可选，Trello 导入时为原看板名称）
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// 所有列表
+    #[serde(default)]
+    pub lists: Vec<ArchivedList>,
+}
+
+/// 归档中的列表（对应 Matrix Space）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedList {
+    /// 列表名称
+    pub name: String,
+
+    /// 排序位置
+    #[serde(default)]
+    pub position: f64,
+
+    /// Space 标签库
+    #[serde(default)]
+    pub tags: Vec<SpaceTag>,
+
+    /// Space 工作流（没有自定义工作流时为空）
+    #[serde(default)]
+    pub workflow: Option<Workflow>,
+
+    /// Space 卡片模板
+    #[serde(default)]
+    pub templates: Vec<CardTemplate>,
+
+    /// 列表中的卡片（按 position 排序）
+    #[serde(default)]
+    pub cards: Vec<ArchivedCard>,
+}
+
+/// 归档中的卡片（对应 Matrix Room）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedCard {
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub position: f64,
+    /// 列表内的排序键（旧版归档中没有）
+    #[serde(default)]
+    pub rank: String,
+    #[serde(default = "default_status")]
+    pub status: String,
+    /// 标签 ID 列表（对应所在列表的 `tags`）
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub end_time: Option<u64>,
+    #[serde(default)]
+    pub recurrence: Option<RecurrenceRule>,
+    #[serde(default)]
+    pub estimate: Option<Estimate>,
+    #[serde(default)]
+    pub todos: Vec<TodoItem>,
+    #[serde(default)]
+    pub created_at: u64,
+    #[serde(default)]
+    pub updated_at: u64,
+    /// 活动记录（最新的在前）
+    #[serde(default)]
+    pub activities: Vec<CardActivity>,
+}
+
+fn default_status() -> String {
+    STATUS_PENDING.to_string()
+}
+
+impl ArchivedCard {
+    /// 从内存中的卡片创建归档卡片
+    pub fn from_card(card: &KanbanCard, activities: Vec<CardActivity>) -> Self {
+        Self {
+            title: card.title.clone(),
+            description: card.description.clone(),
+            position: card.position,
+            rank: card.rank.clone(),
+            status: card.status.clone(),
+            tags: card.tags.clone(),
+            end_time: card.end_time,
+            recurrence: card.recurrence.clone(),
+            estimate: card.estimate.clone(),
+            todos: card.todos.clone(),
+            created_at: card.created_at,
+            updated_at: card.updated_at,
+            activities,
+        }
+    }
+
+    /// 转换为指定 Room / Space 下的卡片
+    pub fn to_card(&self, id: OwnedRoomId, space_id: OwnedRoomId) -> KanbanCard {
+        let mut card = KanbanCard::new(id, self.title.clone(), space_id);
+        card.description = self.description.clone();
+        card.position = self.position;
+        card.rank = self.rank.clone();
+        card.status = self.status.clone();
+        card.tags = self.tags.clone();
+        card.end_time = self.end_time;
+        card.recurrence = self.recurrence.clone();
+        card.estimate = self.estimate.clone();
+        // 导入后卡片 Room ID 会变化，子卡片引用无法保留
+        card.todos = self
+            .todos
+            .iter()
+            .cloned()
+            .map(|mut todo| {
+                todo.child_card_id = None;
+                todo
+            })
+            .collect();
+        if self.created_at > 0 {
+            card.created_at = self.created_at;
+        }
+        if self.updated_at > 0 {
+            card.updated_at = self.updated_at;
+        }
+        card
+    }
+}
+
+impl BoardArchive {
+    /// 创建空归档
+    pub fn new(name: Option<String>) -> Self {
+        Self {
+            format: ARCHIVE_FORMAT.to_string(),
+            version: ARCHIVE_VERSION,
+            exported_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            name,
+            lists: Vec::new(),
+        }
+    }
+
+    /// 卡片总数
+    pub fn card_count(&self) -> usize {
+        self.lists.iter().map(|l| l.cards.len()).sum()
+    }
+
+    /// 解析归档 JSON
+    ///
+    /// 同时支持 Toona 归档和 Trello 的 JSON 导出，后者会先被转换为归档。
+    pub fn from_json(json: &str) -> Result<Self> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).context("Failed to parse archive JSON")?;
+
+        if super::trello::is_trello_export(&value) {
+            log!("📦 Detected Trello JSON export, converting...");
+            return super::trello::convert_trello_board(value);
+        }
+
+        match value.get("format").and_then(|v| v.as_str()) {
+            Some(ARCHIVE_FORMAT) => {}
+            Some(other) => bail!("Unknown archive format: {}", other),
+            None => bail!("Not a kanban archive (missing 'format' field)"),
+        }
+
+        let version = value
+            .get("version")
+            .and_then(|v| v.as_u64())
+            .context("Archive is missing 'version' field")? as u32;
+
+        // 新版本在此处追加迁移步骤：vN -> vN+1
+        if version == 0 || version > ARCHIVE_VERSION {
+            bail!(
+                "Unsupported archive version {} (supported: 1..={})",
+                version,
+                ARCHIVE_VERSION
+            );
+        }
+
+        serde_json::from_value(value).context("Failed to deserialize kanban archive")
+    }
+}
+
+/// 导入结果统计
+#[derive(Debug, Clone, Default)]
+pub struct ImportSummary {
+    /// 成功创建的列表数
+    pub lists: usize,
+
+    /// 成功创建的卡片数
+    pub cards: usize,
+
+    /// 失败项描述
+    pub failures: Vec<String>,
+}
+
+/// 导出看板中的列表（`list_ids`），`name` 为看板名称
+pub async fn export_board<R>(
+    repository: &R,
+    name: Option<String>,
+    list_ids: &[OwnedRoomId],
+) -> Result<BoardArchive>
+where
+    R: ListRepository
+        + CardRepository
+        + TagRepository
+        + WorkflowRepository
+        + TemplateRepository
+        + ActivityRepository
+        + Sync,
+{
+    let mut lists = repository.load_lists().await?;
+    lists.retain(|list| list_ids.contains(&list.id));
+    let mut archive = BoardArchive::new(name);
+
+    log!("📦 Exporting {} kanban lists...", lists.len());
+
+    for list in lists {
+        let tags = repository.load_tags(&list.id).await.unwrap_or_default();
+        let workflow = repository.load_workflow(&list.id).await.unwrap_or_default();
+        let templates = repository.load_templates(&list.id).await.unwrap_or_default();
+
+        let mut cards = Vec::with_capacity(list.card_ids.len());
+        for card_id in &list.card_ids {
+            match repository.load_card(card_id, list.id.clone()).await {
+                Ok(card) => {
+                    let activities = repository
+                        .load_activities(card_id, Some(ACTIVITY_EXPORT_LIMIT))
+                        .await
+                        .unwrap_or_default();
+                    cards.push(ArchivedCard::from_card(&card, activities));
+                }
+                Err(e) => {
+                    error!("❌ Failed to export card {}: {e:?}", card_id);
+                }
+            }
+        }
+        cards.sort_by(|a, b| {
+            a.rank.cmp(&b.rank).then(
+                a.position
+                    .partial_cmp(&b.position)
+                    .unwrap_or(std::cmp::Ordering::Equal),
+            )
+        });
+
+        log!("📦 Exported list '{}' with {} cards", list.name, cards.len());
+
+        archive.lists.push(ArchivedList {
+            name: list.name,
+            position: list.position,
+            tags,
+            workflow,
+            templates,
+            cards,
+        });
+    }
+
+    Ok(archive)
+}
+
+/// 在当前 homeserver 上创建新看板，并在其中重新创建归档中的所有列表和卡片
+///
+/// 看板创建失败时返回错误；单个列表或卡片失败不会中断整个导入，
+/// 失败项记录在 [`ImportSummary::failures`] 中。
+pub async fn import_board<R>(repository: &R, archive: &BoardArchive) -> Result<ImportSummary>
+where
+    R: BoardRepository
+        + ListRepository
+        + CardRepository
+        + TagRepository
+        + WorkflowRepository
+        + TemplateRepository
+        + ActivityRepository
+        + Sync,
+{
+    let mut summary = ImportSummary::default();
+
+    let board_name = archive
+        .name
+        .clone()
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_IMPORTED_BOARD_NAME.to_string());
+    let board_id = repository
+        .create_board(&board_name, BOARD_BACKGROUND_COLORS[0])
+        .await
+        .context("Failed to create board")?;
+    log!(
+        "📥 Created board '{}' ({}) for import",
+        board_name,
+        board_id
+    );
+
+    log!(
+        "📥 Importing {} lists / {} cards...",
+        archive.lists.len(),
+        archive.card_count()
+    );
+
+    for list in &archive.lists {
+        let space_id = match repository.create_list(&list.name).await {
+            Ok(space_id) => space_id,
+            Err(e) => {
+                error!("❌ Failed to create list '{}': {e:?}", list.name);
+                summary.failures.push(format!("列表 '{}': {}", list.name, e));
+                continue;
+            }
+        };
+        summary.lists += 1;
+
+        if let Err(e) = repository.add_list(&board_id, &space_id).await {
+            error!("❌ Failed to add list '{}' to board: {e:?}", list.name);
+            summary
+                .failures
+                .push(format!("列表 '{}' 加入看板: {}", list.name, e));
+        }
+
+        if !list.tags.is_empty() {
+            if let Err(e) = repository.save_tags(&space_id, list.tags.clone()).await {
+                error!("❌ Failed to import tags for list '{}': {e:?}", list.name);
+                summary
+                    .failures
+                    .push(format!("列表 '{}' 的标签库: {}", list.name, e));
+            }
+        }
+
+        if let Some(workflow) = &list.workflow {
+            if let Err(e) = repository.save_workflow(&space_id, workflow).await {
+                error!("❌ Failed to import workflow for list '{}': {e:?}", list.name);
+                summary
+                    .failures
+                    .push(format!("列表 '{}' 的工作流: {}", list.name, e));
+            }
+        }
+
+        if !list.templates.is_empty() {
+            if let Err(e) = repository.save_templates(&space_id, &list.templates).await {
+                error!("❌ Failed to import templates for list '{}': {e:?}", list.name);
+                summary
+                    .failures
+                    .push(format!("列表 '{}' 的卡片模板: {}", list.name, e));
+            }
+        }
+
+        for archived in &list.cards {
+            match import_card(repository, &space_id, archived).await {
+                Ok(card_id) => {
+                    log!("📥 Imported card '{}' ({})", archived.title, card_id);
+                    summary.cards += 1;
+                }
+                Err(e) => {
+                    error!("❌ Failed to import card '{}': {e:?}", archived.title);
+                    summary
+                        .failures
+                        .push(format!("卡片 '{}': {}", archived.title, e));
+                }
+            }
+        }
+    }
+
+    log!(
+        "✅ Import finished: {} lists, {} cards, {} failures",
+        summary.lists,
+        summary.cards,
+        summary.failures.len()
+    );
+    Ok(summary)
+}
+
+/// 创建单张卡片并写入元数据、Todo 和活动记录
+async fn import_card<R>(
+    repository: &R,
+    space_id: &OwnedRoomId,
+    archived: &ArchivedCard,
+) -> Result<OwnedRoomId>
+where
+    R: CardRepository + ActivityRepository + Sync,
+{
+    let card_id = repository.create_card(space_id, &archived.title).await?;
+
+    let card = archived.to_card(card_id.clone(), space_id.clone());
+    repository.save_card(&card).await?;
+
+    if !card.todos.is_empty() {
+        repository.save_todos(&card_id, &card.todos).await?;
+    }
+
+    // 按时间顺序回放活动记录，原作者和时间写入内容中
+    let mut activities: Vec<&CardActivity> = archived.activities.iter().collect();
+    activities.sort_by_key(|a| a.created_at);
+    for activity in activities {
+        let (text, metadata) = replay_content(activity);
+        if let Err(e) = repository
+            .record_activity(&card_id, activity.activity_type.clone(), text, metadata)
+            .await
+        {
+            error!("⚠️ Failed to replay activity {}: {e:?}", activity.id);
+        }
+    }
+
+    Ok(card_id)
+}
+
+/// 构造回放活动的内容
+fn replay_content(activity: &CardActivity) -> (String, Option<serde_json::Value>) {
+    let date = chrono::DateTime::from_timestamp(activity.created_at as i64, 0)
+        .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default();
+
+    if matches!(activity.activity_type, ActivityType::Comment) {
+        let text = format!("{}\n\n— {} · {}", activity.text, activity.user_id, date);
+        return (text, None);
+    }
+
+    let metadata = serde_json::json!({
+        "imported": true,
+        "original_user_id": activity.user_id,
+        "original_created_at": activity.created_at,
+        "original_metadata": activity.metadata,
+    });
+    (activity.text.clone(), Some(metadata))
+}
+
+/// 默认导出路径：`<app_data_dir>/kanban_exports/board_<时间>.json`
+pub fn default_export_path() -> PathBuf {
+    crate::app_data_dir().join("kanban_exports").join(format!(
+        "board_{}.json",
+        chrono::Local::now().format("%Y%m%d_%H%M%S")
+    ))
+}
+
+/// 将归档写入文件
+pub fn write_archive_file(archive: &BoardArchive, path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).context("Failed to create export directory")?;
+    }
+    let json = serde_json::to_string_pretty(archive).context("Failed to serialize archive")?;
+    std::fs::write(path, json).context("Failed to write archive file")?;
+    log!("💾 Wrote kanban archive to {}", path.display());
+    Ok(())
+}
+
+/// 从文件读取归档（支持 Trello JSON 导出）
+pub fn read_archive_file(path: &Path) -> Result<BoardArchive> {
+    let json = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    BoardArchive::from_json(&json)
+}
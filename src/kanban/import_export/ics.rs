@@ -0,0 +1,257 @@
+//! 卡片截止时间的 iCalendar（.ics）导出
+//!
+//! 当前看板中每张有截止时间且未归档的卡片导出为一个 `VEVENT`：
+//! `UID` 使用卡片 Room ID，`DTSTART` 为截止时间（UTC），
+//! `SUMMARY` 为卡片标题，`DESCRIPTION` 包含所在列表、状态和描述，
+//! 标签按所在列表的标签库解析为名称后导出为 `CATEGORIES`。可直接导入到常见的日历应用中。
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use makepad_widgets::log;
+
+use crate::kanban::state::kanban_state::{KanbanAppState, KanbanCard};
+
+/// iCalendar 规定的单行最大长度（字节，不含换行）
+const MAX_LINE_OCTETS: usize = 75;
+
+/// 生成包含当前看板中未归档卡片截止时间的 iCalendar 文本，返回 (内容, 导出的卡片数)
+pub fn export_deadlines(state: &KanbanAppState) -> (String, usize) {
+    let mut cards: Vec<&KanbanCard> = state
+        .cards
+        .values()
+        .filter(|card| card.end_time.is_some())
+        .filter(|card| !state.is_card_archived(card) && state.is_in_current_board(&card.space_id))
+        .collect();
+    cards.sort_by_key(|card| card.end_time);
+
+    let now = format_utc(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+    );
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Toona//Kanban//ZH".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:Toona 看板截止时间".to_string(),
+    ];
+
+    for card in &cards {
+        let Some(end_time) = card.end_time else {
+            continue;
+        };
+        let list_name = state
+            .lists
+            .get(&card.space_id)
+            .map_or("", |list| list.name.as_str());
+        let status = state
+            .workflow(&card.space_id)
+            .display_name(&card.status)
+            .to_string();
+
+        let mut description = format!("列表：{}\n状态：{}", list_name, status);
+        if let Some(desc) = card.description.as_deref().filter(|d| !d.is_empty()) {
+            description.push_str("\n\n");
+            description.push_str(desc);
+        }
+
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}@toona", card.id));
+        lines.push(format!("DTSTAMP:{}", now));
+        lines.push(format!("DTSTART:{}", format_utc(end_time)));
+        lines.push(format!("DTEND:{}", format_utc(end_time)));
+        lines.push(format!("LAST-MODIFIED:{}", format_utc(card.updated_at)));
+        lines.push(format!("SUMMARY:{}", escape_text(&card.title)));
+        lines.push(format!("DESCRIPTION:{}", escape_text(&description)));
+        let space_tags = state.space_tags.get(&card.space_id);
+        let tags: Vec<String> = card
+            .tags
+            .iter()
+            .filter_map(|tag_id| space_tags?.iter().find(|tag| &tag.id == tag_id))
+            .map(|tag| escape_text(&tag.name))
+            .collect();
+        if !tags.is_empty() {
+            lines.push(format!("CATEGORIES:{}", tags.join(",")));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let content = lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<_>>()
+        .join("\r\n")
+        + "\r\n";
+    (content, cards.len())
+}
+
+/// 默认的日历导出路径：`<app_data_dir>/kanban_exports/deadlines_<时间>.ics`
+pub fn default_ics_path() -> PathBuf {
+    crate::app_data_dir().join("kanban_exports").join(format!(
+        "deadlines_{}.ics",
+        chrono::Local::now().format("%Y%m%d_%H%M%S")
+    ))
+}
+
+/// 将 iCalendar 文本写入文件
+pub fn write_ics_file(content: &str, path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).context("Failed to create export directory")?;
+    }
+    std::fs::write(path, content).context("Failed to write calendar file")?;
+    log!("📅 Wrote kanban calendar to {}", path.display());
+    Ok(())
+}
+
+/// Unix timestamp（秒）格式化为 UTC 时间，例如 `20250310T100000Z`
+fn format_utc(timestamp: u64) -> String {
+    chrono::DateTime::from_timestamp(timestamp as i64, 0)
+        .unwrap_or_default()
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+}
+
+/// 按 RFC 5545 转义 TEXT 类型的值
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 超过 75 字节的行折行（续行以一个空格开头），不会拆开 UTF-8 字符
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + line.len() / MAX_LINE_OCTETS * 3);
+    let mut line_len = 0;
+    for c in line.chars() {
+        if line_len + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            line_len = 1;
+        }
+        folded.push(c);
+        line_len += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kanban::state::kanban_state::SpaceTag;
+    use crate::kanban::test_util::room_id;
+
+    /// 反向处理折行，得到原始的内容行
+    fn unfold(content: &str) -> Vec<String> {
+        content
+            .replace("\r\n ", "")
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn escapes_text_values() {
+        assert_eq!(escape_text("a;b,c\\d"), "a\\;b\\,c\\\\d");
+        assert_eq!(escape_text("第一行\r\n第二行"), "第一行\\n第二行");
+        assert_eq!(escape_text("普通文本"), "普通文本");
+    }
+
+    #[test]
+    fn short_lines_are_not_folded() {
+        let line = "x".repeat(MAX_LINE_OCTETS);
+        assert_eq!(fold_line(&line), line);
+    }
+
+    #[test]
+    fn folds_long_lines_at_75_octets() {
+        let line = "x".repeat(160);
+        let folded = fold_line(&line);
+        let parts: Vec<&str> = folded.split("\r\n").collect();
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0].len(), 75);
+        assert_eq!(parts[1].len(), 75);
+        assert!(parts[1].starts_with(' '));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+
+    #[test]
+    fn folding_never_splits_utf8_characters() {
+        let line = format!("SUMMARY:{}", "看板".repeat(40));
+        let folded = fold_line(&line);
+        for part in folded.split("\r\n") {
+            assert!(part.len() <= MAX_LINE_OCTETS, "{part:?}");
+        }
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+
+    #[test]
+    fn exports_cards_with_deadlines() {
+        let list_id = room_id("list");
+        let mut state = KanbanAppState::new();
+
+        let mut urgent = SpaceTag::new("紧急, 重要".to_string(), "#EB5A46".to_string());
+        urgent.id = "tag_urgent".to_string();
+        let mut bug = SpaceTag::new("Bug".to_string(), "#0079BF".to_string());
+        bug.id = "tag_bug".to_string();
+        state.space_tags.insert(list_id.clone(), vec![urgent, bug]);
+
+        let mut card = KanbanCard::new(room_id("card"), "发布; 上线".to_string(), list_id.clone());
+        card.end_time = Some(1_704_067_200);
+        card.tags = vec![
+            "tag_urgent".to_string(),
+            "tag_missing".to_string(),
+            "tag_bug".to_string(),
+        ];
+        state.cards.insert(card.id.clone(), card);
+
+        let no_deadline = KanbanCard::new(room_id("other"), "没有截止时间".to_string(), list_id);
+        state.cards.insert(no_deadline.id.clone(), no_deadline);
+
+        let (content, count) = export_deadlines(&state);
+        assert_eq!(count, 1);
+        assert!(content.ends_with("END:VCALENDAR\r\n"));
+
+        let lines = unfold(&content);
+        assert!(lines.contains(&"UID:!card:example.org@toona".to_string()));
+        assert!(lines.contains(&"DTSTART:20240101T000000Z".to_string()));
+        assert!(lines.contains(&"SUMMARY:发布\\; 上线".to_string()));
+        // 标签导出为名称，找不到的标签被跳过
+        assert!(lines.contains(&"CATEGORIES:紧急\\, 重要,Bug".to_string()));
+    }
+
+    #[test]
+    fn skips_archived_cards_and_other_boards() {
+        let list_id = room_id("list");
+        let mut state = KanbanAppState::new();
+
+        let mut card = KanbanCard::new(room_id("card"), "发布".to_string(), list_id.clone());
+        card.end_time = Some(1_704_067_200);
+        let mut archived = card.clone();
+        archived.id = room_id("archived");
+        archived.archived_at = Some(1_704_000_000);
+        state.cards.insert(card.id.clone(), card);
+        state.cards.insert(archived.id.clone(), archived);
+
+        let (content, count) = export_deadlines(&state);
+        assert_eq!(count, 1);
+        assert!(!content.contains("archived"));
+
+        // 列表不属于当前看板
+        state.current_board_id = Some(room_id("board"));
+        let (content, count) = export_deadlines(&state);
+        assert_eq!(count, 0);
+        assert!(!content.contains("BEGIN:VEVENT"));
+    }
+}
@@ -0,0 +1,15 @@
+//! 看板导入/导出模块
+//!
+//! - `archive`: Toona 自有的版本化 JSON 归档格式（导出 / 导入 / 版本迁移）
+//! - `trello`: Trello 看板 JSON 导出格式的转换器（转换为归档后复用同一套导入流程）
+//! - `ics`: 卡片截止时间的 iCalendar 导出
+
+pub mod archive;
+pub mod trello;
+pub mod ics;
+
+pub use archive::{
+    BoardArchive, ImportSummary, default_export_path, export_board, import_board,
+    read_archive_file, write_archive_file,
+};
+pub use ics::{default_ics_path, export_deadlines, write_ics_file};
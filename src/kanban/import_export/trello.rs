@@ -0,0 +1,600 @@
+//! Trello JSON 导出格式导入器
+//!
+//! 将 Trello 看板菜单中「打印、导出和共享 → 导出为 JSON」得到的文件转换为
+//! [`BoardArchive`]，之后与普通归档走相同的导入流程。
+//!
+//! 映射关系：
+//! - list → 列表（Space），已关闭的 list 及其卡片会被跳过
+//! - card → 卡片（Room）；`closed` → 已归档，`dueComplete` → 已完成
+//! - label → 所在列表的 `SpaceTag`
+//! - checklist 条目 → `TodoItem`（卡片有多个 checklist 时以 `清单名: ` 作为前缀）
+//! - `due` → 截止时间
+//! - `commentCard` action → 评论活动
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::archive::{ArchivedCard, ArchivedList, BoardArchive};
+use crate::kanban::state::kanban_state::{
+    ActivityType, CardActivity, SpaceTag, TodoItem,
+};
+use crate::kanban::state::workflow::{STATUS_ARCHIVED, STATUS_COMPLETED, STATUS_PENDING};
+
+#[derive(Debug, Deserialize)]
+struct TrelloBoard {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    lists: Vec<TrelloList>,
+    #[serde(default)]
+    cards: Vec<TrelloCard>,
+    #[serde(default)]
+    labels: Vec<TrelloLabel>,
+    #[serde(default)]
+    checklists: Vec<TrelloChecklist>,
+    #[serde(default)]
+    actions: Vec<TrelloAction>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrelloList {
+    id: String,
+    name: String,
+    #[serde(default)]
+    closed: bool,
+    #[serde(default)]
+    pos: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrelloCard {
+    id: String,
+    name: String,
+    #[serde(default)]
+    desc: String,
+    #[serde(rename = "idList")]
+    id_list: String,
+    #[serde(default)]
+    closed: bool,
+    #[serde(default)]
+    pos: f64,
+    #[serde(default)]
+    due: Option<String>,
+    #[serde(rename = "dueComplete", default)]
+    due_complete: bool,
+    #[serde(rename = "idLabels", default)]
+    id_labels: Vec<String>,
+    #[serde(rename = "dateLastActivity", default)]
+    date_last_activity: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrelloLabel {
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    color: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrelloChecklist {
+    #[serde(rename = "idCard")]
+    id_card: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    pos: f64,
+    #[serde(rename = "checkItems", default)]
+    check_items: Vec<TrelloCheckItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrelloCheckItem {
+    id: String,
+    name: String,
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    pos: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrelloAction {
+    id: String,
+    #[serde(rename = "type")]
+    action_type: String,
+    #[serde(default)]
+    date: Option<String>,
+    #[serde(default)]
+    data: serde_json::Value,
+    #[serde(rename = "memberCreator", default)]
+    member_creator: Option<TrelloMember>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrelloMember {
+    #[serde(default)]
+    username: String,
+    #[serde(rename = "fullName", default)]
+    full_name: String,
+}
+
+/// 判断 JSON 是否为 Trello 看板导出
+pub fn is_trello_export(value: &serde_json::Value) -> bool {
+    value.get("format").is_none()
+        && value.get("lists").is_some_and(|v| v.is_array())
+        && value.get("cards").is_some_and(|v| v.is_array())
+}
+
+/// 将 Trello 看板导出转换为归档
+pub fn convert_trello_board(value: serde_json::Value) -> Result<BoardArchive> {
+    let board: TrelloBoard =
+        serde_json::from_value(value).context("Failed to parse Trello board export")?;
+
+    let labels: HashMap<&str, &TrelloLabel> =
+        board.labels.iter().map(|l| (l.id.as_str(), l)).collect();
+
+    // 卡片 ID -> 排好序的 checklist
+    let mut checklists: HashMap<&str, Vec<&TrelloChecklist>> = HashMap::new();
+    for checklist in &board.checklists {
+        checklists
+            .entry(checklist.id_card.as_str())
+            .or_default()
+            .push(checklist);
+    }
+    for lists in checklists.values_mut() {
+        lists.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+    }
+
+    // 卡片 ID -> 评论活动
+    let mut comments: HashMap<String, Vec<CardActivity>> = HashMap::new();
+    for action in board.actions.iter().filter(|a| a.action_type == "commentCard") {
+        let Some(card_id) = action.data.pointer("/card/id").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let text = action
+            .data
+            .get("text")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let user_id = action
+            .member_creator
+            .as_ref()
+            .map(|m| {
+                if m.full_name.is_empty() {
+                    m.username.clone()
+                } else {
+                    m.full_name.clone()
+                }
+            })
+            .unwrap_or_default();
+        comments
+            .entry(card_id.to_string())
+            .or_default()
+            .push(CardActivity {
+                id: action.id.clone(),
+                activity_type: ActivityType::Comment,
+                text,
+                metadata: None,
+                created_at: action
+                    .date
+                    .as_deref()
+                    .and_then(parse_trello_date)
+                    .unwrap_or_else(|| timestamp_from_trello_id(&action.id)),
+                user_id,
+            });
+    }
+
+    let mut open_lists: Vec<&TrelloList> = board.lists.iter().filter(|l| !l.closed).collect();
+    open_lists.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+
+    let mut archive = BoardArchive::new(Some(board.name.clone()));
+
+    for (list_index, list) in open_lists.into_iter().enumerate() {
+        let mut cards: Vec<&TrelloCard> =
+            board.cards.iter().filter(|c| c.id_list == list.id).collect();
+        cards.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+
+        // 只把本列表卡片用到的 label 放进该列表的标签库
+        let mut tags: Vec<SpaceTag> = Vec::new();
+        let mut archived_cards = Vec::with_capacity(cards.len());
+
+        for (card_index, card) in cards.into_iter().enumerate() {
+            let mut tag_ids = Vec::new();
+            for label_id in &card.id_labels {
+                let Some(label) = labels.get(label_id.as_str()) else {
+                    continue;
+                };
+                let tag = label_to_tag(label);
+                if !tags.iter().any(|t| t.id == tag.id) {
+                    tags.push(tag.clone());
+                }
+                tag_ids.push(tag.id);
+            }
+
+            let created_at = timestamp_from_trello_id(&card.id);
+            let status = if card.closed {
+                STATUS_ARCHIVED
+            } else if card.due_complete {
+                STATUS_COMPLETED
+            } else {
+                STATUS_PENDING
+            }
+            .to_string();
+
+            let card_checklists = checklists.get(card.id.as_str());
+            let todos = card_checklists
+                .map(|lists| checklist_todos(lists))
+                .unwrap_or_default();
+
+            let mut activities = comments.remove(&card.id).unwrap_or_default();
+            activities.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+            archived_cards.push(ArchivedCard {
+                title: card.name.clone(),
+                description: (!card.desc.is_empty()).then(|| card.desc.clone()),
+                position: (card_index as f64 + 1.0) * 1000.0,
+                // 没有排序键时按 position 排序，下次插入卡片时重新分配
+                rank: String::new(),
+                status,
+                tags: tag_ids,
+                end_time: card.due.as_deref().and_then(parse_trello_date),
+                recurrence: None,
+                estimate: None,
+                todos,
+                created_at,
+                updated_at: card
+                    .date_last_activity
+                    .as_deref()
+                    .and_then(parse_trello_date)
+                    .unwrap_or(created_at),
+                activities,
+            });
+        }
+
+        archive.lists.push(ArchivedList {
+            name: list.name.clone(),
+            position: (list_index as f64 + 1.0) * 1000.0,
+            tags,
+            workflow: None,
+            templates: Vec::new(),
+            cards: archived_cards,
+        });
+    }
+
+    Ok(archive)
+}
+
+/// 将一张卡片的所有 checklist 展开为 Todo 列表
+fn checklist_todos(checklists: &[&TrelloChecklist]) -> Vec<TodoItem> {
+    let with_prefix = checklists.len() > 1;
+    let mut todos = Vec::new();
+
+    for checklist in checklists {
+        let mut items: Vec<&TrelloCheckItem> = checklist.check_items.iter().collect();
+        items.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+
+        for item in items {
+            let text = if with_prefix {
+                format!("{}: {}", checklist.name, item.name)
+            } else {
+                item.name.clone()
+            };
+            let mut todo = TodoItem::new(text);
+            todo.created_at = timestamp_from_trello_id(&item.id);
+            if item.state == "complete" {
+                todo.completed = true;
+                todo.completed_at = Some(todo.created_at);
+            }
+            todos.push(todo);
+        }
+    }
+
+    todos
+}
+
+/// Trello label -> SpaceTag（保留 label ID，保证卡片标签引用一致）
+fn label_to_tag(label: &TrelloLabel) -> SpaceTag {
+    let color_name = label.color.as_deref().unwrap_or_default();
+    let name = match label.name.as_deref() {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ if !color_name.is_empty() => color_name.to_string(),
+        _ => "标签".to_string(),
+    };
+
+    let mut tag = SpaceTag::new(name, trello_color_to_hex(color_name).to_string());
+    tag.id = format!("tag_trello_{}", label.id);
+    tag
+}
+
+/// Trello 颜色名称 -> 十六进制颜色（与 `PREDEFINED_TAG_COLORS` 保持一致）
+fn trello_color_to_hex(color: &str) -> &'static str {
+    let base = color
+        .trim_end_matches("_dark")
+        .trim_end_matches("_light");
+    match base {
+        "red" => "#EB5A46",
+        "orange" => "#FF9F1A",
+        "yellow" => "#F2D600",
+        "green" | "lime" => "#61BD4F",
+        "sky" => "#00C2E0",
+        "blue" => "#0079BF",
+        "purple" => "#9775FA",
+        "pink" => "#FF78CB",
+        "black" => "#343434",
+        _ => "#95A5A6",
+    }
+}
+
+/// 解析 Trello 的 ISO 8601 时间（如 `2024-03-01T10:00:00.000Z`）
+fn parse_trello_date(date: &str) -> Option<u64> {
+    chrono::DateTime::parse_from_rfc3339(date)
+        .ok()
+        .map(|d| d.timestamp().max(0) as u64)
+}
+
+/// Trello 对象 ID 的前 8 位十六进制是创建时间戳
+fn timestamp_from_trello_id(id: &str) -> u64 {
+    id.get(..8)
+        .and_then(|hex| u64::from_str_radix(hex, 16).ok())
+        .unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// 2024-03-01T09:41:04Z
+    const CREATED_AT: u64 = 1_709_286_064;
+
+    fn board() -> serde_json::Value {
+        json!({
+            "name": "Trello 看板",
+            "lists": [
+                { "id": "list_done", "name": "Done", "pos": 2.0 },
+                { "id": "list_todo", "name": "To Do", "pos": 1.0 },
+                { "id": "list_old", "name": "Old", "pos": 0.5, "closed": true },
+            ],
+            "cards": [
+                {
+                    "id": "65e1a2b0000000000000000b",
+                    "name": "第二张",
+                    "idList": "list_todo",
+                    "pos": 20.0,
+                    "closed": true,
+                },
+                {
+                    "id": "65e1a2b0000000000000000a",
+                    "name": "第一张",
+                    "desc": "描述",
+                    "idList": "list_todo",
+                    "pos": 10.0,
+                    "due": "2024-03-10T10:00:00.000Z",
+                    "idLabels": ["label_red", "label_missing", "label_plain"],
+                    "dateLastActivity": "2024-03-02T00:00:00.000Z",
+                },
+                {
+                    "id": "65e1a2b0000000000000000c",
+                    "name": "已完成",
+                    "idList": "list_done",
+                    "dueComplete": true,
+                    "idLabels": ["label_red"],
+                },
+                {
+                    "id": "65e1a2b0000000000000000d",
+                    "name": "关闭列表中的卡片",
+                    "idList": "list_old",
+                },
+            ],
+            "labels": [
+                { "id": "label_red", "name": "紧急", "color": "red_dark" },
+                { "id": "label_plain", "name": "", "color": null },
+            ],
+            "checklists": [
+                {
+                    "idCard": "65e1a2b0000000000000000a",
+                    "name": "发布",
+                    "pos": 2.0,
+                    "checkItems": [
+                        { "id": "65e1a2c00000000000000001", "name": "上线", "pos": 1.0 },
+                    ],
+                },
+                {
+                    "idCard": "65e1a2b0000000000000000a",
+                    "name": "准备",
+                    "pos": 1.0,
+                    "checkItems": [
+                        { "id": "65e1a2c00000000000000003", "name": "测试", "pos": 2.0 },
+                        {
+                            "id": "65e1a2c00000000000000002",
+                            "name": "编码",
+                            "pos": 1.0,
+                            "state": "complete",
+                        },
+                    ],
+                },
+                {
+                    "idCard": "65e1a2b0000000000000000c",
+                    "name": "唯一清单",
+                    "checkItems": [
+                        { "id": "65e1a2c00000000000000004", "name": "检查" },
+                    ],
+                },
+            ],
+            "actions": [
+                {
+                    "id": "65e1a2c0000000000000000e",
+                    "type": "commentCard",
+                    "date": "2024-03-01T12:00:00.000Z",
+                    "data": { "text": "较早的评论", "card": { "id": "65e1a2b0000000000000000a" } },
+                    "memberCreator": { "username": "alice", "fullName": "Alice" },
+                },
+                {
+                    "id": "65e1a2c0000000000000000f",
+                    "type": "commentCard",
+                    "date": "2024-03-02T12:00:00.000Z",
+                    "data": { "text": "较新的评论", "card": { "id": "65e1a2b0000000000000000a" } },
+                    "memberCreator": { "username": "bob", "fullName": "" },
+                },
+                {
+                    "id": "65e1a2c00000000000000010",
+                    "type": "updateCard",
+                    "data": { "card": { "id": "65e1a2b0000000000000000a" } },
+                },
+            ],
+        })
+    }
+
+    #[test]
+    fn detects_trello_exports() {
+        assert!(is_trello_export(&board()));
+        assert!(!is_trello_export(
+            &json!({ "format": "toona", "lists": [], "cards": [] })
+        ));
+        assert!(!is_trello_export(&json!({ "lists": [] })));
+    }
+
+    #[test]
+    fn skips_closed_lists_and_orders_by_position() {
+        let archive = convert_trello_board(board()).unwrap();
+
+        let lists: Vec<&str> = archive.lists.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(lists, vec!["To Do", "Done"]);
+        assert_eq!(archive.lists[0].position, 1000.0);
+        assert_eq!(archive.lists[1].position, 2000.0);
+        assert_eq!(archive.card_count(), 3);
+
+        let titles: Vec<&str> = archive.lists[0]
+            .cards
+            .iter()
+            .map(|c| c.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["第一张", "第二张"]);
+    }
+
+    #[test]
+    fn converts_card_fields() {
+        let archive = convert_trello_board(board()).unwrap();
+        let first = &archive.lists[0].cards[0];
+        let closed = &archive.lists[0].cards[1];
+        let done = &archive.lists[1].cards[0];
+
+        assert_eq!(first.description.as_deref(), Some("描述"));
+        assert_eq!(first.status, STATUS_PENDING);
+        assert_eq!(closed.status, STATUS_ARCHIVED);
+        assert_eq!(done.status, STATUS_COMPLETED);
+        assert_eq!(closed.description, None);
+
+        assert_eq!(first.end_time, parse_trello_date("2024-03-10T10:00:00Z"));
+        assert_eq!(first.created_at, CREATED_AT);
+        assert_eq!(
+            first.updated_at,
+            parse_trello_date("2024-03-02T00:00:00Z").unwrap()
+        );
+        assert_eq!(closed.updated_at, CREATED_AT);
+    }
+
+    #[test]
+    fn converts_labels_to_list_tags() {
+        let archive = convert_trello_board(board()).unwrap();
+        let todo_list = &archive.lists[0];
+        let first = &todo_list.cards[0];
+
+        assert_eq!(
+            first.tags,
+            vec!["tag_trello_label_red", "tag_trello_label_plain"]
+        );
+        let tags: Vec<(&str, &str)> = todo_list
+            .tags
+            .iter()
+            .map(|t| (t.name.as_str(), t.color.as_str()))
+            .collect();
+        assert_eq!(tags, vec![("紧急", "#EB5A46"), ("标签", "#95A5A6")]);
+
+        // 每个列表只包含本列表卡片用到的标签
+        let done_tags: Vec<&str> = archive.lists[1]
+            .tags
+            .iter()
+            .map(|t| t.id.as_str())
+            .collect();
+        assert_eq!(done_tags, vec!["tag_trello_label_red"]);
+    }
+
+    #[test]
+    fn converts_checklists_to_todos() {
+        let archive = convert_trello_board(board()).unwrap();
+
+        let todos = &archive.lists[0].cards[0].todos;
+        let texts: Vec<&str> = todos.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, vec!["准备: 编码", "准备: 测试", "发布: 上线"]);
+        assert!(todos[0].completed);
+        assert_eq!(todos[0].completed_at, Some(todos[0].created_at));
+        assert!(!todos[1].completed);
+        assert_eq!(todos[2].created_at, 0x65e1a2c0);
+
+        // 只有一个清单时不加前缀
+        let single: Vec<&str> = archive.lists[1].cards[0]
+            .todos
+            .iter()
+            .map(|t| t.text.as_str())
+            .collect();
+        assert_eq!(single, vec!["检查"]);
+    }
+
+    #[test]
+    fn converts_comments_newest_first() {
+        let archive = convert_trello_board(board()).unwrap();
+        let activities = &archive.lists[0].cards[0].activities;
+
+        assert_eq!(activities.len(), 2);
+        assert_eq!(activities[0].text, "较新的评论");
+        assert_eq!(activities[0].user_id, "bob");
+        assert_eq!(activities[1].text, "较早的评论");
+        assert_eq!(activities[1].user_id, "Alice");
+        assert!(
+            activities
+                .iter()
+                .all(|a| a.activity_type == ActivityType::Comment)
+        );
+    }
+
+    #[test]
+    fn maps_trello_colors() {
+        assert_eq!(trello_color_to_hex("green_light"), "#61BD4F");
+        assert_eq!(trello_color_to_hex("lime"), "#61BD4F");
+        assert_eq!(trello_color_to_hex("sky_dark"), "#00C2E0");
+        assert_eq!(trello_color_to_hex("unknown"), "#95A5A6");
+    }
+
+    #[test]
+    fn label_name_falls_back_to_color() {
+        let label = TrelloLabel {
+            id: "label".to_string(),
+            name: None,
+            color: Some("blue".to_string()),
+        };
+        let tag = label_to_tag(&label);
+        assert_eq!(tag.name, "blue");
+        assert_eq!(tag.color, "#0079BF");
+        assert_eq!(tag.id, "tag_trello_label");
+    }
+
+    #[test]
+    fn reads_creation_time_from_trello_ids() {
+        assert_eq!(
+            timestamp_from_trello_id("65e1a2b0000000000000000a"),
+            CREATED_AT
+        );
+        assert_eq!(parse_trello_date("not a date"), None);
+    }
+}
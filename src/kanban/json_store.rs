@@ -0,0 +1,76 @@
+//! 保存在 `app_data_dir` 下的 JSON 文件
+//!
+//! 数据保存在全局 Mutex 中，首次访问时从磁盘加载；修改后由调用方调用
+//! [`JsonFileStore::save`] 写回。文件不存在或无法解析时使用默认值。
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use makepad_widgets::{log, error};
+use serde::{Serialize, de::DeserializeOwned};
+
+/// 一个 JSON 文件及其在内存中的数据
+pub struct JsonFileStore<T> {
+    /// `app_data_dir` 下的文件名
+    file_name: &'static str,
+
+    /// 已加载的数据（首次访问前为 `None`）
+    data: Mutex<Option<T>>,
+}
+
+impl<T> JsonFileStore<T> {
+    pub const fn new(file_name: &'static str) -> Self {
+        Self {
+            file_name,
+            data: Mutex::new(None),
+        }
+    }
+
+    fn path(&self) -> PathBuf {
+        crate::app_data_dir().join(self.file_name)
+    }
+}
+
+impl<T: Default + Serialize + DeserializeOwned> JsonFileStore<T> {
+    /// 在已加载的数据上执行操作
+    pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let mut guard = self.data.lock().unwrap();
+        f(guard.get_or_insert_with(|| self.load()))
+    }
+
+    /// 把数据写回磁盘
+    pub fn save(&self, data: &T) {
+        match serde_json::to_string_pretty(data) {
+            Ok(json_str) => {
+                if let Err(e) = std::fs::write(self.path(), json_str) {
+                    error!("Failed to write {}: {:?}", self.file_name, e);
+                }
+            }
+            Err(e) => error!("Failed to serialize {}: {:?}", self.file_name, e),
+        }
+    }
+
+    fn load(&self) -> T {
+        let path = self.path();
+        if !path.exists() {
+            return T::default();
+        }
+
+        match std::fs::read_to_string(&path) {
+            Ok(json_str) => match serde_json::from_str::<T>(&json_str) {
+                Ok(data) => {
+                    log!("📂 Loaded {} from disk", self.file_name);
+                    data
+                }
+                Err(e) => {
+                    error!("Failed to parse {}: {:?}", self.file_name, e);
+                    T::default()
+                }
+            },
+            Err(e) => {
+                error!("Failed to read {}: {:?}", self.file_name, e);
+                T::default()
+            }
+        }
+    }
+}
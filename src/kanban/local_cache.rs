@@ -139,6 +139,18 @@ pub fn remove_card_from_cache(space_id: &OwnedRoomId, card_id: &OwnedRoomId) {
     save_cache_to_disk(cache_data);
 }
 
+/// 从本地缓存移除整个 Space（列表被永久删除时）
+pub fn remove_space_from_cache(space_id: &OwnedRoomId) {
+    ensure_cache_initialized();
+    let mut cache = SPACE_CARDS_CACHE.lock().unwrap();
+    let cache_data = cache.as_mut().unwrap();
+
+    cache_data.space_cards.remove(&space_id.to_string());
+
+    // 保存到磁盘
+    save_cache_to_disk(cache_data);
+}
+
 /// 清空所有缓存
 pub fn clear_cache() {
     let mut cache = SPACE_CARDS_CACHE.lock().unwrap();
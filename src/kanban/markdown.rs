@@ -0,0 +1,254 @@
+//! 卡片描述的 Markdown
+//!
+//! 描述的 Markdown 原文保存在 `description` 中，渲染后的 HTML 保存在 `description_html` 中，
+//! 与 Matrix 消息的 `body` / `formatted_body` 相同。渲染前先展开看板特有的写法：
+//! - `- [ ] 事项` / `- [x] 事项`：清单，显示为 ☐ / ☑
+//! - `@user:server`：提及用户，链接文字是用户的显示名称（来自 [`crate::profile::user_profile_cache`]）
+//! - `[[卡片标题]]`：链接到同名卡片（有多张时优先链接同一列表中的，再按卡片 ID 选择）
+//!
+//! 提及和卡片链接都是 `matrix.to` 链接，在 [`crate::shared::html_or_plaintext::HtmlOrPlaintext`]
+//! 中显示为 Matrix 链接胶囊。代码块和行内代码中的内容保持原样。
+
+use makepad_widgets::Cx;
+use matrix_sdk::ruma::{OwnedRoomId, UserId, events::room::message::FormattedBody};
+
+use crate::kanban::state::kanban_state::{KanbanAppState, KanbanCard};
+use crate::profile::user_profile_cache::with_user_profile;
+
+/// 渲染 `card_id` 的描述，返回 HTML；描述中没有任何格式时返回 `None`（按纯文本显示）
+pub fn render_description(
+    cx: &mut Cx,
+    markdown: &str,
+    state: &KanbanAppState,
+    card_id: Option<&OwnedRoomId>,
+) -> Option<String> {
+    let space_id = card_id
+        .and_then(|card_id| state.cards.get(card_id))
+        .map(|card| &card.space_id);
+    let expanded = expand_markdown(markdown, |token| match token {
+        Token::Mention(user_id) => {
+            let name = with_user_profile(cx, user_id.to_owned(), true, |profile, _| {
+                profile.displayable_name().to_string()
+            })
+            .unwrap_or_else(|| user_id.to_string());
+            Some(format!(
+                "[{}]({})",
+                escape_markdown(&name),
+                user_id.matrix_to_uri()
+            ))
+        }
+        Token::CardLink(title) => linked_card(state, title, space_id).map(|card| {
+            format!(
+                "[{}]({})",
+                escape_markdown(&card.title),
+                card.id.matrix_to_uri()
+            )
+        }),
+    });
+    FormattedBody::markdown(&expanded).map(|formatted| formatted.body)
+}
+
+/// 描述中清单的完成情况 `(已完成, 总数)`；没有清单时返回 `None`
+pub fn checklist_progress(markdown: &str) -> Option<(usize, usize)> {
+    let (done, total) = code_free_lines(markdown)
+        .filter_map(checklist_item)
+        .fold((0, 0), |(done, total), checked| {
+            (done + usize::from(checked), total + 1)
+        });
+    (total > 0).then_some((done, total))
+}
+
+/// `[[title]]` 链接到的卡片：未归档的同名卡片中优先选 `space_id` 列表中的，再按卡片 ID 选择
+fn linked_card<'a>(
+    state: &'a KanbanAppState,
+    title: &str,
+    space_id: Option<&OwnedRoomId>,
+) -> Option<&'a KanbanCard> {
+    state
+        .cards
+        .values()
+        .filter(|card| card.archived_at.is_none() && card.title == title)
+        .min_by_key(|&card| (Some(&card.space_id) != space_id, &card.id))
+}
+
+/// 转义 Markdown 特殊字符，让显示名称和卡片标题按原文显示在链接文字中
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii_punctuation() {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// 需要展开的写法
+enum Token<'a> {
+    Mention(&'a UserId),
+    CardLink(&'a str),
+}
+
+/// 展开清单、提及和卡片链接；`expand` 返回 `None` 时保留原文
+fn expand_markdown(markdown: &str, mut expand: impl FnMut(Token) -> Option<String>) -> String {
+    let mut output = String::with_capacity(markdown.len());
+    let mut in_code_block = false;
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            output.push_str(line);
+        } else if in_code_block {
+            output.push_str(line);
+        } else {
+            let line = expand_checklist(line);
+            // 奇数段是行内代码
+            for (i, segment) in line.split('`').enumerate() {
+                if i > 0 {
+                    output.push('`');
+                }
+                if i % 2 == 1 {
+                    output.push_str(segment);
+                } else {
+                    expand_inline(segment, &mut expand, &mut output);
+                }
+            }
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// `- [ ] 事项` -> `- ☐ 事项`，`- [x] 事项` -> `- ☑ 事项`
+fn expand_checklist(line: &str) -> String {
+    let Some(checked) = checklist_item(line) else {
+        return line.to_string();
+    };
+    let indent = &line[..line.len() - line.trim_start().len()];
+    let text = line.trim_start()[5..].trim_start();
+    format!("{}- {} {}", indent, if checked { "☑" } else { "☐" }, text)
+}
+
+/// 清单项是否已完成；不是清单项时返回 `None`
+fn checklist_item(line: &str) -> Option<bool> {
+    let item = line.trim_start();
+    let item = item
+        .strip_prefix("- ")
+        .or_else(|| item.strip_prefix("* "))?;
+    match item.get(..3)? {
+        "[ ]" => Some(false),
+        "[x]" | "[X]" => Some(true),
+        _ => None,
+    }
+}
+
+/// 代码块以外的行
+fn code_free_lines(markdown: &str) -> impl Iterator<Item = &str> {
+    let mut in_code_block = false;
+    markdown.lines().filter(move |line| {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            return false;
+        }
+        !in_code_block
+    })
+}
+
+/// 展开一段不含代码的文本中的提及和卡片链接
+fn expand_inline(
+    text: &str,
+    expand: &mut impl FnMut(Token) -> Option<String>,
+    output: &mut String,
+) {
+    let mut rest = text;
+    while let Some(index) = rest.find(['@', '[']) {
+        let (before, candidate) = rest.split_at(index);
+        output.push_str(before);
+
+        // 只展开位于行首或空白之后的提及，避免改动已有链接中的用户 ID
+        let at_word_start = output
+            .chars()
+            .last()
+            .is_none_or(|c| c.is_whitespace() || c == '(');
+        let expanded = if candidate.starts_with('@') && at_word_start {
+            let end = candidate
+                .char_indices()
+                .skip(1)
+                .find(|(_, c)| !(c.is_alphanumeric() || "._=-/+:".contains(*c)))
+                .map_or(candidate.len(), |(i, _)| i);
+            // 句末的 `.` / `:` 不属于用户 ID
+            let user_id = candidate[..end].trim_end_matches(['.', ':']);
+            UserId::parse(user_id)
+                .ok()
+                .and_then(|user_id| expand(Token::Mention(&user_id)))
+                .map(|replacement| (replacement, user_id.len()))
+        } else if let Some(link) = candidate.strip_prefix("[[") {
+            link.find("]]").and_then(|end| {
+                let title = link[..end].trim();
+                expand(Token::CardLink(title)).map(|replacement| (replacement, end + 4))
+            })
+        } else {
+            None
+        };
+
+        match expanded {
+            Some((replacement, consumed)) => {
+                output.push_str(&replacement);
+                rest = &candidate[consumed..];
+            }
+            None => {
+                let skip = candidate.chars().next().map_or(1, char::len_utf8);
+                output.push_str(&candidate[..skip]);
+                rest = &candidate[skip..];
+            }
+        }
+    }
+    output.push_str(rest);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kanban::test_util::room_id;
+
+    fn add_card(state: &mut KanbanAppState, id: &str, title: &str, space_id: &str) {
+        state.upsert_card(KanbanCard::new(
+            room_id(id),
+            title.to_string(),
+            room_id(space_id),
+        ));
+    }
+
+    #[test]
+    fn escapes_markdown_in_link_text() {
+        assert_eq!(escape_markdown("*重要* [草稿]"), "\\*重要\\* \\[草稿\\]");
+        assert_eq!(escape_markdown("<b>&"), "\\<b\\>\\&");
+        assert_eq!(escape_markdown("普通标题"), "普通标题");
+    }
+
+    #[test]
+    fn card_links_prefer_the_current_list() {
+        let mut state = KanbanAppState::default();
+        add_card(&mut state, "a", "发布", "other");
+        add_card(&mut state, "b", "发布", "list");
+        add_card(&mut state, "c", "发布", "list");
+
+        let current = room_id("list");
+        let card = linked_card(&state, "发布", Some(&current)).unwrap();
+        assert_eq!(card.id, room_id("b"));
+
+        let card = linked_card(&state, "发布", None).unwrap();
+        assert_eq!(card.id, room_id("a"));
+        assert!(linked_card(&state, "不存在", None).is_none());
+    }
+
+    #[test]
+    fn card_links_skip_archived_cards() {
+        let mut state = KanbanAppState::default();
+        add_card(&mut state, "a", "发布", "list");
+        add_card(&mut state, "b", "发布", "list");
+        state.cards.get_mut(&room_id("a")).unwrap().archived_at = Some(1);
+
+        let card = linked_card(&state, "发布", None).unwrap();
+        assert_eq!(card.id, room_id("b"));
+    }
+}
@@ -8,14 +8,111 @@
 //! Topic 标记：
 //! - `[kanban-list]` - 标识一个 Space 是看板列表
 
+use std::path::Path;
+
 use anyhow::{Context, Result};
 use makepad_widgets::{log, error};
 use matrix_sdk::{
     Client,
-    ruma::{OwnedRoomId, RoomId},
+    ruma::{OwnedRoomId, RoomId, UserId},
     Room,
 };
 
+use crate::kanban::drag_drop::fractional_index::{MAX_RANK_LEN, is_valid_rank, rank_between};
+use crate::kanban::state::attachments::{AttachmentKind, CardAttachment, detect_mimetype};
+use crate::kanban::state::boards::BOARD_TOPIC_MARKER;
+use crate::kanban::state::history::{CardFields, CardRevision, CardSnapshot, TagLibraryRevision};
+use crate::kanban::state::subscriptions::activity_notice;
+use crate::kanban::state::kanban_state::KanbanBoard;
+use crate::kanban::state::schema::{
+    BOARD_EVENT_TYPE, BoardMetadata, CARD_TODOS_EVENT_TYPE, CardMetadata, TAG_LIBRARY_EVENT_TYPE,
+    activity_content, parse_tags, parse_todos, tag_library_content, todos_content,
+};
+
+/// 卡片元数据消息的前缀（消息体为 `__KANBAN_METADATA__:{json}`）
+pub const CARD_METADATA_PREFIX: &str = "__KANBAN_METADATA__:";
+
+/// 卡片系统活动消息的 msgtype
+pub const CARD_ACTIVITY_MSGTYPE: &str = "m.kanban.card.activity";
+
+/// 按时间范围加载活动时每页读取的时间线消息数
+const ACTIVITY_PAGE_SIZE: u32 = 200;
+
+/// 加载附件时每页读取的时间线消息数
+const ATTACHMENT_PAGE_SIZE: u32 = 200;
+
+/// 加载历史版本时每页扫描的时间线事件数
+const HISTORY_SCAN_LIMIT: u32 = 200;
+
+/// 加载卡片历史版本时最多向前读取的页数
+const HISTORY_MAX_PAGES: usize = 10;
+
+/// 是否为卡片元数据消息（不应显示在时间线中）
+pub fn is_card_metadata_event(event: &matrix_sdk::ruma::events::AnySyncTimelineEvent) -> bool {
+    use matrix_sdk::ruma::events::{
+        AnySyncMessageLikeEvent, AnySyncTimelineEvent, room::message::MessageType,
+    };
+
+    let AnySyncTimelineEvent::MessageLike(AnySyncMessageLikeEvent::RoomMessage(room_msg)) = event
+    else {
+        return false;
+    };
+    room_msg.as_original().is_some_and(|original| {
+        matches!(
+            &original.content.msgtype,
+            MessageType::Text(text) if text.body.starts_with(CARD_METADATA_PREFIX)
+        )
+    })
+}
+
+/// 从时间线中的 `m.image` / `m.file` 消息解析附件
+///
+/// 卡片 Room 不加密，加密的附件（没有明文地址）会被跳过。
+fn attachment_from_event(
+    event: &matrix_sdk::ruma::events::AnyTimelineEvent,
+) -> Option<CardAttachment> {
+    use matrix_sdk::ruma::events::{
+        AnyMessageLikeEvent, AnyTimelineEvent,
+        room::{MediaSource, message::MessageType},
+    };
+
+    let AnyTimelineEvent::MessageLike(AnyMessageLikeEvent::RoomMessage(room_msg)) = event else {
+        return None;
+    };
+    let original = room_msg.as_original()?;
+    let (kind, name, source, mimetype, size) = match &original.content.msgtype {
+        MessageType::Image(image) => (
+            AttachmentKind::Image,
+            image.filename(),
+            &image.source,
+            image.info.as_ref().and_then(|info| info.mimetype.clone()),
+            image.info.as_ref().and_then(|info| info.size),
+        ),
+        MessageType::File(file) => (
+            AttachmentKind::File,
+            file.filename(),
+            &file.source,
+            file.info.as_ref().and_then(|info| info.mimetype.clone()),
+            file.info.as_ref().and_then(|info| info.size),
+        ),
+        _ => return None,
+    };
+    let MediaSource::Plain(url) = source else {
+        return None;
+    };
+
+    Some(CardAttachment {
+        id: original.event_id.to_string(),
+        kind,
+        name: name.to_string(),
+        url: url.clone(),
+        mimetype,
+        size: size.map(u64::from),
+        user_id: original.sender.to_string(),
+        created_at: original.origin_server_ts.as_secs().into(),
+    })
+}
+
 /// Matrix 到 Kanban 的适配器
 pub struct MatrixKanbanAdapter {
     client: Client,
@@ -40,6 +137,11 @@ impl MatrixKanbanAdapter {
         for room in all_rooms {
             let room_id = room.room_id();
 
+            // 只显示已加入的列表；收到邀请的列表在加入所属看板时一并加入
+            if !matches!(room.state(), matrix_sdk::RoomState::Joined) {
+                continue;
+            }
+
             // 检查 room 是否是 Space 类型
             let is_space = room.is_space();
 
@@ -69,12 +171,14 @@ impl MatrixKanbanAdapter {
                         card_rooms.iter().map(|r| r.room_id().to_owned()).collect();
 
                     let card_count = card_ids.len();
+                    let archived_at = self.get_list_archived_at(&room).await;
 
                     lists.push(crate::kanban::state::kanban_state::KanbanList {
                         id: room_id.to_owned(),
                         name,
                         card_ids,
                         position: 1000.0, // TODO: 从 state event 读取
+                        archived_at,
                     });
 
                     log!(
@@ -93,6 +197,16 @@ impl MatrixKanbanAdapter {
 
     /// 创建新的看板 Space（列表）
     pub async fn create_space(&self, name: &str) -> Result<OwnedRoomId> {
+        self.create_kanban_space(name, format!("[kanban-list] {}", name))
+            .await
+    }
+
+    /// 创建 Space 并设置带标记的 topic（列表和看板共用）
+    async fn create_kanban_space(
+        &self,
+        name: &str,
+        topic_with_marker: String,
+    ) -> Result<OwnedRoomId> {
         use matrix_sdk::ruma::{
             api::client::room::create_room::v3::{Request as CreateRoomRequest, RoomPreset},
             events::room::topic::RoomTopicEventContent,
@@ -154,9 +268,7 @@ impl MatrixKanbanAdapter {
             return Err(anyhow::anyhow!("Room not found in client after creation"));
         }
 
-        // 设置 topic（包含 [kanban-list] 标记）
-        let topic_with_marker = format!("[kanban-list] {}", name);
-
+        // 设置 topic（包含 [kanban-list] / [kanban-board] 标记）
         log!("📝 Setting topic: {}", topic_with_marker);
 
         let topic_content = RoomTopicEventContent::new(topic_with_marker);
@@ -172,6 +284,408 @@ impl MatrixKanbanAdapter {
         Ok(space_id)
     }
 
+    /// 重命名看板 Space（列表）
+    ///
+    /// 加载时从 topic 读取列表名称，所以必须更新 topic；Room 名称只是为了保持一致。
+    pub async fn rename_space(&self, space_id: &RoomId, name: &str) -> Result<()> {
+        use matrix_sdk::ruma::events::room::topic::RoomTopicEventContent;
+
+        let space = self.client.get_room(space_id).context("Space not found")?;
+
+        let topic_with_marker = format!("[kanban-list] {}", name);
+        space
+            .send_state_event(RoomTopicEventContent::new(topic_with_marker.clone()))
+            .await
+            .context("Failed to update space topic")?;
+        log!(
+            "✅ Successfully updated list topic on Matrix server: {} -> {}",
+            space_id,
+            topic_with_marker
+        );
+
+        if let Err(e) = space.set_name(name.to_string()).await {
+            log!("⚠️ Warning: Failed to update room name: {e:?}");
+        }
+        Ok(())
+    }
+
+    // ========== 看板（父 Space） ==========
+
+    /// 获取当前用户可见的所有看板（已加入或收到邀请的、带有 [kanban-board] topic 标记的 Space）
+    pub async fn get_all_boards(&self) -> Result<Vec<KanbanBoard>> {
+        use matrix_sdk::RoomState;
+
+        let mut boards = Vec::new();
+        for room in self.client.rooms() {
+            let invited = match room.state() {
+                RoomState::Joined => false,
+                RoomState::Invited => true,
+                _ => continue,
+            };
+            let Some(topic) = room.topic() else {
+                continue;
+            };
+            if !topic.starts_with(BOARD_TOPIC_MARKER) {
+                continue;
+            }
+
+            let name = topic.trim_start_matches(BOARD_TOPIC_MARKER).trim();
+            let name = if name.is_empty() {
+                room.display_name().await?.to_string()
+            } else {
+                name.to_string()
+            };
+
+            // 收到邀请时只能看到少量状态，背景和列表在加入后才能读取
+            let (metadata, list_ids) = if invited {
+                (BoardMetadata::default(), Vec::new())
+            } else {
+                (
+                    self.load_board_metadata(&room).await,
+                    self.get_board_list_ids(&room).await,
+                )
+            };
+
+            log!(
+                "Found kanban board: {} ({}) with {} lists, invited: {}",
+                name,
+                room.room_id(),
+                list_ids.len(),
+                invited
+            );
+            boards.push(KanbanBoard {
+                id: room.room_id().to_owned(),
+                name,
+                description: None,
+                background_color: metadata.background_color,
+                background_image: metadata.background_image,
+                list_ids,
+                archived_at: metadata.archived_at,
+                invited,
+                subscriptions: metadata.subscriptions,
+            });
+        }
+
+        log!("Found {} kanban boards total", boards.len());
+        Ok(boards)
+    }
+
+    /// 创建看板（父 Space）
+    pub async fn create_board(&self, name: &str, background_color: &str) -> Result<OwnedRoomId> {
+        log!("🏗️ Creating kanban board: {}", name);
+        let board_id = self
+            .create_kanban_space(name, format!("{} {}", BOARD_TOPIC_MARKER, name))
+            .await?;
+
+        let metadata = BoardMetadata {
+            background_color: background_color.to_string(),
+            ..BoardMetadata::default()
+        };
+        self.save_board_metadata(&board_id, &metadata).await?;
+        Ok(board_id)
+    }
+
+    /// 保存看板的背景、归档状态和订阅（`m.kanban.board` 状态事件）
+    pub async fn save_board_metadata(
+        &self,
+        board_id: &RoomId,
+        metadata: &BoardMetadata,
+    ) -> Result<()> {
+        let board = self.client.get_room(board_id).context("Board not found")?;
+        board
+            .send_state_event_raw(
+                BOARD_EVENT_TYPE,
+                "",
+                serde_json::value::to_raw_value(metadata)
+                    .context("Failed to serialize board metadata")?,
+            )
+            .await
+            .context("Failed to save board metadata")?;
+        log!("✅ Saved board metadata for {}", board_id);
+        Ok(())
+    }
+
+    /// 读取看板的 `m.kanban.board` 状态事件（没有时使用默认背景）
+    async fn load_board_metadata(&self, board: &Room) -> BoardMetadata {
+        use matrix_sdk::ruma::events::StateEventType;
+
+        let event_type = StateEventType::from(BOARD_EVENT_TYPE);
+        let content = match board.get_state_event(event_type, "").await {
+            Ok(Some(raw_event)) => serde_json::to_value(&raw_event)
+                .ok()
+                .and_then(|event| event.get("content").cloned()),
+            Ok(None) => None,
+            Err(e) => {
+                log!("⚠️ Failed to read {} state: {:?}", BOARD_EVENT_TYPE, e);
+                None
+            }
+        };
+        content
+            .and_then(|content| BoardMetadata::parse(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// 看板的列表 ID（看板中带有 `via` 的 m.space.child 事件）
+    async fn get_board_list_ids(&self, board: &Room) -> Vec<OwnedRoomId> {
+        use matrix_sdk::ruma::events::StateEventType;
+
+        self.space_relation_ids(board, StateEventType::SpaceChild)
+            .await
+    }
+
+    /// Room 的 m.space.child / m.space.parent 指向的 Room ID
+    async fn space_relation_ids(
+        &self,
+        room: &Room,
+        event_type: matrix_sdk::ruma::events::StateEventType,
+    ) -> Vec<OwnedRoomId> {
+        let events = match room.get_state_events(event_type.clone()).await {
+            Ok(events) => events,
+            Err(e) => {
+                log!(
+                    "⚠️ Failed to read {} events of {}: {:?}",
+                    event_type,
+                    room.room_id(),
+                    e
+                );
+                return Vec::new();
+            }
+        };
+
+        // 根据 Matrix 规范，没有 `via` 的 m.space.child / m.space.parent 表示关系已被移除
+        events
+            .iter()
+            .filter_map(|raw_event| serde_json::to_value(raw_event).ok())
+            .filter(|event| {
+                event
+                    .get("content")
+                    .and_then(|content| content.get("via"))
+                    .is_some_and(serde_json::Value::is_array)
+            })
+            .filter_map(|event| {
+                event
+                    .get("state_key")?
+                    .as_str()
+                    .and_then(|state_key| OwnedRoomId::try_from(state_key).ok())
+            })
+            .collect()
+    }
+
+    /// 列表所属的看板（列表带有 `via` 的 m.space.parent 中带有 [kanban-board] 标记的 Space）
+    async fn find_board_of_list(&self, list_id: &RoomId) -> Option<Room> {
+        use matrix_sdk::ruma::events::StateEventType;
+
+        let list = self.client.get_room(list_id)?;
+        self.space_relation_ids(&list, StateEventType::SpaceParent)
+            .await
+            .into_iter()
+            .filter_map(|parent_id| self.client.get_room(&parent_id))
+            .find(|parent| {
+                parent
+                    .topic()
+                    .is_some_and(|topic| topic.starts_with(BOARD_TOPIC_MARKER))
+            })
+    }
+
+    /// 把列表 Space 加入看板（双向的 m.space.child / m.space.parent），并邀请看板成员
+    pub async fn add_list_to_board(&self, board_id: &RoomId, list_id: &RoomId) -> Result<()> {
+        use matrix_sdk::ruma::events::space::{
+            child::SpaceChildEventContent, parent::SpaceParentEventContent,
+        };
+
+        let board = self.client.get_room(board_id).context("Board not found")?;
+        let list = self.client.get_room(list_id).context("List not found")?;
+
+        // 看板成员需要通过 via 中的服务器加入列表
+        let via: Vec<_> = self
+            .client
+            .user_id()
+            .map(|user_id| user_id.server_name().to_owned())
+            .into_iter()
+            .collect();
+
+        board
+            .send_state_event_raw(
+                "m.space.child",
+                list_id.as_str(),
+                serde_json::value::to_raw_value(&SpaceChildEventContent::new(via.clone()))
+                    .context("Failed to serialize space child content")?,
+            )
+            .await
+            .context("Failed to add list to board")?;
+        if let Err(e) = list
+            .send_state_event_raw(
+                "m.space.parent",
+                board_id.as_str(),
+                serde_json::value::to_raw_value(&SpaceParentEventContent::new(via))
+                    .context("Failed to serialize space parent content")?,
+            )
+            .await
+        {
+            error!("❌ Failed to send m.space.parent event: {:?}", e);
+        }
+        log!("🔗 Added list {} to board {}", list_id, board_id);
+
+        let invited = self.invite_members(&board, &list).await;
+        if invited > 0 {
+            log!("📨 Invited {} board members to list {}", invited, list_id);
+        }
+        Ok(())
+    }
+
+    /// 邀请用户加入看板，同时邀请到看板中的所有列表和卡片，返回新邀请的 Room 数量
+    pub async fn invite_to_board(&self, board_id: &RoomId, user_id: &UserId) -> Result<usize> {
+        let board = self.client.get_room(board_id).context("Board not found")?;
+
+        let mut invited = 0;
+        if !self.is_member(&board, user_id).await {
+            board
+                .invite_user_by_id(user_id)
+                .await
+                .context("Failed to invite user to board")?;
+            invited += 1;
+        }
+
+        let mut rooms = Vec::new();
+        for list_id in self.get_board_list_ids(&board).await {
+            let Some(list) = self.client.get_room(&list_id) else {
+                continue;
+            };
+            rooms.extend(self.get_space_children(&list).await.unwrap_or_default());
+            rooms.push(list);
+        }
+        for room in rooms {
+            if self.is_member(&room, user_id).await {
+                continue;
+            }
+            match room.invite_user_by_id(user_id).await {
+                Ok(()) => invited += 1,
+                Err(e) => error!(
+                    "❌ Failed to invite {} to {}: {:?}",
+                    user_id,
+                    room.room_id(),
+                    e
+                ),
+            }
+        }
+
+        log!(
+            "📨 Invited {} to board {} ({} rooms)",
+            user_id,
+            board_id,
+            invited
+        );
+        Ok(invited)
+    }
+
+    /// 接受看板邀请：加入看板，以及其中收到邀请的列表和卡片，返回加入的 Room 数量
+    pub async fn join_board(&self, board_id: &RoomId) -> Result<usize> {
+        let mut joined = 0;
+        let board = match self.client.get_room(board_id) {
+            Some(room) if matches!(room.state(), matrix_sdk::RoomState::Joined) => room,
+            _ => {
+                joined += 1;
+                self.client
+                    .join_room_by_id(board_id)
+                    .await
+                    .context("Failed to join board")?
+            }
+        };
+
+        // 等待 SDK 同步看板的状态
+        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+        let list_ids = self.get_board_list_ids(&board).await;
+        joined += self.join_invited_rooms(&list_ids).await;
+
+        // 列表加入后才能读取其中的卡片
+        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+        for list_id in &list_ids {
+            let Some(list) = self.client.get_room(list_id) else {
+                continue;
+            };
+            let card_ids: Vec<OwnedRoomId> = self
+                .get_space_children(&list)
+                .await
+                .unwrap_or_default()
+                .iter()
+                .map(|room| room.room_id().to_owned())
+                .collect();
+            joined += self.join_invited_rooms(&card_ids).await;
+        }
+
+        log!("✅ Joined board {} ({} rooms)", board_id, joined);
+        Ok(joined)
+    }
+
+    /// 加入其中收到邀请的 Room，返回加入的数量
+    async fn join_invited_rooms(&self, room_ids: &[OwnedRoomId]) -> usize {
+        let mut joined = 0;
+        for room_id in room_ids {
+            let invited = self
+                .client
+                .get_room(room_id)
+                .is_some_and(|room| matches!(room.state(), matrix_sdk::RoomState::Invited));
+            if !invited {
+                continue;
+            }
+            match self.client.join_room_by_id(room_id).await {
+                Ok(_) => joined += 1,
+                Err(e) => error!("❌ Failed to join {}: {:?}", room_id, e),
+            }
+        }
+        joined
+    }
+
+    /// 把 `source` 中已加入和已邀请的成员邀请到 `target`，返回新邀请的人数
+    async fn invite_members(&self, source: &Room, target: &Room) -> usize {
+        use matrix_sdk::RoomMemberships;
+
+        let members = match source
+            .members(RoomMemberships::JOIN | RoomMemberships::INVITE)
+            .await
+        {
+            Ok(members) => members,
+            Err(e) => {
+                log!("⚠️ Failed to load members of {}: {:?}", source.room_id(), e);
+                return 0;
+            }
+        };
+
+        let mut invited = 0;
+        for member in members {
+            let user_id = member.user_id();
+            if self.client.user_id() == Some(user_id) || self.is_member(target, user_id).await {
+                continue;
+            }
+            match target.invite_user_by_id(user_id).await {
+                Ok(()) => invited += 1,
+                Err(e) => error!(
+                    "❌ Failed to invite {} to {}: {:?}",
+                    user_id,
+                    target.room_id(),
+                    e
+                ),
+            }
+        }
+        invited
+    }
+
+    /// 用户是否已加入或已被邀请到 Room
+    async fn is_member(&self, room: &Room, user_id: &UserId) -> bool {
+        use matrix_sdk::ruma::events::room::member::MembershipState;
+
+        room.get_member_no_sync(user_id)
+            .await
+            .ok()
+            .flatten()
+            .is_some_and(|m| {
+                matches!(
+                    m.membership(),
+                    MembershipState::Join | MembershipState::Invite
+                )
+            })
+    }
+
     /// 创建新卡片（Matrix Room）并添加到 Space
     pub async fn create_card(&self, space_id: &RoomId, title: &str) -> Result<OwnedRoomId> {
         use matrix_sdk::ruma::{
@@ -208,44 +722,59 @@ impl MatrixKanbanAdapter {
 
         log!("✓✓ Room created successfully: {}", card_room_id);
 
-        // 计算新卡片的 position
-        // 获取 Space 中现有卡片的最大 position，新卡片排在最后
-        let position = match self.client.get_room(space_id) {
+        // 计算新卡片的 position 和排序键
+        // 获取 Space 中现有卡片的最大 position / 排序键，新卡片排在最后
+        let (position, last_rank) = match self.client.get_room(space_id) {
             Some(space_room) => match self.get_card_list_from_state(&space_room).await {
                 Ok(card_ids) => {
                     let mut max_position = 0.0;
+                    let mut last_rank: Option<String> = None;
                     for card_id in card_ids {
                         if let Ok(card) = self.load_card(&card_id, space_id.to_owned()).await {
                             if card.position > max_position {
                                 max_position = card.position;
                             }
+                            if is_valid_rank(&card.rank)
+                                && last_rank.as_ref().is_none_or(|rank| card.rank > *rank)
+                            {
+                                last_rank = Some(card.rank);
+                            }
                         }
                     }
-                    max_position + 1000.0
+                    (max_position + 1000.0, last_rank)
                 }
                 Err(e) => {
                     log!(
                         "⚠️ Failed to get existing cards, using default position: {:?}",
                         e
                     );
-                    1000.0
+                    (1000.0, None)
                 }
             },
             None => {
                 log!("⚠️ Space not found, using default position");
-                1000.0
+                (1000.0, None)
             }
         };
+        // 排序键过长时留空，下次在该列表中插入卡片时重新分配
+        let rank = rank_between(last_rank.as_deref(), None)
+            .filter(|rank| rank.len() <= MAX_RANK_LEN)
+            .unwrap_or_default();
 
-        log!("📍 Calculated position for new card: {}", position);
+        log!(
+            "📍 Calculated position for new card: {}, rank: {:?}",
+            position,
+            rank
+        );
 
-        // 创建初始的 Card 元数据，使用计算出的 position
+        // 创建初始的 Card 元数据，使用计算出的 position 和排序键
         let mut card = crate::kanban::state::kanban_state::KanbanCard::new(
             card_room_id.clone(),
             title.to_string(),
             space_id.to_owned(),
         );
         card.position = position;
+        card.rank = rank;
 
         // 保存元数据到 Matrix State
         log!("💾 Saving initial card metadata...");
@@ -266,6 +795,11 @@ impl MatrixKanbanAdapter {
             }
         }
 
+        // 列表的成员（看板成员）也能看到新卡片
+        if let Some(space_room) = self.client.get_room(space_id) {
+            self.invite_members(&space_room, &room).await;
+        }
+
         log!(
             "✓✓✓✓ Created kanban card: {} in space {} ({}) with position {}",
             title,
@@ -286,25 +820,15 @@ impl MatrixKanbanAdapter {
 
         // 尝试从 State Event 加载完整元数据
         match self.load_card_metadata(&room).await {
-            Ok(metadata) => {
+            Ok(Some(metadata)) => {
                 // 加载 TodoList
                 let todos = self.load_card_todos(&room).await.unwrap_or_default();
 
-                Ok(crate::kanban::state::kanban_state::KanbanCard {
-                    id: room_id.to_owned(),
-                    title: metadata.title,
-                    description: metadata.description,
-                    space_id,
-                    position: metadata.position,
-                    status: metadata.status,
-                    tags: metadata.tags,
-                    end_time: metadata.end_time,
-                    todos,
-                    created_at: metadata.created_at,
-                    updated_at: metadata.updated_at,
-                })
+                let mut card = metadata.into_card(room_id.to_owned(), space_id, todos);
+                self.retain_member_assignees(&room, &mut card).await;
+                Ok(card)
             }
-            Err(_) => {
+            _ => {
                 // 如果没有元数据，使用默认值
                 let display_name = room.display_name().await?;
                 let title = display_name.to_string();
@@ -318,6 +842,45 @@ impl MatrixKanbanAdapter {
         }
     }
 
+    /// 严格加载服务器端的卡片状态（用于离线队列的冲突合并）
+    ///
+    /// 与 `load_card` 不同，网络错误会直接返回 Err 而不是回退到默认值，
+    /// 避免把"加载失败"误判为"服务器端数据被清空"。
+    pub async fn load_remote_card(
+        &self,
+        room_id: &RoomId,
+        space_id: OwnedRoomId,
+    ) -> Result<crate::kanban::state::kanban_state::KanbanCard> {
+        let room = self.client.get_room(room_id).context("Room not found")?;
+
+        let todos = self.load_card_todos(&room).await?;
+        match self.load_card_metadata(&room).await? {
+            Some(metadata) => Ok(metadata.into_card(room_id.to_owned(), space_id, todos)),
+            None => {
+                let title = room.display_name().await?.to_string();
+                let mut card = crate::kanban::state::kanban_state::KanbanCard::new(
+                    room_id.to_owned(),
+                    title,
+                    space_id,
+                );
+                card.todos = todos;
+                Ok(card)
+            }
+        }
+    }
+
+    /// 更新 Card Room 的名称
+    pub async fn rename_card_room(&self, room_id: &RoomId, title: &str) -> Result<()> {
+        let room = self
+            .client
+            .get_room(room_id)
+            .context("Card room not found")?;
+        room.set_name(title.to_string())
+            .await
+            .context("Failed to update card room name")?;
+        Ok(())
+    }
+
     /// 保存 Card 元数据到 Matrix Room State
     pub async fn save_card_metadata(
         &self,
@@ -333,16 +896,7 @@ impl MatrixKanbanAdapter {
             .context("Card room not found")?;
 
         // Create metadata JSON
-        let metadata = serde_json::json!({
-            "title": card.title,
-            "description": card.description,
-            "position": card.position,
-            "status": card.status,
-            "end_time": card.end_time,
-            "tags": card.tags,
-            "created_at": card.created_at,
-            "updated_at": card.updated_at,
-        });
+        let metadata = CardMetadata::from_card(card);
 
         log!(
             "💾 [2/3] Saving metadata as timeline message for {} - title: {}, status: {:?}, tags: {:?}, end_time: {:?}",
@@ -359,7 +913,7 @@ impl MatrixKanbanAdapter {
 
         // Use a custom message type that won't be displayed in chat UI
         let content =
-            RoomMessageEventContent::text_plain(format!("__KANBAN_METADATA__:{}", metadata_json));
+            RoomMessageEventContent::text_plain(format!("{}{}", CARD_METADATA_PREFIX, metadata_json));
 
         room.send(content).await?;
 
@@ -378,16 +932,14 @@ impl MatrixKanbanAdapter {
             .get_room(card_id)
             .context("Card room not found")?;
 
-        let todos_content = serde_json::json!({
-            "todos": todos,
-        });
+        let todos_content = todos_content(todos);
 
         log!("💾 Saving {} todos for card {}", todos.len(), card_id);
         log!("💾 Todos content: {:?}", todos_content);
 
         let response = room
             .send_state_event_raw(
-                "m.kanban.card.todos",
+                CARD_TODOS_EVENT_TYPE,
                 "",
                 serde_json::value::to_raw_value(&todos_content)
                     .context("Failed to serialize todos")?,
@@ -403,7 +955,9 @@ impl MatrixKanbanAdapter {
 
     /// 从 Room Messages 加载元数据
     /// 使用 Matrix /messages API 直接读取最近的消息
-    async fn load_card_metadata(&self, room: &Room) -> Result<CardMetadataRaw> {
+    ///
+    /// 没有找到元数据消息时返回 `Ok(None)`，请求失败时返回 Err
+    async fn load_card_metadata(&self, room: &Room) -> Result<Option<CardMetadata>> {
         use matrix_sdk::ruma::api::client::message::get_message_events;
         use matrix_sdk::ruma::events::{AnySyncTimelineEvent, AnySyncMessageLikeEvent};
         use matrix_sdk::ruma::events::room::message::{SyncRoomMessageEvent, MessageType};
@@ -422,7 +976,7 @@ impl MatrixKanbanAdapter {
 
                 // 遍历消息查找 metadata（从最新到最旧，取第一个找到的）
                 // Matrix /messages API 返回的消息是按时间倒序排列的（最新的在前）
-                let mut found_metadata: Option<CardMetadataRaw> = None;
+                let mut found_metadata: Option<CardMetadata> = None;
 
                 for raw_event in response.chunk {
                     // 尝试反序列化为同步消息事件
@@ -435,11 +989,10 @@ impl MatrixKanbanAdapter {
 
                                         // 检查是否是 metadata 消息
                                         if let Some(json_str) =
-                                            body.strip_prefix("__KANBAN_METADATA__:")
+                                            body.strip_prefix(CARD_METADATA_PREFIX)
                                         {
                                             log!("📖 Found metadata message, parsing...");
-                                            match serde_json::from_str::<CardMetadataRaw>(json_str)
-                                            {
+                                            match CardMetadata::parse(json_str) {
                                                 Ok(metadata) => {
                                                     log!(
                                                         "✅ Loaded card metadata: title={}, tags={:?}, end_time={:?}",
@@ -464,12 +1017,10 @@ impl MatrixKanbanAdapter {
                     }
                 }
 
-                if let Some(metadata) = found_metadata {
-                    Ok(metadata)
-                } else {
+                if found_metadata.is_none() {
                     log!("⚠ No metadata message found in recent messages");
-                    Err(anyhow::anyhow!("No card metadata found"))
                 }
+                Ok(found_metadata)
             }
             Err(e) => {
                 error!("❌ Error loading messages: {:?}", e);
@@ -504,26 +1055,16 @@ impl MatrixKanbanAdapter {
                     if let Ok(json) = serde_json::from_str::<serde_json::Value>(json_str) {
                         // 检查 event type
                         if let Some(event_type_str) = json.get("type").and_then(|v| v.as_str()) {
-                            if event_type_str == "m.kanban.card.todos" {
+                            if event_type_str == CARD_TODOS_EVENT_TYPE {
                                 log!("📖 Found m.kanban.card.todos event");
                                 if let Some(content) = json.get("content") {
                                     log!("📖 State event content: {:?}", content);
-                                    if let Some(todos_array) =
-                                        content.get("todos").and_then(|v| v.as_array())
-                                    {
-                                        log!("📖 Todos array length: {}", todos_array.len());
-                                        let todos: Vec<
-                                            crate::kanban::state::kanban_state::TodoItem,
-                                        > = todos_array
-                                            .iter()
-                                            .filter_map(|v| serde_json::from_value(v.clone()).ok())
-                                            .collect();
-                                        log!(
-                                            "✅ Loaded {} todos successfully from server",
-                                            todos.len()
-                                        );
-                                        return Ok(todos);
-                                    }
+                                    let todos = parse_todos(content);
+                                    log!(
+                                        "✅ Loaded {} todos successfully from server",
+                                        todos.len()
+                                    );
+                                    return Ok(todos);
                                 }
                             }
                         }
@@ -535,7 +1076,7 @@ impl MatrixKanbanAdapter {
             }
             Err(e) => {
                 error!("❌ Error loading state events from server: {:?}", e);
-                Ok(Vec::new())
+                Err(anyhow::anyhow!("Failed to load state events: {}", e))
             }
         }
     }
@@ -743,6 +1284,30 @@ impl MatrixKanbanAdapter {
         Ok(())
     }
 
+    /// 从 m.kanban.list.archive 状态事件读取列表的归档时间
+    async fn get_list_archived_at(&self, space: &Room) -> Option<u64> {
+        use crate::kanban::state::archived::LIST_ARCHIVE_EVENT_TYPE;
+        use matrix_sdk::ruma::events::StateEventType;
+
+        let event_type = StateEventType::from(LIST_ARCHIVE_EVENT_TYPE);
+        match space.get_state_event(event_type, "").await {
+            Ok(Some(raw_event)) => serde_json::to_value(&raw_event)
+                .ok()?
+                .get("content")?
+                .get("archived_at")?
+                .as_u64(),
+            Ok(None) => None,
+            Err(e) => {
+                log!(
+                    "⚠️ Failed to read {} state: {:?}",
+                    LIST_ARCHIVE_EVENT_TYPE,
+                    e
+                );
+                None
+            }
+        }
+    }
+
     /// 从 m.kanban.cards 状态事件读取卡片列表
     async fn get_card_list_from_state(&self, space: &Room) -> Result<Vec<OwnedRoomId>> {
         use matrix_sdk::ruma::events::StateEventType;
@@ -944,6 +1509,22 @@ impl MatrixKanbanAdapter {
         activity_type: crate::kanban::state::kanban_state::ActivityType,
         text: String,
         metadata: Option<serde_json::Value>,
+    ) -> Result<()> {
+        self.send_activity_in_list(card_id, None, activity_type, text, metadata)
+            .await
+    }
+
+    /// 发送活动记录，并转发到看板订阅的聊天 Room
+    ///
+    /// 卡片刚创建或刚移动时本地可能还没有同步到新的 m.space.parent，
+    /// 这时通过 `list_id` 指定卡片所在的列表；为 `None` 时从 m.space.parent 查找。
+    pub async fn send_activity_in_list(
+        &self,
+        card_id: &RoomId,
+        list_id: Option<&RoomId>,
+        activity_type: crate::kanban::state::kanban_state::ActivityType,
+        text: String,
+        metadata: Option<serde_json::Value>,
     ) -> Result<()> {
         let room = self
             .client
@@ -959,36 +1540,17 @@ impl MatrixKanbanAdapter {
 
         use crate::kanban::state::kanban_state::ActivityType;
 
-        // 评论使用标准的 m.text 消息
+        // 评论使用标准的 m.text 消息（支持 Markdown，与聊天消息一样渲染）
         if matches!(activity_type, ActivityType::Comment) {
             use matrix_sdk::ruma::events::room::message::RoomMessageEventContent;
-            let content = RoomMessageEventContent::text_plain(&text);
+            let content = RoomMessageEventContent::text_markdown(&text);
             room.send(content).await.context("Failed to send comment")?;
             log!("✓ Comment sent successfully");
             return Ok(());
         }
 
         // 系统活动使用自定义消息类型
-        let activity_type_str = match activity_type {
-            ActivityType::StatusChange => "status_change",
-            ActivityType::TagAdded => "tag_added",
-            ActivityType::TagRemoved => "tag_removed",
-            ActivityType::TodoAdded => "todo_added",
-            ActivityType::TodoCompleted => "todo_completed",
-            ActivityType::TodoUncompleted => "todo_uncompleted",
-            ActivityType::EndTimeSet => "end_time_set",
-            ActivityType::EndTimeRemoved => "end_time_removed",
-            ActivityType::DescriptionChanged => "description_changed",
-            ActivityType::TitleChanged => "title_changed",
-            ActivityType::Comment => unreachable!(),
-        };
-
-        let content = serde_json::json!({
-            "msgtype": "m.kanban.card.activity",
-            "body": text,
-            "activity_type": activity_type_str,
-            "metadata": metadata,
-        });
+        let content = activity_content(&activity_type, &text, metadata);
 
         let raw_content = serde_json::value::to_raw_value(&content)
             .context("Failed to serialize activity content")?;
@@ -998,9 +1560,90 @@ impl MatrixKanbanAdapter {
             .context("Failed to send activity")?;
 
         log!("✓ System activity sent successfully");
+        self.mirror_activity(&room, list_id, &activity_type, &text)
+            .await;
         Ok(())
     }
 
+    /// 把卡片活动转发到看板订阅的聊天 Room（见 [`crate::kanban::state::subscriptions`]）
+    ///
+    /// 转发失败不影响活动记录本身，只记录日志。
+    async fn mirror_activity(
+        &self,
+        card_room: &Room,
+        list_id: Option<&RoomId>,
+        activity_type: &crate::kanban::state::kanban_state::ActivityType,
+        text: &str,
+    ) {
+        use matrix_sdk::RoomState;
+        use matrix_sdk::ruma::events::{StateEventType, room::message::RoomMessageEventContent};
+
+        let list_ids = match list_id {
+            Some(list_id) => vec![list_id.to_owned()],
+            None => {
+                self.space_relation_ids(card_room, StateEventType::SpaceParent)
+                    .await
+            }
+        };
+        let mut board = None;
+        for list_id in &list_ids {
+            board = self.find_board_of_list(list_id).await;
+            if board.is_some() {
+                break;
+            }
+        }
+        let Some(board) = board else {
+            return;
+        };
+
+        let metadata = self.load_board_metadata(&board).await;
+        let targets: Vec<&OwnedRoomId> = metadata
+            .subscriptions
+            .iter()
+            .filter(|subscription| subscription.wants(activity_type))
+            .map(|subscription| &subscription.room_id)
+            .collect();
+        if targets.is_empty() {
+            return;
+        }
+
+        let board_name = board
+            .topic()
+            .map(|topic| {
+                topic
+                    .trim_start_matches(BOARD_TOPIC_MARKER)
+                    .trim()
+                    .to_string()
+            })
+            .unwrap_or_default();
+        let card_title = card_room
+            .name()
+            .unwrap_or_else(|| card_room.room_id().to_string());
+        let (body, html) = activity_notice(
+            &board_name,
+            &card_title,
+            card_room.room_id(),
+            activity_type,
+            text,
+        );
+
+        for room_id in targets {
+            let Some(room) = self
+                .client
+                .get_room(room_id)
+                .filter(|room| room.state() == RoomState::Joined)
+            else {
+                log!("⚠️ Skipping subscription to {}: room not joined", room_id);
+                continue;
+            };
+            let content = RoomMessageEventContent::notice_html(body.clone(), html.clone());
+            match room.send(content).await {
+                Ok(_) => log!("📣 Mirrored {:?} activity to {}", activity_type, room_id),
+                Err(e) => error!("❌ Failed to mirror activity to {}: {:?}", room_id, e),
+            }
+        }
+    }
+
     /// 加载活动记录（从Timeline Events）
     ///
     /// 加载卡片的活动记录
@@ -1053,14 +1696,67 @@ impl MatrixKanbanAdapter {
         }
     }
 
-    /// 从原始事件解析活动记录
-    fn parse_activity_from_raw_event(
+    /// 加载 `since`（Unix timestamp 秒）之后的所有活动记录（最新的在前）
+    ///
+    /// 从最新的消息开始向前分页，直到读到更早的消息或者 Room 的开头。
+    pub async fn load_activities_since(
         &self,
-        event: &matrix_sdk::ruma::events::AnyTimelineEvent,
-    ) -> Option<crate::kanban::state::kanban_state::CardActivity> {
-        use crate::kanban::state::kanban_state::{CardActivity, ActivityType};
-        use matrix_sdk::ruma::events::AnyTimelineEvent;
-
+        card_id: &RoomId,
+        since: u64,
+    ) -> Result<Vec<crate::kanban::state::kanban_state::CardActivity>> {
+        use matrix_sdk::ruma::api::{Direction, client::message::get_message_events};
+
+        let mut activities = Vec::new();
+        let mut from = None;
+        loop {
+            let mut request =
+                get_message_events::v3::Request::new(card_id.to_owned(), Direction::Backward);
+            request.limit = ACTIVITY_PAGE_SIZE.into();
+            request.from = from.take();
+
+            let response = self
+                .client
+                .send(request)
+                .await
+                .context("Failed to load card messages")?;
+            let mut reached_since = false;
+            for raw_event in &response.chunk {
+                let Ok(event) = raw_event.deserialize() else {
+                    continue;
+                };
+                if u64::from(event.origin_server_ts().as_secs()) < since {
+                    reached_since = true;
+                    break;
+                }
+                if let Some(activity) = self.parse_activity_from_raw_event(&event) {
+                    activities.push(activity);
+                }
+            }
+
+            match response.end {
+                Some(end) if !reached_since && !response.chunk.is_empty() => from = Some(end),
+                _ => break,
+            }
+        }
+
+        activities.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        log!(
+            "✅ Loaded {} activities since {} from card {}",
+            activities.len(),
+            since,
+            card_id
+        );
+        Ok(activities)
+    }
+
+    /// 从原始事件解析活动记录
+    fn parse_activity_from_raw_event(
+        &self,
+        event: &matrix_sdk::ruma::events::AnyTimelineEvent,
+    ) -> Option<crate::kanban::state::kanban_state::CardActivity> {
+        use crate::kanban::state::kanban_state::{CardActivity, ActivityType};
+        use matrix_sdk::ruma::events::AnyTimelineEvent;
+
         match event {
             AnyTimelineEvent::MessageLike(msg_event) => {
                 use matrix_sdk::ruma::events::AnyMessageLikeEvent;
@@ -1090,7 +1786,7 @@ impl MatrixKanbanAdapter {
                                 // 跳过卡片元数据消息
                                 if body.starts_with("Card metadata:")
                                     || body.starts_with("Card Metadata:")
-                                    || body.starts_with("__KANBAN_METADATA__")
+                                    || body.starts_with(CARD_METADATA_PREFIX)
                                 {
                                     log!("  ⏭️ Skipping metadata message");
                                     return None;
@@ -1107,6 +1803,23 @@ impl MatrixKanbanAdapter {
                                     user_id: sender,
                                 });
                             }
+                            // 系统活动
+                            other if other.msgtype() == CARD_ACTIVITY_MSGTYPE => {
+                                let data = other.data();
+                                let activity_type = data
+                                    .get("activity_type")
+                                    .and_then(|t| t.as_str())
+                                    .and_then(ActivityType::parse)?;
+                                log!("  ✅ Parsed as {:?} activity", activity_type);
+                                return Some(CardActivity {
+                                    id: event_id,
+                                    activity_type,
+                                    text: other.body().to_string(),
+                                    metadata: data.get("metadata").filter(|m| !m.is_null()).cloned(),
+                                    created_at,
+                                    user_id: sender,
+                                });
+                            }
                             other => {
                                 log!("  ⏭️ Skipping non-text message type: {:?}", other);
                             }
@@ -1125,6 +1838,269 @@ impl MatrixKanbanAdapter {
         None
     }
 
+    // ========== 卡片附件 ==========
+
+    /// 加载卡片 Room 时间线中的图片和文件附件
+    ///
+    /// 从最新的消息开始向前分页，直到读到 Room 的开头。
+    pub async fn load_attachments(&self, card_id: &RoomId) -> Result<Vec<CardAttachment>> {
+        use matrix_sdk::ruma::api::{Direction, client::message::get_message_events};
+
+        let mut attachments = Vec::new();
+        let mut from = None;
+        loop {
+            let mut request =
+                get_message_events::v3::Request::new(card_id.to_owned(), Direction::Backward);
+            request.limit = ATTACHMENT_PAGE_SIZE.into();
+            request.from = from.take();
+
+            let response = self
+                .client
+                .send(request)
+                .await
+                .context("Failed to load card messages")?;
+            attachments.extend(
+                response
+                    .chunk
+                    .iter()
+                    .filter_map(|raw_event| raw_event.deserialize().ok())
+                    .filter_map(|event| attachment_from_event(&event)),
+            );
+
+            match response.end {
+                Some(end) if !response.chunk.is_empty() => from = Some(end),
+                _ => break,
+            }
+        }
+
+        log!(
+            "📎 Loaded {} attachments from card {}",
+            attachments.len(),
+            card_id
+        );
+        Ok(attachments)
+    }
+
+    /// 上传文件到媒体仓库，并作为 `m.image` / `m.file` 消息发送到卡片 Room
+    pub async fn upload_attachment(&self, card_id: &RoomId, path: &Path) -> Result<CardAttachment> {
+        use matrix_sdk::ruma::{
+            UInt,
+            api::client::media::create_content,
+            events::room::{
+                ImageInfo,
+                message::{
+                    FileInfo, FileMessageEventContent, ImageMessageEventContent, MessageType,
+                    RoomMessageEventContent,
+                },
+            },
+        };
+
+        let room = self
+            .client
+            .get_room(card_id)
+            .context("Card room not found")?;
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .context("Attachment path has no file name")?;
+
+        // 先检查大小，避免把超过服务器上限的文件读入内存
+        let max_size = self
+            .client
+            .load_or_fetch_max_upload_size()
+            .await
+            .context("Failed to load the server's upload size limit")?;
+        let file_size = tokio::fs::metadata(path)
+            .await
+            .with_context(|| format!("Failed to read attachment {}", path.display()))?
+            .len();
+        if file_size > u64::from(max_size) {
+            return Err(anyhow::anyhow!(
+                "附件「{}」大小为 {} 字节，超过服务器的上传上限 {} 字节",
+                name,
+                file_size,
+                max_size
+            ));
+        }
+
+        let data = tokio::fs::read(path)
+            .await
+            .with_context(|| format!("Failed to read attachment {}", path.display()))?;
+        let (kind, mimetype) = detect_mimetype(&name, &data);
+        let size = data.len() as u64;
+
+        log!(
+            "📎 Uploading {} ({} bytes, {}) to card {}",
+            name,
+            size,
+            mimetype,
+            card_id
+        );
+
+        let mut request = create_content::v3::Request::new(data);
+        request.filename = Some(name.clone());
+        request.content_type = Some(mimetype.clone());
+        let url = self
+            .client
+            .send(request)
+            .await
+            .context("Failed to upload attachment")?
+            .content_uri;
+
+        let msgtype = match kind {
+            AttachmentKind::Image => {
+                let mut info = ImageInfo::new();
+                info.mimetype = Some(mimetype.clone());
+                info.size = UInt::new(size);
+                MessageType::Image(
+                    ImageMessageEventContent::plain(name.clone(), url.clone()).info(Box::new(info)),
+                )
+            }
+            AttachmentKind::File => {
+                let mut info = FileInfo::new();
+                info.mimetype = Some(mimetype.clone());
+                info.size = UInt::new(size);
+                MessageType::File(
+                    FileMessageEventContent::plain(name.clone(), url.clone()).info(Box::new(info)),
+                )
+            }
+        };
+        let response = room
+            .send(RoomMessageEventContent::new(msgtype))
+            .await
+            .context("Failed to send attachment message")?;
+
+        log!("✅ Attachment {} sent to card {}", name, card_id);
+        Ok(CardAttachment {
+            id: response.event_id.to_string(),
+            kind,
+            name,
+            url,
+            mimetype: Some(mimetype),
+            size: Some(size),
+            user_id: self
+                .client
+                .user_id()
+                .map(|user_id| user_id.to_string())
+                .unwrap_or_default(),
+            created_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+        })
+    }
+
+    // ========== 负责人 ==========
+
+    /// 加载卡片 Room 的成员（已加入和已邀请），用于选择负责人
+    pub async fn load_card_members(
+        &self,
+        card_id: &RoomId,
+    ) -> Result<Vec<crate::kanban::state::kanban_state::CardMember>> {
+        use matrix_sdk::RoomMemberships;
+
+        let room = self
+            .client
+            .get_room(card_id)
+            .context("Card room not found")?;
+
+        let members = room
+            .members(RoomMemberships::JOIN | RoomMemberships::INVITE)
+            .await
+            .context("Failed to load card members")?;
+
+        Ok(members
+            .into_iter()
+            .map(|m| crate::kanban::state::kanban_state::CardMember {
+                user_id: m.user_id().to_owned(),
+                display_name: m.display_name().map(|n| n.to_owned()),
+            })
+            .collect())
+    }
+
+    /// 分配负责人：不是卡片成员的用户会先被邀请进卡片 Room
+    ///
+    /// 只有邀请失败才返回错误，此时负责人不是卡片成员，调用方应撤销分配
+    pub async fn assign_card_member(
+        &self,
+        card_id: &RoomId,
+        user_id: &matrix_sdk::ruma::UserId,
+    ) -> Result<()> {
+        use matrix_sdk::ruma::events::room::member::MembershipState;
+
+        let room = self
+            .client
+            .get_room(card_id)
+            .context("Card room not found")?;
+
+        let is_member = room.get_member_no_sync(user_id).await?.is_some_and(|m| {
+            matches!(m.membership(), MembershipState::Join | MembershipState::Invite)
+        });
+        if !is_member {
+            log!("📨 Inviting {} to card {} before assigning", user_id, card_id);
+            room.invite_user_by_id(user_id)
+                .await
+                .context("Failed to invite assignee")?;
+        }
+
+        // 邀请已成功，活动记录失败不应撤销分配
+        if let Err(e) = self
+            .send_activity(
+                card_id,
+                crate::kanban::state::kanban_state::ActivityType::AssigneeChanged,
+                format!("分配给了 {}", user_id),
+                Some(serde_json::json!({ "action": "assigned", "user_id": user_id })),
+            )
+            .await
+        {
+            error!(
+                "❌ Failed to record assignment activity for card {}: {:?}",
+                card_id, e
+            );
+        }
+        Ok(())
+    }
+
+    /// 取消分配负责人（保留其 Room 成员身份）
+    pub async fn unassign_card_member(
+        &self,
+        card_id: &RoomId,
+        user_id: &matrix_sdk::ruma::UserId,
+    ) -> Result<()> {
+        self.send_activity(
+            card_id,
+            crate::kanban::state::kanban_state::ActivityType::AssigneeChanged,
+            format!("取消分配 {}", user_id),
+            Some(serde_json::json!({ "action": "unassigned", "user_id": user_id })),
+        )
+        .await
+    }
+
+    /// 移除已离开卡片 Room 的负责人
+    async fn retain_member_assignees(
+        &self,
+        room: &Room,
+        card: &mut crate::kanban::state::kanban_state::KanbanCard,
+    ) {
+        use matrix_sdk::RoomMemberships;
+
+        if card.assignees.is_empty() {
+            return;
+        }
+        let Ok(members) = room
+            .members_no_sync(RoomMemberships::JOIN | RoomMemberships::INVITE)
+            .await
+        else {
+            return;
+        };
+        // 成员列表尚未同步时不做过滤
+        if members.is_empty() {
+            return;
+        }
+        card.assignees
+            .retain(|a| members.iter().any(|m| m.user_id() == a.as_ref()));
+    }
+
     // ========== Space 标签库管理 ==========
 
     /// 加载 Space 标签库
@@ -1151,35 +2127,29 @@ impl MatrixKanbanAdapter {
                     // 先转换为 JSON 查看事件类型
                     if let Ok(json_value) = serde_json::to_value(&raw_event) {
                         if let Some(event_type) = json_value.get("type").and_then(|v| v.as_str()) {
-                            if event_type == "m.space.tag_library" {
+                            if event_type == TAG_LIBRARY_EVENT_TYPE {
                                 log!("✅ Found m.space.tag_library event");
 
-                                // 解析事件内容
+                                // 解析事件内容（旧版标签会先升级）
                                 if let Some(content) = json_value.get("content") {
-                                    if let Some(tags_value) = content.get("tags") {
-                                        match serde_json::from_value::<
-                                            Vec<crate::kanban::state::kanban_state::SpaceTag>,
-                                        >(
-                                            tags_value.clone()
-                                        ) {
-                                            Ok(tags) => {
+                                    match parse_tags(content) {
+                                        Ok(tags) => {
+                                            log!(
+                                                "✅ Successfully loaded {} tags from space",
+                                                tags.len()
+                                            );
+                                            for tag in &tags {
                                                 log!(
-                                                    "✅ Successfully loaded {} tags from space",
-                                                    tags.len()
+                                                    "  - Tag: id={}, name={}, color={}",
+                                                    tag.id,
+                                                    tag.name,
+                                                    tag.color
                                                 );
-                                                for tag in &tags {
-                                                    log!(
-                                                        "  - Tag: id={}, name={}, color={}",
-                                                        tag.id,
-                                                        tag.name,
-                                                        tag.color
-                                                    );
-                                                }
-                                                return Ok(tags);
-                                            }
-                                            Err(e) => {
-                                                error!("❌ Failed to parse tags: {}", e);
                                             }
+                                            return Ok(tags);
+                                        }
+                                        Err(e) => {
+                                            error!("❌ Failed to parse tags: {}", e);
                                         }
                                     }
                                 }
@@ -1204,21 +2174,21 @@ impl MatrixKanbanAdapter {
         space_id: &RoomId,
         tags: Vec<crate::kanban::state::kanban_state::SpaceTag>,
     ) -> Result<()> {
+        self.ensure_permission(
+            space_id,
+            crate::kanban::state::permissions::KanbanPermission::ManageTags,
+        )
+        .await?;
+
         let space = self.client.get_room(space_id).context("Space not found")?;
 
         log!("💾 Saving {} tags to space {}", tags.len(), space_id);
 
         // 构建标签库内容
-        let content = serde_json::json!({
-            "tags": tags,
-            "version": std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-        });
+        let content = tag_library_content(&tags);
 
         // 使用 send_state_event_raw 发送自定义状态事件
-        let event_type = "m.space.tag_library";
+        let event_type = TAG_LIBRARY_EVENT_TYPE;
         let state_key = "";
 
         let raw_content = serde_json::value::to_raw_value(&content)
@@ -1233,6 +2203,239 @@ impl MatrixKanbanAdapter {
         Ok(())
     }
 
+    // ========== 历史版本 ==========
+
+    /// 读取 Room 时间线中最近的事件（按时间正序，最早的在前）
+    ///
+    /// 从最新的事件开始向前分页，直到 `enough` 对已读取的事件返回 `true`、读到 Room 的开头
+    /// 或者读满 [`HISTORY_MAX_PAGES`] 页。返回的布尔值表示是否已经读到 Room 的开头。
+    async fn timeline_events_oldest_first(
+        &self,
+        room_id: &RoomId,
+        enough: impl Fn(&[serde_json::Value]) -> bool,
+    ) -> Result<(Vec<serde_json::Value>, bool)> {
+        use matrix_sdk::ruma::api::{Direction, client::message::get_message_events};
+
+        let mut events: Vec<serde_json::Value> = Vec::new();
+        let mut from = None;
+        for _ in 0..HISTORY_MAX_PAGES {
+            let mut request =
+                get_message_events::v3::Request::new(room_id.to_owned(), Direction::Backward);
+            request.limit = HISTORY_SCAN_LIMIT.into();
+            request.from = from.take();
+
+            let response = self
+                .client
+                .send(request)
+                .await
+                .context("Failed to load room messages")?;
+            events.extend(
+                response
+                    .chunk
+                    .iter()
+                    .filter_map(|raw_event| serde_json::from_str(raw_event.json().get()).ok()),
+            );
+
+            match response.end {
+                Some(end) if !response.chunk.is_empty() => from = Some(end),
+                _ => {
+                    events.reverse();
+                    return Ok((events, true));
+                }
+            }
+            if enough(&events) {
+                break;
+            }
+        }
+        events.reverse();
+        Ok((events, false))
+    }
+
+    /// 加载卡片的历史版本（最早的在前）
+    ///
+    /// 每条元数据消息和每个 `m.kanban.card.todos` 状态事件都是一个版本；只改变了排序、
+    /// 位置等不在 [`CardSnapshot`] 中的字段的保存会被跳过。时间线会一直向前读取，直到
+    /// 元数据消息和待办事件都出现过；仍然没有出现的字段在版本中是未知的。
+    pub async fn load_card_history(&self, card_id: &RoomId) -> Result<Vec<CardRevision>> {
+        fn metadata_of(event: &serde_json::Value) -> Option<CardMetadata> {
+            let content = &event["content"];
+            if event["type"] != "m.room.message" || content["msgtype"] != "m.text" {
+                return None;
+            }
+            let json_str = content["body"]
+                .as_str()?
+                .strip_prefix(CARD_METADATA_PREFIX)?;
+            CardMetadata::parse(json_str).ok()
+        }
+        let is_todos = |event: &serde_json::Value| event["type"] == CARD_TODOS_EVENT_TYPE;
+
+        let (events, reached_start) = self
+            .timeline_events_oldest_first(card_id, |events| {
+                events.iter().any(|event| metadata_of(event).is_some())
+                    && events.iter().any(is_todos)
+            })
+            .await?;
+
+        let mut revisions: Vec<CardRevision> = Vec::new();
+        // 读到 Room 的开头时，之前没有出现过的字段就是空的
+        let mut snapshot = if reached_start {
+            CardSnapshot::empty()
+        } else {
+            CardSnapshot::default()
+        };
+        for event in &events {
+            if let Some(metadata) = metadata_of(event) {
+                snapshot.fields = Some(CardFields {
+                    title: metadata.title,
+                    description: metadata.description,
+                    description_html: metadata.description_html,
+                    tags: metadata.tags,
+                    end_time: metadata.end_time,
+                });
+            } else if is_todos(event) {
+                snapshot.todos = Some(parse_todos(&event["content"]));
+            } else {
+                continue;
+            }
+
+            if revisions
+                .last()
+                .is_some_and(|last| last.snapshot == snapshot)
+            {
+                continue;
+            }
+            revisions.push(CardRevision {
+                event_id: event["event_id"].as_str().unwrap_or_default().to_string(),
+                sender: event["sender"].as_str().unwrap_or_default().to_string(),
+                timestamp: event["origin_server_ts"].as_u64().unwrap_or_default() / 1000,
+                snapshot: snapshot.clone(),
+            });
+        }
+
+        log!(
+            "🕘 Loaded {} revisions of card {}",
+            revisions.len(),
+            card_id
+        );
+        Ok(revisions)
+    }
+
+    /// 加载 Space 标签库的历史版本（最早的在前）
+    pub async fn load_tag_library_history(
+        &self,
+        space_id: &RoomId,
+    ) -> Result<Vec<TagLibraryRevision>> {
+        let (events, _) = self
+            .timeline_events_oldest_first(space_id, |_| true)
+            .await?;
+
+        let revisions: Vec<TagLibraryRevision> = events
+            .iter()
+            .filter(|event| event["type"] == TAG_LIBRARY_EVENT_TYPE)
+            .filter_map(|event| {
+                let tags = parse_tags(&event["content"]).ok()?;
+                Some(TagLibraryRevision {
+                    event_id: event["event_id"].as_str()?.to_string(),
+                    sender: event["sender"].as_str().unwrap_or_default().to_string(),
+                    timestamp: event["origin_server_ts"].as_u64().unwrap_or_default() / 1000,
+                    tags,
+                })
+            })
+            .collect();
+
+        log!(
+            "🕘 Loaded {} revisions of the tag library of space {}",
+            revisions.len(),
+            space_id
+        );
+        Ok(revisions)
+    }
+
+    // ========== 看板权限 ==========
+
+    /// 加载 Space 的 `m.room.power_levels` 事件内容（没有时返回空对象）
+    async fn load_power_levels_content(&self, space_id: &RoomId) -> Result<serde_json::Value> {
+        use crate::kanban::state::permissions::POWER_LEVELS_EVENT_TYPE;
+        use matrix_sdk::ruma::api::client::state::get_state_events;
+
+        let request = get_state_events::v3::Request::new(space_id.to_owned());
+        let response = self
+            .client
+            .send(request)
+            .await
+            .context("Failed to load state events")?;
+
+        for raw_event in response.room_state {
+            let Ok(json_value) = serde_json::to_value(&raw_event) else {
+                continue;
+            };
+            if json_value.get("type").and_then(|v| v.as_str()) != Some(POWER_LEVELS_EVENT_TYPE) {
+                continue;
+            }
+            if let Some(content) = json_value.get("content") {
+                return Ok(content.clone());
+            }
+        }
+        Ok(serde_json::json!({}))
+    }
+
+    /// 加载当前用户在 Space 中的看板权限
+    pub async fn load_board_permissions(
+        &self,
+        space_id: &RoomId,
+    ) -> Result<crate::kanban::state::permissions::BoardPermissions> {
+        let content = self.load_power_levels_content(space_id).await?;
+        let permissions = crate::kanban::state::permissions::BoardPermissions::from_content(
+            &content,
+            self.client.user_id(),
+        );
+        log!(
+            "🔐 Loaded board permissions for space {}: level={}, can_edit={}",
+            space_id,
+            permissions.user_level,
+            permissions.can_edit
+        );
+        Ok(permissions)
+    }
+
+    /// 保存看板权限（只修改看板相关的事件类型和用户等级，保留其他权限设置）
+    pub async fn save_board_permissions(
+        &self,
+        space_id: &RoomId,
+        permissions: &crate::kanban::state::permissions::BoardPermissions,
+    ) -> Result<()> {
+        use crate::kanban::state::permissions::POWER_LEVELS_EVENT_TYPE;
+
+        let space = self.client.get_room(space_id).context("Space not found")?;
+
+        let mut content = self.load_power_levels_content(space_id).await?;
+        permissions.apply_to_content(&mut content);
+
+        log!("💾 Saving board permissions to space {}", space_id);
+        let raw_content = serde_json::value::to_raw_value(&content)
+            .context("Failed to serialize power levels content")?;
+        space
+            .send_state_event_raw(POWER_LEVELS_EVENT_TYPE, "", raw_content)
+            .await
+            .context("Failed to save power levels")?;
+
+        log!("✅ Board permissions saved successfully to Matrix");
+        Ok(())
+    }
+
+    /// 当前用户没有该权限时返回错误
+    pub async fn ensure_permission(
+        &self,
+        space_id: &RoomId,
+        permission: crate::kanban::state::permissions::KanbanPermission,
+    ) -> Result<()> {
+        let permissions = self.load_board_permissions(space_id).await?;
+        if !permissions.allows(permission) {
+            return Err(anyhow::anyhow!("没有「{}」权限", permission.display_name()));
+        }
+        Ok(())
+    }
+
     /// 添加标签到 Space
     pub async fn add_space_tag(
         &self,
@@ -1297,46 +2500,366 @@ impl MatrixKanbanAdapter {
         Ok(())
     }
 
-    /// 迁移旧格式标签（标签名称 -> 标签 ID）
-    pub async fn migrate_card_tags(
+    // ========== Space 工作流 ==========
+
+    /// 加载 Space 工作流；没有自定义工作流时返回 None
+    ///
+    /// 与标签库不同，网络错误会返回 Err，避免在迁移时误用默认工作流。
+    pub async fn load_space_workflow(
         &self,
-        _card: &mut crate::kanban::state::kanban_state::KanbanCard,
-        _space_id: &RoomId,
+        space_id: &RoomId,
+    ) -> Result<Option<crate::kanban::state::workflow::Workflow>> {
+        use crate::kanban::state::workflow::{WORKFLOW_EVENT_TYPE, Workflow};
+        use matrix_sdk::ruma::api::client::state::get_state_events;
+
+        log!("🔀 Loading workflow from space {}", space_id);
+
+        let request = get_state_events::v3::Request::new(space_id.to_owned());
+        let response = self
+            .client
+            .send(request)
+            .await
+            .context("Failed to load state events")?;
+
+        for raw_event in response.room_state {
+            let Ok(json_value) = serde_json::to_value(&raw_event) else {
+                continue;
+            };
+            if json_value.get("type").and_then(|v| v.as_str()) != Some(WORKFLOW_EVENT_TYPE) {
+                continue;
+            }
+
+            let Some(content) = json_value.get("content") else {
+                continue;
+            };
+            // 空内容表示工作流已被重置为默认
+            if content.get("statuses").is_none() {
+                return Ok(None);
+            }
+            let workflow = serde_json::from_value::<Workflow>(content.clone())
+                .context("Failed to parse workflow")?;
+            log!(
+                "✅ Loaded workflow with {} statuses from space {}",
+                workflow.statuses.len(),
+                space_id
+            );
+            return Ok(Some(workflow));
+        }
+
+        Ok(None)
+    }
+
+    /// 保存 Space 工作流
+    pub async fn save_space_workflow(
+        &self,
+        space_id: &RoomId,
+        workflow: &crate::kanban::state::workflow::Workflow,
     ) -> Result<()> {
-        // 简化方案：直接使用标签名称，不需要迁移
+        use crate::kanban::state::workflow::WORKFLOW_EVENT_TYPE;
+
+        let space = self.client.get_room(space_id).context("Space not found")?;
+
+        log!(
+            "💾 Saving workflow with {} statuses to space {}",
+            workflow.statuses.len(),
+            space_id
+        );
+
+        let raw_content = serde_json::value::to_raw_value(workflow)
+            .context("Failed to serialize workflow content")?;
+
+        space
+            .send_state_event_raw(WORKFLOW_EVENT_TYPE, "", raw_content)
+            .await
+            .context("Failed to save workflow")?;
+
+        log!("✅ Workflow saved successfully to Matrix");
         Ok(())
     }
-}
 
-/// 原始元数据结构（用于反序列化）
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-struct CardMetadataRaw {
-    pub title: String,
-    #[serde(default)]
-    pub description: Option<String>,
-    #[serde(default = "default_position")]
-    pub position: f64,
-    #[serde(default)]
-    pub end_time: Option<u64>,
-    #[serde(default)]
-    pub tags: Vec<String>,
-    #[serde(default)]
-    pub status: crate::kanban::state::kanban_state::CardStatus,
-    #[serde(default = "default_timestamp")]
-    pub created_at: u64,
-    #[serde(default = "default_timestamp")]
-    pub updated_at: u64,
-}
+    /// 把 Space 中所有卡片的状态迁移到新的工作流，返回被修改的卡片
+    pub async fn migrate_card_statuses(
+        &self,
+        space_id: &RoomId,
+        workflow: &crate::kanban::state::workflow::Workflow,
+    ) -> Result<Vec<crate::kanban::state::kanban_state::KanbanCard>> {
+        use crate::kanban::state::kanban_state::ActivityType;
 
-fn default_position() -> f64 {
-    1000.0
-}
+        let space = self.client.get_room(space_id).context("Space not found")?;
+        let card_ids = self.get_card_list_from_state(&space).await?;
+
+        let mut migrated = Vec::new();
+        for card_id in card_ids {
+            let mut card = match self.load_remote_card(&card_id, space_id.to_owned()).await {
+                Ok(card) => card,
+                Err(e) => {
+                    error!("❌ Skipping status migration for card {}: {e:?}", card_id);
+                    continue;
+                }
+            };
+
+            let new_status = workflow.migrate_status(&card.status);
+            if new_status == card.status {
+                continue;
+            }
 
-fn default_timestamp() -> u64 {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs()
+            log!(
+                "🔀 Migrating card {} status '{}' -> '{}'",
+                card_id,
+                card.status,
+                new_status
+            );
+            let old_status = std::mem::replace(&mut card.status, new_status);
+            card.touch();
+            self.save_card_metadata(&card).await?;
+
+            if let Err(e) = self
+                .send_activity(
+                    &card_id,
+                    ActivityType::StatusChange,
+                    format!(
+                        "工作流变更: {} → {}",
+                        old_status,
+                        workflow.display_name(&card.status)
+                    ),
+                    Some(serde_json::json!({ "from": old_status, "to": card.status })),
+                )
+                .await
+            {
+                error!("❌ Failed to record status migration for {}: {e:?}", card_id);
+            }
+            migrated.push(card);
+        }
+
+        Ok(migrated)
+    }
+
+    // ========== 卡片模板与周期卡片 ==========
+
+    /// 加载 Space 的卡片模板
+    pub async fn load_space_templates(
+        &self,
+        space_id: &RoomId,
+    ) -> Result<Vec<crate::kanban::state::template::CardTemplate>> {
+        use crate::kanban::state::template::{CARD_TEMPLATES_EVENT_TYPE, CardTemplate};
+        use matrix_sdk::ruma::api::client::state::get_state_events;
+
+        log!("📋 Loading card templates from space {}", space_id);
+
+        let request = get_state_events::v3::Request::new(space_id.to_owned());
+        let response = self
+            .client
+            .send(request)
+            .await
+            .context("Failed to load state events")?;
+
+        for raw_event in response.room_state {
+            let Ok(json_value) = serde_json::to_value(&raw_event) else {
+                continue;
+            };
+            if json_value.get("type").and_then(|v| v.as_str()) != Some(CARD_TEMPLATES_EVENT_TYPE)
+            {
+                continue;
+            }
+
+            let Some(templates_value) = json_value
+                .get("content")
+                .and_then(|content| content.get("templates"))
+            else {
+                return Ok(Vec::new());
+            };
+            let templates = serde_json::from_value::<Vec<CardTemplate>>(templates_value.clone())
+                .context("Failed to parse card templates")?;
+            log!(
+                "✅ Loaded {} card templates from space {}",
+                templates.len(),
+                space_id
+            );
+            return Ok(templates);
+        }
+
+        Ok(Vec::new())
+    }
+
+    /// 保存 Space 的卡片模板
+    pub async fn save_space_templates(
+        &self,
+        space_id: &RoomId,
+        templates: &[crate::kanban::state::template::CardTemplate],
+    ) -> Result<()> {
+        use crate::kanban::state::template::CARD_TEMPLATES_EVENT_TYPE;
+
+        let space = self.client.get_room(space_id).context("Space not found")?;
+
+        log!(
+            "💾 Saving {} card templates to space {}",
+            templates.len(),
+            space_id
+        );
+
+        let content = serde_json::json!({
+            "templates": templates,
+        });
+        let raw_content = serde_json::value::to_raw_value(&content)
+            .context("Failed to serialize card templates")?;
+
+        space
+            .send_state_event_raw(CARD_TEMPLATES_EVENT_TYPE, "", raw_content)
+            .await
+            .context("Failed to save card templates")?;
+
+        log!("✅ Card templates saved successfully to Matrix");
+        Ok(())
+    }
+
+    /// 用模板创建新卡片；`end_time` 不为 None 时覆盖模板的截止时间
+    pub async fn create_card_from_template(
+        &self,
+        space_id: &RoomId,
+        template: &crate::kanban::state::template::CardTemplate,
+        end_time: Option<u64>,
+    ) -> Result<crate::kanban::state::kanban_state::KanbanCard> {
+        use crate::kanban::state::kanban_state::ActivityType;
+
+        log!(
+            "📋 Creating card from template '{}' in space {}",
+            template.name,
+            space_id
+        );
+
+        let card_id = self.create_card(space_id, &template.title).await?;
+        let mut card = self.load_card(&card_id, space_id.to_owned()).await?;
+
+        template.apply_to(&mut card);
+        if end_time.is_some() {
+            card.end_time = end_time;
+        }
+        card.touch();
+        self.save_card_metadata(&card).await?;
+        if !card.todos.is_empty() {
+            self.save_card_todos(&card_id, &card.todos).await?;
+        }
+
+        if let Err(e) = self
+            .send_activity_in_list(
+                &card_id,
+                Some(space_id),
+                ActivityType::Created,
+                format!("从模板 '{}' 创建", template.name),
+                Some(serde_json::json!({ "template_id": template.id })),
+            )
+            .await
+        {
+            error!("❌ Failed to record template activity for {}: {e:?}", card_id);
+        }
+
+        log!("✅ Created card {} from template '{}'", card_id, template.name);
+        Ok(card)
+    }
+
+    /// 为周期卡片创建下一张，返回（服务器端清除了周期规则的原卡片，新卡片）
+    ///
+    /// 周期规则以服务器端的原卡片为准：规则从原卡片移到新卡片，保证同一周期只会生成一次。
+    /// 服务器端的原卡片已经没有周期规则时（已由其他客户端或之前的请求生成）不创建新卡片。
+    pub async fn spawn_next_occurrence(
+        &self,
+        card: &crate::kanban::state::kanban_state::KanbanCard,
+    ) -> Result<(
+        crate::kanban::state::kanban_state::KanbanCard,
+        Option<crate::kanban::state::kanban_state::KanbanCard>,
+    )> {
+        use crate::kanban::state::kanban_state::ActivityType;
+        use crate::kanban::state::template::next_occurrence;
+
+        // 先清除原卡片的周期规则，避免重复生成
+        let mut previous = self
+            .load_remote_card(&card.id, card.space_id.clone())
+            .await?;
+        let Some(rule) = previous.recurrence.take() else {
+            log!(
+                "🔁 Recurring card {} has no recurrence rule on the server, skipping",
+                card.id
+            );
+            return Ok((previous, None));
+        };
+        previous.touch();
+        self.save_card_metadata(&previous).await?;
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let end_time = rule.next_end_time(card.end_time, now);
+        let template = next_occurrence(&previous, &rule);
+        let next = self
+            .create_card_from_template(&card.space_id, &template, Some(end_time))
+            .await?;
+
+        if let Err(e) = self
+            .send_activity(
+                &card.id,
+                ActivityType::Created,
+                format!("已创建下一张周期卡片（{}）", rule.describe()),
+                Some(serde_json::json!({ "next_card": next.id })),
+            )
+            .await
+        {
+            error!("❌ Failed to record recurrence activity for {}: {e:?}", card.id);
+        }
+
+        log!(
+            "🔁 Spawned next occurrence {} of recurring card {}",
+            next.id,
+            card.id
+        );
+        Ok((previous, Some(next)))
+    }
+
+    /// 把待办事项转换为子卡片
+    ///
+    /// 子卡片与父卡片在同一列表（同一个 Space，带有 `m.space.parent`），
+    /// 继承待办的截止时间和负责人，并通过"关联"链接回父卡片。
+    pub async fn create_child_card(
+        &self,
+        parent: &crate::kanban::state::kanban_state::KanbanCard,
+        todo: &crate::kanban::state::kanban_state::TodoItem,
+    ) -> Result<crate::kanban::state::kanban_state::KanbanCard> {
+        use crate::kanban::state::dependency::{CardLink, CardLinkType};
+        use crate::kanban::state::kanban_state::ActivityType;
+
+        let card_id = self.create_card(&parent.space_id, &todo.text).await?;
+        let mut card = self.load_card(&card_id, parent.space_id.clone()).await?;
+
+        card.end_time = todo.due_time;
+        if let Some(assignee) = &todo.assignee {
+            match self.assign_card_member(&card_id, assignee).await {
+                Ok(()) => card.assignees = vec![assignee.clone()],
+                Err(e) => error!("❌ Failed to assign {} to child card {}: {e:?}", assignee, card_id),
+            }
+        }
+        card.links.push(CardLink {
+            link_type: CardLinkType::RelatesTo,
+            card_id: parent.id.clone(),
+        });
+        card.touch();
+        self.save_card_metadata(&card).await?;
+
+        if let Err(e) = self
+            .send_activity_in_list(
+                &card_id,
+                Some(&parent.space_id),
+                ActivityType::Created,
+                format!("由「{}」的待办转换而来", parent.title),
+                Some(serde_json::json!({ "parent_card": parent.id, "todo_id": todo.id })),
+            )
+            .await
+        {
+            error!("❌ Failed to record child card activity for {}: {e:?}", card_id);
+        }
+
+        log!("🧩 Created child card {} from todo {} of {}", card_id, todo.id, parent.id);
+        Ok(card)
+    }
 }
 
 impl MatrixKanbanAdapter {
@@ -1574,6 +3097,177 @@ impl MatrixKanbanAdapter {
             target_space_id
         );
 
+        let target_name = target_space
+            .topic()
+            .map(|topic| topic.trim_start_matches("[kanban-list]").trim().to_string())
+            .unwrap_or_else(|| target_space_id.to_string());
+        if let Err(e) = self
+            .send_activity_in_list(
+                card_id,
+                Some(target_space_id),
+                crate::kanban::state::kanban_state::ActivityType::Moved,
+                format!("移动到「{}」", target_name),
+                Some(serde_json::json!({ "from": source_space_id, "to": target_space_id })),
+            )
+            .await
+        {
+            error!("❌ Failed to record move activity for {}: {e:?}", card_id);
+        }
+
+        Ok(())
+    }
+
+    // ========== 归档与永久删除 ==========
+
+    /// 归档或恢复列表（`archived_at` 为 `None` 时恢复）
+    pub async fn set_list_archived(
+        &self,
+        space_id: &RoomId,
+        archived_at: Option<u64>,
+    ) -> Result<()> {
+        use crate::kanban::state::archived::LIST_ARCHIVE_EVENT_TYPE;
+
+        self.ensure_permission(
+            space_id,
+            crate::kanban::state::permissions::KanbanPermission::ManageLists,
+        )
+        .await?;
+
+        let space = self.client.get_room(space_id).context("Space not found")?;
+        let content = match archived_at {
+            Some(archived_at) => serde_json::json!({ "archived_at": archived_at }),
+            None => serde_json::json!({}),
+        };
+
+        log!(
+            "🗄️ Setting list {} archived_at to {:?}",
+            space_id,
+            archived_at
+        );
+        space
+            .send_state_event_raw(
+                LIST_ARCHIVE_EVENT_TYPE,
+                "",
+                serde_json::value::to_raw_value(&content)
+                    .context("Failed to serialize archive content")?,
+            )
+            .await
+            .context("Failed to save list archive state")?;
+
+        Ok(())
+    }
+
+    /// 永久删除卡片：解除与 Space 的父子关系、从备用列表和本地缓存中移除，并退出卡片 Room
+    pub async fn delete_card(&self, space_id: &RoomId, card_id: &RoomId) -> Result<()> {
+        self.ensure_permission(
+            space_id,
+            crate::kanban::state::permissions::KanbanPermission::Archive,
+        )
+        .await?;
+
+        self.remove_card_room(space_id, card_id).await
+    }
+
+    /// 永久删除列表：删除列表中的所有卡片，然后退出列表 Space
+    pub async fn delete_list(&self, space_id: &RoomId, card_ids: &[OwnedRoomId]) -> Result<()> {
+        self.ensure_permission(
+            space_id,
+            crate::kanban::state::permissions::KanbanPermission::ManageLists,
+        )
+        .await?;
+
+        log!(
+            "🗑️ delete_list: Deleting list {} with {} cards",
+            space_id,
+            card_ids.len()
+        );
+        for card_id in card_ids {
+            if let Err(e) = self.remove_card_room(space_id, card_id).await {
+                log!(
+                    "⚠️ Failed to delete card {} of list {}: {:?}",
+                    card_id,
+                    space_id,
+                    e
+                );
+            }
+        }
+
+        let space = self.client.get_room(space_id).context("Space not found")?;
+        space.leave().await.context("Failed to leave list space")?;
+        crate::kanban::local_cache::remove_space_from_cache(&space_id.to_owned());
+
+        log!("✅ Successfully deleted list {}", space_id);
+        Ok(())
+    }
+
+    /// 解除卡片与 Space 的关系并退出卡片 Room
+    async fn remove_card_room(&self, space_id: &RoomId, card_id: &RoomId) -> Result<()> {
+        log!(
+            "🗑️ remove_card_room: Deleting card {} from space {}",
+            card_id,
+            space_id
+        );
+
+        let empty_content = || {
+            serde_json::value::to_raw_value(&serde_json::json!({}))
+                .context("Failed to serialize empty content")
+        };
+
+        // 1. 删除 Space 的 m.space.child 事件和 m.kanban.cards 备用列表中的条目
+        if let Some(space) = self.client.get_room(space_id) {
+            if let Err(e) = space
+                .send_state_event_raw("m.space.child", card_id.as_str(), empty_content()?)
+                .await
+            {
+                log!("⚠️ Failed to remove m.space.child from space: {:?}", e);
+            }
+
+            match self.get_card_list_from_state(&space).await {
+                Ok(mut card_ids) if card_ids.iter().any(|id| id == card_id) => {
+                    card_ids.retain(|id| id != card_id);
+                    let cards_content = serde_json::json!({
+                        "card_ids": card_ids.iter().map(|id| id.as_str()).collect::<Vec<_>>()
+                    });
+                    if let Err(e) = space
+                        .send_state_event_raw(
+                            "m.kanban.cards",
+                            "",
+                            serde_json::value::to_raw_value(&cards_content)
+                                .context("Failed to serialize cards content")?,
+                        )
+                        .await
+                    {
+                        log!("⚠️ Failed to update space backup card list: {:?}", e);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    log!("⚠️ Failed to get space card list: {:?}", e);
+                }
+            }
+        }
+
+        // 2. 删除卡片 Room 的 m.space.parent 事件并退出 Room
+        if let Some(card_room) = self.client.get_room(card_id) {
+            if let Err(e) = card_room
+                .send_state_event_raw("m.space.parent", space_id.as_str(), empty_content()?)
+                .await
+            {
+                log!("⚠️ Failed to remove m.space.parent from card room: {:?}", e);
+            }
+            card_room
+                .leave()
+                .await
+                .context("Failed to leave card room")?;
+        }
+
+        // 3. 清理本地缓存
+        crate::kanban::local_cache::remove_card_from_cache(
+            &space_id.to_owned(),
+            &card_id.to_owned(),
+        );
+
+        log!("✅ Successfully deleted card {}", card_id);
         Ok(())
     }
 }
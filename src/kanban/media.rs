@@ -0,0 +1,29 @@
+//! 看板图片缓存
+//!
+//! 附件缩略图和卡片封面共用一个 [`MediaCache`]，只能在 UI 线程访问。
+//! 缩略图下载完成后会发出 UI 信号，显示图片的组件在 `Event::Signal` 时重绘即可。
+
+use std::cell::RefCell;
+
+use makepad_widgets::{Cx, ImageRef};
+use matrix_sdk::ruma::OwnedMxcUri;
+
+use crate::media_cache::{MediaCache, MediaCacheEntry};
+use crate::utils::{self, MEDIA_THUMBNAIL_FORMAT};
+
+thread_local! {
+    static KANBAN_MEDIA_CACHE: RefCell<MediaCache> = const { RefCell::new(MediaCache::new(None)) };
+}
+
+/// 把图片的缩略图显示到 `image` 中
+///
+/// 缩略图还在下载或下载失败时返回 `false`。
+pub fn load_thumbnail(cx: &mut Cx, image: &ImageRef, mxc_uri: &OwnedMxcUri) -> bool {
+    let (entry, _format) = KANBAN_MEDIA_CACHE.with_borrow_mut(|cache| {
+        cache.try_get_media_or_fetch(mxc_uri.clone(), MEDIA_THUMBNAIL_FORMAT.into())
+    });
+    match entry {
+        MediaCacheEntry::Loaded(data) => utils::load_png_or_jpg(image, cx, &data).is_ok(),
+        MediaCacheEntry::Requested | MediaCacheEntry::Failed(_) => false,
+    }
+}
@@ -8,15 +8,26 @@ pub mod api;
 pub mod state;
 pub mod drag_drop;
 pub mod matrix_adapter;
-pub mod models;
 pub mod components;
 pub mod local_cache;
+pub mod json_store;
+pub mod saved_filters;
+pub mod reminders;
+pub mod outbox;
+pub mod import_export;
+pub mod analytics;
+pub mod media;
+pub mod chat_commands;
+pub mod markdown;
+#[cfg(test)]
+pub(crate) mod test_util;
 
 // Re-export main types for convenience
-// 使用简化的数据模型
-pub use state::kanban_state::{KanbanList, KanbanCard, KanbanAppState};
+// 统一的数据模型，存储格式见 state::schema
+pub use state::kanban_state::{KanbanBoard, KanbanList, KanbanCard, KanbanAppState};
 pub use state::kanban_actions::KanbanActions;
 pub use matrix_adapter::MatrixKanbanAdapter;
+pub use api::KanbanApi;
 
 pub fn live_design(cx: &mut Cx) {
     components::live_design(cx);
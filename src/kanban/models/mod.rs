@@ -1,6 +0,0 @@
-// 数据模型层
-pub mod dto;
-pub mod state;
-
-pub use dto::*;
-pub use state::*;
@@ -0,0 +1,375 @@
+//! 三方合并：基准（入队时的服务器状态）/ 本地（队列中的修改）/ 远端（发送前的服务器状态）
+//!
+//! 规则：本地相对基准改动过的字段以本地为准，其余字段采用远端的值，
+//! 这样队友在离线期间对其他字段的修改不会被覆盖。
+
+use std::collections::BTreeMap;
+
+use crate::kanban::state::kanban_state::{KanbanCard, TodoItem};
+
+/// 本地改过则取本地，否则取远端
+fn pick<T: PartialEq + Clone>(base: &T, local: &T, remote: &T) -> T {
+    if local != base {
+        local.clone()
+    } else {
+        remote.clone()
+    }
+}
+
+/// 合并集合类字段（如标签）：远端内容 + 本地新增 - 本地删除
+pub fn merge_set<T: PartialEq + Clone>(base: &[T], local: &[T], remote: &[T]) -> Vec<T> {
+    let mut merged: Vec<T> = remote
+        .iter()
+        .filter(|item| !(base.contains(item) && !local.contains(item)))
+        .cloned()
+        .collect();
+
+    for item in local {
+        if !base.contains(item) && !merged.contains(item) {
+            merged.push(item.clone());
+        }
+    }
+    merged
+}
+
+/// 合并累加的计数（如每个成员的工时）：远端的值 + 本地相对基准的增量
+pub fn merge_counters<K: Ord + Clone>(
+    base: &BTreeMap<K, u64>,
+    local: &BTreeMap<K, u64>,
+    remote: &BTreeMap<K, u64>,
+) -> BTreeMap<K, u64> {
+    let mut merged = remote.clone();
+    for (key, local_value) in local {
+        let base_value = base.get(key).copied().unwrap_or_default();
+        if *local_value != base_value {
+            let value = merged.entry(key.clone()).or_default();
+            *value = (*value + local_value).saturating_sub(base_value);
+        }
+    }
+    merged
+}
+
+/// 合并卡片元数据
+///
+/// Todo 单独保存在 `m.kanban.card.todos` 中，这里始终保留远端的 Todo。
+/// 没有基准时（例如卡片从未成功加载过）以本地为准。
+pub fn merge_card(base: Option<&KanbanCard>, local: &KanbanCard, remote: &KanbanCard) -> KanbanCard {
+    let Some(base) = base else {
+        let mut merged = local.clone();
+        merged.todos = remote.todos.clone();
+        return merged;
+    };
+
+    KanbanCard {
+        id: local.id.clone(),
+        title: pick(&base.title, &local.title, &remote.title),
+        description: pick(&base.description, &local.description, &remote.description),
+        // HTML 跟随描述原文，避免原文和 HTML 来自不同的一方
+        description_html: if local.description != base.description {
+            local.description_html.clone()
+        } else {
+            remote.description_html.clone()
+        },
+        space_id: local.space_id.clone(),
+        position: pick(&base.position, &local.position, &remote.position),
+        rank: pick(&base.rank, &local.rank, &remote.rank),
+        status: pick(&base.status, &local.status, &remote.status),
+        tags: merge_set(&base.tags, &local.tags, &remote.tags),
+        end_time: pick(&base.end_time, &local.end_time, &remote.end_time),
+        assignees: merge_set(&base.assignees, &local.assignees, &remote.assignees),
+        recurrence: pick(&base.recurrence, &local.recurrence, &remote.recurrence),
+        links: merge_set(&base.links, &local.links, &remote.links),
+        cover: pick(&base.cover, &local.cover, &remote.cover),
+        archived_at: pick(&base.archived_at, &local.archived_at, &remote.archived_at),
+        estimate: pick(&base.estimate, &local.estimate, &remote.estimate),
+        logged_time: merge_counters(&base.logged_time, &local.logged_time, &remote.logged_time),
+        timers: merge_set(&base.timers, &local.timers, &remote.timers),
+        todos: remote.todos.clone(),
+        created_at: local.created_at,
+        updated_at: local.updated_at.max(remote.updated_at),
+    }
+}
+
+/// 按 Todo ID 合并待办列表
+///
+/// - 本地删除的条目被移除
+/// - 本地修改的条目以本地为准
+/// - 双方新增的条目都保留（远端顺序在前）
+/// - 远端已删除、本地修改过的条目视为已删除
+pub fn merge_todos(
+    base: Option<&[TodoItem]>,
+    local: &[TodoItem],
+    remote: &[TodoItem],
+) -> Vec<TodoItem> {
+    let Some(base) = base else {
+        return local.to_vec();
+    };
+
+    let find = |items: &[TodoItem], id: &str| items.iter().find(|t| t.id == id).cloned();
+
+    let mut merged = Vec::with_capacity(remote.len().max(local.len()));
+    for remote_todo in remote {
+        match (find(base, &remote_todo.id), find(local, &remote_todo.id)) {
+            (Some(_), None) => continue,
+            (Some(base_todo), Some(local_todo)) if local_todo != base_todo => {
+                merged.push(local_todo)
+            }
+            _ => merged.push(remote_todo.clone()),
+        }
+    }
+
+    for local_todo in local {
+        if find(base, &local_todo.id).is_none() && find(&merged, &local_todo.id).is_none() {
+            merged.push(local_todo.clone());
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kanban::test_util::{room_id, user};
+
+    fn card() -> KanbanCard {
+        let id = room_id("card");
+        let space_id = room_id("list");
+        let mut card = KanbanCard::new(id, "标题".to_string(), space_id);
+        card.description = Some("描述".to_string());
+        card.tags = vec!["a".to_string(), "b".to_string()];
+        card
+    }
+
+    fn todo(id: &str, text: &str) -> TodoItem {
+        let mut todo = TodoItem::new(text.to_string());
+        todo.id = id.to_string();
+        todo
+    }
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn local_only_edit_wins() {
+        let base = card();
+        let mut local = base.clone();
+        local.title = "本地标题".to_string();
+
+        let merged = merge_card(Some(&base), &local, &base);
+        assert_eq!(merged.title, "本地标题");
+        assert_eq!(merged.description, base.description);
+    }
+
+    #[test]
+    fn remote_only_edit_is_kept() {
+        let base = card();
+        let mut remote = base.clone();
+        remote.description = Some("远端描述".to_string());
+        remote.description_html = Some("<p>远端描述</p>".to_string());
+        remote.end_time = Some(1_704_067_200);
+
+        let merged = merge_card(Some(&base), &base, &remote);
+        assert_eq!(merged.description.as_deref(), Some("远端描述"));
+        assert_eq!(merged.description_html.as_deref(), Some("<p>远端描述</p>"));
+        assert_eq!(merged.end_time, Some(1_704_067_200));
+    }
+
+    #[test]
+    fn edits_to_different_fields_are_combined() {
+        let base = card();
+        let mut local = base.clone();
+        local.title = "本地标题".to_string();
+        let mut remote = base.clone();
+        remote.end_time = Some(1_704_067_200);
+
+        let merged = merge_card(Some(&base), &local, &remote);
+        assert_eq!(merged.title, "本地标题");
+        assert_eq!(merged.end_time, Some(1_704_067_200));
+    }
+
+    #[test]
+    fn local_wins_when_both_sides_edit_the_same_field() {
+        let base = card();
+        let mut local = base.clone();
+        local.description = Some("本地描述".to_string());
+        local.description_html = None;
+        let mut remote = base.clone();
+        remote.description = Some("远端描述".to_string());
+        remote.description_html = Some("<p>远端描述</p>".to_string());
+
+        let merged = merge_card(Some(&base), &local, &remote);
+        assert_eq!(merged.description.as_deref(), Some("本地描述"));
+        // HTML 跟随本地的描述原文
+        assert_eq!(merged.description_html, None);
+    }
+
+    #[test]
+    fn card_todos_always_come_from_remote() {
+        let base = card();
+        let mut local = base.clone();
+        local.todos = vec![todo("t1", "本地")];
+        let mut remote = base.clone();
+        remote.todos = vec![todo("t2", "远端")];
+
+        assert_eq!(merge_card(Some(&base), &local, &remote).todos, remote.todos);
+        assert_eq!(merge_card(None, &local, &remote).todos, remote.todos);
+    }
+
+    #[test]
+    fn missing_base_prefers_local() {
+        let base = card();
+        let mut local = base.clone();
+        local.title = "本地标题".to_string();
+        let mut remote = base.clone();
+        remote.end_time = Some(1_704_067_200);
+
+        let merged = merge_card(None, &local, &remote);
+        assert_eq!(merged.title, "本地标题");
+        assert_eq!(merged.end_time, None);
+    }
+
+    #[test]
+    fn set_keeps_additions_from_both_sides() {
+        let base = strings(&["a"]);
+        let local = strings(&["a", "local"]);
+        let remote = strings(&["a", "remote"]);
+
+        assert_eq!(
+            merge_set(&base, &local, &remote),
+            strings(&["a", "remote", "local"])
+        );
+    }
+
+    #[test]
+    fn set_applies_removals_from_both_sides() {
+        let base = strings(&["a", "b", "c"]);
+        let local = strings(&["a", "c"]);
+        let remote = strings(&["a", "b"]);
+
+        assert_eq!(merge_set(&base, &local, &remote), strings(&["a"]));
+    }
+
+    #[test]
+    fn set_does_not_duplicate_items_added_on_both_sides() {
+        let base = strings(&[]);
+        let local = strings(&["x"]);
+        let remote = strings(&["x"]);
+
+        assert_eq!(merge_set(&base, &local, &remote), strings(&["x"]));
+    }
+
+    #[test]
+    fn card_merges_assignee_sets() {
+        let mut base = card();
+        base.assignees = vec![user("alice"), user("bob")];
+        let mut local = base.clone();
+        local.assignees = vec![user("alice"), user("carol")];
+        let mut remote = base.clone();
+        remote.assignees = vec![user("alice"), user("bob"), user("dave")];
+
+        let merged = merge_card(Some(&base), &local, &remote);
+        assert_eq!(
+            merged.assignees,
+            vec![user("alice"), user("dave"), user("carol")]
+        );
+    }
+
+    #[test]
+    fn todos_local_only_edits() {
+        let base = vec![todo("t1", "一"), todo("t2", "二")];
+        let mut local = base.clone();
+        local[0].completed = true;
+        local.retain(|t| t.id != "t2");
+        local.push(todo("t3", "三"));
+
+        let merged = merge_todos(Some(&base), &local, &base);
+        assert_eq!(merged, local);
+    }
+
+    #[test]
+    fn todos_remote_only_edits() {
+        let base = vec![todo("t1", "一"), todo("t2", "二")];
+        let mut remote = base.clone();
+        remote[1].text = "远端二".to_string();
+        remote.push(todo("t3", "三"));
+
+        assert_eq!(merge_todos(Some(&base), &base, &remote), remote);
+    }
+
+    #[test]
+    fn todos_both_sides_edit() {
+        let base = vec![todo("t1", "一"), todo("t2", "二")];
+        let mut local = base.clone();
+        local[0].text = "本地一".to_string();
+        local[1].text = "本地二".to_string();
+        local.push(todo("local", "本地新增"));
+        let mut remote = base.clone();
+        remote[0].text = "远端一".to_string();
+        remote.remove(1);
+        remote.push(todo("remote", "远端新增"));
+
+        let merged = merge_todos(Some(&base), &local, &remote);
+        let texts: Vec<&str> = merged.iter().map(|t| t.text.as_str()).collect();
+        // 同一条目以本地为准；远端删除的条目即使本地修改过也不会复活
+        assert_eq!(texts, vec!["本地一", "远端新增", "本地新增"]);
+    }
+
+    #[test]
+    fn todos_without_base_prefer_local() {
+        let local = vec![todo("t1", "本地")];
+        let remote = vec![todo("t2", "远端")];
+
+        assert_eq!(merge_todos(None, &local, &remote), local);
+    }
+
+    fn counters(entries: &[(&str, u64)]) -> BTreeMap<String, u64> {
+        entries.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn counters_add_local_increments_to_remote() {
+        let base = counters(&[("alice", 100)]);
+        let local = counters(&[("alice", 160), ("bob", 30)]);
+        let remote = counters(&[("alice", 200), ("carol", 50)]);
+
+        assert_eq!(
+            merge_counters(&base, &local, &remote),
+            counters(&[("alice", 260), ("bob", 30), ("carol", 50)])
+        );
+    }
+
+    #[test]
+    fn unchanged_counters_keep_remote_values() {
+        let base = counters(&[("alice", 100)]);
+        let remote = counters(&[("alice", 300)]);
+
+        assert_eq!(merge_counters(&base, &base, &remote), remote);
+    }
+
+    #[test]
+    fn counter_decrements_never_underflow() {
+        let base = counters(&[("alice", 100)]);
+        let local = counters(&[("alice", 40)]);
+        let remote = counters(&[("alice", 20)]);
+
+        assert_eq!(
+            merge_counters(&base, &local, &remote),
+            counters(&[("alice", 0)])
+        );
+    }
+
+    #[test]
+    fn card_merges_logged_time() {
+        let mut base = card();
+        base.logged_time = BTreeMap::from([(user("alice"), 600)]);
+        let mut local = base.clone();
+        *local.logged_time.get_mut(&user("alice")).unwrap() += 300;
+        let mut remote = base.clone();
+        remote.logged_time.insert(user("bob"), 120);
+
+        let merged = merge_card(Some(&base), &local, &remote);
+        assert_eq!(merged.logged_time[&user("alice")], 900);
+        assert_eq!(merged.logged_time[&user("bob")], 120);
+    }
+}
@@ -0,0 +1,225 @@
+//! 离线操作队列（Outbox）
+//!
+//! 看板的所有写操作先追加到持久化队列，再由 worker 按顺序发送到 homeserver：
+//! - UI 端先在 `KanbanAppState` 上乐观更新，再调用 [`submit`] 入队
+//! - 队列保存在 `kanban_outbox.json`，重启后会继续发送
+//! - 发送失败按指数退避重试，后台定时任务会在 homeserver 可达后继续发送；
+//!   等待重试的操作只阻塞同一卡片或列表后面的操作
+//! - 发送前与服务器端当前状态做三方合并（见 [`merge`]），不会覆盖队友的并发修改
+
+pub mod merge;
+pub mod replay;
+mod store;
+
+use makepad_widgets::Cx;
+use matrix_sdk::ruma::{OwnedRoomId, OwnedUserId};
+use serde::{Deserialize, Serialize};
+
+use crate::kanban::KanbanActions;
+use crate::kanban::state::kanban_state::{KanbanCard, KanbanList, TodoItem};
+use crate::sliding_sync::{MatrixRequest, submit_async_request};
+
+pub use replay::{flush, start_retry_loop};
+pub use store::{last_error, pending_count};
+
+/// 队列中的一次看板写操作
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum KanbanMutation {
+    /// 保存卡片元数据（状态、标签、截止时间、描述等）
+    SaveCardMetadata { card: KanbanCard },
+
+    /// 更新卡片标题（Room 名称 + 元数据）
+    UpdateCardTitle { card: KanbanCard },
+
+    /// 保存卡片的完整 Todo 列表
+    SaveCardTodos {
+        card_id: OwnedRoomId,
+        space_id: OwnedRoomId,
+        todos: Vec<TodoItem>,
+    },
+
+    /// 移动卡片到其他列表
+    MoveCard {
+        card_id: OwnedRoomId,
+        source_space_id: OwnedRoomId,
+        target_space_id: OwnedRoomId,
+        card: KanbanCard,
+    },
+
+    /// 更新列表名称
+    UpdateListName { list_id: OwnedRoomId, name: String },
+
+    /// 把用户设为卡片负责人（需要时邀请到卡片 Room）
+    AssignCardMember {
+        card_id: OwnedRoomId,
+        user_id: OwnedUserId,
+    },
+
+    /// 取消用户的卡片负责人身份
+    UnassignCardMember {
+        card_id: OwnedRoomId,
+        user_id: OwnedUserId,
+    },
+}
+
+impl KanbanMutation {
+    /// 操作涉及的卡片 ID
+    pub fn card_id(&self) -> Option<&OwnedRoomId> {
+        match self {
+            KanbanMutation::SaveCardMetadata { card } | KanbanMutation::UpdateCardTitle { card } => {
+                Some(&card.id)
+            }
+            KanbanMutation::SaveCardTodos { card_id, .. }
+            | KanbanMutation::MoveCard { card_id, .. }
+            | KanbanMutation::AssignCardMember { card_id, .. }
+            | KanbanMutation::UnassignCardMember { card_id, .. } => Some(card_id),
+            KanbanMutation::UpdateListName { .. } => None,
+        }
+    }
+
+    /// 操作的对象（卡片或列表）；同一对象的操作按入队顺序发送
+    pub fn object_id(&self) -> &OwnedRoomId {
+        match self {
+            KanbanMutation::UpdateListName { list_id, .. } => list_id,
+            KanbanMutation::SaveCardMetadata { card } | KanbanMutation::UpdateCardTitle { card } => {
+                &card.id
+            }
+            KanbanMutation::SaveCardTodos { card_id, .. }
+            | KanbanMutation::MoveCard { card_id, .. }
+            | KanbanMutation::AssignCardMember { card_id, .. }
+            | KanbanMutation::UnassignCardMember { card_id, .. } => card_id,
+        }
+    }
+
+    /// 简短描述（用于失败提示）
+    pub fn describe(&self) -> String {
+        match self {
+            KanbanMutation::SaveCardMetadata { card } => format!("保存卡片 '{}'", card.title),
+            KanbanMutation::UpdateCardTitle { card } => format!("重命名卡片 '{}'", card.title),
+            KanbanMutation::SaveCardTodos { todos, .. } => format!("保存 {} 条待办", todos.len()),
+            KanbanMutation::MoveCard { card, .. } => format!("移动卡片 '{}'", card.title),
+            KanbanMutation::UpdateListName { name, .. } => format!("重命名列表 '{}'", name),
+            KanbanMutation::AssignCardMember { user_id, .. } => format!("分配负责人 {}", user_id),
+            KanbanMutation::UnassignCardMember { user_id, .. } => {
+                format!("取消负责人 {}", user_id)
+            }
+        }
+    }
+
+    /// 是否可以与队尾的同类操作合并（只保留最新的一次）
+    fn coalesces_with(&self, other: &KanbanMutation) -> bool {
+        match (self, other) {
+            (
+                KanbanMutation::SaveCardMetadata { card: a },
+                KanbanMutation::SaveCardMetadata { card: b },
+            ) => a.id == b.id,
+            (
+                KanbanMutation::UpdateCardTitle { card: a },
+                KanbanMutation::UpdateCardTitle { card: b },
+            ) => a.id == b.id,
+            (
+                KanbanMutation::SaveCardTodos { card_id: a, .. },
+                KanbanMutation::SaveCardTodos { card_id: b, .. },
+            ) => a == b,
+            (
+                KanbanMutation::UpdateListName { list_id: a, .. },
+                KanbanMutation::UpdateListName { list_id: b, .. },
+            ) => a == b,
+            _ => false,
+        }
+    }
+
+    /// 把尚未同步的本地修改叠加到服务器端的卡片状态上
+    fn overlay(&self, remote: KanbanCard, base: Option<&KanbanCard>) -> KanbanCard {
+        match self {
+            KanbanMutation::SaveCardMetadata { card } | KanbanMutation::UpdateCardTitle { card } => {
+                merge::merge_card(base, card, &remote)
+            }
+            KanbanMutation::SaveCardTodos { todos, .. } => {
+                let mut merged = remote;
+                merged.todos =
+                    merge::merge_todos(base.map(|b| b.todos.as_slice()), todos, &merged.todos);
+                merged
+            }
+            KanbanMutation::MoveCard {
+                target_space_id,
+                card,
+                ..
+            } => {
+                let mut merged = remote;
+                merged.space_id = target_space_id.clone();
+                merged.status = card.status.clone();
+                merged.position = card.position;
+                merged.rank = card.rank.clone();
+                merged
+            }
+            // 负责人列表随卡片元数据保存，见 `SaveCardMetadata`
+            KanbanMutation::UpdateListName { .. }
+            | KanbanMutation::AssignCardMember { .. }
+            | KanbanMutation::UnassignCardMember { .. } => remote,
+        }
+    }
+}
+
+/// 队列条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    /// 条目 ID
+    pub id: String,
+
+    /// 写操作
+    pub mutation: KanbanMutation,
+
+    /// 入队时最近一次确认的服务器端卡片状态（三方合并的基准）
+    #[serde(default)]
+    pub base: Option<KanbanCard>,
+
+    /// 所属批量操作的 ID（发送成功或被放弃时报告 `BulkCardProcessed`）
+    #[serde(default)]
+    pub bulk_id: Option<String>,
+
+    /// 入队时间（Unix timestamp 秒）
+    pub enqueued_at: u64,
+
+    /// 已尝试次数
+    #[serde(default)]
+    pub attempts: u32,
+
+    /// 下次允许重试的时间（Unix timestamp 秒）
+    #[serde(default)]
+    pub next_attempt_at: u64,
+
+    /// 最近一次失败的错误信息
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+/// 将写操作加入离线队列，并通知 worker 尝试发送
+pub fn submit(mutation: KanbanMutation) {
+    enqueue(mutation, None);
+}
+
+/// 将批量操作 `bulk_id` 中一张卡片的写操作加入离线队列，发送结果作为该操作的进度报告
+pub fn submit_bulk(bulk_id: &str, mutation: KanbanMutation) {
+    enqueue(mutation, Some(bulk_id.to_string()));
+}
+
+fn enqueue(mutation: KanbanMutation, bulk_id: Option<String>) {
+    store::enqueue(mutation, bulk_id);
+    Cx::post_action(KanbanActions::SyncStatus {
+        pending: store::pending_count(),
+        error: store::last_error(),
+    });
+    submit_async_request(MatrixRequest::FlushKanbanOutbox);
+}
+
+/// 服务器端卡片已加载：记录为合并基准，并叠加仍在队列中的本地修改
+pub fn reconcile_loaded_card(remote: KanbanCard) -> KanbanCard {
+    store::reconcile_card(remote)
+}
+
+/// 服务器端列表已加载：按队列中尚未同步的移动操作调整卡片归属
+pub fn reconcile_loaded_list(list: KanbanList) -> KanbanList {
+    store::reconcile_list(list)
+}
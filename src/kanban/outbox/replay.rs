@@ -0,0 +1,291 @@
+//! 离线队列的发送（重放）逻辑，运行在 Matrix worker 的 tokio runtime 中
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{Context, Result};
+use makepad_widgets::{Cx, SignalToUI, log, error};
+use matrix_sdk::Client;
+use matrix_sdk::ruma::{OwnedRoomId, RoomId};
+
+use super::{KanbanMutation, OutboxEntry, merge, store};
+use crate::kanban::KanbanActions;
+use crate::kanban::api::KanbanApi;
+use crate::kanban::data::repositories::{CardRepository, ListRepository, MemberRepository};
+use crate::kanban::state::kanban_state::KanbanCard;
+use crate::sliding_sync::{MatrixRequest, submit_async_request};
+
+/// 后台重试间隔（秒）
+const RETRY_INTERVAL_SECS: u64 = 15;
+
+/// 同一时间只允许一个发送任务
+static FLUSHING: AtomicBool = AtomicBool::new(false);
+
+static RETRY_LOOP_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// 按顺序发送队列中的条目，直到没有可以发送的条目
+///
+/// 失败的条目等待退避时间后由重试任务再次触发；在此之前同一对象后面的操作也不会发送，
+/// 以保证它们的顺序，其他对象的操作继续发送。
+pub async fn flush(client: Client) {
+    if FLUSHING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let api = KanbanApi::new(client);
+
+    while let Some(entry) = store::begin_next() {
+        log!(
+            "📮 Sending outbox entry {} (attempt {}): {}",
+            entry.id,
+            entry.attempts + 1,
+            entry.mutation.describe()
+        );
+
+        match execute(api.repository(), &entry).await {
+            Ok(synced_card) => {
+                store::complete(&entry.id);
+                log!("✅ Outbox entry {} synced", entry.id);
+                report_bulk_progress(&entry, None);
+                if let Some(card) = synced_card {
+                    Cx::post_action(KanbanActions::CardLoaded(card));
+                }
+            }
+            Err(e) => {
+                error!("❌ Outbox entry {} failed: {e:?}", entry.id);
+                let Some(dropped) = store::fail(&entry.id, format!("{e}")) else {
+                    continue;
+                };
+
+                // 超过最大重试次数：放弃该条目，继续发送后面的操作
+                if let KanbanMutation::MoveCard {
+                    card_id,
+                    source_space_id,
+                    card,
+                    ..
+                } = &dropped.mutation
+                {
+                    Cx::post_action(KanbanActions::MoveCardFailed {
+                        card_id: card_id.clone(),
+                        original_space_id: source_space_id.clone(),
+                        original_rank: dropped
+                            .base
+                            .as_ref()
+                            .map_or_else(|| card.rank.clone(), |b| b.rank.clone()),
+                        error: format!("{e}"),
+                    });
+                }
+                match &dropped.mutation {
+                    KanbanMutation::AssignCardMember { card_id, user_id } => {
+                        Cx::post_action(KanbanActions::AssignCardFailed {
+                            card_id: card_id.clone(),
+                            user_id: user_id.clone(),
+                            error: format!("{e}"),
+                        });
+                    }
+                    KanbanMutation::UnassignCardMember { card_id, .. } => {
+                        Cx::post_action(KanbanActions::AssigneeUpdateFailed {
+                            card_id: card_id.clone(),
+                            error: format!("{e}"),
+                        });
+                    }
+                    _ => {}
+                }
+                report_bulk_progress(&dropped, Some(format!("{e}")));
+                Cx::post_action(KanbanActions::SyncEntryDropped {
+                    description: dropped.mutation.describe(),
+                    error: format!("{e}"),
+                });
+                reload_dropped(api.repository(), &dropped).await;
+            }
+        }
+    }
+
+    FLUSHING.store(false, Ordering::SeqCst);
+
+    Cx::post_action(KanbanActions::SyncStatus {
+        pending: store::pending_count(),
+        error: store::last_error(),
+    });
+    SignalToUI::set_ui_signal();
+}
+
+/// 启动后台重试任务（只会启动一次）
+pub fn start_retry_loop() {
+    if RETRY_LOOP_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(tokio::time::Duration::from_secs(RETRY_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            if store::pending_count() > 0 {
+                submit_async_request(MatrixRequest::FlushKanbanOutbox);
+            }
+        }
+    });
+}
+
+/// 批量操作的条目已发送成功或被放弃：报告该卡片的同步结果（被放弃的算作失败）
+fn report_bulk_progress(entry: &OutboxEntry, error: Option<String>) {
+    let Some(bulk_id) = &entry.bulk_id else {
+        return;
+    };
+    if let Some(card_id) = entry.mutation.card_id() {
+        Cx::post_action(KanbanActions::BulkCardProcessed {
+            bulk_id: bulk_id.clone(),
+            card_id: card_id.clone(),
+            error,
+        });
+    }
+}
+
+/// 条目被放弃后从服务器重新加载它修改的卡片或列表，丢弃 UI 中乐观更新的内容
+///
+/// 队列中同一对象后面的操作仍会在加载时叠加上去（见 [`super::reconcile_loaded_card`]）。
+async fn reload_dropped<R>(repository: &R, entry: &OutboxEntry)
+where
+    R: CardRepository + ListRepository + Sync,
+{
+    let result = match &entry.mutation {
+        KanbanMutation::SaveCardMetadata { card } | KanbanMutation::UpdateCardTitle { card } => {
+            reload_card(repository, &card.id, &card.space_id).await
+        }
+        KanbanMutation::SaveCardTodos {
+            card_id, space_id, ..
+        } => reload_card(repository, card_id, space_id).await,
+        // 移动失败时卡片仍在原来的列表中
+        KanbanMutation::MoveCard {
+            card_id,
+            source_space_id,
+            ..
+        } => reload_card(repository, card_id, source_space_id).await,
+        KanbanMutation::UpdateListName { list_id, .. } => repository
+            .load_lists()
+            .await
+            .and_then(|lists| {
+                lists
+                    .into_iter()
+                    .find(|list| &list.id == list_id)
+                    .context("List not found")
+            })
+            .map(|list| Cx::post_action(KanbanActions::ListLoaded(list))),
+        // 负责人列表已由 `AssignCardFailed` 恢复
+        KanbanMutation::AssignCardMember { .. } | KanbanMutation::UnassignCardMember { .. } => {
+            Ok(())
+        }
+    };
+    if let Err(e) = result {
+        error!(
+            "❌ Failed to reload {} after dropping outbox entry {}: {e:?}",
+            entry.mutation.object_id(),
+            entry.id
+        );
+    }
+}
+
+async fn reload_card<R>(repository: &R, card_id: &RoomId, list_id: &OwnedRoomId) -> Result<()>
+where
+    R: CardRepository + Sync,
+{
+    let card = repository.load_card(card_id, list_id.clone()).await?;
+    Cx::post_action(KanbanActions::CardLoaded(card));
+    Ok(())
+}
+
+/// 执行单个条目；返回同步后的卡片（用于刷新 UI）
+async fn execute<R>(repository: &R, entry: &OutboxEntry) -> Result<Option<KanbanCard>>
+where
+    R: CardRepository + ListRepository + MemberRepository + Sync,
+{
+    match &entry.mutation {
+        KanbanMutation::SaveCardMetadata { card } => {
+            let merged = merge_with_remote(repository, card, entry.base.as_ref()).await?;
+            repository.save_card(&merged).await?;
+            Ok(Some(merged))
+        }
+
+        KanbanMutation::UpdateCardTitle { card } => {
+            let merged = merge_with_remote(repository, card, entry.base.as_ref()).await?;
+            repository.rename_card(&merged.id, &merged.title).await?;
+            repository.save_card(&merged).await?;
+            Ok(Some(merged))
+        }
+
+        KanbanMutation::SaveCardTodos {
+            card_id,
+            space_id,
+            todos,
+        } => {
+            let mut remote = repository
+                .load_remote_card(card_id, space_id.clone())
+                .await?;
+            let merged_todos = merge::merge_todos(
+                entry.base.as_ref().map(|b| b.todos.as_slice()),
+                todos,
+                &remote.todos,
+            );
+            repository.save_todos(card_id, &merged_todos).await?;
+            remote.todos = merged_todos;
+            Ok(Some(remote))
+        }
+
+        KanbanMutation::MoveCard {
+            card_id,
+            source_space_id,
+            target_space_id,
+            card,
+        } => {
+            repository.move_card(card, source_space_id).await?;
+            Ok(repository
+                .load_card(card_id, target_space_id.clone())
+                .await
+                .ok())
+        }
+
+        KanbanMutation::UpdateListName { list_id, name } => {
+            repository.rename_list(list_id, name).await?;
+            Ok(None)
+        }
+
+        KanbanMutation::AssignCardMember { card_id, user_id } => {
+            repository.assign_member(card_id, user_id).await?;
+            // 被邀请的用户出现在成员列表中
+            if let Ok(members) = repository.load_members(card_id).await {
+                Cx::post_action(KanbanActions::CardMembersLoaded {
+                    card_id: card_id.clone(),
+                    members,
+                });
+            }
+            Ok(None)
+        }
+
+        KanbanMutation::UnassignCardMember { card_id, user_id } => {
+            repository.unassign_member(card_id, user_id).await?;
+            Ok(None)
+        }
+    }
+}
+
+/// 拉取服务器端最新状态并与本地修改做三方合并
+async fn merge_with_remote<R>(
+    repository: &R,
+    local: &KanbanCard,
+    base: Option<&KanbanCard>,
+) -> Result<KanbanCard>
+where
+    R: CardRepository + Sync,
+{
+    let remote = repository
+        .load_remote_card(&local.id, local.space_id.clone())
+        .await?;
+
+    if base.is_some_and(|b| b.updated_at != remote.updated_at) {
+        log!(
+            "🔀 Card {} was changed remotely while offline, merging",
+            local.id
+        );
+    }
+    Ok(merge::merge_card(base, local, &remote))
+}
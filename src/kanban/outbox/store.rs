@@ -0,0 +1,223 @@
+//! 离线队列的持久化存储
+//!
+//! 保存在 `app_data_dir` 下的 `kanban_outbox.json`（见 [`JsonFileStore`]），
+//! 每次修改后写回。
+
+use std::collections::HashMap;
+
+use makepad_widgets::log;
+use matrix_sdk::ruma::OwnedRoomId;
+use serde::{Deserialize, Serialize};
+
+use super::{KanbanMutation, OutboxEntry};
+use crate::kanban::json_store::JsonFileStore;
+use crate::kanban::state::kanban_state::{KanbanCard, KanbanList};
+
+/// 超过该次数仍失败的条目会被放弃
+pub const MAX_ATTEMPTS: u32 = 8;
+
+/// 退避上限（秒）
+const MAX_BACKOFF_SECS: u64 = 300;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct OutboxData {
+    /// 按入队顺序排列的待发送条目
+    entries: Vec<OutboxEntry>,
+
+    /// Card ID -> 最近一次从服务器加载到的状态（只在内存中保存，入队时复制到条目里）
+    #[serde(skip)]
+    remote_cards: HashMap<OwnedRoomId, KanbanCard>,
+
+    /// 正在发送的条目 ID，不会被合并
+    #[serde(skip)]
+    in_flight: Option<String>,
+}
+
+static OUTBOX: JsonFileStore<OutboxData> = JsonFileStore::new("kanban_outbox.json");
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// 入队；若队尾是同一对象的同类操作、属于同一批量操作（或都不属于批量操作）且未在发送中，
+/// 则直接替换为最新内容
+pub(super) fn enqueue(mutation: KanbanMutation, bulk_id: Option<String>) {
+    OUTBOX.with(|data| {
+        if let Some(last) = data.entries.last_mut() {
+            if data.in_flight.as_ref() != Some(&last.id)
+                && last.bulk_id == bulk_id
+                && mutation.coalesces_with(&last.mutation)
+            {
+                log!("📮 Coalescing outbox entry {}: {}", last.id, mutation.describe());
+                last.mutation = mutation;
+                OUTBOX.save(data);
+                return;
+            }
+        }
+
+        let base = mutation
+            .card_id()
+            .and_then(|card_id| data.remote_cards.get(card_id))
+            .cloned();
+        let now = now_secs();
+        let entry = OutboxEntry {
+            id: format!("op_{}_{}", now, &uuid::Uuid::new_v4().to_string()[..8]),
+            mutation,
+            base,
+            bulk_id,
+            enqueued_at: now,
+            attempts: 0,
+            next_attempt_at: 0,
+            last_error: None,
+        };
+        log!("📮 Enqueued outbox entry {}: {}", entry.id, entry.mutation.describe());
+        data.entries.push(entry);
+        OUTBOX.save(data);
+    })
+}
+
+/// 取出下一个可以发送的条目（见 [`next_ready_index`]），没有则返回 None
+pub(super) fn begin_next() -> Option<OutboxEntry> {
+    OUTBOX.with(|data| {
+        let index = next_ready_index(&data.entries, now_secs())?;
+        let entry = data.entries[index].clone();
+        data.in_flight = Some(entry.id.clone());
+        Some(entry)
+    })
+}
+
+/// 下一个可以发送的条目：已到重试时间，且前面没有同一对象的条目
+///
+/// 同一对象的操作按入队顺序发送；等待重试的条目只阻塞同一对象后面的操作。
+fn next_ready_index(entries: &[OutboxEntry], now: u64) -> Option<usize> {
+    let mut waiting: Vec<&OwnedRoomId> = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let object_id = entry.mutation.object_id();
+        if waiting.contains(&object_id) {
+            continue;
+        }
+        if entry.next_attempt_at <= now {
+            return Some(index);
+        }
+        waiting.push(object_id);
+    }
+    None
+}
+
+/// 条目发送成功，从队列中移除
+pub(super) fn complete(entry_id: &str) {
+    OUTBOX.with(|data| {
+        data.entries.retain(|e| e.id != entry_id);
+        data.in_flight = None;
+        OUTBOX.save(data);
+    })
+}
+
+/// 条目发送失败：记录错误并按指数退避推迟重试
+///
+/// 超过 [`MAX_ATTEMPTS`] 次时从队列中移除并返回该条目，由调用方负责回滚和提示。
+pub(super) fn fail(entry_id: &str, error: String) -> Option<OutboxEntry> {
+    OUTBOX.with(|data| {
+        data.in_flight = None;
+        let index = data.entries.iter().position(|e| e.id == entry_id)?;
+        let entry = &mut data.entries[index];
+        entry.attempts += 1;
+        entry.last_error = Some(error);
+
+        let dropped = if entry.attempts >= MAX_ATTEMPTS {
+            Some(data.entries.remove(index))
+        } else {
+            let backoff = (5u64 << entry.attempts.min(6)).min(MAX_BACKOFF_SECS);
+            entry.next_attempt_at = now_secs() + backoff;
+            None
+        };
+        OUTBOX.save(data);
+        dropped
+    })
+}
+
+/// 待同步条目数
+pub fn pending_count() -> usize {
+    OUTBOX.with(|data| data.entries.len())
+}
+
+/// 最早一个失败过的条目最近一次的错误信息
+pub fn last_error() -> Option<String> {
+    OUTBOX.with(|data| data.entries.iter().find_map(|e| e.last_error.clone()))
+}
+
+pub(super) fn reconcile_card(remote: KanbanCard) -> KanbanCard {
+    OUTBOX.with(|data| {
+        data.remote_cards.insert(remote.id.clone(), remote.clone());
+
+        let mut card = remote;
+        for entry in data
+            .entries
+            .iter()
+            .filter(|e| e.mutation.card_id() == Some(&card.id))
+        {
+            card = entry.mutation.overlay(card, entry.base.as_ref());
+        }
+        card
+    })
+}
+
+pub(super) fn reconcile_list(mut list: KanbanList) -> KanbanList {
+    OUTBOX.with(|data| {
+        for entry in &data.entries {
+            match &entry.mutation {
+                KanbanMutation::MoveCard {
+                    card_id,
+                    source_space_id,
+                    target_space_id,
+                    ..
+                } => {
+                    if source_space_id == &list.id {
+                        list.card_ids.retain(|id| id != card_id);
+                    } else if target_space_id == &list.id && !list.card_ids.contains(card_id) {
+                        list.card_ids.push(card_id.clone());
+                    }
+                }
+                KanbanMutation::UpdateListName { list_id, name } if list_id == &list.id => {
+                    list.name = name.clone();
+                }
+                _ => {}
+            }
+        }
+        list
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kanban::test_util::room_id;
+
+    fn entry(list: &str, next_attempt_at: u64) -> OutboxEntry {
+        OutboxEntry {
+            id: format!("op_{list}_{next_attempt_at}"),
+            mutation: KanbanMutation::UpdateListName {
+                list_id: room_id(list),
+                name: list.to_string(),
+            },
+            base: None,
+            bulk_id: None,
+            enqueued_at: 0,
+            attempts: 0,
+            next_attempt_at,
+            last_error: None,
+        }
+    }
+
+    #[test]
+    fn waiting_entries_only_block_the_same_object() {
+        let entries = vec![entry("a", 100), entry("a", 0), entry("b", 0)];
+        assert_eq!(next_ready_index(&entries, 50), Some(2));
+        assert_eq!(next_ready_index(&entries, 100), Some(0));
+        assert_eq!(next_ready_index(&entries[..2], 50), None);
+        assert_eq!(next_ready_index(&[], 50), None);
+    }
+}
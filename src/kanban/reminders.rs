@@ -0,0 +1,298 @@
+//! 卡片截止时间提醒
+//!
+//! - tokio runtime 中的后台任务定时发送 [`KanbanActions::CheckDueReminders`]
+//! - UI 线程收到后用 [`collect_due`] 检查 `KanbanAppState` 中所有卡片的截止时间，
+//!   到达提前提醒时间的卡片弹出应用内通知，并在支持的平台上发送系统通知
+//! - 同一个任务也会发送 [`KanbanActions::CheckRecurringCards`]，按时生成下一张周期卡片
+//! - 提醒设置和已发送的提醒保存在 `kanban_reminders.json`，重启后不会重复提醒
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use makepad_widgets::{Cx, SignalToUI, log, error};
+use matrix_sdk::ruma::OwnedRoomId;
+use serde::{Deserialize, Serialize};
+
+use crate::kanban::KanbanActions;
+use crate::kanban::json_store::JsonFileStore;
+use crate::kanban::state::kanban_state::{KanbanAppState, KanbanCard};
+use crate::kanban::state::workflow::StatusCategory;
+
+/// 检查间隔（秒）
+const CHECK_INTERVAL_SECS: u64 = 30;
+
+/// 截止时间早于该时长（秒）的提醒记录会被清理
+const FIRED_RETENTION_SECS: u64 = 30 * 86400;
+
+/// 提醒设置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReminderSettings {
+    /// 提前提醒的时间（分钟），0 表示到期时提醒
+    pub lead_times_mins: Vec<u64>,
+
+    /// 是否同时发送系统通知
+    #[serde(default = "default_true")]
+    pub os_notifications: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for ReminderSettings {
+    fn default() -> Self {
+        Self {
+            lead_times_mins: vec![24 * 60, 60, 0],
+            os_notifications: true,
+        }
+    }
+}
+
+impl ReminderSettings {
+    /// "即将到期"视图的时间范围（秒）：最长的提前提醒时间，至少一天
+    pub fn due_soon_window_secs(&self) -> u64 {
+        self.lead_times_mins
+            .iter()
+            .max()
+            .copied()
+            .unwrap_or(0)
+            .max(24 * 60)
+            * 60
+    }
+
+    /// 解析逗号分隔的提前提醒时间（分钟），例如 `1440, 60, 0`
+    pub fn parse_lead_times(text: &str) -> anyhow::Result<Vec<u64>> {
+        let mut lead_times = text
+            .split([',', '，', ' '])
+            .filter(|t| !t.trim().is_empty())
+            .map(|t| {
+                t.trim()
+                    .parse::<u64>()
+                    .map_err(|_| anyhow::anyhow!("无效的提醒时间 '{}'（单位为分钟）", t.trim()))
+            })
+            .collect::<anyhow::Result<Vec<u64>>>()?;
+        lead_times.sort_unstable_by(|a, b| b.cmp(a));
+        lead_times.dedup();
+        Ok(lead_times)
+    }
+
+    /// 转换为逗号分隔的文本（[`ReminderSettings::parse_lead_times`] 的逆操作）
+    pub fn lead_times_text(&self) -> String {
+        self.lead_times_mins
+            .iter()
+            .map(|m| m.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// 一条到期提醒
+#[derive(Debug, Clone)]
+pub struct DueReminder {
+    pub card_id: OwnedRoomId,
+    pub title: String,
+    pub end_time: u64,
+    /// 触发的提前提醒时间（分钟）
+    pub lead_mins: u64,
+}
+
+impl DueReminder {
+    /// 是否为到期（而不是提前）提醒
+    pub fn is_overdue(&self) -> bool {
+        self.lead_mins == 0
+    }
+
+    /// 通知内容
+    pub fn message(&self, now: u64) -> String {
+        if self.is_overdue() {
+            format!("卡片 '{}' 已到期", self.title)
+        } else {
+            format!(
+                "卡片 '{}' 即将到期（{}）",
+                self.title,
+                format_remaining(self.end_time, now)
+            )
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RemindersData {
+    #[serde(default)]
+    settings: ReminderSettings,
+
+    /// 已发送的提醒：`卡片ID|截止时间|提前分钟数`
+    #[serde(default)]
+    fired: HashSet<String>,
+}
+
+static REMINDERS: JsonFileStore<RemindersData> = JsonFileStore::new("kanban_reminders.json");
+
+static SCHEDULER_STARTED: AtomicBool = AtomicBool::new(false);
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// 获取提醒设置
+pub fn settings() -> ReminderSettings {
+    REMINDERS.with(|data| data.settings.clone())
+}
+
+/// 保存提醒设置
+pub fn set_settings(settings: ReminderSettings) {
+    REMINDERS.with(|data| {
+        data.settings = settings;
+        REMINDERS.save(data);
+    });
+}
+
+/// 启动后台提醒任务（只会启动一次，同时负责周期卡片的检查），需要在 tokio runtime 中调用
+pub fn start_scheduler() {
+    if SCHEDULER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    log!("⏰ Starting kanban due-date reminder scheduler");
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(tokio::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            Cx::post_action(KanbanActions::CheckDueReminders);
+            Cx::post_action(KanbanActions::CheckRecurringCards);
+            SignalToUI::set_ui_signal();
+        }
+    });
+}
+
+/// 找出需要发送的提醒，并记录为已发送
+///
+/// 同一张卡片一次只提醒最临近的一个时间点（例如启动时卡片已过期，
+/// 只发送到期提醒而不是同时发送提前一天 / 一小时的提醒）。
+/// 已完成和已归档的卡片不会提醒。
+pub fn collect_due(state: &KanbanAppState) -> Vec<DueReminder> {
+    let now = now_secs();
+    REMINDERS.with(|data| {
+        let mut reminders = Vec::new();
+        let mut lead_times = data.settings.lead_times_mins.clone();
+        lead_times.sort_unstable();
+
+        for card in state.cards.values() {
+            let Some(end_time) = card.end_time else {
+                continue;
+            };
+            if !is_open(state, card) {
+                continue;
+            }
+
+            // 已经到达的提醒时间点中最临近截止时间的一个
+            let Some(&lead_mins) = lead_times
+                .iter()
+                .find(|&&lead| now + lead * 60 >= end_time)
+            else {
+                continue;
+            };
+
+            let key = fired_key(&card.id, end_time, lead_mins);
+            if data.fired.contains(&key) {
+                continue;
+            }
+            // 更早的提醒时间点不再补发
+            for &lead in lead_times.iter().filter(|&&lead| lead >= lead_mins) {
+                data.fired.insert(fired_key(&card.id, end_time, lead));
+            }
+            reminders.push(DueReminder {
+                card_id: card.id.clone(),
+                title: card.title.clone(),
+                end_time,
+                lead_mins,
+            });
+        }
+
+        if !reminders.is_empty() {
+            data.fired.retain(|key| {
+                key.split('|')
+                    .nth(1)
+                    .and_then(|t| t.parse::<u64>().ok())
+                    .is_some_and(|end_time| end_time + FIRED_RETENTION_SECS > now)
+            });
+            REMINDERS.save(data);
+        }
+        reminders
+    })
+}
+
+/// 卡片是否仍需提醒（未完成、未归档）
+pub fn is_open(state: &KanbanAppState, card: &KanbanCard) -> bool {
+    !state.is_card_archived(card)
+        && !matches!(
+            state.workflow(&card.space_id).category(&card.status),
+            StatusCategory::Done | StatusCategory::Archived
+        )
+}
+
+fn fired_key(card_id: &OwnedRoomId, end_time: u64, lead_mins: u64) -> String {
+    format!("{}|{}|{}", card_id, end_time, lead_mins)
+}
+
+/// 剩余 / 已过期时间的描述，例如"剩余 2 小时"、"已过期 3 天"
+pub fn format_remaining(end_time: u64, now: u64) -> String {
+    let (prefix, secs) = if end_time >= now {
+        ("剩余", end_time - now)
+    } else {
+        ("已过期", now - end_time)
+    };
+    let amount = match secs {
+        s if s < 3600 => format!("{} 分钟", (s / 60).max(1)),
+        s if s < 86400 => format!("{} 小时", s / 3600),
+        s => format!("{} 天", s / 86400),
+    };
+    format!("{} {}", prefix, amount)
+}
+
+/// 截止时间的本地时间显示
+pub fn format_due_time(end_time: u64) -> String {
+    chrono::DateTime::from_timestamp(end_time as i64, 0)
+        .map(|dt| {
+            dt.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+/// 发送系统通知（不支持的平台上什么也不做）
+///
+/// 在单独的线程中等待通知命令结束，避免阻塞 UI 线程。
+pub fn send_os_notification(title: &str, body: &str) {
+    #[cfg(target_os = "linux")]
+    let mut command = {
+        let mut command = std::process::Command::new("notify-send");
+        command.arg(title).arg(body);
+        command
+    };
+
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = std::process::Command::new("osascript");
+        command.arg("-e").arg(format!(
+            "display notification {:?} with title {:?}",
+            body, title
+        ));
+        command
+    };
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    std::thread::spawn(move || {
+        if let Err(e) = command.status() {
+            error!("Failed to send OS notification: {:?}", e);
+        }
+    });
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    let _ = (title, body);
+}
@@ -0,0 +1,87 @@
+//! 保存的看板过滤器
+//!
+//! 保存在 `app_data_dir` 下的 `kanban_saved_filters.json`（见 [`JsonFileStore`]），
+//! 每次修改后写回。过滤器按看板分别保存，key 是看板 Space 的 ID。
+
+use std::collections::HashMap;
+
+use matrix_sdk::ruma::OwnedRoomId;
+use serde::{Deserialize, Serialize};
+
+use crate::kanban::json_store::JsonFileStore;
+
+/// 未分组列表（不属于任何看板的列表）的 key
+pub const UNGROUPED_BOARD_KEY: &str = "ungrouped";
+
+/// 多看板之前所有过滤器都保存在这个 key 下，加载看板后迁移到第一个看板
+const LEGACY_BOARD_KEY: &str = "default";
+
+/// 一个保存的过滤器
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedFilter {
+    /// 名称
+    pub name: String,
+
+    /// 过滤栏查询文本（见 [`crate::kanban::state::card_filter::CardFilter::parse`]）
+    pub query: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SavedFiltersData {
+    /// 看板 key -> 保存的过滤器
+    boards: HashMap<String, Vec<SavedFilter>>,
+}
+
+static SAVED_FILTERS: JsonFileStore<SavedFiltersData> =
+    JsonFileStore::new("kanban_saved_filters.json");
+
+/// 看板的 key：看板 Space 的 ID，未分组列表为 [`UNGROUPED_BOARD_KEY`]
+pub fn board_key(board_id: Option<&OwnedRoomId>) -> String {
+    board_id.map_or_else(|| UNGROUPED_BOARD_KEY.to_string(), |id| id.to_string())
+}
+
+/// 把多看板之前保存的过滤器迁移到 `board_key`（同名的保留该看板已有的过滤器）
+pub fn migrate_legacy_filters(board_key: &str) {
+    SAVED_FILTERS.with(|data| {
+        let Some(legacy) = data.boards.remove(LEGACY_BOARD_KEY) else {
+            return;
+        };
+        let filters = data.boards.entry(board_key.to_string()).or_default();
+        for filter in legacy {
+            if !filters.iter().any(|f| f.name == filter.name) {
+                filters.push(filter);
+            }
+        }
+        SAVED_FILTERS.save(data);
+    })
+}
+
+/// 获取看板保存的过滤器
+pub fn saved_filters(board_key: &str) -> Vec<SavedFilter> {
+    SAVED_FILTERS.with(|data| data.boards.get(board_key).cloned().unwrap_or_default())
+}
+
+/// 保存过滤器（同名的会被覆盖），返回看板当前所有过滤器
+pub fn save_filter(board_key: &str, filter: SavedFilter) -> Vec<SavedFilter> {
+    SAVED_FILTERS.with(|data| {
+        let filters = data.boards.entry(board_key.to_string()).or_default();
+        match filters.iter_mut().find(|f| f.name == filter.name) {
+            Some(existing) => *existing = filter,
+            None => filters.push(filter),
+        }
+        let filters = filters.clone();
+        SAVED_FILTERS.save(data);
+        filters
+    })
+}
+
+/// 删除过滤器，返回看板当前所有过滤器
+pub fn delete_filter(board_key: &str, name: &str) -> Vec<SavedFilter> {
+    SAVED_FILTERS.with(|data| {
+        let filters = data.boards.entry(board_key.to_string()).or_default();
+        filters.retain(|f| f.name != name);
+        let filters = filters.clone();
+        SAVED_FILTERS.save(data);
+        filters
+    })
+}
@@ -0,0 +1,101 @@
+//! 已归档的卡片和列表
+//!
+//! 归档不移动任何数据：卡片在元数据中记录 `archived_at`，列表在 Space 的
+//! `m.kanban.list.archive` 状态事件中记录归档时间。卡片仍保留所属列表和排序位置，
+//! 恢复时直接回到原来的位置。永久删除会解除 Space 父子关系并退出卡片 Room，
+//! 见 [`crate::kanban::MatrixKanbanAdapter::delete_card`]。
+
+use matrix_sdk::ruma::OwnedRoomId;
+
+use super::kanban_state::{KanbanAppState, KanbanCard, KanbanList};
+
+/// 列表归档状态事件类型（内容为 `{"archived_at": 秒}`，恢复时为空对象）
+pub const LIST_ARCHIVE_EVENT_TYPE: &str = "m.kanban.list.archive";
+
+/// 归档面板中的一项
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArchivedItem {
+    List {
+        id: OwnedRoomId,
+        name: String,
+        card_count: usize,
+        archived_at: u64,
+    },
+    Card {
+        id: OwnedRoomId,
+        title: String,
+        /// 原来所在的列表名称
+        list_name: String,
+        archived_at: u64,
+    },
+}
+
+impl ArchivedItem {
+    /// 卡片或列表的 ID
+    pub fn id(&self) -> &OwnedRoomId {
+        match self {
+            ArchivedItem::List { id, .. } | ArchivedItem::Card { id, .. } => id,
+        }
+    }
+}
+
+impl KanbanAppState {
+    /// 卡片是否已归档（卡片本身或所在列表被归档）
+    pub fn is_card_archived(&self, card: &KanbanCard) -> bool {
+        card.archived_at.is_some()
+            || self
+                .lists
+                .get(&card.space_id)
+                .is_some_and(|list| list.archived_at.is_some())
+    }
+
+    /// 当前看板中已归档的列表（最近归档的在前）
+    pub fn archived_lists(&self) -> Vec<&KanbanList> {
+        let mut lists: Vec<&KanbanList> = self
+            .lists
+            .values()
+            .filter(|list| list.archived_at.is_some())
+            .filter(|list| self.is_in_current_board(&list.id))
+            .collect();
+        lists.sort_by(|a, b| b.archived_at.cmp(&a.archived_at));
+        lists
+    }
+
+    /// 当前看板中单独归档的卡片（最近归档的在前）
+    pub fn archived_cards(&self) -> Vec<&KanbanCard> {
+        let mut cards: Vec<&KanbanCard> = self
+            .cards
+            .values()
+            .filter(|card| card.archived_at.is_some())
+            .filter(|card| self.is_in_current_board(&card.space_id))
+            .collect();
+        cards.sort_by(|a, b| b.archived_at.cmp(&a.archived_at));
+        cards
+    }
+
+    /// 归档面板显示的条目：先列表，后卡片
+    pub fn archived_items(&self) -> Vec<ArchivedItem> {
+        let lists = self
+            .archived_lists()
+            .into_iter()
+            .map(|list| ArchivedItem::List {
+                id: list.id.clone(),
+                name: list.name.clone(),
+                card_count: list.card_ids.len(),
+                archived_at: list.archived_at.unwrap_or_default(),
+            });
+        let cards = self
+            .archived_cards()
+            .into_iter()
+            .map(|card| ArchivedItem::Card {
+                id: card.id.clone(),
+                title: card.title.clone(),
+                list_name: self
+                    .lists
+                    .get(&card.space_id)
+                    .map_or_else(|| card.space_id.to_string(), |list| list.name.clone()),
+                archived_at: card.archived_at.unwrap_or_default(),
+            });
+        lists.chain(cards).collect()
+    }
+}
@@ -0,0 +1,120 @@
+//! 卡片附件与封面
+//!
+//! 附件是卡片 Room 时间线中的标准 `m.image` / `m.file` 消息，文件本身上传到媒体仓库，
+//! 所以在普通 Matrix 客户端里也能看到和下载。卡片封面只记录某个图片附件的 mxc URI
+//! （[`super::kanban_state::KanbanCard::cover`]），随卡片元数据一起保存。
+
+use matrix_sdk::ruma::{OwnedMxcUri, OwnedRoomId};
+use serde::{Deserialize, Serialize};
+
+use super::kanban_state::KanbanAppState;
+
+/// 附件类型
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttachmentKind {
+    /// `m.image`，可以作为卡片封面
+    Image,
+    /// `m.file`
+    File,
+}
+
+/// 卡片附件
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CardAttachment {
+    /// 附件消息的事件 ID
+    pub id: String,
+
+    pub kind: AttachmentKind,
+
+    /// 文件名
+    pub name: String,
+
+    /// 媒体仓库中的地址
+    pub url: OwnedMxcUri,
+
+    pub mimetype: Option<String>,
+
+    /// 文件大小（字节）
+    pub size: Option<u64>,
+
+    /// 上传者
+    pub user_id: String,
+
+    /// 上传时间（Unix timestamp 秒）
+    pub created_at: u64,
+}
+
+impl CardAttachment {
+    /// 用于显示的文件大小，例如 `1.2 MiB`
+    pub fn size_text(&self) -> String {
+        self.size
+            .map(|size| bytesize::ByteSize::b(size).to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn is_image(&self) -> bool {
+        self.kind == AttachmentKind::Image
+    }
+}
+
+/// 根据文件内容和扩展名判断附件类型和 MIME 类型
+///
+/// 只有能识别出格式的图片才作为 `m.image` 发送，其余文件都是 `m.file`。
+pub fn detect_mimetype(name: &str, data: &[u8]) -> (AttachmentKind, String) {
+    let image_type = match imghdr::from_bytes(data) {
+        Some(imghdr::Type::Png) => Some("image/png"),
+        Some(imghdr::Type::Jpeg) => Some("image/jpeg"),
+        Some(imghdr::Type::Gif) => Some("image/gif"),
+        Some(imghdr::Type::Webp) => Some("image/webp"),
+        Some(imghdr::Type::Bmp) => Some("image/bmp"),
+        _ => None,
+    };
+    if let Some(mimetype) = image_type {
+        return (AttachmentKind::Image, mimetype.to_string());
+    }
+
+    let extension = name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    let mimetype = match extension.as_str() {
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "json" => "application/json",
+        "txt" | "log" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xls" => "application/vnd.ms-excel",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "mp4" => "video/mp4",
+        "mp3" => "audio/mpeg",
+        _ => "application/octet-stream",
+    };
+    (AttachmentKind::File, mimetype.to_string())
+}
+
+impl KanbanAppState {
+    /// 卡片的附件（最新的在前）
+    pub fn card_attachments(&self, card_id: &OwnedRoomId) -> &[CardAttachment] {
+        self.attachments
+            .get(card_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// 用服务器加载到的附件替换卡片附件
+    pub fn set_attachments(&mut self, card_id: OwnedRoomId, mut attachments: Vec<CardAttachment>) {
+        attachments.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        self.attachments.insert(card_id, attachments);
+    }
+
+    /// 添加刚上传的附件
+    pub fn add_attachment(&mut self, card_id: OwnedRoomId, attachment: CardAttachment) {
+        let attachments = self.attachments.entry(card_id).or_default();
+        attachments.retain(|existing| existing.id != attachment.id);
+        attachments.insert(0, attachment);
+    }
+}
@@ -0,0 +1,126 @@
+//! 多看板
+//!
+//! 看板是一个父 Space（topic 为 `[kanban-board] 名称`），它的 `m.space.child` 是各个列表 Space，
+//! 背景颜色 / 图片保存在 `m.kanban.board` 状态事件中（见 [`super::schema::BoardMetadata`]）。
+//! 用户能看到哪些看板完全由 Matrix 成员关系决定：已加入的看板正常显示，
+//! 收到邀请的看板显示为待接受。邀请用户加入看板时会一并邀请到其中的列表和卡片。
+//!
+//! 不属于任何看板的列表（多看板之前创建的列表）归到“未分组列表”，
+//! 对应 `current_board_id` 为 `None`。
+
+use matrix_sdk::ruma::OwnedRoomId;
+
+use super::kanban_state::{KanbanAppState, KanbanBoard};
+use crate::kanban::saved_filters;
+
+/// 看板 Space 的 topic 标记
+pub const BOARD_TOPIC_MARKER: &str = "[kanban-board]";
+
+/// 新建看板时可选的背景颜色
+pub const BOARD_BACKGROUND_COLORS: &[&str] = &[
+    "#0079BF", "#D29034", "#519839", "#B04632", "#89609E", "#CD5A91", "#4BBF6B", "#00AECC",
+];
+
+impl KanbanAppState {
+    /// 所有看板：已加入的在前，然后是收到邀请的，各自按名称排序
+    pub fn all_boards(&self) -> Vec<&KanbanBoard> {
+        let mut boards: Vec<&KanbanBoard> = self
+            .boards
+            .values()
+            .filter(|board| board.archived_at.is_none())
+            .collect();
+        boards.sort_by(|a, b| a.invited.cmp(&b.invited).then_with(|| a.name.cmp(&b.name)));
+        boards
+    }
+
+    /// 当前选中的看板（`None` 表示未分组列表）
+    pub fn current_board(&self) -> Option<&KanbanBoard> {
+        self.current_board_id
+            .as_ref()
+            .and_then(|board_id| self.boards.get(board_id))
+    }
+
+    /// 列表所属的看板
+    pub fn board_of_list(&self, list_id: &OwnedRoomId) -> Option<&OwnedRoomId> {
+        self.boards
+            .values()
+            .find(|board| board.list_ids.contains(list_id))
+            .map(|board| &board.id)
+    }
+
+    /// 列表是否属于当前看板
+    pub fn is_in_current_board(&self, list_id: &OwnedRoomId) -> bool {
+        self.board_of_list(list_id) == self.current_board_id.as_ref()
+    }
+
+    /// 是否有不属于任何看板的列表
+    pub fn has_ungrouped_lists(&self) -> bool {
+        self.lists
+            .keys()
+            .any(|list_id| self.board_of_list(list_id).is_none())
+    }
+
+    /// 添加或更新看板
+    pub fn upsert_board(&mut self, board: KanbanBoard) {
+        self.boards.insert(board.id.clone(), board);
+    }
+
+    /// 用加载到的看板替换所有看板
+    ///
+    /// 第一次加载或当前看板不再可见（退出 / 归档）时，切换到第一个已加入的看板。
+    pub fn set_boards(&mut self, boards: Vec<KanbanBoard>) {
+        let first_load = self.boards.is_empty();
+        self.boards = boards
+            .into_iter()
+            .map(|board| (board.id.clone(), board))
+            .collect();
+
+        // 多看板之前保存的过滤器归到第一个看板
+        if first_load {
+            if let Some(board) = self.all_boards().into_iter().find(|board| !board.invited) {
+                saved_filters::migrate_legacy_filters(&saved_filters::board_key(Some(&board.id)));
+            }
+        }
+
+        let current_hidden = match self.current_board() {
+            Some(board) => board.invited || board.archived_at.is_some(),
+            None => self.current_board_id.is_some() || first_load,
+        };
+        if current_hidden {
+            self.current_board_id = self
+                .all_boards()
+                .into_iter()
+                .find(|board| !board.invited)
+                .map(|board| board.id.clone());
+        }
+        self.reload_saved_filters();
+    }
+
+    /// 切换当前看板，清空只对当前看板有意义的选择并加载该看板保存的过滤器
+    pub fn select_board(&mut self, board_id: Option<OwnedRoomId>) {
+        self.current_board_id = board_id;
+        self.selected_cards.clear();
+        self.selection_anchor = None;
+        self.board_report = None;
+        self.reload_saved_filters();
+    }
+
+    /// 当前看板保存过滤器使用的 key
+    pub fn saved_filters_key(&self) -> String {
+        saved_filters::board_key(self.current_board_id.as_ref())
+    }
+
+    /// 重新加载当前看板保存的过滤器
+    pub fn reload_saved_filters(&mut self) {
+        self.saved_filters = saved_filters::saved_filters(&self.saved_filters_key());
+    }
+
+    /// 把列表加入看板（本地状态）
+    pub fn add_list_to_board(&mut self, board_id: &OwnedRoomId, list_id: OwnedRoomId) {
+        if let Some(board) = self.boards.get_mut(board_id) {
+            if !board.list_ids.contains(&list_id) {
+                board.list_ids.push(list_id);
+            }
+        }
+    }
+}
@@ -0,0 +1,391 @@
+//! 卡片多选与批量操作
+//!
+//! 多选只保存在本地状态中：Ctrl / ⌘ 点击切换单张卡片，Shift 点击选中同一列表中
+//! 上次点击的卡片到当前卡片之间的所有卡片。批量操作先在本地逐张校验并更新
+//! （权限、工作流流转、WIP 上限、标签是否存在、移到已完成状态的卡片是否仍被阻塞），
+//! 再把每张更新后的卡片作为批量条目加入离线队列，由队列发送时逐张报告进度和失败原因。
+
+use std::collections::HashMap;
+
+use matrix_sdk::ruma::{OwnedRoomId, UserId};
+
+use super::kanban_state::{KanbanAppState, KanbanCard};
+use super::permissions::KanbanPermission;
+
+/// 批量操作
+#[derive(Debug, Clone, PartialEq)]
+pub enum BulkOperation {
+    /// 移动到列表末尾
+    MoveToList { target_space_id: OwnedRoomId },
+    /// 设置状态（按状态名称或 ID 在每张卡片所在列表的工作流中查找）
+    SetStatus { status: String },
+    /// 添加标签（按名称在每张卡片所在列表的标签库中查找）
+    AddTag { tag_name: String },
+    /// 移除标签
+    RemoveTag { tag_name: String },
+    /// 设置截止时间；`None` 表示清除
+    SetDueDate { end_time: Option<u64> },
+    /// 归档
+    Archive,
+}
+
+impl BulkOperation {
+    /// 执行该操作需要的权限
+    pub fn permission(&self) -> KanbanPermission {
+        match self {
+            BulkOperation::MoveToList { .. } | BulkOperation::SetStatus { .. } => {
+                KanbanPermission::MoveCard
+            }
+            BulkOperation::AddTag { .. }
+            | BulkOperation::RemoveTag { .. }
+            | BulkOperation::SetDueDate { .. } => KanbanPermission::EditCard,
+            BulkOperation::Archive => KanbanPermission::Archive,
+        }
+    }
+
+    /// 显示名称，例如"添加标签「紧急」"
+    pub fn describe(&self, state: &KanbanAppState) -> String {
+        match self {
+            BulkOperation::MoveToList { target_space_id } => format!(
+                "移动到「{}」",
+                state
+                    .lists
+                    .get(target_space_id)
+                    .map_or_else(|| target_space_id.to_string(), |list| list.name.clone())
+            ),
+            BulkOperation::SetStatus { status } => format!("设置状态「{}」", status),
+            BulkOperation::AddTag { tag_name } => format!("添加标签「{}」", tag_name),
+            BulkOperation::RemoveTag { tag_name } => format!("移除标签「{}」", tag_name),
+            BulkOperation::SetDueDate {
+                end_time: Some(end_time),
+            } => format!(
+                "设置截止时间 {}",
+                crate::kanban::reminders::format_due_time(*end_time)
+            ),
+            BulkOperation::SetDueDate { end_time: None } => "清除截止时间".to_string(),
+            BulkOperation::Archive => "归档".to_string(),
+        }
+    }
+}
+
+/// 批量操作中需要同步的一张卡片
+#[derive(Debug, Clone)]
+pub struct BulkCardUpdate {
+    /// 更新后的卡片
+    pub card: KanbanCard,
+
+    /// 卡片原来所在的列表（与 `card.space_id` 不同时需要移动）
+    pub source_space_id: OwnedRoomId,
+
+    /// 原来的状态（状态变更时用于记录活动）
+    pub previous_status: String,
+}
+
+/// 批量操作中失败的一张卡片
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulkFailure {
+    pub card_id: OwnedRoomId,
+    pub title: String,
+    pub error: String,
+}
+
+/// 正在执行（或刚执行完）的批量操作的进度
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulkProgress {
+    /// 操作 ID（离线队列中该操作的条目带有同一个 ID）
+    pub id: String,
+
+    /// 操作描述
+    pub label: String,
+
+    /// 需要同步的卡片数
+    pub total: usize,
+
+    /// 已同步（成功或失败）的卡片数
+    pub processed: usize,
+
+    /// 失败的卡片（包括本地校验失败的）
+    pub failures: Vec<BulkFailure>,
+
+    /// 状态有变化、尚未同步的卡片 ->（原来的列表，原来的状态）（同步成功后记录活动）
+    pub status_changes: HashMap<OwnedRoomId, (OwnedRoomId, String)>,
+}
+
+impl BulkProgress {
+    /// 开始一个批量操作，生成新的操作 ID
+    pub fn new(
+        label: String,
+        total: usize,
+        failures: Vec<BulkFailure>,
+        status_changes: HashMap<OwnedRoomId, (OwnedRoomId, String)>,
+    ) -> Self {
+        Self {
+            id: format!("bulk_{}", uuid::Uuid::new_v4()),
+            label,
+            total,
+            processed: 0,
+            failures,
+            status_changes,
+        }
+    }
+
+    /// 是否已全部同步
+    pub fn is_finished(&self) -> bool {
+        self.processed >= self.total
+    }
+
+    /// 进度或结果的描述，例如"移动到「完成」：3/10"
+    pub fn summary(&self) -> String {
+        if !self.is_finished() {
+            return format!("{}：{}/{}", self.label, self.processed, self.total);
+        }
+        if self.failures.is_empty() {
+            format!("{}：{} 张卡片已完成", self.label, self.total)
+        } else {
+            let details: Vec<String> = self
+                .failures
+                .iter()
+                .map(|failure| format!("「{}」{}", failure.title, failure.error))
+                .collect();
+            format!(
+                "{}：{} 张失败 — {}",
+                self.label,
+                self.failures.len(),
+                details.join("；")
+            )
+        }
+    }
+}
+
+impl KanbanAppState {
+    /// 卡片是否被多选选中
+    pub fn is_card_selected(&self, card_id: &OwnedRoomId) -> bool {
+        self.selected_cards.contains(card_id)
+    }
+
+    /// 切换单张卡片的选中状态（Ctrl / ⌘ 点击）
+    pub fn toggle_card_selection(&mut self, card_id: &OwnedRoomId) {
+        if !self.selected_cards.remove(card_id) {
+            self.selected_cards.insert(card_id.clone());
+        }
+        self.selection_anchor = Some(card_id.clone());
+    }
+
+    /// 选中上次点击的卡片到该卡片之间的所有可见卡片（Shift 点击）
+    ///
+    /// 两张卡片不在同一列表中时只切换该卡片。
+    pub fn extend_card_selection(&mut self, card_id: &OwnedRoomId, current_user: Option<&UserId>) {
+        let range = self.selection_anchor.as_ref().and_then(|anchor| {
+            let space_id = &self.cards.get(card_id)?.space_id;
+            let ids: Vec<OwnedRoomId> = self
+                .visible_list_cards(space_id, current_user)
+                .into_iter()
+                .map(|card| card.id.clone())
+                .collect();
+            let from = ids.iter().position(|id| id == anchor)?;
+            let to = ids.iter().position(|id| id == card_id)?;
+            Some(ids[from.min(to)..=from.max(to)].to_vec())
+        });
+        match range {
+            Some(ids) => self.selected_cards.extend(ids),
+            None => self.toggle_card_selection(card_id),
+        }
+    }
+
+    /// 选中（或在已全部选中时取消选中）列表中所有可见的卡片
+    pub fn select_all_in_list(&mut self, space_id: &OwnedRoomId, current_user: Option<&UserId>) {
+        let ids: Vec<OwnedRoomId> = self
+            .visible_list_cards(space_id, current_user)
+            .into_iter()
+            .map(|card| card.id.clone())
+            .collect();
+        if ids.iter().all(|id| self.selected_cards.contains(id)) {
+            for id in &ids {
+                self.selected_cards.remove(id);
+            }
+        } else {
+            self.selected_cards.extend(ids);
+        }
+    }
+
+    /// 开始一个批量操作：丢弃已完成的操作的进度，记录新操作
+    pub fn start_bulk_operation(&mut self, progress: BulkProgress) {
+        self.bulk_operations
+            .retain(|operation| !operation.is_finished());
+        self.bulk_operations.push(progress);
+    }
+
+    /// 按 ID 查找批量操作的进度
+    pub fn bulk_operation_mut(&mut self, bulk_id: &str) -> Option<&mut BulkProgress> {
+        self.bulk_operations
+            .iter_mut()
+            .find(|operation| operation.id == bulk_id)
+    }
+
+    /// 清除多选
+    pub fn clear_card_selection(&mut self) {
+        self.selected_cards.clear();
+        self.selection_anchor = None;
+    }
+
+    /// 对选中的卡片执行批量操作（只更新本地状态）
+    ///
+    /// 返回需要同步的卡片和本地校验失败的卡片；没有变化的卡片两者都不包含。
+    pub fn apply_bulk_operation(
+        &mut self,
+        operation: &BulkOperation,
+    ) -> (Vec<BulkCardUpdate>, Vec<BulkFailure>) {
+        let mut selected: Vec<&KanbanCard> = self
+            .selected_cards
+            .iter()
+            .filter_map(|card_id| self.cards.get(card_id))
+            .collect();
+        selected.sort_by(|a, b| a.cmp_order(b));
+        let card_ids: Vec<OwnedRoomId> = selected.into_iter().map(|card| card.id.clone()).collect();
+
+        let mut updates = Vec::new();
+        let mut failures = Vec::new();
+        for card_id in card_ids {
+            let Some(card) = self.cards.get(&card_id) else {
+                continue;
+            };
+            let source_space_id = card.space_id.clone();
+            let previous_status = card.status.clone();
+            let title = card.title.clone();
+
+            match self.apply_to_card(&card_id, operation) {
+                Ok(true) => {
+                    let card = self.cards[&card_id].clone();
+                    updates.push(BulkCardUpdate {
+                        card,
+                        source_space_id,
+                        previous_status,
+                    });
+                }
+                Ok(false) => {}
+                Err(error) => failures.push(BulkFailure {
+                    card_id,
+                    title,
+                    error,
+                }),
+            }
+        }
+        // 移动时目标列表可能重新分配了排序键，同步最新的卡片
+        for update in &mut updates {
+            if let Some(card) = self.cards.get(&update.card.id) {
+                update.card = card.clone();
+            }
+        }
+        (updates, failures)
+    }
+
+    /// 对一张卡片执行操作，返回卡片是否有变化
+    fn apply_to_card(
+        &mut self,
+        card_id: &OwnedRoomId,
+        operation: &BulkOperation,
+    ) -> Result<bool, String> {
+        let permission = operation.permission();
+        if !self.can_on_card(card_id, permission) {
+            return Err(format!("没有「{}」权限", permission.display_name()));
+        }
+        let card = &self.cards[card_id];
+        let space_id = card.space_id.clone();
+
+        match operation {
+            BulkOperation::MoveToList { target_space_id } => {
+                if &space_id == target_space_id {
+                    return Ok(false);
+                }
+                if !self.can(target_space_id, KanbanPermission::MoveCard) {
+                    return Err("没有移动到目标列表的权限".to_string());
+                }
+                // 移动后处于已完成状态的卡片不能仍被阻塞
+                if self.is_done_after_move(card_id, target_space_id)
+                    && let Some(reason) = self.blocked_reason(card_id)
+                {
+                    return Err(reason);
+                }
+                // 放到目标列表末尾
+                self.move_card_to(card_id, target_space_id);
+                Ok(true)
+            }
+            BulkOperation::SetStatus { status } => {
+                let workflow = self.workflow(&space_id);
+                let Some(target) = workflow
+                    .statuses
+                    .iter()
+                    .find(|s| s.id == *status || s.name.eq_ignore_ascii_case(status))
+                else {
+                    return Err(format!("列表中没有状态「{}」", status));
+                };
+                if card.status == target.id {
+                    return Ok(false);
+                }
+                if !workflow.can_transition(&card.status, &target.id) {
+                    return Err(format!(
+                        "工作流不允许从「{}」流转到「{}」",
+                        workflow.display_name(&card.status),
+                        target.name
+                    ));
+                }
+                if workflow.exceeds_wip_limit(&target.id, self.status_count(&space_id, &target.id))
+                {
+                    return Err(format!("「{}」已达到 WIP 上限", target.name));
+                }
+                let status = target.id.clone();
+                let card = self.cards.get_mut(card_id).expect("card exists");
+                card.status = status;
+                card.touch();
+                Ok(true)
+            }
+            BulkOperation::AddTag { tag_name } | BulkOperation::RemoveTag { tag_name } => {
+                let Some(tag_id) = self
+                    .space_tags
+                    .get(&space_id)
+                    .and_then(|tags| {
+                        tags.iter()
+                            .find(|tag| tag.name.eq_ignore_ascii_case(tag_name.trim()))
+                    })
+                    .map(|tag| tag.id.clone())
+                else {
+                    return Err(format!("列表中没有标签「{}」", tag_name));
+                };
+                let card = self.cards.get_mut(card_id).expect("card exists");
+                let has_tag = card.tags.contains(&tag_id);
+                match operation {
+                    BulkOperation::AddTag { .. } if !has_tag => card.tags.push(tag_id),
+                    BulkOperation::RemoveTag { .. } if has_tag => {
+                        card.tags.retain(|id| id != &tag_id)
+                    }
+                    _ => return Ok(false),
+                }
+                card.touch();
+                Ok(true)
+            }
+            BulkOperation::SetDueDate { end_time } => {
+                let card = self.cards.get_mut(card_id).expect("card exists");
+                if card.end_time == *end_time {
+                    return Ok(false);
+                }
+                card.end_time = *end_time;
+                card.touch();
+                Ok(true)
+            }
+            BulkOperation::Archive => {
+                let card = self.cards.get_mut(card_id).expect("card exists");
+                if card.archived_at.is_some() {
+                    return Ok(false);
+                }
+                card.archived_at = Some(
+                    std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs(),
+                );
+                card.touch();
+                Ok(true)
+            }
+        }
+    }
+}
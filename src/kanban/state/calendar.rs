@@ -0,0 +1,195 @@
+//! 日历与时间线视图
+//!
+//! - 日历视图按月（6 周）或按周显示，卡片出现在截止时间所在的那一天
+//! - 时间线（甘特图）视图从周一开始显示 [`TIMELINE_DAYS`] 天，
+//!   每张卡片是一根从创建时间到截止时间的横条
+//!
+//! 日期都使用本地时区。
+
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveTime, TimeZone};
+use matrix_sdk::ruma::UserId;
+
+use super::kanban_state::{KanbanAppState, KanbanCard};
+
+/// 时间线视图显示的天数
+pub const TIMELINE_DAYS: i64 = 28;
+
+/// 没有原截止时间的卡片拖到某一天时使用的默认时间（18:00）
+const DEFAULT_DUE_HOUR: u32 = 18;
+
+/// 看板主体的显示方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoardViewMode {
+    /// 按列表（或泳道）显示
+    #[default]
+    Board,
+    Calendar,
+    Timeline,
+}
+
+impl BoardViewMode {
+    /// 依次切换到下一种显示方式
+    pub fn next(self) -> Self {
+        match self {
+            BoardViewMode::Board => BoardViewMode::Calendar,
+            BoardViewMode::Calendar => BoardViewMode::Timeline,
+            BoardViewMode::Timeline => BoardViewMode::Board,
+        }
+    }
+
+    /// 显示名称
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            BoardViewMode::Board => "看板",
+            BoardViewMode::Calendar => "日历",
+            BoardViewMode::Timeline => "时间线",
+        }
+    }
+}
+
+/// 日历的显示范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CalendarMode {
+    #[default]
+    Month,
+    Week,
+}
+
+impl CalendarMode {
+    /// 日历显示的所有日期：月视图为包含该月的 6 周，周视图为该周的 7 天（周一开始）
+    pub fn days(&self, anchor: NaiveDate) -> Vec<NaiveDate> {
+        let (first, count) = match self {
+            CalendarMode::Month => (week_start(anchor.with_day(1).unwrap_or(anchor)), 42),
+            CalendarMode::Week => (week_start(anchor), 7),
+        };
+        (0..count).map(|i| first + Duration::days(i)).collect()
+    }
+
+    /// 向前 / 向后翻一页后的日期
+    pub fn shift(&self, anchor: NaiveDate, forward: bool) -> NaiveDate {
+        match (self, forward) {
+            (CalendarMode::Month, true) => anchor
+                .checked_add_months(chrono::Months::new(1))
+                .unwrap_or(anchor),
+            (CalendarMode::Month, false) => anchor
+                .checked_sub_months(chrono::Months::new(1))
+                .unwrap_or(anchor),
+            (CalendarMode::Week, true) => anchor + Duration::days(7),
+            (CalendarMode::Week, false) => anchor - Duration::days(7),
+        }
+    }
+
+    /// 标题，例如"2025年3月"或"2025-03-10 ~ 2025-03-16"
+    pub fn title(&self, anchor: NaiveDate) -> String {
+        match self {
+            CalendarMode::Month => format!("{}年{}月", anchor.year(), anchor.month()),
+            CalendarMode::Week => {
+                let start = week_start(anchor);
+                format!(
+                    "{} ~ {}",
+                    start.format("%Y-%m-%d"),
+                    (start + Duration::days(6)).format("%Y-%m-%d")
+                )
+            }
+        }
+    }
+}
+
+/// 时间线中的一张卡片
+#[derive(Debug, Clone)]
+pub struct TimelineEntry {
+    pub card: KanbanCard,
+
+    /// 横条开始的天数（相对于时间线的第一天，可以为负）
+    pub start_day: f64,
+
+    /// 横条结束的天数（截止时间）
+    pub end_day: f64,
+}
+
+/// 今天（本地时区）
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// 日期所在周的周一
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+}
+
+/// 本地时间某一天 0 点的 Unix timestamp（秒）
+pub fn day_start(date: NaiveDate) -> u64 {
+    local_timestamp(date, NaiveTime::MIN)
+}
+
+/// Unix timestamp 对应的本地日期
+pub fn local_date(timestamp: u64) -> Option<NaiveDate> {
+    Local
+        .timestamp_opt(timestamp as i64, 0)
+        .single()
+        .map(|dt| dt.date_naive())
+}
+
+/// 把截止时间移到另一天，保留原来的时刻（没有原截止时间时为当天 18:00）
+pub fn end_time_on_day(previous_end_time: Option<u64>, date: NaiveDate) -> u64 {
+    let time = previous_end_time
+        .and_then(|end_time| Local.timestamp_opt(end_time as i64, 0).single())
+        .map(|dt| dt.time())
+        .unwrap_or_else(|| {
+            NaiveTime::from_hms_opt(DEFAULT_DUE_HOUR, 0, 0).unwrap_or(NaiveTime::MIN)
+        });
+    local_timestamp(date, time)
+}
+
+fn local_timestamp(date: NaiveDate, time: NaiveTime) -> u64 {
+    Local
+        .from_local_datetime(&date.and_time(time))
+        .earliest()
+        .map_or(0, |dt| dt.timestamp().max(0) as u64)
+}
+
+impl KanbanAppState {
+    /// 日历 / 时间线当前显示的日期（未设置时为今天）
+    pub fn calendar_anchor(&self) -> NaiveDate {
+        self.calendar_anchor.unwrap_or_else(today)
+    }
+
+    /// 截止时间在某一天的可见卡片，按截止时间排序
+    pub fn cards_due_on(&self, date: NaiveDate, current_user: Option<&UserId>) -> Vec<&KanbanCard> {
+        let start = day_start(date);
+        let end = day_start(date + Duration::days(1));
+        let mut cards: Vec<&KanbanCard> = self
+            .all_lists()
+            .into_iter()
+            .flat_map(|list| self.visible_list_cards(&list.id, current_user))
+            .filter(|card| card.end_time.is_some_and(|t| t >= start && t < end))
+            .collect();
+        cards.sort_by_key(|card| card.end_time);
+        cards
+    }
+
+    /// 时间线中的卡片：有截止时间、且与显示范围有交集的可见卡片，按开始时间排序
+    pub fn timeline_entries(&self, current_user: Option<&UserId>) -> Vec<TimelineEntry> {
+        let first_day = week_start(self.calendar_anchor());
+        let window_start = day_start(first_day) as f64;
+        let to_days = |timestamp: u64| (timestamp as f64 - window_start) / 86400.0;
+
+        let mut entries: Vec<TimelineEntry> = self
+            .all_lists()
+            .into_iter()
+            .flat_map(|list| self.visible_list_cards(&list.id, current_user))
+            .filter_map(|card| {
+                let end_time = card.end_time?;
+                let start_day = to_days(card.created_at.min(end_time));
+                let end_day = to_days(end_time);
+                (end_day >= 0.0 && start_day <= TIMELINE_DAYS as f64).then(|| TimelineEntry {
+                    card: card.clone(),
+                    start_day,
+                    end_day,
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| a.start_day.total_cmp(&b.start_day));
+        entries
+    }
+}
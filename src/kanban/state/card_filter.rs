@@ -0,0 +1,450 @@
+//! 看板卡片过滤
+//!
+//! [`CardFilter`] 描述一组过滤条件，不同种类的条件必须同时满足，
+//! 同一种类的多个值（标签、状态、负责人）满足其一即可。
+//! 可以用 [`CardFilterBuilder`] 构建，也可以从过滤栏的查询文本解析（[`CardFilter::parse`]）：
+//!
+//! ```text
+//! 登录 tag:紧急 status:进行中 @me overdue todo:open due:<2025-01-31
+//! ```
+//!
+//! | 语法 | 含义 |
+//! |------|------|
+//! | 普通文本 | 标题或描述包含该文本（不区分大小写） |
+//! | `tag:名称` | 带有该标签 |
+//! | `status:名称` | 处于该状态 |
+//! | `@me` / `assignee:@user:server` | 分配给当前用户 / 指定用户 |
+//! | `overdue` | 已过期 |
+//! | `todo:open` / `todo:done` / `todo:none` | 有未完成待办 / 待办全部完成 / 没有待办 |
+//! | `due:<日期` / `due:>日期` / `due:日期..日期` | 截止时间范围（`YYYY-MM-DD`） |
+
+use anyhow::{Result, bail};
+use matrix_sdk::ruma::{OwnedUserId, UserId};
+use serde::{Deserialize, Serialize};
+
+use super::kanban_state::{KanbanAppState, KanbanCard};
+
+/// 待办完成情况
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoCompletion {
+    /// 没有待办
+    None,
+
+    /// 有未完成的待办
+    Open,
+
+    /// 有待办且全部完成
+    Done,
+}
+
+/// 卡片过滤条件
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CardFilter {
+    /// 标题或描述中包含的文本（小写）
+    #[serde(default)]
+    pub text: String,
+
+    /// 满足其一即可的标签 ID
+    #[serde(default)]
+    pub tag_ids: Vec<String>,
+
+    /// 满足其一即可的状态 ID
+    #[serde(default)]
+    pub statuses: Vec<String>,
+
+    /// 满足其一即可的负责人
+    #[serde(default)]
+    pub assignees: Vec<OwnedUserId>,
+
+    /// 分配给当前用户
+    #[serde(default)]
+    pub assigned_to_me: bool,
+
+    /// 只显示已过期的卡片
+    #[serde(default)]
+    pub overdue_only: bool,
+
+    /// 待办完成情况
+    #[serde(default)]
+    pub todo: Option<TodoCompletion>,
+
+    /// 截止时间不早于（Unix timestamp 秒）
+    #[serde(default)]
+    pub due_after: Option<u64>,
+
+    /// 截止时间不晚于（Unix timestamp 秒）
+    #[serde(default)]
+    pub due_before: Option<u64>,
+}
+
+impl CardFilter {
+    /// 是否没有任何过滤条件
+    pub fn is_empty(&self) -> bool {
+        *self == CardFilter::default()
+    }
+
+    /// 卡片是否满足所有过滤条件
+    pub fn matches(&self, card: &KanbanCard, current_user: Option<&UserId>) -> bool {
+        if !self.text.is_empty() {
+            let in_title = card.title.to_lowercase().contains(&self.text);
+            let in_description = card
+                .description
+                .as_ref()
+                .is_some_and(|d| d.to_lowercase().contains(&self.text));
+            if !in_title && !in_description {
+                return false;
+            }
+        }
+
+        if !self.tag_ids.is_empty() && !self.tag_ids.iter().any(|tag| card.tags.contains(tag)) {
+            return false;
+        }
+
+        if !self.statuses.is_empty() && !self.statuses.contains(&card.status) {
+            return false;
+        }
+
+        if !self.assignees.is_empty() && !self.assignees.iter().any(|a| card.is_assigned_to(a)) {
+            return false;
+        }
+
+        if self.assigned_to_me && !current_user.is_some_and(|me| card.is_assigned_to(me)) {
+            return false;
+        }
+
+        if self.overdue_only && !card.is_overdue() {
+            return false;
+        }
+
+        if let Some(todo) = self.todo {
+            let (completed, total) = card.todo_progress();
+            let actual = match total {
+                0 => TodoCompletion::None,
+                _ if completed < total => TodoCompletion::Open,
+                _ => TodoCompletion::Done,
+            };
+            if actual != todo {
+                return false;
+            }
+        }
+
+        if self.due_after.is_some() || self.due_before.is_some() {
+            let Some(end_time) = card.end_time else {
+                return false;
+            };
+            if self.due_after.is_some_and(|after| end_time < after)
+                || self.due_before.is_some_and(|before| end_time > before)
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// 解析过滤栏的查询文本
+    ///
+    /// 标签和状态按名称在整个看板的标签库和工作流中查找（不区分大小写），
+    /// 同名的标签 / 状态在不同列表中的 ID 都会被匹配。
+    pub fn parse(query: &str, state: &KanbanAppState) -> Result<CardFilter> {
+        let mut builder = CardFilterBuilder::new();
+        let mut words = Vec::new();
+
+        for token in query.split_whitespace() {
+            let lower = token.to_lowercase();
+            if let Some(name) = lower.strip_prefix("tag:") {
+                let ids: Vec<String> = state
+                    .space_tags
+                    .values()
+                    .flatten()
+                    .filter(|tag| tag.name.to_lowercase() == name)
+                    .map(|tag| tag.id.clone())
+                    .collect();
+                if ids.is_empty() {
+                    bail!("找不到标签 '{}'", name);
+                }
+                for id in ids {
+                    builder = builder.tag(id);
+                }
+            } else if let Some(name) = lower.strip_prefix("status:") {
+                let mut ids: Vec<String> = state
+                    .workflows
+                    .values()
+                    .cloned()
+                    .chain(std::iter::once(super::workflow::Workflow::legacy()))
+                    .flat_map(|workflow| workflow.statuses)
+                    .filter(|status| status.name.to_lowercase() == name || status.id == name)
+                    .map(|status| status.id)
+                    .collect();
+                ids.sort();
+                ids.dedup();
+                if ids.is_empty() {
+                    bail!("找不到状态 '{}'", name);
+                }
+                for id in ids {
+                    builder = builder.status(id);
+                }
+            } else if lower == "@me" {
+                builder = builder.assigned_to_me();
+            } else if let Some(user) = token.strip_prefix("assignee:") {
+                let user_id = UserId::parse(user)
+                    .map_err(|_| anyhow::anyhow!("无效的用户 ID '{}'", user))?;
+                builder = builder.assignee(user_id);
+            } else if lower == "overdue" {
+                builder = builder.overdue();
+            } else if let Some(value) = lower.strip_prefix("todo:") {
+                let todo = match value {
+                    "open" => TodoCompletion::Open,
+                    "done" => TodoCompletion::Done,
+                    "none" => TodoCompletion::None,
+                    other => bail!("无效的待办条件 '{}'（可选 open / done / none）", other),
+                };
+                builder = builder.todo(todo);
+            } else if let Some(range) = lower.strip_prefix("due:") {
+                let (after, before) = parse_due_range(range)?;
+                builder = builder.due_between(after, before);
+            } else {
+                words.push(lower);
+            }
+        }
+
+        Ok(builder.text(words.join(" ")).build())
+    }
+}
+
+/// 用于创建 [`CardFilter`] 的构建器
+///
+/// ## Example
+/// ```rust,ignore
+///   let filter = CardFilterBuilder::new()
+///     .text("登录")
+///     .status("in_progress")
+///     .assigned_to_me()
+///     .overdue()
+///     .build();
+/// ```
+#[derive(Debug, Default)]
+pub struct CardFilterBuilder {
+    filter: CardFilter,
+}
+
+impl CardFilterBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.filter.text = text.into().trim().to_lowercase();
+        self
+    }
+
+    pub fn tag(mut self, tag_id: impl Into<String>) -> Self {
+        self.filter.tag_ids.push(tag_id.into());
+        self
+    }
+
+    pub fn status(mut self, status: impl Into<String>) -> Self {
+        self.filter.statuses.push(status.into());
+        self
+    }
+
+    pub fn assignee(mut self, user_id: OwnedUserId) -> Self {
+        self.filter.assignees.push(user_id);
+        self
+    }
+
+    pub fn assigned_to_me(mut self) -> Self {
+        self.filter.assigned_to_me = true;
+        self
+    }
+
+    pub fn overdue(mut self) -> Self {
+        self.filter.overdue_only = true;
+        self
+    }
+
+    pub fn todo(mut self, todo: TodoCompletion) -> Self {
+        self.filter.todo = Some(todo);
+        self
+    }
+
+    pub fn due_between(mut self, after: Option<u64>, before: Option<u64>) -> Self {
+        if after.is_some() {
+            self.filter.due_after = after;
+        }
+        if before.is_some() {
+            self.filter.due_before = before;
+        }
+        self
+    }
+
+    pub fn build(self) -> CardFilter {
+        self.filter
+    }
+}
+
+/// 解析 `<日期`、`>日期`、`日期..日期` 或单个日期
+fn parse_due_range(range: &str) -> Result<(Option<u64>, Option<u64>)> {
+    if let Some(date) = range.strip_prefix('<') {
+        return Ok((None, Some(end_of_day(date)?)));
+    }
+    if let Some(date) = range.strip_prefix('>') {
+        return Ok((Some(start_of_day(date)?), None));
+    }
+    if let Some((from, to)) = range.split_once("..") {
+        let after = (!from.is_empty()).then(|| start_of_day(from)).transpose()?;
+        let before = (!to.is_empty()).then(|| end_of_day(to)).transpose()?;
+        return Ok((after, before));
+    }
+    Ok((Some(start_of_day(range)?), Some(end_of_day(range)?)))
+}
+
+fn parse_date(date: &str) -> Result<chrono::NaiveDate> {
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("无效的日期 '{}'（格式为 YYYY-MM-DD）", date))
+}
+
+fn start_of_day(date: &str) -> Result<u64> {
+    local_timestamp(parse_date(date)?.and_hms_opt(0, 0, 0).unwrap())
+}
+
+fn end_of_day(date: &str) -> Result<u64> {
+    local_timestamp(parse_date(date)?.and_hms_opt(23, 59, 59).unwrap())
+}
+
+fn local_timestamp(datetime: chrono::NaiveDateTime) -> Result<u64> {
+    use chrono::TimeZone;
+    chrono::Local
+        .from_local_datetime(&datetime)
+        .earliest()
+        .map(|dt| dt.timestamp().max(0) as u64)
+        .ok_or_else(|| anyhow::anyhow!("无效的本地时间"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kanban::state::kanban_state::SpaceTag;
+    use crate::kanban::state::workflow::Workflow;
+    use crate::kanban::test_util::room_id;
+
+    fn tag(id: &str, name: &str) -> SpaceTag {
+        let mut tag = SpaceTag::new(name.to_string(), "#EB5A46".to_string());
+        tag.id = id.to_string();
+        tag
+    }
+
+    /// 两个列表都有名为「紧急」的标签，其中一个列表定义了「进行中」状态
+    fn state() -> KanbanAppState {
+        let mut state = KanbanAppState::new();
+        state.space_tags.insert(
+            room_id("a"),
+            vec![tag("tag_a", "紧急"), tag("tag_b", "Bug")],
+        );
+        state
+            .space_tags
+            .insert(room_id("b"), vec![tag("tag_c", "紧急")]);
+        let workflow =
+            Workflow::parse_definition("待办\n进行中 [doing]\n完成 [done]", &Workflow::legacy())
+                .unwrap();
+        state.workflows.insert(room_id("a"), workflow);
+        state
+    }
+
+    fn day(date: &str) -> (u64, u64) {
+        (start_of_day(date).unwrap(), end_of_day(date).unwrap())
+    }
+
+    #[test]
+    fn empty_query_is_empty_filter() {
+        let filter = CardFilter::parse("   ", &state()).unwrap();
+        assert!(filter.is_empty());
+    }
+
+    #[test]
+    fn plain_words_become_lowercase_text() {
+        let filter = CardFilter::parse("登录  Page", &state()).unwrap();
+        assert_eq!(filter.text, "登录 page");
+    }
+
+    #[test]
+    fn tags_match_by_name_across_lists() {
+        let state = state();
+        let mut filter = CardFilter::parse("tag:紧急 tag:bug", &state).unwrap();
+        filter.tag_ids.sort();
+        assert_eq!(filter.tag_ids, vec!["tag_a", "tag_b", "tag_c"]);
+
+        let error = CardFilter::parse("tag:不存在", &state).unwrap_err();
+        assert!(error.to_string().contains("找不到标签"));
+    }
+
+    #[test]
+    fn statuses_match_by_name_or_id() {
+        let state = state();
+        let in_progress = state.workflows[&room_id("a")].statuses[1].id.clone();
+
+        let filter = CardFilter::parse("status:进行中", &state).unwrap();
+        assert_eq!(filter.statuses, vec![in_progress]);
+
+        // 旧版状态始终可用
+        let filter = CardFilter::parse("status:completed", &state).unwrap();
+        assert_eq!(filter.statuses, vec!["completed"]);
+
+        assert!(CardFilter::parse("status:阻塞", &state).is_err());
+    }
+
+    #[test]
+    fn parses_assignees_and_flags() {
+        let filter = CardFilter::parse(
+            "@ME assignee:@alice:example.org overdue todo:open",
+            &state(),
+        )
+        .unwrap();
+        assert!(filter.assigned_to_me);
+        assert!(filter.overdue_only);
+        assert_eq!(filter.todo, Some(TodoCompletion::Open));
+        assert_eq!(filter.assignees.len(), 1);
+        assert_eq!(filter.assignees[0].as_str(), "@alice:example.org");
+        assert!(filter.text.is_empty());
+
+        assert!(CardFilter::parse("assignee:alice", &state()).is_err());
+        assert!(CardFilter::parse("todo:maybe", &state()).is_err());
+    }
+
+    #[test]
+    fn parses_due_ranges() {
+        let (start, end) = day("2025-01-31");
+        assert_eq!(parse_due_range("<2025-01-31").unwrap(), (None, Some(end)));
+        assert_eq!(parse_due_range(">2025-01-31").unwrap(), (Some(start), None));
+        assert_eq!(
+            parse_due_range("2025-01-31").unwrap(),
+            (Some(start), Some(end))
+        );
+
+        let (from, _) = day("2025-01-01");
+        assert_eq!(
+            parse_due_range("2025-01-01..2025-01-31").unwrap(),
+            (Some(from), Some(end))
+        );
+        assert_eq!(parse_due_range("2025-01-01..").unwrap(), (Some(from), None));
+        assert_eq!(parse_due_range("..2025-01-31").unwrap(), (None, Some(end)));
+        assert_eq!(end - start, 24 * 60 * 60 - 1);
+    }
+
+    #[test]
+    fn rejects_invalid_due_dates() {
+        for range in ["<tomorrow", "2025-13-01", "2025-01-01..soon", "31/01/2025"] {
+            assert!(parse_due_range(range).is_err(), "{range}");
+        }
+    }
+
+    #[test]
+    fn due_query_sets_both_bounds() {
+        let (start, _) = day("2025-01-01");
+        let (_, end) = day("2025-01-31");
+        let filter = CardFilter::parse("due:>2025-01-01 due:<2025-01-31", &state()).unwrap();
+        assert_eq!(filter.due_after, Some(start));
+        assert_eq!(filter.due_before, Some(end));
+    }
+}
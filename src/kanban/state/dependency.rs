@@ -0,0 +1,498 @@
+//! 卡片之间的关联与阻塞关系
+//!
+//! 关联保存在发起方卡片的元数据（`links`）中，对方卡片不重复保存；
+//! 显示时从所有已加载的卡片中反查指向当前卡片的关联，按反向含义显示
+//! （例如 A「阻塞」B，在 B 上显示为「被阻塞于 A」）。
+//!
+//! 阻塞关系（`blocks` / `blocked_by`）构成有向图，添加关联时会拒绝产生环的关联。
+
+use std::collections::{HashMap, HashSet};
+
+use matrix_sdk::ruma::OwnedRoomId;
+use serde::{Deserialize, Serialize};
+
+use super::kanban_state::{KanbanAppState, KanbanCard};
+use super::workflow::{STATUS_COMPLETED, StatusCategory};
+
+/// 关联类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CardLinkType {
+    /// 本卡片阻塞对方
+    #[default]
+    Blocks,
+    /// 本卡片被对方阻塞
+    BlockedBy,
+    /// 相关
+    RelatesTo,
+    /// 本卡片与对方重复
+    Duplicates,
+}
+
+impl CardLinkType {
+    pub const ALL: [CardLinkType; 4] = [
+        CardLinkType::Blocks,
+        CardLinkType::BlockedBy,
+        CardLinkType::RelatesTo,
+        CardLinkType::Duplicates,
+    ];
+
+    /// 依次切换到下一种关联类型
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|t| *t == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// 从发起方看的显示名称
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CardLinkType::Blocks => "阻塞",
+            CardLinkType::BlockedBy => "被阻塞于",
+            CardLinkType::RelatesTo => "关联",
+            CardLinkType::Duplicates => "重复于",
+        }
+    }
+
+    /// 从对方看的显示名称
+    pub fn inverse_display_name(&self) -> &'static str {
+        match self {
+            CardLinkType::Blocks => "被阻塞于",
+            CardLinkType::BlockedBy => "阻塞",
+            CardLinkType::RelatesTo => "关联",
+            CardLinkType::Duplicates => "被重复于",
+        }
+    }
+}
+
+/// 一条关联
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CardLink {
+    pub link_type: CardLinkType,
+
+    /// 对方卡片 ID
+    pub card_id: OwnedRoomId,
+}
+
+/// 在某张卡片上显示的一条关联（包括其他卡片指向它的关联）
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkedCard {
+    /// 保存该关联的卡片
+    pub source_id: OwnedRoomId,
+    pub link: CardLink,
+
+    /// 另一张卡片
+    pub other_id: OwnedRoomId,
+
+    /// 从当前卡片看的显示名称
+    pub label: &'static str,
+}
+
+/// 添加关联失败的原因
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinkError {
+    SelfLink,
+    UnknownCard,
+    AlreadyLinked,
+    /// 会形成阻塞环（附带环上的卡片标题）
+    Cycle(Vec<String>),
+}
+
+impl std::fmt::Display for LinkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LinkError::SelfLink => write!(f, "不能关联卡片自身"),
+            LinkError::UnknownCard => write!(f, "找不到要关联的卡片"),
+            LinkError::AlreadyLinked => write!(f, "两张卡片之间已有关联"),
+            LinkError::Cycle(titles) => write!(f, "会形成循环阻塞: {}", titles.join(" → ")),
+        }
+    }
+}
+
+impl KanbanAppState {
+    /// 卡片的所有关联（自己保存的在前，其他卡片指向它的在后）
+    pub fn card_links(&self, card_id: &OwnedRoomId) -> Vec<LinkedCard> {
+        let mut links: Vec<LinkedCard> = self
+            .cards
+            .get(card_id)
+            .map(|card| {
+                card.links
+                    .iter()
+                    .map(|link| LinkedCard {
+                        source_id: card_id.clone(),
+                        link: link.clone(),
+                        other_id: link.card_id.clone(),
+                        label: link.link_type.display_name(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut incoming: Vec<LinkedCard> = self
+            .cards
+            .values()
+            .filter(|card| &card.id != card_id)
+            .flat_map(|card| {
+                card.links
+                    .iter()
+                    .filter(|link| &link.card_id == card_id)
+                    .map(|link| LinkedCard {
+                        source_id: card.id.clone(),
+                        link: link.clone(),
+                        other_id: card.id.clone(),
+                        label: link.link_type.inverse_display_name(),
+                    })
+            })
+            .collect();
+        incoming.sort_by(|a, b| a.other_id.cmp(&b.other_id));
+        links.extend(incoming);
+        links
+    }
+
+    /// 所有阻塞关系（阻塞方，被阻塞方）
+    fn blocking_edges(&self) -> Vec<(OwnedRoomId, OwnedRoomId)> {
+        self.cards
+            .values()
+            .flat_map(|card| {
+                card.links.iter().filter_map(|link| match link.link_type {
+                    CardLinkType::Blocks => Some((card.id.clone(), link.card_id.clone())),
+                    CardLinkType::BlockedBy => Some((link.card_id.clone(), card.id.clone())),
+                    CardLinkType::RelatesTo | CardLinkType::Duplicates => None,
+                })
+            })
+            .collect()
+    }
+
+    /// 阻塞该卡片且尚未完成的卡片
+    pub fn blockers(&self, card_id: &OwnedRoomId) -> Vec<&KanbanCard> {
+        let mut seen = HashSet::new();
+        self.blocking_edges()
+            .into_iter()
+            .filter(|(_, blocked)| blocked == card_id)
+            .filter_map(|(blocker, _)| self.cards.get(&blocker))
+            .filter(|blocker| !self.is_card_done(blocker))
+            .filter(|blocker| seen.insert(blocker.id.clone()))
+            .collect()
+    }
+
+    /// 卡片是否被未完成的卡片阻塞
+    pub fn is_blocked(&self, card_id: &OwnedRoomId) -> bool {
+        !self.blockers(card_id).is_empty()
+    }
+
+    /// 被阻塞卡片的提示文字，例如"「发布」仍被「测试」「评审」阻塞"；未被阻塞时返回 `None`
+    pub fn blocked_warning(&self, card_id: &OwnedRoomId) -> Option<String> {
+        let card = self.cards.get(card_id)?;
+        let reason = self.blocked_reason(card_id)?;
+        Some(format!("「{}」{}", card.title, reason))
+    }
+
+    /// 卡片被阻塞的原因，例如"仍被「测试」「评审」阻塞"；未被阻塞时返回 `None`
+    pub fn blocked_reason(&self, card_id: &OwnedRoomId) -> Option<String> {
+        let blockers = self.blockers(card_id);
+        if blockers.is_empty() {
+            return None;
+        }
+        let titles: String = blockers
+            .iter()
+            .map(|blocker| format!("「{}」", blocker.title))
+            .collect();
+        Some(format!("仍被{}阻塞", titles))
+    }
+
+    /// 卡片状态是否属于"已完成"分类
+    pub fn is_card_done(&self, card: &KanbanCard) -> bool {
+        self.workflow(&card.space_id).category(&card.status) == StatusCategory::Done
+    }
+
+    /// 卡片移到 `target_space_id` 后是否处于"已完成"分类
+    ///
+    /// 按卡片在目标列表中得到的状态（见 [`KanbanAppState::status_in_list`]）判断，
+    /// 旧版的已完成状态也算作已完成。
+    pub fn is_done_after_move(&self, card_id: &OwnedRoomId, target_space_id: &OwnedRoomId) -> bool {
+        let Some(card) = self.cards.get(card_id) else {
+            return false;
+        };
+        let status = self.status_in_list(card, target_space_id);
+        status == STATUS_COMPLETED
+            || self.workflow(target_space_id).category(&status) == StatusCategory::Done
+    }
+
+    /// 检查能否添加关联：不能关联自身、不能重复关联、阻塞关系不能形成环
+    pub fn check_link(
+        &self,
+        card_id: &OwnedRoomId,
+        link_type: CardLinkType,
+        target_id: &OwnedRoomId,
+    ) -> Result<(), LinkError> {
+        if card_id == target_id {
+            return Err(LinkError::SelfLink);
+        }
+        if !self.cards.contains_key(card_id) || !self.cards.contains_key(target_id) {
+            return Err(LinkError::UnknownCard);
+        }
+        let already_linked = self
+            .card_links(card_id)
+            .iter()
+            .any(|linked| &linked.other_id == target_id);
+        if already_linked {
+            return Err(LinkError::AlreadyLinked);
+        }
+
+        let (blocker, blocked) = match link_type {
+            CardLinkType::Blocks => (card_id, target_id),
+            CardLinkType::BlockedBy => (target_id, card_id),
+            CardLinkType::RelatesTo | CardLinkType::Duplicates => return Ok(()),
+        };
+
+        // 新增 blocker → blocked 后，如果 blocked 能沿阻塞关系回到 blocker 就形成了环
+        let mut graph: HashMap<OwnedRoomId, Vec<OwnedRoomId>> = HashMap::new();
+        for (from, to) in self.blocking_edges() {
+            graph.entry(from).or_default().push(to);
+        }
+        match find_path(&graph, blocked, blocker) {
+            Some(path) => {
+                let title = |id: &OwnedRoomId| {
+                    self.cards
+                        .get(id)
+                        .map_or_else(|| id.to_string(), |card| card.title.clone())
+                };
+                let titles = std::iter::once(blocker)
+                    .chain(path.iter())
+                    .map(title)
+                    .collect();
+                Err(LinkError::Cycle(titles))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// 按卡片 ID 或标题（不区分大小写，需唯一）查找卡片
+    pub fn find_card_by_reference(&self, reference: &str) -> Option<&KanbanCard> {
+        let reference = reference.trim();
+        if let Some(card) = self
+            .cards
+            .values()
+            .find(|card| card.id.as_str() == reference)
+        {
+            return Some(card);
+        }
+        let lower = reference.to_lowercase();
+        let mut matches = self
+            .cards
+            .values()
+            .filter(|card| card.title.to_lowercase() == lower);
+        let card = matches.next()?;
+        matches.next().is_none().then_some(card)
+    }
+}
+
+/// 深度优先查找 `from` 到 `to` 的路径（包含两端）
+fn find_path(
+    graph: &HashMap<OwnedRoomId, Vec<OwnedRoomId>>,
+    from: &OwnedRoomId,
+    to: &OwnedRoomId,
+) -> Option<Vec<OwnedRoomId>> {
+    let mut visited = HashSet::new();
+    let mut stack = vec![vec![from.clone()]];
+    while let Some(path) = stack.pop() {
+        let last = path.last()?;
+        if last == to {
+            return Some(path);
+        }
+        if !visited.insert(last.clone()) {
+            continue;
+        }
+        for next in graph.get(last).into_iter().flatten() {
+            let mut next_path = path.clone();
+            next_path.push(next.clone());
+            stack.push(next_path);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kanban::state::workflow::Workflow;
+    use crate::kanban::test_util::room_id;
+
+    /// 一个列表中有卡片 a、b、c、d（标题为大写字母），`links` 为（发起方，关联类型，对方）
+    fn state_with_links(links: &[(&str, CardLinkType, &str)]) -> KanbanAppState {
+        let mut state = KanbanAppState::default();
+        for name in ["a", "b", "c", "d"] {
+            let card = KanbanCard::new(room_id(name), name.to_uppercase(), room_id("list"));
+            state.cards.insert(card.id.clone(), card);
+        }
+        for (source, link_type, target) in links {
+            let card = state.cards.get_mut(&room_id(source)).unwrap();
+            card.links.push(CardLink {
+                link_type: *link_type,
+                card_id: room_id(target),
+            });
+        }
+        state
+    }
+
+    fn cycle(titles: &[&str]) -> Result<(), LinkError> {
+        Err(LinkError::Cycle(
+            titles.iter().map(|title| title.to_string()).collect(),
+        ))
+    }
+
+    #[test]
+    fn check_link_rejects_self_links() {
+        let state = state_with_links(&[]);
+        for link_type in CardLinkType::ALL {
+            assert_eq!(
+                state.check_link(&room_id("a"), link_type, &room_id("a")),
+                Err(LinkError::SelfLink)
+            );
+        }
+        assert_eq!(
+            state.check_link(&room_id("a"), CardLinkType::Blocks, &room_id("x")),
+            Err(LinkError::UnknownCard)
+        );
+    }
+
+    #[test]
+    fn check_link_rejects_duplicate_links() {
+        let state = state_with_links(&[("a", CardLinkType::RelatesTo, "b")]);
+        // 发起方和对方都不能再添加任何类型的关联
+        for link_type in CardLinkType::ALL {
+            assert_eq!(
+                state.check_link(&room_id("a"), link_type, &room_id("b")),
+                Err(LinkError::AlreadyLinked)
+            );
+            assert_eq!(
+                state.check_link(&room_id("b"), link_type, &room_id("a")),
+                Err(LinkError::AlreadyLinked)
+            );
+        }
+        assert_eq!(
+            state.check_link(&room_id("a"), CardLinkType::Blocks, &room_id("c")),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn check_link_rejects_two_card_cycles() {
+        // a 阻塞 b（保存在 b 上）；b 再阻塞 a 会形成两张卡片的环
+        let state = state_with_links(&[("b", CardLinkType::BlockedBy, "a")]);
+        assert_eq!(
+            state.check_link(&room_id("b"), CardLinkType::Blocks, &room_id("a")),
+            Err(LinkError::AlreadyLinked)
+        );
+        assert_eq!(
+            state.check_link(&room_id("a"), CardLinkType::BlockedBy, &room_id("b")),
+            Err(LinkError::AlreadyLinked)
+        );
+    }
+
+    #[test]
+    fn check_link_rejects_longer_cycles() {
+        // a → b → c，混合使用 blocks 和 blocked_by 保存
+        let state = state_with_links(&[
+            ("a", CardLinkType::Blocks, "b"),
+            ("c", CardLinkType::BlockedBy, "b"),
+        ]);
+        assert_eq!(
+            state.check_link(&room_id("c"), CardLinkType::Blocks, &room_id("a")),
+            cycle(&["C", "A", "B", "C"])
+        );
+        assert_eq!(
+            state.check_link(&room_id("a"), CardLinkType::BlockedBy, &room_id("c")),
+            cycle(&["C", "A", "B", "C"])
+        );
+        // 同方向的阻塞不会形成环
+        assert_eq!(
+            state.check_link(&room_id("a"), CardLinkType::Blocks, &room_id("c")),
+            Ok(())
+        );
+
+        let state = state_with_links(&[
+            ("a", CardLinkType::Blocks, "b"),
+            ("b", CardLinkType::Blocks, "c"),
+            ("c", CardLinkType::Blocks, "d"),
+        ]);
+        assert_eq!(
+            state.check_link(&room_id("d"), CardLinkType::Blocks, &room_id("a")),
+            cycle(&["D", "A", "B", "C", "D"])
+        );
+    }
+
+    #[test]
+    fn check_link_ignores_non_blocking_links() {
+        // 关联 / 重复不是阻塞关系，不会形成环
+        let state = state_with_links(&[
+            ("a", CardLinkType::RelatesTo, "b"),
+            ("b", CardLinkType::Duplicates, "c"),
+        ]);
+        assert_eq!(
+            state.check_link(&room_id("c"), CardLinkType::Blocks, &room_id("a")),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn find_path_terminates_on_existing_cycles() {
+        // 数据中已有的环（例如并发编辑产生的）不会导致死循环
+        let state = state_with_links(&[
+            ("a", CardLinkType::Blocks, "b"),
+            ("b", CardLinkType::Blocks, "a"),
+        ]);
+        assert_eq!(
+            state.check_link(&room_id("c"), CardLinkType::Blocks, &room_id("a")),
+            Ok(())
+        );
+
+        let mut graph: HashMap<OwnedRoomId, Vec<OwnedRoomId>> = HashMap::new();
+        graph.insert(room_id("a"), vec![room_id("b")]);
+        graph.insert(room_id("b"), vec![room_id("a"), room_id("c")]);
+        assert_eq!(
+            find_path(&graph, &room_id("a"), &room_id("c")),
+            Some(vec![room_id("a"), room_id("b"), room_id("c")])
+        );
+        assert_eq!(find_path(&graph, &room_id("a"), &room_id("d")), None);
+    }
+
+    #[test]
+    fn blockers_skip_done_cards() {
+        let mut state = state_with_links(&[
+            ("a", CardLinkType::Blocks, "c"),
+            ("b", CardLinkType::Blocks, "c"),
+        ]);
+        assert_eq!(state.blockers(&room_id("c")).len(), 2);
+
+        state.cards.get_mut(&room_id("a")).unwrap().status =
+            crate::kanban::state::workflow::STATUS_COMPLETED.to_string();
+        let blockers = state.blockers(&room_id("c"));
+        assert_eq!(blockers.len(), 1);
+        assert_eq!(blockers[0].id, room_id("b"));
+        assert_eq!(
+            state.blocked_warning(&room_id("c")).as_deref(),
+            Some("「C」仍被「B」阻塞")
+        );
+    }
+
+    #[test]
+    fn done_after_move_uses_the_status_in_the_target_list() {
+        let mut state = state_with_links(&[]);
+        let done = room_id("done");
+        assert!(!state.is_done_after_move(&room_id("a"), &done));
+
+        // 没有自定义工作流的列表：旧版的已完成状态保持不变
+        state.cards.get_mut(&room_id("a")).unwrap().status = STATUS_COMPLETED.to_string();
+        assert!(state.is_done_after_move(&room_id("a"), &done));
+
+        // 自定义工作流：已完成状态迁移到目标列表的已完成分类
+        let workflow =
+            Workflow::parse_definition("Todo -> Shipped\nShipped [done]", &Workflow::legacy())
+                .unwrap();
+        state.workflows.insert(done.clone(), workflow);
+        assert!(state.is_done_after_move(&room_id("a"), &done));
+
+        // 未完成的卡片迁移到初始状态，即使目标列表有已完成状态也不算完成
+        assert!(!state.is_done_after_move(&room_id("b"), &done));
+    }
+}
@@ -0,0 +1,859 @@
+//! 卡片与标签库的历史版本，以及本次会话中的撤销 / 重做
+//!
+//! 卡片元数据每次保存都会发送一条新的 `__KANBAN_METADATA__:` 消息，待办和标签库每次保存
+//! 都是新的 `m.kanban.card.todos` / `m.space.tag_library` 状态事件。旧版本仍保留在 Room
+//! 时间线中，[`crate::kanban::MatrixKanbanAdapter::load_card_history`] 和
+//! [`crate::kanban::MatrixKanbanAdapter::load_tag_library_history`] 按时间顺序把它们读出来。
+//! 恢复历史版本就是把旧版本的字段重新保存一次，所以恢复本身也会成为一个新版本。
+//!
+//! 撤销 / 重做只在内存中记录：处理修改卡片或标签库的 Action 前后各取一次快照，
+//! 内容有变化时记为一步（见 [`KanbanAppState::begin_undo`]）。标签库的修改要等服务器
+//! 返回新的标签库后才知道结果，所以在 [`KanbanAppState::tags_loaded_for_undo`] 中记录。
+
+use std::collections::HashMap;
+
+use matrix_sdk::ruma::OwnedRoomId;
+
+use super::kanban_actions::KanbanActions;
+use super::kanban_state::{KanbanAppState, KanbanCard, SpaceTag, TodoItem};
+
+/// 最多保留的撤销步数
+const MAX_UNDO_ENTRIES: usize = 50;
+
+/// 字段变化中文本的最大长度（字符）
+const MAX_CHANGE_TEXT_CHARS: usize = 80;
+
+// ========== 历史版本 ==========
+
+/// 卡片元数据消息中可以比较和恢复的字段
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CardFields {
+    pub title: String,
+    pub description: Option<String>,
+    /// 随描述一起恢复，不参与比较
+    pub description_html: Option<String>,
+    pub tags: Vec<String>,
+    pub end_time: Option<u64>,
+}
+
+/// 卡片历史中可以比较和恢复的字段
+///
+/// 历史只读取时间线中最近的一部分事件，读到第一条元数据消息 / 待办事件之前对应的字段
+/// 是未知的（`None`）。比较和恢复时跳过未知的字段，不会把它们当作空内容写回卡片。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CardSnapshot {
+    pub fields: Option<CardFields>,
+    pub todos: Option<Vec<TodoItem>>,
+}
+
+impl CardSnapshot {
+    /// 所有字段都已知且为空（Room 刚创建时的内容）
+    pub fn empty() -> Self {
+        Self {
+            fields: Some(CardFields::default()),
+            todos: Some(Vec::new()),
+        }
+    }
+
+    pub fn from_card(card: &KanbanCard) -> Self {
+        Self {
+            fields: Some(CardFields {
+                title: card.title.clone(),
+                description: card.description.clone(),
+                description_html: card.description_html.clone(),
+                tags: card.tags.clone(),
+                end_time: card.end_time,
+            }),
+            todos: Some(card.todos.clone()),
+        }
+    }
+
+    /// 把快照中已知的字段写回卡片
+    pub fn apply_to(&self, card: &mut KanbanCard) {
+        if let Some(fields) = &self.fields {
+            card.title = fields.title.clone();
+            card.description = fields.description.clone();
+            card.description_html = fields.description_html.clone();
+            card.tags = fields.tags.clone();
+            card.end_time = fields.end_time;
+        }
+        if let Some(todos) = &self.todos {
+            card.todos = todos.clone();
+        }
+    }
+}
+
+/// 卡片的一个历史版本（一次元数据或待办的保存）
+#[derive(Debug, Clone)]
+pub struct CardRevision {
+    /// 保存该版本的事件 ID
+    pub event_id: String,
+
+    /// 保存者
+    pub sender: String,
+
+    /// 保存时间（Unix timestamp 秒）
+    pub timestamp: u64,
+
+    /// 保存后卡片的完整字段
+    pub snapshot: CardSnapshot,
+}
+
+/// 标签库的一个历史版本
+#[derive(Debug, Clone)]
+pub struct TagLibraryRevision {
+    pub event_id: String,
+    pub sender: String,
+    pub timestamp: u64,
+    pub tags: Vec<SpaceTag>,
+}
+
+/// 当前打开的历史面板；版本为 `None` 表示正在加载
+#[derive(Debug, Clone)]
+pub enum HistoryPanel {
+    Card {
+        card_id: OwnedRoomId,
+        revisions: Option<Vec<CardRevision>>,
+    },
+    TagLibrary {
+        space_id: OwnedRoomId,
+        revisions: Option<Vec<TagLibraryRevision>>,
+    },
+}
+
+/// 一个字段的变化
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub before: String,
+    pub after: String,
+}
+
+impl FieldChange {
+    /// 例如 `标题: 旧标题 → 新标题`
+    pub fn describe(&self) -> String {
+        format!("{}: {} → {}", self.field, self.before, self.after)
+    }
+}
+
+/// 历史面板中的一项（最新的在前）
+#[derive(Debug, Clone)]
+pub struct HistoryItem {
+    /// 版本的事件 ID（用于恢复）
+    pub event_id: String,
+
+    /// 例如 `2024-05-01 10:30 · @alice:example.org`
+    pub header: String,
+
+    /// 与上一个版本相比的变化；最早的版本与空内容比较
+    pub changes: Vec<FieldChange>,
+}
+
+/// 比较卡片的两个版本；`tag_name` 把标签 ID 转换为显示名称，任一版本中未知的字段不比较
+pub fn diff_cards(
+    before: &CardSnapshot,
+    after: &CardSnapshot,
+    tag_name: impl Fn(&str) -> String,
+) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    let mut push = |field, before: String, after: String| {
+        if before != after {
+            changes.push(FieldChange {
+                field,
+                before: shorten(&before),
+                after: shorten(&after),
+            });
+        }
+    };
+
+    if let (Some(before), Some(after)) = (&before.fields, &after.fields) {
+        push("标题", before.title.clone(), after.title.clone());
+        push(
+            "描述",
+            before.description.clone().unwrap_or_default(),
+            after.description.clone().unwrap_or_default(),
+        );
+        let tags_text = |tags: &[String]| {
+            tags.iter()
+                .map(|tag| tag_name(tag.as_str()))
+                .collect::<Vec<_>>()
+                .join("、")
+        };
+        push("标签", tags_text(&before.tags), tags_text(&after.tags));
+        let due_text = |end_time: Option<u64>| {
+            end_time
+                .map(crate::kanban::reminders::format_due_time)
+                .unwrap_or_default()
+        };
+        push(
+            "截止时间",
+            due_text(before.end_time),
+            due_text(after.end_time),
+        );
+    }
+    if let (Some(before), Some(after)) = (&before.todos, &after.todos) {
+        let todos_text = |todos: &[TodoItem]| {
+            todos
+                .iter()
+                .map(|todo| {
+                    let mark = if todo.completed { "☑" } else { "☐" };
+                    format!("{} {}", mark, todo.text)
+                })
+                .collect::<Vec<_>>()
+                .join("；")
+        };
+        push("待办", todos_text(before), todos_text(after));
+    }
+    changes
+}
+
+/// 比较标签库的两个版本
+pub fn diff_tag_libraries(before: &[SpaceTag], after: &[SpaceTag]) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    for tag in after {
+        match before.iter().find(|old| old.id == tag.id) {
+            None => changes.push(FieldChange {
+                field: "新增标签",
+                before: String::new(),
+                after: format!("{} {}", tag.name, tag.color),
+            }),
+            Some(old) if old.name != tag.name || old.color != tag.color => {
+                changes.push(FieldChange {
+                    field: "修改标签",
+                    before: format!("{} {}", old.name, old.color),
+                    after: format!("{} {}", tag.name, tag.color),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    for old in before {
+        if !after.iter().any(|tag| tag.id == old.id) {
+            changes.push(FieldChange {
+                field: "删除标签",
+                before: format!("{} {}", old.name, old.color),
+                after: String::new(),
+            });
+        }
+    }
+    changes
+}
+
+/// 过长的文本截断，空文本显示为「无」
+fn shorten(text: &str) -> String {
+    let text = text.trim().replace('\n', " ");
+    if text.is_empty() {
+        return "无".to_string();
+    }
+    let mut short: String = text.chars().take(MAX_CHANGE_TEXT_CHARS).collect();
+    if text.chars().count() > MAX_CHANGE_TEXT_CHARS {
+        short.push('…');
+    }
+    short
+}
+
+fn revision_header(timestamp: u64, sender: &str) -> String {
+    let time = chrono::DateTime::from_timestamp(timestamp as i64, 0)
+        .map(|dt| {
+            dt.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default();
+    format!("{} · {}", time, sender)
+}
+
+// ========== 撤销 / 重做 ==========
+
+/// 撤销记录中保存的内容
+#[derive(Debug, Clone)]
+pub enum UndoState {
+    Card(Box<KanbanCard>),
+    TagLibrary {
+        space_id: OwnedRoomId,
+        tags: Vec<SpaceTag>,
+    },
+}
+
+/// 一步可以撤销的修改
+#[derive(Debug, Clone)]
+pub struct UndoEntry {
+    /// 例如 `修改标题`
+    pub label: &'static str,
+    pub before: UndoState,
+    pub after: UndoState,
+}
+
+/// 正在处理的 Action 修改前的快照
+#[derive(Debug)]
+pub struct PendingUndo {
+    label: &'static str,
+    before: UndoState,
+}
+
+/// 本次会话中的撤销 / 重做记录
+#[derive(Debug, Clone, Default)]
+pub struct UndoHistory {
+    undo: Vec<UndoEntry>,
+    redo: Vec<UndoEntry>,
+    /// 等待服务器返回新标签库的修改（Space ID -> 修改名称, 修改前的标签库）
+    pending_tags: HashMap<OwnedRoomId, (&'static str, Vec<SpaceTag>)>,
+}
+
+impl UndoHistory {
+    /// 记录一步新的修改（清空重做记录）
+    pub fn push(&mut self, entry: UndoEntry) {
+        self.undo.push(entry);
+        if self.undo.len() > MAX_UNDO_ENTRIES {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    /// 取出要撤销的一步，返回要恢复的内容
+    pub fn take_undo(&mut self) -> Option<(&'static str, UndoState)> {
+        let entry = self.undo.pop()?;
+        let result = (entry.label, entry.before.clone());
+        self.redo.push(entry);
+        Some(result)
+    }
+
+    /// 取出要重做的一步，返回要恢复的内容
+    pub fn take_redo(&mut self) -> Option<(&'static str, UndoState)> {
+        let entry = self.redo.pop()?;
+        let result = (entry.label, entry.after.clone());
+        self.undo.push(entry);
+        Some(result)
+    }
+
+    /// 下一步撤销的名称
+    pub fn undo_label(&self) -> Option<&'static str> {
+        self.undo.last().map(|entry| entry.label)
+    }
+
+    /// 下一步重做的名称
+    pub fn redo_label(&self) -> Option<&'static str> {
+        self.redo.last().map(|entry| entry.label)
+    }
+}
+
+/// 修改的对象
+enum UndoTarget<'a> {
+    Card(&'a OwnedRoomId),
+    TagLibrary(&'a OwnedRoomId),
+}
+
+/// Action 修改的对象和修改名称；不能撤销的 Action 返回 `None`
+fn undo_target(action: &KanbanActions) -> Option<(UndoTarget<'_>, &'static str)> {
+    use KanbanActions::*;
+    let (target, label) = match action {
+        UpdateCardTitle { card_id, .. } => (UndoTarget::Card(card_id), "修改标题"),
+        UpdateCardDescription { card_id, .. } => (UndoTarget::Card(card_id), "修改描述"),
+        UpdateCardStatus { card_id, .. } => (UndoTarget::Card(card_id), "修改状态"),
+        AddTodo { card_id, .. } => (UndoTarget::Card(card_id), "添加待办"),
+        ToggleTodo { card_id, .. } => (UndoTarget::Card(card_id), "完成待办"),
+        UpdateTodoText { card_id, .. } => (UndoTarget::Card(card_id), "修改待办"),
+        DeleteTodo { card_id, .. } => (UndoTarget::Card(card_id), "删除待办"),
+        SetTodoAssignee { card_id, .. } | SetTodoDueTime { card_id, .. } => {
+            (UndoTarget::Card(card_id), "修改待办")
+        }
+        AddTag { card_id, .. }
+        | AddTagToCard { card_id, .. }
+        | AddTagToCardByName { card_id, .. } => (UndoTarget::Card(card_id), "添加标签"),
+        RemoveTag { card_id, .. } | RemoveTagFromCard { card_id, .. } => {
+            (UndoTarget::Card(card_id), "移除标签")
+        }
+        SetEndTime { card_id, .. } | DropCardOnDate { card_id, .. } => {
+            (UndoTarget::Card(card_id), "设置截止时间")
+        }
+        ClearEndTime { card_id } => (UndoTarget::Card(card_id), "清除截止时间"),
+        AssignCard { card_id, .. } | UnassignCard { card_id, .. } => {
+            (UndoTarget::Card(card_id), "修改负责人")
+        }
+        SetCardRecurrence { card_id, .. } => (UndoTarget::Card(card_id), "修改周期"),
+        SetCardCover { card_id, .. } => (UndoTarget::Card(card_id), "修改封面"),
+        SetCardEstimate { card_id, .. } => (UndoTarget::Card(card_id), "修改估时"),
+        RestoreCardRevision { card_id, .. } => (UndoTarget::Card(card_id), "恢复历史版本"),
+        CreateSpaceTag { space_id, .. } => (UndoTarget::TagLibrary(space_id), "创建标签"),
+        UpdateSpaceTag { space_id, .. } => (UndoTarget::TagLibrary(space_id), "修改标签"),
+        DeleteSpaceTag { space_id, .. } => (UndoTarget::TagLibrary(space_id), "删除标签"),
+        RestoreTagLibraryRevision { space_id, .. } => {
+            (UndoTarget::TagLibrary(space_id), "恢复标签库")
+        }
+        _ => return None,
+    };
+    Some((target, label))
+}
+
+/// 比较时忽略更新时间
+fn same_card(a: &KanbanCard, b: &KanbanCard) -> bool {
+    let normalize = |card: &KanbanCard| {
+        let mut card = card.clone();
+        card.updated_at = 0;
+        serde_json::to_value(card).ok()
+    };
+    normalize(a) == normalize(b)
+}
+
+fn same_tags(a: &[SpaceTag], b: &[SpaceTag]) -> bool {
+    serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+}
+
+impl KanbanAppState {
+    /// 处理 Action 前调用：记录修改前的快照
+    pub fn begin_undo(&self, action: &KanbanActions) -> Option<PendingUndo> {
+        let (target, label) = undo_target(action)?;
+        let before = match target {
+            UndoTarget::Card(card_id) => {
+                UndoState::Card(Box::new(self.cards.get(card_id)?.clone()))
+            }
+            UndoTarget::TagLibrary(space_id) => UndoState::TagLibrary {
+                space_id: space_id.clone(),
+                tags: self.space_tags.get(space_id).cloned().unwrap_or_default(),
+            },
+        };
+        Some(PendingUndo { label, before })
+    }
+
+    /// 处理 Action 后调用：内容有变化时记为一步修改
+    pub fn finish_undo(&mut self, pending: PendingUndo) {
+        let PendingUndo { label, before } = pending;
+        let after = match &before {
+            UndoState::Card(card) => match self.cards.get(&card.id) {
+                Some(current) if !same_card(card, current) => {
+                    UndoState::Card(Box::new(current.clone()))
+                }
+                _ => return,
+            },
+            UndoState::TagLibrary { space_id, tags } => {
+                let current = self.space_tags.get(space_id).cloned().unwrap_or_default();
+                if same_tags(tags, &current) {
+                    // 创建 / 修改 / 删除标签要等服务器返回新的标签库
+                    self.undo_history
+                        .pending_tags
+                        .insert(space_id.clone(), (label, tags.clone()));
+                    return;
+                }
+                UndoState::TagLibrary {
+                    space_id: space_id.clone(),
+                    tags: current,
+                }
+            }
+        };
+        self.undo_history.push(UndoEntry {
+            label,
+            before,
+            after,
+        });
+    }
+
+    /// 收到服务器返回的标签库时调用：完成等待中的标签库修改
+    pub fn tags_loaded_for_undo(&mut self, space_id: &OwnedRoomId, tags: &[SpaceTag]) {
+        let Some((label, before)) = self.undo_history.pending_tags.remove(space_id) else {
+            return;
+        };
+        if same_tags(&before, tags) {
+            return;
+        }
+        self.undo_history.push(UndoEntry {
+            label,
+            before: UndoState::TagLibrary {
+                space_id: space_id.clone(),
+                tags: before,
+            },
+            after: UndoState::TagLibrary {
+                space_id: space_id.clone(),
+                tags: tags.to_vec(),
+            },
+        });
+    }
+
+    /// 历史面板中的版本（最新的在前）
+    pub fn history_items(&self) -> Vec<HistoryItem> {
+        match &self.history_panel {
+            Some(HistoryPanel::Card {
+                card_id,
+                revisions: Some(revisions),
+            }) => {
+                let space_id = self.cards.get(card_id).map(|card| &card.space_id);
+                let tag_name = |tag_id: &str| {
+                    space_id
+                        .and_then(|space_id| self.space_tags.get(space_id))
+                        .and_then(|tags| tags.iter().find(|tag| tag.id == tag_id))
+                        .map_or_else(|| tag_id.to_string(), |tag| tag.name.clone())
+                };
+                let empty = CardSnapshot::empty();
+                let mut items: Vec<HistoryItem> = revisions
+                    .iter()
+                    .enumerate()
+                    .map(|(index, revision)| {
+                        let previous = index
+                            .checked_sub(1)
+                            .map_or(&empty, |prev| &revisions[prev].snapshot);
+                        HistoryItem {
+                            event_id: revision.event_id.clone(),
+                            header: revision_header(revision.timestamp, &revision.sender),
+                            changes: diff_cards(previous, &revision.snapshot, &tag_name),
+                        }
+                    })
+                    .collect();
+                items.reverse();
+                items
+            }
+            Some(HistoryPanel::TagLibrary {
+                revisions: Some(revisions),
+                ..
+            }) => {
+                let mut items: Vec<HistoryItem> = revisions
+                    .iter()
+                    .enumerate()
+                    .map(|(index, revision)| {
+                        let previous = index
+                            .checked_sub(1)
+                            .map_or(&[][..], |prev| revisions[prev].tags.as_slice());
+                        HistoryItem {
+                            event_id: revision.event_id.clone(),
+                            header: revision_header(revision.timestamp, &revision.sender),
+                            changes: diff_tag_libraries(previous, &revision.tags),
+                        }
+                    })
+                    .collect();
+                items.reverse();
+                items
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// 历史面板中某个卡片版本
+    pub fn card_revision(&self, event_id: &str) -> Option<&CardRevision> {
+        match &self.history_panel {
+            Some(HistoryPanel::Card {
+                revisions: Some(revisions),
+                ..
+            }) => revisions
+                .iter()
+                .find(|revision| revision.event_id == event_id),
+            _ => None,
+        }
+    }
+
+    /// 历史面板中某个标签库版本
+    pub fn tag_library_revision(&self, event_id: &str) -> Option<&TagLibraryRevision> {
+        match &self.history_panel {
+            Some(HistoryPanel::TagLibrary {
+                revisions: Some(revisions),
+                ..
+            }) => revisions
+                .iter()
+                .find(|revision| revision.event_id == event_id),
+            _ => None,
+        }
+    }
+}
+
+/// 撤销 / 重做时把保存的卡片内容写回当前卡片（保留所在列表、排序、归档状态和工时记录）
+///
+/// 负责人和状态不在这里写回，见 [`undo_replay_actions`]。
+pub fn restore_card(current: &mut KanbanCard, saved: &KanbanCard) {
+    current.title = saved.title.clone();
+    current.description = saved.description.clone();
+    current.description_html = saved.description_html.clone();
+    current.tags = saved.tags.clone();
+    current.end_time = saved.end_time;
+    current.recurrence = saved.recurrence.clone();
+    current.links = saved.links.clone();
+    current.cover = saved.cover.clone();
+    current.estimate = saved.estimate.clone();
+    current.todos = saved.todos.clone();
+}
+
+/// 撤销 / 重做时恢复负责人和状态需要重新执行的 Action
+///
+/// 修改负责人要同时邀请 / 移出卡片 Room 的成员，修改状态要检查工作流的流转规则和 WIP 上限
+/// 并记录状态变更活动，所以交给对应的 Action 处理，而不是直接写回字段。
+pub fn undo_replay_actions(current: &KanbanCard, saved: &KanbanCard) -> Vec<KanbanActions> {
+    let mut actions = Vec::new();
+    for user_id in &current.assignees {
+        if !saved.is_assigned_to(user_id) {
+            actions.push(KanbanActions::UnassignCard {
+                card_id: current.id.clone(),
+                user_id: user_id.clone(),
+            });
+        }
+    }
+    for user_id in &saved.assignees {
+        if !current.is_assigned_to(user_id) {
+            actions.push(KanbanActions::AssignCard {
+                card_id: current.id.clone(),
+                user_id: user_id.clone(),
+            });
+        }
+    }
+    if current.status != saved.status {
+        actions.push(KanbanActions::UpdateCardStatus {
+            card_id: current.id.clone(),
+            status: saved.status.clone(),
+        });
+    }
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kanban::test_util::{room_id, user};
+
+    fn card(title: &str) -> KanbanCard {
+        KanbanCard::new(room_id("card"), title.to_string(), room_id("list"))
+    }
+
+    fn tag(id: &str, name: &str, color: &str) -> SpaceTag {
+        let mut tag = SpaceTag::new(name.to_string(), color.to_string());
+        tag.id = id.to_string();
+        tag
+    }
+
+    #[test]
+    fn diff_cards_skips_unknown_fields() {
+        let mut before = CardSnapshot::from_card(&card("旧标题"));
+        let mut after = CardSnapshot::from_card(&card("新标题"));
+        after.todos = Some(vec![TodoItem::new("写测试".to_string())]);
+
+        let changes = diff_cards(&before, &after, |tag| tag.to_string());
+        assert_eq!(
+            changes,
+            vec![
+                FieldChange {
+                    field: "标题",
+                    before: "旧标题".to_string(),
+                    after: "新标题".to_string(),
+                },
+                FieldChange {
+                    field: "待办",
+                    before: "无".to_string(),
+                    after: "☐ 写测试".to_string(),
+                },
+            ]
+        );
+
+        // 任一版本中未知的字段不比较
+        before.todos = None;
+        let changes = diff_cards(&before, &after, |tag| tag.to_string());
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "标题");
+
+        after.fields = None;
+        assert!(diff_cards(&before, &after, |tag| tag.to_string()).is_empty());
+    }
+
+    #[test]
+    fn diff_cards_shows_tag_names() {
+        let before = CardSnapshot::from_card(&card("卡片"));
+        let mut tagged = card("卡片");
+        tagged.tags = vec!["tag_a".to_string(), "tag_b".to_string()];
+        let after = CardSnapshot::from_card(&tagged);
+
+        let changes = diff_cards(&before, &after, |tag| tag.trim_start_matches("tag_").into());
+        assert_eq!(
+            changes,
+            vec![FieldChange {
+                field: "标签",
+                before: "无".to_string(),
+                after: "a、b".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_tag_libraries_reports_added_changed_and_removed_tags() {
+        let before = vec![tag("a", "紧急", "#ff0000"), tag("b", "文档", "#00ff00")];
+        let after = vec![tag("a", "紧急", "#ff8800"), tag("c", "测试", "#0000ff")];
+
+        let changes = diff_tag_libraries(&before, &after);
+        assert_eq!(
+            changes,
+            vec![
+                FieldChange {
+                    field: "修改标签",
+                    before: "紧急 #ff0000".to_string(),
+                    after: "紧急 #ff8800".to_string(),
+                },
+                FieldChange {
+                    field: "新增标签",
+                    before: String::new(),
+                    after: "测试 #0000ff".to_string(),
+                },
+                FieldChange {
+                    field: "删除标签",
+                    before: "文档 #00ff00".to_string(),
+                    after: String::new(),
+                },
+            ]
+        );
+        assert!(diff_tag_libraries(&before, &before).is_empty());
+        assert_eq!(diff_tag_libraries(&[], &after).len(), 2);
+    }
+
+    #[test]
+    fn restore_card_keeps_list_rank_and_status() {
+        let mut saved = card("旧标题");
+        saved.description = Some("旧描述".to_string());
+        saved.tags = vec!["tag_a".to_string()];
+        saved.end_time = Some(1_700_000_000);
+        saved.todos = vec![TodoItem::new("写测试".to_string())];
+        saved.status = "todo".to_string();
+
+        let mut current = card("新标题");
+        current.space_id = room_id("other_list");
+        current.rank = "5".to_string();
+        current.status = "done".to_string();
+        current.archived_at = Some(1_700_000_100);
+        current.assignees = vec![user("alice")];
+
+        restore_card(&mut current, &saved);
+        assert_eq!(current.title, "旧标题");
+        assert_eq!(current.description.as_deref(), Some("旧描述"));
+        assert_eq!(current.tags, saved.tags);
+        assert_eq!(current.end_time, saved.end_time);
+        assert_eq!(current.todos.len(), 1);
+        // 所在列表、排序、归档状态、负责人和状态不变
+        assert_eq!(current.space_id, room_id("other_list"));
+        assert_eq!(current.rank, "5");
+        assert_eq!(current.archived_at, Some(1_700_000_100));
+        assert_eq!(current.assignees, vec![user("alice")]);
+        assert_eq!(current.status, "done");
+    }
+
+    #[test]
+    fn undo_replay_actions_restore_assignees_and_status() {
+        let mut saved = card("卡片");
+        saved.assignees = vec![user("alice"), user("bob")];
+        saved.status = "todo".to_string();
+        let mut current = card("卡片");
+        current.assignees = vec![user("bob"), user("carol")];
+        current.status = "done".to_string();
+
+        let actions = undo_replay_actions(&current, &saved);
+        assert_eq!(actions.len(), 3);
+        assert!(matches!(
+            &actions[0],
+            KanbanActions::UnassignCard { user_id, .. } if *user_id == user("carol")
+        ));
+        assert!(matches!(
+            &actions[1],
+            KanbanActions::AssignCard { user_id, .. } if *user_id == user("alice")
+        ));
+        assert!(matches!(
+            &actions[2],
+            KanbanActions::UpdateCardStatus { status, .. } if status == "todo"
+        ));
+
+        assert!(undo_replay_actions(&saved, &saved).is_empty());
+    }
+
+    fn title_of(state: &UndoState) -> &str {
+        match state {
+            UndoState::Card(card) => &card.title,
+            UndoState::TagLibrary { .. } => panic!("expected a card"),
+        }
+    }
+
+    #[test]
+    fn undo_and_redo_walk_the_card_changes() {
+        let mut state = KanbanAppState::default();
+        let card_id = room_id("card");
+        state.cards.insert(card_id.clone(), card("标题 1"));
+
+        for title in ["标题 2", "标题 3"] {
+            let action = KanbanActions::UpdateCardTitle {
+                card_id: card_id.clone(),
+                title: title.to_string(),
+            };
+            let pending = state.begin_undo(&action).unwrap();
+            state.cards.get_mut(&card_id).unwrap().title = title.to_string();
+            state.finish_undo(pending);
+        }
+        // 没有变化的 Action 不记为一步
+        let action = KanbanActions::UpdateCardTitle {
+            card_id: card_id.clone(),
+            title: "标题 3".to_string(),
+        };
+        let pending = state.begin_undo(&action).unwrap();
+        state.finish_undo(pending);
+
+        let history = &mut state.undo_history;
+        assert_eq!(history.undo_label(), Some("修改标题"));
+        assert_eq!(history.redo_label(), None);
+
+        let (label, restored) = history.take_undo().unwrap();
+        assert_eq!(label, "修改标题");
+        assert_eq!(title_of(&restored), "标题 2");
+        let (_, restored) = history.take_undo().unwrap();
+        assert_eq!(title_of(&restored), "标题 1");
+        assert!(history.take_undo().is_none());
+
+        let (_, restored) = history.take_redo().unwrap();
+        assert_eq!(title_of(&restored), "标题 2");
+        assert_eq!(history.redo_label(), Some("修改标题"));
+
+        // 新的修改清空重做记录
+        history.push(UndoEntry {
+            label: "修改描述",
+            before: UndoState::Card(Box::new(card("标题 2"))),
+            after: UndoState::Card(Box::new(card("标题 2"))),
+        });
+        assert_eq!(history.undo_label(), Some("修改描述"));
+        assert!(history.take_redo().is_none());
+    }
+
+    #[test]
+    fn undo_history_keeps_the_latest_entries() {
+        let mut history = UndoHistory::default();
+        for index in 0..=MAX_UNDO_ENTRIES {
+            history.push(UndoEntry {
+                label: "修改标题",
+                before: UndoState::Card(Box::new(card(&format!("标题 {index}")))),
+                after: UndoState::Card(Box::new(card("新标题"))),
+            });
+        }
+        let mut oldest = None;
+        while let Some((_, restored)) = history.take_undo() {
+            oldest = Some(title_of(&restored).to_string());
+        }
+        assert_eq!(oldest.as_deref(), Some("标题 1"));
+    }
+
+    #[test]
+    fn tag_library_changes_wait_for_the_server() {
+        let mut state = KanbanAppState::default();
+        let space_id = room_id("board");
+        state
+            .space_tags
+            .insert(space_id.clone(), vec![tag("a", "紧急", "#ff0000")]);
+
+        let action = KanbanActions::CreateSpaceTag {
+            space_id: space_id.clone(),
+            name: "文档".to_string(),
+            color: "#00ff00".to_string(),
+        };
+        let pending = state.begin_undo(&action).unwrap();
+        state.finish_undo(pending);
+        assert_eq!(state.undo_history.undo_label(), None);
+
+        let tags = vec![tag("a", "紧急", "#ff0000"), tag("b", "文档", "#00ff00")];
+        state.tags_loaded_for_undo(&space_id, &tags);
+        assert_eq!(state.undo_history.undo_label(), Some("创建标签"));
+        match state.undo_history.take_undo() {
+            Some((_, UndoState::TagLibrary { space_id: id, tags })) => {
+                assert_eq!(id, space_id);
+                assert_eq!(tags.len(), 1);
+            }
+            other => panic!("unexpected undo state: {other:?}"),
+        }
+
+        // 没有等待中的修改时忽略
+        state.tags_loaded_for_undo(&space_id, &[]);
+        assert_eq!(state.undo_history.undo_label(), None);
+    }
+}
@@ -47,19 +47,21 @@ pub enum KanbanActions {
     /// 更新卡片标题
     UpdateCardTitle { card_id: OwnedRoomId, title: String },
 
-    /// 更新卡片描述
+    /// 更新卡片描述（Markdown 原文和渲染后的 HTML）
     UpdateCardDescription {
         card_id: OwnedRoomId,
         description: Option<String>,
+        description_html: Option<String>,
     },
 
-    /// 删除卡片
+    /// 永久删除卡片（解除与列表的关系并退出卡片 Room）
     DeleteCard { card_id: OwnedRoomId },
 
     /// 更新卡片状态
     UpdateCardStatus {
         card_id: OwnedRoomId,
-        status: crate::kanban::state::kanban_state::CardStatus,
+        /// 目标状态 ID（见所在 Space 的工作流）
+        status: String,
     },
 
     // ========== Phase 2: TodoList Actions ==========
@@ -85,6 +87,36 @@ pub enum KanbanActions {
         todo_id: String,
     },
 
+    /// 设置 / 清除待办事项的负责人
+    SetTodoAssignee {
+        card_id: OwnedRoomId,
+        todo_id: String,
+        assignee: Option<matrix_sdk::ruma::OwnedUserId>,
+    },
+
+    /// 设置 / 清除待办事项的截止时间
+    SetTodoDueTime {
+        card_id: OwnedRoomId,
+        todo_id: String,
+        due_time: Option<u64>,
+    },
+
+    /// 把待办事项转换为同一列表中的子卡片
+    ConvertTodoToCard {
+        card_id: OwnedRoomId,
+        todo_id: String,
+    },
+
+    /// 子卡片已创建
+    TodoConvertedToCard {
+        parent_id: OwnedRoomId,
+        todo_id: String,
+        card: KanbanCard,
+    },
+
+    /// 子卡片创建失败
+    TodoConversionFailed { card_id: OwnedRoomId, error: String },
+
     // ========== Phase 3: Tags Actions ==========
     /// 添加标签（旧版本，使用标签名称）
     AddTag { card_id: OwnedRoomId, tag: String },
@@ -149,6 +181,168 @@ pub enum KanbanActions {
     /// 关闭标签管理模态框
     CloseTagManagementModal,
 
+    // ========== Space 工作流 Actions ==========
+    /// Space 工作流已加载（None 表示使用默认工作流）
+    SpaceWorkflowLoaded {
+        space_id: OwnedRoomId,
+        workflow: Option<crate::kanban::state::workflow::Workflow>,
+    },
+
+    /// 显示工作流编辑模态框
+    ShowWorkflowEditor { space_id: OwnedRoomId },
+
+    /// 关闭工作流编辑模态框
+    CloseWorkflowEditor,
+
+    /// 保存 Space 工作流（并迁移现有卡片的状态）
+    SaveSpaceWorkflow {
+        space_id: OwnedRoomId,
+        workflow: crate::kanban::state::workflow::Workflow,
+    },
+
+    /// 工作流已保存
+    WorkflowSaved { space_id: OwnedRoomId, migrated: usize },
+
+    /// 工作流保存或迁移失败
+    WorkflowUpdateFailed { space_id: OwnedRoomId, error: String },
+
+    // ========== 看板权限 Actions ==========
+    /// 看板权限已加载（或保存后重新加载）
+    BoardPermissionsLoaded {
+        space_id: OwnedRoomId,
+        permissions: crate::kanban::state::permissions::BoardPermissions,
+    },
+
+    /// 打开看板设置（角色与权限）
+    ShowBoardSettings { space_id: OwnedRoomId },
+
+    /// 关闭看板设置
+    CloseBoardSettings,
+
+    /// 保存看板权限
+    SaveBoardPermissions {
+        space_id: OwnedRoomId,
+        permissions: crate::kanban::state::permissions::BoardPermissions,
+    },
+
+    /// 看板权限保存失败
+    BoardPermissionsFailed { space_id: OwnedRoomId, error: String },
+
+    // ========== 归档 Actions ==========
+    /// 归档卡片（保留所属列表和位置）
+    ArchiveCard { card_id: OwnedRoomId },
+
+    /// 恢复已归档的卡片
+    RestoreCard { card_id: OwnedRoomId },
+
+    /// 归档列表
+    ArchiveList { list_id: OwnedRoomId },
+
+    /// 恢复已归档的列表
+    RestoreList { list_id: OwnedRoomId },
+
+    /// 永久删除列表及其中的所有卡片
+    DeleteList { list_id: OwnedRoomId },
+
+    /// 打开归档面板
+    ShowArchive,
+
+    /// 关闭归档面板
+    CloseArchive,
+
+    /// 列表归档或恢复失败
+    ListArchiveFailed { list_id: OwnedRoomId, error: String },
+
+    /// 永久删除卡片或列表失败
+    DeleteFailed { id: OwnedRoomId, error: String },
+
+    // ========== 多选与批量操作 ==========
+    /// 切换单张卡片的选中状态（Ctrl / ⌘ 点击）
+    ToggleCardSelection { card_id: OwnedRoomId },
+
+    /// 选中上次点击的卡片到该卡片之间的卡片（Shift 点击）
+    ExtendCardSelection { card_id: OwnedRoomId },
+
+    /// 选中（或取消选中）列表中所有可见的卡片
+    SelectAllInList { space_id: OwnedRoomId },
+
+    /// 清除多选
+    ClearCardSelection,
+
+    /// 对选中的卡片执行批量操作（权限按卡片逐张检查）
+    BulkApply {
+        operation: crate::kanban::state::bulk::BulkOperation,
+    },
+
+    /// 批量操作 `bulk_id` 中一张卡片已同步（或重试多次后被放弃）；`error` 为失败原因
+    BulkCardProcessed {
+        bulk_id: String,
+        card_id: OwnedRoomId,
+        error: Option<String>,
+    },
+
+    // ========== 卡片模板与周期卡片 ==========
+    /// Space 卡片模板已加载
+    SpaceTemplatesLoaded {
+        space_id: OwnedRoomId,
+        templates: Vec<crate::kanban::state::template::CardTemplate>,
+    },
+
+    /// 显示卡片模板模态框
+    ShowCardTemplates { space_id: OwnedRoomId },
+
+    /// 关闭卡片模板模态框
+    CloseCardTemplates,
+
+    /// 把卡片保存为模板
+    SaveCardAsTemplate { card_id: OwnedRoomId, name: String },
+
+    /// 删除卡片模板
+    DeleteCardTemplate {
+        space_id: OwnedRoomId,
+        template_id: String,
+    },
+
+    /// 用模板创建卡片
+    CreateCardFromTemplate {
+        space_id: OwnedRoomId,
+        template_id: String,
+    },
+
+    /// 设置 / 清除卡片的周期规则
+    SetCardRecurrence {
+        card_id: OwnedRoomId,
+        recurrence: Option<crate::kanban::state::template::RecurrenceRule>,
+    },
+
+    /// 添加卡片关联（保存在 `card_id` 的元数据中）
+    AddCardLink {
+        card_id: OwnedRoomId,
+        link_type: crate::kanban::state::dependency::CardLinkType,
+        target_id: OwnedRoomId,
+    },
+
+    /// 删除卡片关联（`source_id` 为保存该关联的卡片）
+    RemoveCardLink {
+        source_id: OwnedRoomId,
+        link: crate::kanban::state::dependency::CardLink,
+    },
+
+    /// 检查到期需要生成下一张的周期卡片（由后台任务定时发送）
+    CheckRecurringCards,
+
+    /// 周期卡片的下一张已创建
+    RecurringCardSpawned {
+        previous: KanbanCard,
+        card: KanbanCard,
+    },
+
+    /// 周期卡片生成失败
+    RecurringCardFailed { card_id: OwnedRoomId, error: String },
+
+    /// 模板保存或创建卡片失败
+    TemplateUpdateFailed { space_id: OwnedRoomId, error: String },
+
     // ========== Phase 4: EndTime Actions ==========
     /// 设置截止时间
     SetEndTime {
@@ -159,6 +353,25 @@ pub enum KanbanActions {
     /// 清除截止时间
     ClearEndTime { card_id: OwnedRoomId },
 
+    // ========== 估时与工时 Actions ==========
+    /// 设置或清除卡片估时
+    SetCardEstimate {
+        card_id: OwnedRoomId,
+        estimate: Option<crate::kanban::state::time_tracking::Estimate>,
+    },
+
+    /// 当前用户开始在卡片上计时
+    StartCardTimer { card_id: OwnedRoomId },
+
+    /// 当前用户停止计时并记录工时
+    StopCardTimer { card_id: OwnedRoomId },
+
+    /// 手动补记一段到现在为止的工时（秒）
+    LogCardWork {
+        card_id: OwnedRoomId,
+        duration_secs: u64,
+    },
+
     // ========== Phase 5: Activities Actions ==========
     /// 添加评论
     AddComment { card_id: OwnedRoomId, text: String },
@@ -169,32 +382,340 @@ pub enum KanbanActions {
         activities: Vec<crate::kanban::state::kanban_state::CardActivity>,
     },
 
+    // ========== 附件与封面 Actions ==========
+    /// 附件已加载
+    AttachmentsLoaded {
+        card_id: OwnedRoomId,
+        attachments: Vec<crate::kanban::state::attachments::CardAttachment>,
+    },
+
+    /// 上传本地文件作为附件
+    UploadAttachment { card_id: OwnedRoomId, path: String },
+
+    /// 附件已上传
+    AttachmentUploaded {
+        card_id: OwnedRoomId,
+        attachment: crate::kanban::state::attachments::CardAttachment,
+    },
+
+    /// 附件上传失败
+    AttachmentUploadFailed { card_id: OwnedRoomId, error: String },
+
+    /// 设置（`Some`）或移除（`None`）卡片封面
+    SetCardCover {
+        card_id: OwnedRoomId,
+        cover: Option<matrix_sdk::ruma::OwnedMxcUri>,
+    },
+
+    // ========== 负责人 Actions ==========
+    /// 分配负责人
+    AssignCard {
+        card_id: OwnedRoomId,
+        user_id: matrix_sdk::ruma::OwnedUserId,
+    },
+
+    /// 取消分配负责人
+    UnassignCard {
+        card_id: OwnedRoomId,
+        user_id: matrix_sdk::ruma::OwnedUserId,
+    },
+
+    /// 卡片 Room 成员已加载
+    CardMembersLoaded {
+        card_id: OwnedRoomId,
+        members: Vec<crate::kanban::state::kanban_state::CardMember>,
+    },
+
+    /// 取消分配负责人失败
+    AssigneeUpdateFailed { card_id: OwnedRoomId, error: String },
+
+    /// 邀请负责人失败，需要撤销本地的分配
+    AssignCardFailed {
+        card_id: OwnedRoomId,
+        user_id: matrix_sdk::ruma::OwnedUserId,
+        error: String,
+    },
+
+    /// 切换"只看我的卡片"
+    ToggleMyCardsFilter,
+
+    // ========== 过滤 Actions ==========
+    /// 设置过滤栏的查询文本
+    SetCardFilter { query: String },
+
+    /// 把当前的查询保存为过滤器
+    SaveCardFilter { name: String },
+
+    /// 删除保存的过滤器
+    DeleteSavedFilter { name: String },
+
+    // ========== 截止时间提醒 Actions ==========
+    /// 后台提醒任务触发的定时检查
+    CheckDueReminders,
+
+    /// 打开"即将到期"视图
+    ShowDueSoon,
+
+    /// 关闭"即将到期"视图
+    CloseDueSoon,
+
+    /// 更新提醒设置
+    UpdateReminderSettings {
+        settings: crate::kanban::reminders::ReminderSettings,
+    },
+
     // ========== Phase 6: Drag and Drop Actions ==========
     /// 开始拖拽卡片
     StartDragCard {
         card_id: OwnedRoomId,
         space_id: OwnedRoomId,
         position: f64,
+        /// 从泳道视图拖出时所在泳道的 key
+        lane: Option<String>,
     },
 
-    /// 结束拖拽（放置卡片）
+    /// 拖拽经过列表中的卡片时更新放置位置（见 [`super::kanban_state::DragState::drop_index`]）
+    UpdateDropIndex { drop_index: (OwnedRoomId, usize) },
+
+    /// 结束拖拽（放置卡片到拖拽经过的位置，没有时放到目标列表末尾）
     DropCard {
         card_id: OwnedRoomId,
         target_space_id: OwnedRoomId,
-        target_position: f64,
     },
 
     /// 取消拖拽
     CancelDragCard,
 
+    /// 在泳道视图中放置卡片（更新分组属性，必要时移动到其他列表）
+    DropCardInLane {
+        card_id: OwnedRoomId,
+        target_space_id: OwnedRoomId,
+        lane_key: String,
+    },
+
+    // ========== 泳道 Actions ==========
+    /// 切换泳道分组方式
+    SetSwimlaneGrouping {
+        grouping: crate::kanban::state::swimlane::SwimlaneGrouping,
+    },
+
+    /// 折叠 / 展开泳道
+    ToggleSwimlane { lane_key: String },
+
+    // ========== 日历 / 时间线 Actions ==========
+    /// 切换看板主体的显示方式（列表 / 日历 / 时间线）
+    SetBoardViewMode {
+        mode: crate::kanban::state::calendar::BoardViewMode,
+    },
+
+    /// 切换日历的显示范围和日期（`anchor` 为 `None` 时回到今天）
+    SetCalendarView {
+        mode: crate::kanban::state::calendar::CalendarMode,
+        anchor: Option<chrono::NaiveDate>,
+    },
+
+    /// 在日历视图中把卡片拖到某一天（修改截止时间）
+    DropCardOnDate {
+        card_id: OwnedRoomId,
+        date: chrono::NaiveDate,
+    },
+
     /// 卡片移动失败（用于回滚）
     MoveCardFailed {
         card_id: OwnedRoomId,
         original_space_id: OwnedRoomId,
-        original_position: f64,
+        original_rank: String,
         error: String,
     },
 
+    // ========== 统计报表 Actions ==========
+    /// 打开统计报表（同时加载所有卡片的活动记录）
+    ShowAnalytics,
+
+    /// 关闭统计报表
+    CloseAnalytics,
+
+    /// 所有卡片的活动记录已加载
+    BoardActivitiesLoaded {
+        activities: std::collections::HashMap<
+            OwnedRoomId,
+            Vec<crate::kanban::state::kanban_state::CardActivity>,
+        >,
+    },
+
+    /// 加载统计数据失败
+    AnalyticsFailed(String),
+
+    /// 将当前报表导出为 CSV
+    ExportAnalyticsCsv,
+
+    /// 导出日期范围内（含首尾）的工时表 CSV
+    ExportTimesheet {
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
+    },
+
+    /// 工时表所需的工作时间已加载（包括已归档的卡片）
+    WorkSessionsLoaded {
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
+        sessions: Vec<crate::kanban::state::time_tracking::WorkSession>,
+    },
+
+    // ========== 离线队列 Actions ==========
+    /// 离线队列同步状态更新
+    SyncStatus {
+        pending: usize,
+        error: Option<String>,
+    },
+
+    /// 离线队列中的操作多次重试失败后被放弃（随后会从服务器重新加载被修改的卡片或列表）
+    SyncEntryDropped { description: String, error: String },
+
+    // ========== 看板 Actions ==========
+    /// 看板已加载（包括收到邀请的看板）
+    BoardsLoaded(Vec<crate::kanban::state::kanban_state::KanbanBoard>),
+
+    /// 切换当前看板（`None` 表示不属于任何看板的列表）
+    SelectBoard { board_id: Option<OwnedRoomId> },
+
+    /// 创建看板（父 Space）
+    CreateBoard {
+        name: String,
+        background_color: String,
+    },
+
+    /// 看板已创建
+    BoardCreated(crate::kanban::state::kanban_state::KanbanBoard),
+
+    /// 新列表已加入看板
+    ListAddedToBoard {
+        board_id: OwnedRoomId,
+        list_id: OwnedRoomId,
+    },
+
+    /// 修改看板背景（颜色和 mxc:// 图片）
+    UpdateBoardBackground {
+        board_id: OwnedRoomId,
+        background_color: String,
+        background_image: Option<String>,
+    },
+
+    /// 邀请用户加入看板（以及其中的列表和卡片）
+    InviteToBoard {
+        board_id: OwnedRoomId,
+        user_id: matrix_sdk::ruma::OwnedUserId,
+    },
+
+    /// 看板邀请已发送
+    BoardInviteSent {
+        board_id: OwnedRoomId,
+        user_id: matrix_sdk::ruma::OwnedUserId,
+        /// 新邀请的 Room 数量（看板、列表和卡片）
+        rooms: usize,
+    },
+
+    /// 接受看板邀请
+    JoinBoard { board_id: OwnedRoomId },
+
+    /// 已加入看板
+    BoardJoined { board_id: OwnedRoomId },
+
+    /// 把看板的卡片活动转发到聊天 Room（Room ID 或 `#别名`），`activity_types` 为空时取消订阅
+    SetBoardSubscription {
+        board_id: OwnedRoomId,
+        room: String,
+        activity_types: Vec<crate::kanban::state::kanban_state::ActivityType>,
+    },
+
+    // ========== 导入 / 导出 Actions ==========
+    /// 导出整块看板到归档文件
+    ExportBoard,
+
+    /// 看板已导出
+    BoardExported {
+        path: std::path::PathBuf,
+        lists: usize,
+        cards: usize,
+    },
+
+    /// 从归档文件（或 Trello JSON 导出）导入看板
+    ImportBoard { path: std::path::PathBuf },
+
+    /// 看板已导入
+    BoardImported {
+        lists: usize,
+        cards: usize,
+        failures: Vec<String>,
+    },
+
+    /// 导出当前看板中未归档卡片的截止时间为 iCalendar（.ics）文件
+    ExportCalendar,
+
+    /// 导入 / 导出失败
+    ImportExportFailed(String),
+
+    // ========== 从聊天创建卡片 Actions ==========
+    /// 打开从消息创建卡片的模态框（用消息内容预填）
+    ShowCreateCardFromMessage { title: String, description: String },
+
+    /// 关闭从消息创建卡片的模态框
+    CloseCreateCardModal,
+
+    /// 在列表中创建带描述的卡片
+    CreateCardFromChat {
+        space_id: OwnedRoomId,
+        title: String,
+        description: Option<String>,
+    },
+
+    /// 执行输入框中的 `/card` / `/todo` 命令
+    RunCardCommand {
+        command: crate::kanban::chat_commands::CardCommand,
+        /// 输入命令的 Room
+        room_id: OwnedRoomId,
+    },
+
+    // ========== 历史版本与撤销 Actions ==========
+    /// 撤销本次会话中最近一次卡片 / 标签库修改
+    Undo,
+
+    /// 重做最近一次撤销的修改
+    Redo,
+
+    /// 打开卡片的历史版本
+    ShowCardHistory { card_id: OwnedRoomId },
+
+    /// 打开列表标签库的历史版本
+    ShowTagLibraryHistory { space_id: OwnedRoomId },
+
+    /// 卡片历史版本已加载（最早的在前）
+    CardHistoryLoaded {
+        card_id: OwnedRoomId,
+        revisions: Vec<crate::kanban::state::history::CardRevision>,
+    },
+
+    /// 标签库历史版本已加载（最早的在前）
+    TagLibraryHistoryLoaded {
+        space_id: OwnedRoomId,
+        revisions: Vec<crate::kanban::state::history::TagLibraryRevision>,
+    },
+
+    /// 把卡片恢复为某个历史版本
+    RestoreCardRevision {
+        card_id: OwnedRoomId,
+        event_id: String,
+    },
+
+    /// 把标签库恢复为某个历史版本
+    RestoreTagLibraryRevision {
+        space_id: OwnedRoomId,
+        event_id: String,
+    },
+
+    /// 关闭历史版本面板
+    CloseHistory,
+
     /// 设置加载状态
     Loading(bool),
 
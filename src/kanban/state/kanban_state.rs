@@ -1,47 +1,19 @@
-use std::collections::HashMap;
-use matrix_sdk::ruma::OwnedRoomId;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use matrix_sdk::ruma::{OwnedMxcUri, OwnedRoomId, OwnedUserId, UserId};
 use serde::{Deserialize, Serialize};
 use makepad_widgets::log;
 
-/// Card 状态枚举
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum CardStatus {
-    /// 未完成状态
-    Pending,
-
-    /// 已完成状态
-    Completed,
-
-    /// 已归档状态
-    Archived,
-}
-
-impl CardStatus {
-    /// 获取状态的显示名称
-    pub fn display_name(&self) -> &'static str {
-        match self {
-            CardStatus::Pending => "未完成",
-            CardStatus::Completed => "已完成",
-            CardStatus::Archived => "已归档",
-        }
-    }
-
-    /// 获取状态的颜色
-    pub fn color(&self) -> &'static str {
-        match self {
-            CardStatus::Pending => "#FFA500",   // 橙色
-            CardStatus::Completed => "#61BD4F", // 绿色
-            CardStatus::Archived => "#95A5A6",  // 灰色
-        }
-    }
-}
-
-impl Default for CardStatus {
-    fn default() -> Self {
-        CardStatus::Pending
-    }
-}
+use super::attachments::CardAttachment;
+use super::subscriptions::BoardSubscription;
+use super::calendar::{BoardViewMode, CalendarMode};
+use super::card_filter::CardFilter;
+use super::dependency::CardLink;
+use super::swimlane::SwimlaneGrouping;
+use super::template::{CardTemplate, RecurrenceRule};
+use super::time_tracking::{Estimate, RunningTimer};
+use super::workflow::Workflow;
+use crate::kanban::reminders::ReminderSettings;
+use crate::kanban::saved_filters::SavedFilter;
 
 /// Space 标签定义
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -123,6 +95,39 @@ pub const PREDEFINED_TAG_COLORS: &[(&str, &str)] = &[
     ("黑色", "#343434"),
 ];
 
+/// 看板（对应包含多个列表 Space 的父 Space，存储格式见 [`super::schema::BoardMetadata`]）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KanbanBoard {
+    /// 看板 ID（父 Space ID）
+    pub id: OwnedRoomId,
+
+    /// 看板名称
+    pub name: String,
+
+    /// 看板描述
+    pub description: Option<String>,
+
+    /// 背景颜色（十六进制）
+    pub background_color: String,
+
+    /// 背景图片 URL
+    pub background_image: Option<String>,
+
+    /// 列表 ID（子 Space ID）
+    pub list_ids: Vec<OwnedRoomId>,
+
+    /// 归档时间（Unix timestamp 秒）；`None` 表示未归档
+    pub archived_at: Option<u64>,
+
+    /// 当前用户收到了邀请但还没有加入
+    #[serde(default)]
+    pub invited: bool,
+
+    /// 把卡片活动转发到聊天 Room 的订阅（见 [`super::subscriptions`]）
+    #[serde(default)]
+    pub subscriptions: Vec<BoardSubscription>,
+}
+
 /// 简化的看板列表（对应 Matrix Space）
 #[derive(Debug, Clone)]
 pub struct KanbanList {
@@ -137,10 +142,13 @@ pub struct KanbanList {
 
     /// 排序位置
     pub position: f64,
+
+    /// 归档时间（Unix timestamp 秒）；`None` 表示未归档
+    pub archived_at: Option<u64>,
 }
 
 /// 简化的看板卡片（对应 Matrix Room）
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KanbanCard {
     /// 卡片 ID（Room ID）
     pub id: OwnedRoomId,
@@ -151,14 +159,24 @@ pub struct KanbanCard {
     /// 卡片描述（支持 Markdown）
     pub description: Option<String>,
 
+    /// 描述渲染后的 HTML（类似 Matrix 消息的 `formatted_body`，见 [`crate::kanban::markdown`]）；
+    /// `None` 表示按纯文本显示
+    #[serde(default)]
+    pub description_html: Option<String>,
+
     /// 所属列表 ID（Space ID）
     pub space_id: OwnedRoomId,
 
-    /// 排序位置（用于拖拽排序）
+    /// 旧版排序位置；只用于比较还没有排序键的卡片
     pub position: f64,
 
-    /// 卡片状态
-    pub status: CardStatus,
+    /// 列表内的排序键（分数索引，见 [`crate::kanban::drag_drop::fractional_index`]）；
+    /// 空字符串表示旧版卡片，下次在该列表中插入卡片时会重新分配
+    #[serde(default)]
+    pub rank: String,
+
+    /// 卡片状态 ID（对应所在 Space 工作流中的状态，见 [`super::workflow::Workflow`]）
+    pub status: String,
 
     // ========== Phase 1: 基础元数据 ==========
     /// 标签列表
@@ -167,6 +185,39 @@ pub struct KanbanCard {
     /// 截止时间（Unix timestamp 秒）
     pub end_time: Option<u64>,
 
+    /// 负责人（必须是卡片 Room 的成员）
+    #[serde(default)]
+    pub assignees: Vec<OwnedUserId>,
+
+    /// 周期规则（见 [`super::template::RecurrenceRule`]）
+    #[serde(default)]
+    pub recurrence: Option<RecurrenceRule>,
+
+    /// 与其他卡片的关联（见 [`super::dependency::CardLink`]）
+    #[serde(default)]
+    pub links: Vec<CardLink>,
+
+    /// 封面图片（卡片 Room 中某个附件的 mxc URI，见 [`super::attachments`]）
+    #[serde(default)]
+    pub cover: Option<OwnedMxcUri>,
+
+    /// 归档时间（Unix timestamp 秒）；`None` 表示未归档（见 [`super::archived`]）
+    #[serde(default)]
+    pub archived_at: Option<u64>,
+
+    // ========== 估时与工时（见 time_tracking.rs） ==========
+    /// 估时（故事点或小时）
+    #[serde(default)]
+    pub estimate: Option<Estimate>,
+
+    /// 每个成员已记录的工时（秒）
+    #[serde(default)]
+    pub logged_time: BTreeMap<OwnedUserId, u64>,
+
+    /// 正在进行的计时
+    #[serde(default)]
+    pub timers: Vec<RunningTimer>,
+
     // ========== Phase 2: TodoList ==========
     /// 待办事项列表
     pub todos: Vec<TodoItem>,
@@ -190,11 +241,21 @@ impl KanbanCard {
             id,
             title,
             description: None,
+            description_html: None,
             space_id,
             position: 1000.0,
-            status: CardStatus::default(),
+            rank: String::new(),
+            status: super::workflow::STATUS_PENDING.to_string(),
             tags: Vec::new(),
             end_time: None,
+            assignees: Vec::new(),
+            recurrence: None,
+            links: Vec::new(),
+            cover: None,
+            archived_at: None,
+            estimate: None,
+            logged_time: BTreeMap::new(),
+            timers: Vec::new(),
             todos: Vec::new(),
             created_at: now,
             updated_at: now,
@@ -222,6 +283,11 @@ impl KanbanCard {
         }
     }
 
+    /// 是否分配给了指定用户
+    pub fn is_assigned_to(&self, user_id: &UserId) -> bool {
+        self.assignees.iter().any(|a| a == user_id)
+    }
+
     /// 获取 Todo 完成进度 (completed, total)
     pub fn todo_progress(&self) -> (usize, usize) {
         let completed = self.todos.iter().filter(|t| t.completed).count();
@@ -231,7 +297,7 @@ impl KanbanCard {
 }
 
 /// 待办事项
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TodoItem {
     /// Todo 唯一标识符
     pub id: String,
@@ -247,6 +313,18 @@ pub struct TodoItem {
 
     /// 完成时间（Unix timestamp 秒）
     pub completed_at: Option<u64>,
+
+    /// 负责人
+    #[serde(default)]
+    pub assignee: Option<OwnedUserId>,
+
+    /// 截止时间（Unix timestamp 秒）
+    #[serde(default)]
+    pub due_time: Option<u64>,
+
+    /// 转换成的子卡片（Room ID）；设置后完成状态跟随子卡片的状态
+    #[serde(default)]
+    pub child_card_id: Option<OwnedRoomId>,
 }
 
 /// 活动记录类型
@@ -264,6 +342,10 @@ pub enum ActivityType {
     EndTimeRemoved,
     DescriptionChanged,
     TitleChanged,
+    AssigneeChanged,
+    Created,
+    Moved,
+    WorkLogged,
 }
 
 impl ActivityType {
@@ -281,6 +363,10 @@ impl ActivityType {
             ActivityType::EndTimeRemoved => "📅",
             ActivityType::DescriptionChanged => "📝",
             ActivityType::TitleChanged => "✏️",
+            ActivityType::AssigneeChanged => "👤",
+            ActivityType::Created => "🆕",
+            ActivityType::Moved => "🚚",
+            ActivityType::WorkLogged => "⏱️",
         }
     }
 
@@ -298,6 +384,10 @@ impl ActivityType {
             ActivityType::EndTimeRemoved => "#D0021B",     // 红色 - 移除截止时间
             ActivityType::DescriptionChanged => "#4A90D9", // 蓝色 - 描述变更
             ActivityType::TitleChanged => "#4A90D9",       // 蓝色 - 标题变更
+            ActivityType::AssigneeChanged => "#9775FA",    // 紫色 - 负责人变更
+            ActivityType::Created => "#7ED321",            // 绿色 - 创建卡片
+            ActivityType::Moved => "#F5A623",              // 橙色 - 移动到其他列表
+            ActivityType::WorkLogged => "#00C2E0",         // 青色 - 记录工时
         }
     }
 
@@ -315,8 +405,55 @@ impl ActivityType {
             ActivityType::EndTimeRemoved => "移除了截止时间",
             ActivityType::DescriptionChanged => "更新了描述",
             ActivityType::TitleChanged => "更新了标题",
+            ActivityType::AssigneeChanged => "更新了负责人",
+            ActivityType::Created => "创建了卡片",
+            ActivityType::Moved => "移动了卡片",
+            ActivityType::WorkLogged => "记录了工时",
         }
     }
+
+    /// 活动类型在 `m.kanban.card.activity` 消息中的标识
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActivityType::Comment => "comment",
+            ActivityType::StatusChange => "status_change",
+            ActivityType::TagAdded => "tag_added",
+            ActivityType::TagRemoved => "tag_removed",
+            ActivityType::TodoAdded => "todo_added",
+            ActivityType::TodoCompleted => "todo_completed",
+            ActivityType::TodoUncompleted => "todo_uncompleted",
+            ActivityType::EndTimeSet => "end_time_set",
+            ActivityType::EndTimeRemoved => "end_time_removed",
+            ActivityType::DescriptionChanged => "description_changed",
+            ActivityType::TitleChanged => "title_changed",
+            ActivityType::AssigneeChanged => "assignee_changed",
+            ActivityType::Created => "created",
+            ActivityType::Moved => "moved",
+            ActivityType::WorkLogged => "work_logged",
+        }
+    }
+
+    /// 从消息中的标识解析活动类型
+    pub fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "comment" => ActivityType::Comment,
+            "status_change" => ActivityType::StatusChange,
+            "tag_added" => ActivityType::TagAdded,
+            "tag_removed" => ActivityType::TagRemoved,
+            "todo_added" => ActivityType::TodoAdded,
+            "todo_completed" => ActivityType::TodoCompleted,
+            "todo_uncompleted" => ActivityType::TodoUncompleted,
+            "end_time_set" => ActivityType::EndTimeSet,
+            "end_time_removed" => ActivityType::EndTimeRemoved,
+            "description_changed" => ActivityType::DescriptionChanged,
+            "title_changed" => ActivityType::TitleChanged,
+            "assignee_changed" => ActivityType::AssigneeChanged,
+            "created" => ActivityType::Created,
+            "moved" => ActivityType::Moved,
+            "work_logged" => ActivityType::WorkLogged,
+            _ => return None,
+        })
+    }
 }
 
 /// 活动记录
@@ -360,6 +497,9 @@ impl TodoItem {
             completed: false,
             created_at: now,
             completed_at: None,
+            assignee: None,
+            due_time: None,
+            child_card_id: None,
         }
     }
 
@@ -379,6 +519,25 @@ impl TodoItem {
     }
 }
 
+/// 卡片 Room 成员（用于选择负责人）
+#[derive(Debug, Clone)]
+pub struct CardMember {
+    /// 用户 ID
+    pub user_id: OwnedUserId,
+
+    /// 显示名称
+    pub display_name: Option<String>,
+}
+
+impl CardMember {
+    /// 显示名称，没有时回退到用户 ID
+    pub fn name(&self) -> String {
+        self.display_name
+            .clone()
+            .unwrap_or_else(|| self.user_id.to_string())
+    }
+}
+
 /// 拖拽状态
 #[derive(Debug, Clone)]
 pub struct DragState {
@@ -391,6 +550,13 @@ pub struct DragState {
     /// 卡片在原列表中的位置
     pub source_position: f64,
 
+    /// 从泳道视图拖出时卡片原来所在泳道的 key
+    pub source_lane: Option<String>,
+
+    /// 放置位置：（目标列表，在其可见卡片（不包括被拖拽的卡片）中的下标）；
+    /// `None` 或放到其他列表时放到末尾
+    pub drop_index: Option<(OwnedRoomId, usize)>,
+
     /// 拖拽开始时间（Unix timestamp 毫秒）
     pub start_time: u64,
 }
@@ -398,6 +564,12 @@ pub struct DragState {
 /// 看板应用状态
 #[derive(Debug, Clone, Default)]
 pub struct KanbanAppState {
+    /// 看板数据缓存（父 Space ID -> KanbanBoard）
+    pub boards: HashMap<OwnedRoomId, KanbanBoard>,
+
+    /// 当前看板；`None` 表示不属于任何看板的列表
+    pub current_board_id: Option<OwnedRoomId>,
+
     /// 列表数据缓存（Space ID -> KanbanList）
     pub lists: HashMap<OwnedRoomId, KanbanList>,
 
@@ -407,15 +579,90 @@ pub struct KanbanAppState {
     /// 活动记录缓存（Card ID -> Activities）
     pub activities: HashMap<OwnedRoomId, Vec<CardActivity>>,
 
+    /// 附件缓存（Card ID -> Attachments，最新的在前）
+    pub attachments: HashMap<OwnedRoomId, Vec<CardAttachment>>,
+
     /// Space 标签库（Space ID -> Tags）
     pub space_tags: HashMap<OwnedRoomId, Vec<SpaceTag>>,
 
+    /// Space 工作流（Space ID -> Workflow）；没有条目的 Space 使用默认工作流
+    pub workflows: HashMap<OwnedRoomId, Workflow>,
+
+    /// Space 卡片模板（Space ID -> Templates）
+    pub space_templates: HashMap<OwnedRoomId, Vec<CardTemplate>>,
+
+    /// 卡片 Room 成员缓存（Card ID -> Members）
+    pub card_members: HashMap<OwnedRoomId, Vec<CardMember>>,
+
+    /// 只显示分配给当前用户的卡片
+    pub show_only_my_cards: bool,
+
+    /// 过滤栏当前的查询文本
+    pub filter_query: String,
+
+    /// 由查询文本解析出的过滤条件
+    pub card_filter: CardFilter,
+
+    /// 查询文本的解析错误
+    pub filter_error: Option<String>,
+
+    /// 当前看板保存的过滤器
+    pub saved_filters: Vec<SavedFilter>,
+
+    /// 截止时间提醒设置
+    pub reminder_settings: ReminderSettings,
+
+    /// 泳道分组方式
+    pub swimlane_grouping: SwimlaneGrouping,
+
+    /// 已折叠的泳道 key
+    pub collapsed_lanes: HashSet<String>,
+
+    /// 看板主体的显示方式（列表 / 日历 / 时间线）
+    pub board_view_mode: BoardViewMode,
+
+    /// 日历按月或按周显示
+    pub calendar_mode: CalendarMode,
+
+    /// 日历和时间线当前显示的日期；`None` 表示今天
+    pub calendar_anchor: Option<chrono::NaiveDate>,
+
+    /// 最近一次生成的统计报表；`None` 表示正在加载或尚未打开
+    pub board_report: Option<crate::kanban::analytics::BoardReport>,
+
+    /// 每个列表 Space 的看板权限（来自 `m.room.power_levels`）
+    pub board_permissions: HashMap<OwnedRoomId, super::permissions::BoardPermissions>,
+
     /// 当前选中的卡片 ID（用于显示详情）
     pub selected_card_id: Option<OwnedRoomId>,
 
+    /// 当前打开的历史面板（卡片或标签库）
+    pub history_panel: Option<super::history::HistoryPanel>,
+
+    /// 本次会话中的撤销 / 重做记录
+    pub undo_history: super::history::UndoHistory,
+
     /// 拖拽状态
     pub drag_state: Option<DragState>,
 
+    /// 多选选中的卡片
+    pub selected_cards: HashSet<OwnedRoomId>,
+
+    /// 最近一次多选点击的卡片（Shift 点击时作为范围起点）
+    pub selection_anchor: Option<OwnedRoomId>,
+
+    /// 进行中（和最近完成）的批量操作的进度，按开始顺序排列
+    pub bulk_operations: Vec<super::bulk::BulkProgress>,
+
+    /// 重新分配了排序键、尚未保存的卡片（见 [`KanbanAppState::take_rebalanced_cards`]）
+    pub rebalanced_cards: Vec<OwnedRoomId>,
+
+    /// 离线队列中待同步的操作数
+    pub pending_sync: usize,
+
+    /// 最近一次同步失败的错误信息
+    pub sync_error: Option<String>,
+
     /// 加载状态
     pub loading: bool,
 
@@ -428,23 +675,98 @@ impl KanbanAppState {
         Self::default()
     }
 
-    /// 获取所有列表（按位置排序）
+    /// 获取当前看板中所有未归档的列表（按位置排序）
     pub fn all_lists(&self) -> Vec<&KanbanList> {
-        let mut lists: Vec<&KanbanList> = self.lists.values().collect();
+        let mut lists: Vec<&KanbanList> = self
+            .lists
+            .values()
+            .filter(|list| list.archived_at.is_none())
+            .filter(|list| self.is_in_current_board(&list.id))
+            .collect();
         lists.sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap());
         lists
     }
 
-    /// 获取列表中的卡片
+    /// 获取列表中未归档的卡片（按排序键排序）
     pub fn list_cards(&self, space_id: &OwnedRoomId) -> Vec<&KanbanCard> {
-        if let Some(list) = self.lists.get(space_id) {
-            list.card_ids
-                .iter()
-                .filter_map(|card_id| self.cards.get(card_id))
-                .collect()
-        } else {
-            Vec::new()
+        self.ordered_list_cards(space_id)
+            .into_iter()
+            .filter(|card| card.archived_at.is_none())
+            .collect()
+    }
+
+    /// 获取列表中当前可见的卡片（应用"我的卡片"和过滤栏的条件）
+    pub fn visible_list_cards(
+        &self,
+        space_id: &OwnedRoomId,
+        current_user: Option<&UserId>,
+    ) -> Vec<&KanbanCard> {
+        self.list_cards(space_id)
+            .into_iter()
+            .filter(|card| {
+                !self.show_only_my_cards || current_user.is_none_or(|me| card.is_assigned_to(me))
+            })
+            .filter(|card| self.card_filter.matches(card, current_user))
+            .collect()
+    }
+
+    /// 获取即将到期（或已过期）且未完成的卡片，按截止时间排序
+    pub fn due_soon_cards(&self, window_secs: u64) -> Vec<&KanbanCard> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let mut cards: Vec<&KanbanCard> = self
+            .cards
+            .values()
+            .filter(|card| card.end_time.is_some_and(|end_time| end_time <= now + window_secs))
+            .filter(|card| crate::kanban::reminders::is_open(self, card))
+            .collect();
+        cards.sort_by_key(|card| card.end_time);
+        cards
+    }
+
+    /// 获取 Space 的工作流（未定义时为默认工作流）
+    pub fn workflow(&self, space_id: &OwnedRoomId) -> Workflow {
+        self.workflows.get(space_id).cloned().unwrap_or_default()
+    }
+
+    /// 把 Space 中状态不在其工作流里的卡片迁移到该工作流（只更新本地状态），返回迁移的卡片数
+    ///
+    /// 只处理定义了工作流的 Space；服务器端的迁移由保存工作流的请求完成。
+    pub fn migrate_list_statuses(&mut self, space_id: &OwnedRoomId) -> usize {
+        let Some(workflow) = self.workflows.get(space_id) else {
+            return 0;
+        };
+        let mut migrated = 0;
+        for card in self.cards.values_mut() {
+            if &card.space_id == space_id && workflow.status(&card.status).is_none() {
+                card.status = workflow.migrate_status(&card.status);
+                migrated += 1;
+            }
+        }
+        migrated
+    }
+
+    /// 刚加载的卡片状态不在所属 Space 的工作流里时迁移到该工作流（只更新本地状态），
+    /// 返回是否迁移；工作流还未加载时不处理，等 [`Self::migrate_list_statuses`] 迁移
+    pub fn migrate_loaded_card_status(&self, card: &mut KanbanCard) -> bool {
+        let Some(workflow) = self.workflows.get(&card.space_id) else {
+            return false;
+        };
+        if workflow.status(&card.status).is_some() {
+            return false;
         }
+        card.status = workflow.migrate_status(&card.status);
+        true
+    }
+
+    /// 统计 Space 中处于某状态的卡片数量
+    pub fn status_count(&self, space_id: &OwnedRoomId, status: &str) -> usize {
+        self.list_cards(space_id)
+            .iter()
+            .filter(|card| card.status == status)
+            .count()
     }
 
     /// 添加或更新列表
@@ -487,6 +809,114 @@ impl KanbanAppState {
         self.cards.insert(card.id.clone(), card);
     }
 
+    /// 由待办转换成子卡片后，让父卡片中对应待办的完成状态跟随子卡片的状态
+    ///
+    /// 返回待办发生变化的父卡片 ID（需要保存它们的 Todo 列表）。
+    pub fn sync_child_todos(&mut self, child_id: &OwnedRoomId) -> Vec<OwnedRoomId> {
+        let Some(child) = self.cards.get(child_id) else {
+            return Vec::new();
+        };
+        let child_done = self.workflow(&child.space_id).category(&child.status)
+            == super::workflow::StatusCategory::Done;
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let mut changed = Vec::new();
+        for parent in self.cards.values_mut() {
+            let mut parent_changed = false;
+            for todo in parent
+                .todos
+                .iter_mut()
+                .filter(|todo| todo.child_card_id.as_ref() == Some(child_id))
+            {
+                if todo.completed != child_done {
+                    todo.completed = child_done;
+                    todo.completed_at = child_done.then_some(now);
+                    parent_changed = true;
+                }
+            }
+            if parent_changed {
+                parent.touch();
+                changed.push(parent.id.clone());
+            }
+        }
+        changed
+    }
+
+    /// 卡片移到 `target_space_id` 后的状态
+    ///
+    /// 目标列表的工作流已加载且其中没有卡片当前的状态时，按 [`Workflow::migrate_status`] 迁移；
+    /// 否则保持不变。
+    pub fn status_in_list(&self, card: &KanbanCard, target_space_id: &OwnedRoomId) -> String {
+        if &card.space_id == target_space_id {
+            return card.status.clone();
+        }
+        self.workflows.get(target_space_id).map_or_else(
+            || card.status.clone(),
+            |workflow| workflow.migrate_status(&card.status),
+        )
+    }
+
+    /// 把卡片移动到目标列表末尾（只更新本地状态），返回卡片原来所在的列表 ID
+    pub fn move_card_to(
+        &mut self,
+        card_id: &OwnedRoomId,
+        target_space_id: &OwnedRoomId,
+    ) -> Option<OwnedRoomId> {
+        self.move_card_before(card_id, target_space_id, None)
+    }
+
+    /// 把卡片移动到目标列表中 `next_id` 之前（`None` 表示末尾，只更新本地状态），
+    /// 返回卡片原来所在的列表 ID
+    ///
+    /// 目标列表需要重新分配排序键时，其他卡片记录在 `rebalanced_cards` 中等待保存。
+    /// 卡片状态不在目标列表的工作流中时迁移到该工作流，随移动操作一起保存。
+    pub fn move_card_before(
+        &mut self,
+        card_id: &OwnedRoomId,
+        target_space_id: &OwnedRoomId,
+        next_id: Option<&OwnedRoomId>,
+    ) -> Option<OwnedRoomId> {
+        let status = self.status_in_list(self.cards.get(card_id)?, target_space_id);
+        let rank = self.rank_before(target_space_id, card_id, next_id);
+        let position = self
+            .ordered_list_cards(target_space_id)
+            .iter()
+            .filter(|card| &card.id != card_id)
+            .map(|card| card.position)
+            .fold(0.0, f64::max)
+            + 1000.0;
+
+        let card = self.cards.get_mut(card_id)?;
+        let old_space_id = std::mem::replace(&mut card.space_id, target_space_id.clone());
+        card.rank = rank;
+        card.position = position;
+        card.touch();
+
+        if &old_space_id != target_space_id {
+            if card.status != status {
+                log!(
+                    "🔀 Migrating status '{}' -> '{}' for moved card {}",
+                    card.status,
+                    status,
+                    card_id
+                );
+                card.status = status;
+            }
+            if let Some(old_list) = self.lists.get_mut(&old_space_id) {
+                old_list.card_ids.retain(|id| id != card_id);
+            }
+            if let Some(new_list) = self.lists.get_mut(target_space_id) {
+                if !new_list.card_ids.contains(card_id) {
+                    new_list.card_ids.push(card_id.clone());
+                }
+            }
+        }
+        Some(old_space_id)
+    }
+
     /// 设置加载状态
     pub fn set_loading(&mut self, loading: bool) {
         self.loading = loading;
@@ -1,6 +1,36 @@
 pub mod kanban_state;
 pub mod kanban_actions;
+pub mod workflow;
+pub mod card_filter;
+pub mod template;
+pub mod swimlane;
+pub mod calendar;
+pub mod dependency;
+pub mod permissions;
+pub mod archived;
+pub mod bulk;
+pub mod ordering;
+pub mod schema;
+pub mod boards;
+pub mod attachments;
+pub mod subscriptions;
+pub mod history;
+pub mod time_tracking;
 
 // Re-export main types
 pub use kanban_state::*;
 pub use kanban_actions::*;
+pub use workflow::*;
+pub use card_filter::*;
+pub use template::*;
+pub use swimlane::*;
+pub use calendar::*;
+pub use dependency::*;
+pub use permissions::*;
+pub use archived::*;
+pub use bulk::*;
+pub use boards::*;
+pub use attachments::*;
+pub use subscriptions::*;
+pub use history::*;
+pub use time_tracking::*;
@@ -0,0 +1,259 @@
+//! 列表内卡片的顺序
+//!
+//! 卡片按元数据中的排序键（`rank`，见 [`crate::kanban::drag_drop::fractional_index`]）排序，
+//! 排序键相同时按卡片 ID 排序。还没有排序键的旧卡片排在前面，按旧版的 `position` 排序。
+//! 拖放时按放置位置前后两张卡片的排序键计算新键（见 [`KanbanAppState::rank_before`]）。
+//! 在列表中插入卡片时如果无法计算新的排序键（列表中有旧卡片或排序键过长），
+//! 先按当前顺序重新分配整个列表的排序键；这些卡片记录在 `rebalanced_cards` 中，
+//! 由调用方通过离线队列保存到服务器。两个客户端同时重新分配同一个列表时，
+//! 由于顺序和算法都是确定的，得到的排序键也相同。
+
+use std::cmp::Ordering;
+
+use makepad_widgets::log;
+use matrix_sdk::ruma::{OwnedRoomId, UserId};
+
+use super::kanban_state::{KanbanAppState, KanbanCard};
+use crate::kanban::drag_drop::fractional_index::{
+    MAX_RANK_LEN, compare_ranks, evenly_spaced_ranks, is_valid_rank, rank_between,
+};
+
+impl KanbanCard {
+    /// 比较两张卡片在列表中的先后顺序
+    pub fn cmp_order(&self, other: &KanbanCard) -> Ordering {
+        match (self.rank.is_empty(), other.rank.is_empty()) {
+            (false, false) => {
+                compare_ranks(&self.rank, self.id.as_str(), &other.rank, other.id.as_str())
+            }
+            (true, true) => self
+                .position
+                .total_cmp(&other.position)
+                .then_with(|| self.id.cmp(&other.id)),
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+        }
+    }
+}
+
+impl KanbanAppState {
+    /// 列表中的所有卡片（包括已归档的），按排序键排序
+    pub fn ordered_list_cards(&self, space_id: &OwnedRoomId) -> Vec<&KanbanCard> {
+        let Some(list) = self.lists.get(space_id) else {
+            return Vec::new();
+        };
+        let mut cards: Vec<&KanbanCard> = list
+            .card_ids
+            .iter()
+            .filter_map(|card_id| self.cards.get(card_id))
+            .collect();
+        cards.sort_by(|a, b| a.cmp_order(b));
+        cards
+    }
+
+    /// 放到列表末尾时使用的排序键（不考虑 `card_id` 本身），必要时先重新分配列表
+    pub fn rank_at_end(&mut self, space_id: &OwnedRoomId, card_id: &OwnedRoomId) -> String {
+        self.rank_before(space_id, card_id, None)
+    }
+
+    /// 放到列表中 `next_id` 之前（`None` 表示末尾）时使用的排序键（不考虑 `card_id` 本身）
+    ///
+    /// 新键超过 [`MAX_RANK_LEN`] 或前后的卡片没有有效的排序键时，先重新分配列表。
+    pub fn rank_before(
+        &mut self,
+        space_id: &OwnedRoomId,
+        card_id: &OwnedRoomId,
+        next_id: Option<&OwnedRoomId>,
+    ) -> String {
+        if let Some(rank) = self.try_rank_before(space_id, card_id, next_id) {
+            return rank;
+        }
+        self.rebalance_list(space_id, card_id);
+        self.try_rank_before(space_id, card_id, next_id)
+            .expect("ranks are valid after rebalancing")
+    }
+
+    fn try_rank_before(
+        &self,
+        space_id: &OwnedRoomId,
+        card_id: &OwnedRoomId,
+        next_id: Option<&OwnedRoomId>,
+    ) -> Option<String> {
+        let cards: Vec<&KanbanCard> = self
+            .ordered_list_cards(space_id)
+            .into_iter()
+            .filter(|card| &card.id != card_id)
+            .collect();
+        if cards.iter().any(|card| !is_valid_rank(&card.rank)) {
+            return None;
+        }
+        // 找不到 `next_id` 时放到末尾
+        let index = next_id
+            .and_then(|next_id| cards.iter().position(|card| &card.id == next_id))
+            .unwrap_or(cards.len());
+        let previous = index.checked_sub(1).map(|i| cards[i].rank.as_str());
+        let next = cards.get(index).map(|card| card.rank.as_str());
+        rank_between(previous, next).filter(|rank| rank.len() <= MAX_RANK_LEN)
+    }
+
+    /// 拖拽 `dragged_id` 经过 `card_id` 时的放置位置：`card_id` 所在的列表，以及放到它之前
+    /// （`after` 为 false）或之后时在该列表可见卡片（不包括被拖拽的卡片）中的下标
+    pub fn drop_index_at(
+        &self,
+        card_id: &OwnedRoomId,
+        after: bool,
+        dragged_id: &OwnedRoomId,
+        current_user: Option<&UserId>,
+    ) -> Option<(OwnedRoomId, usize)> {
+        let space_id = &self.cards.get(card_id)?.space_id;
+        let index = self
+            .visible_list_cards(space_id, current_user)
+            .into_iter()
+            .filter(|card| &card.id != dragged_id)
+            .position(|card| &card.id == card_id)?;
+        Some((space_id.clone(), index + usize::from(after)))
+    }
+
+    /// 放到可见卡片（不包括 `card_id`）中下标 `index` 处时，排在它后面的卡片；`None` 表示末尾
+    pub fn card_at_drop_index(
+        &self,
+        space_id: &OwnedRoomId,
+        card_id: &OwnedRoomId,
+        index: usize,
+        current_user: Option<&UserId>,
+    ) -> Option<OwnedRoomId> {
+        self.visible_list_cards(space_id, current_user)
+            .into_iter()
+            .filter(|card| &card.id != card_id)
+            .nth(index)
+            .map(|card| card.id.clone())
+    }
+
+    /// 按当前顺序为列表中的卡片（`excluding` 除外）重新均匀分配排序键
+    pub fn rebalance_list(&mut self, space_id: &OwnedRoomId, excluding: &OwnedRoomId) {
+        let card_ids: Vec<OwnedRoomId> = self
+            .ordered_list_cards(space_id)
+            .into_iter()
+            .filter(|card| &card.id != excluding)
+            .map(|card| card.id.clone())
+            .collect();
+        let ranks = evenly_spaced_ranks(card_ids.len());
+        log!(
+            "🔢 Rebalancing ranks of {} cards in list {}",
+            card_ids.len(),
+            space_id
+        );
+
+        for (card_id, rank) in card_ids.into_iter().zip(ranks) {
+            let Some(card) = self.cards.get_mut(&card_id) else {
+                continue;
+            };
+            if card.rank != rank {
+                card.rank = rank;
+                card.touch();
+                if !self.rebalanced_cards.contains(&card_id) {
+                    self.rebalanced_cards.push(card_id);
+                }
+            }
+        }
+    }
+
+    /// 取出重新分配了排序键、需要保存的卡片
+    pub fn take_rebalanced_cards(&mut self) -> Vec<OwnedRoomId> {
+        std::mem::take(&mut self.rebalanced_cards)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kanban::state::kanban_state::KanbanList;
+    use crate::kanban::test_util::room_id;
+
+    /// 一个列表，卡片按 `cards`（名称，排序键）的顺序加入
+    fn state_with_ranks(cards: &[(&str, &str)]) -> KanbanAppState {
+        let mut state = KanbanAppState::default();
+        let list_id = room_id("list");
+        for (name, rank) in cards {
+            let mut card = KanbanCard::new(room_id(name), name.to_string(), list_id.clone());
+            card.rank = rank.to_string();
+            state.cards.insert(card.id.clone(), card);
+        }
+        state.lists.insert(
+            list_id.clone(),
+            KanbanList {
+                id: list_id,
+                name: "List".to_string(),
+                card_ids: cards.iter().map(|(name, _)| room_id(name)).collect(),
+                position: 0.0,
+                archived_at: None,
+            },
+        );
+        state
+    }
+
+    fn order(state: &KanbanAppState) -> Vec<String> {
+        state
+            .ordered_list_cards(&room_id("list"))
+            .into_iter()
+            .map(|card| card.title.clone())
+            .collect()
+    }
+
+    #[test]
+    fn rank_before_uses_the_neighbouring_cards() {
+        let mut state = state_with_ranks(&[("a", "1"), ("b", "3"), ("c", "5")]);
+        let rank = state.rank_before(&room_id("list"), &room_id("c"), Some(&room_id("b")));
+        assert_eq!(rank, "2");
+        assert!(state.take_rebalanced_cards().is_empty());
+
+        state.cards.get_mut(&room_id("c")).unwrap().rank = rank;
+        assert_eq!(order(&state), ["a", "c", "b"]);
+
+        // 找不到后面的卡片时放到末尾
+        let rank = state.rank_before(&room_id("list"), &room_id("a"), None);
+        assert!(rank.as_str() > "3");
+    }
+
+    #[test]
+    fn rank_before_rebalances_when_the_rank_gets_too_long() {
+        let mut state = state_with_ranks(&[("a", "1"), ("b", "10000001"), ("c", "5")]);
+        let list_id = room_id("list");
+        assert!(
+            rank_between(Some("1"), Some("10000001")).is_some_and(|rank| rank.len() > MAX_RANK_LEN)
+        );
+
+        let rank = state.rank_before(&list_id, &room_id("c"), Some(&room_id("b")));
+        assert!(rank.len() <= MAX_RANK_LEN);
+
+        // 其他卡片按原来的顺序重新分配了排序键，需要保存
+        let rebalanced = state.take_rebalanced_cards();
+        assert!(rebalanced.contains(&room_id("a")));
+        assert!(rebalanced.contains(&room_id("b")));
+        assert!(!rebalanced.contains(&room_id("c")));
+
+        state.cards.get_mut(&room_id("c")).unwrap().rank = rank;
+        assert_eq!(order(&state), ["a", "c", "b"]);
+    }
+
+    #[test]
+    fn drop_index_skips_the_dragged_card() {
+        let state = state_with_ranks(&[("a", "1"), ("b", "3"), ("c", "5")]);
+        let list_id = room_id("list");
+        assert_eq!(
+            state.drop_index_at(&room_id("c"), false, &room_id("a"), None),
+            Some((list_id.clone(), 1))
+        );
+        assert_eq!(
+            state.drop_index_at(&room_id("c"), true, &room_id("a"), None),
+            Some((list_id.clone(), 2))
+        );
+        assert_eq!(
+            state.card_at_drop_index(&list_id, &room_id("a"), 1, None),
+            Some(room_id("c"))
+        );
+        assert_eq!(
+            state.card_at_drop_index(&list_id, &room_id("a"), 2, None),
+            None
+        );
+    }
+}
@@ -0,0 +1,486 @@
+//! 看板权限
+//!
+//! 每种看板操作对应列表 Space 的 `m.room.power_levels` 中 `events` 下的一组事件类型，
+//! 用户的权限等级不低于第一个事件类型要求的等级时才允许执行。保存权限时同一操作的
+//! 所有事件类型都设置为相同的等级：
+//!
+//! | 操作 | 事件类型 | 未设置时的默认等级 |
+//! |------|----------|--------------------|
+//! | 编辑卡片 | `m.kanban.card.edit` | `users_default` |
+//! | 移动卡片 | `m.space.child`、`m.kanban.cards` | `state_default` |
+//! | 管理标签 | `m.space.tag_library` | `state_default` |
+//! | 管理列表 | `m.space.workflow`、`m.kanban.list.archive`、`m.space.card_templates`、`m.room.name`、`m.room.topic` | `state_default` |
+//! | 删除 / 归档卡片 | `m.kanban.card.archive` | `users_default` |
+//!
+//! 移动卡片、管理标签和管理列表写入的都是列表 Space 中的这些状态事件，由服务器强制校验。
+//! 卡片在列表之间移动和在列表中创建、删除卡片都要修改 Space 的 `m.space.child`，
+//! 所以「移动卡片」的等级同样限制了这些操作。卡片 Room 中的 `m.space.parent` 和
+//! 编辑、归档卡片写入的内容都在各自的卡片 Room 中，不受列表权限约束，只在客户端校验。
+
+use std::collections::HashMap;
+
+use matrix_sdk::ruma::{OwnedRoomId, OwnedUserId, UserId};
+use serde_json::Value;
+
+use super::kanban_actions::KanbanActions;
+use super::kanban_state::KanbanAppState;
+
+/// 权限等级事件类型
+pub const POWER_LEVELS_EVENT_TYPE: &str = "m.room.power_levels";
+
+/// Matrix 规范中 `users_default` 的默认值
+const DEFAULT_USERS_LEVEL: i64 = 0;
+
+/// Matrix 规范中 `state_default` 的默认值
+const DEFAULT_STATE_LEVEL: i64 = 50;
+
+/// 看板操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KanbanPermission {
+    EditCard,
+    MoveCard,
+    ManageTags,
+    ManageLists,
+    Archive,
+}
+
+impl KanbanPermission {
+    pub const ALL: [KanbanPermission; 5] = [
+        KanbanPermission::EditCard,
+        KanbanPermission::MoveCard,
+        KanbanPermission::ManageTags,
+        KanbanPermission::ManageLists,
+        KanbanPermission::Archive,
+    ];
+
+    /// 对应的 `m.room.power_levels` 事件类型；第一个决定要求的等级
+    pub fn event_types(&self) -> &'static [&'static str] {
+        use crate::kanban::state::archived::LIST_ARCHIVE_EVENT_TYPE;
+        use crate::kanban::state::template::CARD_TEMPLATES_EVENT_TYPE;
+        use crate::kanban::state::workflow::WORKFLOW_EVENT_TYPE;
+
+        match self {
+            KanbanPermission::EditCard => &["m.kanban.card.edit"],
+            KanbanPermission::MoveCard => &["m.space.child", "m.kanban.cards"],
+            KanbanPermission::ManageTags => &["m.space.tag_library"],
+            KanbanPermission::ManageLists => &[
+                WORKFLOW_EVENT_TYPE,
+                LIST_ARCHIVE_EVENT_TYPE,
+                CARD_TEMPLATES_EVENT_TYPE,
+                "m.room.name",
+                "m.room.topic",
+            ],
+            KanbanPermission::Archive => &["m.kanban.card.archive"],
+        }
+    }
+
+    /// 是否是 Space 中真实的状态事件（未设置时使用 `state_default`）
+    fn is_state_event(&self) -> bool {
+        matches!(
+            self,
+            KanbanPermission::MoveCard
+                | KanbanPermission::ManageTags
+                | KanbanPermission::ManageLists
+        )
+    }
+
+    /// 显示名称
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            KanbanPermission::EditCard => "编辑卡片",
+            KanbanPermission::MoveCard => "移动卡片",
+            KanbanPermission::ManageTags => "管理标签",
+            KanbanPermission::ManageLists => "管理列表",
+            KanbanPermission::Archive => "删除卡片",
+        }
+    }
+}
+
+/// 看板角色（对应常用的权限等级）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardRole {
+    Member,
+    Moderator,
+    Admin,
+}
+
+impl BoardRole {
+    /// 权限等级
+    pub fn level(&self) -> i64 {
+        match self {
+            BoardRole::Member => 0,
+            BoardRole::Moderator => 50,
+            BoardRole::Admin => 100,
+        }
+    }
+
+    /// 权限等级对应的角色（取不超过该等级的最高角色）
+    pub fn from_level(level: i64) -> Self {
+        if level >= BoardRole::Admin.level() {
+            BoardRole::Admin
+        } else if level >= BoardRole::Moderator.level() {
+            BoardRole::Moderator
+        } else {
+            BoardRole::Member
+        }
+    }
+
+    /// 依次切换到下一个角色
+    pub fn next(self) -> Self {
+        match self {
+            BoardRole::Member => BoardRole::Moderator,
+            BoardRole::Moderator => BoardRole::Admin,
+            BoardRole::Admin => BoardRole::Member,
+        }
+    }
+
+    /// 显示名称
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            BoardRole::Member => "成员",
+            BoardRole::Moderator => "协管员",
+            BoardRole::Admin => "管理员",
+        }
+    }
+}
+
+/// 一个列表 Space 的看板权限
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardPermissions {
+    /// 当前用户的权限等级
+    pub user_level: i64,
+
+    /// 每种操作要求的等级
+    pub required: HashMap<KanbanPermission, i64>,
+
+    /// 单独设置了等级的用户（按等级从高到低排序）
+    pub users: Vec<(OwnedUserId, i64)>,
+
+    /// 当前用户能否修改权限设置
+    pub can_edit: bool,
+}
+
+impl BoardPermissions {
+    /// 从 `m.room.power_levels` 事件内容解析
+    pub fn from_content(content: &Value, user_id: Option<&UserId>) -> Self {
+        let level_of = |value: Option<&Value>, default: i64| {
+            value
+                .and_then(|v| v.as_i64().or_else(|| v.as_str()?.parse().ok()))
+                .unwrap_or(default)
+        };
+        let users_default = level_of(content.get("users_default"), DEFAULT_USERS_LEVEL);
+        let state_default = level_of(content.get("state_default"), DEFAULT_STATE_LEVEL);
+        let events = content.get("events");
+        let event_level = |event_type: &str, default: i64| {
+            level_of(events.and_then(|events| events.get(event_type)), default)
+        };
+
+        let required = KanbanPermission::ALL
+            .iter()
+            .map(|permission| {
+                let default = if permission.is_state_event() {
+                    state_default
+                } else {
+                    users_default
+                };
+                (
+                    *permission,
+                    event_level(permission.event_types()[0], default),
+                )
+            })
+            .collect();
+
+        let mut users: Vec<(OwnedUserId, i64)> = content
+            .get("users")
+            .and_then(Value::as_object)
+            .map(|users| {
+                users
+                    .iter()
+                    .filter_map(|(user_id, level)| {
+                        let user_id = OwnedUserId::try_from(user_id.as_str()).ok()?;
+                        Some((user_id, level_of(Some(level), users_default)))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        users.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let user_level = user_id
+            .and_then(|user_id| users.iter().find(|(id, _)| id == user_id))
+            .map_or(users_default, |(_, level)| *level);
+        let can_edit = user_level >= event_level(POWER_LEVELS_EVENT_TYPE, state_default);
+
+        Self {
+            user_level,
+            required,
+            users,
+            can_edit,
+        }
+    }
+
+    /// 把要求的等级和用户等级写回 `m.room.power_levels` 事件内容（保留其他字段）
+    ///
+    /// `users` 会被替换为 [`BoardPermissions::users`]，不在其中的用户恢复为 `users_default`。
+    pub fn apply_to_content(&self, content: &mut Value) {
+        if !content.is_object() {
+            *content = Value::Object(Default::default());
+        }
+        let Some(object) = content.as_object_mut() else {
+            return;
+        };
+
+        let events = object
+            .entry("events")
+            .or_insert_with(|| Value::Object(Default::default()));
+        if let Some(events) = events.as_object_mut() {
+            for (permission, level) in &self.required {
+                for event_type in permission.event_types() {
+                    events.insert(event_type.to_string(), Value::from(*level));
+                }
+            }
+        }
+
+        // 不在列表中的用户从 `users` 中删除，恢复为 `users_default`
+        let users = object
+            .entry("users")
+            .or_insert_with(|| Value::Object(Default::default()));
+        if let Some(users) = users.as_object_mut() {
+            users.retain(|user_id, _| self.users.iter().any(|(id, _)| id.as_str() == user_id));
+            for (user_id, level) in &self.users {
+                users.insert(user_id.to_string(), Value::from(*level));
+            }
+        }
+    }
+
+    /// 当前用户是否可以执行该操作
+    pub fn allows(&self, permission: KanbanPermission) -> bool {
+        self.required
+            .get(&permission)
+            .is_none_or(|required| self.user_level >= *required)
+    }
+
+    /// 该操作要求的角色
+    pub fn required_role(&self, permission: KanbanPermission) -> BoardRole {
+        BoardRole::from_level(self.required.get(&permission).copied().unwrap_or_default())
+    }
+}
+
+impl KanbanAppState {
+    /// 当前用户能否在该列表中执行操作（权限尚未加载时允许，由服务器最终校验）
+    pub fn can(&self, space_id: &OwnedRoomId, permission: KanbanPermission) -> bool {
+        self.board_permissions
+            .get(space_id)
+            .is_none_or(|permissions| permissions.allows(permission))
+    }
+
+    /// 当前用户能否对卡片执行操作
+    pub fn can_on_card(&self, card_id: &OwnedRoomId, permission: KanbanPermission) -> bool {
+        self.cards
+            .get(card_id)
+            .is_none_or(|card| self.can(&card.space_id, permission))
+    }
+
+    /// 执行该 Action 需要的权限（所在列表，操作）；不需要权限时返回 `None`
+    pub fn required_permission(
+        &self,
+        action: &KanbanActions,
+    ) -> Option<(OwnedRoomId, KanbanPermission)> {
+        let on_card = |card_id: &OwnedRoomId, permission| {
+            self.cards
+                .get(card_id)
+                .map(|card| (card.space_id.clone(), permission))
+        };
+        match action {
+            KanbanActions::UpdateCardTitle { card_id, .. }
+            | KanbanActions::UpdateCardDescription { card_id, .. }
+            | KanbanActions::AddTodo { card_id, .. }
+            | KanbanActions::ToggleTodo { card_id, .. }
+            | KanbanActions::UpdateTodoText { card_id, .. }
+            | KanbanActions::DeleteTodo { card_id, .. }
+            | KanbanActions::SetTodoAssignee { card_id, .. }
+            | KanbanActions::SetTodoDueTime { card_id, .. }
+            | KanbanActions::ConvertTodoToCard { card_id, .. }
+            | KanbanActions::AddTag { card_id, .. }
+            | KanbanActions::RemoveTag { card_id, .. }
+            | KanbanActions::AddTagToCard { card_id, .. }
+            | KanbanActions::RemoveTagFromCard { card_id, .. }
+            | KanbanActions::SetCardRecurrence { card_id, .. }
+            | KanbanActions::AddCardLink { card_id, .. }
+            | KanbanActions::SetEndTime { card_id, .. }
+            | KanbanActions::ClearEndTime { card_id }
+            | KanbanActions::AssignCard { card_id, .. }
+            | KanbanActions::UnassignCard { card_id, .. }
+            | KanbanActions::DropCardOnDate { card_id, .. }
+            | KanbanActions::RestoreCardRevision { card_id, .. }
+            | KanbanActions::SetCardEstimate { card_id, .. }
+            | KanbanActions::StartCardTimer { card_id }
+            | KanbanActions::StopCardTimer { card_id }
+            | KanbanActions::LogCardWork { card_id, .. } => {
+                on_card(card_id, KanbanPermission::EditCard)
+            }
+            KanbanActions::RemoveCardLink { source_id, .. } => {
+                on_card(source_id, KanbanPermission::EditCard)
+            }
+            KanbanActions::UpdateCardStatus { card_id, .. }
+            | KanbanActions::StartDragCard { card_id, .. }
+            | KanbanActions::DropCard { card_id, .. }
+            | KanbanActions::DropCardInLane { card_id, .. } => {
+                on_card(card_id, KanbanPermission::MoveCard)
+            }
+            KanbanActions::MoveCard {
+                target_space_id, ..
+            } => Some((target_space_id.clone(), KanbanPermission::MoveCard)),
+            KanbanActions::DeleteCard { card_id }
+            | KanbanActions::ArchiveCard { card_id }
+            | KanbanActions::RestoreCard { card_id } => on_card(card_id, KanbanPermission::Archive),
+            KanbanActions::CreateSpaceTag { space_id, .. }
+            | KanbanActions::UpdateSpaceTag { space_id, .. }
+            | KanbanActions::DeleteSpaceTag { space_id, .. }
+            | KanbanActions::AddTagToCardByName { space_id, .. }
+            | KanbanActions::RestoreTagLibraryRevision { space_id, .. } => {
+                Some((space_id.clone(), KanbanPermission::ManageTags))
+            }
+            KanbanActions::UpdateListName { list_id, .. }
+            | KanbanActions::ArchiveList { list_id }
+            | KanbanActions::RestoreList { list_id }
+            | KanbanActions::DeleteList { list_id } => {
+                Some((list_id.clone(), KanbanPermission::ManageLists))
+            }
+            KanbanActions::SaveSpaceWorkflow { space_id, .. }
+            | KanbanActions::DeleteCardTemplate { space_id, .. } => {
+                Some((space_id.clone(), KanbanPermission::ManageLists))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kanban::test_util::user;
+    use serde_json::json;
+
+    #[test]
+    fn missing_levels_use_spec_defaults() {
+        let permissions = BoardPermissions::from_content(&json!({}), Some(&user("alice")));
+
+        assert_eq!(permissions.user_level, DEFAULT_USERS_LEVEL);
+        assert_eq!(
+            permissions.required[&KanbanPermission::EditCard],
+            DEFAULT_USERS_LEVEL
+        );
+        assert_eq!(
+            permissions.required[&KanbanPermission::Archive],
+            DEFAULT_USERS_LEVEL
+        );
+        assert_eq!(
+            permissions.required[&KanbanPermission::MoveCard],
+            DEFAULT_STATE_LEVEL
+        );
+        assert_eq!(
+            permissions.required[&KanbanPermission::ManageLists],
+            DEFAULT_STATE_LEVEL
+        );
+        assert!(permissions.allows(KanbanPermission::EditCard));
+        assert!(!permissions.allows(KanbanPermission::ManageTags));
+        assert!(!permissions.can_edit);
+    }
+
+    #[test]
+    fn reads_levels_from_first_event_type() {
+        let content = json!({
+            "users_default": 10,
+            "state_default": 60,
+            "events": {
+                "m.kanban.card.edit": "20",
+                "m.space.child": 40,
+                "m.kanban.cards": 100,
+                "m.room.power_levels": 100,
+            },
+            "users": {
+                "@alice:example.org": 100,
+                "@bob:example.org": 50,
+                "not a user id": 100,
+            },
+        });
+        let permissions = BoardPermissions::from_content(&content, Some(&user("bob")));
+
+        assert_eq!(permissions.user_level, 50);
+        assert_eq!(permissions.required[&KanbanPermission::EditCard], 20);
+        assert_eq!(permissions.required[&KanbanPermission::MoveCard], 40);
+        assert_eq!(permissions.required[&KanbanPermission::ManageTags], 60);
+        assert_eq!(permissions.required[&KanbanPermission::Archive], 10);
+        assert_eq!(
+            permissions.users,
+            vec![(user("alice"), 100), (user("bob"), 50)]
+        );
+        assert!(permissions.allows(KanbanPermission::MoveCard));
+        assert!(!permissions.allows(KanbanPermission::ManageTags));
+        assert!(!permissions.can_edit);
+
+        let unknown = BoardPermissions::from_content(&content, Some(&user("carol")));
+        assert_eq!(unknown.user_level, 10);
+        let admin = BoardPermissions::from_content(&content, Some(&user("alice")));
+        assert!(admin.can_edit);
+    }
+
+    #[test]
+    fn writes_every_event_type_and_keeps_other_fields() {
+        let mut content = json!({
+            "ban": 50,
+            "events": { "m.room.avatar": 50 },
+            "users": { "@alice:example.org": 100 },
+        });
+        let mut permissions = BoardPermissions::from_content(&content, Some(&user("alice")));
+        permissions
+            .required
+            .insert(KanbanPermission::ManageLists, 100);
+        permissions.required.insert(KanbanPermission::MoveCard, 0);
+        permissions.apply_to_content(&mut content);
+
+        assert_eq!(content["ban"], 50);
+        assert_eq!(content["events"]["m.room.avatar"], 50);
+        for event_type in KanbanPermission::ManageLists.event_types() {
+            assert_eq!(content["events"][*event_type], 100, "{event_type}");
+        }
+        assert_eq!(content["events"]["m.space.child"], 0);
+        assert_eq!(content["events"]["m.kanban.cards"], 0);
+    }
+
+    #[test]
+    fn removed_users_are_dropped() {
+        let mut content = json!({
+            "users": {
+                "@alice:example.org": 100,
+                "@bob:example.org": 50,
+            },
+        });
+        let mut permissions = BoardPermissions::from_content(&content, Some(&user("alice")));
+        permissions.users.retain(|(id, _)| *id != user("bob"));
+        permissions.users.push((user("carol"), 50));
+        permissions.apply_to_content(&mut content);
+
+        assert_eq!(
+            content["users"],
+            json!({ "@alice:example.org": 100, "@carol:example.org": 50 })
+        );
+    }
+
+    #[test]
+    fn content_round_trips() {
+        let content = json!({
+            "users_default": 0,
+            "events": { "m.space.tag_library": 100 },
+            "users": { "@alice:example.org": 100 },
+        });
+        let permissions = BoardPermissions::from_content(&content, Some(&user("alice")));
+
+        let mut written = json!(null);
+        permissions.apply_to_content(&mut written);
+        let reread = BoardPermissions::from_content(&written, Some(&user("alice")));
+        assert_eq!(reread.required, permissions.required);
+        assert_eq!(reread.users, permissions.users);
+        assert_eq!(reread.user_level, permissions.user_level);
+    }
+}
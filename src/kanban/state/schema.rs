@@ -0,0 +1,666 @@
+//! 看板数据在 Matrix 事件中的存储格式（带版本号）
+//!
+//! [`super::kanban_state`] 中的类型是看板唯一的数据模型，这里定义它们在事件中的格式：
+//!
+//! | 数据 | 事件 | 内容 |
+//! |------|------|------|
+//! | 看板 | 父 Space 的 topic（`[kanban-board] 名称`）和 `m.kanban.board` 状态事件 | [`BoardMetadata`] |
+//! | 列表 | Space 的 topic（`[kanban-list] 名称`）和 `m.kanban.cards` 状态事件 | 卡片 ID 列表 |
+//! | 卡片 | 卡片 Room 中 `__KANBAN_METADATA__:` 开头的消息 | [`CardMetadata`] |
+//! | 待办 | 卡片 Room 的 `m.kanban.card.todos` 状态事件 | `{"todos": [...]}` |
+//! | 标签 | Space 的 `m.space.tag_library` 状态事件 | `{"tags": [...]}` |
+//! | 活动 | 卡片 Room 中 msgtype 为 `m.kanban.card.activity` 的消息 | [`activity_content`] |
+//! | 附件 | 卡片 Room 中的 `m.image` / `m.file` 消息，封面为 [`CardMetadata`] 的 `cover` | 见 [`super::attachments`] |
+//!
+//! 写入时带上 `schema_version`（当前为 [`KANBAN_SCHEMA_VERSION`]），读取时先把旧版本的
+//! 内容升级到当前版本再反序列化。没有 `schema_version` 的内容视为版本 1，包括早期
+//! `kanban/data/models.rs` 的格式：`label_ids` / `member_ids`、`due_date` 对象、
+//! `is_archived`、RFC 3339 时间戳、检查清单（`checklists`，项目为 `name` / `is_checked`）
+//! 以及枚举名称表示的标签颜色。升级只补充缺失的新字段，已有的新字段保持不变。
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use matrix_sdk::ruma::{OwnedMxcUri, OwnedRoomId, OwnedUserId};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+use super::dependency::CardLink;
+use super::kanban_state::{ActivityType, KanbanBoard, KanbanCard, SpaceTag, TodoItem};
+use super::subscriptions::BoardSubscription;
+use super::template::RecurrenceRule;
+use super::time_tracking::{Estimate, RunningTimer};
+use super::workflow::{STATUS_COMPLETED, STATUS_PENDING};
+
+/// 当前的存储格式版本
+pub const KANBAN_SCHEMA_VERSION: u32 = 2;
+
+/// 看板状态事件类型（保存在看板的父 Space 上）
+pub const BOARD_EVENT_TYPE: &str = "m.kanban.board";
+
+/// 卡片待办状态事件类型
+pub const CARD_TODOS_EVENT_TYPE: &str = "m.kanban.card.todos";
+
+/// Space 标签库状态事件类型
+pub const TAG_LIBRARY_EVENT_TYPE: &str = "m.space.tag_library";
+
+/// 旧版看板的默认背景颜色
+pub const DEFAULT_BOARD_BACKGROUND: &str = "#0079BF";
+
+/// 内容的存储格式版本（没有时为 1）
+pub fn schema_version(content: &Value) -> u32 {
+    content
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .map_or(1, |version| version as u32)
+}
+
+// ========== 卡片 ==========
+
+/// 卡片元数据消息的内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardMetadata {
+    #[serde(default = "current_version")]
+    pub schema_version: u32,
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub description_html: Option<String>,
+    #[serde(default = "default_position")]
+    pub position: f64,
+    #[serde(default)]
+    pub rank: String,
+    #[serde(default)]
+    pub end_time: Option<u64>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub assignees: Vec<OwnedUserId>,
+    #[serde(default)]
+    pub recurrence: Option<RecurrenceRule>,
+    #[serde(default)]
+    pub links: Vec<CardLink>,
+    #[serde(default)]
+    pub cover: Option<OwnedMxcUri>,
+    #[serde(default)]
+    pub archived_at: Option<u64>,
+    #[serde(default)]
+    pub estimate: Option<Estimate>,
+    #[serde(default)]
+    pub logged_time: BTreeMap<OwnedUserId, u64>,
+    #[serde(default)]
+    pub timers: Vec<RunningTimer>,
+    #[serde(default = "default_status")]
+    pub status: String,
+    #[serde(default = "now")]
+    pub created_at: u64,
+    #[serde(default = "now")]
+    pub updated_at: u64,
+}
+
+impl CardMetadata {
+    /// 从卡片生成要保存的元数据（待办单独保存，见 [`todos_content`]）
+    pub fn from_card(card: &KanbanCard) -> Self {
+        Self {
+            schema_version: KANBAN_SCHEMA_VERSION,
+            title: card.title.clone(),
+            description: card.description.clone(),
+            description_html: card.description_html.clone(),
+            position: card.position,
+            rank: card.rank.clone(),
+            end_time: card.end_time,
+            tags: card.tags.clone(),
+            assignees: card.assignees.clone(),
+            recurrence: card.recurrence.clone(),
+            links: card.links.clone(),
+            cover: card.cover.clone(),
+            archived_at: card.archived_at,
+            estimate: card.estimate.clone(),
+            logged_time: card.logged_time.clone(),
+            timers: card.timers.clone(),
+            status: card.status.clone(),
+            created_at: card.created_at,
+            updated_at: card.updated_at,
+        }
+    }
+
+    /// 解析元数据消息中的 JSON，必要时先升级旧版本
+    pub fn parse(json_str: &str) -> Result<Self> {
+        let mut content: Value =
+            serde_json::from_str(json_str).context("Card metadata is not valid JSON")?;
+        migrate_card(&mut content);
+        serde_json::from_value(content).context("Failed to parse card metadata")
+    }
+
+    /// 转换为卡片
+    pub fn into_card(
+        self,
+        id: OwnedRoomId,
+        space_id: OwnedRoomId,
+        todos: Vec<TodoItem>,
+    ) -> KanbanCard {
+        KanbanCard {
+            id,
+            title: self.title,
+            description: self.description,
+            description_html: self.description_html,
+            space_id,
+            position: self.position,
+            rank: self.rank,
+            status: self.status,
+            tags: self.tags,
+            end_time: self.end_time,
+            assignees: self.assignees,
+            recurrence: self.recurrence,
+            links: self.links,
+            cover: self.cover,
+            archived_at: self.archived_at,
+            estimate: self.estimate,
+            logged_time: self.logged_time,
+            timers: self.timers,
+            todos,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+/// 把卡片元数据升级到当前版本
+fn migrate_card(content: &mut Value) {
+    if schema_version(content) >= KANBAN_SCHEMA_VERSION {
+        return;
+    }
+    let Some(object) = content.as_object_mut() else {
+        return;
+    };
+
+    migrate_timestamps(object);
+    rename_missing(object, "label_ids", "tags");
+    rename_missing(object, "labels", "tags");
+    rename_missing(object, "member_ids", "assignees");
+
+    // `due_date` 为 `{"date": ..., "is_completed": ...}` 或日期字符串
+    if let Some(due_date) = object.remove("due_date") {
+        let (date, completed) = match &due_date {
+            Value::Object(due) => (
+                due.get("date").and_then(Value::as_str),
+                due.get("is_completed").and_then(Value::as_bool) == Some(true),
+            ),
+            Value::String(date) => (Some(date.as_str()), false),
+            _ => (None, false),
+        };
+        if !object.contains_key("end_time") {
+            if let Some(end_time) = date.and_then(parse_legacy_time) {
+                object.insert("end_time".to_string(), json!(end_time));
+            }
+        }
+        if completed && !object.contains_key("status") {
+            object.insert("status".to_string(), json!(STATUS_COMPLETED));
+        }
+    }
+    if object.remove("is_completed").and_then(|v| v.as_bool()) == Some(true)
+        && !object.contains_key("status")
+    {
+        object.insert("status".to_string(), json!(STATUS_COMPLETED));
+    }
+    migrate_archived_flag(object);
+
+    // 列表 / 看板 ID 由卡片所在的 Space 决定；待办从 `m.kanban.card.todos` 事件读取
+    for key in ["id", "list_id", "space_id", "board_id", "checklists"] {
+        object.remove(key);
+    }
+    object.insert("schema_version".to_string(), json!(KANBAN_SCHEMA_VERSION));
+}
+
+// ========== 待办 ==========
+
+/// 生成 `m.kanban.card.todos` 状态事件的内容
+pub fn todos_content(todos: &[TodoItem]) -> Value {
+    json!({
+        "schema_version": KANBAN_SCHEMA_VERSION,
+        "todos": todos,
+    })
+}
+
+/// 解析 `m.kanban.card.todos` 状态事件的内容（无法解析的项会被跳过）
+///
+/// 旧版内容可能是检查清单（`checklists`），其中所有项目按顺序合并为待办。
+pub fn parse_todos(content: &Value) -> Vec<TodoItem> {
+    let legacy = schema_version(content) < KANBAN_SCHEMA_VERSION;
+    let items: Vec<Value> = match content.get("todos").and_then(Value::as_array) {
+        Some(todos) => todos.clone(),
+        None if legacy => content
+            .get("checklists")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|checklist| checklist.get("items").and_then(Value::as_array))
+            .flatten()
+            .cloned()
+            .collect(),
+        None => Vec::new(),
+    };
+
+    items
+        .into_iter()
+        .filter_map(|mut item| {
+            if legacy {
+                migrate_todo(&mut item);
+            }
+            serde_json::from_value(item).ok()
+        })
+        .collect()
+}
+
+/// 把旧版检查清单项目（`name` / `is_checked`）升级为待办
+fn migrate_todo(item: &mut Value) {
+    let Some(object) = item.as_object_mut() else {
+        return;
+    };
+    migrate_timestamps(object);
+    rename_missing(object, "name", "text");
+    rename_missing(object, "is_checked", "completed");
+    if !object.contains_key("id") {
+        let random = uuid::Uuid::new_v4().to_string();
+        object.insert(
+            "id".to_string(),
+            json!(format!("todo_{}_{}", now(), &random[..8])),
+        );
+    }
+    if !object.contains_key("created_at") {
+        object.insert("created_at".to_string(), json!(now()));
+    }
+    object.entry("completed").or_insert(json!(false));
+    object.entry("completed_at").or_insert(Value::Null);
+}
+
+// ========== 标签 ==========
+
+/// 生成 `m.space.tag_library` 状态事件的内容
+///
+/// `version` 是保存时间，与存储格式版本 `schema_version` 无关。
+pub fn tag_library_content(tags: &[SpaceTag]) -> Value {
+    json!({
+        "schema_version": KANBAN_SCHEMA_VERSION,
+        "tags": tags,
+        "version": now(),
+    })
+}
+
+/// 解析 `m.space.tag_library` 状态事件的内容
+pub fn parse_tags(content: &Value) -> Result<Vec<SpaceTag>> {
+    let Some(tags) = content.get("tags") else {
+        return Ok(Vec::new());
+    };
+    let mut tags = tags.clone();
+    if schema_version(content) < KANBAN_SCHEMA_VERSION {
+        for tag in tags.as_array_mut().into_iter().flatten() {
+            migrate_tag(tag);
+        }
+    }
+    serde_json::from_value(tags).context("Failed to parse tag library")
+}
+
+/// 把旧版标签（颜色为枚举名称，没有时间戳）升级到当前版本
+fn migrate_tag(tag: &mut Value) {
+    let Some(object) = tag.as_object_mut() else {
+        return;
+    };
+    migrate_timestamps(object);
+    if let Some(Value::String(color)) = object.get_mut("color") {
+        if let Some(hex) = legacy_label_color(color) {
+            *color = hex.to_string();
+        }
+    }
+    let timestamp = now();
+    object.entry("created_at").or_insert(json!(timestamp));
+    object.entry("updated_at").or_insert(json!(timestamp));
+    object.entry("description").or_insert(Value::Null);
+}
+
+/// 旧版标签颜色名称对应的十六进制颜色
+fn legacy_label_color(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "Green" => "#61BD4F",
+        "Yellow" => "#F2D600",
+        "Orange" => "#FF9F1A",
+        "Red" => "#EB5A46",
+        "Purple" => "#9775FA",
+        "Blue" => "#0079BF",
+        "Sky" => "#00C2E0",
+        "Lime" => "#51E898",
+        "Pink" => "#FF78CB",
+        "Black" => "#343434",
+        _ => return None,
+    })
+}
+
+// ========== 活动 ==========
+
+/// 生成系统活动消息的内容（评论使用普通的 m.text 消息）
+pub fn activity_content(
+    activity_type: &ActivityType,
+    text: &str,
+    metadata: Option<Value>,
+) -> Value {
+    json!({
+        "msgtype": crate::kanban::matrix_adapter::CARD_ACTIVITY_MSGTYPE,
+        "schema_version": KANBAN_SCHEMA_VERSION,
+        "body": text,
+        "activity_type": activity_type.as_str(),
+        "metadata": metadata,
+    })
+}
+
+// ========== 看板 ==========
+
+/// `m.kanban.board` 状态事件的内容（看板名称保存在父 Space 的 topic 中）
+///
+/// `subscriptions` 是把卡片活动转发到聊天 Room 的订阅（见 [`super::subscriptions`]）。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardMetadata {
+    #[serde(default = "current_version")]
+    pub schema_version: u32,
+    #[serde(default = "default_background")]
+    pub background_color: String,
+    #[serde(default)]
+    pub background_image: Option<String>,
+    #[serde(default)]
+    pub archived_at: Option<u64>,
+    #[serde(default)]
+    pub subscriptions: Vec<BoardSubscription>,
+}
+
+impl Default for BoardMetadata {
+    fn default() -> Self {
+        Self {
+            schema_version: KANBAN_SCHEMA_VERSION,
+            background_color: default_background(),
+            background_image: None,
+            archived_at: None,
+            subscriptions: Vec::new(),
+        }
+    }
+}
+
+impl BoardMetadata {
+    /// 从看板生成要保存的内容
+    pub fn from_board(board: &KanbanBoard) -> Self {
+        Self {
+            schema_version: KANBAN_SCHEMA_VERSION,
+            background_color: board.background_color.clone(),
+            background_image: board.background_image.clone(),
+            archived_at: board.archived_at,
+            subscriptions: board.subscriptions.clone(),
+        }
+    }
+
+    /// 解析状态事件内容，必要时先升级旧版本
+    pub fn parse(content: &Value) -> Result<Self> {
+        let mut content = content.clone();
+        if schema_version(&content) < KANBAN_SCHEMA_VERSION {
+            if let Some(object) = content.as_object_mut() {
+                migrate_timestamps(object);
+                migrate_archived_flag(object);
+            }
+        }
+        serde_json::from_value(content).context("Failed to parse board metadata")
+    }
+}
+
+// ========== 辅助函数 ==========
+
+/// 新字段不存在时，把旧字段改名为新字段；否则丢弃旧字段
+fn rename_missing(object: &mut Map<String, Value>, old: &str, new: &str) {
+    if let Some(value) = object.remove(old) {
+        object.entry(new).or_insert(value);
+    }
+}
+
+/// 把 RFC 3339 格式的 `created_at` / `updated_at` 转换为 Unix 时间戳（秒）
+fn migrate_timestamps(object: &mut Map<String, Value>) {
+    for key in ["created_at", "updated_at"] {
+        if let Some(Value::String(time)) = object.get(key) {
+            match parse_legacy_time(time) {
+                Some(secs) => object.insert(key.to_string(), json!(secs)),
+                None => object.remove(key),
+            };
+        }
+    }
+}
+
+/// `is_archived: true` 转换为 `archived_at`（使用最后更新时间）
+fn migrate_archived_flag(object: &mut Map<String, Value>) {
+    if object.remove("is_archived").and_then(|v| v.as_bool()) == Some(true)
+        && !object.contains_key("archived_at")
+    {
+        let archived_at = object
+            .get("updated_at")
+            .and_then(Value::as_u64)
+            .unwrap_or_else(now);
+        object.insert("archived_at".to_string(), json!(archived_at));
+    }
+}
+
+/// 解析旧版的时间字符串（RFC 3339 或 `YYYY-MM-DD`）
+fn parse_legacy_time(time: &str) -> Option<u64> {
+    if let Ok(datetime) = chrono::DateTime::parse_from_rfc3339(time) {
+        return Some(datetime.timestamp().max(0) as u64);
+    }
+    chrono::NaiveDate::parse_from_str(time, "%Y-%m-%d")
+        .ok()
+        .map(|date| super::calendar::end_time_on_day(None, date))
+}
+
+fn current_version() -> u32 {
+    KANBAN_SCHEMA_VERSION
+}
+
+fn default_position() -> f64 {
+    1000.0
+}
+
+fn default_status() -> String {
+    STATUS_PENDING.to_string()
+}
+
+fn default_background() -> String {
+    DEFAULT_BOARD_BACKGROUND.to_string()
+}
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kanban::test_util::room_id;
+
+    /// 2024-01-01T00:00:00Z
+    const JAN_1: u64 = 1_704_067_200;
+
+    /// 解析后重新保存再解析，内容不变
+    fn assert_card_round_trip(metadata: &CardMetadata) {
+        let card = metadata
+            .clone()
+            .into_card(room_id("card"), room_id("list"), Vec::new());
+        let saved = serde_json::to_string(&CardMetadata::from_card(&card)).unwrap();
+        let reparsed = CardMetadata::parse(&saved).unwrap();
+        assert_eq!(
+            serde_json::to_value(&reparsed).unwrap(),
+            serde_json::to_value(metadata).unwrap()
+        );
+    }
+
+    #[test]
+    fn migrates_legacy_card_fields() {
+        let legacy = json!({
+            "id": "card_1",
+            "list_id": "list_1",
+            "title": "旧卡片",
+            "label_ids": ["tag_a", "tag_b"],
+            "member_ids": ["@alice:example.org"],
+            "due_date": { "date": "2024-05-01T10:30:00+08:00", "is_completed": true },
+            "is_archived": true,
+            "checklists": [],
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-02T00:00:00Z",
+        });
+        let metadata = CardMetadata::parse(&legacy.to_string()).unwrap();
+
+        assert_eq!(metadata.schema_version, KANBAN_SCHEMA_VERSION);
+        assert_eq!(metadata.title, "旧卡片");
+        assert_eq!(metadata.tags, ["tag_a", "tag_b"]);
+        assert_eq!(metadata.assignees.len(), 1);
+        assert_eq!(metadata.assignees[0].as_str(), "@alice:example.org");
+        assert_eq!(metadata.end_time, Some(1_714_530_600));
+        assert_eq!(metadata.status, STATUS_COMPLETED);
+        assert_eq!(metadata.created_at, JAN_1);
+        assert_eq!(metadata.updated_at, JAN_1 + 86_400);
+        // 归档时间取最后更新时间
+        assert_eq!(metadata.archived_at, Some(JAN_1 + 86_400));
+        assert_card_round_trip(&metadata);
+    }
+
+    #[test]
+    fn migrates_due_date_strings() {
+        let legacy = json!({
+            "title": "只有日期",
+            "due_date": "2024-05-01",
+            "created_at": JAN_1,
+            "updated_at": JAN_1,
+        });
+        let metadata = CardMetadata::parse(&legacy.to_string()).unwrap();
+
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        assert_eq!(
+            metadata.end_time,
+            Some(super::super::calendar::end_time_on_day(None, date))
+        );
+        assert_eq!(metadata.status, STATUS_PENDING);
+        assert_eq!(metadata.archived_at, None);
+        assert_card_round_trip(&metadata);
+    }
+
+    #[test]
+    fn migration_keeps_existing_new_fields() {
+        let legacy = json!({
+            "title": "新旧字段并存",
+            "tags": ["new_tag"],
+            "label_ids": ["old_tag"],
+            "end_time": 42,
+            "due_date": { "date": "2024-05-01T00:00:00Z", "is_completed": true },
+            "status": "in_review",
+            "archived_at": 7,
+            "is_archived": true,
+        });
+        let metadata = CardMetadata::parse(&legacy.to_string()).unwrap();
+
+        assert_eq!(metadata.tags, ["new_tag"]);
+        assert_eq!(metadata.end_time, Some(42));
+        assert_eq!(metadata.status, "in_review");
+        assert_eq!(metadata.archived_at, Some(7));
+    }
+
+    #[test]
+    fn current_cards_are_not_migrated() {
+        let current = json!({
+            "schema_version": KANBAN_SCHEMA_VERSION,
+            "title": "当前版本",
+            "label_ids": ["ignored"],
+            "is_archived": true,
+        });
+        let metadata = CardMetadata::parse(&current.to_string()).unwrap();
+
+        assert!(metadata.tags.is_empty());
+        assert_eq!(metadata.archived_at, None);
+        assert_card_round_trip(&metadata);
+    }
+
+    #[test]
+    fn migrates_legacy_checklists_to_todos() {
+        let legacy = json!({
+            "checklists": [
+                {
+                    "name": "发布",
+                    "items": [
+                        { "id": "item_1", "name": "打包", "is_checked": true, "created_at": "2024-01-01T00:00:00Z" },
+                        { "name": "上传" },
+                    ],
+                },
+                { "name": "空清单", "items": [] },
+                { "name": "验证", "items": [{ "id": "item_3", "name": "冒烟测试", "is_checked": false }] },
+            ],
+        });
+        let todos = parse_todos(&legacy);
+
+        let texts: Vec<&str> = todos.iter().map(|todo| todo.text.as_str()).collect();
+        assert_eq!(texts, ["打包", "上传", "冒烟测试"]);
+        assert_eq!(todos[0].id, "item_1");
+        assert!(todos[0].completed);
+        assert_eq!(todos[0].created_at, JAN_1);
+        // 缺少的 ID 会被生成
+        assert!(todos[1].id.starts_with("todo_"));
+        assert!(!todos[1].completed);
+        assert!(!todos[2].completed);
+
+        assert_eq!(parse_todos(&todos_content(&todos)), todos);
+    }
+
+    #[test]
+    fn current_todos_ignore_checklists() {
+        let current = json!({
+            "schema_version": KANBAN_SCHEMA_VERSION,
+            "checklists": [{ "items": [{ "name": "旧项目" }] }],
+        });
+        assert!(parse_todos(&current).is_empty());
+    }
+
+    #[test]
+    fn migrates_legacy_tag_colors() {
+        let legacy = json!({
+            "tags": [
+                { "id": "tag_red", "name": "缺陷", "color": "Red", "created_at": "2024-01-01T00:00:00Z" },
+                { "id": "tag_hex", "name": "文档", "color": "#123456", "created_at": JAN_1, "updated_at": JAN_1 },
+            ],
+        });
+        let tags = parse_tags(&legacy).unwrap();
+
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].color, "#EB5A46");
+        assert_eq!(tags[0].created_at, JAN_1);
+        assert_eq!(tags[0].description, None);
+        assert_eq!(tags[1].color, "#123456");
+
+        let reparsed = parse_tags(&tag_library_content(&tags)).unwrap();
+        assert_eq!(
+            serde_json::to_value(&reparsed).unwrap(),
+            serde_json::to_value(&tags).unwrap()
+        );
+    }
+
+    #[test]
+    fn migrates_legacy_board_metadata() {
+        let legacy = json!({
+            "is_archived": true,
+            "updated_at": "2024-01-01T00:00:00Z",
+        });
+        let metadata = BoardMetadata::parse(&legacy).unwrap();
+
+        assert_eq!(metadata.schema_version, KANBAN_SCHEMA_VERSION);
+        assert_eq!(metadata.background_color, DEFAULT_BOARD_BACKGROUND);
+        assert_eq!(metadata.archived_at, Some(JAN_1));
+        assert!(metadata.subscriptions.is_empty());
+
+        let reparsed = BoardMetadata::parse(&serde_json::to_value(&metadata).unwrap()).unwrap();
+        assert_eq!(
+            serde_json::to_value(&reparsed).unwrap(),
+            serde_json::to_value(&metadata).unwrap()
+        );
+    }
+}
@@ -0,0 +1,94 @@
+//! 看板动态订阅
+//!
+//! 看板可以把卡片活动（`m.kanban.card.activity`）转发到已加入的聊天 Room，
+//! 每个订阅可以选择转发哪些活动类型。订阅保存在看板的 `m.kanban.board` 状态事件中
+//! （见 [`super::schema::BoardMetadata`]），由执行操作的客户端在记录活动后发送通知，
+//! 所以每条活动只会转发一次。
+
+use matrix_sdk::ruma::{OwnedRoomId, RoomId};
+use serde::{Deserialize, Serialize};
+
+use super::kanban_state::{ActivityType, KanbanBoard};
+
+/// 可以订阅的活动类型（按界面中的显示顺序）
+pub const SUBSCRIBABLE_ACTIVITY_TYPES: &[ActivityType] = &[
+    ActivityType::Created,
+    ActivityType::Moved,
+    ActivityType::StatusChange,
+    ActivityType::EndTimeSet,
+];
+
+/// 转发到一个聊天 Room 的订阅
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BoardSubscription {
+    /// 接收通知的 Room
+    pub room_id: OwnedRoomId,
+
+    /// 转发的活动类型
+    #[serde(default = "default_activity_types")]
+    pub activity_types: Vec<ActivityType>,
+}
+
+fn default_activity_types() -> Vec<ActivityType> {
+    SUBSCRIBABLE_ACTIVITY_TYPES.to_vec()
+}
+
+impl BoardSubscription {
+    /// 是否转发该类型的活动
+    pub fn wants(&self, activity_type: &ActivityType) -> bool {
+        self.activity_types.contains(activity_type)
+    }
+
+    /// 订阅的活动类型名称，例如 `创建了卡片、移动了卡片`
+    pub fn describe(&self) -> String {
+        self.activity_types
+            .iter()
+            .map(ActivityType::display_text)
+            .collect::<Vec<_>>()
+            .join("、")
+    }
+}
+
+impl KanbanBoard {
+    /// 添加或更新转发到 `room_id` 的订阅；`activity_types` 为空时取消订阅
+    pub fn set_subscription(&mut self, room_id: OwnedRoomId, activity_types: Vec<ActivityType>) {
+        if activity_types.is_empty() {
+            self.subscriptions
+                .retain(|subscription| subscription.room_id != room_id);
+            return;
+        }
+        match self
+            .subscriptions
+            .iter_mut()
+            .find(|subscription| subscription.room_id == room_id)
+        {
+            Some(subscription) => subscription.activity_types = activity_types,
+            None => self.subscriptions.push(BoardSubscription {
+                room_id,
+                activity_types,
+            }),
+        }
+    }
+}
+
+/// 转发到聊天 Room 的通知内容（纯文本, HTML），带有打开卡片的 matrix.to 链接
+pub fn activity_notice(
+    board_name: &str,
+    card_title: &str,
+    card_id: &RoomId,
+    activity_type: &ActivityType,
+    text: &str,
+) -> (String, String) {
+    let link = card_id.matrix_to_uri().to_string();
+    let icon = activity_type.icon_symbol();
+    let body = format!("[{board_name}] {icon} {card_title}: {text}\n{link}");
+    let html = format!(
+        "<b>[{}]</b> {} <a href=\"{}\">{}</a>: {}",
+        htmlize::escape_text(board_name),
+        icon,
+        htmlize::escape_attribute(&link),
+        htmlize::escape_text(card_title),
+        htmlize::escape_text(text),
+    );
+    (body, html)
+}
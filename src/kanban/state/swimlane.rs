@@ -0,0 +1,188 @@
+//! 泳道分组
+//!
+//! 在现有列表（列）的基础上，把卡片按标签、负责人或状态分成横向的泳道：
+//! 每条泳道是一行，行内每个列表对应一个单元格。
+//! 标签和负责人可以有多个值，卡片会出现在每个匹配的泳道中；
+//! 没有对应值的卡片放在最后一条"无"泳道（key 为 [`NO_VALUE_LANE`]）。
+
+use std::collections::HashMap;
+
+use matrix_sdk::ruma::{OwnedRoomId, UserId};
+
+use super::kanban_state::{KanbanAppState, KanbanCard};
+
+/// 没有对应属性值的卡片所在泳道的 key
+pub const NO_VALUE_LANE: &str = "";
+
+/// 泳道分组方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SwimlaneGrouping {
+    /// 不分组（按列表显示）
+    #[default]
+    None,
+    Tag,
+    Assignee,
+    Status,
+}
+
+impl SwimlaneGrouping {
+    /// 依次切换到下一种分组方式
+    pub fn next(self) -> Self {
+        match self {
+            SwimlaneGrouping::None => SwimlaneGrouping::Tag,
+            SwimlaneGrouping::Tag => SwimlaneGrouping::Assignee,
+            SwimlaneGrouping::Assignee => SwimlaneGrouping::Status,
+            SwimlaneGrouping::Status => SwimlaneGrouping::None,
+        }
+    }
+
+    /// 显示名称
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            SwimlaneGrouping::None => "无",
+            SwimlaneGrouping::Tag => "标签",
+            SwimlaneGrouping::Assignee => "负责人",
+            SwimlaneGrouping::Status => "状态",
+        }
+    }
+
+    /// "无"泳道的标题
+    fn no_value_title(&self) -> &'static str {
+        match self {
+            SwimlaneGrouping::Tag => "无标签",
+            SwimlaneGrouping::Assignee => "未分配",
+            SwimlaneGrouping::None | SwimlaneGrouping::Status => "其他",
+        }
+    }
+
+    /// 卡片所属泳道的 key
+    pub fn lane_keys(&self, card: &KanbanCard) -> Vec<String> {
+        let keys: Vec<String> = match self {
+            SwimlaneGrouping::None => Vec::new(),
+            SwimlaneGrouping::Tag => card.tags.clone(),
+            SwimlaneGrouping::Assignee => card.assignees.iter().map(|u| u.to_string()).collect(),
+            SwimlaneGrouping::Status => vec![card.status.clone()],
+        };
+        if keys.is_empty() {
+            vec![NO_VALUE_LANE.to_string()]
+        } else {
+            keys
+        }
+    }
+}
+
+/// 一条泳道
+#[derive(Debug, Clone)]
+pub struct Swimlane {
+    /// 泳道 key：标签 ID、用户 ID 或状态 ID
+    pub key: String,
+
+    /// 显示标题
+    pub title: String,
+
+    /// 每个列表中属于该泳道的卡片（按 position 排序）
+    pub cards: HashMap<OwnedRoomId, Vec<OwnedRoomId>>,
+}
+
+impl Swimlane {
+    /// 泳道中的卡片总数
+    pub fn card_count(&self) -> usize {
+        self.cards.values().map(Vec::len).sum()
+    }
+
+    /// 某个列表中属于该泳道的卡片
+    pub fn cards_in(&self, space_id: &OwnedRoomId) -> &[OwnedRoomId] {
+        self.cards.get(space_id).map_or(&[], Vec::as_slice)
+    }
+}
+
+impl KanbanAppState {
+    /// 按当前的分组方式把可见卡片分成泳道
+    ///
+    /// 状态泳道按工作流中的顺序排列，标签和负责人泳道按标题排序，"无"泳道总在最后。
+    pub fn swimlanes(&self, current_user: Option<&UserId>) -> Vec<Swimlane> {
+        let grouping = self.swimlane_grouping;
+        if grouping == SwimlaneGrouping::None {
+            return Vec::new();
+        }
+
+        let mut lanes: HashMap<String, Swimlane> = HashMap::new();
+        for list in self.all_lists() {
+            for card in self.visible_list_cards(&list.id, current_user) {
+                for key in grouping.lane_keys(card) {
+                    lanes
+                        .entry(key.clone())
+                        .or_insert_with(|| Swimlane {
+                            title: self.lane_title(grouping, &key),
+                            key,
+                            cards: HashMap::new(),
+                        })
+                        .cards
+                        .entry(list.id.clone())
+                        .or_default()
+                        .push(card.id.clone());
+                }
+            }
+        }
+
+        let status_order: Vec<String> = self
+            .all_lists()
+            .into_iter()
+            .flat_map(|list| self.workflow(&list.id).statuses)
+            .map(|status| status.id)
+            .collect();
+
+        let mut lanes: Vec<Swimlane> = lanes.into_values().collect();
+        lanes.sort_by(|a, b| {
+            let a_none = a.key == NO_VALUE_LANE;
+            let b_none = b.key == NO_VALUE_LANE;
+            a_none.cmp(&b_none).then_with(|| match grouping {
+                SwimlaneGrouping::Status => {
+                    let index = |key: &str| {
+                        status_order
+                            .iter()
+                            .position(|id| id == key)
+                            .unwrap_or(usize::MAX)
+                    };
+                    index(&a.key).cmp(&index(&b.key))
+                }
+                _ => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+            })
+        });
+        lanes
+    }
+
+    /// 泳道是否已折叠
+    pub fn is_lane_collapsed(&self, key: &str) -> bool {
+        self.collapsed_lanes.contains(key)
+    }
+
+    fn lane_title(&self, grouping: SwimlaneGrouping, key: &str) -> String {
+        if key == NO_VALUE_LANE {
+            return grouping.no_value_title().to_string();
+        }
+        match grouping {
+            SwimlaneGrouping::Tag => self
+                .space_tags
+                .values()
+                .flatten()
+                .find(|tag| tag.id == key || tag.name == key)
+                .map_or_else(|| key.to_string(), |tag| tag.name.clone()),
+            SwimlaneGrouping::Assignee => self
+                .card_members
+                .values()
+                .flatten()
+                .find(|member| member.user_id.as_str() == key)
+                .map_or_else(|| key.to_string(), |member| member.name()),
+            SwimlaneGrouping::Status => self
+                .all_lists()
+                .into_iter()
+                .map(|list| self.workflow(&list.id))
+                .find(|workflow| workflow.status(key).is_some())
+                .map_or_else(|| key.to_string(), |workflow| {
+                    workflow.display_name(key).to_string()
+                }),
+            SwimlaneGrouping::None => key.to_string(),
+        }
+    }
+}
@@ -0,0 +1,364 @@
+//! 卡片模板与周期规则
+//!
+//! 模板以 `m.space.card_templates` 状态事件保存在看板 Space 上（与 `m.space.tag_library`
+//! 相同的方式），包含标题、描述、待办清单和默认标签，可以一键创建新卡片。
+//!
+//! 卡片可以带有 [`RecurrenceRule`]：
+//! - [`RecurrenceTrigger::OnComplete`]：卡片完成时创建下一张
+//! - [`RecurrenceTrigger::OnSchedule`]：到达截止时间时创建下一张（不论是否完成）
+//!
+//! 新卡片沿用原卡片的内容（待办重置为未完成），截止时间按周期顺延，
+//! 周期规则随之移到新卡片上，原卡片不再重复生成。
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+use super::kanban_state::{KanbanCard, TodoItem};
+
+/// 卡片模板状态事件类型
+pub const CARD_TEMPLATES_EVENT_TYPE: &str = "m.space.card_templates";
+
+/// 周期频率
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecurrenceFrequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// 何时创建下一张周期卡片
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RecurrenceTrigger {
+    /// 卡片完成时
+    #[default]
+    OnComplete,
+
+    /// 到达截止时间时
+    OnSchedule,
+}
+
+/// 周期规则
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecurrenceRule {
+    /// 频率
+    pub frequency: RecurrenceFrequency,
+
+    /// 间隔（每 N 天 / 周 / 月）
+    #[serde(default = "default_interval")]
+    pub interval: u32,
+
+    /// 触发方式
+    #[serde(default)]
+    pub trigger: RecurrenceTrigger,
+}
+
+fn default_interval() -> u32 {
+    1
+}
+
+impl RecurrenceRule {
+    /// 从 `from` 顺延一个周期后的时间（Unix timestamp 秒）
+    pub fn advance(&self, from: u64) -> u64 {
+        let interval = self.interval.max(1);
+        match self.frequency {
+            RecurrenceFrequency::Daily => from + u64::from(interval) * 86400,
+            RecurrenceFrequency::Weekly => from + u64::from(interval) * 7 * 86400,
+            RecurrenceFrequency::Monthly => {
+                use chrono::TimeZone;
+                chrono::Local
+                    .timestamp_opt(from as i64, 0)
+                    .single()
+                    .and_then(|dt| dt.checked_add_months(chrono::Months::new(interval)))
+                    .map_or(from + u64::from(interval) * 30 * 86400, |dt| {
+                        dt.timestamp().max(0) as u64
+                    })
+            }
+        }
+    }
+
+    /// 下一张卡片的截止时间：从上一张的截止时间（没有则为当前时间）顺延，直到晚于当前时间
+    pub fn next_end_time(&self, previous_end_time: Option<u64>, now: u64) -> u64 {
+        let mut next = self.advance(previous_end_time.unwrap_or(now));
+        while next <= now {
+            next = self.advance(next);
+        }
+        next
+    }
+
+    /// 解析周期规则文本
+    ///
+    /// ```text
+    /// weekly            每周，完成时创建下一张
+    /// daily 2 schedule  每 2 天，到期时创建下一张
+    /// 每月 完成后
+    /// ```
+    pub fn parse(text: &str) -> Result<RecurrenceRule> {
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut trigger = RecurrenceTrigger::default();
+
+        for token in text.split_whitespace() {
+            match token.to_lowercase().as_str() {
+                "daily" | "每天" | "每日" => frequency = Some(RecurrenceFrequency::Daily),
+                "weekly" | "每周" => frequency = Some(RecurrenceFrequency::Weekly),
+                "monthly" | "每月" => frequency = Some(RecurrenceFrequency::Monthly),
+                "complete" | "on-complete" | "完成后" => trigger = RecurrenceTrigger::OnComplete,
+                "schedule" | "on-schedule" | "到期" | "按时" => {
+                    trigger = RecurrenceTrigger::OnSchedule
+                }
+                other => match other.parse::<u32>() {
+                    Ok(n) if n > 0 => interval = n,
+                    _ => bail!("无法识别的周期设置 '{}'", token),
+                },
+            }
+        }
+
+        let Some(frequency) = frequency else {
+            bail!("请指定周期频率（daily / weekly / monthly）");
+        };
+        Ok(RecurrenceRule {
+            frequency,
+            interval,
+            trigger,
+        })
+    }
+
+    /// 转换为文本（[`RecurrenceRule::parse`] 的逆操作）
+    pub fn to_text(&self) -> String {
+        let frequency = match self.frequency {
+            RecurrenceFrequency::Daily => "daily",
+            RecurrenceFrequency::Weekly => "weekly",
+            RecurrenceFrequency::Monthly => "monthly",
+        };
+        let trigger = match self.trigger {
+            RecurrenceTrigger::OnComplete => "complete",
+            RecurrenceTrigger::OnSchedule => "schedule",
+        };
+        if self.interval > 1 {
+            format!("{} {} {}", frequency, self.interval, trigger)
+        } else {
+            format!("{} {}", frequency, trigger)
+        }
+    }
+
+    /// 显示文本，例如"每 2 周（完成后）"
+    pub fn describe(&self) -> String {
+        let unit = match self.frequency {
+            RecurrenceFrequency::Daily => "天",
+            RecurrenceFrequency::Weekly => "周",
+            RecurrenceFrequency::Monthly => "月",
+        };
+        let trigger = match self.trigger {
+            RecurrenceTrigger::OnComplete => "完成后",
+            RecurrenceTrigger::OnSchedule => "到期时",
+        };
+        if self.interval > 1 {
+            format!("每 {} {}（{}创建下一张）", self.interval, unit, trigger)
+        } else {
+            format!("每{}（{}创建下一张）", unit, trigger)
+        }
+    }
+}
+
+/// 卡片模板
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardTemplate {
+    /// 模板 ID
+    pub id: String,
+
+    /// 模板名称
+    pub name: String,
+
+    /// 新卡片的标题
+    pub title: String,
+
+    /// 新卡片的描述
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// 待办清单
+    #[serde(default)]
+    pub todos: Vec<String>,
+
+    /// 默认标签 ID
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// 截止时间为创建后的第几天
+    #[serde(default)]
+    pub due_in_days: Option<u32>,
+
+    /// 周期规则
+    #[serde(default)]
+    pub recurrence: Option<RecurrenceRule>,
+}
+
+impl CardTemplate {
+    /// 从现有卡片创建模板
+    pub fn from_card(name: String, card: &KanbanCard) -> Self {
+        let now = now_secs();
+        let random = uuid::Uuid::new_v4().to_string();
+        Self {
+            id: format!("template_{}_{}", now, &random[..8]),
+            name,
+            title: card.title.clone(),
+            description: card.description.clone(),
+            todos: card.todos.iter().map(|todo| todo.text.clone()).collect(),
+            tags: card.tags.clone(),
+            due_in_days: card
+                .end_time
+                .map(|end_time| (end_time.saturating_sub(card.created_at) / 86400) as u32)
+                .filter(|days| *days > 0),
+            recurrence: card.recurrence.clone(),
+        }
+    }
+
+    /// 用模板内容填充新建的卡片（标题、位置和状态沿用新卡片自身的值）
+    pub fn apply_to(&self, card: &mut KanbanCard) {
+        card.description = self.description.clone();
+        card.tags = self.tags.clone();
+        card.todos = self.todos.iter().cloned().map(TodoItem::new).collect();
+        card.recurrence = self.recurrence.clone();
+        card.end_time = self
+            .due_in_days
+            .map(|days| card.created_at + u64::from(days) * 86400);
+    }
+}
+
+/// 周期卡片的下一张：复制内容、重置待办，截止时间按周期顺延
+pub fn next_occurrence(card: &KanbanCard, rule: &RecurrenceRule) -> CardTemplate {
+    CardTemplate {
+        id: String::new(),
+        name: card.title.clone(),
+        title: card.title.clone(),
+        description: card.description.clone(),
+        todos: card.todos.iter().map(|todo| todo.text.clone()).collect(),
+        tags: card.tags.clone(),
+        due_in_days: None,
+        recurrence: Some(rule.clone()),
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 86400;
+
+    fn rule(frequency: RecurrenceFrequency, interval: u32) -> RecurrenceRule {
+        RecurrenceRule {
+            frequency,
+            interval,
+            trigger: RecurrenceTrigger::OnComplete,
+        }
+    }
+
+    fn local(year: i32, month: u32, day: u32) -> u64 {
+        use chrono::TimeZone;
+        chrono::Local
+            .with_ymd_and_hms(year, month, day, 9, 0, 0)
+            .earliest()
+            .expect("valid local time")
+            .timestamp() as u64
+    }
+
+    #[test]
+    fn advances_by_days_and_weeks() {
+        let from = local(2024, 1, 1);
+        assert_eq!(
+            rule(RecurrenceFrequency::Daily, 1).advance(from),
+            from + DAY
+        );
+        assert_eq!(
+            rule(RecurrenceFrequency::Daily, 3).advance(from),
+            from + 3 * DAY
+        );
+        assert_eq!(
+            rule(RecurrenceFrequency::Weekly, 2).advance(from),
+            from + 14 * DAY
+        );
+        // 间隔为 0 时按 1 处理
+        assert_eq!(
+            rule(RecurrenceFrequency::Weekly, 0).advance(from),
+            from + 7 * DAY
+        );
+    }
+
+    #[test]
+    fn advances_by_calendar_months() {
+        let monthly = rule(RecurrenceFrequency::Monthly, 1);
+        assert_eq!(monthly.advance(local(2024, 1, 15)), local(2024, 2, 15));
+        assert_eq!(monthly.advance(local(2024, 12, 15)), local(2025, 1, 15));
+        // 月末对齐到下个月的最后一天
+        assert_eq!(monthly.advance(local(2024, 1, 31)), local(2024, 2, 29));
+        assert_eq!(
+            rule(RecurrenceFrequency::Monthly, 3).advance(local(2024, 1, 15)),
+            local(2024, 4, 15)
+        );
+    }
+
+    #[test]
+    fn next_end_time_is_after_now() {
+        let weekly = rule(RecurrenceFrequency::Weekly, 1);
+        let previous = local(2024, 1, 1);
+
+        // 上一张还没到期：顺延一个周期
+        assert_eq!(
+            weekly.next_end_time(Some(previous), previous - DAY),
+            previous + 7 * DAY
+        );
+        // 上一张已过期多个周期：一直顺延到当前时间之后
+        let now = previous + 20 * DAY;
+        assert_eq!(
+            weekly.next_end_time(Some(previous), now),
+            previous + 21 * DAY
+        );
+        // 刚好等于当前时间时再顺延一个周期
+        assert_eq!(
+            weekly.next_end_time(Some(previous), previous + 7 * DAY),
+            previous + 14 * DAY
+        );
+        // 没有截止时间：从当前时间开始
+        assert_eq!(weekly.next_end_time(None, now), now + 7 * DAY);
+    }
+
+    #[test]
+    fn parses_rule_text() {
+        assert_eq!(
+            RecurrenceRule::parse("weekly").unwrap(),
+            rule(RecurrenceFrequency::Weekly, 1)
+        );
+        assert_eq!(
+            RecurrenceRule::parse("Daily 2 schedule").unwrap(),
+            RecurrenceRule {
+                frequency: RecurrenceFrequency::Daily,
+                interval: 2,
+                trigger: RecurrenceTrigger::OnSchedule,
+            }
+        );
+        assert_eq!(
+            RecurrenceRule::parse("每月 完成后").unwrap(),
+            rule(RecurrenceFrequency::Monthly, 1)
+        );
+
+        for text in ["", "2 schedule", "weekly 0", "weekly sometimes"] {
+            assert!(RecurrenceRule::parse(text).is_err(), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn rule_text_round_trips() {
+        for text in ["daily complete", "weekly 2 schedule", "monthly 3 complete"] {
+            assert_eq!(RecurrenceRule::parse(text).unwrap().to_text(), text);
+        }
+    }
+}
@@ -0,0 +1,406 @@
+//! 卡片估时与工时记录
+//!
+//! 卡片可以带有估时（[`Estimate`]，故事点或小时）。成员开始计时时在卡片上记下
+//! [`RunningTimer`]；停止计时（或手动补记）时把这段时间累加到卡片的 `logged_time`，
+//! 并在卡片 Room 中记录一条 `work_logged` 活动（`m.kanban.card.activity`），
+//! `metadata` 中有 `started_at` / `ended_at` / `duration_secs`。
+//!
+//! 列表标题下显示列表的估时和已记录工时（按成员汇总），
+//! 工时表由活动中的 [`WorkSession`] 生成（见 [`crate::kanban::analytics::write_timesheet`]）。
+
+use std::collections::BTreeMap;
+
+use anyhow::{Result, bail};
+use matrix_sdk::ruma::{OwnedRoomId, OwnedUserId, UserId};
+use serde::{Deserialize, Serialize};
+
+use super::kanban_state::{ActivityType, CardActivity, KanbanAppState, KanbanCard};
+
+/// 估时单位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EstimateUnit {
+    /// 故事点
+    Points,
+    /// 小时
+    Hours,
+}
+
+/// 卡片估时
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Estimate {
+    pub value: f64,
+    pub unit: EstimateUnit,
+}
+
+impl Estimate {
+    /// 解析估时文本；不带单位时按小时计算
+    ///
+    /// ```text
+    /// 3h / 2.5 小时 / 4    小时
+    /// 5p / 5pt / 5 点       故事点
+    /// ```
+    pub fn parse(text: &str) -> Result<Estimate> {
+        let text = text.trim().to_lowercase();
+        let number_end = text
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(text.len());
+        let (number, unit) = text.split_at(number_end);
+        let Ok(value) = number.parse::<f64>() else {
+            bail!("请输入估时，例如 3h 或 5p");
+        };
+        if !value.is_finite() || value <= 0.0 {
+            bail!("估时必须大于 0");
+        }
+        let unit = match unit.trim() {
+            "" | "h" | "hr" | "hrs" | "hour" | "hours" | "小时" | "时" => EstimateUnit::Hours,
+            "p" | "pt" | "pts" | "point" | "points" | "点" => EstimateUnit::Points,
+            other => bail!("无法识别的估时单位 '{}'", other),
+        };
+        Ok(Estimate { value, unit })
+    }
+
+    /// 转换为文本（[`Estimate::parse`] 的逆操作）
+    pub fn to_text(&self) -> String {
+        match self.unit {
+            EstimateUnit::Hours => format!("{}h", format_number(self.value)),
+            EstimateUnit::Points => format!("{}p", format_number(self.value)),
+        }
+    }
+
+    /// 显示文本，例如"3 小时"、"5 点"
+    pub fn describe(&self) -> String {
+        match self.unit {
+            EstimateUnit::Hours => format!("{} 小时", format_number(self.value)),
+            EstimateUnit::Points => format!("{} 点", format_number(self.value)),
+        }
+    }
+}
+
+/// 正在进行的计时
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunningTimer {
+    /// 计时的成员
+    pub user_id: OwnedUserId,
+
+    /// 开始时间（Unix timestamp 秒）
+    pub started_at: u64,
+}
+
+/// 一段工作时间（对应一条 `work_logged` 活动）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkSession {
+    pub card_id: OwnedRoomId,
+    pub user_id: OwnedUserId,
+
+    /// 开始时间（Unix timestamp 秒）
+    pub started_at: u64,
+
+    /// 结束时间（Unix timestamp 秒）
+    pub ended_at: u64,
+}
+
+impl WorkSession {
+    /// 时长（秒）
+    pub fn duration_secs(&self) -> u64 {
+        self.ended_at.saturating_sub(self.started_at)
+    }
+
+    /// 记录到活动中的 `metadata`
+    pub fn activity_metadata(&self) -> serde_json::Value {
+        serde_json::json!({
+            "started_at": self.started_at,
+            "ended_at": self.ended_at,
+            "duration_secs": self.duration_secs(),
+        })
+    }
+}
+
+/// 从卡片的活动记录中提取工作时间，按开始时间排序
+pub fn work_sessions(card_id: &OwnedRoomId, activities: &[CardActivity]) -> Vec<WorkSession> {
+    let mut sessions: Vec<WorkSession> = activities
+        .iter()
+        .filter(|activity| activity.activity_type == ActivityType::WorkLogged)
+        .filter_map(|activity| {
+            let metadata = activity.metadata.as_ref()?;
+            Some(WorkSession {
+                card_id: card_id.clone(),
+                user_id: UserId::parse(&activity.user_id).ok()?,
+                started_at: metadata.get("started_at")?.as_u64()?,
+                ended_at: metadata.get("ended_at")?.as_u64()?,
+            })
+        })
+        .collect();
+    sessions.sort_by_key(|session| session.started_at);
+    sessions
+}
+
+impl KanbanCard {
+    /// 所有成员已记录的工时（秒）
+    pub fn logged_secs(&self) -> u64 {
+        self.logged_time.values().sum()
+    }
+
+    /// 该成员正在进行的计时
+    pub fn timer_of(&self, user_id: &UserId) -> Option<&RunningTimer> {
+        self.timers.iter().find(|timer| timer.user_id == user_id)
+    }
+
+    /// 开始计时；已经在计时时返回 `false`
+    pub fn start_timer(&mut self, user_id: OwnedUserId, now: u64) -> bool {
+        if self.timer_of(&user_id).is_some() {
+            return false;
+        }
+        self.timers.push(RunningTimer {
+            user_id,
+            started_at: now,
+        });
+        true
+    }
+
+    /// 停止计时并记录这段时间；没有在计时时返回 `None`
+    pub fn stop_timer(&mut self, user_id: &UserId, now: u64) -> Option<WorkSession> {
+        let index = self
+            .timers
+            .iter()
+            .position(|timer| timer.user_id == user_id)?;
+        let timer = self.timers.remove(index);
+        Some(self.log_work(timer.user_id, timer.started_at, now))
+    }
+
+    /// 记录一段工作时间
+    pub fn log_work(
+        &mut self,
+        user_id: OwnedUserId,
+        started_at: u64,
+        ended_at: u64,
+    ) -> WorkSession {
+        let session = WorkSession {
+            card_id: self.id.clone(),
+            user_id,
+            started_at,
+            ended_at: ended_at.max(started_at),
+        };
+        *self.logged_time.entry(session.user_id.clone()).or_default() += session.duration_secs();
+        session
+    }
+}
+
+/// 一组卡片的估时和工时合计
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TimeTotals {
+    /// 以小时估时的合计
+    pub estimate_hours: f64,
+
+    /// 以故事点估时的合计
+    pub estimate_points: f64,
+
+    /// 已记录的工时（秒）
+    pub logged_secs: u64,
+
+    /// 每个成员已记录的工时（秒），按工时从多到少排序
+    pub by_user: Vec<(OwnedUserId, u64)>,
+}
+
+impl TimeTotals {
+    /// 汇总多张卡片
+    pub fn from_cards<'a>(cards: impl IntoIterator<Item = &'a KanbanCard>) -> Self {
+        let mut totals = Self::default();
+        let mut by_user: BTreeMap<OwnedUserId, u64> = BTreeMap::new();
+        for card in cards {
+            match &card.estimate {
+                Some(Estimate {
+                    value,
+                    unit: EstimateUnit::Hours,
+                }) => totals.estimate_hours += value,
+                Some(Estimate {
+                    value,
+                    unit: EstimateUnit::Points,
+                }) => totals.estimate_points += value,
+                None => {}
+            }
+            for (user_id, secs) in &card.logged_time {
+                *by_user.entry(user_id.clone()).or_default() += secs;
+            }
+        }
+        totals.logged_secs = by_user.values().sum();
+        totals.by_user = by_user.into_iter().collect();
+        totals
+            .by_user
+            .sort_by(|(a_user, a), (b_user, b)| b.cmp(a).then_with(|| a_user.cmp(b_user)));
+        totals
+    }
+
+    /// 没有任何估时和工时
+    pub fn is_empty(&self) -> bool {
+        self.estimate_hours == 0.0 && self.estimate_points == 0.0 && self.logged_secs == 0
+    }
+
+    /// 摘要文本，例如"⏱ 已记录 12h 30m · 估时 20 小时 / 8 点"
+    pub fn summary(&self) -> String {
+        let mut estimates = Vec::new();
+        if self.estimate_hours > 0.0 {
+            estimates.push(format!("{} 小时", format_number(self.estimate_hours)));
+        }
+        if self.estimate_points > 0.0 {
+            estimates.push(format!("{} 点", format_number(self.estimate_points)));
+        }
+        let mut text = format!("⏱ 已记录 {}", format_duration(self.logged_secs));
+        if !estimates.is_empty() {
+            text.push_str(&format!(" · 估时 {}", estimates.join(" / ")));
+        }
+        text
+    }
+}
+
+impl KanbanAppState {
+    /// 列表中未归档卡片的估时和工时合计
+    pub fn list_time_totals(&self, space_id: &OwnedRoomId) -> TimeTotals {
+        TimeTotals::from_cards(self.list_cards(space_id))
+    }
+}
+
+/// 解析手动补记的时长（秒）；不带单位时按小时计算
+///
+/// ```text
+/// 1.5h / 90m / 1h30m / 2 小时 / 45 分钟
+/// ```
+pub fn parse_duration(text: &str) -> Result<u64> {
+    let text = text.trim().to_lowercase();
+    if text.is_empty() {
+        bail!("请输入时长，例如 1.5h 或 30m");
+    }
+
+    let mut secs = 0.0;
+    let mut rest = text.as_str();
+    while !rest.is_empty() {
+        let number_end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let Ok(value) = rest[..number_end].parse::<f64>() else {
+            bail!("无法识别的时长 '{}'", text);
+        };
+        rest = rest[number_end..].trim_start();
+        let unit_end = rest
+            .find(|c: char| c.is_ascii_digit() || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let unit_secs = match &rest[..unit_end] {
+            "" | "h" | "hr" | "hrs" | "小时" | "时" => 3600.0,
+            "m" | "min" | "mins" | "分钟" | "分" => 60.0,
+            other => bail!("无法识别的时长单位 '{}'", other),
+        };
+        secs += value * unit_secs;
+        rest = rest[unit_end..].trim_start();
+    }
+
+    if !secs.is_finite() || secs < 60.0 {
+        bail!("时长至少为 1 分钟");
+    }
+    Ok(secs.round() as u64)
+}
+
+/// 时长显示文本，例如 `2h 05m`、`45m`
+pub fn format_duration(secs: u64) -> String {
+    let hours = secs / 3600;
+    let minutes = (secs % 3600) / 60;
+    if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// 计时器显示文本，例如 `01:02:03`
+pub fn format_clock(secs: u64) -> String {
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        (secs % 3600) / 60,
+        secs % 60
+    )
+}
+
+/// 去掉多余小数位，例如 `3`、`2.5`
+fn format_number(value: f64) -> String {
+    let text = format!("{:.2}", value);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn estimate(value: f64, unit: EstimateUnit) -> Estimate {
+        Estimate { value, unit }
+    }
+
+    #[test]
+    fn parses_estimates() {
+        let hours = [
+            ("3h", 3.0),
+            ("2.5 小时", 2.5),
+            ("4", 4.0),
+            (" 1.5 HOURS ", 1.5),
+        ];
+        for (text, value) in hours {
+            assert_eq!(
+                Estimate::parse(text).unwrap(),
+                estimate(value, EstimateUnit::Hours),
+                "{text:?}"
+            );
+        }
+        for text in ["5p", "5pt", "5 点", "5 Points"] {
+            assert_eq!(
+                Estimate::parse(text).unwrap(),
+                estimate(5.0, EstimateUnit::Points),
+                "{text:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_estimates() {
+        for text in ["", "h", "0", "0p", "-1", "3 days", "1.2.3h"] {
+            assert!(Estimate::parse(text).is_err(), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn estimate_text_round_trips() {
+        for text in ["3h", "2.5h", "5p", "0.25p"] {
+            assert_eq!(Estimate::parse(text).unwrap().to_text(), text);
+        }
+        assert_eq!(Estimate::parse("3 小时").unwrap().to_text(), "3h");
+    }
+
+    #[test]
+    fn parses_durations() {
+        let cases = [
+            ("1.5h", 5400),
+            ("90m", 5400),
+            ("1h30m", 5400),
+            ("1h 30m", 5400),
+            ("2", 7200),
+            ("2 小时", 7200),
+            ("45 分钟", 2700),
+            ("1 MIN", 60),
+        ];
+        for (text, secs) in cases {
+            assert_eq!(parse_duration(text).unwrap(), secs, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        for text in ["", "   ", "h", "abc", "30s", "0.5m", "0h", "1h xm"] {
+            assert!(parse_duration(text).is_err(), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(45 * 60), "45m");
+        assert_eq!(format_duration(2 * 3600 + 5 * 60), "2h 05m");
+        assert_eq!(format_clock(3723), "01:02:03");
+    }
+}
@@ -0,0 +1,464 @@
+//! 看板工作流定义
+//!
+//! 每个看板 Space 可以定义自己的状态流程（例如 Backlog → In Progress → Review → Done），
+//! 以 `m.space.workflow` 状态事件保存在 Space 上（与 `m.space.tag_library` 相同的方式）。
+//! 卡片元数据中的 `status` 保存的是状态 ID。
+//!
+//! 没有定义工作流的 Space 使用 [`Workflow::legacy`]，与旧版固定的
+//! 未完成 / 已完成 / 已归档三种状态保持兼容。
+
+use std::collections::HashMap;
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+use super::kanban_state::PREDEFINED_TAG_COLORS;
+
+/// 工作流状态事件类型
+pub const WORKFLOW_EVENT_TYPE: &str = "m.space.workflow";
+
+/// 旧版固定状态的 ID（同时也是默认工作流的状态 ID）
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_COMPLETED: &str = "completed";
+pub const STATUS_ARCHIVED: &str = "archived";
+
+/// 一个工作流最多允许的状态数量（卡片详情中的状态按钮数量）
+pub const MAX_WORKFLOW_STATUSES: usize = 8;
+
+/// 状态分类，用于判断卡片是否已完成 / 已归档
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusCategory {
+    /// 未开始
+    #[default]
+    Todo,
+
+    /// 进行中
+    InProgress,
+
+    /// 已完成
+    Done,
+
+    /// 已归档
+    Archived,
+}
+
+/// 工作流中的一个状态
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowStatus {
+    /// 状态 ID（写入卡片元数据）
+    pub id: String,
+
+    /// 显示名称
+    pub name: String,
+
+    /// 颜色（十六进制）
+    pub color: String,
+
+    /// 状态分类
+    #[serde(default)]
+    pub category: StatusCategory,
+
+    /// WIP 上限（同一 Space 中处于该状态的卡片数量上限）
+    #[serde(default)]
+    pub wip_limit: Option<usize>,
+}
+
+/// 看板工作流
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Workflow {
+    /// 状态列表（第一个为新卡片的初始状态）
+    pub statuses: Vec<WorkflowStatus>,
+
+    /// 允许的流转（状态 ID -> 可流转到的状态 ID）；没有条目的状态可以流转到任意状态
+    #[serde(default)]
+    pub transitions: HashMap<String, Vec<String>>,
+
+    /// 版本（保存时间，Unix timestamp 秒）
+    #[serde(default)]
+    pub version: u64,
+}
+
+impl Default for Workflow {
+    fn default() -> Self {
+        Self::legacy()
+    }
+}
+
+impl Workflow {
+    /// 旧版固定状态对应的默认工作流
+    pub fn legacy() -> Self {
+        Self {
+            statuses: vec![
+                WorkflowStatus {
+                    id: STATUS_PENDING.to_string(),
+                    name: "未完成".to_string(),
+                    color: "#FFA500".to_string(),
+                    category: StatusCategory::Todo,
+                    wip_limit: None,
+                },
+                WorkflowStatus {
+                    id: STATUS_COMPLETED.to_string(),
+                    name: "已完成".to_string(),
+                    color: "#61BD4F".to_string(),
+                    category: StatusCategory::Done,
+                    wip_limit: None,
+                },
+                WorkflowStatus {
+                    id: STATUS_ARCHIVED.to_string(),
+                    name: "已归档".to_string(),
+                    color: "#95A5A6".to_string(),
+                    category: StatusCategory::Archived,
+                    wip_limit: None,
+                },
+            ],
+            transitions: HashMap::new(),
+            version: 0,
+        }
+    }
+
+    /// 按 ID 查找状态
+    pub fn status(&self, id: &str) -> Option<&WorkflowStatus> {
+        self.statuses.iter().find(|s| s.id == id)
+    }
+
+    /// 新卡片的初始状态
+    pub fn initial_status(&self) -> &str {
+        self.statuses
+            .first()
+            .map_or(STATUS_PENDING, |s| s.id.as_str())
+    }
+
+    /// 状态的显示名称（未知状态显示 ID）
+    pub fn display_name<'a>(&'a self, id: &'a str) -> &'a str {
+        self.status(id).map_or(id, |s| s.name.as_str())
+    }
+
+    /// 状态的颜色（未知状态显示为灰色）
+    pub fn color(&self, id: &str) -> &str {
+        self.status(id).map_or("#95A5A6", |s| s.color.as_str())
+    }
+
+    /// 状态的分类
+    pub fn category(&self, id: &str) -> StatusCategory {
+        self.status(id).map_or(StatusCategory::Todo, |s| s.category)
+    }
+
+    /// 是否允许从 `from` 流转到 `to`
+    pub fn can_transition(&self, from: &str, to: &str) -> bool {
+        if from == to {
+            return true;
+        }
+        if self.status(to).is_none() {
+            return false;
+        }
+        // 当前状态已不在工作流中时允许流转到任意状态，避免卡片被"卡住"
+        match self.transitions.get(from) {
+            Some(targets) if self.status(from).is_some() => targets.iter().any(|t| t == to),
+            _ => true,
+        }
+    }
+
+    /// 从 `from` 出发可以流转到的状态
+    pub fn next_statuses(&self, from: &str) -> Vec<&WorkflowStatus> {
+        self.statuses
+            .iter()
+            .filter(|s| s.id != from && self.can_transition(from, &s.id))
+            .collect()
+    }
+
+    /// 当前数量为 `count` 时，再放入一张卡片是否会超过 WIP 上限
+    pub fn exceeds_wip_limit(&self, status: &str, count: usize) -> bool {
+        self.status(status)
+            .and_then(|s| s.wip_limit)
+            .is_some_and(|limit| count >= limit)
+    }
+
+    /// 把卡片当前的状态 ID 迁移到本工作流中
+    ///
+    /// - 已在工作流中的状态保持不变
+    /// - 旧版状态按分类映射（未完成 → 第一个未开始状态，已完成 → 第一个已完成状态，
+    ///   已归档 → 第一个已归档状态，没有则映射到已完成状态）
+    /// - 其他未知状态映射到初始状态
+    pub fn migrate_status(&self, status: &str) -> String {
+        if self.status(status).is_some() {
+            return status.to_string();
+        }
+
+        let first_in = |categories: &[StatusCategory]| {
+            categories.iter().find_map(|category| {
+                self.statuses
+                    .iter()
+                    .find(|s| s.category == *category)
+                    .map(|s| s.id.clone())
+            })
+        };
+
+        let mapped = match status {
+            STATUS_COMPLETED => first_in(&[StatusCategory::Done]),
+            STATUS_ARCHIVED => first_in(&[StatusCategory::Archived, StatusCategory::Done]),
+            _ => first_in(&[StatusCategory::Todo]),
+        };
+        mapped.unwrap_or_else(|| self.initial_status().to_string())
+    }
+
+    /// 解析文本形式的工作流定义
+    ///
+    /// 每行一个状态：`名称 [WIP上限] [done|archived|doing] -> 目标1, 目标2`
+    ///
+    /// ```text
+    /// Backlog -> In Progress
+    /// In Progress [3] [doing] -> Review, Backlog
+    /// Review [2] [doing] -> Done, In Progress
+    /// Done [done]
+    /// ```
+    ///
+    /// 名称与 `previous` 中已有状态相同时沿用原状态 ID 和颜色。
+    pub fn parse_definition(text: &str, previous: &Workflow) -> Result<Workflow> {
+        struct Line<'a> {
+            name: &'a str,
+            wip_limit: Option<usize>,
+            category: StatusCategory,
+            targets: Option<Vec<&'a str>>,
+        }
+
+        let mut lines = Vec::new();
+        for (line_no, raw) in text.lines().enumerate() {
+            let raw = raw.trim();
+            if raw.is_empty() {
+                continue;
+            }
+
+            let (head, targets) = match raw.split_once("->") {
+                Some((head, rest)) => (
+                    head,
+                    Some(
+                        rest.split(',')
+                            .map(str::trim)
+                            .filter(|t| !t.is_empty())
+                            .collect(),
+                    ),
+                ),
+                None => (raw, None),
+            };
+
+            let mut name = head.trim();
+            let mut wip_limit = None;
+            let mut category = StatusCategory::Todo;
+            while let Some(start) = name.rfind('[') {
+                let Some(end) = name[start..].find(']') else {
+                    bail!("第 {} 行: 缺少 ']'", line_no + 1);
+                };
+                if !name[start + end + 1..].trim().is_empty() {
+                    bail!(
+                        "第 {} 行: 标记 '{}' 之后不能有其他内容",
+                        line_no + 1,
+                        &name[start..]
+                    );
+                }
+                let token = name[start + 1..start + end].trim();
+                match token.to_lowercase().as_str() {
+                    "done" => category = StatusCategory::Done,
+                    "archived" => category = StatusCategory::Archived,
+                    "doing" => category = StatusCategory::InProgress,
+                    other => match other.parse::<usize>() {
+                        Ok(limit) if limit > 0 => wip_limit = Some(limit),
+                        _ => bail!("第 {} 行: 无法识别的标记 '[{}]'", line_no + 1, token),
+                    },
+                }
+                name = name[..start].trim_end();
+            }
+
+            if name.is_empty() {
+                bail!("第 {} 行: 状态名称不能为空", line_no + 1);
+            }
+            if lines.iter().any(|l: &Line| l.name == name) {
+                bail!("状态 '{}' 重复定义", name);
+            }
+            lines.push(Line {
+                name,
+                wip_limit,
+                category,
+                targets,
+            });
+        }
+
+        if lines.is_empty() {
+            bail!("工作流至少需要一个状态");
+        }
+        if lines.len() > MAX_WORKFLOW_STATUSES {
+            bail!("工作流最多支持 {} 个状态", MAX_WORKFLOW_STATUSES);
+        }
+
+        let statuses: Vec<WorkflowStatus> = lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| match previous.statuses.iter().find(|s| s.name == line.name) {
+                Some(existing) => WorkflowStatus {
+                    name: line.name.to_string(),
+                    category: line.category,
+                    wip_limit: line.wip_limit,
+                    ..existing.clone()
+                },
+                None => {
+                    let random = uuid::Uuid::new_v4().to_string();
+                    WorkflowStatus {
+                        id: format!("status_{}", &random[..8]),
+                        name: line.name.to_string(),
+                        color: PREDEFINED_TAG_COLORS[i % PREDEFINED_TAG_COLORS.len()]
+                            .1
+                            .to_string(),
+                        category: line.category,
+                        wip_limit: line.wip_limit,
+                    }
+                }
+            })
+            .collect();
+
+        let mut transitions = HashMap::new();
+        for (line, status) in lines.iter().zip(&statuses) {
+            let Some(targets) = &line.targets else {
+                continue;
+            };
+            let mut ids = Vec::with_capacity(targets.len());
+            for target in targets {
+                let Some(target_status) = statuses.iter().find(|s| s.name == *target) else {
+                    bail!("状态 '{}' 的流转目标 '{}' 不存在", line.name, target);
+                };
+                ids.push(target_status.id.clone());
+            }
+            transitions.insert(status.id.clone(), ids);
+        }
+
+        Ok(Workflow {
+            statuses,
+            transitions,
+            version: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+        })
+    }
+
+    /// 转换为文本形式的定义（[`Workflow::parse_definition`] 的逆操作）
+    pub fn to_definition(&self) -> String {
+        self.statuses
+            .iter()
+            .map(|status| {
+                let mut line = status.name.clone();
+                if let Some(limit) = status.wip_limit {
+                    line.push_str(&format!(" [{}]", limit));
+                }
+                match status.category {
+                    StatusCategory::Todo => {}
+                    StatusCategory::InProgress => line.push_str(" [doing]"),
+                    StatusCategory::Done => line.push_str(" [done]"),
+                    StatusCategory::Archived => line.push_str(" [archived]"),
+                }
+                if let Some(targets) = self.transitions.get(&status.id) {
+                    let names: Vec<&str> = targets
+                        .iter()
+                        .filter_map(|id| self.status(id).map(|s| s.name.as_str()))
+                        .collect();
+                    line.push_str(&format!(" -> {}", names.join(", ")));
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(workflow: &Workflow) -> Vec<&str> {
+        workflow.statuses.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    fn target_names<'a>(workflow: &'a Workflow, name: &str) -> Option<Vec<&'a str>> {
+        let status = workflow.statuses.iter().find(|s| s.name == name)?;
+        let targets = workflow.transitions.get(&status.id)?;
+        Some(targets.iter().map(|id| workflow.display_name(id)).collect())
+    }
+
+    #[test]
+    fn parses_names_markers_and_transitions() {
+        let text = "Backlog -> In Progress\n\
+                    In Progress [3] [doing] -> Review, Backlog\n\
+                    \n\
+                    Review [2] [doing] -> Done, In Progress\n\
+                    Done [done]\n\
+                    Shelved [ARCHIVED]";
+        let workflow = Workflow::parse_definition(text, &Workflow::legacy()).unwrap();
+
+        assert_eq!(
+            names(&workflow),
+            vec!["Backlog", "In Progress", "Review", "Done", "Shelved"]
+        );
+        let in_progress = &workflow.statuses[1];
+        assert_eq!(in_progress.wip_limit, Some(3));
+        assert_eq!(in_progress.category, StatusCategory::InProgress);
+        assert_eq!(workflow.statuses[0].category, StatusCategory::Todo);
+        assert_eq!(workflow.statuses[3].category, StatusCategory::Done);
+        assert_eq!(workflow.statuses[4].category, StatusCategory::Archived);
+
+        assert_eq!(
+            target_names(&workflow, "In Progress"),
+            Some(vec!["Review", "Backlog"])
+        );
+        assert_eq!(target_names(&workflow, "Done"), None);
+        assert!(workflow.can_transition(&workflow.statuses[3].id, &workflow.statuses[0].id));
+        assert!(!workflow.can_transition(&workflow.statuses[0].id, &workflow.statuses[2].id));
+    }
+
+    #[test]
+    fn reuses_ids_and_colors_of_existing_statuses() {
+        let previous = Workflow::legacy();
+        let workflow =
+            Workflow::parse_definition("未完成\n新状态\n已完成 [done]", &previous).unwrap();
+
+        assert_eq!(workflow.statuses[0].id, STATUS_PENDING);
+        assert_eq!(workflow.statuses[0].color, previous.statuses[0].color);
+        assert_eq!(workflow.statuses[2].id, STATUS_COMPLETED);
+        assert!(workflow.statuses[1].id.starts_with("status_"));
+    }
+
+    #[test]
+    fn definition_round_trips() {
+        let text = "Backlog -> In Progress\n\
+                    In Progress [3] [doing] -> Done, Backlog\n\
+                    Done [done]";
+        let workflow = Workflow::parse_definition(text, &Workflow::legacy()).unwrap();
+        assert_eq!(workflow.to_definition(), text);
+
+        let reparsed = Workflow::parse_definition(&workflow.to_definition(), &workflow).unwrap();
+        assert_eq!(reparsed.statuses, workflow.statuses);
+        assert_eq!(reparsed.transitions, workflow.transitions);
+    }
+
+    #[test]
+    fn rejects_invalid_definitions() {
+        let legacy = Workflow::legacy();
+        let invalid = [
+            ("", "至少需要一个状态"),
+            ("Doing [3", "缺少 ']'"),
+            ("Doing [soon]", "无法识别的标记"),
+            ("Doing [0]", "无法识别的标记"),
+            ("[done]", "名称不能为空"),
+            ("Doing\nDoing", "重复定义"),
+            ("Doing -> Missing", "不存在"),
+            ("Review [2] later", "之后不能有其他内容"),
+            ("Review [2] later [doing]", "之后不能有其他内容"),
+            ("A\nB\nC\nD\nE\nF\nG\nH\nI", "最多支持"),
+        ];
+        for (text, expected) in invalid {
+            let error = Workflow::parse_definition(text, &legacy).unwrap_err();
+            assert!(
+                error.to_string().contains(expected),
+                "{text:?}: unexpected error '{error}'"
+            );
+        }
+    }
+}
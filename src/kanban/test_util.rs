@@ -0,0 +1,13 @@
+//! 单元测试共用的辅助函数
+
+use matrix_sdk::ruma::{OwnedRoomId, OwnedUserId};
+
+/// `!{name}:example.org`
+pub(crate) fn room_id(name: &str) -> OwnedRoomId {
+    OwnedRoomId::try_from(format!("!{name}:example.org").as_str()).expect("valid room id")
+}
+
+/// `@{name}:example.org`
+pub(crate) fn user(name: &str) -> OwnedUserId {
+    OwnedUserId::try_from(format!("@{name}:example.org").as_str()).expect("valid user id")
+}
@@ -29,6 +29,7 @@ use crate::{
         room_screen::{populate_preview_of_timeline_item, MessageAction, RoomScreenProps},
         tombstone_footer::{SuccessorRoomDetails, TombstoneFooterWidgetExt},
     },
+    kanban::{chat_commands, KanbanActions},
     location::init_location_subscriber,
     shared::{
         avatar::AvatarWidgetRefExt,
@@ -323,8 +324,27 @@ impl RoomInputBar {
                 .is_some_and(|(_, m)| m.is_primary())
         {
             let entered_text = mentionable_text_input.text().trim().to_string();
-            if !entered_text.is_empty() {
-                let message = mentionable_text_input.create_message_with_mentions(&entered_text);
+            // `/card` and `/todo` create a kanban card instead of sending a message.
+            if let Some(command) = chat_commands::parse_command(&entered_text) {
+                match command {
+                    Ok(command) => {
+                        cx.action(KanbanActions::RunCardCommand {
+                            command,
+                            room_id: room_screen_props.room_name_id.room_id().clone(),
+                        });
+                        mentionable_text_input.set_text(cx, "");
+                        self.enable_send_message_button(cx, false);
+                    }
+                    Err(usage) => enqueue_popup_notification(PopupItem {
+                        message: usage.to_string(),
+                        kind: PopupKind::Warning,
+                        auto_dismissal_duration: Some(3000.0),
+                    }),
+                }
+            } else if !entered_text.is_empty() {
+                // `//card` and `//todo` send a message starting with `/card` or `/todo`.
+                let entered_text = chat_commands::unescape_command(&entered_text);
+                let message = mentionable_text_input.create_message_with_mentions(entered_text);
                 submit_async_request(MatrixRequest::SendMessage {
                     room_id: room_screen_props.room_name_id.room_id().clone(),
                     message,
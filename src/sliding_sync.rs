@@ -37,7 +37,7 @@ use matrix_sdk_ui::{
     sync_service::{self, SyncService},
     timeline::{
         EventTimelineItem, LatestEventValue, RoomExt, TimelineDetails, TimelineEventItemId,
-        TimelineItem,
+        TimelineItem, default_event_filter,
     },
 };
 use robius_open::Uri;
@@ -538,10 +538,29 @@ pub enum MatrixRequest {
     },
     /// Request to load all kanban lists (Matrix spaces with [kanban-list] topic).
     LoadKanbanLists,
-    /// Request to create a new kanban list (Matrix space).
-    CreateKanbanList { name: String },
+    /// Request to create a new kanban list (Matrix space), optionally inside a board.
+    CreateKanbanList {
+        name: String,
+        board_id: Option<OwnedRoomId>,
+    },
     /// Request to update a kanban list name (Matrix space name).
     UpdateKanbanListName { list_id: OwnedRoomId, name: String },
+    /// Request to create a new kanban board (a parent Matrix space of list spaces).
+    CreateKanbanBoard {
+        name: String,
+        background_color: String,
+    },
+    /// Request to save a board's background (the `m.kanban.board` state event).
+    SaveKanbanBoard {
+        board: crate::kanban::state::kanban_state::KanbanBoard,
+    },
+    /// Request to invite a user to a board, along with all of its lists and cards.
+    InviteToKanbanBoard {
+        board_id: OwnedRoomId,
+        user_id: OwnedUserId,
+    },
+    /// Request to accept a board invite, also joining its invited lists and cards.
+    JoinKanbanBoard { board_id: OwnedRoomId },
 
     /// Request to update a kanban card title (Matrix room name).
     UpdateKanbanCardTitle {
@@ -551,15 +570,18 @@ pub enum MatrixRequest {
     },
 
     /// Request to update a kanban card description.
+    ///
+    /// The card carries both the markdown description and its rendered HTML.
     UpdateKanbanCardDescription {
         card_id: OwnedRoomId,
-        description: Option<String>,
         card: crate::kanban::state::kanban_state::KanbanCard,
     },
-    /// Request to create a new kanban card (Matrix room in a space).
+    /// Request to create a new kanban card (Matrix room in a space),
+    /// optionally with an initial description (e.g., when created from a chat message).
     CreateKanbanCard {
         space_id: OwnedRoomId,
         title: String,
+        description: Option<String>,
     },
     /// Request to load a single card with all its data (metadata, todos, etc.)
     LoadCard {
@@ -591,6 +613,34 @@ pub enum MatrixRequest {
         limit: Option<usize>,
     },
 
+    /// Request to record a system activity (status change, todo completion, ...) on a card
+    RecordCardActivity {
+        card_id: OwnedRoomId,
+        activity_type: crate::kanban::state::kanban_state::ActivityType,
+        text: String,
+        metadata: Option<serde_json::Value>,
+    },
+
+    /// Request to load the activities of many cards at once (for board analytics)
+    LoadBoardActivities { card_ids: Vec<OwnedRoomId> },
+
+    /// Request to load every work session logged on the given cards between two dates
+    /// (inclusive), paginating each card's timeline back to the start date (for timesheets)
+    LoadWorkSessions {
+        card_ids: Vec<OwnedRoomId>,
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
+    },
+
+    /// Request to load the image / file attachments of a card
+    LoadCardAttachments { card_id: OwnedRoomId },
+
+    /// Request to upload a local file as an attachment of a card
+    UploadCardAttachment {
+        card_id: OwnedRoomId,
+        path: std::path::PathBuf,
+    },
+
     // ========== Space 标签库管理 Requests ==========
     /// Request to load Space tag library
     LoadSpaceTags { space_id: OwnedRoomId },
@@ -614,6 +664,85 @@ pub enum MatrixRequest {
         tag_id: String,
     },
 
+    /// Request to replace the whole Space tag library (used by history restore and undo)
+    SaveSpaceTags {
+        space_id: OwnedRoomId,
+        tags: Vec<crate::kanban::state::kanban_state::SpaceTag>,
+    },
+
+    // ========== 历史版本 Requests ==========
+    /// Request to load the prior versions of a card from its timeline
+    LoadCardHistory { card_id: OwnedRoomId },
+
+    /// Request to load the prior versions of a Space tag library from its timeline
+    LoadTagLibraryHistory { space_id: OwnedRoomId },
+
+    // ========== Space 工作流 Requests ==========
+    /// Request to load the workflow definition of a Space
+    LoadSpaceWorkflow { space_id: OwnedRoomId },
+
+    /// Request to save a Space workflow and migrate its cards' statuses
+    SaveSpaceWorkflow {
+        space_id: OwnedRoomId,
+        workflow: crate::kanban::state::workflow::Workflow,
+    },
+
+    // ========== 看板权限 Requests ==========
+    /// Request to load the current user's kanban permissions from a Space's power levels
+    LoadBoardPermissions { space_id: OwnedRoomId },
+
+    /// Request to save kanban permissions into a Space's power levels
+    SaveBoardPermissions {
+        space_id: OwnedRoomId,
+        permissions: crate::kanban::state::permissions::BoardPermissions,
+    },
+
+    // ========== 归档 Requests ==========
+    /// Request to archive (or restore, if `archived_at` is `None`) a kanban list
+    SetListArchived {
+        space_id: OwnedRoomId,
+        archived_at: Option<u64>,
+    },
+
+    /// Request to permanently delete a card: unlink it from its Space and leave its room
+    DeleteKanbanCard {
+        space_id: OwnedRoomId,
+        card_id: OwnedRoomId,
+    },
+
+    /// Request to permanently delete a list together with its cards
+    DeleteKanbanList {
+        space_id: OwnedRoomId,
+        card_ids: Vec<OwnedRoomId>,
+    },
+
+    // ========== 卡片模板与周期卡片 Requests ==========
+    /// Request to load the card templates of a Space
+    LoadSpaceTemplates { space_id: OwnedRoomId },
+
+    /// Request to save the card templates of a Space
+    SaveSpaceTemplates {
+        space_id: OwnedRoomId,
+        templates: Vec<crate::kanban::state::template::CardTemplate>,
+    },
+
+    /// Request to create a new card from a template
+    CreateCardFromTemplate {
+        space_id: OwnedRoomId,
+        template: crate::kanban::state::template::CardTemplate,
+    },
+
+    /// Request to create the next occurrence of a recurring card
+    SpawnRecurringCard {
+        card: crate::kanban::state::kanban_state::KanbanCard,
+    },
+
+    /// Request to promote a todo item into a child card in the parent card's list
+    CreateChildCard {
+        parent: crate::kanban::state::kanban_state::KanbanCard,
+        todo: crate::kanban::state::kanban_state::TodoItem,
+    },
+
     // ========== Phase 6: Drag and Drop Requests ==========
     /// Request to move a card to a different space
     MoveCard {
@@ -622,6 +751,28 @@ pub enum MatrixRequest {
         target_space_id: OwnedRoomId,
         card: crate::kanban::state::kanban_state::KanbanCard,
     },
+
+    // ========== Offline Outbox Requests ==========
+    /// Request to send pending kanban mutations from the persistent outbox
+    FlushKanbanOutbox,
+
+    // ========== Assignee Requests ==========
+    /// Request to load the joined and invited members of a card room
+    LoadCardMembers { card_id: OwnedRoomId },
+
+
+    // ========== Import / Export Requests ==========
+    /// Request to export the lists and cards of one board into an archive file
+    ExportKanbanBoard {
+        path: std::path::PathBuf,
+        /// Board name stored in the archive
+        name: Option<String>,
+        /// Lists of the exported board
+        list_ids: Vec<OwnedRoomId>,
+    },
+
+    /// Request to create a new board from an archive file (or a Trello JSON export)
+    ImportKanbanBoard { path: std::path::PathBuf },
 }
 
 /// Submits a request to the worker thread to be executed asynchronously.
@@ -1821,13 +1972,25 @@ async fn matrix_worker_task(
                     continue;
                 };
 
-                let _load_lists_task = Handle::current().spawn(async move {
-                    use crate::kanban::MatrixKanbanAdapter;
+                // 看板加载后开始检查卡片截止时间
+                crate::kanban::reminders::start_scheduler();
 
+                let _load_lists_task = Handle::current().spawn(async move {
                     log!("Loading kanban lists (Spaces) from Matrix...");
-                    let adapter = MatrixKanbanAdapter::new(client);
+                    let api = crate::kanban::KanbanApi::new(client);
+
+                    // 先加载看板，列表按所属看板显示
+                    match api.load_boards().await {
+                        Ok(boards) => {
+                            log!("Successfully loaded {} kanban boards", boards.len());
+                            Cx::post_action(KanbanActions::BoardsLoaded(boards));
+                        }
+                        Err(e) => {
+                            error!("Failed to load kanban boards: {e:?}");
+                        }
+                    }
 
-                    match adapter.get_all_kanban_spaces().await {
+                    match api.load_lists().await {
                         Ok(spaces) => {
                             log!("Successfully loaded {} kanban spaces", spaces.len());
 
@@ -1837,7 +2000,7 @@ async fn matrix_worker_task(
 
                                 // 加载 Space 标签库
                                 log!("Loading tags for space {}", space.id);
-                                match adapter.load_space_tags(&space.id).await {
+                                match api.load_tags(&space.id).await {
                                     Ok(tags) => {
                                         log!("Loaded {} tags for space {}", tags.len(), space.id);
                                         Cx::post_action(KanbanActions::SpaceTagsLoaded {
@@ -1850,33 +2013,9 @@ async fn matrix_worker_task(
                                     }
                                 }
 
-                                // 加载这个 Space 中的所有卡片
-                                log!(
-                                    "Loading {} cards for space {}",
-                                    space.card_ids.len(),
-                                    space.id
-                                );
-                                for card_id in &space.card_ids {
-                                    match adapter.load_card(card_id, space.id.clone()).await {
-                                        Ok(mut card) => {
-                                            // 自动迁移旧格式标签
-                                            if let Err(e) = adapter
-                                                .migrate_card_tags(&mut card, &space.id)
-                                                .await
-                                            {
-                                                error!(
-                                                    "Failed to migrate tags for card {}: {e:?}",
-                                                    card_id
-                                                );
-                                            }
-
-                                            log!("Loaded card: {} ({})", card.title, card.id);
-                                            Cx::post_action(KanbanActions::CardLoaded(card));
-                                        }
-                                        Err(e) => {
-                                            error!("Failed to load card {}: {e:?}", card_id);
-                                        }
-                                    }
+                                // 加载这个 Space 中的所有卡片（旧格式的元数据在解析时升级）
+                                for card in api.load_list_cards(space).await {
+                                    Cx::post_action(KanbanActions::CardLoaded(card));
                                 }
                             }
 
@@ -1901,15 +2040,13 @@ async fn matrix_worker_task(
                 };
 
                 let _load_card_task = Handle::current().spawn(async move {
-                    use crate::kanban::MatrixKanbanAdapter;
-
                     log!(
                         "🔄 Loading card {} from Matrix (including todos)...",
                         card_id
                     );
-                    let adapter = MatrixKanbanAdapter::new(client);
+                    let api = crate::kanban::KanbanApi::new(client);
 
-                    match adapter.load_card(&card_id, space_id).await {
+                    match api.load_card(&card_id, space_id).await {
                         Ok(card) => {
                             log!(
                                 "✅ Successfully loaded card: {} with {} todos",
@@ -1926,7 +2063,7 @@ async fn matrix_worker_task(
                 });
             }
 
-            MatrixRequest::CreateKanbanList { name } => {
+            MatrixRequest::CreateKanbanList { name, board_id } => {
                 let Some(client) = get_client() else {
                     error!("Cannot create kanban list: Matrix client not available");
                     Cx::post_action(KanbanActions::Error(
@@ -1936,32 +2073,22 @@ async fn matrix_worker_task(
                 };
 
                 let _create_list_task = Handle::current().spawn(async move {
-                    use crate::kanban::MatrixKanbanAdapter;
-
                     log!("Creating kanban list (Space): {}", name);
-                    let adapter = MatrixKanbanAdapter::new(client.clone());
-
-                    match adapter.create_space(&name).await {
-                        Ok(space_id) => {
-                            log!("Successfully created kanban list: {}", space_id);
-
-                            // 等待Matrix SDK同步新创建的Space
-                            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-
-                            // 只加载新创建的Space，不要重新加载所有Space
-                            // 创建一个新的KanbanList对象
-                            let new_list = crate::kanban::state::kanban_state::KanbanList {
-                                id: space_id.clone(),
-                                name: name.clone(),
-                                card_ids: Vec::new(), // 新创建的Space没有卡片
-                                position: 1000.0,
-                            };
+                    let api = crate::kanban::KanbanApi::new(client);
 
+                    match api.create_list(&name, board_id.as_deref()).await {
+                        Ok(new_list) => {
                             log!(
                                 "Sending ListLoaded for newly created space: {} ({})",
                                 name,
-                                space_id
+                                new_list.id
                             );
+                            if let Some(board_id) = board_id {
+                                Cx::post_action(KanbanActions::ListAddedToBoard {
+                                    board_id,
+                                    list_id: new_list.id.clone(),
+                                });
+                            }
                             Cx::post_action(KanbanActions::ListLoaded(new_list));
                             Cx::post_action(KanbanActions::Loading(false));
                         }
@@ -1977,52 +2104,79 @@ async fn matrix_worker_task(
                 });
             }
 
-            MatrixRequest::UpdateKanbanListName { list_id, name } => {
+            MatrixRequest::CreateKanbanBoard {
+                name,
+                background_color,
+            } => {
                 let Some(client) = get_client() else {
-                    error!("Cannot update kanban list name: Matrix client not available");
+                    error!("Cannot create kanban board: Matrix client not available");
                     Cx::post_action(KanbanActions::Error(
                         "Matrix client not available".to_string(),
                     ));
                     continue;
                 };
 
-                let _update_list_name_task = Handle::current().spawn(async move {
-                    use matrix_sdk::ruma::events::room::topic::RoomTopicEventContent;
+                let _create_board_task = Handle::current().spawn(async move {
+                    log!("Creating kanban board (parent Space): {}", name);
+                    let api = crate::kanban::KanbanApi::new(client);
 
-                    log!("Updating kanban list name: {} -> {}", list_id, name);
+                    match api.create_board(&name, &background_color).await {
+                        Ok(board) => {
+                            log!("✅ Created kanban board: {} ({})", board.name, board.id);
+                            Cx::post_action(KanbanActions::BoardCreated(board));
+                        }
+                        Err(e) => {
+                            error!("Failed to create kanban board: {e:?}");
+                            Cx::post_action(KanbanActions::Error(format!(
+                                "Failed to create board: {e}"
+                            )));
+                        }
+                    }
+                    Cx::post_action(KanbanActions::Loading(false));
+                    SignalToUI::set_ui_signal();
+                });
+            }
 
-                    // 获取 Space (Room)
-                    let Some(room) = client.get_room(&list_id) else {
-                        error!("Cannot find room for list_id: {}", list_id);
-                        Cx::post_action(KanbanActions::Error(format!(
-                            "List not found: {}",
-                            list_id
-                        )));
-                        return;
-                    };
+            MatrixRequest::SaveKanbanBoard { board } => {
+                let Some(client) = get_client() else {
+                    error!("Cannot save kanban board: Matrix client not available");
+                    continue;
+                };
 
-                    // 更新 Space 的 topic（包含 [kanban-list] 标记）
-                    // 因为加载时是从 topic 读取名称的，所以必须更新 topic
-                    let topic_with_marker = format!("[kanban-list] {}", name);
-                    let topic_content = RoomTopicEventContent::new(topic_with_marker.clone());
+                let _save_board_task = Handle::current().spawn(async move {
+                    let api = crate::kanban::KanbanApi::new(client);
+                    if let Err(e) = api.save_board(&board).await {
+                        error!("❌ Failed to save board {}: {e:?}", board.id);
+                        Cx::post_action(KanbanActions::Error(format!("Failed to save board: {e}")));
+                        SignalToUI::set_ui_signal();
+                    }
+                });
+            }
 
-                    match room.send_state_event(topic_content).await {
-                        Ok(_) => {
-                            log!(
-                                "✅ Successfully updated list topic on Matrix server: {} -> {}",
-                                list_id,
-                                topic_with_marker
-                            );
+            MatrixRequest::InviteToKanbanBoard { board_id, user_id } => {
+                let Some(client) = get_client() else {
+                    error!("Cannot invite to kanban board: Matrix client not available");
+                    continue;
+                };
 
-                            // 同时更新 room name 以保持一致性
-                            if let Err(e) = room.set_name(name.clone()).await {
-                                log!("⚠️ Warning: Failed to update room name: {e:?}");
-                            }
-                        }
+                let _invite_task = Handle::current().spawn(async move {
+                    log!("📨 Inviting {} to board {}", user_id, board_id);
+                    let api = crate::kanban::KanbanApi::new(client);
+
+                    match api.invite_to_board(&board_id, &user_id).await {
+                        Ok(rooms) => Cx::post_action(KanbanActions::BoardInviteSent {
+                            board_id,
+                            user_id,
+                            rooms,
+                        }),
                         Err(e) => {
-                            error!("Failed to update list topic: {e:?}");
+                            error!(
+                                "❌ Failed to invite {} to board {}: {e:?}",
+                                user_id, board_id
+                            );
                             Cx::post_action(KanbanActions::Error(format!(
-                                "Failed to update list name: {e}"
+                                "邀请 {} 失败: {e}",
+                                user_id
                             )));
                         }
                     }
@@ -2030,6 +2184,54 @@ async fn matrix_worker_task(
                 });
             }
 
+            MatrixRequest::JoinKanbanBoard { board_id } => {
+                let Some(client) = get_client() else {
+                    error!("Cannot join kanban board: Matrix client not available");
+                    continue;
+                };
+
+                let _join_task = Handle::current().spawn(async move {
+                    log!("🚪 Joining board {}", board_id);
+                    let api = crate::kanban::KanbanApi::new(client);
+
+                    match api.join_board(&board_id).await {
+                        Ok(rooms) => {
+                            log!("✅ Joined board {} ({} rooms)", board_id, rooms);
+                            Cx::post_action(KanbanActions::BoardJoined { board_id });
+                        }
+                        Err(e) => {
+                            error!("❌ Failed to join board {}: {e:?}", board_id);
+                            Cx::post_action(KanbanActions::Error(format!("加入看板失败: {e}")));
+                            Cx::post_action(KanbanActions::Loading(false));
+                        }
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            MatrixRequest::UpdateKanbanListName { list_id, name } => {
+                let Some(client) = get_client() else {
+                    error!("Cannot update kanban list name: Matrix client not available");
+                    Cx::post_action(KanbanActions::Error(
+                        "Matrix client not available".to_string(),
+                    ));
+                    continue;
+                };
+
+                let _update_list_name_task = Handle::current().spawn(async move {
+                    log!("Updating kanban list name: {} -> {}", list_id, name);
+
+                    let api = crate::kanban::KanbanApi::new(client);
+                    if let Err(e) = api.rename_list(&list_id, &name).await {
+                        error!("Failed to update list topic: {e:?}");
+                        Cx::post_action(KanbanActions::Error(format!(
+                            "Failed to update list name: {e}"
+                        )));
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
             MatrixRequest::UpdateKanbanCardTitle {
                 card_id,
                 title,
@@ -2075,11 +2277,7 @@ async fn matrix_worker_task(
                     }
 
                     // 同时更新元数据中的标题，确保重启后不会丢失
-                    if let Err(e) =
-                        crate::kanban::matrix_adapter::MatrixKanbanAdapter::new(client.clone())
-                            .save_card_metadata(&card)
-                            .await
-                    {
+                    if let Err(e) = crate::kanban::KanbanApi::new(client).save_card(&card).await {
                         error!("Failed to save card metadata after title update: {e:?}");
                     } else {
                         log!("✅ Successfully saved card metadata with new title");
@@ -2089,11 +2287,7 @@ async fn matrix_worker_task(
                 });
             }
 
-            MatrixRequest::UpdateKanbanCardDescription {
-                card_id,
-                description,
-                card,
-            } => {
+            MatrixRequest::UpdateKanbanCardDescription { card_id, card } => {
                 let Some(client) = get_client() else {
                     error!("Cannot update kanban card description: Matrix client not available");
                     Cx::post_action(KanbanActions::Error(
@@ -2104,17 +2298,14 @@ async fn matrix_worker_task(
 
                 let _update_card_description_task = Handle::current().spawn(async move {
                     log!(
-                        "Updating kanban card description: {} -> {:?}",
+                        "Updating kanban card description: {} -> {:?} (html: {})",
                         card_id,
-                        description
+                        card.description,
+                        card.description_html.is_some()
                     );
 
                     // 保存元数据（包括更新后的描述）
-                    if let Err(e) =
-                        crate::kanban::matrix_adapter::MatrixKanbanAdapter::new(client.clone())
-                            .save_card_metadata(&card)
-                            .await
-                    {
+                    if let Err(e) = crate::kanban::KanbanApi::new(client).save_card(&card).await {
                         error!("Failed to save card metadata after description update: {e:?}");
                         Cx::post_action(KanbanActions::Error(format!(
                             "Failed to update card description: {e}"
@@ -2127,7 +2318,11 @@ async fn matrix_worker_task(
                 });
             }
 
-            MatrixRequest::CreateKanbanCard { space_id, title } => {
+            MatrixRequest::CreateKanbanCard {
+                space_id,
+                title,
+                description,
+            } => {
                 log!(
                     "🚀🚀🚀 MatrixRequest::CreateKanbanCard received! space_id={}, title={}",
                     space_id,
@@ -2145,50 +2340,19 @@ async fn matrix_worker_task(
                 log!("🚀 Client available, spawning create_card_task...");
 
                 let _create_card_task = Handle::current().spawn(async move {
-                    use crate::kanban::MatrixKanbanAdapter;
-                    
                     log!("🚀 Task started: Creating kanban card: {} in space {}", title, space_id);
-                    let adapter = MatrixKanbanAdapter::new(client.clone());
-                    
-                    match adapter.create_card(&space_id, &title).await {
-                        Ok(card_id) => {
-                            log!("✅ Successfully created kanban card: {}", card_id);
-                            
-                            // Wait longer for Matrix SDK to sync the new card
-                            log!("⏳ Waiting for Matrix SDK to sync the new card...");
-                            tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-                            
-                            // Try to load the newly created card with retries
-                            let mut retries = 3;
-                            let mut card_loaded = false;
-                            
-                            while retries > 0 && !card_loaded {
-                                match adapter.load_card(&card_id, space_id.clone()).await {
-                                    Ok(card) => {
-                                        log!("✅ Loaded newly created card: {} (title: {})", card.id, card.title);
-                                        Cx::post_action(KanbanActions::CardLoaded(card));
-                                        card_loaded = true;
-                                    }
-                                    Err(e) => {
-                                        retries -= 1;
-                                        if retries > 0 {
-                                            error!("❌ Failed to load newly created card (retries left: {}): {e:?}", retries);
-                                            tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-                                        } else {
-                                            error!("❌ Failed to load newly created card after all retries: {e:?}");
-                                            // Create a temporary card object as fallback
-                                            log!("🔧 Creating temporary card object for {}", card_id);
-                                            let temp_card = crate::kanban::state::kanban_state::KanbanCard::new(
-                                                card_id.clone(),
-                                                title.clone(),
-                                                space_id.clone(),
-                                            );
-                                            Cx::post_action(KanbanActions::CardLoaded(temp_card));
-                                        }
-                                    }
+                    let api = crate::kanban::KanbanApi::new(client);
+
+                    match api.create_card(&space_id, &title).await {
+                        Ok(mut card) => {
+                            if let Some(description) = description {
+                                card.description = Some(description);
+                                card.touch();
+                                if let Err(e) = api.save_card(&card).await {
+                                    error!("❌ Failed to save description of new card: {e:?}");
                                 }
                             }
-                            
+                            Cx::post_action(KanbanActions::CardLoaded(card));
                             Cx::post_action(KanbanActions::Loading(false));
                         }
                         Err(e) => {
@@ -2217,17 +2381,15 @@ async fn matrix_worker_task(
                 };
 
                 let _save_todos_task = Handle::current().spawn(async move {
-                    use crate::kanban::MatrixKanbanAdapter;
-
                     log!(
                         "📝 Task started: Saving {} todos for card {}",
                         todos.len(),
                         card_id
                     );
-                    let adapter = MatrixKanbanAdapter::new(client.clone());
+                    let api = crate::kanban::KanbanApi::new(client);
 
                     // Save todos directly (no need to load first)
-                    match adapter.save_card_todos(&card_id, &todos).await {
+                    match api.save_todos(&card_id, &todos).await {
                         Ok(_) => {
                             log!("✅ Successfully saved todos for card {}", card_id);
                             // No need to post CardLoaded since memory is already updated
@@ -2256,11 +2418,9 @@ async fn matrix_worker_task(
                 };
 
                 let _save_metadata_task = Handle::current().spawn(async move {
-                    use crate::kanban::MatrixKanbanAdapter;
-
-                    let adapter = MatrixKanbanAdapter::new(client.clone());
+                    let api = crate::kanban::KanbanApi::new(client);
 
-                    match adapter.save_card_metadata(&card).await {
+                    match api.save_card(&card).await {
                         Ok(_) => {
                             log!("✅ SaveCardMetadata: Successfully saved card {}", card.id);
                         }
@@ -2289,22 +2449,21 @@ async fn matrix_worker_task(
                 };
 
                 let _add_comment_task = Handle::current().spawn(async move {
-                    use crate::kanban::MatrixKanbanAdapter;
                     use crate::kanban::state::kanban_state::ActivityType;
 
                     log!("💬 Task started: Adding comment to card {}", card_id);
-                    let adapter = MatrixKanbanAdapter::new(client.clone());
+                    let api = crate::kanban::KanbanApi::new(client);
 
                     // Send activity (comment)
-                    match adapter
-                        .send_activity(&card_id, ActivityType::Comment, text, None)
+                    match api
+                        .record_activity(&card_id, ActivityType::Comment, text, None)
                         .await
                     {
                         Ok(_) => {
                             log!("✅ Successfully added comment to card {}", card_id);
 
                             // Reload activities to update UI
-                            match adapter.load_activities(&card_id, Some(50)).await {
+                            match api.load_activities(&card_id, Some(50)).await {
                                 Ok(activities) => {
                                     log!(
                                         "✓ Loaded {} activities after adding comment",
@@ -2341,12 +2500,10 @@ async fn matrix_worker_task(
                 };
 
                 let _load_activities_task = Handle::current().spawn(async move {
-                    use crate::kanban::MatrixKanbanAdapter;
-
                     log!("📖 Task started: Loading activities for card {}", card_id);
-                    let adapter = MatrixKanbanAdapter::new(client.clone());
+                    let api = crate::kanban::KanbanApi::new(client);
 
-                    match adapter.load_activities(&card_id, limit).await {
+                    match api.load_activities(&card_id, limit).await {
                         Ok(activities) => {
                             log!(
                                 "✅ Successfully loaded {} activities for card {}",
@@ -2366,125 +2523,616 @@ async fn matrix_worker_task(
                 });
             }
 
-            // ========== Space 标签库管理 Request Handlers ==========
-            MatrixRequest::LoadSpaceTags { space_id } => {
-                log!(
-                    "📚 MatrixRequest::LoadSpaceTags received! space_id={}",
-                    space_id
-                );
-
+            MatrixRequest::LoadCardAttachments { card_id } => {
                 let Some(client) = get_client() else {
-                    error!("❌ Cannot load space tags: Matrix client not available");
+                    error!("Cannot load attachments: Matrix client not available");
                     continue;
                 };
 
-                let _load_tags_task = Handle::current().spawn(async move {
-                    use crate::kanban::MatrixKanbanAdapter;
-
-                    log!("📚 Task started: Loading tags for space {}", space_id);
-                    let adapter = MatrixKanbanAdapter::new(client.clone());
+                let _load_attachments_task = Handle::current().spawn(async move {
+                    let api = crate::kanban::KanbanApi::new(client);
 
-                    match adapter.load_space_tags(&space_id).await {
-                        Ok(tags) => {
-                            log!(
-                                "✅ Successfully loaded {} tags for space {}",
-                                tags.len(),
-                                space_id
-                            );
-                            Cx::post_action(KanbanActions::SpaceTagsLoaded { space_id, tags });
+                    match api.load_attachments(&card_id).await {
+                        Ok(attachments) => {
+                            Cx::post_action(KanbanActions::AttachmentsLoaded {
+                                card_id,
+                                attachments,
+                            });
                         }
                         Err(e) => {
-                            error!("❌ Failed to load space tags: {e:?}");
+                            error!("❌ Failed to load attachments of card {}: {e:?}", card_id);
                         }
                     }
                     SignalToUI::set_ui_signal();
                 });
             }
 
-            MatrixRequest::CreateSpaceTag {
-                space_id,
-                name,
-                color,
-            } => {
-                log!(
-                    "➕ MatrixRequest::CreateSpaceTag received! space_id={}, name={}, color={}",
-                    space_id,
-                    name,
-                    color
-                );
-
+            MatrixRequest::UploadCardAttachment { card_id, path } => {
                 let Some(client) = get_client() else {
-                    error!("❌ Cannot create space tag: Matrix client not available");
+                    error!("Cannot upload attachment: Matrix client not available");
                     continue;
                 };
 
-                let _create_tag_task = Handle::current().spawn(async move {
-                    use crate::kanban::MatrixKanbanAdapter;
-                    use crate::kanban::state::kanban_state::SpaceTag;
-
-                    log!(
-                        "➕ Task started: Creating tag '{}' in space {}",
-                        name,
-                        space_id
-                    );
-                    let adapter = MatrixKanbanAdapter::new(client.clone());
-
-                    let new_tag = SpaceTag::new(name, color);
+                let _upload_task = Handle::current().spawn(async move {
+                    log!("📎 Uploading {} to card {}", path.display(), card_id);
+                    let api = crate::kanban::KanbanApi::new(client);
 
-                    match adapter.add_space_tag(&space_id, new_tag).await {
-                        Ok(_) => {
-                            log!("✅ Successfully created tag in space {}", space_id);
-                            // Reload tags to update UI
-                            match adapter.load_space_tags(&space_id).await {
-                                Ok(tags) => {
-                                    Cx::post_action(KanbanActions::SpaceTagsLoaded {
-                                        space_id,
-                                        tags,
-                                    });
-                                }
-                                Err(e) => {
-                                    error!("⚠️ Failed to reload tags: {e:?}");
-                                }
-                            }
+                    match api.upload_attachment(&card_id, &path).await {
+                        Ok(attachment) => {
+                            Cx::post_action(KanbanActions::AttachmentUploaded {
+                                card_id,
+                                attachment,
+                            });
                         }
                         Err(e) => {
-                            error!("❌ Failed to create tag: {e:?}");
-                            Cx::post_action(KanbanActions::Error(format!(
-                                "Failed to create tag: {e}"
-                            )));
+                            error!("❌ Failed to upload {}: {e:?}", path.display());
+                            Cx::post_action(KanbanActions::AttachmentUploadFailed {
+                                card_id,
+                                error: e.to_string(),
+                            });
                         }
                     }
                     SignalToUI::set_ui_signal();
                 });
             }
 
-            MatrixRequest::UpdateSpaceTag { space_id, tag } => {
+            MatrixRequest::RecordCardActivity {
+                card_id,
+                activity_type,
+                text,
+                metadata,
+            } => {
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot record activity: Matrix client not available");
+                    continue;
+                };
+
+                let _record_activity_task = Handle::current().spawn(async move {
+                    let api = crate::kanban::KanbanApi::new(client);
+                    if let Err(e) = api
+                        .record_activity(&card_id, activity_type, text, metadata)
+                        .await
+                    {
+                        error!("❌ Failed to record activity on card {}: {e:?}", card_id);
+                    }
+                });
+            }
+
+            MatrixRequest::LoadBoardActivities { card_ids } => {
                 log!(
-                    "✏️ MatrixRequest::UpdateSpaceTag received! space_id={}, tag_id={}",
-                    space_id,
-                    tag.id
+                    "📊 MatrixRequest::LoadBoardActivities received! cards={}",
+                    card_ids.len()
                 );
 
                 let Some(client) = get_client() else {
-                    error!("❌ Cannot update space tag: Matrix client not available");
+                    error!("❌ Cannot load board activities: Matrix client not available");
+                    Cx::post_action(KanbanActions::AnalyticsFailed(
+                        "Matrix 客户端不可用".to_string(),
+                    ));
+                    SignalToUI::set_ui_signal();
                     continue;
                 };
 
-                let _update_tag_task = Handle::current().spawn(async move {
-                    use crate::kanban::MatrixKanbanAdapter;
+                let _load_board_activities_task = Handle::current().spawn(async move {
+                    use crate::kanban::analytics::ACTIVITY_LIMIT;
 
+                    let api = crate::kanban::KanbanApi::new(client);
+                    // 单张卡片加载失败不影响整体统计
+                    let activities = api.load_board_activities(&card_ids, ACTIVITY_LIMIT).await;
+                    log!("✅ Loaded activities of {} cards for analytics", activities.len());
+                    Cx::post_action(KanbanActions::BoardActivitiesLoaded { activities });
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            MatrixRequest::LoadWorkSessions { card_ids, from, to } => {
+                log!(
+                    "⏱️ MatrixRequest::LoadWorkSessions received! cards={}, {}..={}",
+                    card_ids.len(),
+                    from,
+                    to
+                );
+
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot load work sessions: Matrix client not available");
+                    Cx::post_action(KanbanActions::Error(
+                        "Matrix 客户端不可用，无法导出工时表".to_string(),
+                    ));
+                    SignalToUI::set_ui_signal();
+                    continue;
+                };
+
+                let _load_work_sessions_task = Handle::current().spawn(async move {
+                    use crate::kanban::state::calendar::day_start;
+
+                    let api = crate::kanban::KanbanApi::new(client);
+                    let sessions = api.load_work_sessions(&card_ids, day_start(from)).await;
+                    log!(
+                        "✅ Loaded {} work sessions for the timesheet",
+                        sessions.len()
+                    );
+                    Cx::post_action(KanbanActions::WorkSessionsLoaded { from, to, sessions });
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            // ========== Space 标签库管理 Request Handlers ==========
+            MatrixRequest::LoadSpaceTags { space_id } => {
+                log!(
+                    "📚 MatrixRequest::LoadSpaceTags received! space_id={}",
+                    space_id
+                );
+
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot load space tags: Matrix client not available");
+                    continue;
+                };
+
+                let _load_tags_task = Handle::current().spawn(async move {
+                    log!("📚 Task started: Loading tags for space {}", space_id);
+                    let api = crate::kanban::KanbanApi::new(client);
+
+                    match api.load_tags(&space_id).await {
+                        Ok(tags) => {
+                            log!(
+                                "✅ Successfully loaded {} tags for space {}",
+                                tags.len(),
+                                space_id
+                            );
+                            Cx::post_action(KanbanActions::SpaceTagsLoaded { space_id, tags });
+                        }
+                        Err(e) => {
+                            error!("❌ Failed to load space tags: {e:?}");
+                        }
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            // ========== Space 工作流 Request Handlers ==========
+            MatrixRequest::LoadSpaceWorkflow { space_id } => {
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot load workflow: Matrix client not available");
+                    continue;
+                };
+
+                let _load_workflow_task = Handle::current().spawn(async move {
+                    let api = crate::kanban::KanbanApi::new(client);
+                    match api.load_workflow(&space_id).await {
+                        Ok(workflow) => {
+                            Cx::post_action(KanbanActions::SpaceWorkflowLoaded {
+                                space_id,
+                                workflow,
+                            });
+                        }
+                        Err(e) => {
+                            error!("❌ Failed to load workflow for space {space_id}: {e:?}");
+                        }
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            MatrixRequest::LoadBoardPermissions { space_id } => {
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot load board permissions: Matrix client not available");
+                    continue;
+                };
+
+                let _load_permissions_task = Handle::current().spawn(async move {
+                    let api = crate::kanban::KanbanApi::new(client);
+                    match api.load_permissions(&space_id).await {
+                        Ok(permissions) => {
+                            Cx::post_action(KanbanActions::BoardPermissionsLoaded {
+                                space_id,
+                                permissions,
+                            });
+                        }
+                        Err(e) => {
+                            error!("❌ Failed to load board permissions for space {space_id}: {e:?}");
+                        }
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            MatrixRequest::SaveBoardPermissions {
+                space_id,
+                permissions,
+            } => {
+                log!(
+                    "🔐 MatrixRequest::SaveBoardPermissions received! space_id={}",
+                    space_id
+                );
+
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot save board permissions: Matrix client not available");
+                    continue;
+                };
+
+                let _save_permissions_task = Handle::current().spawn(async move {
+                    let api = crate::kanban::KanbanApi::new(client);
+                    match api.save_permissions(&space_id, &permissions).await {
+                        Ok(permissions) => {
+                            Cx::post_action(KanbanActions::BoardPermissionsLoaded {
+                                space_id,
+                                permissions,
+                            });
+                        }
+                        Err(e) => {
+                            error!("❌ Failed to save board permissions for space {space_id}: {e:?}");
+                            Cx::post_action(KanbanActions::BoardPermissionsFailed {
+                                space_id,
+                                error: format!("{e}"),
+                            });
+                        }
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            MatrixRequest::SaveSpaceWorkflow { space_id, workflow } => {
+                log!(
+                    "🔀 MatrixRequest::SaveSpaceWorkflow received! space_id={}, statuses={}",
+                    space_id,
+                    workflow.statuses.len()
+                );
+
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot save workflow: Matrix client not available");
+                    continue;
+                };
+
+                let _save_workflow_task = Handle::current().spawn(async move {
+                    let api = crate::kanban::KanbanApi::new(client);
+
+                    if let Err(e) = api.save_workflow(&space_id, &workflow).await {
+                        error!("❌ Failed to save workflow for space {space_id}: {e:?}");
+                        Cx::post_action(KanbanActions::WorkflowUpdateFailed {
+                            space_id,
+                            error: format!("{e}"),
+                        });
+                        SignalToUI::set_ui_signal();
+                        return;
+                    }
+
+                    // 迁移现有卡片的状态
+                    match api.migrate_card_statuses(&space_id, &workflow).await {
+                        Ok(cards) => {
+                            log!("✅ Migrated {} cards to the new workflow", cards.len());
+                            let migrated = cards.len();
+                            for card in cards {
+                                Cx::post_action(KanbanActions::CardLoaded(card));
+                            }
+                            Cx::post_action(KanbanActions::WorkflowSaved { space_id, migrated });
+                        }
+                        Err(e) => {
+                            error!("❌ Failed to migrate card statuses for {space_id}: {e:?}");
+                            Cx::post_action(KanbanActions::WorkflowUpdateFailed {
+                                space_id,
+                                error: format!("卡片状态迁移失败: {e}"),
+                            });
+                        }
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            // ========== 归档 Request Handlers ==========
+            MatrixRequest::SetListArchived {
+                space_id,
+                archived_at,
+            } => {
+                log!(
+                    "🗄️ MatrixRequest::SetListArchived received! space_id={}, archived_at={:?}",
+                    space_id,
+                    archived_at
+                );
+
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot archive list: Matrix client not available");
+                    continue;
+                };
+
+                let _archive_list_task = Handle::current().spawn(async move {
+                    let api = crate::kanban::KanbanApi::new(client);
+                    if let Err(e) = api.set_list_archived(&space_id, archived_at).await {
+                        error!("❌ Failed to archive list {space_id}: {e:?}");
+                        Cx::post_action(KanbanActions::ListArchiveFailed {
+                            list_id: space_id,
+                            error: format!("{e}"),
+                        });
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            MatrixRequest::DeleteKanbanCard { space_id, card_id } => {
+                log!(
+                    "🗑️ MatrixRequest::DeleteKanbanCard received! space_id={}, card_id={}",
+                    space_id,
+                    card_id
+                );
+
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot delete card: Matrix client not available");
+                    continue;
+                };
+
+                let _delete_card_task = Handle::current().spawn(async move {
+                    let api = crate::kanban::KanbanApi::new(client);
+                    if let Err(e) = api.delete_card(&space_id, &card_id).await {
+                        error!("❌ Failed to delete card {card_id}: {e:?}");
+                        Cx::post_action(KanbanActions::DeleteFailed {
+                            id: card_id,
+                            error: format!("{e}"),
+                        });
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            MatrixRequest::DeleteKanbanList { space_id, card_ids } => {
+                log!(
+                    "🗑️ MatrixRequest::DeleteKanbanList received! space_id={}, cards={}",
+                    space_id,
+                    card_ids.len()
+                );
+
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot delete list: Matrix client not available");
+                    continue;
+                };
+
+                let _delete_list_task = Handle::current().spawn(async move {
+                    let api = crate::kanban::KanbanApi::new(client);
+                    if let Err(e) = api.delete_list(&space_id, &card_ids).await {
+                        error!("❌ Failed to delete list {space_id}: {e:?}");
+                        Cx::post_action(KanbanActions::DeleteFailed {
+                            id: space_id,
+                            error: format!("{e}"),
+                        });
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            // ========== 卡片模板与周期卡片 Request Handlers ==========
+            MatrixRequest::LoadSpaceTemplates { space_id } => {
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot load card templates: Matrix client not available");
+                    continue;
+                };
+
+                let _load_templates_task = Handle::current().spawn(async move {
+                    let api = crate::kanban::KanbanApi::new(client);
+                    match api.load_templates(&space_id).await {
+                        Ok(templates) => {
+                            Cx::post_action(KanbanActions::SpaceTemplatesLoaded {
+                                space_id,
+                                templates,
+                            });
+                        }
+                        Err(e) => {
+                            error!("❌ Failed to load card templates for space {space_id}: {e:?}");
+                        }
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            MatrixRequest::SaveSpaceTemplates { space_id, templates } => {
+                log!(
+                    "📋 MatrixRequest::SaveSpaceTemplates received! space_id={}, templates={}",
+                    space_id,
+                    templates.len()
+                );
+
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot save card templates: Matrix client not available");
+                    continue;
+                };
+
+                let _save_templates_task = Handle::current().spawn(async move {
+                    let api = crate::kanban::KanbanApi::new(client);
+                    match api.save_templates(&space_id, &templates).await {
+                        Ok(()) => {
+                            Cx::post_action(KanbanActions::SpaceTemplatesLoaded {
+                                space_id,
+                                templates,
+                            });
+                        }
+                        Err(e) => {
+                            error!("❌ Failed to save card templates for space {space_id}: {e:?}");
+                            Cx::post_action(KanbanActions::TemplateUpdateFailed {
+                                space_id,
+                                error: format!("{e}"),
+                            });
+                        }
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            MatrixRequest::CreateCardFromTemplate { space_id, template } => {
+                log!(
+                    "📋 MatrixRequest::CreateCardFromTemplate received! space_id={}, template={}",
+                    space_id,
+                    template.name
+                );
+
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot create card from template: Matrix client not available");
+                    continue;
+                };
+
+                let _create_from_template_task = Handle::current().spawn(async move {
+                    let api = crate::kanban::KanbanApi::new(client);
+                    match api.create_card_from_template(&space_id, &template).await {
+                        Ok(card) => {
+                            log!("✅ Created card {} from template '{}'", card.id, template.name);
+                            Cx::post_action(KanbanActions::CardLoaded(card));
+                        }
+                        Err(e) => {
+                            error!("❌ Failed to create card from template: {e:?}");
+                            Cx::post_action(KanbanActions::TemplateUpdateFailed {
+                                space_id,
+                                error: format!("从模板创建卡片失败: {e}"),
+                            });
+                        }
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            MatrixRequest::SpawnRecurringCard { card } => {
+                log!(
+                    "🔁 MatrixRequest::SpawnRecurringCard received! card_id={}",
+                    card.id
+                );
+
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot spawn recurring card: Matrix client not available");
+                    continue;
+                };
+
+                let _spawn_recurring_task = Handle::current().spawn(async move {
+                    let api = crate::kanban::KanbanApi::new(client);
+                    match api.spawn_next_occurrence(&card).await {
+                        Ok((previous, Some(card))) => {
+                            Cx::post_action(KanbanActions::RecurringCardSpawned { previous, card });
+                        }
+                        Ok((previous, None)) => {
+                            // 已经生成过：只用服务器端的原卡片刷新本地状态
+                            Cx::post_action(KanbanActions::CardLoaded(previous));
+                        }
+                        Err(e) => {
+                            error!("❌ Failed to spawn next occurrence of {}: {e:?}", card.id);
+                            Cx::post_action(KanbanActions::RecurringCardFailed {
+                                card_id: card.id,
+                                error: format!("{e}"),
+                            });
+                        }
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            MatrixRequest::CreateChildCard { parent, todo } => {
+                log!(
+                    "🧩 MatrixRequest::CreateChildCard received! parent={}, todo={}",
+                    parent.id,
+                    todo.id
+                );
+
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot create child card: Matrix client not available");
+                    continue;
+                };
+
+                let _create_child_task = Handle::current().spawn(async move {
+                    let api = crate::kanban::KanbanApi::new(client);
+                    match api.create_child_card(&parent, &todo).await {
+                        Ok(card) => {
+                            Cx::post_action(KanbanActions::TodoConvertedToCard {
+                                parent_id: parent.id,
+                                todo_id: todo.id,
+                                card,
+                            });
+                        }
+                        Err(e) => {
+                            error!("❌ Failed to create child card for todo {}: {e:?}", todo.id);
+                            Cx::post_action(KanbanActions::TodoConversionFailed {
+                                card_id: parent.id,
+                                error: format!("{e}"),
+                            });
+                        }
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            MatrixRequest::CreateSpaceTag {
+                space_id,
+                name,
+                color,
+            } => {
+                log!(
+                    "➕ MatrixRequest::CreateSpaceTag received! space_id={}, name={}, color={}",
+                    space_id,
+                    name,
+                    color
+                );
+
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot create space tag: Matrix client not available");
+                    continue;
+                };
+
+                let _create_tag_task = Handle::current().spawn(async move {
+                    use crate::kanban::state::kanban_state::SpaceTag;
+
+                    log!(
+                        "➕ Task started: Creating tag '{}' in space {}",
+                        name,
+                        space_id
+                    );
+                    let api = crate::kanban::KanbanApi::new(client);
+
+                    let new_tag = SpaceTag::new(name, color);
+
+                    match api.add_tag(&space_id, new_tag).await {
+                        Ok(_) => {
+                            log!("✅ Successfully created tag in space {}", space_id);
+                            // Reload tags to update UI
+                            match api.load_tags(&space_id).await {
+                                Ok(tags) => {
+                                    Cx::post_action(KanbanActions::SpaceTagsLoaded {
+                                        space_id,
+                                        tags,
+                                    });
+                                }
+                                Err(e) => {
+                                    error!("⚠️ Failed to reload tags: {e:?}");
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            error!("❌ Failed to create tag: {e:?}");
+                            Cx::post_action(KanbanActions::Error(format!(
+                                "Failed to create tag: {e}"
+                            )));
+                        }
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            MatrixRequest::UpdateSpaceTag { space_id, tag } => {
+                log!(
+                    "✏️ MatrixRequest::UpdateSpaceTag received! space_id={}, tag_id={}",
+                    space_id,
+                    tag.id
+                );
+
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot update space tag: Matrix client not available");
+                    continue;
+                };
+
+                let _update_tag_task = Handle::current().spawn(async move {
                     log!(
                         "✏️ Task started: Updating tag '{}' in space {}",
                         tag.id,
                         space_id
                     );
-                    let adapter = MatrixKanbanAdapter::new(client.clone());
+                    let api = crate::kanban::KanbanApi::new(client);
 
-                    match adapter.update_space_tag(&space_id, tag).await {
+                    match api.update_tag(&space_id, tag).await {
                         Ok(_) => {
                             log!("✅ Successfully updated tag in space {}", space_id);
                             // Reload tags to update UI
-                            match adapter.load_space_tags(&space_id).await {
+                            match api.load_tags(&space_id).await {
                                 Ok(tags) => {
                                     Cx::post_action(KanbanActions::SpaceTagsLoaded {
                                         space_id,
@@ -2520,20 +3168,18 @@ async fn matrix_worker_task(
                 };
 
                 let _delete_tag_task = Handle::current().spawn(async move {
-                    use crate::kanban::MatrixKanbanAdapter;
-
                     log!(
                         "🗑️ Task started: Deleting tag '{}' from space {}",
                         tag_id,
                         space_id
                     );
-                    let adapter = MatrixKanbanAdapter::new(client.clone());
+                    let api = crate::kanban::KanbanApi::new(client);
 
-                    match adapter.delete_space_tag(&space_id, &tag_id).await {
+                    match api.delete_tag(&space_id, &tag_id).await {
                         Ok(_) => {
                             log!("✅ Successfully deleted tag from space {}", space_id);
                             // Reload tags to update UI
-                            match adapter.load_space_tags(&space_id).await {
+                            match api.load_tags(&space_id).await {
                                 Ok(tags) => {
                                     Cx::post_action(KanbanActions::SpaceTagsLoaded {
                                         space_id,
@@ -2556,6 +3202,90 @@ async fn matrix_worker_task(
                 });
             }
 
+            MatrixRequest::SaveSpaceTags { space_id, tags } => {
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot save space tags: Matrix client not available");
+                    continue;
+                };
+
+                let _save_tags_task = Handle::current().spawn(async move {
+                    log!("💾 Replacing tag library of space {}", space_id);
+                    let api = crate::kanban::KanbanApi::new(client);
+
+                    let result = match api.save_tags(&space_id, tags).await {
+                        Ok(()) => api.load_tags(&space_id).await,
+                        Err(e) => Err(e),
+                    };
+                    match result {
+                        Ok(tags) => {
+                            Cx::post_action(KanbanActions::SpaceTagsLoaded { space_id, tags });
+                        }
+                        Err(e) => {
+                            error!("❌ Failed to save tag library: {e:?}");
+                            Cx::post_action(KanbanActions::Error(format!(
+                                "Failed to save tag library: {e}"
+                            )));
+                        }
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            // ========== 历史版本 Request Handlers ==========
+            MatrixRequest::LoadCardHistory { card_id } => {
+                let Some(client) = get_client() else {
+                    error!("Cannot load card history: Matrix client not available");
+                    continue;
+                };
+
+                let _load_history_task = Handle::current().spawn(async move {
+                    let api = crate::kanban::KanbanApi::new(client);
+
+                    match api.load_card_history(&card_id).await {
+                        Ok(revisions) => {
+                            Cx::post_action(KanbanActions::CardHistoryLoaded {
+                                card_id,
+                                revisions,
+                            });
+                        }
+                        Err(e) => {
+                            error!("❌ Failed to load history of card {}: {e:?}", card_id);
+                            Cx::post_action(KanbanActions::Error(format!(
+                                "Failed to load card history: {e}"
+                            )));
+                        }
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            MatrixRequest::LoadTagLibraryHistory { space_id } => {
+                let Some(client) = get_client() else {
+                    error!("Cannot load tag library history: Matrix client not available");
+                    continue;
+                };
+
+                let _load_history_task = Handle::current().spawn(async move {
+                    let api = crate::kanban::KanbanApi::new(client);
+
+                    match api.load_tag_history(&space_id).await {
+                        Ok(revisions) => {
+                            Cx::post_action(KanbanActions::TagLibraryHistoryLoaded {
+                                space_id,
+                                revisions,
+                            });
+                        }
+                        Err(e) => {
+                            error!("❌ Failed to load tag history of {}: {e:?}", space_id);
+                            Cx::post_action(KanbanActions::Error(format!(
+                                "Failed to load tag library history: {e}"
+                            )));
+                        }
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
             // ========== Phase 6: Drag and Drop Request Handlers ==========
             MatrixRequest::MoveCard {
                 card_id,
@@ -2576,20 +3306,17 @@ async fn matrix_worker_task(
                 };
 
                 let _move_card_task = Handle::current().spawn(async move {
-                    use crate::kanban::MatrixKanbanAdapter;
-
                     log!(
                         "🚚 Task started: Moving card {} from space {} to space {}",
                         card_id,
                         source_space_id,
                         target_space_id
                     );
-                    let adapter = MatrixKanbanAdapter::new(client.clone());
+                    let api = crate::kanban::KanbanApi::new(client);
+                    let mut card = card;
+                    card.space_id = target_space_id.clone();
 
-                    match adapter
-                        .move_card(&card_id, &source_space_id, &target_space_id, &card)
-                        .await
-                    {
+                    match api.move_card(&card, &source_space_id).await {
                         Ok(_) => {
                             log!(
                                 "✅ Successfully moved card {} to space {}",
@@ -2598,7 +3325,7 @@ async fn matrix_worker_task(
                             );
 
                             // Reload the card to confirm the move
-                            match adapter.load_card(&card_id, target_space_id.clone()).await {
+                            match api.load_card(&card_id, target_space_id.clone()).await {
                                 Ok(updated_card) => {
                                     Cx::post_action(KanbanActions::CardLoaded(updated_card));
                                 }
@@ -2614,7 +3341,7 @@ async fn matrix_worker_task(
                             Cx::post_action(KanbanActions::MoveCardFailed {
                                 card_id,
                                 original_space_id: source_space_id,
-                                original_position: card.position,
+                                original_rank: card.rank.clone(),
                                 error: format!("{}", e),
                             });
                         }
@@ -2622,6 +3349,131 @@ async fn matrix_worker_task(
                     SignalToUI::set_ui_signal();
                 });
             }
+
+            // ========== Offline Outbox Request Handlers ==========
+            MatrixRequest::FlushKanbanOutbox => {
+                let Some(client) = get_client() else {
+                    log!("📮 FlushKanbanOutbox: Matrix client not available, keeping entries queued");
+                    continue;
+                };
+
+                crate::kanban::outbox::start_retry_loop();
+                let _flush_outbox_task = Handle::current().spawn(async move {
+                    crate::kanban::outbox::flush(client).await;
+                });
+            }
+
+            // ========== Import / Export Request Handlers ==========
+            MatrixRequest::ExportKanbanBoard {
+                path,
+                name,
+                list_ids,
+            } => {
+                log!(
+                    "📦 MatrixRequest::ExportKanbanBoard received! path={}, lists={}",
+                    path.display(),
+                    list_ids.len()
+                );
+
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot export board: Matrix client not available");
+                    continue;
+                };
+
+                let _export_board_task = Handle::current().spawn(async move {
+                    use crate::kanban::import_export;
+
+                    let api = crate::kanban::KanbanApi::new(client);
+
+                    let result = match api.export_board(name, &list_ids).await {
+                        Ok(archive) => import_export::write_archive_file(&archive, &path)
+                            .map(|_| (archive.lists.len(), archive.card_count())),
+                        Err(e) => Err(e),
+                    };
+
+                    match result {
+                        Ok((lists, cards)) => {
+                            log!("✅ Exported {} lists / {} cards", lists, cards);
+                            Cx::post_action(KanbanActions::BoardExported { path, lists, cards });
+                        }
+                        Err(e) => {
+                            error!("❌ Failed to export board: {e:?}");
+                            Cx::post_action(KanbanActions::ImportExportFailed(format!(
+                                "导出看板失败: {e}"
+                            )));
+                        }
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            MatrixRequest::LoadCardMembers { card_id } => {
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot load card members: Matrix client not available");
+                    continue;
+                };
+
+                let _load_members_task = Handle::current().spawn(async move {
+                    let api = crate::kanban::KanbanApi::new(client);
+                    match api.load_members(&card_id).await {
+                        Ok(members) => {
+                            log!("👥 Loaded {} members for card {}", members.len(), card_id);
+                            Cx::post_action(KanbanActions::CardMembersLoaded { card_id, members });
+                        }
+                        Err(e) => {
+                            error!("❌ Failed to load members for card {card_id}: {e:?}");
+                        }
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
+
+            MatrixRequest::ImportKanbanBoard { path } => {
+                log!(
+                    "📥 MatrixRequest::ImportKanbanBoard received! path={}",
+                    path.display()
+                );
+
+                let Some(client) = get_client() else {
+                    error!("❌ Cannot import board: Matrix client not available");
+                    continue;
+                };
+
+                let _import_board_task = Handle::current().spawn(async move {
+                    use crate::kanban::import_export;
+
+                    let archive = match import_export::read_archive_file(&path) {
+                        Ok(archive) => archive,
+                        Err(e) => {
+                            error!("❌ Failed to read archive {}: {e:?}", path.display());
+                            Cx::post_action(KanbanActions::ImportExportFailed(format!(
+                                "读取归档失败: {e}"
+                            )));
+                            SignalToUI::set_ui_signal();
+                            return;
+                        }
+                    };
+
+                    let api = crate::kanban::KanbanApi::new(client);
+
+                    match api.import_board(&archive).await {
+                        Ok(summary) => {
+                            Cx::post_action(KanbanActions::BoardImported {
+                                lists: summary.lists,
+                                cards: summary.cards,
+                                failures: summary.failures,
+                            });
+                        }
+                        Err(e) => {
+                            error!("❌ Failed to import board: {e:?}");
+                            Cx::post_action(KanbanActions::ImportExportFailed(format!(
+                                "导入看板失败: {e}"
+                            )));
+                        }
+                    }
+                    SignalToUI::set_ui_signal();
+                });
+            }
         }
     }
 
@@ -3729,6 +4581,11 @@ async fn add_new_room(
             .room
             .timeline_builder()
             .track_read_marker_and_receipts()
+            // Kanban card rooms store their metadata as timeline messages, which should never be shown.
+            .event_filter(|event, rules| {
+                !crate::kanban::matrix_adapter::is_card_metadata_event(event)
+                    && default_event_filter(event, rules)
+            })
             .build()
             .await
             .map_err(|e| {